@@ -1,5 +1,5 @@
 use std::io;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use serde::{Deserialize, Serialize};
 use sled::{Db, Tree};
@@ -20,6 +20,13 @@ struct SpineMeta {
     next_seq: u64,
 }
 
+/// Receives each event after it is written to the spine.
+///
+/// Observers run on the writer's thread and must not block.
+pub trait EventObserver: Send + Sync {
+    fn on_event(&self, event: &EventRecord);
+}
+
 #[derive(Clone)]
 pub struct EventStore {
     db: Db,
@@ -28,6 +35,7 @@ pub struct EventStore {
     session_event_index: Tree,
     spine_meta: Tree,
     spine_record_index: Tree,
+    observers: Arc<RwLock<Vec<Arc<dyn EventObserver>>>>,
 }
 
 impl EventStore {
@@ -48,9 +56,17 @@ impl EventStore {
             session_event_index,
             spine_meta,
             spine_record_index,
+            observers: Arc::new(RwLock::new(Vec::new())),
         })
     }
 
+    /// Register an observer for events written after this call.
+    pub fn add_observer(&self, observer: Arc<dyn EventObserver>) {
+        if let Ok(mut observers) = self.observers.write() {
+            observers.push(observer);
+        }
+    }
+
     pub fn shared(db: Db) -> Result<Arc<Self>, StorageError> {
         Ok(Arc::new(Self::new(db)?))
     }
@@ -113,6 +129,11 @@ impl EventStore {
                 .insert(record_id.as_bytes(), &encode_seq(event.seq))
                 .map_err(to_storage_io)?;
         }
+        if let Ok(observers) = self.observers.read() {
+            for observer in observers.iter() {
+                observer.on_event(event);
+            }
+        }
         Ok(())
     }
 
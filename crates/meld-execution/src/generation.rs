@@ -29,6 +29,10 @@ pub struct CompletionOptions {
     pub stop: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub additional_json: BTreeMap<String, Value>,
+    /// Token limit for one retry when a completion is cut off by `max_tokens`.
+    /// Not sent to the provider; when unset, truncated completions are stored and flagged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncation_retry_max_tokens: Option<u32>,
 }
 
 impl Default for CompletionOptions {
@@ -41,6 +45,7 @@ impl Default for CompletionOptions {
             presence_penalty: None,
             stop: None,
             additional_json: BTreeMap::new(),
+            truncation_retry_max_tokens: None,
         }
     }
 }
@@ -60,6 +65,16 @@ pub struct CompletionResponse {
     pub finish_reason: Option<String>,
 }
 
+/// Normalized finish reason for a completion cut off by the token limit.
+pub const FINISH_REASON_LENGTH: &str = "length";
+
+impl CompletionResponse {
+    /// True when the provider stopped because it hit the token limit.
+    pub fn is_truncated(&self) -> bool {
+        self.finish_reason.as_deref() == Some(FINISH_REASON_LENGTH)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationOrchestrationRequest {
     pub request_id: u64,
@@ -69,6 +84,62 @@ pub struct GenerationOrchestrationRequest {
     pub frame_type: String,
     pub retry_count: usize,
     pub force: bool,
+    /// Verbatim user prompt used instead of the agent's user prompt template.
+    #[serde(default)]
+    pub user_prompt_override: Option<String>,
+    /// Stream the provider response, echoing text to stderr as it arrives.
+    #[serde(default)]
+    pub stream_output: bool,
+    /// Record the source file's modification time in the frame's `source_mtime` metadata.
+    #[serde(default)]
+    pub preserve_mtime: bool,
+    /// Record generation duration, retry count, and provider name in frame metadata.
+    #[serde(default)]
+    pub record_provenance: bool,
+    /// Custom `{key}` placeholders substituted after the built-in ones.
+    #[serde(default)]
+    pub prompt_vars: BTreeMap<String, String>,
+    /// What to do when the head moved away from `expected_head` before the frame is written.
+    #[serde(default)]
+    pub on_conflict: HeadConflictPolicy,
+    /// Head frame for `(node_id, frame_type)` when the plan was built; `None` when there was none.
+    /// Ignored under [`HeadConflictPolicy::LastWins`].
+    #[serde(default)]
+    pub expected_head: Option<NodeId>,
+}
+
+/// How a generated frame is written when another writer replaced the head since the plan
+/// was built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeadConflictPolicy {
+    /// Overwrite the head unconditionally; the concurrent frame is no longer a head.
+    #[default]
+    LastWins,
+    /// Leave the head alone and fail the request.
+    Fail,
+    /// Make the new frame the head and link the displaced head as its basis frame.
+    KeepBoth,
+}
+
+impl HeadConflictPolicy {
+    /// Parse an `--on-conflict` value: `last-wins`, `fail`, or `keep-both`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "last-wins" => Some(HeadConflictPolicy::LastWins),
+            "fail" => Some(HeadConflictPolicy::Fail),
+            "keep-both" => Some(HeadConflictPolicy::KeepBoth),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HeadConflictPolicy::LastWins => "last-wins",
+            HeadConflictPolicy::Fail => "fail",
+            HeadConflictPolicy::KeepBoth => "keep-both",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
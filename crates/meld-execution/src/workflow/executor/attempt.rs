@@ -88,6 +88,13 @@ where
             frame_type: request.frame_type.clone(),
             retry_count: attempt.saturating_sub(1),
             force: request.force,
+            user_prompt_override: None,
+            stream_output: false,
+            preserve_mtime: false,
+            record_provenance: false,
+            prompt_vars: Default::default(),
+            on_conflict: Default::default(),
+            expected_head: None,
         };
         let provider_preparation = api.prepare_provider_for_request(&orchestration_request)?;
 
@@ -6,6 +6,7 @@
 
 pub mod commands;
 pub mod context_access;
+pub mod effective;
 pub mod identity;
 pub mod profile;
 pub mod prompt;
@@ -15,13 +16,18 @@ pub mod summary;
 pub mod tooling;
 
 pub use commands::{
-    AgentCommandService, AgentCreateResult, AgentEditResult, AgentListItem, AgentListResult,
-    AgentRemoveResult, AgentShowResult, AgentStatusEntryResult, AgentValidateAllResult,
-    AgentValidateSingleResult,
+    AgentCommandService, AgentCreateResult, AgentEditResult, AgentListDetails, AgentListItem,
+    AgentListResult, AgentRemoveResult, AgentShowResult, AgentStatusEntryResult,
+    AgentValidateAllResult, AgentValidateSingleResult,
 };
 pub use context_access::{AgentAdapter, ContextApiAdapter};
+pub use effective::{
+    AgentConfigLayer, AgentConfigSource, EffectiveAgentConfig, EffectiveSetting, ShadowedValue,
+};
 pub use identity::{AgentIdentity, AgentRole, Capability, ValidationResult};
 pub use profile::AgentConfig;
 pub use prompt::{resolve_prompt_path, PromptCache};
 pub use registry::AgentRegistry;
-pub use storage::{AgentStorage, StoredAgentConfig, XdgAgentStorage};
+pub use storage::{
+    AgentStorage, InvalidAgentConfig, StoredAgentConfig, StoredAgentConfigs, XdgAgentStorage,
+};
@@ -2,11 +2,12 @@
 //!
 //! Owns all agent workflow logic; CLI parses, calls one method per variant, and formats output.
 
-use crate::agent::identity::{AgentRole, ValidationResult};
+use crate::agent::identity::{AgentIdentity, AgentRole, ValidationResult};
 use crate::agent::profile::AgentConfig;
 use crate::agent::prompt::resolve_prompt_path;
 use crate::agent::registry::AgentRegistry;
 use crate::error::ApiError;
+use crate::provider::ProviderRegistry;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -22,6 +23,17 @@ pub struct AgentListResult {
 pub struct AgentListItem {
     pub agent_id: String,
     pub role: AgentRole,
+    /// Set by `agent list --detailed`.
+    pub details: Option<AgentListDetails>,
+}
+
+/// Extra `agent list --detailed` fields. Prompt files are checked for existence, never read.
+#[derive(Debug, Clone)]
+pub struct AgentListDetails {
+    pub prompt_path: Option<String>,
+    /// `None` when the agent has no prompt path.
+    pub prompt_exists: Option<bool>,
+    pub default_provider: Option<String>,
 }
 
 /// Result of agent show command.
@@ -43,6 +55,14 @@ pub struct AgentValidateSingleResult {
 #[derive(Debug, Clone)]
 pub struct AgentValidateAllResult {
     pub results: Vec<(String, ValidationResult)>,
+    /// Agents left unvalidated because `fail_fast` stopped at an invalid agent.
+    pub skipped: usize,
+}
+
+impl AgentValidateAllResult {
+    pub fn invalid_count(&self) -> usize {
+        self.results.iter().filter(|(_, r)| !r.is_valid()).count()
+    }
 }
 
 /// Result of agent status command (one entry per agent).
@@ -125,20 +145,37 @@ impl AgentCommandService {
         }
     }
 
-    /// List agents, optionally filtered by role.
+    /// List agents, optionally filtered by role. `detailed` adds prompt path, prompt file
+    /// existence, and default provider per agent.
     pub fn list(
         registry: &AgentRegistry,
         role_filter: Option<&str>,
+        detailed: bool,
     ) -> Result<AgentListResult, ApiError> {
         let role = role_filter.map(Self::parse_role).transpose()?;
         let agents = registry.list_by_role(role);
-        let items = agents
-            .iter()
-            .map(|a| AgentListItem {
-                agent_id: a.agent_id.clone(),
-                role: a.role,
-            })
-            .collect();
+        let mut items = Vec::with_capacity(agents.len());
+        for agent in agents {
+            let details = if detailed {
+                let shown = Self::show(registry, &agent.agent_id, false)?;
+                let prompt_exists = shown
+                    .prompt_path
+                    .as_ref()
+                    .map(|path| std::path::Path::new(path).is_file());
+                Some(AgentListDetails {
+                    prompt_path: shown.prompt_path,
+                    prompt_exists,
+                    default_provider: agent.default_provider().map(str::to_string),
+                })
+            } else {
+                None
+            };
+            items.push(AgentListItem {
+                agent_id: agent.agent_id.clone(),
+                role: agent.role,
+                details,
+            });
+        }
         Ok(AgentListResult { agents: items })
     }
 
@@ -187,27 +224,59 @@ impl AgentCommandService {
     /// Validate a single agent.
     pub fn validate_single(
         registry: &AgentRegistry,
+        providers: &ProviderRegistry,
         agent_id: &str,
     ) -> Result<AgentValidateSingleResult, ApiError> {
-        let result = registry.validate_agent(agent_id)?;
+        let mut result = registry.validate_agent(agent_id)?;
+        if let Some(agent) = registry.get(agent_id) {
+            Self::warn_unknown_default_provider(agent, providers, &mut result);
+        }
         Ok(AgentValidateSingleResult { result })
     }
 
-    /// Validate all agents.
-    pub fn validate_all(registry: &AgentRegistry) -> Result<AgentValidateAllResult, ApiError> {
-        let agents = registry.list_all();
+    /// Validate all agents in agent ID order. With `fail_fast`, stop after the first invalid agent.
+    pub fn validate_all(
+        registry: &AgentRegistry,
+        providers: &ProviderRegistry,
+        fail_fast: bool,
+    ) -> Result<AgentValidateAllResult, ApiError> {
+        let mut agents = registry.list_all();
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        let total = agents.len();
         let mut results = Vec::new();
         for agent in agents {
-            let validation = registry
+            let mut validation = registry
                 .validate_agent(&agent.agent_id)
                 .unwrap_or_else(|e| {
                     let mut r = ValidationResult::new(agent.agent_id.clone());
                     r.add_error(format!("Failed to validate: {}", e));
                     r
                 });
+            Self::warn_unknown_default_provider(agent, providers, &mut validation);
+            let valid = validation.is_valid();
             results.push((agent.agent_id.clone(), validation));
+            if fail_fast && !valid {
+                break;
+            }
+        }
+        let skipped = total - results.len();
+        Ok(AgentValidateAllResult { results, skipped })
+    }
+
+    /// A missing default provider is a warning: providers may be created after the agent.
+    fn warn_unknown_default_provider(
+        agent: &AgentIdentity,
+        providers: &ProviderRegistry,
+        result: &mut ValidationResult,
+    ) {
+        if let Some(provider_name) = agent.default_provider() {
+            if providers.get(provider_name).is_none() {
+                result.add_warning(format!(
+                    "default_provider '{}' is not configured. Use `meld provider create` to add it.",
+                    provider_name
+                ));
+            }
         }
-        Ok(AgentValidateAllResult { results })
     }
 
     /// Status: list all agents with validation and prompt file status.
@@ -265,6 +334,9 @@ impl AgentCommandService {
             system_prompt: None,
             system_prompt_path: normalized_prompt_path.clone(),
             workflow_id: None,
+            default_provider: None,
+            frame_type_per_node_type: false,
+            capabilities: Vec::new(),
             metadata: Default::default(),
         };
         if role != AgentRole::Reader {
@@ -368,8 +440,12 @@ impl AgentCommandService {
         registry: &mut AgentRegistry,
         agent_id: &str,
     ) -> Result<AgentRemoveResult, ApiError> {
-        registry.get_or_error(agent_id)?;
         let config_path = registry.agent_config_path(agent_id)?;
+        // An agent skipped at load for an invalid config is not registered, but its file
+        // can still be removed.
+        if !config_path.exists() {
+            registry.get_or_error(agent_id)?;
+        }
         registry.delete_agent_config(agent_id)?;
         registry.load_from_xdg()?;
         Ok(AgentRemoveResult {
@@ -489,6 +565,9 @@ mod tests {
                 system_prompt: None,
                 system_prompt_path: Some("prompts/missing.md".to_string()),
                 workflow_id: None,
+                default_provider: None,
+                frame_type_per_node_type: false,
+                capabilities: Vec::new(),
                 metadata,
             };
 
@@ -0,0 +1,190 @@
+//! Effective agent configuration: the settings an agent runs with and where each came from.
+//!
+//! The registry loads `config.toml` agents first and XDG agent files second; an XDG file
+//! replaces the whole `config.toml` definition of the same agent. The registry keeps every
+//! layer it loaded so `agent show --effective` can report the winning value of each setting
+//! and the values it shadowed.
+
+use crate::agent::identity::AgentIdentity;
+use crate::agent::profile::prompt_contract::KEY_SYSTEM_PROMPT;
+use crate::agent::profile::AgentConfig;
+use crate::agent::prompt::resolve_prompt_path;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where one layer of an agent's configuration was read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AgentConfigSource {
+    /// `[agents.<id>]` in the merged workspace/global `config.toml`.
+    ConfigToml,
+    /// An agent file in the XDG agents directory.
+    Xdg { path: PathBuf },
+    /// Registered in code; no config file backs it.
+    Runtime,
+}
+
+impl fmt::Display for AgentConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentConfigSource::ConfigToml => write!(f, "config.toml"),
+            AgentConfigSource::Xdg { path } => write!(f, "xdg ({})", path.display()),
+            AgentConfigSource::Runtime => write!(f, "runtime"),
+        }
+    }
+}
+
+/// One loaded definition of an agent, in load order.
+#[derive(Debug, Clone)]
+pub struct AgentConfigLayer {
+    pub source: AgentConfigSource,
+    pub config: AgentConfig,
+}
+
+/// A value a later layer replaced.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowedValue {
+    pub value: String,
+    pub source: AgentConfigSource,
+}
+
+/// One effective setting; metadata keys are reported as `metadata.<key>`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveSetting {
+    pub key: String,
+    pub value: String,
+    pub source: AgentConfigSource,
+    /// Values for the same key from earlier layers, oldest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ShadowedValue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveAgentConfig {
+    pub agent_id: String,
+    /// Every layer that defined the agent, in load order; the last one is in effect.
+    pub layers: Vec<AgentConfigSource>,
+    pub settings: Vec<EffectiveSetting>,
+}
+
+/// Resolve the settings `agent` runs with from the layers that defined it.
+///
+/// Without layers (an agent registered in code) the identity itself is reported with a
+/// `runtime` source.
+pub fn resolve_effective_config(
+    agent: &AgentIdentity,
+    layers: &[AgentConfigLayer],
+) -> EffectiveAgentConfig {
+    let Some((winner, shadowed)) = layers.split_last() else {
+        return EffectiveAgentConfig {
+            agent_id: agent.agent_id.clone(),
+            layers: vec![AgentConfigSource::Runtime],
+            settings: identity_settings(agent)
+                .into_iter()
+                .map(|(key, value)| EffectiveSetting {
+                    key,
+                    value,
+                    source: AgentConfigSource::Runtime,
+                    overrides: Vec::new(),
+                })
+                .collect(),
+        };
+    };
+
+    let earlier: Vec<(BTreeMap<String, String>, &AgentConfigSource)> = shadowed
+        .iter()
+        .map(|layer| (config_settings(&layer.config), &layer.source))
+        .collect();
+    let settings = config_settings(&winner.config)
+        .into_iter()
+        .map(|(key, value)| {
+            let overrides = earlier
+                .iter()
+                .filter_map(|(settings, source)| {
+                    settings.get(&key).map(|value| ShadowedValue {
+                        value: value.clone(),
+                        source: (*source).clone(),
+                    })
+                })
+                .collect();
+            EffectiveSetting {
+                key,
+                value,
+                source: winner.source.clone(),
+                overrides,
+            }
+        })
+        .collect();
+
+    EffectiveAgentConfig {
+        agent_id: agent.agent_id.clone(),
+        layers: layers.iter().map(|layer| layer.source.clone()).collect(),
+        settings,
+    }
+}
+
+/// Settings a config layer sets explicitly, keyed by setting name.
+fn config_settings(config: &AgentConfig) -> BTreeMap<String, String> {
+    let mut settings = BTreeMap::new();
+    settings.insert("role".to_string(), format!("{:?}", config.role));
+    if let Some(prompt) = &config.system_prompt {
+        settings.insert("system_prompt".to_string(), inline_prompt_label(prompt));
+    }
+    if let Some(path) = &config.system_prompt_path {
+        settings.insert("system_prompt_path".to_string(), resolved_prompt_path(path));
+    }
+    if let Some(workflow_id) = &config.workflow_id {
+        settings.insert("workflow_id".to_string(), workflow_id.clone());
+    }
+    if let Some(provider) = &config.default_provider {
+        settings.insert("default_provider".to_string(), provider.clone());
+    }
+    if config.frame_type_per_node_type {
+        settings.insert("frame_type_per_node_type".to_string(), "true".to_string());
+    }
+    if !config.capabilities.is_empty() {
+        settings.insert(
+            "capabilities".to_string(),
+            format!("{:?}", config.capabilities),
+        );
+    }
+    for (key, value) in config.metadata.iter() {
+        settings.insert(format!("metadata.{}", key), value.clone());
+    }
+    settings
+}
+
+fn identity_settings(agent: &AgentIdentity) -> BTreeMap<String, String> {
+    let mut settings = BTreeMap::new();
+    settings.insert("role".to_string(), format!("{:?}", agent.role));
+    if let Some(workflow_id) = &agent.workflow_id {
+        settings.insert("workflow_id".to_string(), workflow_id.clone());
+    }
+    if let Some(provider) = &agent.default_provider {
+        settings.insert("default_provider".to_string(), provider.clone());
+    }
+    for (key, value) in agent.metadata.iter() {
+        let value = if key == KEY_SYSTEM_PROMPT {
+            inline_prompt_label(value)
+        } else {
+            value.clone()
+        };
+        settings.insert(format!("metadata.{}", key), value);
+    }
+    settings
+}
+
+fn inline_prompt_label(prompt: &str) -> String {
+    format!("[inline, {} chars]", prompt.chars().count())
+}
+
+/// Prompt path as `agent show` resolves it: relative to the meld config directory.
+pub fn resolved_prompt_path(path: &str) -> String {
+    crate::config::xdg::config_home()
+        .ok()
+        .and_then(|home| resolve_prompt_path(path, &home.join("meld")).ok())
+        .map(|resolved| resolved.display().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
@@ -4,6 +4,7 @@
 
 use crate::agent::profile::metadata_types::AgentMetadata;
 use crate::error::ApiError;
+use crate::store::NodeType;
 use serde::{Deserialize, Serialize};
 
 /// Agent role defining what operations an agent can perform
@@ -16,6 +17,18 @@ pub enum AgentRole {
     Writer,
 }
 
+impl AgentRole {
+    /// Whether an agent of this role may hold `capability`.
+    ///
+    /// Readers hold only `Read`; writing and synthesis need the Writer role.
+    pub fn allows(&self, capability: &Capability) -> bool {
+        match self {
+            AgentRole::Reader => *capability == Capability::Read,
+            AgentRole::Writer => true,
+        }
+    }
+}
+
 /// Agent capability (for future extensibility)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Capability {
@@ -23,6 +36,8 @@ pub enum Capability {
     Read,
     /// Can write context frames
     Write,
+    /// Can produce directory frames synthesized from child frames
+    Synthesize,
 }
 
 /// Agent identity with role and capabilities
@@ -37,6 +52,12 @@ pub struct AgentIdentity {
     /// Optional workflow profile binding.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workflow_id: Option<String>,
+    /// Provider used for generation when no provider is given explicitly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_provider: Option<String>,
+    /// Whether the default frame type carries a `-file` / `-dir` node type suffix.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub frame_type_per_node_type: bool,
     /// Metadata for agent (e.g., system prompts, custom settings)
     #[serde(default)]
     pub metadata: AgentMetadata,
@@ -55,6 +76,8 @@ impl AgentIdentity {
             role,
             capabilities,
             workflow_id: None,
+            default_provider: None,
+            frame_type_per_node_type: false,
             metadata: AgentMetadata::new(),
         }
     }
@@ -64,6 +87,26 @@ impl AgentIdentity {
         self.workflow_id.as_deref()
     }
 
+    /// Return optional default provider name.
+    pub fn default_provider(&self) -> Option<&str> {
+        self.default_provider.as_deref()
+    }
+
+    /// Frame type used when no explicit frame type is requested for a node of `node_type`.
+    ///
+    /// `context-<agent_id>`, or `context-<agent_id>-file` / `context-<agent_id>-dir` when
+    /// `frame_type_per_node_type` is enabled.
+    pub fn default_frame_type(&self, node_type: &NodeType) -> String {
+        if !self.frame_type_per_node_type {
+            return format!("context-{}", self.agent_id);
+        }
+        let suffix = match node_type {
+            NodeType::File { .. } | NodeType::Symlink { .. } => "file",
+            NodeType::Directory => "dir",
+        };
+        format!("context-{}-{}", self.agent_id, suffix)
+    }
+
     /// Check if the agent has read capability
     pub fn can_read(&self) -> bool {
         self.capabilities.contains(&Capability::Read)
@@ -74,6 +117,32 @@ impl AgentIdentity {
         self.capabilities.contains(&Capability::Write)
     }
 
+    /// Check if the agent has synthesize capability
+    pub fn can_synthesize(&self) -> bool {
+        self.capabilities.contains(&Capability::Synthesize)
+    }
+
+    /// Add capabilities beyond the role defaults, skipping ones already held.
+    ///
+    /// Fails without granting anything when the role does not allow one of them.
+    pub fn grant_capabilities(&mut self, capabilities: &[Capability]) -> Result<(), ApiError> {
+        if let Some(denied) = capabilities
+            .iter()
+            .find(|capability| !self.role.allows(capability))
+        {
+            return Err(ApiError::Unauthorized(format!(
+                "Agent {} (role: {:?}) cannot be granted {:?}",
+                self.agent_id, self.role, denied
+            )));
+        }
+        for capability in capabilities {
+            if !self.capabilities.contains(capability) {
+                self.capabilities.push(capability.clone());
+            }
+        }
+        Ok(())
+    }
+
     /// Verify that the agent can perform read operations
     pub fn verify_read(&self) -> Result<(), ApiError> {
         if !self.can_read() {
@@ -95,6 +164,17 @@ impl AgentIdentity {
         }
         Ok(())
     }
+
+    /// Verify that the agent can produce synthesized directory frames
+    pub fn verify_synthesize(&self) -> Result<(), ApiError> {
+        if !self.can_synthesize() {
+            return Err(ApiError::Unauthorized(format!(
+                "Agent {} (role: {:?}) cannot synthesize directory frames",
+                self.agent_id, self.role
+            )));
+        }
+        Ok(())
+    }
 }
 
 /// Validation result for agent configuration
@@ -103,6 +183,8 @@ pub struct ValidationResult {
     pub agent_id: String,
     pub checks: Vec<(String, bool)>,
     pub errors: Vec<String>,
+    /// Non-fatal findings; they do not affect `is_valid`.
+    pub warnings: Vec<String>,
 }
 
 impl ValidationResult {
@@ -111,6 +193,7 @@ impl ValidationResult {
             agent_id,
             checks: Vec::new(),
             errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -122,6 +205,10 @@ impl ValidationResult {
         self.errors.push(error);
     }
 
+    pub fn add_warning(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
     pub fn is_valid(&self) -> bool {
         self.errors.is_empty() && self.checks.iter().all(|(_, passed)| *passed)
     }
@@ -8,4 +8,4 @@ pub mod validation;
 pub use config::AgentConfig;
 pub use metadata_types::AgentMetadata;
 pub use prompt_contract::PromptContract;
-pub use validation::validate_agent_config;
+pub use validation::{parse_agent_config, validate_agent_config};
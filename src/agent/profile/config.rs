@@ -1,12 +1,15 @@
 //! Agent configuration schema owned by the agent domain.
 
-use crate::agent::identity::AgentRole;
+use crate::agent::identity::{AgentRole, Capability};
 use crate::agent::profile::metadata_types::AgentMetadata;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Agent configuration
+///
+/// Unknown fields are rejected so a misspelled key fails at load instead of being ignored.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AgentConfig {
     /// Unique agent identifier
     pub agent_id: String,
@@ -32,6 +35,20 @@ pub struct AgentConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workflow_id: Option<String>,
 
+    /// Provider used by `context generate` when `--provider` is omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_provider: Option<String>,
+
+    /// Suffix the default frame type with the node type
+    /// (`context-<agent>-file` / `context-<agent>-dir`) so file and directory frames can be
+    /// queried apart. Off by default, keeping the flat `context-<agent>` type.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub frame_type_per_node_type: bool,
+
+    /// Capabilities granted on top of the role defaults, e.g. `["Synthesize"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<Capability>,
+
     /// Agent-specific metadata
     #[serde(default)]
     pub metadata: AgentMetadata,
@@ -43,6 +60,11 @@ impl AgentConfig {
         &self,
         _providers: &HashMap<String, crate::provider::ProviderConfig>,
     ) -> Result<(), String> {
-        crate::agent::profile::validation::validate_agent_config(self, _providers)
+        let result = crate::agent::profile::validation::validate_agent_config(self, _providers);
+        if result.is_valid() {
+            Ok(())
+        } else {
+            Err(result.errors.join("; "))
+        }
     }
 }
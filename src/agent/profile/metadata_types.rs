@@ -4,10 +4,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 
-/// Agent metadata contract type.
+/// Agent metadata contract type. Keys serialize in sorted order.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(transparent)]
-pub struct AgentMetadata(HashMap<String, String>);
+pub struct AgentMetadata(
+    #[serde(serialize_with = "crate::metadata::serialize_sorted_map")] HashMap<String, String>,
+);
 
 impl AgentMetadata {
     pub fn new() -> Self {
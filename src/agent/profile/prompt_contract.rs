@@ -4,11 +4,15 @@ use crate::agent::identity::AgentIdentity;
 use crate::agent::profile::metadata_types::AgentMetadata;
 use crate::error::ApiError;
 use crate::store::NodeType;
+use std::collections::BTreeMap;
 
 pub const KEY_SYSTEM_PROMPT: &str = "system_prompt";
 pub const KEY_USER_PROMPT_FILE: &str = "user_prompt_file";
 pub const KEY_USER_PROMPT_DIRECTORY: &str = "user_prompt_directory";
 
+/// Placeholders filled by `render_user_prompt`; `--prompt-var` may not redefine them.
+pub const BUILTIN_PLACEHOLDERS: &[&str] = &["path", "node_type", "file_size"];
+
 #[derive(Debug, Clone)]
 pub struct PromptContract {
     pub system_prompt: String,
@@ -32,14 +36,17 @@ impl PromptContract {
         })
     }
 
+    /// Render the user prompt template. `prompt_vars` are substituted after the built-in
+    /// placeholders.
     pub fn render_user_prompt(
         &self,
         node_type: NodeType,
         path: &str,
         file_size: Option<u64>,
+        prompt_vars: &BTreeMap<String, String>,
     ) -> String {
         let template = match node_type {
-            NodeType::File { .. } => &self.user_prompt_file,
+            NodeType::File { .. } | NodeType::Symlink { .. } => &self.user_prompt_file,
             NodeType::Directory => &self.user_prompt_directory,
         };
 
@@ -48,16 +55,54 @@ impl PromptContract {
             match node_type {
                 NodeType::File { .. } => "File",
                 NodeType::Directory => "Directory",
+                NodeType::Symlink { .. } => "Symlink",
             },
         );
 
         if let Some(size) = file_size {
             rendered = rendered.replace("{file_size}", &size.to_string());
         }
+        for (key, value) in prompt_vars {
+            rendered = rendered.replace(&format!("{{{}}}", key), value);
+        }
         rendered
     }
 }
 
+/// Parse `--prompt-var key=value` pairs. Keys must be non-empty, unique, and must not
+/// collide with a built-in placeholder.
+pub fn parse_prompt_vars(pairs: &[String]) -> Result<BTreeMap<String, String>, ApiError> {
+    let mut vars = BTreeMap::new();
+    for pair in pairs {
+        let (key, value) = pair.split_once('=').ok_or_else(|| {
+            ApiError::ConfigError(format!(
+                "Invalid --prompt-var '{}': expected key=value",
+                pair
+            ))
+        })?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(['{', '}']) {
+            return Err(ApiError::ConfigError(format!(
+                "Invalid --prompt-var '{}': key must be a non-empty placeholder name",
+                pair
+            )));
+        }
+        if BUILTIN_PLACEHOLDERS.contains(&key) {
+            return Err(ApiError::ConfigError(format!(
+                "--prompt-var '{}' conflicts with the built-in placeholder {{{}}}",
+                key, key
+            )));
+        }
+        if vars.insert(key.to_string(), value.to_string()).is_some() {
+            return Err(ApiError::ConfigError(format!(
+                "--prompt-var '{}' is given more than once",
+                key
+            )));
+        }
+    }
+    Ok(vars)
+}
+
 fn get_required(
     agent_id: &str,
     metadata: &AgentMetadata,
@@ -71,3 +116,39 @@ fn get_required(
             field: key,
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(template: &str) -> PromptContract {
+        PromptContract {
+            system_prompt: "system".to_string(),
+            user_prompt_file: template.to_string(),
+            user_prompt_directory: template.to_string(),
+        }
+    }
+
+    #[test]
+    fn custom_vars_are_substituted_after_builtins() {
+        let vars = parse_prompt_vars(&[
+            "project_name=meld".to_string(),
+            "style_guide=terse, no emoji".to_string(),
+        ])
+        .unwrap();
+        let rendered = contract("Document {path} for {project_name}. Style: {style_guide}.")
+            .render_user_prompt(NodeType::Directory, "src/lib", None, &vars);
+        assert_eq!(
+            rendered,
+            "Document src/lib for meld. Style: terse, no emoji."
+        );
+    }
+
+    #[test]
+    fn custom_var_colliding_with_builtin_errors() {
+        let err = parse_prompt_vars(&["path=/etc".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("{path}"));
+        assert!(parse_prompt_vars(&["no_equals".to_string()]).is_err());
+        assert!(parse_prompt_vars(&["=value".to_string()]).is_err());
+    }
+}
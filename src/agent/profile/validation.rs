@@ -1,21 +1,27 @@
 //! Agent configuration validation owned by the agent domain.
+//!
+//! [`parse_agent_config`] is the load-time entry point: it rejects unknown fields, missing
+//! required fields, and wrong value types through the `AgentConfig` schema, then runs
+//! [`validate_agent_config`] for per-role requirements. Every error names the offending field.
 
 use super::config::AgentConfig;
-use crate::agent::identity::AgentRole;
+use crate::agent::identity::{AgentRole, ValidationResult};
 use std::collections::HashMap;
 
-/// Validate agent configuration.
+/// Validate agent configuration values, one error per offending field.
 pub fn validate_agent_config(
     agent: &AgentConfig,
     _providers: &HashMap<String, crate::provider::ProviderConfig>,
-) -> Result<(), String> {
+) -> ValidationResult {
+    let mut result = ValidationResult::new(agent.agent_id.clone());
+
     if agent.agent_id.trim().is_empty() {
-        return Err("Agent ID cannot be empty".to_string());
+        result.add_error("agent_id: cannot be empty".to_string());
     }
 
     if let Some(ref prompt) = agent.system_prompt {
         if prompt.trim().is_empty() {
-            return Err("System prompt cannot be empty if provided".to_string());
+            result.add_error("system_prompt: cannot be empty if provided".to_string());
         }
     }
 
@@ -23,29 +29,141 @@ pub fn validate_agent_config(
         && agent.system_prompt.is_none()
         && agent.system_prompt_path.is_none()
     {
-        return Err(format!(
-            "Agent '{}' (role: {:?}) requires either system_prompt or system_prompt_path",
+        result.add_error(format!(
+            "system_prompt_path: agent '{}' (role: {:?}) requires either system_prompt or system_prompt_path",
             agent.agent_id, agent.role
         ));
     }
 
     if let Some(ref prompt_path) = agent.system_prompt_path {
         if prompt_path.trim().is_empty() {
-            return Err("system_prompt_path cannot be empty if provided".to_string());
+            result.add_error("system_prompt_path: cannot be empty if provided".to_string());
         }
     }
 
     if let Some(workflow_id) = &agent.workflow_id {
         if workflow_id.trim().is_empty() {
-            return Err("workflow_id cannot be empty if provided".to_string());
-        }
-        if agent.role != AgentRole::Writer {
-            return Err(format!(
-                "Agent '{}' has workflow_id set but role {:?} is not allowed for workflow binding",
+            result.add_error("workflow_id: cannot be empty if provided".to_string());
+        } else if agent.role != AgentRole::Writer {
+            result.add_error(format!(
+                "workflow_id: agent '{}' has role {:?}, which is not allowed for workflow binding",
                 agent.agent_id, agent.role
             ));
         }
     }
 
-    Ok(())
+    for capability in &agent.capabilities {
+        if !agent.role.allows(capability) {
+            result.add_error(format!(
+                "capabilities: agent '{}' has role {:?}, which cannot hold {:?}",
+                agent.agent_id, agent.role, capability
+            ));
+        }
+    }
+
+    if let Some(default_provider) = &agent.default_provider {
+        if default_provider.trim().is_empty() {
+            result.add_error("default_provider: cannot be empty if provided".to_string());
+        }
+    }
+
+    result
+}
+
+/// Parse an agent TOML document and validate it.
+///
+/// Schema problems (an unknown or misspelled field, a missing `agent_id` or `role`, a value of
+/// the wrong type) come back as a single error carrying the parser's field and line; value
+/// problems come back as one error per field.
+pub fn parse_agent_config(agent_id: &str, content: &str) -> Result<AgentConfig, ValidationResult> {
+    let config: AgentConfig = toml::from_str(content).map_err(|e| {
+        let mut result = ValidationResult::new(agent_id.to_string());
+        result.add_error(e.to_string().trim().to_string());
+        result
+    })?;
+    let result = validate_agent_config(&config, &HashMap::new());
+    if result.is_valid() {
+        Ok(config)
+    } else {
+        Err(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_field_is_rejected_by_name() {
+        let result = parse_agent_config(
+            "writer",
+            "agent_id = \"writer\"\nrole = \"Writer\"\nsystem_promt = \"typo\"\n",
+        )
+        .unwrap_err();
+        assert_eq!(result.errors.len(), 1);
+        assert!(
+            result.errors[0].contains("unknown field `system_promt`"),
+            "{:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn writer_without_prompt_names_the_missing_field() {
+        let result =
+            parse_agent_config("writer", "agent_id = \"writer\"\nrole = \"Writer\"\n").unwrap_err();
+        assert_eq!(result.agent_id, "writer");
+        assert_eq!(result.errors.len(), 1);
+        assert!(
+            result.errors[0].starts_with("system_prompt_path:"),
+            "{:?}",
+            result.errors
+        );
+
+        let result = parse_agent_config("writer", "agent_id = \"writer\"\n").unwrap_err();
+        assert!(
+            result.errors[0].contains("missing field `role`"),
+            "{:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn wrong_value_type_is_rejected() {
+        let result = parse_agent_config(
+            "reader",
+            "agent_id = \"reader\"\nrole = \"Reader\"\nframe_type_per_node_type = \"yes\"\n",
+        )
+        .unwrap_err();
+        assert!(!result.is_valid());
+    }
+
+    #[test]
+    fn valid_config_parses() {
+        let config = parse_agent_config(
+            "writer",
+            "agent_id = \"writer\"\nrole = \"Writer\"\nsystem_prompt = \"Summarize.\"\n\n[metadata]\nuser_prompt_file = \"Analyze {path}\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.role, AgentRole::Writer);
+        assert_eq!(config.system_prompt.as_deref(), Some("Summarize."));
+
+        let reader = parse_agent_config("reader", "agent_id = \"reader\"\nrole = \"Reader\"\n");
+        assert!(reader.is_ok());
+    }
+
+    #[test]
+    fn reader_cannot_hold_write_capabilities() {
+        let result = parse_agent_config(
+            "reader",
+            "agent_id = \"reader\"\nrole = \"Reader\"\ncapabilities = [\"Read\", \"Write\"]\n",
+        )
+        .unwrap_err();
+        assert_eq!(result.errors.len(), 1);
+        assert!(
+            result.errors[0].starts_with("capabilities:"),
+            "{:?}",
+            result.errors
+        );
+    }
 }
@@ -1,25 +1,49 @@
 //! Agent registry: in-memory aggregate of loaded agents.
 
-use crate::agent::identity::{AgentIdentity, AgentRole, ValidationResult};
+use crate::agent::effective::{
+    resolve_effective_config, AgentConfigLayer, AgentConfigSource, EffectiveAgentConfig,
+};
+use crate::agent::identity::{AgentIdentity, AgentRole, Capability, ValidationResult};
 use crate::agent::profile::prompt_contract::{
     KEY_SYSTEM_PROMPT, KEY_USER_PROMPT_DIRECTORY, KEY_USER_PROMPT_FILE,
 };
-use crate::agent::profile::AgentConfig;
+use crate::agent::profile::{parse_agent_config, validate_agent_config, AgentConfig};
 use crate::agent::storage::AgentStorage;
 use crate::error::ApiError;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use toml;
 
 /// Agent registry for managing agent identities
 ///
 /// Holds in-memory aggregate and delegates persistence to the storage port.
 pub struct AgentRegistry {
     agents: HashMap<String, AgentIdentity>,
+    /// Config layers each agent was loaded from, in load order (for `agent show --effective`)
+    layers: HashMap<String, Vec<AgentConfigLayer>>,
+    /// Agents skipped at load because their config is invalid, with the reasons
+    invalid: HashMap<String, SkippedAgent>,
     storage: Arc<dyn AgentStorage>,
 }
 
+/// An agent config that failed validation and was left out of the registry.
+#[derive(Debug, Clone, PartialEq)]
+struct SkippedAgent {
+    source: AgentConfigSource,
+    errors: Vec<String>,
+}
+
+impl SkippedAgent {
+    fn describe(&self, agent_id: &str) -> String {
+        format!(
+            "Invalid agent config '{}' in {}:\n  - {}",
+            agent_id,
+            self.source,
+            self.errors.join("\n  - ")
+        )
+    }
+}
+
 impl AgentRegistry {
     /// Create a new empty agent registry with default XDG storage
     pub fn new() -> Self {
@@ -30,6 +54,8 @@ impl AgentRegistry {
     pub fn with_storage(storage: Arc<dyn AgentStorage>) -> Self {
         Self {
             agents: HashMap::new(),
+            layers: HashMap::new(),
+            invalid: HashMap::new(),
             storage,
         }
     }
@@ -45,9 +71,48 @@ impl AgentRegistry {
     }
 
     /// Get an agent identity by ID or return an error
+    ///
+    /// An agent skipped at load reports why its config was rejected.
     pub fn get_or_error(&self, agent_id: &str) -> Result<&AgentIdentity, ApiError> {
-        self.get(agent_id)
-            .ok_or_else(|| ApiError::Unauthorized(format!("Agent not found: {}", agent_id)))
+        if let Some(agent) = self.get(agent_id) {
+            return Ok(agent);
+        }
+        match self.invalid.get(agent_id) {
+            Some(skipped) => Err(ApiError::ConfigError(skipped.describe(agent_id))),
+            None => Err(ApiError::Unauthorized(format!(
+                "Agent not found: {}",
+                agent_id
+            ))),
+        }
+    }
+
+    /// Record an agent whose config failed validation and print why on stderr.
+    ///
+    /// A reload that finds the same errors again does not repeat the warning.
+    fn skip_invalid(
+        &mut self,
+        agent_id: &str,
+        skipped: SkippedAgent,
+        previous: &HashMap<String, SkippedAgent>,
+    ) {
+        if previous.get(agent_id) != Some(&skipped) {
+            let message = skipped.describe(agent_id);
+            tracing::warn!("{}", message);
+            eprintln!("Warning: skipping agent. {}", message);
+        }
+        self.invalid.insert(agent_id.to_string(), skipped);
+    }
+
+    /// Drop the skipped agents recorded from `source`, returning them.
+    fn take_skipped(
+        &mut self,
+        from_source: impl Fn(&AgentConfigSource) -> bool,
+    ) -> HashMap<String, SkippedAgent> {
+        let (taken, kept) = std::mem::take(&mut self.invalid)
+            .into_iter()
+            .partition(|(_, skipped)| from_source(&skipped.source));
+        self.invalid = kept;
+        taken
     }
 
     /// Get all registered agents
@@ -58,6 +123,25 @@ impl AgentRegistry {
     /// Remove an agent from the registry
     pub fn remove(&mut self, agent_id: &str) {
         self.agents.remove(agent_id);
+        self.layers.remove(agent_id);
+        self.invalid.remove(agent_id);
+    }
+
+    /// Effective configuration of an agent with the source of each setting
+    pub fn effective_config(&self, agent_id: &str) -> Result<EffectiveAgentConfig, ApiError> {
+        let agent = self.get_or_error(agent_id)?;
+        let layers = self.layers.get(agent_id).map(Vec::as_slice).unwrap_or(&[]);
+        Ok(resolve_effective_config(agent, layers))
+    }
+
+    /// Reloading a source replaces its earlier layer rather than stacking a duplicate.
+    fn record_layer(&mut self, source: AgentConfigSource, config: &AgentConfig) {
+        let layers = self.layers.entry(config.agent_id.clone()).or_default();
+        layers.retain(|layer| layer.source != source);
+        layers.push(AgentConfigLayer {
+            source,
+            config: config.clone(),
+        });
     }
 
     /// Load agents from configuration
@@ -65,9 +149,22 @@ impl AgentRegistry {
         &mut self,
         config: &crate::config::MerkleConfig,
     ) -> Result<(), ApiError> {
+        let previous = self.take_skipped(|source| *source == AgentConfigSource::ConfigToml);
         for agent_config in config.agents.values() {
+            let validation = validate_agent_config(agent_config, &config.providers);
+            if !validation.is_valid() {
+                let skipped = SkippedAgent {
+                    source: AgentConfigSource::ConfigToml,
+                    errors: validation.errors,
+                };
+                self.skip_invalid(&agent_config.agent_id, skipped, &previous);
+                continue;
+            }
             let mut identity = AgentIdentity::new(agent_config.agent_id.clone(), agent_config.role);
             identity.workflow_id = agent_config.workflow_id.clone();
+            identity.default_provider = agent_config.default_provider.clone();
+            identity.frame_type_per_node_type = agent_config.frame_type_per_node_type;
+            identity.grant_capabilities(&agent_config.capabilities)?;
 
             // Store system prompt in metadata if provided
             if let Some(system_prompt) = &agent_config.system_prompt {
@@ -81,17 +178,34 @@ impl AgentRegistry {
                 identity.metadata.insert(key.clone(), value.clone());
             }
 
+            self.invalid.remove(&agent_config.agent_id);
             self.register(identity);
+            self.record_layer(AgentConfigSource::ConfigToml, agent_config);
         }
         Ok(())
     }
 
     /// Load agents from XDG directory via the storage
+    ///
+    /// Files that fail to parse are skipped with a warning on stderr; `get_or_error` reports
+    /// the recorded errors for them.
     pub fn load_from_xdg(&mut self) -> Result<(), ApiError> {
-        for stored in self.storage.list()? {
+        let listing = self.storage.list()?;
+        let previous = self.take_skipped(|source| matches!(source, AgentConfigSource::Xdg { .. }));
+        for invalid in listing.invalid {
+            let skipped = SkippedAgent {
+                source: AgentConfigSource::Xdg { path: invalid.path },
+                errors: invalid.errors,
+            };
+            self.skip_invalid(&invalid.agent_id, skipped, &previous);
+        }
+        for stored in listing.loaded {
             let mut identity =
                 AgentIdentity::new(stored.config.agent_id.clone(), stored.config.role);
             identity.workflow_id = stored.config.workflow_id.clone();
+            identity.default_provider = stored.config.default_provider.clone();
+            identity.frame_type_per_node_type = stored.config.frame_type_per_node_type;
+            identity.grant_capabilities(&stored.config.capabilities)?;
             if let Some(prompt) = stored.resolved_system_prompt {
                 if !prompt.is_empty() {
                     identity
@@ -102,7 +216,14 @@ impl AgentRegistry {
             for (key, value) in &stored.config.metadata {
                 identity.metadata.insert(key.clone(), value.clone());
             }
+            self.invalid.remove(&stored.agent_id);
             self.agents.insert(stored.agent_id.clone(), identity);
+            self.record_layer(
+                AgentConfigSource::Xdg {
+                    path: stored.path.clone(),
+                },
+                &stored.config,
+            );
         }
         Ok(())
     }
@@ -119,6 +240,17 @@ impl AgentRegistry {
         }
     }
 
+    /// List agents holding `capability`, sorted by agent ID
+    pub fn list_by_capability(&self, capability: Capability) -> Vec<&AgentIdentity> {
+        let mut agents: Vec<&AgentIdentity> = self
+            .agents
+            .values()
+            .filter(|agent| agent.capabilities.contains(&capability))
+            .collect();
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        agents
+    }
+
     /// Get the XDG config file path for an agent
     pub fn agent_config_path(&self, agent_id: &str) -> Result<PathBuf, ApiError> {
         self.storage.path_for(agent_id)
@@ -147,6 +279,13 @@ impl AgentRegistry {
         let agent = match self.get(agent_id) {
             Some(a) => a,
             None => {
+                // An invalid config is skipped at load; report why.
+                if let Some(skipped) = self.invalid.get(agent_id) {
+                    for error in &skipped.errors {
+                        result.add_error(format!("Invalid config ({}): {}", skipped.source, error));
+                    }
+                    return Ok(result);
+                }
                 result.add_error("Agent not found in registry".to_string());
                 return Ok(result);
             }
@@ -184,6 +323,15 @@ impl AgentRegistry {
         // Validate role is valid (should always be valid if loaded)
         result.add_check("Role is valid", true);
 
+        for capability in &agent.capabilities {
+            if !agent.role.allows(capability) {
+                result.add_error(format!(
+                    "{:?} capability requires the Writer role",
+                    capability
+                ));
+            }
+        }
+
         // Load and validate config file
         let content = match std::fs::read_to_string(&config_path) {
             Ok(c) => c,
@@ -193,10 +341,12 @@ impl AgentRegistry {
             }
         };
 
-        let agent_config: AgentConfig = match toml::from_str(&content) {
+        let agent_config = match parse_agent_config(agent_id, &content) {
             Ok(config) => config,
-            Err(e) => {
-                result.add_error(format!("Failed to parse config file: {}", e));
+            Err(invalid) => {
+                for error in invalid.errors {
+                    result.add_error(format!("Invalid config file: {}", error));
+                }
                 return Ok(result);
             }
         };
@@ -294,6 +444,34 @@ mod tests {
         assert!(agent.verify_write().is_ok());
     }
 
+    #[test]
+    fn test_list_by_capability() {
+        let mut registry = AgentRegistry::new();
+
+        let mut synthesizer = AgentIdentity::new("synth".to_string(), AgentRole::Writer);
+        synthesizer
+            .grant_capabilities(&[Capability::Synthesize, Capability::Write])
+            .unwrap();
+        registry.register(synthesizer);
+        registry.register(AgentIdentity::new("writer".to_string(), AgentRole::Writer));
+        registry.register(AgentIdentity::new("reader".to_string(), AgentRole::Reader));
+
+        let ids = |capability| {
+            registry
+                .list_by_capability(capability)
+                .iter()
+                .map(|agent| agent.agent_id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(Capability::Synthesize), vec!["synth"]);
+        assert_eq!(ids(Capability::Write), vec!["synth", "writer"]);
+        assert_eq!(ids(Capability::Read), vec!["reader", "synth", "writer"]);
+        assert_eq!(
+            registry.get("synth").unwrap().capabilities,
+            vec![Capability::Read, Capability::Write, Capability::Synthesize]
+        );
+    }
+
     #[test]
     fn test_agent_registry() {
         let mut registry = AgentRegistry::new();
@@ -15,8 +15,23 @@ pub struct StoredAgentConfig {
     pub resolved_system_prompt: Option<String>,
 }
 
+/// An agent file that failed to parse, with the reasons
+#[derive(Debug, Clone)]
+pub struct InvalidAgentConfig {
+    pub agent_id: String,
+    pub path: PathBuf,
+    pub errors: Vec<String>,
+}
+
+/// Agent files in storage, split into loadable configs and ones that failed to parse
+#[derive(Debug, Clone, Default)]
+pub struct StoredAgentConfigs {
+    pub loaded: Vec<StoredAgentConfig>,
+    pub invalid: Vec<InvalidAgentConfig>,
+}
+
 pub trait AgentStorage: Send + Sync {
-    fn list(&self) -> Result<Vec<StoredAgentConfig>, ApiError>;
+    fn list(&self) -> Result<StoredAgentConfigs, ApiError>;
     fn path_for(&self, agent_id: &str) -> Result<PathBuf, ApiError>;
     fn save(&self, agent_id: &str, config: &AgentConfig) -> Result<(), ApiError>;
     fn delete(&self, agent_id: &str) -> Result<(), ApiError>;
@@ -1,6 +1,6 @@
-use super::{AgentStorage, StoredAgentConfig};
+use super::{AgentStorage, InvalidAgentConfig, StoredAgentConfig, StoredAgentConfigs};
 use crate::agent::identity::AgentRole;
-use crate::agent::profile::AgentConfig;
+use crate::agent::profile::{parse_agent_config, AgentConfig};
 use crate::agent::prompt::{resolve_prompt_path, PromptCache};
 use crate::error::ApiError;
 use std::ffi::OsStr;
@@ -37,10 +37,10 @@ fn agents_dir() -> Result<PathBuf, ApiError> {
 }
 
 impl AgentStorage for XdgAgentStorage {
-    fn list(&self) -> Result<Vec<StoredAgentConfig>, ApiError> {
+    fn list(&self) -> Result<StoredAgentConfigs, ApiError> {
         let agents_dir = agents_dir()?;
         if !agents_dir.exists() {
-            return Ok(Vec::new());
+            return Ok(StoredAgentConfigs::default());
         }
 
         let entries = std::fs::read_dir(&agents_dir).map_err(|e| {
@@ -53,7 +53,7 @@ impl AgentStorage for XdgAgentStorage {
 
         let base_dir = crate::config::xdg::config_home()?.join("meld");
         let mut prompt_cache = PromptCache::new();
-        let mut loaded = Vec::new();
+        let mut listing = StoredAgentConfigs::default();
 
         for entry in entries {
             let entry = match entry {
@@ -89,10 +89,14 @@ impl AgentStorage for XdgAgentStorage {
                 }
             };
 
-            let agent_config: AgentConfig = match toml::from_str(&content) {
+            let agent_config = match parse_agent_config(&agent_id, &content) {
                 Ok(config) => config,
-                Err(e) => {
-                    tracing::error!("Failed to parse agent config {}: {}", path.display(), e);
+                Err(result) => {
+                    listing.invalid.push(InvalidAgentConfig {
+                        agent_id,
+                        path,
+                        errors: result.errors,
+                    });
                     continue;
                 }
             };
@@ -143,7 +147,7 @@ impl AgentStorage for XdgAgentStorage {
                     None
                 };
 
-            loaded.push(StoredAgentConfig {
+            listing.loaded.push(StoredAgentConfig {
                 agent_id: agent_config.agent_id.clone(),
                 config: agent_config,
                 path,
@@ -151,7 +155,7 @@ impl AgentStorage for XdgAgentStorage {
             });
         }
 
-        Ok(loaded)
+        Ok(listing)
     }
 
     fn path_for(&self, agent_id: &str) -> Result<PathBuf, ApiError> {
@@ -1,9 +1,10 @@
 use crate::agent::commands::AgentCommandService;
 use crate::api::ContextApi;
 use crate::cli::{
-    format_agent_list_result_json, format_agent_list_result_text, format_agent_show_result_json,
-    format_agent_show_result_text, format_validation_result, format_validation_results_all,
-    AgentCommands, AgentPromptCommands,
+    format_agent_effective_json, format_agent_effective_text, format_agent_list_result_json,
+    format_agent_list_result_text, format_agent_show_result_json, format_agent_show_result_text,
+    format_validation_result, format_validation_results_all, to_json_output, AgentCommands,
+    AgentPromptCommands,
 };
 use crate::error::ApiError;
 use crate::workspace::{format_agent_status_text, AgentStatusEntry, AgentStatusOutput};
@@ -12,17 +13,23 @@ use std::path::{Path, PathBuf};
 pub fn handle_cli_command(api: &ContextApi, command: &AgentCommands) -> Result<String, ApiError> {
     match command {
         AgentCommands::Status { format } => handle_status(api, format),
-        AgentCommands::List { format, role } => handle_list(api, format, role.as_deref()),
+        AgentCommands::List {
+            format,
+            role,
+            detailed,
+        } => handle_list(api, format, role.as_deref(), *detailed),
         AgentCommands::Show {
             agent_id,
             format,
             include_prompt,
-        } => handle_show(api, agent_id, format, *include_prompt),
+            effective,
+        } => handle_show(api, agent_id, format, *include_prompt, *effective),
         AgentCommands::Validate {
             agent_id,
             all,
+            fail_fast,
             verbose,
-        } => handle_validate(api, agent_id.as_deref(), *all, *verbose),
+        } => handle_validate(api, agent_id.as_deref(), *all, *fail_fast, *verbose),
         AgentCommands::Create {
             agent_id,
             role,
@@ -70,9 +77,10 @@ fn handle_list(
     api: &ContextApi,
     format: &str,
     role_filter: Option<&str>,
+    detailed: bool,
 ) -> Result<String, ApiError> {
     let registry = api.agent_registry().read();
-    let result = AgentCommandService::list(&registry, role_filter)?;
+    let result = AgentCommandService::list(&registry, role_filter, detailed)?;
     match format {
         "json" => Ok(format_agent_list_result_json(&result)),
         _ => Ok(format_agent_list_result_text(&result)),
@@ -84,8 +92,16 @@ fn handle_show(
     agent_id: &str,
     format: &str,
     include_prompt: bool,
+    effective: bool,
 ) -> Result<String, ApiError> {
     let registry = api.agent_registry().read();
+    if effective {
+        let result = registry.effective_config(agent_id)?;
+        return match format {
+            "json" => Ok(format_agent_effective_json(&result)),
+            _ => Ok(format_agent_effective_text(&result)),
+        };
+    }
     let result = AgentCommandService::show(&registry, agent_id, include_prompt)?;
     match format {
         "json" => Ok(format_agent_show_result_json(&result)),
@@ -97,20 +113,30 @@ fn handle_validate(
     api: &ContextApi,
     agent_id: Option<&str>,
     all: bool,
+    fail_fast: bool,
     verbose: bool,
 ) -> Result<String, ApiError> {
     let registry = api.agent_registry().read();
+    let providers = api.provider_registry().read();
     if all {
-        let result = AgentCommandService::validate_all(&registry)?;
+        let result = AgentCommandService::validate_all(&registry, &providers, fail_fast)?;
         if result.results.is_empty() {
             return Ok("No agents found to validate.".to_string());
         }
-        Ok(format_validation_results_all(&result.results, verbose))
+        let report = format_validation_results_all(&result.results, result.skipped, verbose);
+        if result.invalid_count() > 0 {
+            return Err(ApiError::ConfigError(format!(
+                "{} agent(s) failed validation\n\n{}",
+                result.invalid_count(),
+                report
+            )));
+        }
+        Ok(report)
     } else {
         let id = agent_id.ok_or_else(|| {
             ApiError::ConfigError("Agent ID required unless --all is specified".to_string())
         })?;
-        let result = AgentCommandService::validate_single(&registry, id)?;
+        let result = AgentCommandService::validate_single(&registry, &providers, id)?;
         Ok(format_validation_result(&result.result, verbose))
     }
 }
@@ -226,8 +252,13 @@ fn edit_with_editor(
 
     let edited_content = std::fs::read_to_string(&temp_path)
         .map_err(|e| ApiError::ConfigError(format!("Failed to read edited file: {}", e)))?;
-    let agent_config: crate::agent::AgentConfig = toml::from_str(&edited_content)
-        .map_err(|e| ApiError::ConfigError(format!("Invalid config after editing: {}", e)))?;
+    let agent_config = crate::agent::profile::parse_agent_config(agent_id, &edited_content)
+        .map_err(|result| {
+            ApiError::ConfigError(format!(
+                "Invalid config after editing:\n  - {}",
+                result.errors.join("\n  - ")
+            ))
+        })?;
 
     let mut registry = api.agent_registry().write();
     AgentCommandService::persist_edited_config(&mut registry, agent_id, agent_config)?;
@@ -360,7 +391,7 @@ fn handle_status(api: &ContextApi, format: &str) -> Result<String, ApiError> {
     let valid_count = entries.iter().filter(|entry| entry.valid).count();
 
     if format == "json" {
-        serde_json::to_string_pretty(&AgentStatusOutput {
+        to_json_output(&AgentStatusOutput {
             agents: entries.clone(),
             total: entries.len(),
             valid_count,
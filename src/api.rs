@@ -10,19 +10,21 @@ use crate::context::events::{
     frame_added_envelope, head_selected_envelope, head_tombstoned_envelope,
 };
 use crate::context::frame::{Basis, Frame, FrameStorage};
+use crate::context::generation::contracts::HeadConflictPolicy;
 use crate::context::head::{decode_frame_anchor_target, node_ref, CurrentFrameHeadRead};
-use crate::context::query::get_node_query;
 use crate::context::query::{compose_frames, CompositionPolicy};
+use crate::context::query::{count_frames_query, get_node_query};
 use crate::context::queue::FrameGenerationQueue;
 use crate::error::ApiError;
 use crate::events::EventEnvelope;
-use crate::heads::HeadIndex;
+use crate::heads::snapshot::{self as head_snapshot, HeadSnapshotInfo};
+use crate::heads::{HeadIndex, LegacyHeadEntry};
 use crate::metadata::frame_write_contract::{
     build_generated_metadata, generated_metadata_input_from_payload, validate_frame_metadata,
     FrameMetadataValidationInput,
 };
 use crate::prompt_context::PromptContextArtifactStorage;
-use crate::store::NodeRecordStore;
+use crate::store::{NodeRecordStore, KEEP_FRAMES_METADATA_KEY, STALE_FRAME_TYPES_METADATA_KEY};
 use crate::telemetry::ProgressRuntime;
 use crate::types::{FrameID, NodeID};
 use crate::views::ViewPolicy;
@@ -30,7 +32,7 @@ use crate::workflow::registry::{RegisteredWorkflowProfile, WorkflowRegistry};
 use crate::world_state::WorldModelQueries;
 use hex;
 use serde_json::Value;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -38,7 +40,9 @@ use std::time::Instant;
 use tracing::{debug, info, instrument, warn};
 
 pub use crate::context::query::view::{ContextView, ContextViewBuilder, NodeContext};
-pub use crate::context::types::{CompactResult, RestoreResult, TombstoneResult};
+pub use crate::context::types::{
+    CompactResult, HeadRecoveryResult, HeadRestoreResult, RestoreResult, TombstoneResult,
+};
 
 /// Context API service
 ///
@@ -67,6 +71,19 @@ pub struct ContextApi {
     world_model_queries: Arc<parking_lot::RwLock<Option<Arc<WorldModelQueries>>>>,
     /// Optional workflow registry adapter for execution and queue hosted workflow runs.
     workflow_registry: Arc<parking_lot::RwLock<Option<Arc<parking_lot::RwLock<WorkflowRegistry>>>>>,
+    /// Reject path targets outside the workspace root; set from `system.strict_paths`.
+    strict_paths: Arc<std::sync::atomic::AtomicBool>,
+    /// Gate directory generation on `Capability::Synthesize`; set from
+    /// `system.require_synthesize_for_directories`.
+    require_synthesize: Arc<std::sync::atomic::AtomicBool>,
+    /// Hash text content with normalized line endings; set from
+    /// `system.normalize_line_endings`.
+    normalize_line_endings: Arc<std::sync::atomic::AtomicBool>,
+    /// File read settings for content hashing; set from `system.hash_chunk_size` and
+    /// `system.hash_mmap_threshold`.
+    content_read_options: Arc<parking_lot::RwLock<crate::tree::hasher::ContentReadOptions>>,
+    /// HTTP clients shared by every provider request made through this API.
+    provider_clients: Arc<crate::provider::ProviderHttpClientPool>,
 }
 
 #[derive(Clone)]
@@ -98,6 +115,11 @@ impl ContextApi {
             progress_context: Arc::new(parking_lot::RwLock::new(None)),
             world_model_queries: Arc::new(parking_lot::RwLock::new(None)),
             workflow_registry: Arc::new(parking_lot::RwLock::new(None)),
+            strict_paths: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            require_synthesize: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            normalize_line_endings: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            content_read_options: Arc::new(parking_lot::RwLock::new(Default::default())),
+            provider_clients: Arc::new(crate::provider::ProviderHttpClientPool::new()),
         }
     }
 
@@ -125,6 +147,11 @@ impl ContextApi {
             progress_context: Arc::new(parking_lot::RwLock::new(None)),
             world_model_queries: Arc::new(parking_lot::RwLock::new(None)),
             workflow_registry: Arc::new(parking_lot::RwLock::new(None)),
+            strict_paths: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            require_synthesize: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            normalize_line_endings: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            content_read_options: Arc::new(parking_lot::RwLock::new(Default::default())),
+            provider_clients: Arc::new(crate::provider::ProviderHttpClientPool::new()),
         }
     }
 
@@ -175,6 +202,8 @@ impl ContextApi {
                 "Workflow profile loading requires workspace root context".to_string(),
             )
         })?;
+        // A run context always sets the registry, loaded with its profile and overrides; an API
+        // built without one only sees the `MELD_PROFILE` profile.
         let config = ConfigLoader::load(workspace_root)?;
         let registry = WorkflowRegistry::load(&config.workflows)?;
         registry
@@ -187,6 +216,49 @@ impl ContextApi {
         self.workspace_root.as_deref()
     }
 
+    pub fn set_strict_paths(&self, strict: bool) {
+        self.strict_paths
+            .store(strict, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Whether path targets must stay inside the workspace root. Defaults to true.
+    pub fn strict_paths(&self) -> bool {
+        self.strict_paths.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn set_require_synthesize(&self, require: bool) {
+        self.require_synthesize
+            .store(require, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Whether directory frames may only be generated by `Synthesize`-capable agents.
+    pub fn require_synthesize(&self) -> bool {
+        self.require_synthesize
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn set_normalize_line_endings(&self, normalize: bool) {
+        self.normalize_line_endings
+            .store(normalize, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Content normalization applied when hashing workspace files. Defaults to exact bytes.
+    pub fn content_normalization(&self) -> crate::tree::hasher::ContentNormalization {
+        crate::tree::hasher::ContentNormalization::from_line_endings_flag(
+            self.normalize_line_endings
+                .load(std::sync::atomic::Ordering::Relaxed),
+        )
+    }
+
+    pub fn set_content_read_options(&self, options: crate::tree::hasher::ContentReadOptions) {
+        *self.content_read_options.write() = options;
+    }
+
+    /// File read settings for content hashing. Defaults to 64 KiB reads and mapping from 16 MiB.
+    pub fn content_read_options(&self) -> crate::tree::hasher::ContentReadOptions {
+        *self.content_read_options.read()
+    }
+
     /// Persist indices to disk if workspace root is configured
     fn persist_indices(&self) -> Result<(), ApiError> {
         if let Some(ref workspace_root) = self.workspace_root {
@@ -250,6 +322,28 @@ impl ContextApi {
         })
     }
 
+    /// Count the node's head frames that pass the view's filters, without returning them.
+    ///
+    /// Frame bodies are never formatted or loaded a second time, and `max_frames` does not
+    /// cap the count. Deleted frames are counted only with `include_deleted`.
+    pub fn count_node_frames(
+        &self,
+        node_id: NodeID,
+        view: ContextView,
+        include_deleted: bool,
+    ) -> Result<usize, ApiError> {
+        let frame_ids = self.current_frame_heads_for_node(&node_id)?;
+        let view_policy: ViewPolicy = view.into();
+        count_frames_query(
+            self.node_store.as_ref(),
+            &frame_ids,
+            node_id,
+            &view_policy,
+            include_deleted,
+            |frame_id| self.frame_storage.get(frame_id).map_err(ApiError::from),
+        )
+    }
+
     /// Put frame: Append new frame to node's frame set
     ///
     /// Creates a new frame and appends it to the node's frame set.
@@ -268,6 +362,24 @@ impl ContextApi {
     /// * Append-only: Creates new frame, never mutates existing
     /// * Atomic: Frame creation and head update are transactional
     /// * Deterministic: Same inputs → same FrameID
+    pub fn put_frame(
+        &self,
+        node_id: NodeID,
+        frame: Frame,
+        agent_id: String,
+    ) -> Result<FrameID, ApiError> {
+        self.put_frame_checked(node_id, frame, agent_id, None, HeadConflictPolicy::LastWins)
+    }
+
+    /// [`ContextApi::put_frame`] with a head conflict check.
+    ///
+    /// `expected_head` is the head the writer saw before it built the frame. Under the node
+    /// lock, a head that moved away from it is handled per `on_conflict`: `LastWins` replaces
+    /// it, `Fail` returns [`ApiError::HeadConflict`] with the head untouched, and `KeepBoth`
+    /// rebuilds a node-based frame with the displaced head as its basis frame before making
+    /// it the head, so the displaced frame stays reachable through lineage. The returned
+    /// FrameID is the one that was written, which differs from `frame.frame_id` after a
+    /// `KeepBoth` rebuild.
     #[instrument(
         skip(self, frame, agent_id),
         fields(
@@ -278,11 +390,13 @@ impl ContextApi {
             content_bytes = frame.content.len()
         )
     )]
-    pub fn put_frame(
+    pub fn put_frame_checked(
         &self,
         node_id: NodeID,
         frame: Frame,
         agent_id: String,
+        expected_head: Option<FrameID>,
+        on_conflict: HeadConflictPolicy,
     ) -> Result<FrameID, ApiError> {
         let start = Instant::now();
         debug!("Creating frame");
@@ -297,15 +411,21 @@ impl ContextApi {
         agent.verify_write()?;
 
         // Verify node exists and is not tombstoned
-        let _node_record = self
+        let node_record = self
             .node_store
             .get(&node_id)
             .map_err(ApiError::from)?
             .ok_or(ApiError::NodeNotFound(node_id))?;
-        if _node_record.tombstoned_at.is_some() {
+        if node_record.tombstoned_at.is_some() {
             return Err(ApiError::NodeNotFound(node_id));
         }
 
+        if self.require_synthesize()
+            && matches!(node_record.node_type, crate::store::NodeType::Directory)
+        {
+            agent.verify_synthesize()?;
+        }
+
         // Verify frame basis matches node_id (if basis is Node-based)
         match &frame.basis {
             crate::context::frame::Basis::Node(basis_node_id) => {
@@ -359,6 +479,18 @@ impl ContextApi {
             (previous_head, previous_metadata)
         };
 
+        let (frame, expected_prior) = match on_conflict {
+            HeadConflictPolicy::LastWins => (frame, None),
+            HeadConflictPolicy::Fail => (frame, Some(expected_head)),
+            HeadConflictPolicy::KeepBoth if previous_head == expected_head => {
+                (frame, Some(expected_head))
+            }
+            HeadConflictPolicy::KeepBoth => (
+                link_displaced_head(frame, previous_head)?,
+                Some(previous_head),
+            ),
+        };
+
         // Shared frame metadata write contract boundary.
         validate_frame_metadata(FrameMetadataValidationInput {
             metadata: &frame.metadata,
@@ -368,25 +500,16 @@ impl ContextApi {
 
         let session_id = self.context_write_session_id()?;
 
-        // Store frame
-        self.frame_storage.store(&frame).map_err(ApiError::from)?;
-
         // Update frame set (get or create)
         // TODO: In a full implementation, we'd retrieve the FrameMerkleSet from storage
         // and update it. For Phase 2B MVP, we'll track frame sets in memory.
         // For now, we'll just update the head index.
 
-        // Update head index.
-        {
-            let mut head_index = self.head_index.write();
-            head_index
-                .update_head(&node_id, &frame.frame_type, &frame.frame_id)
-                .map_err(ApiError::from)?;
+        self.commit_frame_and_head(node_id, &frame, expected_prior)?;
+        if node_record.is_head_stale(&frame.frame_type) {
+            self.clear_stale_head(node_id, &frame.frame_type)?;
         }
 
-        // Persist indices to disk
-        self.persist_indices()?;
-
         // TODO: Update node record's frame_set_root
         // This requires retrieving/updating the FrameMerkleSet and storing it.
         // For Phase 2B MVP, we'll skip this and rely on head index.
@@ -417,6 +540,401 @@ impl ContextApi {
         Ok(frame.frame_id)
     }
 
+    /// Two-phase write of a frame and its head. Caller holds the node write lock.
+    ///
+    /// Ordering guarantee: the frame blob is stored and fsynced before the head index is
+    /// updated and persisted. A crash between the two phases leaves an orphan frame with no
+    /// head, which is harmless and left for garbage collection; it never leaves a persisted
+    /// head pointing at a frame that was not durably stored. Heads that dangle anyway (older
+    /// writers, manual deletion) are dropped by [`ContextApi::recover_heads`] on startup.
+    ///
+    /// With `expected_prior` set, phase 2 is a compare-and-swap against that head (`Some(None)`:
+    /// no active head); a mismatch fails with [`ApiError::HeadConflict`] after phase 1, so the
+    /// frame is stored but not made head.
+    fn commit_frame_and_head(
+        &self,
+        node_id: NodeID,
+        frame: &Frame,
+        expected_prior: Option<Option<FrameID>>,
+    ) -> Result<(), ApiError> {
+        // Phase 1: durable frame blob.
+        self.frame_storage.store(frame).map_err(ApiError::from)?;
+        self.frame_storage
+            .sync(&frame.frame_id)
+            .map_err(ApiError::from)?;
+
+        // Phase 2: head update, persisted with fsync.
+        {
+            let mut head_index = self.head_index.write();
+            match expected_prior {
+                None => head_index
+                    .update_head(&node_id, &frame.frame_type, &frame.frame_id)
+                    .map_err(ApiError::from)?,
+                Some(expected) => {
+                    let swapped = head_index
+                        .update_head_if(
+                            &node_id,
+                            &frame.frame_type,
+                            &frame.frame_id,
+                            expected.as_ref(),
+                        )
+                        .map_err(ApiError::from)?;
+                    if !swapped {
+                        let current = head_index
+                            .get_head(&node_id, &frame.frame_type)
+                            .map_err(ApiError::from)?;
+                        let describe = |head: Option<FrameID>| {
+                            head.map(hex::encode).unwrap_or_else(|| "none".to_string())
+                        };
+                        return Err(ApiError::HeadConflict {
+                            node_id: hex::encode(node_id),
+                            frame_type: frame.frame_type.clone(),
+                            expected: describe(expected),
+                            current: describe(current),
+                            frame_id: hex::encode(frame.frame_id),
+                        });
+                    }
+                }
+            }
+        }
+        self.persist_indices()
+    }
+
+    /// Reconcile the head index with frame storage after an unclean shutdown.
+    ///
+    /// Run on startup. Heads pointing at a missing frame blob are dropped and the index is
+    /// persisted. Orphan frames without a head are left in place for garbage collection. A
+    /// frame that cannot be checked fails the call and no head is dropped.
+    pub fn recover_heads(&self) -> Result<HeadRecoveryResult, ApiError> {
+        let mut result = HeadRecoveryResult {
+            heads_checked: self.head_index.read().heads.len() as u64,
+            ..HeadRecoveryResult::default()
+        };
+        let dangling = self.dangling_heads()?;
+        if dangling.is_empty() {
+            return Ok(result);
+        }
+        for entry in self.remove_head_entries(&dangling)? {
+            result.dangling_heads_dropped += 1;
+            warn!(
+                node_id = %hex::encode(entry.node_id),
+                frame_type = %entry.frame_type,
+                frame_id = %hex::encode(entry.frame_id),
+                "Dropped head pointing at missing frame"
+            );
+        }
+        Ok(result)
+    }
+
+    /// Head entries, live or tombstoned, whose frame blob is missing from frame storage.
+    ///
+    /// A frame that cannot be checked (permissions, unreadable storage) fails the call rather
+    /// than counting as missing, so callers never drop heads over a transient I/O error.
+    pub fn dangling_heads(&self) -> Result<Vec<LegacyHeadEntry>, ApiError> {
+        let entries = self.head_index.read().entries();
+        let mut dangling = Vec::new();
+        for entry in entries {
+            if !self
+                .frame_storage
+                .exists(&entry.frame_id)
+                .map_err(ApiError::from)?
+            {
+                dangling.push(entry);
+            }
+        }
+        Ok(dangling)
+    }
+
+    /// Remove head entries outright and persist the index. Frame blobs are not affected.
+    ///
+    /// Entries whose head moved on to another frame since they were read are skipped; the
+    /// removed ones are returned.
+    pub fn remove_head_entries(
+        &self,
+        entries: &[LegacyHeadEntry],
+    ) -> Result<Vec<LegacyHeadEntry>, ApiError> {
+        let session_id = self.context_write_session_id()?;
+        let mut removed = Vec::new();
+        {
+            let mut head_index = self.head_index.write();
+            for entry in entries {
+                if head_index
+                    .heads
+                    .get(&(entry.node_id, entry.frame_type.clone()))
+                    .is_some_and(|current| current.frame_id == entry.frame_id)
+                {
+                    head_index.remove_head(&entry.node_id, &entry.frame_type);
+                    removed.push(entry.clone());
+                }
+            }
+        }
+        if removed.is_empty() {
+            return Ok(removed);
+        }
+        self.persist_indices()?;
+        for entry in &removed {
+            self.emit_context_envelope(head_tombstoned_envelope(
+                &session_id,
+                entry.node_id,
+                &entry.frame_type,
+                Some(entry.frame_id),
+            ));
+        }
+        Ok(removed)
+    }
+
+    /// Save the current head index as snapshot `name` under the workspace data directory.
+    ///
+    /// An existing snapshot of the same name is only replaced when `overwrite` is set.
+    pub fn snapshot_heads(
+        &self,
+        name: &str,
+        overwrite: bool,
+    ) -> Result<HeadSnapshotInfo, ApiError> {
+        let workspace_root = self.snapshot_workspace_root()?;
+        let path = head_snapshot::snapshot_path(workspace_root, name)?;
+        if path.exists() && !overwrite {
+            return Err(ApiError::ConfigError(format!(
+                "Head snapshot '{}' already exists. Pass --force to replace it.",
+                name
+            )));
+        }
+        let head_count = {
+            let head_index = self.head_index.read();
+            head_index.save_to_disk(&path).map_err(ApiError::from)?;
+            head_index.heads.len()
+        };
+        Ok(head_snapshot::snapshot_info(name, &path, head_count))
+    }
+
+    /// Replace the live head index with snapshot `name` in one swap and persist it.
+    ///
+    /// Frames are append-only, so every head the snapshot recorded still has its frame unless
+    /// compaction purged it; such heads are dropped. Each head that changes emits a
+    /// `head_selected` or `head_tombstoned` event so graph anchors follow the restored index.
+    pub fn restore_head_snapshot(&self, name: &str) -> Result<HeadRestoreResult, ApiError> {
+        let workspace_root = self.snapshot_workspace_root()?;
+        let snapshot = head_snapshot::load_snapshot(workspace_root, name)?;
+        let mut result = HeadRestoreResult {
+            snapshot: name.to_string(),
+            ..HeadRestoreResult::default()
+        };
+        let mut restored = HeadIndex::new();
+        for (key, entry) in snapshot.heads {
+            if self
+                .frame_storage
+                .exists(&entry.frame_id)
+                .map_err(ApiError::from)?
+            {
+                restored.heads.insert(key, entry);
+            } else {
+                result.dangling_heads_skipped += 1;
+            }
+        }
+
+        let session_id = self.context_write_session_id()?;
+        let (selected, removed) = {
+            let mut head_index = self.head_index.write();
+            let live = std::mem::replace(&mut *head_index, restored);
+            let live_active: HashMap<(NodeID, String), FrameID> = live
+                .active_entries()
+                .into_iter()
+                .map(|entry| ((entry.node_id, entry.frame_type), entry.frame_id))
+                .collect();
+            let restored_active: HashMap<(NodeID, String), FrameID> = head_index
+                .active_entries()
+                .into_iter()
+                .map(|entry| ((entry.node_id, entry.frame_type), entry.frame_id))
+                .collect();
+
+            let mut selected = Vec::new();
+            for (key, frame_id) in &restored_active {
+                let previous = live_active.get(key).copied();
+                if previous == Some(*frame_id) {
+                    result.heads_unchanged += 1;
+                } else {
+                    selected.push((key.clone(), *frame_id, previous));
+                }
+            }
+            let mut removed: Vec<((NodeID, String), FrameID)> = live_active
+                .into_iter()
+                .filter(|(key, _)| !restored_active.contains_key(key))
+                .collect();
+            selected.sort_by(|a, b| a.0.cmp(&b.0));
+            removed.sort_by(|a, b| a.0.cmp(&b.0));
+            (selected, removed)
+        };
+        self.persist_indices()?;
+
+        for ((node_id, frame_type), frame_id, previous) in selected {
+            self.emit_context_envelope_required(head_selected_envelope(
+                &session_id,
+                node_id,
+                &frame_type,
+                frame_id,
+                previous,
+            ))?;
+            result.heads_selected += 1;
+        }
+        for ((node_id, frame_type), previous) in removed {
+            self.emit_context_envelope_required(head_tombstoned_envelope(
+                &session_id,
+                node_id,
+                &frame_type,
+                Some(previous),
+            ))?;
+            result.heads_removed += 1;
+        }
+        info!(
+            snapshot = name,
+            heads_selected = result.heads_selected,
+            heads_removed = result.heads_removed,
+            "Restored head snapshot"
+        );
+        Ok(result)
+    }
+
+    /// Saved head snapshots of this workspace, ordered by name.
+    pub fn head_snapshots(&self) -> Result<Vec<HeadSnapshotInfo>, ApiError> {
+        head_snapshot::list_snapshots(self.snapshot_workspace_root()?)
+    }
+
+    /// Frames any saved head snapshot points at; none without a workspace root.
+    pub fn head_snapshot_frame_ids(&self) -> Result<HashSet<FrameID>, ApiError> {
+        match self.workspace_root.as_deref() {
+            Some(workspace_root) => head_snapshot::snapshot_frame_ids(workspace_root),
+            None => Ok(HashSet::new()),
+        }
+    }
+
+    fn snapshot_workspace_root(&self) -> Result<&Path, ApiError> {
+        self.workspace_root.as_deref().ok_or_else(|| {
+            ApiError::ConfigError("Head snapshots require a workspace root.".to_string())
+        })
+    }
+
+    /// Copy the head frame of `from_type` to a new frame of `to_type` on the same node.
+    ///
+    /// The copy keeps the content, basis, agent, and metadata; only the frame type changes,
+    /// so it gets a new FrameID. It is written through [`ContextApi::put_frame`] and becomes
+    /// the head for `to_type`. The source frame and its head are untouched.
+    pub fn copy_frame(
+        &self,
+        node_id: NodeID,
+        from_type: &str,
+        to_type: &str,
+    ) -> Result<FrameID, ApiError> {
+        if from_type == to_type {
+            return Err(ApiError::ConfigError(format!(
+                "Source and target frame type are both '{}'",
+                from_type
+            )));
+        }
+        let source_id = self.get_head(&node_id, from_type)?.ok_or_else(|| {
+            ApiError::ConfigError(format!(
+                "Node {} has no head frame of type '{}'",
+                hex::encode(node_id),
+                from_type
+            ))
+        })?;
+        let source = self
+            .frame_storage
+            .get(&source_id)
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::FrameNotFound(source_id))?;
+
+        let copy = Frame::new(
+            source.basis,
+            source.content,
+            to_type.to_string(),
+            source.agent_id.clone(),
+            source.metadata,
+        )
+        .map_err(ApiError::from)?;
+        self.put_frame(node_id, copy, source.agent_id)
+    }
+
+    /// Mark a node's head frames stale without changing them.
+    ///
+    /// Marks the head of `frame_type`, or every active head when `None`, in the node's
+    /// [`STALE_FRAME_TYPES_METADATA_KEY`] metadata. Generation regenerates marked heads even
+    /// without `--force`; the next head written for a type clears its mark. Returns the frame
+    /// types newly marked; types without a head are skipped.
+    pub fn mark_heads_stale(
+        &self,
+        node_id: NodeID,
+        frame_type: Option<&str>,
+    ) -> Result<Vec<String>, ApiError> {
+        let lock = self.lock_manager.get_lock(&node_id);
+        let _guard = lock.write();
+        let record = self
+            .node_store
+            .get(&node_id)
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::NodeNotFound(node_id))?;
+        if record.tombstoned_at.is_some() {
+            return Err(ApiError::NodeNotFound(node_id));
+        }
+        let head_types: Vec<String> = match frame_type {
+            Some(frame_type) => self
+                .get_head(&node_id, frame_type)?
+                .map(|_| vec![frame_type.to_string()])
+                .unwrap_or_default(),
+            None => self
+                .head_index
+                .read()
+                .entries_for_node(&node_id)
+                .into_iter()
+                .filter(|entry| entry.tombstoned_at.is_none())
+                .map(|entry| entry.frame_type)
+                .collect(),
+        };
+        let mut stale: Vec<String> = record
+            .stale_frame_types()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let mut marked = Vec::new();
+        for frame_type in head_types {
+            if !stale.contains(&frame_type) {
+                stale.push(frame_type.clone());
+                marked.push(frame_type);
+            }
+        }
+        if !marked.is_empty() {
+            stale.sort();
+            self.node_store
+                .merge_metadata(
+                    &node_id,
+                    HashMap::from([(STALE_FRAME_TYPES_METADATA_KEY.to_string(), stale.join(","))]),
+                )
+                .map_err(ApiError::from)?;
+        }
+        marked.sort();
+        Ok(marked)
+    }
+
+    /// Drop `frame_type` from the node's stale marks. Caller holds the node write lock.
+    fn clear_stale_head(&self, node_id: NodeID, frame_type: &str) -> Result<(), ApiError> {
+        let Some(mut record) = self.node_store.get(&node_id).map_err(ApiError::from)? else {
+            return Ok(());
+        };
+        let stale: Vec<String> = record
+            .stale_frame_types()
+            .into_iter()
+            .filter(|t| *t != frame_type)
+            .map(String::from)
+            .collect();
+        if stale.is_empty() {
+            record.metadata.remove(STALE_FRAME_TYPES_METADATA_KEY);
+        } else {
+            record
+                .metadata
+                .insert(STALE_FRAME_TYPES_METADATA_KEY.to_string(), stale.join(","));
+        }
+        self.node_store.put(&record).map_err(ApiError::from)
+    }
+
     /// Collect node_id and all descendant node IDs (BFS from record.children).
     pub fn collect_subtree_node_ids(&self, node_id: NodeID) -> Result<HashSet<NodeID>, ApiError> {
         let mut set = HashSet::new();
@@ -446,6 +964,23 @@ impl ContextApi {
     /// Tombstone a node and all descendants. Marks records in node store and head index.
     /// Frame blobs are not affected.
     pub fn tombstone_node(&self, node_id: NodeID) -> Result<TombstoneResult, ApiError> {
+        self.tombstone_subtree(node_id, false)
+    }
+
+    /// Tombstone a node and all descendants but keep their head frames retrievable.
+    ///
+    /// Node records are tombstoned and marked with [`KEEP_FRAMES_METADATA_KEY`]; head entries
+    /// stay active, so `get_node` by NodeID still returns the frames. `compact` skips marked
+    /// nodes unless asked to purge kept frames.
+    pub fn tombstone_node_keep_frames(&self, node_id: NodeID) -> Result<TombstoneResult, ApiError> {
+        self.tombstone_subtree(node_id, true)
+    }
+
+    fn tombstone_subtree(
+        &self,
+        node_id: NodeID,
+        keep_frames: bool,
+    ) -> Result<TombstoneResult, ApiError> {
         let record = self
             .node_store
             .get(&node_id)
@@ -458,6 +993,23 @@ impl ContextApi {
             });
         }
         let to_tombstone = self.collect_subtree_node_ids(node_id)?;
+        if keep_frames {
+            let mut nodes_tombstoned = 0u64;
+            for &nid in &to_tombstone {
+                self.node_store.tombstone(&nid).map_err(ApiError::from)?;
+                self.node_store
+                    .merge_metadata(
+                        &nid,
+                        HashMap::from([(KEEP_FRAMES_METADATA_KEY.to_string(), "true".to_string())]),
+                    )
+                    .map_err(ApiError::from)?;
+                nodes_tombstoned += 1;
+            }
+            return Ok(TombstoneResult {
+                nodes_tombstoned,
+                head_entries_tombstoned: 0,
+            });
+        }
         let head_entries = {
             let head_index = self.head_index.read();
             head_index
@@ -544,7 +1096,10 @@ impl ContextApi {
         let session_id = self.context_write_session_id()?;
         let mut nodes_restored = 0u64;
         for &nid in &to_restore {
-            self.node_store.restore(&nid).map_err(ApiError::from)?;
+            let mut restored = self.node_store.restore(&nid).map_err(ApiError::from)?;
+            if restored.metadata.remove(KEEP_FRAMES_METADATA_KEY).is_some() {
+                self.node_store.put(&restored).map_err(ApiError::from)?;
+            }
             nodes_restored += 1;
             let mut head_index = self.head_index.write();
             head_index.restore_heads_for_node(&nid);
@@ -565,20 +1120,143 @@ impl ContextApi {
         })
     }
 
+    /// Rebase heads recorded under a node's legacy NodeID (see [`crate::tree::legacy`]) onto
+    /// its current NodeID. `legacy_ids` maps current IDs to legacy ones; nodes that already
+    /// have heads are left alone.
+    ///
+    /// Each head frame is rewritten with the current NodeID as its basis node, keeping its
+    /// content, metadata, and timestamp, and made the head of the current node. The legacy
+    /// frames stay in storage. Returns the number of heads rebased.
+    pub fn carry_heads_forward(
+        &self,
+        legacy_ids: &HashMap<NodeID, NodeID>,
+    ) -> Result<u64, ApiError> {
+        let current_ids: HashMap<NodeID, NodeID> = legacy_ids
+            .iter()
+            .filter(|(node_id, legacy_id)| node_id != legacy_id)
+            .map(|(node_id, legacy_id)| (*legacy_id, *node_id))
+            .collect();
+        let carried = {
+            let head_index = self.head_index.read();
+            let entries = head_index.active_entries();
+            let with_heads: HashSet<NodeID> = entries.iter().map(|entry| entry.node_id).collect();
+            entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let node_id = *current_ids.get(&entry.node_id)?;
+                    (!with_heads.contains(&node_id)).then_some((node_id, entry))
+                })
+                .collect::<Vec<_>>()
+        };
+        if carried.is_empty() {
+            return Ok(0);
+        }
+
+        let session_id = self.context_write_session_id()?;
+        let mut rebased = 0u64;
+        for (node_id, entry) in carried {
+            let Some(legacy_frame) = self
+                .frame_storage
+                .get(&entry.frame_id)
+                .map_err(ApiError::from)?
+            else {
+                continue;
+            };
+            let basis = match legacy_frame.basis {
+                Basis::Node(_) => Basis::Node(node_id),
+                Basis::Both { frame, .. } => Basis::Both {
+                    node: node_id,
+                    frame,
+                },
+                Basis::Frame(frame) => Basis::Frame(frame),
+            };
+            let mut frame = Frame::new(
+                basis,
+                legacy_frame.content,
+                legacy_frame.frame_type,
+                legacy_frame.agent_id,
+                legacy_frame.metadata,
+            )
+            .map_err(ApiError::from)?;
+            frame.timestamp = legacy_frame.timestamp;
+
+            let lock = self.lock_manager.get_lock(&node_id);
+            let _guard = lock.write();
+            match self.commit_frame_and_head(node_id, &frame, Some(None)) {
+                Ok(()) => {}
+                Err(ApiError::HeadConflict { .. }) => continue,
+                Err(err) => return Err(err),
+            }
+            self.emit_context_envelope_required(frame_added_envelope(
+                &session_id,
+                node_id,
+                &frame.basis,
+                frame.frame_id,
+                &frame.frame_type,
+                &frame.agent_id,
+            ))?;
+            self.emit_context_envelope_required(head_selected_envelope(
+                &session_id,
+                node_id,
+                &frame.frame_type,
+                frame.frame_id,
+                None,
+            ))?;
+            rebased += 1;
+        }
+        info!(heads = rebased, "Rebased frame heads from legacy NodeIDs");
+        Ok(rebased)
+    }
+
+    /// Tombstoned nodes at or before `cutoff` that compaction may purge.
+    ///
+    /// Nodes deleted with kept frames are excluded unless `purge_kept` is set.
+    pub fn compactable_tombstoned(
+        &self,
+        cutoff: u64,
+        purge_kept: bool,
+    ) -> Result<Vec<NodeID>, ApiError> {
+        let node_ids = self
+            .node_store
+            .list_tombstoned(Some(cutoff))
+            .map_err(ApiError::from)?;
+        if purge_kept {
+            return Ok(node_ids);
+        }
+        let mut compactable = Vec::with_capacity(node_ids.len());
+        for nid in node_ids {
+            let kept = self
+                .node_store
+                .get(&nid)
+                .map_err(ApiError::from)?
+                .is_some_and(|record| record.keeps_frames());
+            if !kept {
+                compactable.push(nid);
+            }
+        }
+        Ok(compactable)
+    }
+
     /// Compact tombstoned records older than TTL. Optionally purge frame blobs.
-    pub fn compact(&self, ttl_seconds: u64, purge_frames: bool) -> Result<CompactResult, ApiError> {
+    ///
+    /// Nodes deleted with kept frames are skipped, heads and frames included, unless
+    /// `purge_kept` is set; then their still-active head entries are removed as well.
+    pub fn compact(
+        &self,
+        ttl_seconds: u64,
+        purge_frames: bool,
+        purge_kept: bool,
+    ) -> Result<CompactResult, ApiError> {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map_err(|e| ApiError::ConfigError(e.to_string()))?
             .as_secs();
         let cutoff = now.saturating_sub(ttl_seconds);
-        let node_ids = self
-            .node_store
-            .list_tombstoned(Some(cutoff))
-            .map_err(ApiError::from)?;
+        let node_ids = self.compactable_tombstoned(cutoff, purge_kept)?;
         let mut nodes_purged = 0u64;
         let mut frames_purged = 0u64;
         let mut artifacts_purged = 0u64;
+        let mut kept_heads_purged = 0u64;
         for &nid in &node_ids {
             if purge_frames {
                 let frame_ids = self.head_index.read().get_all_heads_for_node(&nid);
@@ -593,11 +1271,20 @@ impl ContextApi {
                 .purge(&nid, cutoff)
                 .map_err(ApiError::from)?;
             nodes_purged += 1;
+            if purge_kept {
+                let mut head_index = self.head_index.write();
+                for entry in head_index.entries_for_node(&nid) {
+                    if entry.tombstoned_at.is_none() {
+                        head_index.remove_head(&nid, &entry.frame_type);
+                        kept_heads_purged += 1;
+                    }
+                }
+            }
         }
         let head_before = self.head_index.read().heads.len();
         self.head_index.write().purge_tombstoned(cutoff);
         let head_after = self.head_index.read().heads.len();
-        let head_entries_purged = (head_before - head_after) as u64;
+        let head_entries_purged = (head_before - head_after) as u64 + kept_heads_purged;
         if purge_frames {
             artifacts_purged = self.prompt_context_storage.purge_older_than(cutoff)?;
         }
@@ -895,6 +1582,11 @@ impl ContextApi {
         &self.head_index
     }
 
+    /// Pooled HTTP clients for provider calls, shared by queue workers.
+    pub fn provider_clients(&self) -> &crate::provider::ProviderHttpClientPool {
+        &self.provider_clients
+    }
+
     fn current_progress_context(&self) -> Option<ProgressEmitterContext> {
         self.progress_context.read().clone()
     }
@@ -1007,6 +1699,25 @@ impl CurrentFrameHeadRead for ContextApi {
     }
 }
 
+/// Rebuild a node-based frame with the head it displaces as its basis frame, so the displaced
+/// frame stays reachable after a `keep-both` head conflict. Other bases are left as they are.
+fn link_displaced_head(frame: Frame, displaced: Option<FrameID>) -> Result<Frame, ApiError> {
+    let (Some(displaced), Basis::Node(node)) = (displaced, frame.basis.clone()) else {
+        return Ok(frame);
+    };
+    Frame::new(
+        Basis::Both {
+            node,
+            frame: displaced,
+        },
+        frame.content,
+        frame.frame_type,
+        frame.agent_id,
+        frame.metadata,
+    )
+    .map_err(ApiError::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1733,13 +2444,15 @@ mod tests {
             .recent()
             .by_type("analysis")
             .by_agent("agent-1")
+            .by_metadata(HashMap::from([("model".to_string(), "m1".to_string())]))
             .build();
 
         assert_eq!(view.max_frames, 50);
         assert_eq!(view.ordering, OrderingPolicy::Recency);
-        assert_eq!(view.filters.len(), 2);
+        assert_eq!(view.filters.len(), 3);
         assert!(matches!(view.filters[0], FrameFilter::ByType(_)));
         assert!(matches!(view.filters[1], FrameFilter::ByAgent(_)));
+        assert!(matches!(view.filters[2], FrameFilter::Metadata(_)));
     }
 
     #[test]
@@ -3,7 +3,7 @@
 //! Command-line interface for the Meld filesystem state management system.
 
 use clap::Parser;
-use meld::cli::{Cli, Commands, DangerCommands, RunContext};
+use meld::cli::{Cli, Commands, DangerCommands, RunContext, WorkspaceCommands};
 use meld::config::ConfigLoader;
 use meld::logging::{init_logging, LoggingConfig};
 use std::path::{Path, PathBuf};
@@ -57,10 +57,18 @@ fn main() {
     }
 
     // Create CLI context
-    let context = match RunContext::new(cli.workspace.clone(), cli.config.clone()) {
+    let context = match RunContext::new_with_profile(
+        cli.workspace.clone(),
+        cli.config.clone(),
+        cli.config_override.clone(),
+        cli.profile.clone(),
+    ) {
         Ok(ctx) => {
             info!("CLI context initialized");
-            ctx
+            ctx.json_compact(cli.json_compact)
+                .color(&cli.color)
+                .progress_file(cli.progress_file.clone())
+                .summary_only(cli.summary_only.clone())
         }
         Err(e) => {
             error!("Error initializing workspace: {}", e);
@@ -69,11 +77,37 @@ fn main() {
         }
     };
 
+    if matches!(cli.command, Commands::Repl) {
+        let stdin = std::io::stdin();
+        if let Err(e) = meld::cli::run_repl(&context, stdin.lock(), &mut std::io::stdout()) {
+            error!("Repl failed: {}", e);
+            eprintln!("{}", meld::cli::map_error(&e));
+            process::exit(1);
+        }
+        return;
+    }
+
     // Execute command
-    match context.execute(&cli.command) {
+    let result = if let Some(compact) = compact_before_vacuum(&cli.command) {
+        // The vacuum replaces the node store on disk, so it runs once the context has
+        // released it.
+        let result = context.execute(&compact);
+        let store_path = context.store_path().to_path_buf();
+        drop(context);
+        result.and_then(|output| {
+            meld::workspace::WorkspaceCommandService::vacuum_node_store(&store_path)
+                .map(|report| format!("{} {}", output, report))
+        })
+    } else {
+        context.execute(&cli.command)
+    };
+    match result {
         Ok(output) => {
             info!("Command completed successfully");
-            println!("{}", output);
+            // Streaming output (e.g. `context get --format ndjson`) is already written.
+            if !output.is_empty() {
+                println!("{}", output);
+            }
         }
         Err(e) => {
             error!("Command failed: {}", e);
@@ -101,6 +135,7 @@ fn try_execute_danger_command(cli: &Cli) -> Option<Result<String, meld::error::A
             Some(meld::workspace::WorkspaceDangerService::flush(
                 &workspace_root,
                 cli.config.as_deref(),
+                cli.profile.as_deref(),
                 *dry_run,
                 *yes,
             ))
@@ -112,15 +147,46 @@ fn try_execute_danger_command(cli: &Cli) -> Option<Result<String, meld::error::A
 fn try_execute_branch_command(cli: &Cli) -> Option<Result<String, meld::error::ApiError>> {
     match &cli.command {
         Commands::Branches { command } => {
-            Some(meld::branches::tooling::handle_cli_command_with_workspace(
-                command,
-                Some(cli.workspace.as_path()),
-            ))
+            Some(meld::cli::with_json_compact(cli.json_compact, || {
+                meld::cli::with_color(meld::cli::resolve_color(&cli.color), || {
+                    meld::branches::tooling::handle_cli_command_with_workspace(
+                        command,
+                        Some(cli.workspace.as_path()),
+                    )
+                })
+            }))
         }
         _ => None,
     }
 }
 
+/// For `workspace compact --vacuum`, the compaction to run before the store is vacuumed.
+fn compact_before_vacuum(command: &Commands) -> Option<Commands> {
+    match command {
+        Commands::Workspace {
+            command:
+                WorkspaceCommands::Compact {
+                    ttl,
+                    all,
+                    keep_frames,
+                    purge_kept,
+                    dry_run,
+                    vacuum: true,
+                },
+        } => Some(Commands::Workspace {
+            command: WorkspaceCommands::Compact {
+                ttl: *ttl,
+                all: *all,
+                keep_frames: *keep_frames,
+                purge_kept: *purge_kept,
+                dry_run: *dry_run,
+                vacuum: false,
+            },
+        }),
+        _ => None,
+    }
+}
+
 fn danger_workspace_override(cli: &Cli) -> Option<PathBuf> {
     match &cli.command {
         Commands::Danger {
@@ -136,19 +202,18 @@ fn danger_workspace_override(cli: &Cli) -> Option<PathBuf> {
 }
 
 /// Build logging configuration from CLI args, environment, and config file.
-/// Precedence: CLI flags override config file override defaults.
+/// Precedence: CLI flags override `--config-override` entries override the active profile
+/// override config file override defaults.
 fn build_logging_config(cli: &Cli, logging_workspace: &Path) -> LoggingConfig {
-    let mut config = if let Some(ref config_path) = cli.config {
-        ConfigLoader::load_from_file(config_path)
-            .ok()
-            .map(|c| c.logging)
-            .unwrap_or_default()
-    } else {
-        ConfigLoader::load(logging_workspace)
-            .ok()
-            .map(|c| c.logging)
-            .unwrap_or_default()
-    };
+    let mut config = ConfigLoader::load_with_profile(
+        logging_workspace,
+        cli.config.as_deref(),
+        &cli.config_override,
+        cli.profile.as_deref(),
+    )
+    .ok()
+    .map(|c| c.logging)
+    .unwrap_or_default();
 
     if cli.quiet {
         config.enabled = false;
@@ -247,4 +312,22 @@ mod tests {
             "explicit --log-output should win over verbose defaults"
         );
     }
+
+    #[test]
+    fn test_build_logging_config_applies_config_override() {
+        let temp = tempfile::tempdir().unwrap();
+        let ws = temp.path().to_string_lossy();
+        let cli = Cli::try_parse_from([
+            "meld",
+            "--workspace",
+            ws.as_ref(),
+            "status",
+            "--config-override",
+            "logging.level=warn",
+        ])
+        .unwrap();
+        assert_eq!(cli.config_override, vec!["logging.level=warn".to_string()]);
+        let config = build_logging_config(&cli, temp.path());
+        assert_eq!(config.level, "warn");
+    }
 }
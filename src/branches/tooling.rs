@@ -6,7 +6,7 @@ use crate::branches::format::{
 };
 use crate::branches::query::BranchQueryScope;
 use crate::branches::{BranchQueryRuntime, BranchRuntime};
-use crate::cli::BranchesCommands;
+use crate::cli::{to_json_output, BranchesCommands};
 use crate::error::{ApiError, StorageError};
 use crate::events::DomainObjectRef;
 use crate::world_state::{GraphWalkSpec, TraversalDirection};
@@ -108,7 +108,7 @@ where
     F: FnOnce(&T) -> String,
 {
     if format == "json" {
-        serde_json::to_string_pretty(output)
+        to_json_output(output)
             .map_err(|err| ApiError::StorageError(StorageError::InvalidPath(err.to_string())))
     } else {
         Ok(render_text(output))
@@ -6,24 +6,39 @@ mod output;
 mod parse;
 mod presentation;
 mod progress;
+mod repl;
 mod route;
 mod runtime_assembly;
 mod session;
+mod summary_line;
 
 pub use help::{command_name, typed_summary_event};
-pub use output::map_error;
+pub use output::{
+    color_enabled, fail_mark, map_error, pass_mark, resolve_color, styled_table, to_json_output,
+    with_color, with_json_compact,
+};
 pub use parse::{
     parse_provider_additional_json_file, AgentCommands, AgentPromptCommands, BranchesCommands, Cli,
-    Commands, ContextCommands, DangerCommands, ProviderCommands, WorkflowCommands,
-    WorkspaceCommands,
+    Commands, ContextCommands, DangerCommands, FrameCommands, GenerateArgs, HeadsCommands,
+    ProviderCommands, TelemetryCommands, TreeCommands, WorkflowCommands, WorkspaceCommands,
 };
 pub use presentation::{
-    format_agent_list_result_json, format_agent_list_result_text, format_agent_show_result_json,
-    format_agent_show_result_text, format_context_json_output, format_context_text_output,
-    format_ignore_result, format_init_preview, format_init_summary, format_list_deleted_result,
+    format_agent_effective_json, format_agent_effective_text, format_agent_list_result_json,
+    format_agent_list_result_text, format_agent_show_result_json, format_agent_show_result_text,
+    format_context_count_output, format_context_json_output, format_context_ndjson_line,
+    format_context_search_hit, format_context_size_output, format_context_text_output,
+    format_frame_show_json, format_frame_show_text, format_head_restore_json,
+    format_head_restore_result, format_head_snapshot_result, format_head_snapshots_json,
+    format_head_snapshots_text, format_ignore_result, format_init_check, format_init_preview,
+    format_init_summary, format_init_templates, format_list_deleted_result, format_node_tree,
     format_provider_list_result_json, format_provider_list_result_text,
-    format_provider_show_result_json, format_provider_show_result_text,
-    format_provider_test_result, format_provider_validation_result, format_validate_result_text,
-    format_validation_result, format_validation_results_all,
+    format_provider_prompt_test_result, format_provider_show_result_json,
+    format_provider_show_result_text, format_provider_test_result,
+    format_provider_validation_result, format_provider_validation_results_all,
+    format_repair_report_text, format_scan_stats, format_telemetry_export_result,
+    format_telemetry_sessions_json, format_telemetry_sessions_text, format_validate_result_text,
+    format_validation_result, format_validation_results_all, format_workspace_size_result,
+    ContextViewOptions,
 };
+pub use repl::{run_repl, ReplOutcome, ReplSession};
 pub use route::RunContext;
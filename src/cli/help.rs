@@ -2,7 +2,8 @@
 
 use crate::cli::parse::{
     AgentCommands, AgentPromptCommands, BranchesCommands, Commands, ContextCommands,
-    DangerCommands, ProviderCommands, WorkflowCommands, WorkspaceCommands,
+    DangerCommands, FrameCommands, HeadsCommands, ProviderCommands, TelemetryCommands,
+    TreeCommands, WorkflowCommands, WorkspaceCommands,
 };
 use crate::telemetry::summary::TypedSummaryEvent;
 
@@ -13,7 +14,13 @@ pub fn command_name(command: &Commands) -> String {
         Commands::Workspace { command } => format!("workspace.{}", workspace_command_name(command)),
         Commands::Status { .. } => "status".to_string(),
         Commands::Validate => "validate".to_string(),
+        Commands::Tree {
+            command: Some(TreeCommands::VerifyDeterminism),
+            ..
+        } => "tree.verify_determinism".to_string(),
+        Commands::Tree { command: None, .. } => "tree".to_string(),
         Commands::Watch { .. } => "watch".to_string(),
+        Commands::Repl => "repl".to_string(),
         Commands::Agent { command } => format!("agent.{}", agent_command_name(command)),
         Commands::Provider { command } => format!("provider.{}", provider_command_name(command)),
         Commands::Init { .. } => "init".to_string(),
@@ -21,6 +28,32 @@ pub fn command_name(command: &Commands) -> String {
         Commands::Workflow { command } => format!("workflow.{}", workflow_command_name(command)),
         Commands::Branches { command } => format!("branches.{}", branches_command_name(command)),
         Commands::Danger { command } => format!("danger.{}", danger_command_name(command)),
+        Commands::Telemetry { command } => {
+            format!("telemetry.{}", telemetry_command_name(command))
+        }
+        Commands::Frame { command } => format!("frame.{}", frame_command_name(command)),
+        Commands::Heads { command } => format!("heads.{}", heads_command_name(command)),
+    }
+}
+
+pub fn heads_command_name(command: &HeadsCommands) -> &'static str {
+    match command {
+        HeadsCommands::Snapshot { .. } => "snapshot",
+        HeadsCommands::Restore { .. } => "restore",
+        HeadsCommands::ListSnapshots { .. } => "list_snapshots",
+    }
+}
+
+pub fn frame_command_name(command: &FrameCommands) -> &'static str {
+    match command {
+        FrameCommands::Show { .. } => "show",
+    }
+}
+
+pub fn telemetry_command_name(command: &TelemetryCommands) -> &'static str {
+    match command {
+        TelemetryCommands::ListSessions { .. } => "list_sessions",
+        TelemetryCommands::Export { .. } => "export",
     }
 }
 
@@ -51,14 +84,19 @@ pub fn workspace_command_name(command: &WorkspaceCommands) -> &'static str {
         WorkspaceCommands::Restore { .. } => "restore",
         WorkspaceCommands::Compact { .. } => "compact",
         WorkspaceCommands::ListDeleted { .. } => "list_deleted",
+        WorkspaceCommands::Size { .. } => "size",
     }
 }
 
 pub fn context_command_name(command: &ContextCommands) -> &'static str {
     match command {
-        ContextCommands::Generate { .. } => "generate",
+        ContextCommands::Generate(_) => "generate",
         ContextCommands::Regenerate { .. } => "regenerate",
         ContextCommands::Get { .. } => "get",
+        ContextCommands::Import { .. } => "import",
+        ContextCommands::Copy { .. } => "copy",
+        ContextCommands::Touch { .. } => "touch",
+        ContextCommands::Search { .. } => "search",
     }
 }
 
@@ -109,10 +147,10 @@ pub fn typed_summary_event(
 ) -> Option<TypedSummaryEvent> {
     match command {
         Commands::Workspace { command } => Some(match command {
-            WorkspaceCommands::Status { format, breakdown } => {
-                crate::workspace::summary::status(format, *breakdown, ok, duration_ms, error)
-            }
-            WorkspaceCommands::Validate { format } => {
+            WorkspaceCommands::Status {
+                format, breakdown, ..
+            } => crate::workspace::summary::status(format, *breakdown, ok, duration_ms, error),
+            WorkspaceCommands::Validate { format, .. } => {
                 crate::workspace::summary::validate(format, ok, duration_ms, error)
             }
             WorkspaceCommands::Delete {
@@ -120,11 +158,13 @@ pub fn typed_summary_event(
                 node,
                 dry_run,
                 no_ignore,
+                keep_frames,
             } => crate::workspace::summary::delete(
                 path.is_some(),
                 node.is_some(),
                 *dry_run,
                 *no_ignore,
+                *keep_frames,
                 ok,
                 duration_ms,
                 error,
@@ -132,10 +172,16 @@ pub fn typed_summary_event(
             WorkspaceCommands::Restore {
                 path,
                 node,
+                all,
+                older_than,
+                newer_than,
                 dry_run,
             } => crate::workspace::summary::restore(
                 path.is_some(),
                 node.is_some(),
+                *all,
+                *older_than,
+                *newer_than,
                 *dry_run,
                 ok,
                 duration_ms,
@@ -145,19 +191,38 @@ pub fn typed_summary_event(
                 ttl,
                 all,
                 keep_frames,
+                purge_kept,
                 dry_run,
+                vacuum,
             } => crate::workspace::summary::compact(
                 *ttl,
                 *all,
                 *keep_frames,
+                *purge_kept,
                 *dry_run,
+                *vacuum,
                 ok,
                 duration_ms,
                 error,
             ),
-            WorkspaceCommands::ListDeleted { older_than, format } => {
-                crate::workspace::summary::list_deleted(*older_than, format, ok, duration_ms, error)
-            }
+            WorkspaceCommands::ListDeleted {
+                older_than,
+                sort,
+                reverse,
+                format,
+            } => crate::workspace::summary::list_deleted(
+                *older_than,
+                sort.as_deref(),
+                *reverse,
+                format,
+                ok,
+                duration_ms,
+                error,
+            ),
+            WorkspaceCommands::Size {
+                by_frame_type,
+                format,
+            } => crate::workspace::summary::size(*by_frame_type, format, ok, duration_ms, error),
             WorkspaceCommands::Ignore {
                 path,
                 dry_run,
@@ -178,6 +243,7 @@ pub fn typed_summary_event(
             providers_only,
             breakdown,
             test_connectivity,
+            ..
         } => {
             let include_all = !*workspace_only && !*agents_only && !*providers_only;
             Some(crate::workspace::summary::unified_status(
@@ -197,6 +263,29 @@ pub fn typed_summary_event(
             duration_ms,
             error,
         )),
+        Commands::Tree {
+            command: Some(TreeCommands::VerifyDeterminism),
+            ..
+        } => Some(crate::workspace::summary::tree_determinism(
+            ok,
+            duration_ms,
+            error,
+        )),
+        Commands::Tree {
+            command: None,
+            path,
+            depth,
+            frames,
+            format,
+        } => Some(crate::workspace::summary::tree(
+            path.is_some(),
+            *depth,
+            *frames,
+            format,
+            ok,
+            duration_ms,
+            error,
+        )),
         Commands::Agent { command } => Some(crate::agent::summary::command(
             agent_command_name(command),
             matches!(
@@ -225,19 +314,12 @@ pub fn typed_summary_event(
             error,
         )),
         Commands::Context { command } => match command {
-            ContextCommands::Generate {
-                node,
-                path,
-                path_positional,
-                force,
-                no_recursive,
-                ..
-            } => Some(crate::context::summary::generation(
+            ContextCommands::Generate(args) => Some(crate::context::summary::generation(
                 context_command_name(command),
-                path.is_some() || path_positional.is_some(),
-                node.is_some(),
-                !*no_recursive,
-                *force,
+                args.path.is_some() || args.path_positional.is_some(),
+                args.node.is_some(),
+                !args.no_recursive,
+                args.force,
                 ok,
                 duration_ms,
                 error,
@@ -258,11 +340,18 @@ pub fn typed_summary_event(
                 duration_ms,
                 error,
             )),
-            ContextCommands::Get { .. } => None,
+            ContextCommands::Get { .. }
+            | ContextCommands::Import { .. }
+            | ContextCommands::Copy { .. }
+            | ContextCommands::Touch { .. }
+            | ContextCommands::Search { .. } => None,
         },
-        Commands::Init { force, list } => Some(crate::init::summary::command(
+        Commands::Init {
+            force, list, check, ..
+        } => Some(crate::init::summary::command(
             *force,
             *list,
+            *check,
             ok,
             duration_ms,
             error,
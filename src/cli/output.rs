@@ -1,9 +1,105 @@
-//! CLI output: error mapping from domain errors to stable CLI surface.
+//! CLI output: error mapping from domain errors to stable CLI surface, JSON rendering, and
+//! `--color` styling for human-readable output.
 
 use crate::error::ApiError;
+use comfy_table::{Attribute, Cell as TableCell, Table};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::cell::Cell;
+use std::io::IsTerminal;
+
+thread_local! {
+    static JSON_COMPACT: Cell<bool> = const { Cell::new(false) };
+    static COLOR: Cell<bool> = const { Cell::new(false) };
+}
 
 /// Map domain/service errors to a string for CLI output.
 /// Keeps route handlers thin; extend with stable categories if needed.
 pub fn map_error(e: &ApiError) -> String {
     e.to_string()
 }
+
+/// Run `f` with `--json-compact` set to `compact` for this thread, restoring the prior value.
+///
+/// Command execution formats output on the calling thread, so the setting is scoped to one
+/// command and concurrent callers (e.g. parallel tests) do not observe each other's mode.
+pub fn with_json_compact<R>(compact: bool, f: impl FnOnce() -> R) -> R {
+    let previous = JSON_COMPACT.with(|cell| cell.replace(compact));
+    let result = f();
+    JSON_COMPACT.with(|cell| cell.set(previous));
+    result
+}
+
+/// Serialize a `--format json` payload: pretty by default, single-line under `--json-compact`.
+///
+/// Callers keep their own error mapping, as they did for `serde_json::to_string_pretty`.
+pub fn to_json_output<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    if JSON_COMPACT.with(Cell::get) {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+/// Resolve a `--color` choice: `always` and `never` are taken as given; `auto` colors only when
+/// stdout is a terminal and `NO_COLOR` is unset.
+pub fn resolve_color(choice: &str) -> bool {
+    match choice {
+        "always" => true,
+        "never" => false,
+        _ => std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal(),
+    }
+}
+
+/// Run `f` with color output `enabled` for this thread, restoring the prior value.
+///
+/// Scoped the same way as [`with_json_compact`].
+pub fn with_color<R>(enabled: bool, f: impl FnOnce() -> R) -> R {
+    let previous = COLOR.with(|cell| cell.replace(enabled));
+    let result = f();
+    COLOR.with(|cell| cell.set(previous));
+    result
+}
+
+/// Whether human-readable output on this thread may contain ANSI styling.
+pub fn color_enabled() -> bool {
+    COLOR.with(Cell::get)
+}
+
+/// Validation mark for a passed check; green when color is enabled.
+pub fn pass_mark() -> String {
+    if color_enabled() {
+        "✓".green().to_string()
+    } else {
+        "✓".to_string()
+    }
+}
+
+/// Validation mark for a failed check; red when color is enabled.
+pub fn fail_mark() -> String {
+    if color_enabled() {
+        "✗".red().to_string()
+    } else {
+        "✗".to_string()
+    }
+}
+
+/// Build a table with `preset` borders and `header`, bold when color is enabled.
+///
+/// Styling follows `--color` rather than comfy-table's own stdout TTY check, since output is
+/// rendered to a string before it is printed.
+pub fn styled_table(preset: &str, header: &[&str]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(preset).force_no_tty();
+    if color_enabled() {
+        table.enforce_styling();
+        table.set_header(
+            header
+                .iter()
+                .map(|title| TableCell::new(title).add_attribute(Attribute::Bold)),
+        );
+    } else {
+        table.set_header(header.to_vec());
+    }
+    table
+}
@@ -1,6 +1,6 @@
 //! CLI parse: clap types for Merkle. No behavior; definitions only.
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
@@ -21,6 +21,18 @@ pub struct Cli {
     #[arg(long)]
     pub config: Option<PathBuf>,
 
+    /// Override one config value for this invocation, e.g. `logging.level=debug` (repeatable)
+    #[arg(long = "config-override", value_name = "KEY=VALUE", global = true)]
+    pub config_override: Vec<String>,
+
+    /// Apply the `[profiles.<NAME>]` config section over the base config (default: `MELD_PROFILE`)
+    #[arg(long, value_name = "NAME", global = true)]
+    pub profile: Option<String>,
+
+    /// Also stream progress events as JSON Lines to this file or named pipe (best-effort)
+    #[arg(long = "progress-file", value_name = "PATH", global = true)]
+    pub progress_file: Option<PathBuf>,
+
     /// Enable verbose logging and mirror logs to stderr unless output is explicitly set
     #[arg(long, default_value = "false")]
     pub verbose: bool,
@@ -44,6 +56,32 @@ pub struct Cli {
     /// Log file path (if output includes "file")
     #[arg(long)]
     pub log_file: Option<PathBuf>,
+
+    /// Emit `--format json` output on a single line instead of pretty-printed
+    #[arg(long, global = true, default_value = "false")]
+    pub json_compact: bool,
+
+    /// Print one line of final counts instead of progress and the full report (scan, context
+    /// generate/regenerate); `--summary-only=json` prints one JSON object
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text",
+        value_parser = ["text", "json"]
+    )]
+    pub summary_only: Option<String>,
+
+    /// Color human-readable output: `auto` (only on a terminal), `always`, or `never`
+    #[arg(
+        long,
+        global = true,
+        default_value = "auto",
+        value_parser = ["auto", "always", "never"]
+    )]
+    pub color: String,
 }
 
 #[derive(Subcommand)]
@@ -53,6 +91,24 @@ pub enum Commands {
         /// Force rebuild even if tree exists
         #[arg(long)]
         force: bool,
+        /// Index hidden dotfiles and directories (`.git` stays excluded)
+        #[arg(long)]
+        include_hidden: bool,
+        /// Record directories with nothing indexable below them (omitted by default)
+        #[arg(long)]
+        include_empty_dirs: bool,
+        /// Rebuild the tree after scanning and fail if the root hash differs
+        #[arg(long)]
+        verify_stable: bool,
+        /// Do not warn when every file is ignored and the tree is empty
+        #[arg(long)]
+        allow_empty_workspace: bool,
+        /// Report file and directory counts, total bytes, largest files, and deepest path
+        #[arg(long)]
+        stats: bool,
+        /// Output format for --stats (text or json)
+        #[arg(long, default_value = "text", requires = "stats")]
+        format: String,
     },
     /// Workspace commands (status, validate)
     Workspace {
@@ -76,12 +132,49 @@ pub enum Commands {
         /// Include top-level path breakdown in workspace section
         #[arg(long)]
         breakdown: bool,
+        /// Include each file's recorded content hash in workspace section
+        #[arg(long)]
+        include_content_hash: bool,
         /// Test provider connectivity
         #[arg(long)]
         test_connectivity: bool,
+        /// Re-render every --interval seconds until Ctrl-C (in place on a terminal)
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between --watch refreshes
+        #[arg(long, default_value = "2", value_name = "SECONDS", requires = "watch")]
+        interval: u64,
+        /// Summarize these workspaces instead (comma-separated roots); each opens its own store
+        #[arg(
+            long,
+            value_delimiter = ',',
+            value_name = "PATHS",
+            conflicts_with = "watch"
+        )]
+        workspaces: Vec<PathBuf>,
     },
     /// Validate workspace integrity
     Validate,
+    /// Show the node hierarchy as an indented tree
+    #[command(args_conflicts_with_subcommands = true)]
+    Tree {
+        #[command(subcommand)]
+        command: Option<TreeCommands>,
+        /// Start from this workspace path instead of the root
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+        /// Maximum depth below the starting node
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Annotate each node with its head frame count
+        #[arg(long)]
+        frames: bool,
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Interactive session that loads the workspace once and runs commands against it
+    Repl,
     /// Start watch mode daemon
     Watch {
         /// Debounce window in milliseconds
@@ -93,6 +186,12 @@ pub enum Commands {
         /// Run in foreground (default: background daemon)
         #[arg(long)]
         foreground: bool,
+        /// Build the tree, process one batch window of changes, then exit
+        #[arg(long)]
+        once: bool,
+        /// Index hidden dotfiles and directories (`.git` stays excluded)
+        #[arg(long)]
+        include_hidden: bool,
     },
     /// Manage agents
     Agent {
@@ -113,6 +212,18 @@ pub enum Commands {
         /// List what would be initialized without creating
         #[arg(long)]
         list: bool,
+
+        /// Verify an existing installation (directories, prompts, agents) without writing anything
+        #[arg(long, conflicts_with_all = ["force", "list"])]
+        check: bool,
+
+        /// Named preset of agents, prompts, and provider stub (see --list-templates)
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+
+        /// List built-in init templates
+        #[arg(long, conflicts_with_all = ["template", "force", "list", "check"])]
+        list_templates: bool,
     },
     /// Context operations (generate and retrieve frames)
     Context {
@@ -134,6 +245,93 @@ pub enum Commands {
         #[command(subcommand)]
         command: DangerCommands,
     },
+    /// Telemetry session inspection and export
+    Telemetry {
+        #[command(subcommand)]
+        command: TelemetryCommands,
+    },
+    /// Low-level inspection of stored context frames
+    Frame {
+        #[command(subcommand)]
+        command: FrameCommands,
+    },
+    /// Head index snapshots for rolling back experimental regenerations
+    Heads {
+        #[command(subcommand)]
+        command: HeadsCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TreeCommands {
+    /// Build the tree twice and check that every directory hashes identically
+    VerifyDeterminism,
+}
+
+#[derive(Subcommand)]
+pub enum TelemetryCommands {
+    /// List recent command sessions
+    ListSessions {
+        /// Maximum sessions to show (most recent first)
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Export one session's ordered event stream as JSON Lines
+    Export {
+        /// Session ID to export
+        #[arg(long, conflicts_with = "last", required_unless_present = "last")]
+        session: Option<String>,
+        /// Export the most recent session before this command
+        #[arg(long)]
+        last: bool,
+        /// Destination file for JSON Lines output
+        #[arg(long, value_name = "PATH")]
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum FrameCommands {
+    /// Show one stored frame with its basis, metadata, and hash check
+    Show {
+        /// Frame ID (hex)
+        frame_id: String,
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Print the complete content instead of a preview
+        #[arg(long)]
+        full: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HeadsCommands {
+    /// Save the current head index as a named snapshot
+    Snapshot {
+        /// Snapshot name (letters, digits, '-', '_', '.')
+        name: String,
+        /// Replace an existing snapshot with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    /// Replace the live head index with a snapshot; frames are left untouched
+    Restore {
+        /// Snapshot name
+        name: String,
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// List saved head snapshots
+    ListSnapshots {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -276,12 +474,41 @@ pub enum WorkspaceCommands {
         /// Include top-level path breakdown
         #[arg(long)]
         breakdown: bool,
+        /// Include each file's recorded content hash (hex Blake3)
+        #[arg(long)]
+        include_content_hash: bool,
+        /// Report head index health: total, dangling, and stale head entries
+        #[arg(long)]
+        check_heads: bool,
+        /// Break coverage down by the agent that produced each node's head frames
+        #[arg(long)]
+        by_agent: bool,
+        /// List head frames whose source file was modified after generation (needs frames
+        /// generated with --preserve-mtime)
+        #[arg(long)]
+        check_staleness: bool,
+        /// Re-render every --interval seconds until Ctrl-C (in place on a terminal)
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between --watch refreshes
+        #[arg(long, default_value = "2", value_name = "SECONDS", requires = "watch")]
+        interval: u64,
     },
     /// Validate workspace integrity
     Validate {
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
+        /// Fix detected inconsistencies: drop dangling and orphan head entries, tombstone live
+        /// heads of deleted nodes, and (with --allow-data-loss) delete orphan frame blobs
+        #[arg(long)]
+        repair: bool,
+        /// With --repair, list what would be repaired without changing anything
+        #[arg(long, requires = "repair")]
+        dry_run: bool,
+        /// With --repair, allow repairs that delete frame blobs
+        #[arg(long, requires = "repair")]
+        allow_data_loss: bool,
     },
     /// List or add paths to the workspace ignore list
     Ignore {
@@ -307,6 +534,9 @@ pub enum WorkspaceCommands {
         /// Do not add the path to the workspace ignore list
         #[arg(long)]
         no_ignore: bool,
+        /// Keep head frames retrievable by node ID; compact skips the node unless --purge-kept
+        #[arg(long)]
+        keep_frames: bool,
     },
     /// Restore a tombstoned node and its descendants
     Restore {
@@ -315,6 +545,16 @@ pub enum WorkspaceCommands {
         /// Node ID (hex) instead of path
         #[arg(long)]
         node: Option<String>,
+        /// Restore every tombstoned node (optionally filtered by age) instead of one target.
+        /// A restored directory brings back its whole subtree, whatever each descendant's age
+        #[arg(long, conflicts_with_all = ["path", "node"])]
+        all: bool,
+        /// With --all, only restore nodes tombstoned more than this many days ago
+        #[arg(long, requires = "all")]
+        older_than: Option<u64>,
+        /// With --all, only restore nodes tombstoned within this many days
+        #[arg(long, requires = "all")]
+        newer_than: Option<u64>,
         /// Report counts without performing the operation
         #[arg(long)]
         dry_run: bool,
@@ -330,15 +570,37 @@ pub enum WorkspaceCommands {
         /// Do not purge frame blobs; only purge node and head index records
         #[arg(long)]
         keep_frames: bool,
+        /// Also purge nodes deleted with --keep-frames, with their head entries
+        #[arg(long)]
+        purge_kept: bool,
         /// Report counts without compaction
         #[arg(long)]
         dry_run: bool,
+        /// After purging, rewrite the node store into a fresh copy to reclaim free space and
+        /// report its size before and after. Fails while another process has the store open
+        #[arg(long, conflicts_with = "dry_run")]
+        vacuum: bool,
+    },
+    /// Report on-disk storage footprint (node store, frames, head index)
+    Size {
+        /// Break frame storage down by frame type
+        #[arg(long)]
+        by_frame_type: bool,
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
     },
     /// List tombstoned (deleted) nodes
     ListDeleted {
         /// Show only nodes tombstoned longer than this many days
         #[arg(long)]
         older_than: Option<u64>,
+        /// Sort rows by field: path, age (youngest first), or tombstoned_at (oldest first)
+        #[arg(long, value_name = "FIELD")]
+        sort: Option<String>,
+        /// Reverse the --sort order
+        #[arg(long, requires = "sort")]
+        reverse: bool,
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
@@ -361,6 +623,9 @@ pub enum AgentCommands {
         /// Filter by role (Reader or Writer)
         #[arg(long)]
         role: Option<String>,
+        /// Include prompt path, prompt file existence, and default provider
+        #[arg(long)]
+        detailed: bool,
     },
     /// Show agent details
     Show {
@@ -372,6 +637,9 @@ pub enum AgentCommands {
         /// Include prompt file content in output
         #[arg(long)]
         include_prompt: bool,
+        /// Show the fully-resolved configuration and the source of each setting
+        #[arg(long, conflicts_with = "include_prompt")]
+        effective: bool,
     },
     /// Validate agent configuration
     Validate {
@@ -381,6 +649,9 @@ pub enum AgentCommands {
         /// Validate all agents
         #[arg(long, conflicts_with = "agent_id")]
         all: bool,
+        /// With --all, stop at the first invalid agent (default: validate every agent)
+        #[arg(long, requires = "all")]
+        fail_fast: bool,
         /// Show detailed validation results
         #[arg(long)]
         verbose: bool,
@@ -464,9 +735,12 @@ pub enum ProviderCommands {
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
-        /// Filter by provider type (openai, anthropic, ollama, local)
+        /// Filter by provider type (openai, anthropic, ollama, local, mock)
         #[arg(long)]
         type_filter: Option<String>,
+        /// Include recorded average latency and sort fastest-first
+        #[arg(long)]
+        show_latency: bool,
     },
     /// Show provider details
     Show {
@@ -481,14 +755,24 @@ pub enum ProviderCommands {
     },
     /// Validate provider configuration
     Validate {
-        /// Provider name
-        provider_name: String,
+        /// Provider name (required unless --all is used)
+        #[arg(required_unless_present = "all")]
+        provider_name: Option<String>,
+        /// Validate all providers
+        #[arg(long, conflicts_with_all = ["provider_name", "check_credentials_only"])]
+        all: bool,
+        /// With --all, stop at the first invalid provider (default: validate every provider)
+        #[arg(long, requires = "all")]
+        fail_fast: bool,
         /// Test provider API connectivity
         #[arg(long)]
         test_connectivity: bool,
         /// Verify model is available
         #[arg(long)]
         check_model: bool,
+        /// Run only offline checks (config, endpoint, API key resolution) and fail on any error
+        #[arg(long, conflicts_with_all = ["test_connectivity", "check_model"])]
+        check_credentials_only: bool,
         /// Show detailed validation results
         #[arg(long)]
         verbose: bool,
@@ -503,12 +787,15 @@ pub enum ProviderCommands {
         /// Connection timeout in seconds (default: 10)
         #[arg(long, default_value = "10")]
         timeout: u64,
+        /// Send this prompt as one completion and print the raw response, finish reason, and usage
+        #[arg(long, value_name = "TEXT")]
+        prompt: Option<String>,
     },
     /// Create new provider
     Create {
         /// Provider name
         provider_name: String,
-        /// Provider type (openai, anthropic, ollama, local)
+        /// Provider type (openai, anthropic, ollama, local, mock)
         #[arg(long, name = "type")]
         type_: Option<String>,
         /// Model name
@@ -526,6 +813,15 @@ pub enum ProviderCommands {
         /// Use non-interactive mode (use flags)
         #[arg(long)]
         non_interactive: bool,
+        /// Copy type, model, endpoint, and default options from an existing provider
+        #[arg(long, value_name = "EXISTING", conflicts_with = "type")]
+        clone: Option<String>,
+        /// With --clone, also copy the existing provider's API key
+        #[arg(long, requires = "clone", conflicts_with = "api_key")]
+        copy_key: bool,
+        /// With --clone, overwrite a provider that already has the new name
+        #[arg(long, requires = "clone")]
+        force: bool,
     },
     /// Edit provider configuration
     Edit {
@@ -540,6 +836,9 @@ pub enum ProviderCommands {
         /// Update API key
         #[arg(long)]
         api_key: Option<String>,
+        /// Clear an optional field (endpoint, api_key, temperature, ...); repeatable
+        #[arg(long = "unset", value_name = "FIELD")]
+        unset: Vec<String>,
         /// Editor to use (default: $EDITOR)
         #[arg(long)]
         editor: Option<String>,
@@ -557,50 +856,7 @@ pub enum ProviderCommands {
 #[derive(Subcommand)]
 pub enum ContextCommands {
     /// Generate context frame for a node
-    Generate {
-        /// Target node by NodeID (hex string)
-        #[arg(long, conflicts_with_all = ["path", "path_positional"])]
-        node: Option<String>,
-
-        /// Target node by workspace-relative or absolute path
-        #[arg(long, value_name = "PATH", conflicts_with = "node")]
-        path: Option<PathBuf>,
-
-        /// Target path (positional; same as --path)
-        #[arg(value_name = "PATH", index = 1, conflicts_with = "node")]
-        path_positional: Option<PathBuf>,
-
-        /// Agent to use for generation
-        #[arg(long)]
-        agent: Option<String>,
-
-        /// Provider to use for generation (required)
-        #[arg(long)]
-        provider: Option<String>,
-
-        /// Override workflow id for this run (bypasses agent workflow binding)
-        #[arg(long)]
-        workflow_id: Option<String>,
-
-        /// Override provider model for this run only
-        #[arg(long)]
-        provider_model: Option<String>,
-
-        /// Path to JSON object merged into provider additional_json for this run only
-        #[arg(long, value_name = "JSON_PATH")]
-        provider_additional_json_file: Option<PathBuf>,
-
-        /// Frame type (defaults to context-<agent_id>)
-        #[arg(long)]
-        frame_type: Option<String>,
-
-        /// Generate even if head frame exists
-        #[arg(long)]
-        force: bool,
-        /// Disable recursive generation for directory targets
-        #[arg(long)]
-        no_recursive: bool,
-    },
+    Generate(Box<GenerateArgs>),
     /// Re generate a context frame for a node and prefer directory only reroll
     Regenerate {
         /// Target node by NodeID (hex string)
@@ -619,7 +875,7 @@ pub enum ContextCommands {
         #[arg(long)]
         agent: Option<String>,
 
-        /// Provider to use for generation (required)
+        /// Provider to use for generation (defaults to the agent's default_provider)
         #[arg(long)]
         provider: Option<String>,
 
@@ -677,7 +933,7 @@ pub enum ContextCommands {
         #[arg(long, default_value = "\n\n---\n\n")]
         separator: String,
 
-        /// Output format: text or json
+        /// Output format: text, json, or ndjson (one JSON object per node in the subtree)
         #[arg(long, default_value = "text")]
         format: String,
 
@@ -688,9 +944,225 @@ pub enum ContextCommands {
         /// Include frames marked deleted (tombstones)
         #[arg(long)]
         include_deleted: bool,
+
+        /// For a directory, concatenate the head frames of all descendant files in path order
+        #[arg(long)]
+        flatten_directory: bool,
+
+        /// Keep only frames whose metadata has this exact value (repeatable; all must match)
+        #[arg(long = "meta", value_name = "KEY=VALUE")]
+        meta: Vec<String>,
+
+        /// Print only the number of matching frames (ignores --max-frames); json gives {"count": N}
+        #[arg(long, conflicts_with_all = ["combine", "include_metadata"])]
+        count_only: bool,
+
+        /// Write the selected frames and the node's head pointers to FILE for `context import`
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["count_only", "combine", "flatten_directory"]
+        )]
+        export: Option<PathBuf>,
+
+        /// With --combine, keep frames in --ordering order until about N tokens (~4 chars each)
+        #[arg(long, value_name = "N", conflicts_with_all = ["count_only", "export"])]
+        budget_tokens: Option<usize>,
+
+        /// Also include each ancestor directory's head frame of the same type, root first
+        #[arg(long, conflicts_with_all = ["count_only", "export"])]
+        with_ancestors: bool,
+
+        /// With --combine, print the frame count, byte size, and estimated tokens instead of content
+        #[arg(long, conflicts_with_all = ["count_only", "export"])]
+        size_only: bool,
+
+        /// Keep only the newest frame from each agent; --max-frames then caps the agents kept
+        #[arg(long, conflicts_with = "count_only")]
+        newest_per_agent: bool,
+
+        /// Show what each frame derives from: the basis node's path and referenced frame
+        #[arg(long, requires = "include_metadata", conflicts_with = "count_only")]
+        resolve_basis: bool,
+
+        /// Show generation_duration_ms, retry_count, and provider_name for frames generated
+        /// with --record-provenance
+        #[arg(long, requires = "include_metadata", conflicts_with = "count_only")]
+        include_provenance: bool,
+    },
+    /// Import frames written by `context get --export` onto one node
+    Import {
+        /// Node export file
+        file: PathBuf,
+
+        /// Target node by NodeID (defaults to the exported node's path in this workspace)
+        #[arg(long, conflicts_with = "path")]
+        node: Option<String>,
+
+        /// Target node by workspace-relative or absolute path
+        #[arg(long, conflicts_with = "node")]
+        path: Option<PathBuf>,
+    },
+    /// Copy a node's head frame to a new frame type without regenerating it
+    Copy {
+        /// Target node by NodeID (hex string)
+        #[arg(long, conflicts_with = "path")]
+        node: Option<String>,
+
+        /// Target node by workspace-relative or absolute path
+        #[arg(long, conflicts_with = "node")]
+        path: Option<PathBuf>,
+
+        /// Frame type whose head frame is copied
+        #[arg(long)]
+        from_type: String,
+
+        /// Frame type of the new frame; it becomes the head for this type
+        #[arg(long)]
+        to_type: String,
+    },
+    /// Mark a node's head frames stale so the next generate regenerates them
+    Touch {
+        /// Target node by NodeID (hex string)
+        #[arg(long, conflicts_with = "path")]
+        node: Option<String>,
+
+        /// Target node by workspace-relative or absolute path
+        #[arg(long, conflicts_with = "node")]
+        path: Option<PathBuf>,
+
+        /// Also mark every descendant of the target
+        #[arg(long)]
+        recursive: bool,
+
+        /// Only mark heads of this frame type (default: every head)
+        #[arg(long)]
+        frame_type: Option<String>,
+    },
+    /// Search frame contents and list matching nodes with a snippet of each match
+    Search {
+        /// Text to find (case-insensitive), or a regular expression with --regex
+        query: String,
+
+        /// Only search frames of nodes under this workspace-relative or absolute path
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Only search frames of this type
+        #[arg(long)]
+        frame_type: Option<String>,
+
+        /// Treat the query as a case-sensitive regular expression
+        #[arg(long)]
+        regex: bool,
+
+        /// Output format: text or ndjson (one JSON object per match)
+        #[arg(long, default_value = "text")]
+        format: String,
     },
 }
 
+/// Arguments of `context generate`, boxed in [`ContextCommands::Generate`] to keep the
+/// command enums small.
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// Target node by NodeID (hex string)
+    #[arg(long, conflicts_with_all = ["path", "path_positional"])]
+    pub node: Option<String>,
+
+    /// Target node by workspace-relative or absolute path
+    #[arg(long, value_name = "PATH", conflicts_with = "node")]
+    pub path: Option<PathBuf>,
+
+    /// Target path (positional; same as --path)
+    #[arg(value_name = "PATH", index = 1, conflicts_with = "node")]
+    pub path_positional: Option<PathBuf>,
+
+    /// Agent to use for generation
+    #[arg(long)]
+    pub agent: Option<String>,
+
+    /// Provider to use for generation (defaults to the agent's default_provider)
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Override workflow id for this run (bypasses agent workflow binding)
+    #[arg(long)]
+    pub workflow_id: Option<String>,
+
+    /// Override provider model for this run only
+    #[arg(long)]
+    pub provider_model: Option<String>,
+
+    /// Path to JSON object merged into provider additional_json for this run only
+    #[arg(long, value_name = "JSON_PATH")]
+    pub provider_additional_json_file: Option<PathBuf>,
+
+    /// Frame type (defaults to context-<agent_id>)
+    #[arg(long)]
+    pub frame_type: Option<String>,
+
+    /// Generate even if head frame exists
+    #[arg(long)]
+    pub force: bool,
+    /// Generate only nodes without a head (the default; explicit form)
+    #[arg(long, conflicts_with_all = ["force", "only_stale"])]
+    pub only_missing: bool,
+    /// Regenerate only nodes whose path has a frame for older content; skip new and current nodes
+    #[arg(long, conflicts_with = "force")]
+    pub only_stale: bool,
+    /// Disable recursive generation for directory targets
+    #[arg(long)]
+    pub no_recursive: bool,
+    /// Keep generating after node failures and report every failed path at the end
+    #[arg(long)]
+    pub continue_on_error: bool,
+    /// Read the user prompt from stdin instead of the agent template (single-node targets only;
+    /// implies --force)
+    #[arg(long, conflicts_with = "prompt_file")]
+    pub stdin_prompt: bool,
+    /// Read the user prompt from a file instead of the agent template (single-node targets only;
+    /// implies --force)
+    #[arg(long, value_name = "PATH")]
+    pub prompt_file: Option<PathBuf>,
+    /// Echo provider output to stderr as it streams in (single-node targets only)
+    #[arg(long)]
+    pub stream: bool,
+    /// Cap on provider retries across the whole run (default: per-request limit only)
+    #[arg(long, value_name = "N")]
+    pub max_total_retries: Option<usize>,
+    /// Also generate frames for files detected as binary (skipped by default)
+    #[arg(long)]
+    pub include_binary: bool,
+    /// Custom prompt placeholder `{key}` filled with value (repeatable)
+    #[arg(long = "prompt-var", value_name = "KEY=VALUE")]
+    pub prompt_var: Vec<String>,
+    /// Limit recursive generation to N levels below the target (0 = target only)
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+    /// Plan the first node in an earlier level than the second (repeatable; must stay acyclic)
+    #[arg(long, value_name = "NODE_ID:NODE_ID")]
+    pub after: Vec<String>,
+    /// Leave matching workspace-relative paths and their subtrees out of the plan (repeatable glob)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+    /// Queue priority: low, normal, high, or urgent (run bulk regeneration at low)
+    #[arg(long, default_value = "urgent")]
+    pub priority: String,
+    /// Record each source file's modification time in frame metadata as `source_mtime`
+    #[arg(long)]
+    pub preserve_mtime: bool,
+    /// Record generation duration, retry count, and provider name in frame metadata
+    #[arg(long)]
+    pub record_provenance: bool,
+    /// Stop the run after this many seconds; completed frames are kept
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+    /// When another writer replaced a head during the run: last-wins, fail, or keep-both
+    #[arg(long, default_value = "last-wins", value_name = "POLICY")]
+    pub on_conflict: String,
+}
+
 pub fn parse_provider_additional_json_file(
     path: Option<&PathBuf>,
 ) -> Result<Option<BTreeMap<String, Value>>, String> {
@@ -782,6 +1254,17 @@ mod tests {
     use clap::Parser;
     use std::path::PathBuf;
 
+    #[test]
+    fn parses_summary_only_flag() {
+        let cli = Cli::try_parse_from(["meld", "scan", "--summary-only"]).unwrap();
+        assert_eq!(cli.summary_only.as_deref(), Some("text"));
+        let cli = Cli::try_parse_from(["meld", "--summary-only=json", "scan"]).unwrap();
+        assert_eq!(cli.summary_only.as_deref(), Some("json"));
+        let cli = Cli::try_parse_from(["meld", "scan"]).unwrap();
+        assert_eq!(cli.summary_only, None);
+        assert!(Cli::try_parse_from(["meld", "scan", "--summary-only=yaml"]).is_err());
+    }
+
     #[test]
     fn parses_branches_status_command() {
         let cli = Cli::try_parse_from(["meld", "branches", "status", "--format", "json"]).unwrap();
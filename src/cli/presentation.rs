@@ -2,19 +2,41 @@
 
 mod agent;
 mod context;
+mod frame;
+mod heads;
 mod init;
 mod provider;
 mod shared;
+mod telemetry;
 
 pub use agent::{
-    format_agent_list_result_json, format_agent_list_result_text, format_agent_show_result_json,
-    format_agent_show_result_text, format_validation_result, format_validation_results_all,
+    format_agent_effective_json, format_agent_effective_text, format_agent_list_result_json,
+    format_agent_list_result_text, format_agent_show_result_json, format_agent_show_result_text,
+    format_validation_result, format_validation_results_all,
+};
+pub use context::{
+    format_context_count_output, format_context_json_output, format_context_ndjson_line,
+    format_context_search_hit, format_context_size_output, format_context_text_output,
+    ContextViewOptions,
+};
+pub use frame::{format_frame_show_json, format_frame_show_text};
+pub use heads::{
+    format_head_restore_json, format_head_restore_result, format_head_snapshot_result,
+    format_head_snapshots_json, format_head_snapshots_text,
+};
+pub use init::{
+    format_init_check, format_init_preview, format_init_summary, format_init_templates,
 };
-pub use context::{format_context_json_output, format_context_text_output};
-pub use init::{format_init_preview, format_init_summary};
 pub use provider::{
     format_provider_list_result_json, format_provider_list_result_text,
-    format_provider_show_result_json, format_provider_show_result_text,
-    format_provider_test_result, format_provider_validation_result,
+    format_provider_prompt_test_result, format_provider_show_result_json,
+    format_provider_show_result_text, format_provider_test_result,
+    format_provider_validation_result, format_provider_validation_results_all,
+};
+pub use shared::{
+    format_ignore_result, format_list_deleted_result, format_node_tree, format_repair_report_text,
+    format_scan_stats, format_validate_result_text, format_workspace_size_result,
+};
+pub use telemetry::{
+    format_telemetry_export_result, format_telemetry_sessions_json, format_telemetry_sessions_text,
 };
-pub use shared::{format_ignore_result, format_list_deleted_result, format_validate_result_text};
@@ -1,6 +1,9 @@
 //! Agent command presentation: list, show, validation text/json.
 
-use crate::agent::{AgentListResult, AgentRole, AgentShowResult, ValidationResult};
+use crate::agent::{
+    AgentListResult, AgentRole, AgentShowResult, EffectiveAgentConfig, ValidationResult,
+};
+use crate::cli::{fail_mark, pass_mark, to_json_output};
 use serde_json::json;
 
 pub fn format_agent_list_result_text(result: &AgentListResult) -> String {
@@ -9,12 +12,36 @@ pub fn format_agent_list_result_text(result: &AgentListResult) -> String {
         return "No agents found.\n\nNote: Agents are provider-agnostic. Providers are selected at runtime.".to_string();
     }
     let mut output = String::from("Available Agents:\n");
+    let detailed = agents.iter().any(|item| item.details.is_some());
+    if detailed {
+        output.push_str(&format!(
+            "  {:<20} {:<10} {:<8} {:<20} {}\n",
+            "ID", "Role", "Prompt", "Default Provider", "Prompt Path"
+        ));
+    }
     for item in agents {
         let role_str = match item.role {
             AgentRole::Reader => "Reader",
             AgentRole::Writer => "Writer",
         };
-        output.push_str(&format!("  {:<20} {:<10}\n", item.agent_id, role_str));
+        match &item.details {
+            Some(details) => {
+                let prompt_status = match details.prompt_exists {
+                    Some(true) => "ok",
+                    Some(false) => "missing",
+                    None => "-",
+                };
+                output.push_str(&format!(
+                    "  {:<20} {:<10} {:<8} {:<20} {}\n",
+                    item.agent_id,
+                    role_str,
+                    prompt_status,
+                    details.default_provider.as_deref().unwrap_or("-"),
+                    details.prompt_path.as_deref().unwrap_or("-"),
+                ));
+            }
+            None => output.push_str(&format!("  {:<20} {:<10}\n", item.agent_id, role_str)),
+        }
     }
     output.push_str(&format!("\nTotal: {} agent(s)\n\nNote: Agents are provider-agnostic. Providers are selected at runtime.", agents.len()));
     output
@@ -25,17 +52,23 @@ pub fn format_agent_list_result_json(result: &AgentListResult) -> String {
         .agents
         .iter()
         .map(|item| {
-            json!({
+            let mut entry = json!({
                 "agent_id": item.agent_id,
                 "role": match item.role {
                     AgentRole::Reader => "Reader",
                     AgentRole::Writer => "Writer",
                 },
-            })
+            });
+            if let Some(details) = &item.details {
+                entry["prompt_path"] = json!(details.prompt_path);
+                entry["prompt_exists"] = json!(details.prompt_exists);
+                entry["default_provider"] = json!(details.default_provider);
+            }
+            entry
         })
         .collect();
     let out = json!({ "agents": agent_list, "total": result.agents.len() });
-    serde_json::to_string_pretty(&out).unwrap_or_else(|_| "{}".to_string())
+    to_json_output(&out).unwrap_or_else(|_| "{}".to_string())
 }
 
 pub fn format_agent_show_result_text(result: &AgentShowResult) -> String {
@@ -70,31 +103,61 @@ pub fn format_agent_show_result_json(result: &AgentShowResult) -> String {
     if let Some(p) = &result.prompt_content {
         out["prompt_content"] = json!(p);
     }
-    serde_json::to_string_pretty(&out).unwrap_or_else(|_| "{}".to_string())
+    to_json_output(&out).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn format_agent_effective_text(result: &EffectiveAgentConfig) -> String {
+    let mut output = format!("Agent: {}\n", result.agent_id);
+    let layers: Vec<String> = result.layers.iter().map(|s| s.to_string()).collect();
+    output.push_str(&format!("Layers: {}\n\n", layers.join(" -> ")));
+    for setting in &result.settings {
+        output.push_str(&format!(
+            "{} = {}  [{}]\n",
+            setting.key, setting.value, setting.source
+        ));
+        for shadowed in &setting.overrides {
+            output.push_str(&format!(
+                "    overrides {} from {}\n",
+                shadowed.value, shadowed.source
+            ));
+        }
+    }
+    output
+}
+
+pub fn format_agent_effective_json(result: &EffectiveAgentConfig) -> String {
+    to_json_output(result).unwrap_or_else(|_| "{}".to_string())
 }
 
 pub fn format_validation_result(result: &ValidationResult, verbose: bool) -> String {
     let mut output = format!("Validating agent: {}\n\n", result.agent_id);
 
     if result.errors.is_empty() && result.checks.iter().all(|(_, passed)| *passed) {
-        output.push_str("✓ All validation checks passed\n\n");
+        output.push_str(&format!("{} All validation checks passed\n\n", pass_mark()));
     } else {
         for (description, passed) in &result.checks {
             if *passed {
-                output.push_str(&format!("✓ {}\n", description));
+                output.push_str(&format!("{} {}\n", pass_mark(), description));
             } else {
-                output.push_str(&format!("✗ {}\n", description));
+                output.push_str(&format!("{} {}\n", fail_mark(), description));
             }
         }
         if !result.errors.is_empty() {
             output.push('\n');
             for error in &result.errors {
-                output.push_str(&format!("✗ {}\n", error));
+                output.push_str(&format!("{} {}\n", fail_mark(), error));
             }
         }
         output.push('\n');
     }
 
+    for warning in &result.warnings {
+        output.push_str(&format!("⚠ {}\n", warning));
+    }
+    if !result.warnings.is_empty() {
+        output.push('\n');
+    }
+
     if verbose {
         output.push_str(&format!(
             "Validation summary: {}/{} checks passed\n",
@@ -121,6 +184,7 @@ pub fn format_validation_result(result: &ValidationResult, verbose: bool) -> Str
 
 pub fn format_validation_results_all(
     results: &[(String, ValidationResult)],
+    skipped: usize,
     verbose: bool,
 ) -> String {
     let mut output = String::from("Validating all agents:\n\n");
@@ -132,25 +196,33 @@ pub fn format_validation_results_all(
             valid_count += 1;
             if verbose {
                 output.push_str(&format!(
-                    "✓ {}: All checks passed ({}/{} checks)\n",
+                    "{} {}: All checks passed ({}/{} checks)\n",
+                    pass_mark(),
                     agent_id,
                     result.passed_checks(),
                     result.total_checks()
                 ));
             } else {
-                output.push_str(&format!("✓ {}: Valid\n", agent_id));
+                output.push_str(&format!("{} {}: Valid\n", pass_mark(), agent_id));
+            }
+            for warning in &result.warnings {
+                output.push_str(&format!("  ⚠ {}\n", warning));
             }
         } else {
             invalid_count += 1;
-            output.push_str(&format!("✗ {}: Validation failed\n", agent_id));
+            output.push_str(&format!(
+                "{} {}: Validation failed\n",
+                fail_mark(),
+                agent_id
+            ));
             if verbose {
                 for (description, passed) in &result.checks {
                     if !passed {
-                        output.push_str(&format!("  ✗ {}\n", description));
+                        output.push_str(&format!("  {} {}\n", fail_mark(), description));
                     }
                 }
                 for error in &result.errors {
-                    output.push_str(&format!("  ✗ {}\n", error));
+                    output.push_str(&format!("  {} {}\n", fail_mark(), error));
                 }
             }
         }
@@ -161,5 +233,11 @@ pub fn format_validation_results_all(
         invalid_count,
         results.len()
     ));
+    if skipped > 0 {
+        output.push_str(&format!(
+            "Stopped at first invalid agent (--fail-fast); {} agent(s) not validated\n",
+            skipped
+        ));
+    }
     output
 }
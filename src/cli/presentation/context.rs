@@ -1,23 +1,50 @@
-//! Context get presentation: text and json formatters.
+//! Context get and search presentation: text and json formatters.
 
 use crate::api::NodeContext;
+use crate::cli::presentation::frame::format_basis_summary;
+use crate::cli::to_json_output;
+use crate::context::frame::inspect::BasisInspection;
+use crate::context::frame::Frame;
+use crate::context::query::{estimate_tokens, SearchHit};
 use crate::error::ApiError;
-use crate::metadata::frame_types::project_visible_metadata;
+use crate::metadata::frame_types::{
+    project_metadata_with_provenance, project_visible_metadata, VisibleFrameMetadata,
+};
+use crate::types::FrameID;
 use serde_json::json;
+use std::collections::HashMap;
+
+/// Which frames a context view shows and how much detail it shows for each.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContextViewOptions<'a> {
+    /// Show frame IDs, agents, timestamps, and metadata.
+    pub include_metadata: bool,
+    /// Also show frames marked deleted.
+    pub include_deleted: bool,
+    /// Resolved frame bases, shown with the metadata.
+    pub bases: Option<&'a HashMap<FrameID, BasisInspection>>,
+    /// Also show provenance metadata keys.
+    pub include_provenance: bool,
+}
+
+impl ContextViewOptions<'_> {
+    fn visible_frames<'c>(&self, context: &'c NodeContext) -> Vec<&'c Frame> {
+        if self.include_deleted {
+            context.frames.iter().collect()
+        } else {
+            context.frames.iter().filter(|f| !f.is_deleted()).collect()
+        }
+    }
+}
 
 pub fn format_context_text_output(
     context: &NodeContext,
     warnings: &[String],
-    include_metadata: bool,
+    options: &ContextViewOptions,
     combine: bool,
     separator: &str,
-    include_deleted: bool,
 ) -> Result<String, ApiError> {
-    let frames: Vec<&crate::context::frame::Frame> = if include_deleted {
-        context.frames.iter().collect()
-    } else {
-        context.frames.iter().filter(|f| !f.is_deleted()).collect()
-    };
+    let frames = options.visible_frames(context);
 
     if frames.is_empty() {
         let mut output = String::new();
@@ -33,15 +60,11 @@ pub fn format_context_text_output(
     }
 
     if combine {
-        let texts: Vec<String> = frames
-            .iter()
-            .filter_map(|f| f.text_content().ok())
-            .collect();
         let mut output = String::new();
         for warning in warnings {
             output.push_str(&format!("Warning: {}\n", warning));
         }
-        output.push_str(&texts.join(separator));
+        output.push_str(&combine_frame_texts(&frames, separator));
         Ok(output)
     } else {
         let mut output = String::new();
@@ -57,15 +80,18 @@ pub fn format_context_text_output(
         ));
         for (i, frame) in frames.iter().enumerate() {
             output.push_str(&format!("--- Frame {} ---\n", i + 1));
-            if include_metadata {
+            if options.include_metadata {
                 output.push_str(&format!("Frame ID: {}\n", hex::encode(frame.frame_id)));
                 output.push_str(&format!("Frame Type: {}\n", frame.frame_type));
                 if let Some(agent_id) = frame.agent_id() {
                     output.push_str(&format!("Agent: {}\n", agent_id));
                 }
                 output.push_str(&format!("Timestamp: {:?}\n", frame.timestamp));
+                if let Some(basis) = options.bases.and_then(|bases| bases.get(&frame.frame_id)) {
+                    output.push_str(&format!("Basis: {}\n", format_basis_summary(basis)));
+                }
                 if !frame.metadata.is_empty() {
-                    let projected = project_visible_metadata(&frame.metadata);
+                    let projected = metadata_view(frame, options.include_provenance);
                     output.push_str("Metadata:\n");
                     for (key, value) in projected {
                         output.push_str(&format!("  {}: {}\n", key, value));
@@ -87,14 +113,125 @@ pub fn format_context_text_output(
 pub fn format_context_json_output(
     context: &NodeContext,
     warnings: &[String],
-    include_metadata: bool,
-    include_deleted: bool,
+    options: &ContextViewOptions,
 ) -> Result<String, ApiError> {
-    let frames: Vec<&crate::context::frame::Frame> = if include_deleted {
-        context.frames.iter().collect()
+    let result = context_json_value(context, warnings, options);
+    to_json_output(&result)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+}
+
+/// Metadata shown by `--include-metadata`; provenance keys only with `--include-provenance`.
+fn metadata_view(frame: &Frame, include_provenance: bool) -> VisibleFrameMetadata {
+    if include_provenance {
+        project_metadata_with_provenance(&frame.metadata)
     } else {
-        context.frames.iter().filter(|f| !f.is_deleted()).collect()
-    };
+        project_visible_metadata(&frame.metadata)
+    }
+}
+
+/// The `--combine` body: text frame contents joined by `separator`. Binary frames are skipped.
+fn combine_frame_texts(frames: &[&Frame], separator: &str) -> String {
+    let texts: Vec<String> = frames
+        .iter()
+        .filter_map(|f| f.text_content().ok())
+        .collect();
+    texts.join(separator)
+}
+
+/// `--size-only` output: what `--combine` would print, measured but not emitted.
+///
+/// Bytes and tokens cover the combined frame contents and separators; warnings are not counted.
+pub fn format_context_size_output(
+    context: &NodeContext,
+    separator: &str,
+    include_deleted: bool,
+    format: &str,
+) -> Result<String, ApiError> {
+    let frames: Vec<&Frame> = context
+        .frames
+        .iter()
+        .filter(|f| include_deleted || !f.is_deleted())
+        .collect();
+    let combined = combine_frame_texts(&frames, separator);
+    let bytes = combined.len();
+    let tokens = estimate_tokens(&combined);
+    match format {
+        "text" => Ok(format!(
+            "Frames: {}\nBytes: {}\nEstimated tokens: {}\n",
+            frames.len(),
+            bytes,
+            tokens
+        )),
+        "json" => to_json_output(&json!({
+            "frame_count": frames.len(),
+            "bytes": bytes,
+            "estimated_tokens": tokens,
+        }))
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e))),
+        _ => Err(ApiError::ConfigError(format!(
+            "Invalid format for --size-only: '{}'. Must be 'text' or 'json'.",
+            format
+        ))),
+    }
+}
+
+/// `--count-only` output: the bare number for text, `{ "count": N }` for json.
+pub fn format_context_count_output(count: usize, format: &str) -> Result<String, ApiError> {
+    match format {
+        "text" => Ok(format!("{}\n", count)),
+        "json" => to_json_output(&json!({ "count": count }))
+            .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e))),
+        _ => Err(ApiError::ConfigError(format!(
+            "Invalid format for --count-only: '{}'. Must be 'text' or 'json'.",
+            format
+        ))),
+    }
+}
+
+/// One `context search` match: a path line and an indented snippet for text, or a single-line
+/// JSON object for ndjson.
+pub fn format_context_search_hit(hit: &SearchHit, format: &str) -> Result<String, ApiError> {
+    match format {
+        "text" => Ok(format!(
+            "{} [{}] {}\n  {}\n",
+            hit.path.display(),
+            hit.frame_type,
+            hex::encode(hit.frame_id),
+            hit.snippet
+        )),
+        "ndjson" => serde_json::to_string(&json!({
+            "node_id": hex::encode(hit.node_id),
+            "path": hit.path.to_string_lossy(),
+            "frame_id": hex::encode(hit.frame_id),
+            "frame_type": hit.frame_type,
+            "snippet": hit.snippet,
+        }))
+        .map(|line| line + "\n")
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e))),
+        _ => Err(ApiError::ConfigError(format!(
+            "Invalid format: '{}'. Must be 'text' or 'ndjson'.",
+            format
+        ))),
+    }
+}
+
+/// One `--format ndjson` line: the `--format json` object for a node, always on a single line.
+pub fn format_context_ndjson_line(
+    context: &NodeContext,
+    warnings: &[String],
+    options: &ContextViewOptions,
+) -> Result<String, ApiError> {
+    let result = context_json_value(context, warnings, options);
+    serde_json::to_string(&result)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+}
+
+fn context_json_value(
+    context: &NodeContext,
+    warnings: &[String],
+    options: &ContextViewOptions,
+) -> serde_json::Value {
+    let frames = options.visible_frames(context);
 
     let frames_json: Vec<serde_json::Value> = frames
         .iter()
@@ -104,11 +241,14 @@ pub fn format_context_json_output(
                 "frame_type": frame.frame_type,
                 "timestamp": frame.timestamp,
             });
-            if include_metadata {
+            if options.include_metadata {
                 if let Some(agent_id) = frame.agent_id() {
                     frame_obj["agent_id"] = json!(agent_id);
                 }
-                frame_obj["metadata"] = json!(project_visible_metadata(&frame.metadata));
+                frame_obj["metadata"] = json!(metadata_view(frame, options.include_provenance));
+                if let Some(basis) = options.bases.and_then(|bases| bases.get(&frame.frame_id)) {
+                    frame_obj["basis"] = json!(basis);
+                }
             }
             if let Ok(text) = frame.text_content() {
                 frame_obj["content"] = json!(text);
@@ -120,19 +260,20 @@ pub fn format_context_json_output(
         })
         .collect();
 
-    let result = json!({
+    json!({
         "node_id": hex::encode(context.node_id),
+        "context_hash": hex::encode(context.content_hash()),
         "path": context.node_record.path.to_string_lossy(),
         "warnings": warnings,
         "node_type": match context.node_record.node_type {
             crate::store::NodeType::File { size, .. } => format!("file:{}", size),
             crate::store::NodeType::Directory => "directory".to_string(),
+            crate::store::NodeType::Symlink { ref target } => {
+                format!("symlink:{}", target.display())
+            }
         },
         "frames": frames_json,
         "frame_count": frames.len(),
         "total_frame_count": context.frame_count,
-    });
-
-    serde_json::to_string_pretty(&result)
-        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+    })
 }
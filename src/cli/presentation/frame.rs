@@ -0,0 +1,98 @@
+//! Frame inspection presentation: text and json formatters for `frame show`.
+
+use crate::cli::{fail_mark, pass_mark, to_json_output};
+use crate::context::frame::inspect::{
+    BasisFrameRef, BasisInspection, BasisNodeRef, FrameInspection,
+};
+use crate::error::ApiError;
+
+pub fn format_frame_show_text(inspection: &FrameInspection) -> String {
+    let mut output = format!(
+        "Frame: {}\nBlob: {}\n",
+        inspection.frame_id, inspection.blob_path
+    );
+
+    let integrity = &inspection.integrity;
+    if integrity.verified {
+        output.push_str(&format!("Integrity: {} hash verified\n", pass_mark()));
+    } else {
+        output.push_str(&format!(
+            "Integrity: {} hash mismatch (stored {}, computed {})\n",
+            fail_mark(),
+            integrity.stored_id,
+            integrity.computed_id
+        ));
+    }
+
+    output.push_str(&format!("Basis: {}\n", inspection.basis.kind));
+    if let Some(node) = &inspection.basis.node {
+        output.push_str(&format!(
+            "  Node: {} ({})\n",
+            node.node_id,
+            basis_node_label(node)
+        ));
+    }
+    if let Some(frame) = &inspection.basis.frame {
+        output.push_str(&format!(
+            "  Frame: {} ({})\n",
+            frame.frame_id,
+            basis_frame_label(frame)
+        ));
+    }
+
+    output.push_str(&format!("Frame Type: {}\n", inspection.frame_type));
+    output.push_str(&format!("Agent: {}\n", inspection.agent_id));
+    output.push_str(&format!("Timestamp: {}\n", inspection.timestamp));
+    if inspection.metadata.is_empty() {
+        output.push_str("Metadata: (none)\n");
+    } else {
+        output.push_str("Metadata:\n");
+        for (key, value) in &inspection.metadata {
+            output.push_str(&format!("  {}: {}\n", key, value));
+        }
+    }
+
+    output.push_str(&format!("Content: {} bytes\n", inspection.content_bytes));
+    if inspection.content_binary {
+        output.push_str("[Binary content - not UTF-8]\n");
+    } else {
+        output.push_str(&inspection.content);
+        output.push('\n');
+        if inspection.content_truncated {
+            output.push_str("[preview truncated; pass --full for the complete content]\n");
+        }
+    }
+    output
+}
+
+/// One-line basis description for `context get --resolve-basis` text output.
+pub(super) fn format_basis_summary(basis: &BasisInspection) -> String {
+    let mut parts = Vec::new();
+    if let Some(node) = &basis.node {
+        parts.push(format!("node {}", basis_node_label(node)));
+    }
+    if let Some(frame) = &basis.frame {
+        parts.push(format!(
+            "frame {} ({})",
+            frame.frame_id,
+            basis_frame_label(frame)
+        ));
+    }
+    parts.join(" + ")
+}
+
+fn basis_node_label(node: &BasisNodeRef) -> &str {
+    node.path.as_deref().unwrap_or("[node record missing]")
+}
+
+fn basis_frame_label(frame: &BasisFrameRef) -> String {
+    match (&frame.frame_type, &frame.agent_id) {
+        (Some(frame_type), Some(agent_id)) => format!("{} by {}", frame_type, agent_id),
+        _ => "[not in frame storage]".to_string(),
+    }
+}
+
+pub fn format_frame_show_json(inspection: &FrameInspection) -> Result<String, ApiError> {
+    to_json_output(inspection)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+}
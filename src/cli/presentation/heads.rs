@@ -0,0 +1,53 @@
+//! Head snapshot presentation: text and json formatters for `heads` commands.
+
+use crate::api::HeadRestoreResult;
+use crate::cli::to_json_output;
+use crate::error::ApiError;
+use crate::heads::snapshot::HeadSnapshotInfo;
+
+pub fn format_head_snapshot_result(info: &HeadSnapshotInfo) -> String {
+    format!(
+        "Saved head snapshot '{}' ({} head(s)) to {}",
+        info.name, info.head_count, info.path
+    )
+}
+
+pub fn format_head_snapshots_text(snapshots: &[HeadSnapshotInfo]) -> String {
+    if snapshots.is_empty() {
+        return "No head snapshots saved.".to_string();
+    }
+    let mut output = String::from("Head Snapshots:\n");
+    for snapshot in snapshots {
+        output.push_str(&format!(
+            "  {:<32} {:>8} head(s)  {}\n",
+            snapshot.name, snapshot.head_count, snapshot.created_at_ms
+        ));
+    }
+    output.push_str(&format!("\nTotal: {} snapshot(s)\n", snapshots.len()));
+    output
+}
+
+pub fn format_head_snapshots_json(snapshots: &[HeadSnapshotInfo]) -> Result<String, ApiError> {
+    let out = serde_json::json!({ "snapshots": snapshots, "total": snapshots.len() });
+    to_json_output(&out)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+}
+
+pub fn format_head_restore_result(result: &HeadRestoreResult) -> String {
+    let mut output = format!(
+        "Restored head snapshot '{}': {} head(s) moved, {} removed, {} unchanged",
+        result.snapshot, result.heads_selected, result.heads_removed, result.heads_unchanged
+    );
+    if result.dangling_heads_skipped > 0 {
+        output.push_str(&format!(
+            "\nSkipped {} head(s) whose frame no longer exists",
+            result.dangling_heads_skipped
+        ));
+    }
+    output
+}
+
+pub fn format_head_restore_json(result: &HeadRestoreResult) -> Result<String, ApiError> {
+    to_json_output(result)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+}
@@ -1,9 +1,38 @@
 //! Init command presentation: preview and summary formatters.
 
 use crate::agent::AgentStorage;
-use crate::init::{InitPreview, InitSummary};
+use crate::cli::{fail_mark, pass_mark};
+use crate::init::{InitCheck, InitPreview, InitSummary, InitTemplate, DEFAULT_TEMPLATE_NAME};
 use std::path::PathBuf;
 
+pub fn format_init_templates(templates: &[InitTemplate]) -> String {
+    let mut output = String::from("Init templates:\n\n");
+    for template in templates {
+        output.push_str(&format!("  {}\n", template.name));
+        output.push_str(&format!("    {}\n", template.description));
+        output.push_str(&format!(
+            "    agents: {}\n",
+            template.agent_ids().join(", ")
+        ));
+        if let Some(provider) = &template.provider {
+            output.push_str(&format!(
+                "    provider: {} ({})\n",
+                provider.name, provider.model
+            ));
+        }
+    }
+    output.push_str("\nRun 'meld init --template <name>' to initialize a template.\n");
+    output
+}
+
+fn init_command(template: &str) -> String {
+    if template == DEFAULT_TEMPLATE_NAME {
+        "meld init".to_string()
+    } else {
+        format!("meld init --template {}", template)
+    }
+}
+
 pub fn format_init_preview(preview: &InitPreview) -> String {
     let mut output = String::from("Initialization Preview:\n\n");
 
@@ -31,10 +60,78 @@ pub fn format_init_preview(preview: &InitPreview) -> String {
         output.push('\n');
     }
 
-    if preview.prompts.is_empty() && preview.workflows.is_empty() && preview.agents.is_empty() {
+    if !preview.providers.is_empty() {
+        output.push_str("Would create providers:\n");
+        for provider in &preview.providers {
+            output.push_str(&format!("  - {}.toml\n", provider));
+        }
+        output.push('\n');
+    }
+
+    if preview.prompts.is_empty()
+        && preview.workflows.is_empty()
+        && preview.agents.is_empty()
+        && preview.providers.is_empty()
+    {
         output.push_str("All default agents, prompts, and workflows already exist.\n");
     } else {
-        output.push_str("Run 'meld init' to perform initialization.\n");
+        output.push_str(&format!(
+            "Run '{}' to perform initialization.\n",
+            init_command(&preview.template)
+        ));
+    }
+    output
+}
+
+pub fn format_init_check(check: &InitCheck) -> String {
+    let mut output = String::from("Installation Check:\n\n");
+
+    if !check.missing.is_empty() {
+        output.push_str("Missing:\n");
+        for item in &check.missing {
+            output.push_str(&format!("  {} {}\n", fail_mark(), item));
+        }
+        output.push('\n');
+    }
+
+    if !check.broken.is_empty() {
+        output.push_str("Broken:\n");
+        for item in &check.broken {
+            output.push_str(&format!("  {} {}\n", fail_mark(), item));
+        }
+        output.push('\n');
+    }
+
+    if !check.validation.results.is_empty() {
+        output.push_str("Agents:\n");
+        for (agent_id, is_valid, errors) in &check.validation.results {
+            if *is_valid {
+                output.push_str(&format!("  {} {} validated\n", pass_mark(), agent_id));
+            } else {
+                output.push_str(&format!(
+                    "  {} {} validation failed:\n",
+                    fail_mark(),
+                    agent_id
+                ));
+                for error in errors {
+                    output.push_str(&format!("    - {}\n", error));
+                }
+            }
+        }
+        output.push('\n');
+    }
+
+    if check.is_healthy() {
+        output.push_str(&format!(
+            "{} Installation is complete and valid.\n",
+            pass_mark()
+        ));
+    } else {
+        output.push_str(&format!(
+            "Installation is incomplete. Run '{}' to restore missing files, or '{} --force' to overwrite broken ones.\n",
+            init_command(&check.template),
+            init_command(&check.template)
+        ));
     }
     output
 }
@@ -49,9 +146,9 @@ pub fn format_init_summary(summary: &InitSummary, force: bool) -> String {
         output.push_str(&format!("Created prompts directory: {}\n", prompts_dir));
         for prompt in &summary.prompts.created {
             if force {
-                output.push_str(&format!("  ✓ {} (overwritten)\n", prompt));
+                output.push_str(&format!("  {} {} (overwritten)\n", pass_mark(), prompt));
             } else {
-                output.push_str(&format!("  ✓ {}\n", prompt));
+                output.push_str(&format!("  {} {}\n", pass_mark(), prompt));
             }
         }
         for prompt in &summary.prompts.skipped {
@@ -68,9 +165,9 @@ pub fn format_init_summary(summary: &InitSummary, force: bool) -> String {
         output.push_str(&format!("Created workflows directory: {}\n", workflows_dir));
         for workflow in &summary.workflows.created {
             if force {
-                output.push_str(&format!("  ✓ {} (overwritten)\n", workflow));
+                output.push_str(&format!("  {} {} (overwritten)\n", pass_mark(), workflow));
             } else {
-                output.push_str(&format!("  ✓ {}\n", workflow));
+                output.push_str(&format!("  {} {}\n", pass_mark(), workflow));
             }
         }
         for workflow in &summary.workflows.skipped {
@@ -94,11 +191,18 @@ pub fn format_init_summary(summary: &InitSummary, force: bool) -> String {
             };
             if force {
                 output.push_str(&format!(
-                    "  ✓ {}.toml ({}) (overwritten)\n",
-                    agent, role_str
+                    "  {} {}.toml ({}) (overwritten)\n",
+                    pass_mark(),
+                    agent,
+                    role_str
                 ));
             } else {
-                output.push_str(&format!("  ✓ {}.toml ({})\n", agent, role_str));
+                output.push_str(&format!(
+                    "  {} {}.toml ({})\n",
+                    pass_mark(),
+                    agent,
+                    role_str
+                ));
             }
         }
         for agent in &summary.agents.skipped {
@@ -116,19 +220,48 @@ pub fn format_init_summary(summary: &InitSummary, force: bool) -> String {
         output.push('\n');
     }
 
+    if !summary.providers.created.is_empty() || !summary.providers.skipped.is_empty() {
+        let providers_dir = crate::config::xdg::providers_dir()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| "~/.config/meld/providers/".to_string());
+        output.push_str(&format!("Created providers directory: {}\n", providers_dir));
+        for provider in &summary.providers.created {
+            if force {
+                output.push_str(&format!(
+                    "  {} {}.toml (overwritten)\n",
+                    pass_mark(),
+                    provider
+                ));
+            } else {
+                output.push_str(&format!("  {} {}.toml\n", pass_mark(), provider));
+            }
+        }
+        for provider in &summary.providers.skipped {
+            output.push_str(&format!(
+                "  ⊘ {}.toml (already exists, skipped)\n",
+                provider
+            ));
+        }
+        output.push('\n');
+    }
+
     if !summary.prompts.errors.is_empty()
         || !summary.workflows.errors.is_empty()
         || !summary.agents.errors.is_empty()
+        || !summary.providers.errors.is_empty()
     {
         output.push_str("Errors:\n");
         for error in &summary.prompts.errors {
-            output.push_str(&format!("  ✗ {}\n", error));
+            output.push_str(&format!("  {} {}\n", fail_mark(), error));
         }
         for error in &summary.workflows.errors {
-            output.push_str(&format!("  ✗ {}\n", error));
+            output.push_str(&format!("  {} {}\n", fail_mark(), error));
         }
         for error in &summary.agents.errors {
-            output.push_str(&format!("  ✗ {}\n", error));
+            output.push_str(&format!("  {} {}\n", fail_mark(), error));
+        }
+        for error in &summary.providers.errors {
+            output.push_str(&format!("  {} {}\n", fail_mark(), error));
         }
         output.push('\n');
     }
@@ -140,14 +273,21 @@ pub fn format_init_summary(summary: &InitSummary, force: bool) -> String {
         .all(|(_, is_valid, _)| *is_valid);
     if all_valid {
         output.push_str("Validation:\n");
-        output.push_str("  ✓ All agents validated successfully\n\n");
+        output.push_str(&format!(
+            "  {} All agents validated successfully\n\n",
+            pass_mark()
+        ));
     } else {
         output.push_str("Validation:\n");
         for (agent_id, is_valid, errors) in &summary.validation.results {
             if *is_valid {
-                output.push_str(&format!("  ✓ {} validated\n", agent_id));
+                output.push_str(&format!("  {} {} validated\n", pass_mark(), agent_id));
             } else {
-                output.push_str(&format!("  ✗ {} validation failed:\n", agent_id));
+                output.push_str(&format!(
+                    "  {} {} validation failed:\n",
+                    fail_mark(),
+                    agent_id
+                ));
                 for error in errors {
                     output.push_str(&format!("    - {}\n", error));
                 }
@@ -159,6 +299,7 @@ pub fn format_init_summary(summary: &InitSummary, force: bool) -> String {
     if summary.prompts.created.is_empty()
         && summary.workflows.created.is_empty()
         && summary.agents.created.is_empty()
+        && summary.providers.created.is_empty()
         && !force
     {
         output.push_str(
@@ -1,6 +1,10 @@
 //! Provider command presentation: list, show, validation, test text/json.
 
-use crate::provider::commands::{ProviderListResult, ProviderShowResult, ProviderTestResult};
+use crate::cli::{fail_mark, pass_mark, to_json_output};
+use crate::provider::commands::{
+    ProviderListResult, ProviderPromptTestResult, ProviderShowResult, ProviderTestResult,
+    ProviderValidateAllResult,
+};
 use crate::provider::profile::provider_type_slug;
 use crate::provider::ValidationResult;
 use serde_json::json;
@@ -15,10 +19,21 @@ pub fn format_provider_list_result_text(result: &ProviderListResult) -> String {
         let type_str = provider_type_slug(provider.provider_type);
         let endpoint_str = provider.endpoint.as_deref().unwrap_or("(default endpoint)");
         let provider_name = provider.provider_name.as_deref().unwrap_or("unknown");
-        output.push_str(&format!(
-            "  {:<20} {:<10} {:<20} {}\n",
-            provider_name, type_str, provider.model, endpoint_str
-        ));
+        if let Some(latencies) = &result.latencies {
+            let latency_str = latencies
+                .get(provider_name)
+                .map(|stats| format!("{:.0} ms", stats.average_ms))
+                .unwrap_or_else(|| "n/a".to_string());
+            output.push_str(&format!(
+                "  {:<20} {:<10} {:<20} {:<10} {}\n",
+                provider_name, type_str, provider.model, latency_str, endpoint_str
+            ));
+        } else {
+            output.push_str(&format!(
+                "  {:<20} {:<10} {:<20} {}\n",
+                provider_name, type_str, provider.model, endpoint_str
+            ));
+        }
     }
     output.push_str(&format!("\nTotal: {} provider(s)\n", providers.len()));
     output
@@ -30,16 +45,23 @@ pub fn format_provider_list_result_json(result: &ProviderListResult) -> String {
         .iter()
         .map(|provider| {
             let type_str = provider_type_slug(provider.provider_type);
-            json!({
-                "provider_name": provider.provider_name.as_deref().unwrap_or("unknown"),
+            let provider_name = provider.provider_name.as_deref().unwrap_or("unknown");
+            let mut entry = json!({
+                "provider_name": provider_name,
                 "provider_type": type_str,
                 "model": provider.model,
                 "endpoint": provider.endpoint,
-            })
+            });
+            if let Some(latencies) = &result.latencies {
+                let stats = latencies.get(provider_name);
+                entry["avg_latency_ms"] = json!(stats.map(|stats| stats.average_ms));
+                entry["latency_samples"] = json!(stats.map_or(0, |stats| stats.samples));
+            }
+            entry
         })
         .collect();
     let out = json!({ "providers": provider_list, "total": result.providers.len() });
-    serde_json::to_string_pretty(&out).unwrap_or_else(|_| "{}".to_string())
+    to_json_output(&out).unwrap_or_else(|_| "{}".to_string())
 }
 
 pub fn format_provider_show_result_text(result: &ProviderShowResult) -> String {
@@ -78,6 +100,12 @@ pub fn format_provider_show_result_text(result: &ProviderShowResult) -> String {
     if let Some(ref stop) = provider.default_options.stop {
         output.push_str(&format!("  stop: {:?}\n", stop));
     }
+    if let Some(retry_max_tokens) = provider.default_options.truncation_retry_max_tokens {
+        output.push_str(&format!(
+            "  truncation_retry_max_tokens: {}\n",
+            retry_max_tokens
+        ));
+    }
     if !provider.default_options.additional_json.is_empty() {
         output.push_str(&format!(
             "  additional_json: {}\n",
@@ -106,6 +134,7 @@ pub fn format_provider_show_result_json(result: &ProviderShowResult) -> String {
         "presence_penalty": provider.default_options.presence_penalty,
         "stop": provider.default_options.stop,
         "additional_json": provider.default_options.additional_json,
+        "truncation_retry_max_tokens": provider.default_options.truncation_retry_max_tokens,
     });
     let out = json!({
         "provider_name": provider.provider_name.as_deref().unwrap_or("unknown"),
@@ -115,7 +144,7 @@ pub fn format_provider_show_result_json(result: &ProviderShowResult) -> String {
         "api_key_status": api_key_status_str,
         "default_options": default_options,
     });
-    serde_json::to_string_pretty(&out).unwrap_or_else(|_| "{}".to_string())
+    to_json_output(&out).unwrap_or_else(|_| "{}".to_string())
 }
 
 pub fn format_provider_validation_result(result: &ValidationResult, verbose: bool) -> String {
@@ -125,19 +154,19 @@ pub fn format_provider_validation_result(result: &ValidationResult, verbose: boo
         && result.warnings.is_empty()
         && result.checks.iter().all(|(_, passed)| *passed)
     {
-        output.push_str("✓ All validation checks passed\n\n");
+        output.push_str(&format!("{} All validation checks passed\n\n", pass_mark()));
     } else {
         for (description, passed) in &result.checks {
             if *passed {
-                output.push_str(&format!("✓ {}\n", description));
+                output.push_str(&format!("{} {}\n", pass_mark(), description));
             } else {
-                output.push_str(&format!("✗ {}\n", description));
+                output.push_str(&format!("{} {}\n", fail_mark(), description));
             }
         }
         if !result.errors.is_empty() {
             output.push_str("\nErrors:\n");
             for error in &result.errors {
-                output.push_str(&format!("✗ {}\n", error));
+                output.push_str(&format!("{} {}\n", fail_mark(), error));
             }
         }
         if !result.warnings.is_empty() {
@@ -168,24 +197,86 @@ pub fn format_provider_validation_result(result: &ValidationResult, verbose: boo
     output
 }
 
+pub fn format_provider_validation_results_all(
+    result: &ProviderValidateAllResult,
+    verbose: bool,
+) -> String {
+    let mut output = String::from("Validating all providers:\n\n");
+    for validation in &result.results {
+        if validation.is_valid() {
+            if verbose {
+                output.push_str(&format!(
+                    "{} {}: All checks passed ({}/{} checks)\n",
+                    pass_mark(),
+                    validation.provider_name,
+                    validation.passed_checks(),
+                    validation.total_checks()
+                ));
+            } else {
+                output.push_str(&format!(
+                    "{} {}: Valid\n",
+                    pass_mark(),
+                    validation.provider_name
+                ));
+            }
+            for warning in &validation.warnings {
+                output.push_str(&format!("  ⚠ {}\n", warning));
+            }
+        } else {
+            output.push_str(&format!(
+                "{} {}: Validation failed\n",
+                fail_mark(),
+                validation.provider_name
+            ));
+            for error in &validation.errors {
+                output.push_str(&format!("  {} {}\n", fail_mark(), error));
+            }
+        }
+    }
+    let invalid_count = result.invalid_count();
+    output.push_str(&format!(
+        "\nSummary: {} valid, {} invalid (out of {} total)\n",
+        result.results.len() - invalid_count,
+        invalid_count,
+        result.results.len()
+    ));
+    if result.skipped > 0 {
+        output.push_str(&format!(
+            "Stopped at first invalid provider (--fail-fast); {} provider(s) not validated\n",
+            result.skipped
+        ));
+    }
+    output
+}
+
 pub fn format_provider_test_result(
     result: &ProviderTestResult,
     elapsed_ms: Option<u128>,
 ) -> String {
     let mut output = format!("Testing provider: {}\n\n", result.provider_name);
-    output.push_str("✓ Provider client created\n");
+    output.push_str(&format!("{} Provider client created\n", pass_mark()));
     if result.connectivity_ok {
         output.push_str(&match elapsed_ms {
-            Some(ms) => format!("✓ API connectivity: OK ({}ms)\n", ms),
-            None => "✓ API connectivity: OK\n".to_string(),
+            Some(ms) => format!("{} API connectivity: OK ({}ms)\n", pass_mark(), ms),
+            None => format!("{} API connectivity: OK\n", pass_mark()),
         });
-        if result.model_available {
+        if let Some(ref reason) = result.model_check_skipped {
+            output.push_str(&format!(
+                "⚠ Model '{}' availability not checked: {}\n",
+                result.model_checked, reason
+            ));
+        } else if result.model_available {
             output.push_str(&format!(
-                "✓ Model '{}' is available\n",
+                "{} Model '{}' is available\n",
+                pass_mark(),
                 result.model_checked
             ));
         } else {
-            output.push_str(&format!("✗ Model '{}' not found\n", result.model_checked));
+            output.push_str(&format!(
+                "{} Model '{}' not found\n",
+                fail_mark(),
+                result.model_checked
+            ));
             output.push_str(&format!(
                 "Available models: {}\n",
                 result.available_models.join(", ")
@@ -194,10 +285,50 @@ pub fn format_provider_test_result(
         }
     } else {
         if let Some(ref msg) = result.error_message {
-            output.push_str(&format!("✗ API connectivity failed: {}\n", msg));
+            output.push_str(&format!(
+                "{} API connectivity failed: {}\n",
+                fail_mark(),
+                msg
+            ));
         }
         return output;
     }
     output.push_str("\nProvider is working correctly.\n");
     output
 }
+
+/// Text for `provider test --prompt`: the raw completion followed by finish reason and usage.
+pub fn format_provider_prompt_test_result(
+    result: &ProviderPromptTestResult,
+    elapsed_ms: Option<u128>,
+) -> String {
+    let mut output = format!(
+        "Testing provider: {} (model: {})\nPrompt: {}\n\n",
+        result.provider_name, result.model, result.prompt
+    );
+    let Some(response) = result.response.as_ref() else {
+        if let Some(ref msg) = result.error_message {
+            output.push_str(&format!("{} Completion failed: {}\n", fail_mark(), msg));
+        }
+        return output;
+    };
+    output.push_str(&match elapsed_ms {
+        Some(ms) => format!("{} Completion received ({}ms)\n", pass_mark(), ms),
+        None => format!("{} Completion received\n", pass_mark()),
+    });
+    output.push_str("\n--- response ---\n");
+    output.push_str(&response.content);
+    if !response.content.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str("--- end response ---\n\n");
+    output.push_str(&format!(
+        "Finish reason: {}\n",
+        response.finish_reason.as_deref().unwrap_or("unknown")
+    ));
+    output.push_str(&format!(
+        "Tokens: prompt={}, completion={}, total={}\n",
+        response.usage.prompt_tokens, response.usage.completion_tokens, response.usage.total_tokens
+    ));
+    output
+}
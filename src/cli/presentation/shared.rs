@@ -1,7 +1,13 @@
-//! Shared presentation: workspace result formatters (validate, ignore, list_deleted).
+//! Shared presentation: workspace result formatters (validate, repair, ignore, list_deleted, size,
+//! tree, scan stats).
 
+use crate::cli::{styled_table, to_json_output};
 use crate::error::ApiError;
-use crate::workspace::{IgnoreResult, ListDeletedResult, ValidateResult};
+use crate::tree::stats::TreeStats;
+use crate::workspace::{
+    IgnoreResult, ListDeletedResult, NodeTreeEntry, RepairKind, RepairReport, ValidateResult,
+    WorkspaceSizeResult,
+};
 
 pub fn format_validate_result_text(result: &ValidateResult) -> String {
     if result.errors.is_empty() && result.warnings.is_empty() {
@@ -30,11 +36,54 @@ pub fn format_validate_result_text(result: &ValidateResult) -> String {
     }
 }
 
+pub fn format_repair_report_text(report: &RepairReport) -> String {
+    let mut s = if report.actions.is_empty() {
+        "Repair: no inconsistencies found".to_string()
+    } else if report.dry_run {
+        format!("Repair dry run: {} issue(s) found", report.actions.len())
+    } else {
+        format!(
+            "Repair: {} of {} issue(s) repaired",
+            report.applied_count(),
+            report.actions.len()
+        )
+    };
+    for action in &report.actions {
+        let remedy = match action.kind {
+            RepairKind::DanglingHead | RepairKind::OrphanHeadEntry => "remove head entry",
+            RepairKind::UntombstonedHead => "tombstone head",
+            RepairKind::OrphanFrame => "delete frame blob",
+        };
+        let target = match (&action.node_id, &action.frame_type) {
+            (Some(node_id), Some(frame_type)) => format!(
+                "node {} ({}) frame {}",
+                node_id, frame_type, action.frame_id
+            ),
+            _ => format!("frame {}", action.frame_id),
+        };
+        let outcome = match &action.skipped_reason {
+            Some(reason) => format!("skipped {}: {}", remedy, reason),
+            None if report.dry_run => format!("would {}", remedy),
+            None if action.applied => format!("{}: done", remedy),
+            None => format!("{}: no longer needed", remedy),
+        };
+        s.push_str(&format!(
+            "\n  - [{}] {}: {}",
+            action.kind.as_str(),
+            target,
+            outcome
+        ));
+    }
+    s.push_str("\n\n");
+    s.push_str(&format_validate_result_text(&report.validation));
+    s
+}
+
 pub fn format_ignore_result(result: &IgnoreResult, format: &str) -> Result<String, ApiError> {
     match (result, format) {
         (IgnoreResult::List { entries }, "json") => {
             let out = serde_json::json!({ "ignored": entries });
-            serde_json::to_string_pretty(&out).map_err(|e| {
+            to_json_output(&out).map_err(|e| {
                 ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
             })
         }
@@ -72,14 +121,14 @@ pub fn format_list_deleted_result(
                 })
             })
             .collect();
-        return serde_json::to_string_pretty(&arr).map_err(|e| {
+        return to_json_output(&arr).map_err(|e| {
             ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
         });
     }
-    use comfy_table::Table;
-    let mut table = Table::new();
-    table.load_preset(comfy_table::presets::UTF8_FULL);
-    table.set_header(vec!["Path", "Node ID", "Tombstoned At", "Age"]);
+    let mut table = styled_table(
+        comfy_table::presets::UTF8_FULL,
+        &["Path", "Node ID", "Tombstoned At", "Age"],
+    );
     for r in &result.rows {
         let ts_str = if r.tombstoned_at > 0 {
             format!("{}", r.tombstoned_at)
@@ -90,3 +139,144 @@ pub fn format_list_deleted_result(
     }
     Ok(table.to_string())
 }
+
+pub fn format_workspace_size_result(
+    result: &WorkspaceSizeResult,
+    format: &str,
+) -> Result<String, ApiError> {
+    if format == "json" {
+        return to_json_output(result).map_err(|e| {
+            ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
+        });
+    }
+    let mut table = styled_table(
+        comfy_table::presets::UTF8_FULL,
+        &["Component", "Size", "Path"],
+    );
+    for c in &result.components {
+        table.add_row(vec![
+            c.component.clone(),
+            format_bytes(c.bytes),
+            c.path.clone(),
+        ]);
+    }
+    table.add_row(vec![
+        "total".to_string(),
+        format_bytes(result.total_bytes),
+        String::new(),
+    ]);
+    let mut out = table.to_string();
+    if let Some(ref by_type) = result.frames_by_type {
+        let mut types = styled_table(
+            comfy_table::presets::UTF8_FULL,
+            &["Frame type", "Frames", "Size"],
+        );
+        for t in by_type {
+            types.add_row(vec![
+                t.frame_type.clone(),
+                t.frame_count.to_string(),
+                format_bytes(t.bytes),
+            ]);
+        }
+        out.push_str("\n\nFrame storage by type:\n");
+        out.push_str(&types.to_string());
+    }
+    Ok(out)
+}
+
+/// Append `scan --stats` output to the scan summary; json wraps both in one object.
+pub fn format_scan_stats(
+    message: &str,
+    stats: &TreeStats,
+    format: &str,
+) -> Result<String, ApiError> {
+    if format == "json" {
+        let out = serde_json::json!({ "message": message, "stats": stats });
+        return to_json_output(&out).map_err(|e| {
+            ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
+        });
+    }
+    let classes = stats
+        .file_classes
+        .iter()
+        .map(|(class, count)| format!("{}: {}", class, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut out = format!("{}\n\nTree stats:\n", message);
+    if classes.is_empty() {
+        out.push_str(&format!("  Files: {}\n", stats.file_count));
+    } else {
+        out.push_str(&format!("  Files: {} ({})\n", stats.file_count, classes));
+    }
+    out.push_str(&format!("  Directories: {}\n", stats.directory_count));
+    if stats.symlink_count > 0 {
+        out.push_str(&format!("  Symlinks: {}\n", stats.symlink_count));
+    }
+    out.push_str(&format!(
+        "  Total size: {} ({} bytes)\n",
+        format_bytes(stats.total_bytes),
+        stats.total_bytes
+    ));
+    if let Some(path) = &stats.deepest_path {
+        out.push_str(&format!(
+            "  Deepest path: {} (depth {})\n",
+            path, stats.max_depth
+        ));
+    }
+    if !stats.largest_files.is_empty() {
+        let mut table = styled_table(comfy_table::presets::UTF8_FULL, &["Largest files", "Size"]);
+        for file in &stats.largest_files {
+            table.add_row(vec![file.path.clone(), format_bytes(file.bytes)]);
+        }
+        out.push('\n');
+        out.push_str(&table.to_string());
+    }
+    Ok(out)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+pub fn format_node_tree(tree: &NodeTreeEntry, format: &str) -> Result<String, ApiError> {
+    if format == "json" {
+        return to_json_output(tree).map_err(|e| {
+            ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
+        });
+    }
+    let mut out = String::new();
+    push_tree_line(&mut out, tree, 0);
+    Ok(out.trim_end().to_string())
+}
+
+fn push_tree_line(out: &mut String, entry: &NodeTreeEntry, depth: usize) {
+    out.push_str(&"  ".repeat(depth));
+    out.push_str(&entry.name);
+    if entry.node_type == "directory" && entry.name != "." {
+        out.push('/');
+    }
+    if let Some(count) = entry.head_frames {
+        out.push_str(&format!(
+            " [{} frame{}]",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+    if entry.truncated {
+        out.push_str(" ...");
+    }
+    out.push('\n');
+    for child in &entry.children {
+        push_tree_line(out, child, depth + 1);
+    }
+}
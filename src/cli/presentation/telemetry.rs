@@ -0,0 +1,45 @@
+//! Telemetry presentation: session list and export summaries.
+
+use crate::cli::to_json_output;
+use crate::error::ApiError;
+use crate::telemetry::export::{SessionExportResult, SessionListEntry};
+use std::path::Path;
+
+pub fn format_telemetry_sessions_text(sessions: &[SessionListEntry]) -> String {
+    if sessions.is_empty() {
+        return "No telemetry sessions recorded.".to_string();
+    }
+    let mut output = String::from("Recent Sessions:\n");
+    for session in sessions {
+        let duration = session
+            .duration_ms
+            .map(|ms| format!("{}ms", ms))
+            .unwrap_or_else(|| "-".to_string());
+        let success = match session.success {
+            Some(true) => "ok",
+            Some(false) => "failed",
+            None => session.status.as_str(),
+        };
+        output.push_str(&format!(
+            "  {:<40} {:<28} {:<15} {:>10} {}\n",
+            session.session_id, session.command, session.started_at_ms, duration, success
+        ));
+    }
+    output.push_str(&format!("\nTotal: {} session(s)\n", sessions.len()));
+    output
+}
+
+pub fn format_telemetry_sessions_json(sessions: &[SessionListEntry]) -> Result<String, ApiError> {
+    let out = serde_json::json!({ "sessions": sessions, "total": sessions.len() });
+    to_json_output(&out)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)))
+}
+
+pub fn format_telemetry_export_result(result: &SessionExportResult, file: &Path) -> String {
+    format!(
+        "Exported {} event(s) from session {} to {}",
+        result.event_count,
+        result.session_id,
+        file.display()
+    )
+}
@@ -1,7 +1,8 @@
+use crate::cli::output::{color_enabled, with_color};
 use crate::cli::parse::{Commands, ContextCommands};
 use crate::events::EventRecord;
 use crate::telemetry::ProgressRuntime;
-use owo_colors::OwoColorize;
+use owo_colors::{OwoColorize, Style};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::io::{self, IsTerminal, Write};
@@ -12,6 +13,7 @@ use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
 const PANEL_REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+const STREAM_ECHO_INTERVAL: Duration = Duration::from_millis(50);
 const DEFAULT_RENDER_WIDTH: usize = 100;
 const MAX_ACTIVE_PATH_WIDTH: usize = 72;
 
@@ -25,10 +27,20 @@ impl LiveProgressHandle {
         runtime: Arc<ProgressRuntime>,
         session_id: &str,
         command: &Commands,
+        color: bool,
     ) -> Option<Self> {
+        if let Commands::Context {
+            command: ContextCommands::Generate(args),
+        } = command
+        {
+            if args.stream {
+                return Some(Self::start_stream_echo(runtime, session_id));
+            }
+        }
+
         let panel_title = match command {
             Commands::Context {
-                command: ContextCommands::Generate { .. },
+                command: ContextCommands::Generate(_),
             } => "meld context generate".to_string(),
             Commands::Context {
                 command: ContextCommands::Regenerate { .. },
@@ -39,7 +51,7 @@ impl LiveProgressHandle {
         if !matches!(
             command,
             Commands::Context {
-                command: ContextCommands::Generate { .. } | ContextCommands::Regenerate { .. }
+                command: ContextCommands::Generate(_) | ContextCommands::Regenerate { .. }
             }
         ) {
             return None;
@@ -54,44 +66,91 @@ impl LiveProgressHandle {
         let thread_runtime = Arc::clone(&runtime);
         let thread_session = session_id.to_string();
 
+        // The panel renders on its own thread, so it takes the command's color choice along.
         let join_handle = thread::spawn(move || {
-            let mut reducer = LivePanelReducer::new();
-            let mut renderer = StderrPanelRenderer::new();
-            let mut last_seq = 0u64;
+            with_color(color, || {
+                let mut reducer = LivePanelReducer::new();
+                let mut renderer = StderrPanelRenderer::new();
+                let mut last_seq = 0u64;
+
+                while !thread_stop.load(Ordering::Relaxed) {
+                    if let Ok(events) = thread_runtime
+                        .store()
+                        .read_events_after(&thread_session, last_seq)
+                    {
+                        for event in events {
+                            last_seq = last_seq.max(event.seq);
+                            reducer.apply(&event);
+                        }
+                        if reducer.has_visible_state() {
+                            let width = renderer.width();
+                            let panel = reducer.render_panel(&panel_title, width);
+                            renderer.render(&panel);
+                        }
+                    }
+                    thread::sleep(PANEL_REFRESH_INTERVAL);
+                }
 
-            while !thread_stop.load(Ordering::Relaxed) {
                 if let Ok(events) = thread_runtime
                     .store()
                     .read_events_after(&thread_session, last_seq)
                 {
                     for event in events {
-                        last_seq = last_seq.max(event.seq);
                         reducer.apply(&event);
                     }
-                    if reducer.has_visible_state() {
-                        let width = renderer.width();
-                        let panel = reducer.render_panel(&panel_title, width);
-                        renderer.render(&panel);
-                    }
                 }
-                thread::sleep(PANEL_REFRESH_INTERVAL);
-            }
+                renderer.clear();
+            })
+        });
+
+        Some(Self {
+            stop_flag,
+            join_handle: Some(join_handle),
+        })
+    }
 
-            if let Ok(events) = thread_runtime
-                .store()
-                .read_events_after(&thread_session, last_seq)
-            {
+    /// Echo `provider_stream_delta` text to stderr in place of the panel, which would
+    /// overwrite it. Unlike the panel, this also runs when stderr is not a terminal.
+    fn start_stream_echo(runtime: Arc<ProgressRuntime>, session_id: &str) -> Self {
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop_flag);
+        let thread_session = session_id.to_string();
+
+        let join_handle = thread::spawn(move || {
+            let mut last_seq = 0u64;
+            let mut ends_with_newline = true;
+            let mut echo = |last_seq: &mut u64| {
+                let Ok(events) = runtime
+                    .store()
+                    .read_events_after(&thread_session, *last_seq)
+                else {
+                    return;
+                };
+                let mut stderr = io::stderr().lock();
                 for event in events {
-                    reducer.apply(&event);
+                    *last_seq = (*last_seq).max(event.seq);
+                    if let Some(text) = stream_delta_text(&event) {
+                        let _ = stderr.write_all(text.as_bytes());
+                        ends_with_newline = text.ends_with('\n');
+                    }
                 }
+                let _ = stderr.flush();
+            };
+
+            while !thread_stop.load(Ordering::Relaxed) {
+                echo(&mut last_seq);
+                thread::sleep(STREAM_ECHO_INTERVAL);
+            }
+            echo(&mut last_seq);
+            if !ends_with_newline {
+                eprintln!();
             }
-            renderer.clear();
         });
 
-        Some(Self {
+        Self {
             stop_flag,
             join_handle: Some(join_handle),
-        })
+        }
     }
 
     pub fn stop(&mut self) {
@@ -240,7 +299,7 @@ impl LivePanelReducer {
 
         let title_line = format!(
             "{} {}",
-            title.bold().bright_cyan(),
+            paint(title, Style::new().bold().bright_cyan()),
             format_elapsed_badge(&elapsed, self.failed > 0)
         );
         let summary_line = join_segments(&[
@@ -282,14 +341,17 @@ impl LivePanelReducer {
         } else if let Some(message) = &self.latest_message {
             format!(
                 "{} {}",
-                "latest".bright_black().bold(),
-                truncate_line(message, width.saturating_sub(8)).yellow()
+                paint("latest", Style::new().bright_black().bold()),
+                paint(
+                    &truncate_line(message, width.saturating_sub(8)),
+                    Style::new().yellow()
+                )
             )
         } else {
             format!(
                 "{} {}",
-                "active".bright_black().bold(),
-                "idle".bright_black()
+                paint("active", Style::new().bright_black().bold()),
+                paint("idle", Style::new().bright_black())
             )
         };
 
@@ -385,9 +447,9 @@ impl StderrPanelRenderer {
 fn format_elapsed_badge(elapsed: &str, has_failures: bool) -> String {
     let badge = format!("{} {}", "live", elapsed);
     if has_failures {
-        badge.red().bold().to_string()
+        paint(&badge, Style::new().red().bold())
     } else {
-        badge.bright_black().to_string()
+        paint(&badge, Style::new().bright_black())
     }
 }
 
@@ -395,10 +457,10 @@ fn format_active_line(active_target: &ActiveTargetState) -> String {
     let path = truncate_line(&active_target.path, MAX_ACTIVE_PATH_WIDTH);
     format!(
         "{} {} {} {}",
-        "active".bright_black().bold(),
-        path.bold(),
-        "•".bright_black(),
-        active_target.stage.cyan().bold()
+        paint("active", Style::new().bright_black().bold()),
+        paint(&path, Style::new().bold()),
+        paint("•", Style::new().bright_black()),
+        paint(&active_target.stage, Style::new().cyan().bold())
     )
 }
 
@@ -418,8 +480,17 @@ fn format_level(current_level: Option<usize>, total_levels: Option<usize>) -> St
     )
 }
 
+/// Apply `style` when color is enabled for this thread, per `--color` and `NO_COLOR`.
+fn paint(text: &str, style: Style) -> String {
+    if color_enabled() {
+        text.style(style).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
 fn join_segments(segments: &[String]) -> String {
-    let divider = format!(" {} ", "|".bright_black());
+    let divider = format!(" {} ", paint("|", Style::new().bright_black()));
     segments.join(&divider)
 }
 
@@ -431,13 +502,13 @@ enum MetricTone {
 }
 
 fn styled_metric(label: &str, value: &str, tone: MetricTone) -> String {
-    let label = label.bright_black().bold().to_string();
+    let label = paint(label, Style::new().bright_black().bold());
     let value = match tone {
-        MetricTone::Good => value.green().bold().to_string(),
-        MetricTone::Info => value.cyan().bold().to_string(),
-        MetricTone::Quiet => value.bright_white().to_string(),
-        MetricTone::Alert(true) => value.red().bold().to_string(),
-        MetricTone::Alert(false) => value.bright_black().to_string(),
+        MetricTone::Good => paint(value, Style::new().green().bold()),
+        MetricTone::Info => paint(value, Style::new().cyan().bold()),
+        MetricTone::Quiet => paint(value, Style::new().bright_white()),
+        MetricTone::Alert(true) => paint(value, Style::new().red().bold()),
+        MetricTone::Alert(false) => paint(value, Style::new().bright_black()),
     };
     format!("{} {}", label, value)
 }
@@ -518,13 +589,20 @@ fn describe_current_work(
 
     format!(
         "{} {} {} {}",
-        "about".bright_black().bold(),
-        ordering.bright_black(),
-        "•".bright_black(),
-        worker_text.bright_black()
+        paint("about", Style::new().bright_black().bold()),
+        paint(ordering, Style::new().bright_black()),
+        paint("•", Style::new().bright_black()),
+        paint(worker_text, Style::new().bright_black())
     )
 }
 
+fn stream_delta_text(event: &EventRecord) -> Option<&str> {
+    if event.event_type != "provider_stream_delta" {
+        return None;
+    }
+    event.data.get("text").and_then(Value::as_str)
+}
+
 fn read_string(data: &Value, key: &str) -> Option<String> {
     data.get(key)
         .and_then(|value| value.as_str())
@@ -689,4 +767,18 @@ mod tests {
         assert!(panel.contains("latest"));
         assert!(panel.contains("boom"));
     }
+
+    #[test]
+    fn panel_styling_follows_color_choice() {
+        let mut reducer = LivePanelReducer::new();
+        reducer.apply(&event(1, "plan_constructed", json!({ "total_nodes": 2 })));
+
+        let plain = with_color(false, || reducer.render_panel("meld context generate", 100));
+        assert!(!plain.contains('\u{1b}'));
+        assert_eq!(plain, strip_ansi(&plain));
+
+        let styled = with_color(true, || reducer.render_panel("meld context generate", 100));
+        assert!(styled.contains('\u{1b}'));
+        assert_eq!(strip_ansi(&styled), plain);
+    }
 }
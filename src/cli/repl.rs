@@ -0,0 +1,274 @@
+//! CLI repl: run commands against one loaded `RunContext`.
+//!
+//! Lines are split shell-style, parsed with the same clap types as one-shot invocations, and
+//! dispatched through `RunContext::execute`. Commands that write agent, provider, or workflow
+//! configuration reload the registries afterwards. Stores and the head index are shared
+//! in-process, and the sled lock keeps other processes from changing them mid-session.
+
+use crate::cli::output::map_error;
+use crate::cli::parse::{
+    AgentCommands, AgentPromptCommands, Commands, ContextCommands, ProviderCommands,
+    WorkspaceCommands,
+};
+use crate::cli::route::RunContext;
+use crate::error::{ApiError, StorageError};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use std::io::{BufRead, Write};
+
+const PROMPT: &str = "meld> ";
+
+const BUILTINS_HELP: &str = "Repl commands:
+  help [COMMAND]   Show this help, or help for a meld command
+  history          List commands entered in this session
+  exit, quit       Leave the repl
+
+Any other line runs a meld command without the `meld` prefix, e.g. `context get --path src`.";
+
+/// Line grammar: one meld subcommand, without the binary name.
+#[derive(Parser)]
+#[command(name = "meld", no_binary_name = true, disable_help_subcommand = true)]
+struct ReplLine {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Result of dispatching one repl line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplOutcome {
+    /// Command or builtin output to print.
+    Output(String),
+    /// Parse or command failure to print; the session continues.
+    Error(String),
+    /// Blank line; nothing to print.
+    Empty,
+    /// `exit` or `quit`.
+    Exit,
+}
+
+/// Interactive session over a single loaded run context.
+pub struct ReplSession<'a> {
+    context: &'a RunContext,
+    history: Vec<String>,
+}
+
+impl<'a> ReplSession<'a> {
+    pub fn new(context: &'a RunContext) -> Self {
+        Self {
+            context,
+            history: Vec::new(),
+        }
+    }
+
+    /// Lines entered so far, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Run one line: a builtin (`help`, `history`, `exit`) or a meld command.
+    pub fn dispatch(&mut self, line: &str) -> ReplOutcome {
+        let line = line.trim();
+        if line.is_empty() {
+            return ReplOutcome::Empty;
+        }
+        self.history.push(line.to_string());
+
+        let mut words = match split_line(line) {
+            Ok(words) => words,
+            Err(message) => return ReplOutcome::Error(message),
+        };
+        match words[0].as_str() {
+            "exit" | "quit" if words.len() == 1 => return ReplOutcome::Exit,
+            "help" if words.len() == 1 => return ReplOutcome::Output(help_text()),
+            "help" => {
+                words.remove(0);
+                words.push("--help".to_string());
+            }
+            "history" if words.len() == 1 => return ReplOutcome::Output(self.format_history()),
+            _ => {}
+        }
+
+        let command = match ReplLine::try_parse_from(&words) {
+            Ok(parsed) => parsed.command,
+            Err(err) => {
+                let rendered = err.render().to_string();
+                return match err.kind() {
+                    ErrorKind::DisplayHelp | ErrorKind::DisplayVersion => {
+                        ReplOutcome::Output(rendered.trim_end().to_string())
+                    }
+                    _ => ReplOutcome::Error(rendered.trim_end().to_string()),
+                };
+            }
+        };
+        if let Some(reason) = unsupported_in_repl(&command) {
+            return ReplOutcome::Error(reason.to_string());
+        }
+
+        let result = self.context.execute(&command);
+        if writes_configuration(&command) {
+            if let Err(err) = self.context.reload_registries() {
+                return ReplOutcome::Error(format!(
+                    "Failed to reload configuration: {}",
+                    map_error(&err)
+                ));
+            }
+        }
+        match result {
+            Ok(output) => ReplOutcome::Output(output),
+            Err(err) => ReplOutcome::Error(map_error(&err)),
+        }
+    }
+
+    fn format_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, line)| format!("{:>4}  {}", i + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Read lines from `input` until EOF or `exit`, writing prompts and results to `output`.
+pub fn run_repl<R: BufRead, W: Write>(
+    context: &RunContext,
+    input: R,
+    output: &mut W,
+) -> Result<(), ApiError> {
+    let mut session = ReplSession::new(context);
+    let mut lines = input.lines();
+    loop {
+        write!(output, "{}", PROMPT).map_err(io_error)?;
+        output.flush().map_err(io_error)?;
+        let Some(line) = lines.next() else {
+            writeln!(output).map_err(io_error)?;
+            return Ok(());
+        };
+        match session.dispatch(&line.map_err(io_error)?) {
+            ReplOutcome::Output(text) => writeln!(output, "{}", text).map_err(io_error)?,
+            ReplOutcome::Error(text) => writeln!(output, "error: {}", text).map_err(io_error)?,
+            ReplOutcome::Empty => {}
+            ReplOutcome::Exit => return Ok(()),
+        }
+    }
+}
+
+fn io_error(err: std::io::Error) -> ApiError {
+    ApiError::StorageError(StorageError::IoError(err))
+}
+
+fn help_text() -> String {
+    let commands = ReplLine::command().render_help().to_string();
+    format!("{}\n\n{}", BUILTINS_HELP, commands.trim_end())
+}
+
+fn unsupported_in_repl(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Repl => Some("Already in a repl session"),
+        Commands::Danger { .. } => {
+            Some("Danger commands need exclusive store access; run them outside the repl")
+        }
+        Commands::Watch { .. } => Some("Watch runs until stopped; run it outside the repl"),
+        // The repl reads its own lines from stdin, so a prompt cannot be read from it too.
+        Commands::Context {
+            command: ContextCommands::Generate(args),
+        } if args.stdin_prompt => {
+            Some("--stdin-prompt reads the repl's own input; use --prompt-file instead")
+        }
+        Commands::Workspace {
+            command: WorkspaceCommands::Compact { vacuum: true, .. },
+        } => Some("--vacuum needs the node store closed; run it outside the repl"),
+        _ => None,
+    }
+}
+
+/// Commands that write agent, provider, prompt, or workflow configuration outside the registries.
+fn writes_configuration(command: &Commands) -> bool {
+    match command {
+        Commands::Init { list, check, .. } => !list && !check,
+        Commands::Agent { command } => matches!(
+            command,
+            AgentCommands::Create { .. }
+                | AgentCommands::Edit { .. }
+                | AgentCommands::Remove { .. }
+                | AgentCommands::Prompt {
+                    command: AgentPromptCommands::Edit { .. }
+                }
+        ),
+        Commands::Provider { command } => matches!(
+            command,
+            ProviderCommands::Create { .. }
+                | ProviderCommands::Edit { .. }
+                | ProviderCommands::Remove { .. }
+        ),
+        _ => false,
+    }
+}
+
+/// Split a line into words: whitespace separates, quotes group, and backslash escapes.
+fn split_line(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| "Trailing backslash in command line".to_string())?;
+                current.push(escaped);
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("Unterminated {} quote in command line", q));
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_line_handles_quotes_and_escapes() {
+        assert_eq!(
+            split_line(r#"context get --path "my dir/a b.md" --separator ' | '"#).unwrap(),
+            vec![
+                "context",
+                "get",
+                "--path",
+                "my dir/a b.md",
+                "--separator",
+                " | "
+            ]
+        );
+        assert_eq!(
+            split_line(r#"a\ b "say \"hi\"" '' "#).unwrap(),
+            vec!["a b", "say \"hi\"", ""]
+        );
+        assert!(split_line("status 'open").is_err());
+    }
+}
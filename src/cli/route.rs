@@ -1,19 +1,20 @@
 //! CLI route: shared runtime context and top-level command dispatch only.
 
 use crate::branches::{BranchHandle, BranchRuntime};
-use crate::cli::parse::Commands;
+use crate::cli::parse::{Commands, TreeCommands};
 use crate::cli::progress::LiveProgressHandle;
 use crate::cli::runtime_assembly::CliRuntimeAssembly;
 use crate::cli::session::{finish_command_session, start_command_session};
-use crate::cli::{command_name, typed_summary_event};
+use crate::cli::summary_line::{supports_summary_only, CommandSummaryLine, SUMMARY_ONLY_COMMANDS};
+use crate::cli::{command_name, resolve_color, typed_summary_event, with_color, with_json_compact};
 use crate::config::ConfigLoader;
 use crate::error::ApiError;
 use crate::session::PrunePolicy;
 use crate::telemetry::emission::{emit_command_summary, truncate_for_summary};
 use crate::telemetry::ProgressRuntime;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::warn;
 
 /// Runtime context for CLI execution: workspace, config paths, and domain facades.
@@ -22,13 +23,17 @@ pub struct RunContext {
     assembly: CliRuntimeAssembly,
     workspace_root: PathBuf,
     config_path: Option<PathBuf>,
-    #[allow(dead_code)]
+    config_overrides: Vec<String>,
+    profile: Option<String>,
     store_path: PathBuf,
     frame_storage_path: PathBuf,
     #[allow(dead_code)]
     artifact_storage_path: PathBuf,
     branch_runtime: BranchRuntime,
     active_branch: BranchHandle,
+    json_compact: bool,
+    color: bool,
+    summary_only: Option<String>,
 }
 
 impl RunContext {
@@ -37,6 +42,11 @@ impl RunContext {
         self.assembly.api().as_ref()
     }
 
+    /// Node store directory. Held open until this context is dropped.
+    pub fn store_path(&self) -> &Path {
+        &self.store_path
+    }
+
     /// Progress runtime for session and event emission.
     pub fn progress_runtime(&self) -> Arc<ProgressRuntime> {
         Arc::clone(self.assembly.progress())
@@ -49,11 +59,33 @@ impl RunContext {
 
     /// Create run context from workspace root and optional config path. Uses ConfigLoader only.
     pub fn new(workspace_root: PathBuf, config_path: Option<PathBuf>) -> Result<Self, ApiError> {
-        let config = if let Some(ref cfg_path) = config_path {
-            ConfigLoader::load_from_file(cfg_path)?
-        } else {
-            ConfigLoader::load(&workspace_root)?
-        };
+        Self::new_with_config_overrides(workspace_root, config_path, Vec::new())
+    }
+
+    /// Create run context, applying `--config-override <dotted.key>=<value>` entries after
+    /// config loading. Overrides are kept and reapplied when registries reload.
+    pub fn new_with_config_overrides(
+        workspace_root: PathBuf,
+        config_path: Option<PathBuf>,
+        config_overrides: Vec<String>,
+    ) -> Result<Self, ApiError> {
+        Self::new_with_profile(workspace_root, config_path, config_overrides, None)
+    }
+
+    /// Create run context with the `--profile` config section applied (`MELD_PROFILE` when
+    /// `profile` is `None`). The profile is kept and reapplied when registries reload.
+    pub fn new_with_profile(
+        workspace_root: PathBuf,
+        config_path: Option<PathBuf>,
+        config_overrides: Vec<String>,
+        profile: Option<String>,
+    ) -> Result<Self, ApiError> {
+        let config = ConfigLoader::load_with_profile(
+            &workspace_root,
+            config_path.as_deref(),
+            &config_overrides,
+            profile.as_deref(),
+        )?;
         let branch_runtime = BranchRuntime::new();
         let active_branch = branch_runtime.resolve_active_branch(&workspace_root)?;
         if let Err(err) = branch_runtime.ensure_active_branch_registered(&active_branch) {
@@ -102,28 +134,100 @@ impl RunContext {
             assembly,
             workspace_root,
             config_path,
+            config_overrides,
+            profile,
             store_path,
             frame_storage_path,
             artifact_storage_path,
             branch_runtime,
             active_branch,
+            json_compact: false,
+            color: resolve_color("auto"),
+            summary_only: None,
         })
     }
 
+    /// Reload workflow, agent, and provider registries from config and XDG storage.
+    ///
+    /// One-shot commands never need this; a long-lived session calls it after commands that
+    /// write configuration so later commands see the change.
+    pub fn reload_registries(&self) -> Result<(), ApiError> {
+        self.assembly.reload_registries(&self.load_config()?)
+    }
+
+    /// Load the workspace config again with this context's `--config`, `--config-override`
+    /// values and profile, as it was loaded at startup.
+    pub fn load_config(&self) -> Result<crate::config::MerkleConfig, ApiError> {
+        Ok(ConfigLoader::load_with_profile(
+            &self.workspace_root,
+            self.config_path.as_deref(),
+            &self.config_overrides,
+            self.profile.as_deref(),
+        )?)
+    }
+
+    /// Render `--format json` output on a single line (`--json-compact`).
+    pub fn json_compact(mut self, compact: bool) -> Self {
+        self.json_compact = compact;
+        self
+    }
+
+    /// Color human-readable output per `--color auto|always|never`.
+    pub fn color(mut self, choice: &str) -> Self {
+        self.color = resolve_color(choice);
+        self
+    }
+
+    /// Replace progress and report output with one summary line (`--summary-only[=json]`).
+    pub fn summary_only(mut self, format: Option<String>) -> Self {
+        self.summary_only = format;
+        self
+    }
+
+    /// Mirror progress events to a file or named pipe as JSON Lines (`--progress-file`).
+    pub fn progress_file(self, path: Option<PathBuf>) -> Self {
+        if let Some(path) = path {
+            self.assembly.progress().mirror_to_file(&path);
+        }
+        self
+    }
+
     /// Execute a CLI command via the single route table.
     pub fn execute(&self, command: &Commands) -> Result<String, ApiError> {
+        if self.summary_only.is_some() && !supports_summary_only(command) {
+            return Err(ApiError::ConfigError(format!(
+                "--summary-only applies only to {}.",
+                SUMMARY_ONLY_COMMANDS
+            )));
+        }
         let started = Instant::now();
         let command_name = command_name(command);
         let session_id = start_command_session(self.assembly.progress().as_ref(), &command_name)?;
         self.assembly
             .api()
             .set_progress_context(Arc::clone(self.assembly.progress()), session_id.clone());
-        let mut live_progress = LiveProgressHandle::start_if_supported(
-            Arc::clone(self.assembly.progress()),
-            &session_id,
-            command,
-        );
-        let result = self.execute_inner(command, &session_id);
+        let mut live_progress = match self.summary_only {
+            Some(_) => None,
+            None => LiveProgressHandle::start_if_supported(
+                Arc::clone(self.assembly.progress()),
+                &session_id,
+                command,
+                self.color,
+            ),
+        };
+        let result = with_json_compact(self.json_compact, || {
+            with_color(self.color, || self.execute_inner(command, &session_id))
+        });
+        let result = match (&self.summary_only, result) {
+            (Some(format), Ok(_)) => self.summary_line(
+                command,
+                &command_name,
+                &session_id,
+                format,
+                started.elapsed().as_millis(),
+            ),
+            (_, result) => result,
+        };
         match self.assembly.graph_runtime().catch_up() {
             Ok(applied_events) => {
                 let last_reduced_seq = match self
@@ -173,7 +277,10 @@ impl RunContext {
         );
         let ok = result.is_ok();
         let err = result.as_ref().err().map(|e| e.to_string());
-        finish_command_session(self.assembly.progress().as_ref(), &session_id, ok, err)?;
+        let finished =
+            finish_command_session(self.assembly.progress().as_ref(), &session_id, ok, err);
+        self.assembly.progress().flush_progress_file();
+        finished?;
         self.assembly.api().clear_progress_context();
         if let Some(handle) = live_progress.as_mut() {
             handle.stop();
@@ -182,13 +289,45 @@ impl RunContext {
         result
     }
 
+    /// Final counts for `--summary-only`, read back from the events of this command's session.
+    fn summary_line(
+        &self,
+        command: &Commands,
+        command_name: &str,
+        session_id: &str,
+        format: &str,
+        duration_ms: u128,
+    ) -> Result<String, ApiError> {
+        let events = self
+            .assembly
+            .progress()
+            .store()
+            .read_events_after(session_id, 0)
+            .map_err(|e| ApiError::ConfigError(format!("Failed to read command events: {}", e)))?;
+        CommandSummaryLine::from_events(command, command_name, &events, duration_ms).render(format)
+    }
+
     fn execute_inner(&self, command: &Commands, session_id: &str) -> Result<String, ApiError> {
         match command {
-            Commands::Scan { force } => crate::workspace::tooling::handle_scan_command(
+            Commands::Scan {
+                force,
+                include_hidden,
+                include_empty_dirs,
+                verify_stable,
+                allow_empty_workspace,
+                stats,
+                format,
+            } => crate::workspace::tooling::handle_scan_command(
                 self.assembly.api().as_ref(),
                 &self.workspace_root,
                 self.assembly.progress(),
                 *force,
+                *include_hidden,
+                *include_empty_dirs,
+                *verify_stable,
+                *allow_empty_workspace,
+                *stats,
+                format,
                 session_id,
             ),
             Commands::Workspace { command } => crate::workspace::tooling::handle_cli_command(
@@ -198,13 +337,39 @@ impl RunContext {
                 &self.frame_storage_path,
                 command,
             ),
+            Commands::Status {
+                format, workspaces, ..
+            } if !workspaces.is_empty() => {
+                crate::workspace::tooling::handle_multi_workspace_status_command(
+                    self.assembly.api().as_ref(),
+                    &self.workspace_root,
+                    &self.store_path,
+                    workspaces,
+                    format,
+                    |root| {
+                        let config = ConfigLoader::load_with_profile(
+                            root,
+                            None,
+                            &self.config_overrides,
+                            self.profile.as_deref(),
+                        )?;
+                        let (store_path, _, _) = config.system.storage.resolve_paths(root)?;
+                        let assembly = CliRuntimeAssembly::load(&root.to_path_buf(), &config)?;
+                        Ok((Arc::clone(assembly.api()), store_path))
+                    },
+                )
+            }
             Commands::Status {
                 format,
                 workspace_only,
                 agents_only,
                 providers_only,
                 breakdown,
+                include_content_hash,
                 test_connectivity,
+                watch,
+                interval,
+                ..
             } => crate::workspace::tooling::handle_status_command(
                 self.assembly.api().as_ref(),
                 &self.workspace_root,
@@ -214,13 +379,36 @@ impl RunContext {
                 *agents_only,
                 *providers_only,
                 *breakdown,
+                *include_content_hash,
                 *test_connectivity,
+                watch.then(|| Duration::from_secs(*interval)),
             ),
             Commands::Validate => crate::workspace::tooling::handle_validate_command(
                 self.assembly.api().as_ref(),
                 &self.workspace_root,
                 &self.frame_storage_path,
             ),
+            Commands::Tree {
+                command: Some(TreeCommands::VerifyDeterminism),
+                ..
+            } => crate::workspace::tooling::handle_tree_verify_determinism_command(
+                self.assembly.api().as_ref(),
+                &self.workspace_root,
+            ),
+            Commands::Tree {
+                command: None,
+                path,
+                depth,
+                frames,
+                format,
+            } => crate::workspace::tooling::handle_tree_command(
+                self.assembly.api().as_ref(),
+                &self.workspace_root,
+                path.as_deref(),
+                *depth,
+                *frames,
+                format,
+            ),
             Commands::Agent { command } => {
                 crate::agent::tooling::handle_cli_command(self.assembly.api().as_ref(), command)
             }
@@ -230,9 +418,19 @@ impl RunContext {
                 command,
                 session_id,
             ),
-            Commands::Init { force, list } => {
-                crate::init::tooling::handle_cli_command(*force, *list)
-            }
+            Commands::Init {
+                force,
+                list,
+                check,
+                template,
+                list_templates,
+            } => crate::init::tooling::handle_cli_command(
+                *force,
+                *list,
+                *check,
+                template.as_deref(),
+                *list_templates,
+            ),
             Commands::Context { command } => crate::context::tooling::handle_cli_command(
                 Arc::clone(self.assembly.api()),
                 &self.workspace_root,
@@ -244,28 +442,47 @@ impl RunContext {
             Commands::Workflow { command } => crate::workflow::tooling::handle_cli_command(
                 self.assembly.api().as_ref(),
                 &self.workspace_root,
-                self.config_path.as_deref(),
+                &|| self.load_config(),
                 self.assembly.workflow_registry(),
                 self.assembly.progress(),
                 command,
                 session_id,
             ),
             Commands::Branches { command } => crate::branches::tooling::handle_cli_command(command),
+            Commands::Telemetry { command } => crate::telemetry::tooling::handle_cli_command(
+                self.assembly.progress().as_ref(),
+                command,
+                session_id,
+            ),
+            Commands::Frame { command } => crate::context::frame::tooling::handle_cli_command(
+                self.assembly.api().as_ref(),
+                command,
+            ),
+            Commands::Heads { command } => {
+                crate::heads::tooling::handle_cli_command(self.assembly.api().as_ref(), command)
+            }
             Commands::Danger { .. } => Err(ApiError::ConfigError(
                 "Danger commands must run from the CLI entry point".to_string(),
             )),
+            Commands::Repl => Err(ApiError::ConfigError(
+                "The repl must run from the CLI entry point".to_string(),
+            )),
             Commands::Watch {
                 debounce_ms,
                 batch_window_ms,
                 foreground: _,
+                once,
+                include_hidden,
             } => crate::workspace::tooling::handle_watch_command(
                 Arc::clone(self.assembly.api()),
                 &self.workspace_root,
-                self.config_path.as_deref(),
+                &self.load_config()?,
                 self.assembly.workflow_registry(),
                 self.assembly.progress(),
                 *debounce_ms,
                 *batch_window_ms,
+                *include_hidden,
+                *once,
                 session_id,
             ),
         }
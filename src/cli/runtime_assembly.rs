@@ -1,11 +1,13 @@
 //! Root runtime assembly for CLI execution.
 
+use crate::agent::AgentRegistry;
 use crate::api::ContextApi;
 use crate::config::MerkleConfig;
 use crate::context::head::backfill_legacy_heads_into_spine;
-use crate::error::ApiError;
+use crate::error::{ApiError, StorageError};
 use crate::heads::HeadIndex;
-use crate::store::persistence::SledNodeRecordStore;
+use crate::provider::ProviderRegistry;
+use crate::store::persistence::{sled_open_error, SledNodeRecordStore};
 use crate::telemetry::ProgressRuntime;
 use crate::workflow::WorkflowRegistry;
 use crate::world_state::graph::runtime::GraphRuntime;
@@ -29,21 +31,31 @@ impl CliRuntimeAssembly {
             &config.workflows,
         )?));
 
-        std::fs::create_dir_all(&store_path)
-            .map_err(|e| ApiError::StorageError(crate::error::StorageError::IoError(e)))?;
-
-        let db = sled::open(&store_path).map_err(|e| {
-            ApiError::StorageError(crate::error::StorageError::IoError(std::io::Error::other(
-                format!("Failed to open sled database: {}", e),
-            )))
+        crate::store::persistence::recover_interrupted_vacuum(&store_path)
+            .map_err(ApiError::from)?;
+        std::fs::create_dir_all(&store_path).map_err(|e| {
+            ApiError::StorageError(StorageError::io_context(
+                "create store directory",
+                &store_path,
+                e,
+            ))
         })?;
-        let node_store = Arc::new(SledNodeRecordStore::from_db(db.clone()));
+
+        let db = sled::open(&store_path)
+            .map_err(|e| ApiError::StorageError(sled_open_error(&store_path, e)))?;
+        let node_store =
+            Arc::new(SledNodeRecordStore::from_db(db.clone()).map_err(ApiError::from)?);
         let progress = Arc::new(ProgressRuntime::new(db.clone()).map_err(ApiError::from)?);
         let graph_runtime = Arc::new(GraphRuntime::new(db).map_err(ApiError::from)?);
         let world_model_queries = Arc::new(WorldModelQueries::new(Arc::clone(&graph_runtime)));
 
-        std::fs::create_dir_all(&frame_storage_path)
-            .map_err(|e| ApiError::StorageError(crate::error::StorageError::IoError(e)))?;
+        std::fs::create_dir_all(&frame_storage_path).map_err(|e| {
+            ApiError::StorageError(StorageError::io_context(
+                "create frame storage directory",
+                &frame_storage_path,
+                e,
+            ))
+        })?;
         std::fs::create_dir_all(&artifact_storage_path)
             .map_err(|e| ApiError::StorageError(crate::error::StorageError::IoError(e)))?;
         let frame_storage = Arc::new(
@@ -63,32 +75,8 @@ impl CliRuntimeAssembly {
                 HeadIndex::new()
             }),
         ));
-        {
-            let head_index_guard = head_index.read();
-            if let Err(err) = backfill_legacy_heads_into_spine(
-                &progress,
-                &head_index_guard,
-                frame_storage.as_ref(),
-                "context_head_backfill",
-            ) {
-                tracing::warn!(error = %err, "failed to backfill legacy heads into spine");
-            }
-        }
-
-        let mut agent_registry = crate::agent::AgentRegistry::new();
-        agent_registry.load_from_config(config)?;
-        agent_registry.load_from_xdg()?;
-
-        let mut provider_registry = crate::provider::ProviderRegistry::new();
-        provider_registry.load_from_config(config)?;
-        provider_registry.load_from_xdg()?;
-
-        {
-            let registry = workflow_registry.read();
-            for agent in agent_registry.list_all() {
-                crate::workflow::binding::validate_agent_binding(agent, &registry)?;
-            }
-        }
+        let (agent_registry, provider_registry) =
+            load_registries(config, &workflow_registry.read())?;
 
         let api = ContextApi::with_workspace_root(
             node_store,
@@ -102,6 +90,25 @@ impl CliRuntimeAssembly {
         );
         api.set_world_model_queries(world_model_queries);
         api.set_workflow_registry(Arc::clone(&workflow_registry));
+        api.set_strict_paths(config.system.strict_paths);
+        api.set_require_synthesize(config.system.require_synthesize_for_directories);
+        api.set_normalize_line_endings(config.system.normalize_line_endings);
+        api.set_content_read_options(config.system.content_read_options());
+
+        if let Err(err) = api.recover_heads() {
+            tracing::warn!(error = %err, "failed to reconcile head index with frame storage");
+        }
+        {
+            let head_index_guard = api.head_index().read();
+            if let Err(err) = backfill_legacy_heads_into_spine(
+                &progress,
+                &head_index_guard,
+                api.frame_storage(),
+                "context_head_backfill",
+            ) {
+                tracing::warn!(error = %err, "failed to backfill legacy heads into spine");
+            }
+        }
 
         Ok(Self {
             api: Arc::new(api),
@@ -126,4 +133,35 @@ impl CliRuntimeAssembly {
     pub fn graph_runtime(&self) -> &Arc<GraphRuntime> {
         &self.graph_runtime
     }
+
+    /// Rebuild workflow, agent, and provider registries from `config` and XDG storage in place.
+    ///
+    /// Used by long-lived sessions after commands that write configuration; stores, the head
+    /// index, and other handles stay shared.
+    pub fn reload_registries(&self, config: &MerkleConfig) -> Result<(), ApiError> {
+        let workflow_registry = WorkflowRegistry::load(&config.workflows)?;
+        let (agent_registry, provider_registry) = load_registries(config, &workflow_registry)?;
+        *self.workflow_registry.write() = workflow_registry;
+        *self.api.agent_registry().write() = agent_registry;
+        *self.api.provider_registry().write() = provider_registry;
+        Ok(())
+    }
+}
+
+fn load_registries(
+    config: &MerkleConfig,
+    workflow_registry: &WorkflowRegistry,
+) -> Result<(AgentRegistry, ProviderRegistry), ApiError> {
+    let mut agent_registry = AgentRegistry::new();
+    agent_registry.load_from_config(config)?;
+    agent_registry.load_from_xdg()?;
+
+    let mut provider_registry = ProviderRegistry::new();
+    provider_registry.load_from_config(config)?;
+    provider_registry.load_from_xdg()?;
+
+    for agent in agent_registry.list_all() {
+        crate::workflow::binding::validate_agent_binding(agent, workflow_registry)?;
+    }
+    Ok((agent_registry, provider_registry))
 }
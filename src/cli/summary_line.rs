@@ -0,0 +1,110 @@
+//! `--summary-only`: replace a long-running command's output with one summary line built from
+//! the events the command emitted in its session.
+
+use crate::cli::parse::{Commands, ContextCommands};
+use crate::error::ApiError;
+use crate::events::EventRecord;
+use serde::Serialize;
+
+/// Commands that accept `--summary-only`, for the error shown on other commands.
+pub const SUMMARY_ONLY_COMMANDS: &str = "scan, context generate, and context regenerate";
+
+pub fn supports_summary_only(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Scan { .. }
+            | Commands::Context {
+                command: ContextCommands::Generate(_) | ContextCommands::Regenerate { .. }
+            }
+    )
+}
+
+/// Final counts of one command run. Fields that do not apply to the command are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CommandSummaryLine {
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodes_scanned: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodes_planned: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames_generated: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed: Option<usize>,
+    pub duration_ms: u128,
+}
+
+impl CommandSummaryLine {
+    /// Fold the session's events into the counts reported for `command`.
+    pub fn from_events(
+        command: &Commands,
+        command_name: &str,
+        events: &[EventRecord],
+        duration_ms: u128,
+    ) -> Self {
+        let mut summary = CommandSummaryLine {
+            command: command_name.to_string(),
+            duration_ms,
+            ..CommandSummaryLine::default()
+        };
+        if matches!(command, Commands::Scan { .. }) {
+            summary.nodes_scanned = Some(0);
+        } else {
+            summary.nodes_planned = Some(0);
+            summary.frames_generated = Some(0);
+            summary.failed = Some(0);
+        }
+        for event in events {
+            match event.event_type.as_str() {
+                // An unchanged tree only reports `scan_progress`; a full scan ends with
+                // `scan_completed`.
+                "scan_progress" | "scan_completed" if summary.nodes_scanned.is_some() => {
+                    summary.nodes_scanned =
+                        read_usize(event, "node_count").or(summary.nodes_scanned);
+                }
+                "plan_constructed" => {
+                    summary.nodes_planned =
+                        read_usize(event, "total_nodes").or(summary.nodes_planned);
+                }
+                "execution.control.generation_completed"
+                | "execution.control.generation_failed" => {
+                    summary.frames_generated =
+                        read_usize(event, "total_generated").or(summary.frames_generated);
+                    summary.failed = read_usize(event, "total_failed").or(summary.failed);
+                }
+                _ => {}
+            }
+        }
+        summary
+    }
+
+    /// One `key=value` line for `text`, one compact JSON object for `json`.
+    pub fn render(&self, format: &str) -> Result<String, ApiError> {
+        if format == "json" {
+            return serde_json::to_string(self)
+                .map_err(|e| ApiError::ConfigError(format!("Failed to serialize JSON: {}", e)));
+        }
+        let mut fields = vec![format!("command={}", self.command)];
+        let counts = [
+            ("nodes_scanned", self.nodes_scanned),
+            ("nodes_planned", self.nodes_planned),
+            ("frames_generated", self.frames_generated),
+            ("failed", self.failed),
+        ];
+        for (key, value) in counts {
+            if let Some(value) = value {
+                fields.push(format!("{}={}", key, value));
+            }
+        }
+        fields.push(format!("duration_ms={}", self.duration_ms));
+        Ok(fields.join(" "))
+    }
+}
+
+fn read_usize(event: &EventRecord, key: &str) -> Option<usize> {
+    event
+        .data
+        .get(key)
+        .and_then(|value| value.as_u64())
+        .and_then(|value| usize::try_from(value).ok())
+}
@@ -74,6 +74,28 @@ pub struct SystemConfig {
     /// Storage paths
     #[serde(default)]
     pub storage: StorageConfig,
+
+    /// Reject `--path` targets that resolve outside the workspace root (default: true)
+    #[serde(default = "default_strict_paths")]
+    pub strict_paths: bool,
+
+    /// Require the `Synthesize` capability for agents generating directory frames (default: false)
+    #[serde(default)]
+    pub require_synthesize_for_directories: bool,
+
+    /// Hash text files with CRLF line endings normalized to LF (default: false).
+    /// Binary files always hash their exact bytes.
+    #[serde(default)]
+    pub normalize_line_endings: bool,
+
+    /// Read buffer size in bytes for content hashing (default: 64 KiB)
+    #[serde(default = "default_hash_chunk_size")]
+    pub hash_chunk_size: usize,
+
+    /// Files at least this many bytes are memory-mapped for content hashing (default: unset,
+    /// never). A mapped file truncated by another process crashes the scan with SIGBUS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_mmap_threshold: Option<u64>,
 }
 
 /// Workflow profile loading configuration
@@ -89,11 +111,24 @@ fn default_workspace_root() -> PathBuf {
     PathBuf::from(".")
 }
 
+fn default_strict_paths() -> bool {
+    true
+}
+
+fn default_hash_chunk_size() -> usize {
+    crate::tree::hasher::DEFAULT_HASH_CHUNK_SIZE
+}
+
 impl Default for SystemConfig {
     fn default() -> Self {
         Self {
             default_workspace_root: default_workspace_root(),
             storage: StorageConfig::default(),
+            strict_paths: default_strict_paths(),
+            require_synthesize_for_directories: false,
+            normalize_line_endings: false,
+            hash_chunk_size: default_hash_chunk_size(),
+            hash_mmap_threshold: None,
         }
     }
 }
@@ -155,6 +190,16 @@ impl std::fmt::Display for ValidationError {
 impl std::error::Error for ValidationError {}
 
 impl SystemConfig {
+    /// File read settings for content hashing.
+    pub fn content_read_options(&self) -> crate::tree::hasher::ContentReadOptions {
+        crate::tree::hasher::ContentReadOptions {
+            chunk_size: self.hash_chunk_size,
+            mmap_threshold: self
+                .hash_mmap_threshold
+                .unwrap_or(crate::tree::hasher::DEFAULT_HASH_MMAP_THRESHOLD),
+        }
+    }
+
     /// Validate system configuration
     pub fn validate(&self) -> Result<(), String> {
         // Validate storage paths are not empty
@@ -167,6 +212,9 @@ impl SystemConfig {
         if self.storage.artifacts_path.as_os_str().is_empty() {
             return Err("Artifacts path cannot be empty".to_string());
         }
+        if self.hash_chunk_size == 0 {
+            return Err("hash_chunk_size must be greater than zero".to_string());
+        }
 
         Ok(())
     }
@@ -323,6 +371,9 @@ mod tests {
             system_prompt: Some("Test prompt".to_string()),
             system_prompt_path: None,
             workflow_id: None,
+            default_provider: None,
+            frame_type_per_node_type: false,
+            capabilities: Vec::new(),
             metadata: Default::default(),
         };
         assert!(agent.validate(&providers).is_ok());
@@ -334,6 +385,9 @@ mod tests {
             system_prompt: None,
             system_prompt_path: None,
             workflow_id: None,
+            default_provider: None,
+            frame_type_per_node_type: false,
+            capabilities: Vec::new(),
             metadata: Default::default(),
         };
         assert!(agent_bad.validate(&providers).is_err());
@@ -345,6 +399,9 @@ mod tests {
             system_prompt: None,
             system_prompt_path: None,
             workflow_id: None,
+            default_provider: None,
+            frame_type_per_node_type: false,
+            capabilities: Vec::new(),
             metadata: Default::default(),
         };
         assert!(agent_reader.validate(&providers).is_ok());
@@ -376,6 +433,9 @@ mod tests {
                 system_prompt: Some("Test".to_string()),
                 system_prompt_path: None,
                 workflow_id: None,
+                default_provider: None,
+                frame_type_per_node_type: false,
+                capabilities: Vec::new(),
                 metadata: Default::default(),
             },
         );
@@ -391,6 +451,9 @@ mod tests {
                 system_prompt: None,
                 system_prompt_path: None,
                 workflow_id: None,
+                default_provider: None,
+                frame_type_per_node_type: false,
+                capabilities: Vec::new(),
                 metadata: Default::default(),
             },
         );
@@ -450,7 +513,7 @@ endpoint = "http://localhost:11434"
 agent_id = "test-agent"
 role = "Writer"
 system_prompt = "Test prompt"
-provider_name = "test-ollama"
+default_provider = "test-ollama"
 "#,
         )
         .unwrap();
@@ -467,6 +530,155 @@ provider_name = "test-ollama"
         assert_eq!(agent.system_prompt.as_ref().unwrap(), "Test prompt");
     }
 
+    #[test]
+    fn test_config_override_sets_nested_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.toml");
+        std::fs::write(
+            &config_file,
+            r#"
+[providers.test-ollama]
+provider_type = "ollama"
+model = "llama2"
+
+[providers.test-ollama.default_options]
+max_tokens = 256
+"#,
+        )
+        .unwrap();
+
+        let config = ConfigLoader::load_with_overrides(
+            temp_dir.path(),
+            Some(&config_file),
+            &[
+                "providers.test-ollama.default_options.max_tokens=1024".to_string(),
+                "system.strict_paths=false".to_string(),
+                "logging.level=debug".to_string(),
+            ],
+        )
+        .unwrap();
+
+        let provider = config.providers.get("test-ollama").unwrap();
+        assert_eq!(provider.default_options.max_tokens, Some(1024));
+        assert_eq!(provider.model, "llama2");
+        assert!(!config.system.strict_paths);
+        assert_eq!(config.logging.level, "debug");
+    }
+
+    #[test]
+    fn test_config_override_rejects_unknown_key_and_type_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.toml");
+        std::fs::write(&config_file, "[system]\nstrict_paths = true\n").unwrap();
+
+        let err = ConfigLoader::load_with_overrides(
+            temp_dir.path(),
+            Some(&config_file),
+            &["system.no_such_setting=1".to_string()],
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown config key 'system.no_such_setting'"),
+            "{}",
+            err
+        );
+
+        let err = ConfigLoader::load_with_overrides(
+            temp_dir.path(),
+            Some(&config_file),
+            &["system.strict_paths=sometimes".to_string()],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("strict_paths"), "{}", err);
+
+        let err = ConfigLoader::load_with_overrides(
+            temp_dir.path(),
+            Some(&config_file),
+            &["system.strict_paths".to_string()],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expected <dotted.key>=<value>"));
+    }
+
+    #[test]
+    fn test_profile_overrides_base_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.toml");
+        std::fs::write(
+            &config_file,
+            r#"
+[system.storage]
+store_path = ".meld/store"
+
+[logging]
+level = "info"
+
+[profiles.work.system.storage]
+store_path = ".meld-work/store"
+
+[profiles.work.logging]
+level = "debug"
+
+[profiles.work.providers.work-ollama]
+provider_type = "ollama"
+model = "llama3"
+"#,
+        )
+        .unwrap();
+
+        let base = ConfigLoader::load_with_profile(temp_dir.path(), Some(&config_file), &[], None)
+            .unwrap();
+        assert_eq!(base.system.storage.store_path, PathBuf::from(".meld/store"));
+        assert!(base.providers.is_empty());
+
+        let work =
+            ConfigLoader::load_with_profile(temp_dir.path(), Some(&config_file), &[], Some("work"))
+                .unwrap();
+        assert_eq!(
+            work.system.storage.store_path,
+            PathBuf::from(".meld-work/store")
+        );
+        assert_eq!(
+            work.system.storage.frames_path,
+            PathBuf::from(".meld/frames")
+        );
+        assert_eq!(work.logging.level, "debug");
+        assert_eq!(work.providers["work-ollama"].model, "llama3");
+
+        // --config-override still wins over the profile.
+        let overridden = ConfigLoader::load_with_profile(
+            temp_dir.path(),
+            Some(&config_file),
+            &["logging.level=warn".to_string()],
+            Some("work"),
+        )
+        .unwrap();
+        assert_eq!(overridden.logging.level, "warn");
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_defined_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.toml");
+        std::fs::write(
+            &config_file,
+            "[profiles.dev.logging]\nlevel = \"debug\"\n\n[profiles.prod.logging]\nlevel = \"warn\"\n",
+        )
+        .unwrap();
+
+        let err =
+            ConfigLoader::load_with_profile(temp_dir.path(), Some(&config_file), &[], Some("qa"))
+                .unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("Unknown config profile 'qa'"),
+            "{}",
+            message
+        );
+        assert!(message.contains("dev, prod"), "{}", message);
+    }
+
     #[test]
     fn test_xdg_config_path() {
         // Serialize access to HOME to avoid race conditions in parallel test execution
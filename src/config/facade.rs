@@ -32,6 +32,29 @@ impl ConfigLoader {
         MergeService::load_from_file(path)
     }
 
+    /// Load configuration like [`Self::load`] or [`Self::load_from_file`], then apply
+    /// `<dotted.key>=<value>` overrides. Unknown keys and values that do not coerce to the
+    /// field type are errors.
+    pub fn load_with_overrides(
+        workspace_root: &Path,
+        config_path: Option<&Path>,
+        overrides: &[String],
+    ) -> Result<MerkleConfig, ConfigError> {
+        MergeService::load_with_overrides(workspace_root, config_path, overrides)
+    }
+
+    /// Load configuration like [`Self::load_with_overrides`], applying the `[profiles.<name>]`
+    /// section named by `profile` (`--profile`), or by `MELD_PROFILE` when `profile` is `None`.
+    /// An unknown profile is an error.
+    pub fn load_with_profile(
+        workspace_root: &Path,
+        config_path: Option<&Path>,
+        overrides: &[String],
+        profile: Option<&str>,
+    ) -> Result<MerkleConfig, ConfigError> {
+        MergeService::load_with_profile(workspace_root, config_path, overrides, profile)
+    }
+
     /// Create the default configuration value.
     pub fn load_default() -> MerkleConfig {
         MerkleConfig::default()
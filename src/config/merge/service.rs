@@ -1,8 +1,10 @@
 //! MergeService: orchestrates sources, applies merge policy, deserializes to MerkleConfig.
 
-use crate::config::sources::{environment, global_file, workspace_file};
+use crate::config::sources::overrides::{self, ConfigOverride};
+use crate::config::sources::{environment, global_file, profile, workspace_file};
 use crate::config::MerkleConfig;
-use config::ConfigError;
+use config::builder::DefaultState;
+use config::{ConfigBuilder, ConfigError};
 use std::path::Path;
 
 use super::merge_policy;
@@ -12,31 +14,70 @@ pub struct MergeService;
 
 impl MergeService {
     /// Load config from workspace and standard sources.
-    /// Precedence: global file (lowest) -> workspace base -> workspace env -> environment (highest).
+    /// Precedence: global file (lowest) -> workspace base -> workspace env -> `MELD_PROFILE`
+    /// profile -> environment (highest).
     pub fn load(workspace_root: &Path) -> Result<MerkleConfig, ConfigError> {
         let builder = merge_policy::builder_with_defaults()?;
         let builder = global_file::add_to_builder(builder)?;
         let builder = workspace_file::add_to_builder(builder, workspace_root)?;
-        let builder = environment::add_to_builder(builder)?;
-
-        let config = builder.build()?;
-        config.try_deserialize()
+        Self::finish(builder, None, &[])
     }
 
-    /// Load config from a specific file with environment overlay.
+    /// Load config from a specific file with profile and environment overlay.
     pub fn load_from_file(path: &Path) -> Result<MerkleConfig, ConfigError> {
-        use config::Environment;
-        use config::File;
+        let builder = merge_policy::builder_with_defaults()?;
+        let builder = builder.add_source(config::File::with_name(path.to_str().unwrap()));
+        Self::finish(builder, None, &[])
+    }
+
+    /// Load config from `config_path` when given, otherwise from workspace and standard
+    /// sources, then apply `--config-override` entries on top of every other source.
+    pub fn load_with_overrides(
+        workspace_root: &Path,
+        config_path: Option<&Path>,
+        raw_overrides: &[String],
+    ) -> Result<MerkleConfig, ConfigError> {
+        Self::load_with_profile(workspace_root, config_path, raw_overrides, None)
+    }
+
+    /// Like [`Self::load_with_overrides`], with `profile` selecting the `[profiles.<name>]`
+    /// section instead of `MELD_PROFILE`.
+    pub fn load_with_profile(
+        workspace_root: &Path,
+        config_path: Option<&Path>,
+        raw_overrides: &[String],
+        profile: Option<&str>,
+    ) -> Result<MerkleConfig, ConfigError> {
+        let parsed = raw_overrides
+            .iter()
+            .map(|raw| overrides::parse(raw))
+            .collect::<Result<Vec<ConfigOverride>, _>>()?;
 
         let builder = merge_policy::builder_with_defaults()?;
-        let builder = builder.add_source(File::with_name(path.to_str().unwrap()));
-        let builder = builder.add_source(
-            Environment::with_prefix("MERKLE")
-                .separator("__")
-                .try_parsing(true),
-        );
-
-        let config = builder.build()?;
-        config.try_deserialize()
+        let builder = match config_path {
+            Some(path) => builder.add_source(config::File::with_name(path.to_str().unwrap())),
+            None => {
+                let builder = global_file::add_to_builder(builder)?;
+                workspace_file::add_to_builder(builder, workspace_root)?
+            }
+        };
+        Self::finish(builder, profile, &parsed)
+    }
+
+    /// Layer the active profile, environment, and overrides over the file sources in
+    /// `builder`, then deserialize.
+    fn finish(
+        builder: ConfigBuilder<DefaultState>,
+        explicit_profile: Option<&str>,
+        parsed: &[ConfigOverride],
+    ) -> Result<MerkleConfig, ConfigError> {
+        let active = profile::active_profile(explicit_profile);
+        let builder = profile::add_to_builder(builder, active.as_deref())?;
+        let builder = environment::add_to_builder(builder)?;
+        let builder = overrides::add_to_builder(builder, parsed)?;
+
+        let config: MerkleConfig = builder.build()?.try_deserialize()?;
+        overrides::ensure_applied(&config, parsed)?;
+        Ok(config)
     }
 }
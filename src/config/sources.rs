@@ -1,5 +1,8 @@
-//! Config source adapters: workspace file, global file, environment.
+//! Config source adapters: workspace file, global file, named profile, environment,
+//! command-line overrides.
 
 pub mod environment;
 pub mod global_file;
+pub mod overrides;
+pub mod profile;
 pub mod workspace_file;
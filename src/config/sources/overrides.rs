@@ -0,0 +1,75 @@
+//! Command-line override source: repeatable `--config-override <dotted.key>=<value>`
+
+use crate::config::MerkleConfig;
+use config::builder::DefaultState;
+use config::ConfigBuilder;
+use config::ConfigError;
+
+/// One parsed `<dotted.key>=<value>` override.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigOverride {
+    pub key: String,
+    pub value: String,
+}
+
+/// Parse a raw `<dotted.key>=<value>` argument.
+pub fn parse(raw: &str) -> Result<ConfigOverride, ConfigError> {
+    let (key, value) = raw.split_once('=').ok_or_else(|| {
+        ConfigError::Message(format!(
+            "Invalid config override '{}': expected <dotted.key>=<value>",
+            raw
+        ))
+    })?;
+    let key = key.trim();
+    if key.is_empty() || key.split('.').any(str::is_empty) {
+        return Err(ConfigError::Message(format!(
+            "Invalid config override '{}': key must be a dotted path such as system.strict_paths",
+            raw
+        )));
+    }
+    Ok(ConfigOverride {
+        key: key.to_string(),
+        value: value.trim().to_string(),
+    })
+}
+
+/// Add overrides to builder. Overrides take precedence over every other source; values are
+/// strings coerced to the target field type when the config is deserialized.
+pub fn add_to_builder(
+    builder: ConfigBuilder<DefaultState>,
+    overrides: &[ConfigOverride],
+) -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+    let mut builder = builder;
+    for entry in overrides {
+        builder = builder.set_override(entry.key.as_str(), entry.value.as_str())?;
+    }
+    Ok(builder)
+}
+
+/// Reject overrides whose key is not a field of the loaded config.
+///
+/// Deserialization ignores unknown keys, so each overridden path must still be present after
+/// the config is serialized back out.
+pub fn ensure_applied(
+    config: &MerkleConfig,
+    overrides: &[ConfigOverride],
+) -> Result<(), ConfigError> {
+    if overrides.is_empty() {
+        return Ok(());
+    }
+    let value = serde_json::to_value(config)
+        .map_err(|err| ConfigError::Message(format!("Failed to inspect config: {}", err)))?;
+    for entry in overrides {
+        let found = entry
+            .key
+            .split('.')
+            .try_fold(&value, |node, segment| node.get(segment));
+        if found.is_none() {
+            return Err(ConfigError::Message(format!(
+                "Unknown config key '{}' in --config-override",
+                entry.key
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Named profile source: a `[profiles.<name>]` table layered over the config files.
+//!
+//! A profile holds the same keys as the top-level config (`system.storage`, `logging`,
+//! `providers`, ...). The active profile is chosen by `--profile <name>`, or `MELD_PROFILE`
+//! when the flag is absent. It applies above every config file and below `MERKLE_*`
+//! environment variables and `--config-override` entries.
+
+use config::builder::DefaultState;
+use config::{ConfigBuilder, ConfigError, File, FileFormat};
+use std::collections::BTreeMap;
+
+/// Environment variable naming the active profile when `--profile` is not given.
+pub const PROFILE_ENV_VAR: &str = "MELD_PROFILE";
+
+/// Profile to apply: `explicit` when set, otherwise a non-empty `MELD_PROFILE`.
+pub fn active_profile(explicit: Option<&str>) -> Option<String> {
+    explicit
+        .map(str::to_string)
+        .or_else(|| std::env::var(PROFILE_ENV_VAR).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Layer `profiles.<profile>` from the sources already in `builder` on top of them.
+///
+/// An unknown profile is an error that lists the profiles the config files define.
+pub fn add_to_builder(
+    builder: ConfigBuilder<DefaultState>,
+    profile: Option<&str>,
+) -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+    let Some(profile) = profile else {
+        return Ok(builder);
+    };
+    let files = builder.build_cloned()?;
+    let profiles: BTreeMap<String, serde_json::Value> = files.get("profiles").unwrap_or_default();
+    let Some(table) = profiles.get(profile) else {
+        let defined = if profiles.is_empty() {
+            "none".to_string()
+        } else {
+            profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+        };
+        return Err(ConfigError::Message(format!(
+            "Unknown config profile '{}'. Defined profiles: {}. Add a [profiles.{}] section to the config file.",
+            profile, defined, profile
+        )));
+    };
+    if !table.is_object() {
+        return Err(ConfigError::Message(format!(
+            "Config profile '{}' must be a table of settings",
+            profile
+        )));
+    }
+    Ok(builder.add_source(File::from_str(&table.to_string(), FileFormat::Json)))
+}
@@ -8,6 +8,7 @@ pub mod frame;
 pub(crate) mod frame_metadata_keys;
 pub mod generation;
 pub mod head;
+pub mod portable;
 pub mod query;
 pub mod queue;
 pub(crate) mod reducer;
@@ -24,7 +25,7 @@ pub use generation::{
 };
 pub use head::{CurrentFrameHead, CurrentFrameHeadRead};
 pub use queue::{
-    FrameGenerationQueue, GenerationConfig, GenerationRequest, GenerationRequestOptions, Priority,
-    QueueEventContext, QueueStats,
+    BatchRequest, FrameGenerationQueue, GenerationConfig, GenerationRequest,
+    GenerationRequestOptions, PartialBatchEnqueue, Priority, QueueEventContext, QueueStats,
 };
 pub use types::{CompactResult, RestoreResult, TombstoneResult};
@@ -18,6 +18,7 @@ use crate::metadata::frame_write_contract::{
 use crate::provider::{ChatMessage, MessageRole, ProviderExecutionBinding};
 use crate::task::{ArtifactProducerRef, ArtifactRecord};
 use crate::telemetry::{FrameMetadataValidationEventData, PromptContextLineageEventData};
+use crate::types::{FrameID, HexHash, NodeID};
 use crate::workflow::gates::evaluate_gate;
 use crate::workflow::profile::WorkflowGate;
 use async_trait::async_trait;
@@ -131,7 +132,7 @@ impl ContextGeneratePrepareCapability {
         u64::from_le_bytes(bytes)
     }
 
-    fn parse_node_id(payload: &CapabilityInvocationPayload) -> Result<[u8; 32], ApiError> {
+    fn parse_node_id(payload: &CapabilityInvocationPayload) -> Result<NodeID, ApiError> {
         let input = payload
             .supplied_inputs
             .iter()
@@ -155,22 +156,15 @@ impl ContextGeneratePrepareCapability {
                     payload.invocation_id
                 ))
             })?;
-        let bytes = hex::decode(node_hex).map_err(|err| {
-            ApiError::ConfigError(format!("Invalid node hex '{}': {}", node_hex, err))
-        })?;
-        if bytes.len() != 32 {
-            return Err(ApiError::ConfigError(format!(
-                "Invalid node hex '{}' length '{}'",
-                node_hex,
-                bytes.len()
-            )));
-        }
-        let mut node_id = [0u8; 32];
-        node_id.copy_from_slice(&bytes);
-        Ok(node_id)
+        node_hex
+            .parse::<HexHash>()
+            .map(NodeID::from)
+            .map_err(|err| {
+                ApiError::ConfigError(format!("Invalid node hex '{}': {}", node_hex, err))
+            })
     }
 
-    fn parse_frame_id(value: &Value, invocation_id: &str) -> Result<[u8; 32], ApiError> {
+    fn parse_frame_id(value: &Value, invocation_id: &str) -> Result<FrameID, ApiError> {
         let frame_hex = value
             .get("frame_id")
             .and_then(Value::as_str)
@@ -180,19 +174,12 @@ impl ContextGeneratePrepareCapability {
                     invocation_id
                 ))
             })?;
-        let bytes = hex::decode(frame_hex).map_err(|err| {
-            ApiError::ConfigError(format!("Invalid frame hex '{}': {}", frame_hex, err))
-        })?;
-        if bytes.len() != 32 {
-            return Err(ApiError::ConfigError(format!(
-                "Invalid frame hex '{}' length '{}'",
-                frame_hex,
-                bytes.len()
-            )));
-        }
-        let mut frame_id = [0u8; 32];
-        frame_id.copy_from_slice(&bytes);
-        Ok(frame_id)
+        frame_hex
+            .parse::<HexHash>()
+            .map(FrameID::from)
+            .map_err(|err| {
+                ApiError::ConfigError(format!("Invalid frame hex '{}': {}", frame_hex, err))
+            })
     }
 
     fn supporting_input_content(
@@ -485,6 +472,13 @@ impl CapabilityInvoker for ContextGeneratePrepareCapability {
             frame_type: frame_type.clone(),
             retry_count: payload.execution_context.attempt.saturating_sub(1) as usize,
             force,
+            user_prompt_override: None,
+            stream_output: false,
+            preserve_mtime: false,
+            record_provenance: false,
+            prompt_vars: Default::default(),
+            on_conflict: Default::default(),
+            expected_head: None,
         };
 
         let agent = api.get_agent(&agent_id)?;
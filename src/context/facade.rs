@@ -10,8 +10,8 @@ pub use crate::context::generation::{
 pub use crate::context::head::{CurrentFrameHead, CurrentFrameHeadRead};
 pub use crate::context::query::{ContextView, ContextViewBuilder, NodeContext};
 pub use crate::context::queue::{
-    FrameGenerationQueue, GenerationConfig, GenerationRequestOptions, Priority, QueueEventContext,
-    QueueStats,
+    BatchRequest, FrameGenerationQueue, GenerationConfig, GenerationRequestOptions,
+    PartialBatchEnqueue, Priority, QueueEventContext, QueueStats,
 };
 pub use crate::context::types::{CompactResult, RestoreResult, TombstoneResult};
 
@@ -4,8 +4,10 @@
 //! Each frame is content-addressed and append-only.
 
 pub mod id;
+pub mod inspect;
 pub mod set;
 pub mod storage;
+pub mod tooling;
 
 pub use set::FrameMerkleSet;
 pub use storage::FrameStorage;
@@ -0,0 +1,195 @@
+//! Low-level frame inspection: load one stored blob, re-check its FrameID, and
+//! resolve its basis references for `frame show`.
+
+use crate::api::ContextApi;
+use crate::context::frame::{id, Basis, Frame};
+use crate::error::ApiError;
+use crate::types::{FrameID, HashParseError, HexHash};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Characters of content shown when the full content is not requested.
+pub const CONTENT_PREVIEW_CHARS: usize = 500;
+
+/// Everything `frame show` reports about one stored frame.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameInspection {
+    pub frame_id: String,
+    pub blob_path: String,
+    pub integrity: FrameIntegrity,
+    pub basis: BasisInspection,
+    pub frame_type: String,
+    pub agent_id: String,
+    pub metadata: BTreeMap<String, String>,
+    pub timestamp: String,
+    pub content_bytes: usize,
+    pub content: String,
+    pub content_truncated: bool,
+    pub content_binary: bool,
+}
+
+impl FrameInspection {
+    pub fn is_intact(&self) -> bool {
+        self.integrity.verified
+    }
+}
+
+/// Result of recomputing the FrameID from the stored structural fields.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameIntegrity {
+    pub verified: bool,
+    /// FrameID recorded inside the blob.
+    pub stored_id: String,
+    /// FrameID recomputed from basis, agent, frame type, and content.
+    pub computed_id: String,
+}
+
+/// Frame basis with its node and frame references resolved against the stores.
+#[derive(Debug, Clone, Serialize)]
+pub struct BasisInspection {
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<BasisNodeRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame: Option<BasisFrameRef>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BasisNodeRef {
+    pub node_id: String,
+    /// Workspace path of the node; `None` when the node record is missing.
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BasisFrameRef {
+    pub frame_id: String,
+    /// Frame type of the referenced frame; `None` when it is not in frame storage.
+    pub frame_type: Option<String>,
+    /// Agent that wrote the referenced frame; `None` when it is not in frame storage.
+    pub agent_id: Option<String>,
+}
+
+/// Parse a 64-character hex FrameID, with or without a `0x` prefix.
+pub fn parse_frame_id(value: &str) -> Result<FrameID, ApiError> {
+    value
+        .parse::<HexHash>()
+        .map(FrameID::from)
+        .map_err(|e| match e {
+            HashParseError::InvalidHex(_) => {
+                ApiError::ConfigError(format!("Invalid frame ID hex: {}", value))
+            }
+            HashParseError::WrongLength(_) => {
+                ApiError::ConfigError("Frame ID must be 32 bytes (64 hex chars).".to_string())
+            }
+        })
+}
+
+/// Load a frame without trusting its hash and describe it.
+///
+/// A frame whose recomputed FrameID differs from the requested one is still returned;
+/// callers decide how to surface `integrity.verified == false`.
+pub fn inspect_frame(
+    api: &ContextApi,
+    frame_id: &FrameID,
+    full: bool,
+) -> Result<FrameInspection, ApiError> {
+    let storage = api.frame_storage();
+    let frame = storage.read_unverified(frame_id)?.ok_or_else(|| {
+        ApiError::ConfigError(format!(
+            "Frame {} not found in frame storage",
+            hex::encode(frame_id)
+        ))
+    })?;
+
+    let computed_id = id::compute_frame_id(
+        &frame.basis,
+        &frame.content,
+        &frame.frame_type,
+        &frame.agent_id,
+    )?;
+    let integrity = FrameIntegrity {
+        verified: frame.frame_id == *frame_id && computed_id == *frame_id,
+        stored_id: hex::encode(frame.frame_id),
+        computed_id: hex::encode(computed_id),
+    };
+    let (content, content_truncated, content_binary) = content_preview(&frame, full);
+
+    Ok(FrameInspection {
+        frame_id: hex::encode(frame_id),
+        blob_path: storage.blob_path(frame_id).display().to_string(),
+        integrity,
+        basis: resolve_basis(api, &frame.basis)?,
+        frame_type: frame.frame_type.clone(),
+        agent_id: frame.agent_id.clone(),
+        metadata: frame
+            .metadata
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        timestamp: DateTime::<Utc>::from(frame.timestamp)
+            .to_rfc3339_opts(SecondsFormat::Millis, true),
+        content_bytes: frame.content.len(),
+        content,
+        content_truncated,
+        content_binary,
+    })
+}
+
+/// Resolve the basis of each frame, keyed by FrameID, for `context get --resolve-basis`.
+pub fn resolve_frame_bases<'a>(
+    api: &ContextApi,
+    frames: impl IntoIterator<Item = &'a Frame>,
+) -> Result<HashMap<FrameID, BasisInspection>, ApiError> {
+    frames
+        .into_iter()
+        .map(|frame| Ok((frame.frame_id, resolve_basis(api, &frame.basis)?)))
+        .collect()
+}
+
+/// Look up the node path and referenced frame a basis points at. Missing references are
+/// reported as `None` rather than failing, since lineage may outlive compaction.
+pub fn resolve_basis(api: &ContextApi, basis: &Basis) -> Result<BasisInspection, ApiError> {
+    let (kind, node, frame) = match basis {
+        Basis::Node(node) => ("node", Some(node), None),
+        Basis::Frame(frame) => ("frame", None, Some(frame)),
+        Basis::Both { node, frame } => ("both", Some(node), Some(frame)),
+    };
+    let node = match node {
+        Some(node_id) => Some(BasisNodeRef {
+            node_id: hex::encode(node_id),
+            path: api
+                .node_store()
+                .get(node_id)?
+                .map(|record| record.path.display().to_string()),
+        }),
+        None => None,
+    };
+    let frame = match frame {
+        Some(frame_id) => {
+            let basis_frame = api.frame_storage().read_unverified(frame_id)?;
+            Some(BasisFrameRef {
+                frame_id: hex::encode(frame_id),
+                frame_type: basis_frame.as_ref().map(|f| f.frame_type.clone()),
+                agent_id: basis_frame.map(|f| f.agent_id),
+            })
+        }
+        None => None,
+    };
+    Ok(BasisInspection { kind, node, frame })
+}
+
+/// Content text, whether it was truncated, and whether it is binary.
+fn content_preview(frame: &Frame, full: bool) -> (String, bool, bool) {
+    let Ok(text) = std::str::from_utf8(&frame.content) else {
+        return (String::new(), false, true);
+    };
+    if full {
+        return (text.to_string(), false, false);
+    }
+    match text.char_indices().nth(CONTENT_PREVIEW_CHARS) {
+        Some((cut, _)) => (text[..cut].to_string(), true, false),
+        None => (text.to_string(), false, false),
+    }
+}
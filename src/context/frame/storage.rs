@@ -10,6 +10,13 @@ use crate::types::FrameID;
 use bincode;
 use std::fs;
 use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// File extension of stored frame blobs.
+const FRAME_EXTENSION: &str = "frame";
+
+/// Directory levels between `{root}/frames` and the frame files (`{hex[0..2]}/{hex[2..4]}`).
+const FRAME_PREFIX_DEPTH: usize = 2;
 
 /// Content-addressed frame storage
 ///
@@ -34,12 +41,8 @@ impl FrameStorage {
 
         // Create the frames directory if it doesn't exist
         let frames_dir = root.join("frames");
-        fs::create_dir_all(&frames_dir).map_err(|e| {
-            StorageError::IoError(std::io::Error::other(format!(
-                "Failed to create frames directory at {:?}: {}",
-                frames_dir, e
-            )))
-        })?;
+        fs::create_dir_all(&frames_dir)
+            .map_err(|e| StorageError::io_context("create frames directory", &frames_dir, e))?;
 
         Ok(Self { root })
     }
@@ -131,6 +134,45 @@ impl FrameStorage {
     /// Returns `None` if the frame doesn't exist.
     /// Returns an error if the frame exists but cannot be deserialized (corruption).
     pub fn get(&self, frame_id: &FrameID) -> Result<Option<Frame>, StorageError> {
+        let Some(frame) = self.read_unverified(frame_id)? else {
+            return Ok(None);
+        };
+        if frame.agent_id.is_empty() {
+            return Err(StorageError::InvalidPath(
+                "Frame missing structural agent_id".to_string(),
+            ));
+        }
+
+        // Verify FrameID matches (corruption detection)
+        if frame.frame_id != *frame_id {
+            return Err(StorageError::HashMismatch {
+                expected: *frame_id,
+                actual: frame.frame_id,
+            });
+        }
+
+        // Verify on-disk frame payload integrity using structural identity fields only.
+        let computed_id = id::compute_frame_id(
+            &frame.basis,
+            &frame.content,
+            &frame.frame_type,
+            &frame.agent_id,
+        )?;
+        if computed_id != frame.frame_id {
+            return Err(StorageError::HashMismatch {
+                expected: frame.frame_id,
+                actual: computed_id,
+            });
+        }
+
+        Ok(Some(frame))
+    }
+
+    /// Read and deserialize a stored frame without checking its FrameID.
+    ///
+    /// For inspection tooling that must show a frame even when its hash no longer
+    /// matches; everything else should use [`FrameStorage::get`].
+    pub fn read_unverified(&self, frame_id: &FrameID) -> Result<Option<Frame>, StorageError> {
         let frame_path = self.frame_path(frame_id);
 
         // Check if file exists
@@ -160,46 +202,56 @@ impl FrameStorage {
                 frame.agent_id = agent_id.clone();
             }
         }
-        if frame.agent_id.is_empty() {
-            return Err(StorageError::InvalidPath(
-                "Frame missing structural agent_id".to_string(),
-            ));
-        }
-
-        // Verify FrameID matches (corruption detection)
-        if frame.frame_id != *frame_id {
-            return Err(StorageError::HashMismatch {
-                expected: *frame_id,
-                actual: frame.frame_id,
-            });
-        }
-
-        // Verify on-disk frame payload integrity using structural identity fields only.
-        let computed_id = id::compute_frame_id(
-            &frame.basis,
-            &frame.content,
-            &frame.frame_type,
-            &frame.agent_id,
-        )?;
-        if computed_id != frame.frame_id {
-            return Err(StorageError::HashMismatch {
-                expected: frame.frame_id,
-                actual: computed_id,
-            });
-        }
 
         Ok(Some(frame))
     }
 
+    /// On-disk blob path for a FrameID, whether or not the frame is stored.
+    pub fn blob_path(&self, frame_id: &FrameID) -> PathBuf {
+        self.frame_path(frame_id)
+    }
+
     /// Check if a frame exists
     ///
     /// Returns `true` if a frame with the given FrameID exists in storage.
     pub fn exists(&self, frame_id: &FrameID) -> Result<bool, StorageError> {
         let frame_path = self.frame_path(frame_id);
-        Ok(frame_path.exists())
+        Ok(frame_path.try_exists()?)
     }
 
-    /// Remove a frame blob from storage (compaction only).
+    /// Flush a stored frame blob and its directory entry to disk.
+    ///
+    /// Called before the head index is updated so a persisted head never points at a
+    /// frame that a crash could still lose. No-op if the frame is not present.
+    pub fn sync(&self, frame_id: &FrameID) -> Result<(), StorageError> {
+        let frame_path = self.frame_path(frame_id);
+        if !frame_path.exists() {
+            return Ok(());
+        }
+        fs::File::open(&frame_path)
+            .and_then(|file| file.sync_all())
+            .map_err(|e| {
+                StorageError::IoError(std::io::Error::other(format!(
+                    "Failed to sync frame {:?}: {}",
+                    frame_path, e
+                )))
+            })?;
+        // Directory fsync makes the rename durable; not supported on every platform.
+        #[cfg(unix)]
+        if let Some(parent) = frame_path.parent() {
+            fs::File::open(parent)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|e| {
+                    StorageError::IoError(std::io::Error::other(format!(
+                        "Failed to sync frame directory {:?}: {}",
+                        parent, e
+                    )))
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Remove a frame blob from storage (compaction and `workspace validate --repair`).
     /// Idempotent: no error if frame_id is not present.
     pub fn purge(&self, frame_id: &FrameID) -> Result<(), StorageError> {
         let frame_path = self.frame_path(frame_id);
@@ -214,6 +266,15 @@ impl FrameStorage {
         Ok(())
     }
 
+    /// Enumerate stored frame IDs without loading frame content.
+    ///
+    /// Walks the on-disk layout lazily, in no particular order. Files that are not valid
+    /// frame blobs (e.g. leftover `.frame.tmp` files or mangled names) are skipped with a
+    /// warning; directory read failures are yielded as errors.
+    pub fn iter_ids(&self) -> impl Iterator<Item = Result<FrameID, StorageError>> {
+        FrameIdIter::new(self.root.join("frames"))
+    }
+
     /// Compute the filesystem path for a given FrameID
     ///
     /// Path structure: `{root}/frames/{hex[0..2]}/{hex[2..4]}/{frame_id}.frame`
@@ -232,10 +293,92 @@ impl FrameStorage {
             .join("frames")
             .join(prefix1)
             .join(prefix2)
-            .join(format!("{}.frame", hex))
+            .join(format!("{}.{}", hex, FRAME_EXTENSION))
+    }
+}
+
+/// Depth-first walk over `{root}/frames/{hex[0..2]}/{hex[2..4]}/{frame_id}.frame`.
+struct FrameIdIter {
+    stack: Vec<(fs::ReadDir, usize)>,
+    pending_error: Option<StorageError>,
+}
+
+impl FrameIdIter {
+    fn new(frames_dir: PathBuf) -> Self {
+        match fs::read_dir(&frames_dir) {
+            Ok(entries) => Self {
+                stack: vec![(entries, 0)],
+                pending_error: None,
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self {
+                stack: Vec::new(),
+                pending_error: None,
+            },
+            Err(e) => Self {
+                stack: Vec::new(),
+                pending_error: Some(read_dir_error(&frames_dir, e)),
+            },
+        }
     }
 }
 
+impl Iterator for FrameIdIter {
+    type Item = Result<FrameID, StorageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(err) = self.pending_error.take() {
+                return Some(Err(err));
+            }
+            let (entries, depth) = self.stack.last_mut()?;
+            let depth = *depth;
+            let entry = match entries.next() {
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+                Some(Err(e)) => return Some(Err(StorageError::IoError(e))),
+                Some(Ok(entry)) => entry,
+            };
+            let path = entry.path();
+            if depth < FRAME_PREFIX_DEPTH {
+                if path.is_dir() {
+                    match fs::read_dir(&path) {
+                        Ok(children) => self.stack.push((children, depth + 1)),
+                        Err(e) => return Some(Err(read_dir_error(&path, e))),
+                    }
+                } else {
+                    warn!(path = %path.display(), "Skipping unexpected file in frame storage");
+                }
+                continue;
+            }
+            match parse_frame_file_name(&path) {
+                Some(frame_id) => return Some(Ok(frame_id)),
+                None => {
+                    warn!(path = %path.display(), "Skipping unrecognized frame storage entry");
+                }
+            }
+        }
+    }
+}
+
+/// FrameID from a `{hex}.frame` path; `None` for temp files and malformed names.
+fn parse_frame_file_name(path: &Path) -> Option<FrameID> {
+    if path.extension()? != FRAME_EXTENSION {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let bytes = hex::decode(stem).ok()?;
+    bytes.try_into().ok()
+}
+
+fn read_dir_error(path: &Path, e: std::io::Error) -> StorageError {
+    StorageError::IoError(std::io::Error::other(format!(
+        "Failed to read frame directory {:?}: {}",
+        path, e
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,6 +443,24 @@ mod tests {
         assert!(frame_path.exists());
     }
 
+    #[test]
+    fn test_open_failure_names_attempted_path() {
+        let temp_dir = TempDir::new().unwrap();
+        // A regular file where the storage root should be makes directory creation fail.
+        let blocker = temp_dir.path().join("not-a-dir");
+        fs::write(&blocker, b"file").unwrap();
+
+        let err = FrameStorage::new(&blocker).err().unwrap();
+        assert!(matches!(err, StorageError::IoContext { .. }), "{:?}", err);
+        let message = err.to_string();
+        assert!(message.contains("create frames directory"), "{}", message);
+        assert!(
+            message.contains(&blocker.join("frames").display().to_string()),
+            "{}",
+            message
+        );
+    }
+
     #[test]
     fn test_get_nonexistent() {
         let temp_dir = TempDir::new().unwrap();
@@ -459,4 +620,34 @@ mod tests {
         let result = storage.get(&frame.frame_id);
         assert!(matches!(result, Err(StorageError::HashMismatch { .. })));
     }
+
+    #[test]
+    fn test_iter_ids_yields_stored_frames_and_skips_junk() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FrameStorage::new(temp_dir.path()).unwrap();
+
+        let mut expected = std::collections::HashSet::new();
+        for i in 0..5u8 {
+            let frame = Frame::new(
+                Basis::Node([i; 32]),
+                format!("content {}", i).into_bytes(),
+                "test".to_string(),
+                "test-agent".to_string(),
+                HashMap::new(),
+            )
+            .unwrap();
+            storage.store(&frame).unwrap();
+            expected.insert(frame.frame_id);
+        }
+
+        // A leftover temp file and a mangled name next to a real frame.
+        let some_id = *expected.iter().next().unwrap();
+        let dir = storage.frame_path(&some_id).parent().unwrap().to_path_buf();
+        fs::write(dir.join("deadbeef.frame.tmp"), b"partial").unwrap();
+        fs::write(dir.join("not-hex.frame"), b"junk").unwrap();
+
+        let ids: std::collections::HashSet<FrameID> =
+            storage.iter_ids().collect::<Result<_, _>>().unwrap();
+        assert_eq!(ids, expected);
+    }
 }
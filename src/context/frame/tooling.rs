@@ -0,0 +1,26 @@
+use crate::api::ContextApi;
+use crate::cli::{format_frame_show_json, format_frame_show_text, FrameCommands};
+use crate::context::frame::inspect::{inspect_frame, parse_frame_id};
+use crate::error::ApiError;
+
+pub fn handle_cli_command(api: &ContextApi, command: &FrameCommands) -> Result<String, ApiError> {
+    match command {
+        FrameCommands::Show {
+            frame_id,
+            format,
+            full,
+        } => {
+            let frame_id = parse_frame_id(frame_id)?;
+            let inspection = inspect_frame(api, &frame_id, *full)?;
+            let report = match format.as_str() {
+                "json" => format_frame_show_json(&inspection)?,
+                _ => format_frame_show_text(&inspection),
+            };
+            if inspection.is_intact() {
+                Ok(report)
+            } else {
+                Err(ApiError::ConfigError(report))
+            }
+        }
+    }
+}
@@ -9,6 +9,21 @@ use crate::metadata::frame_key_descriptor::{
 pub const KEY_AGENT_ID: &str = "agent_id";
 pub const KEY_PROMPT: &str = "prompt";
 pub const KEY_DELETED: &str = "deleted";
+/// Source file modification time when the frame was generated, as Unix nanoseconds.
+pub const KEY_SOURCE_MTIME: &str = "source_mtime";
+/// Wall time of the attempt that produced the frame, from the start of request execution
+/// (prompt build and provider call) until the frame is built.
+pub const KEY_GENERATION_DURATION_MS: &str = "generation_duration_ms";
+/// Queue retries before the attempt that produced the frame.
+pub const KEY_RETRY_COUNT: &str = "retry_count";
+/// Configured provider name the frame was generated with.
+pub const KEY_PROVIDER_NAME: &str = "provider_name";
+/// Keys written by `context generate --record-provenance`, shown by `--include-provenance`.
+pub const PROVENANCE_KEYS: [&str; 3] = [
+    KEY_GENERATION_DURATION_MS,
+    KEY_RETRY_COUNT,
+    KEY_PROVIDER_NAME,
+];
 pub const FORBIDDEN_KEY_CONTEXT: &str = "context";
 pub const FORBIDDEN_KEY_RAW_PROMPT: &str = "raw_prompt";
 pub const FORBIDDEN_KEY_RAW_CONTEXT: &str = "raw_context";
@@ -53,6 +68,59 @@ pub const DESCRIPTOR_DELETED: FrameMetadataKeyDescriptor = FrameMetadataKeyDescr
     visibility_policy: FrameMetadataVisibilityPolicy::HiddenByDefault,
 };
 
+pub const DESCRIPTOR_SOURCE_MTIME: FrameMetadataKeyDescriptor = FrameMetadataKeyDescriptor {
+    key: KEY_SOURCE_MTIME,
+    owner_domain: "context",
+    schema_type: FrameMetadataSchemaType::Utf8String,
+    mutability_class: FrameMetadataMutabilityClass::Annotation,
+    hash_impact: FrameMetadataHashImpact::NonHashCritical,
+    max_bytes: DESCRIPTOR_DEFAULT_MAX_BYTES,
+    retention_policy: FrameMetadataRetentionPolicy::Persistent,
+    redaction_policy: FrameMetadataRedactionPolicy::VisibleByDefault,
+    write_policy: FrameMetadataWritePolicy::Allowed,
+    visibility_policy: FrameMetadataVisibilityPolicy::VisibleByDefault,
+};
+
+pub const DESCRIPTOR_GENERATION_DURATION_MS: FrameMetadataKeyDescriptor =
+    FrameMetadataKeyDescriptor {
+        key: KEY_GENERATION_DURATION_MS,
+        owner_domain: "context",
+        schema_type: FrameMetadataSchemaType::Utf8String,
+        mutability_class: FrameMetadataMutabilityClass::Annotation,
+        hash_impact: FrameMetadataHashImpact::NonHashCritical,
+        max_bytes: DESCRIPTOR_DEFAULT_MAX_BYTES,
+        retention_policy: FrameMetadataRetentionPolicy::Persistent,
+        redaction_policy: FrameMetadataRedactionPolicy::HiddenByDefault,
+        write_policy: FrameMetadataWritePolicy::Allowed,
+        visibility_policy: FrameMetadataVisibilityPolicy::HiddenByDefault,
+    };
+
+pub const DESCRIPTOR_RETRY_COUNT: FrameMetadataKeyDescriptor = FrameMetadataKeyDescriptor {
+    key: KEY_RETRY_COUNT,
+    owner_domain: "context",
+    schema_type: FrameMetadataSchemaType::Utf8String,
+    mutability_class: FrameMetadataMutabilityClass::Annotation,
+    hash_impact: FrameMetadataHashImpact::NonHashCritical,
+    max_bytes: DESCRIPTOR_DEFAULT_MAX_BYTES,
+    retention_policy: FrameMetadataRetentionPolicy::Persistent,
+    redaction_policy: FrameMetadataRedactionPolicy::HiddenByDefault,
+    write_policy: FrameMetadataWritePolicy::Allowed,
+    visibility_policy: FrameMetadataVisibilityPolicy::HiddenByDefault,
+};
+
+pub const DESCRIPTOR_PROVIDER_NAME: FrameMetadataKeyDescriptor = FrameMetadataKeyDescriptor {
+    key: KEY_PROVIDER_NAME,
+    owner_domain: "context",
+    schema_type: FrameMetadataSchemaType::Utf8String,
+    mutability_class: FrameMetadataMutabilityClass::Annotation,
+    hash_impact: FrameMetadataHashImpact::NonHashCritical,
+    max_bytes: DESCRIPTOR_DEFAULT_MAX_BYTES,
+    retention_policy: FrameMetadataRetentionPolicy::Persistent,
+    redaction_policy: FrameMetadataRedactionPolicy::HiddenByDefault,
+    write_policy: FrameMetadataWritePolicy::Allowed,
+    visibility_policy: FrameMetadataVisibilityPolicy::HiddenByDefault,
+};
+
 pub const DESCRIPTOR_CONTEXT: FrameMetadataKeyDescriptor = FrameMetadataKeyDescriptor {
     key: FORBIDDEN_KEY_CONTEXT,
     owner_domain: "context",
@@ -2,9 +2,11 @@
 //! Behavior-named; executor runs the plan; queue and provider stay in their domains.
 
 pub mod contracts;
+pub mod exclude;
 pub mod executor;
 pub mod metadata_construction;
 pub mod orchestration;
+pub mod ordering;
 pub mod plan;
 pub mod program;
 pub mod prompt_collection;
@@ -1,6 +1,7 @@
 use crate::metadata::frame_types::FrameMetadata;
 pub use meld_execution::generation::{
-    GeneratedFrameMetadataInput, GenerationOrchestrationRequest, PromptAssemblyOutput,
+    GeneratedFrameMetadataInput, GenerationOrchestrationRequest, HeadConflictPolicy,
+    PromptAssemblyOutput,
 };
 
 pub type GeneratedMetadataBuilder =
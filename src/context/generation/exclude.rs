@@ -0,0 +1,145 @@
+//! `--exclude` globs: drop workspace subpaths from a recursive plan without touching the
+//! ignore list.
+//!
+//! Patterns match workspace-relative paths with `/` separators. `*` and `?` stay within one
+//! path component, `**` spans any number of components. A path is excluded when it or any of
+//! its ancestors matches, so excluding a directory prunes its whole subtree.
+
+use crate::error::ApiError;
+use std::path::{Component, Path, PathBuf};
+
+/// Compiled `--exclude` patterns, anchored at the workspace root.
+#[derive(Debug, Clone)]
+pub struct PathExclusions {
+    workspace_root: PathBuf,
+    patterns: Vec<Vec<String>>,
+}
+
+impl PathExclusions {
+    /// Parse repeated `--exclude <glob>` arguments. A leading `./` and trailing `/` are ignored.
+    pub fn parse(workspace_root: &Path, entries: &[String]) -> Result<Self, ApiError> {
+        let patterns = entries
+            .iter()
+            .map(|entry| {
+                let trimmed = entry.trim();
+                let trimmed = trimmed.strip_prefix("./").unwrap_or(trimmed);
+                let trimmed = trimmed.trim_end_matches('/');
+                if trimmed.is_empty() || trimmed.starts_with('/') {
+                    return Err(ApiError::ConfigError(format!(
+                        "Invalid --exclude pattern '{}': expected a workspace-relative glob such as 'tests/*'.",
+                        entry
+                    )));
+                }
+                Ok(trimmed.split('/').map(str::to_string).collect())
+            })
+            .collect::<Result<_, _>>()?;
+        let workspace_root = crate::tree::path::canonicalize_path(workspace_root)
+            .unwrap_or_else(|_| workspace_root.to_path_buf());
+        Ok(Self {
+            workspace_root,
+            patterns,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// True when `path`, or a directory above it, matches a pattern. Paths outside the
+    /// workspace are never excluded.
+    pub fn excludes(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.workspace_root) else {
+            return false;
+        };
+        let components: Vec<String> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        (1..=components.len()).any(|len| {
+            self.patterns
+                .iter()
+                .any(|pattern| match_components(pattern, &components[..len]))
+        })
+    }
+}
+
+fn match_components(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                match_component(first.as_bytes(), name.as_bytes())
+                    && match_components(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_component(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_component(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_component(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_component(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclusions(patterns: &[&str]) -> PathExclusions {
+        let entries: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        PathExclusions::parse(Path::new("/ws"), &entries).unwrap()
+    }
+
+    #[test]
+    fn star_matches_within_one_component() {
+        let ex = exclusions(&["tests/*"]);
+        assert!(ex.excludes(Path::new("/ws/tests/a.rs")));
+        assert!(ex.excludes(Path::new("/ws/tests/nested/b.rs")));
+        assert!(!ex.excludes(Path::new("/ws/tests")));
+        assert!(!ex.excludes(Path::new("/ws/src/tests.rs")));
+        assert!(!ex.excludes(Path::new("/ws/src/tests/a.rs")));
+    }
+
+    #[test]
+    fn directory_pattern_prunes_subtree() {
+        let ex = exclusions(&["./vendor/"]);
+        assert!(ex.excludes(Path::new("/ws/vendor")));
+        assert!(ex.excludes(Path::new("/ws/vendor/dep/lib.rs")));
+        assert!(!ex.excludes(Path::new("/ws/vendored.rs")));
+    }
+
+    #[test]
+    fn double_star_spans_components() {
+        let ex = exclusions(&["**/*.snap", "src/**/fixtures"]);
+        assert!(ex.excludes(Path::new("/ws/a.snap")));
+        assert!(ex.excludes(Path::new("/ws/src/deep/x.snap")));
+        assert!(ex.excludes(Path::new("/ws/src/fixtures/data.json")));
+        assert!(ex.excludes(Path::new("/ws/src/a/b/fixtures")));
+        assert!(!ex.excludes(Path::new("/ws/src/lib.rs")));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        let ex = exclusions(&["v?"]);
+        assert!(ex.excludes(Path::new("/ws/v1/a.rs")));
+        assert!(!ex.excludes(Path::new("/ws/v10/a.rs")));
+    }
+
+    #[test]
+    fn rejects_absolute_and_empty_patterns() {
+        let root = Path::new("/ws");
+        assert!(PathExclusions::parse(root, &["/abs".to_string()]).is_err());
+        assert!(PathExclusions::parse(root, &["".to_string()]).is_err());
+        assert!(!PathExclusions::parse(root, &[]).unwrap().excludes(root));
+    }
+}
@@ -11,11 +11,19 @@ use crate::context::generation::provider_execution::{
 use crate::context::queue::QueueEventContext;
 use crate::error::ApiError;
 use crate::execution::ExecutionEventContext;
-use crate::telemetry::{FrameMetadataValidationEventData, PromptContextLineageEventData};
+use crate::metadata::frame_key_registry::{
+    KEY_GENERATION_DURATION_MS, KEY_PROVIDER_NAME, KEY_RETRY_COUNT, KEY_SOURCE_MTIME, KEY_TRUNCATED,
+};
+use crate::provider::CompletionOptions;
+use crate::store::{NodeRecord, NodeType};
+use crate::telemetry::{
+    FrameMetadataValidationEventData, PromptContextLineageEventData, ProviderLifecycleEventData,
+};
 use crate::types::FrameID;
 use meld_execution::{GeneratedMetadataPort, PromptLineagePort, PromptLineageRequest};
 use serde_json::json;
-use tracing::{debug, info};
+use std::time::{Instant, UNIX_EPOCH};
+use tracing::{debug, info, warn};
 
 pub async fn execute_generation_request(
     request: &GenerationOrchestrationRequest,
@@ -23,6 +31,7 @@ pub async fn execute_generation_request(
     metadata_builder: &GeneratedMetadataBuilder,
     event_context: Option<&QueueEventContext>,
 ) -> Result<FrameID, ApiError> {
+    let started = Instant::now();
     debug!(
         request_id = request.request_id,
         node_id = %hex::encode(request.node_id),
@@ -43,11 +52,21 @@ pub async fn execute_generation_request(
         .get(&request.node_id)
         .map_err(ApiError::from)?
         .ok_or(ApiError::NodeNotFound(request.node_id))?;
+    // Read before the prompt is built, so the recorded time matches the content sent.
+    let source_mtime = if request.preserve_mtime {
+        source_mtime_nanos(&node_record)
+    } else {
+        None
+    };
 
-    let prompt_contract = PromptContract::from_agent(&agent)?;
+    let mut prompt_contract = PromptContract::from_agent(&agent)?;
+    if let Some(user_prompt) = request.user_prompt_override.clone() {
+        prompt_contract.user_prompt_file = user_prompt.clone();
+        prompt_contract.user_prompt_directory = user_prompt;
+    }
     let prompt_output = build_prompt_messages(api, request, &node_record, &prompt_contract)?;
 
-    let provider_preparation = prepare_provider_for_request(api, request)?;
+    let mut provider_preparation = prepare_provider_for_request(api, request)?;
     let execution_event_context = event_context.map(ExecutionEventContext::from);
 
     let prepared_lineage = api.prepare_prompt_lineage(
@@ -128,7 +147,7 @@ pub async fn execute_generation_request(
         },
     );
 
-    let generated_metadata = match api.build_and_validate_generated_metadata(
+    let mut generated_metadata = match api.build_and_validate_generated_metadata(
         request,
         &prepared_lineage.metadata_input,
         metadata_builder,
@@ -193,15 +212,85 @@ pub async fn execute_generation_request(
         }
     };
 
-    let response = execute_completion(
+    let mut response = execute_completion(
         api,
         request,
         &provider_preparation,
-        prompt_output.messages,
+        prompt_output.messages.clone(),
         execution_event_context.as_ref(),
     )
     .await?;
 
+    if response.is_truncated() {
+        if let Some(retry_max_tokens) =
+            truncation_retry_limit(&provider_preparation.provider_config.default_options)
+        {
+            warn!(
+                request_id = request.request_id,
+                node_id = %hex::encode(request.node_id),
+                agent_id = %request.agent_id,
+                provider_name = %request.provider.provider_name,
+                retry_max_tokens,
+                "Provider response truncated; retrying with a higher token limit"
+            );
+            provider_preparation
+                .provider_config
+                .default_options
+                .max_tokens = Some(retry_max_tokens);
+            response = execute_completion(
+                api,
+                request,
+                &provider_preparation,
+                prompt_output.messages,
+                execution_event_context.as_ref(),
+            )
+            .await?;
+        }
+    }
+
+    if response.is_truncated() {
+        warn!(
+            request_id = request.request_id,
+            node_id = %hex::encode(request.node_id),
+            agent_id = %request.agent_id,
+            provider_name = %request.provider.provider_name,
+            path = %node_record.path.display(),
+            "Provider response truncated at token limit; storing frame flagged truncated"
+        );
+        if let Some(ctx) = event_context {
+            ctx.progress.emit_event_best_effort(
+                &ctx.session_id,
+                "provider_response_truncated",
+                json!(ProviderLifecycleEventData {
+                    node_id: hex::encode(request.node_id),
+                    agent_id: request.agent_id.clone(),
+                    provider_name: request.provider.provider_name.clone(),
+                    frame_type: request.frame_type.clone(),
+                    duration_ms: None,
+                    error: Some("finish_reason=length".to_string()),
+                    retry_count: Some(request.retry_count),
+                }),
+            );
+        }
+        generated_metadata.insert(KEY_TRUNCATED.to_string(), "true".to_string());
+    }
+
+    if let Some(mtime) = source_mtime {
+        generated_metadata.insert(KEY_SOURCE_MTIME.to_string(), mtime.to_string());
+    }
+
+    if request.record_provenance {
+        generated_metadata.insert(
+            KEY_GENERATION_DURATION_MS.to_string(),
+            started.elapsed().as_millis().to_string(),
+        );
+        generated_metadata.insert(KEY_RETRY_COUNT.to_string(), request.retry_count.to_string());
+        generated_metadata.insert(
+            KEY_PROVIDER_NAME.to_string(),
+            request.provider.provider_name.clone(),
+        );
+    }
+
     let frame = Frame::new(
         Basis::Node(request.node_id),
         response.content.into_bytes(),
@@ -210,7 +299,13 @@ pub async fn execute_generation_request(
         generated_metadata,
     )?;
 
-    let frame_id = api.put_frame(request.node_id, frame, request.agent_id.clone())?;
+    let frame_id = api.put_frame_checked(
+        request.node_id,
+        frame,
+        request.agent_id.clone(),
+        request.expected_head,
+        request.on_conflict,
+    )?;
 
     info!(
         request_id = request.request_id,
@@ -223,6 +318,23 @@ pub async fn execute_generation_request(
     Ok(frame_id)
 }
 
+/// Modification time of a file node's source as Unix nanoseconds; `None` for directories and
+/// for files whose mtime cannot be read.
+pub(crate) fn source_mtime_nanos(node_record: &NodeRecord) -> Option<u128> {
+    if !matches!(node_record.node_type, NodeType::File { .. }) {
+        return None;
+    }
+    let modified = std::fs::metadata(&node_record.path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+/// Token limit for a truncation retry, when configured above the limit that was just hit.
+fn truncation_retry_limit(options: &CompletionOptions) -> Option<u32> {
+    options
+        .truncation_retry_max_tokens
+        .filter(|limit| options.max_tokens.is_none_or(|current| *limit > current))
+}
+
 fn emit_metadata_validation_event(
     event_context: Option<&QueueEventContext>,
     event_type: &str,
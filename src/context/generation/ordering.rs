@@ -0,0 +1,181 @@
+//! `--after` ordering hints: extra "generate A before B" edges on top of the bottom-up plan.
+//!
+//! Plan levels start from the tree's bottom-up batches. Each hint, like each child-to-parent
+//! edge, requires its first node to land in an earlier level than its second; levels are then
+//! assigned by a topological pass, so a node only moves later, never earlier.
+
+use crate::error::ApiError;
+use crate::types::{HexHash, NodeID};
+use std::collections::{BTreeSet, HashMap};
+
+/// Parse repeated `--after <before>:<after>` arguments into (before, after) NodeID pairs.
+pub fn parse_order_hints(entries: &[String]) -> Result<Vec<(NodeID, NodeID)>, ApiError> {
+    entries
+        .iter()
+        .map(|entry| {
+            let invalid = |reason: String| {
+                ApiError::ConfigError(format!(
+                    "Invalid --after hint '{}': {}. Expected <node_id>:<node_id>.",
+                    entry, reason
+                ))
+            };
+            let (before, after) = entry
+                .split_once(':')
+                .ok_or_else(|| invalid("missing ':'".to_string()))?;
+            let before = parse_hint_node_id(before).map_err(&invalid)?;
+            let after = parse_hint_node_id(after).map_err(&invalid)?;
+            if before == after {
+                return Err(invalid("a node cannot follow itself".to_string()));
+            }
+            Ok((before, after))
+        })
+        .collect()
+}
+
+fn parse_hint_node_id(s: &str) -> Result<NodeID, String> {
+    let s = s.trim();
+    s.parse::<HexHash>()
+        .map(NodeID::from)
+        .map_err(|e| format!("'{}': {}", s, e.describe("NodeID")))
+}
+
+/// Reassign `batches` (bottom-up, earliest first) so every `tree_edges` (child, parent) pair and
+/// every `hints` (before, after) pair has its first node in an earlier level than its second.
+///
+/// Nodes keep their batch level unless an edge pushes them later. Hints naming a node outside
+/// the batches, or forming a cycle with each other or the tree, are rejected.
+pub fn apply_order_hints(
+    batches: Vec<Vec<NodeID>>,
+    tree_edges: &[(NodeID, NodeID)],
+    hints: &[(NodeID, NodeID)],
+) -> Result<Vec<Vec<NodeID>>, ApiError> {
+    let nodes: Vec<(NodeID, usize)> = batches
+        .iter()
+        .enumerate()
+        .flat_map(|(level, batch)| batch.iter().map(move |node_id| (*node_id, level)))
+        .collect();
+    let index: HashMap<NodeID, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(position, (node_id, _))| (*node_id, position))
+        .collect();
+
+    for (before, after) in hints {
+        for node_id in [before, after] {
+            if !index.contains_key(node_id) {
+                return Err(ApiError::ConfigError(format!(
+                    "--after node {} is not in the generation plan for this target.",
+                    hex::encode(node_id)
+                )));
+            }
+        }
+    }
+
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut in_degree = vec![0usize; nodes.len()];
+    for (from, to) in tree_edges.iter().chain(hints) {
+        if let (Some(&from), Some(&to)) = (index.get(from), index.get(to)) {
+            successors[from].push(to);
+            in_degree[to] += 1;
+        }
+    }
+
+    // Kahn's algorithm; the ready set is ordered by position so the result is deterministic.
+    let mut levels: Vec<usize> = nodes.iter().map(|(_, level)| *level).collect();
+    let mut ready: BTreeSet<usize> = (0..nodes.len())
+        .filter(|&position| in_degree[position] == 0)
+        .collect();
+    let mut visited = 0;
+    while let Some(position) = ready.pop_first() {
+        visited += 1;
+        for &next in &successors[position] {
+            levels[next] = levels[next].max(levels[position] + 1);
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.insert(next);
+            }
+        }
+    }
+    if visited < nodes.len() {
+        let cyclic: Vec<String> = hints
+            .iter()
+            .filter(|(before, after)| in_degree[index[before]] > 0 || in_degree[index[after]] > 0)
+            .map(|(before, after)| format!("{}:{}", hex::encode(before), hex::encode(after)))
+            .collect();
+        return Err(ApiError::ConfigError(format!(
+            "--after hints form a cycle with each other or the directory tree: {}",
+            cyclic.join(", ")
+        )));
+    }
+
+    let level_count = levels.iter().max().map_or(0, |max| max + 1);
+    let mut reordered: Vec<Vec<NodeID>> = vec![Vec::new(); level_count];
+    for (position, (node_id, _)) in nodes.iter().enumerate() {
+        reordered[levels[position]].push(*node_id);
+    }
+    reordered.retain(|level| !level.is_empty());
+    Ok(reordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(byte: u8) -> NodeID {
+        [byte; 32]
+    }
+
+    #[test]
+    fn hint_moves_dependent_and_its_ancestors_later() {
+        // root(1) has files a(2), b(3), c(4): one level of files, then the root.
+        let batches = vec![vec![id(2), id(3), id(4)], vec![id(1)]];
+        let tree_edges = [(id(2), id(1)), (id(3), id(1)), (id(4), id(1))];
+
+        let hints =
+            parse_order_hints(&[format!("{}:{}", hex::encode(id(2)), hex::encode(id(3)))]).unwrap();
+        let levels = apply_order_hints(batches.clone(), &tree_edges, &hints).unwrap();
+        assert_eq!(
+            levels,
+            vec![vec![id(2), id(4)], vec![id(3)], vec![id(1)]],
+            "b waits for a, and the root still follows all children"
+        );
+
+        assert_eq!(
+            apply_order_hints(batches.clone(), &tree_edges, &[]).unwrap(),
+            batches
+        );
+    }
+
+    #[test]
+    fn cyclic_hints_are_rejected() {
+        let batches = vec![vec![id(2), id(3)], vec![id(1)]];
+        let tree_edges = [(id(2), id(1)), (id(3), id(1))];
+
+        let err = apply_order_hints(
+            batches.clone(),
+            &tree_edges,
+            &[(id(2), id(3)), (id(3), id(2))],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("form a cycle"), "{}", err);
+
+        // Asking a parent to run before its own child contradicts the tree.
+        let err = apply_order_hints(batches.clone(), &tree_edges, &[(id(1), id(2))]).unwrap_err();
+        assert!(err.to_string().contains("form a cycle"), "{}", err);
+
+        let err = apply_order_hints(batches, &tree_edges, &[(id(2), id(9))]).unwrap_err();
+        assert!(
+            err.to_string().contains("not in the generation plan"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_hints() {
+        let a = hex::encode(id(2));
+        assert!(parse_order_hints(std::slice::from_ref(&a)).is_err());
+        assert!(parse_order_hints(&[format!("{}:{}", a, a)]).is_err());
+        assert!(parse_order_hints(&[format!("{}:zz", a)]).is_err());
+    }
+}
@@ -1,10 +1,11 @@
+use crate::context::generation::contracts::HeadConflictPolicy;
 use crate::context::generation::program::TargetExecutionProgram;
 use crate::context::queue::Priority;
 use crate::error::ApiError;
 use crate::provider::ProviderExecutionBinding;
 use crate::types::{FrameID, NodeID};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum GenerationNodeType {
@@ -20,6 +21,31 @@ pub enum PlanPriority {
     Urgent,
 }
 
+impl PlanPriority {
+    /// Parse a `--priority` value: `low`, `normal`, `high`, or `urgent`.
+    pub fn parse(value: &str) -> Result<Self, ApiError> {
+        match value.to_ascii_lowercase().as_str() {
+            "low" => Ok(PlanPriority::Low),
+            "normal" => Ok(PlanPriority::Normal),
+            "high" => Ok(PlanPriority::High),
+            "urgent" => Ok(PlanPriority::Urgent),
+            _ => Err(ApiError::ConfigError(format!(
+                "Invalid priority '{}'. Expected low, normal, high, or urgent.",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PlanPriority::Low => "low",
+            PlanPriority::Normal => "normal",
+            PlanPriority::High => "high",
+            PlanPriority::Urgent => "urgent",
+        }
+    }
+}
+
 impl From<PlanPriority> for Priority {
     fn from(value: PlanPriority) -> Self {
         match value {
@@ -48,6 +74,27 @@ pub struct GenerationItem {
     pub frame_type: String,
     pub force: bool,
     pub program: TargetExecutionProgram,
+    /// Verbatim user prompt replacing the agent template for this item.
+    #[serde(default)]
+    pub user_prompt_override: Option<String>,
+    /// Stream the provider response to stderr while this item is generated.
+    #[serde(default)]
+    pub stream_output: bool,
+    /// Record the source file's modification time in the frame's `source_mtime` metadata.
+    #[serde(default)]
+    pub preserve_mtime: bool,
+    /// Record generation duration, retry count, and provider name in frame metadata.
+    #[serde(default)]
+    pub record_provenance: bool,
+    /// Custom prompt placeholders from `--prompt-var`.
+    #[serde(default)]
+    pub prompt_vars: BTreeMap<String, String>,
+    /// How to write the frame when the head moved away from `expected_head`.
+    #[serde(default)]
+    pub on_conflict: HeadConflictPolicy,
+    /// Head for `(node_id, frame_type)` when the plan was built.
+    #[serde(default)]
+    pub expected_head: Option<FrameID>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +147,9 @@ impl GenerationPlan {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationErrorDetail {
+    /// Workspace path of the failed node; empty for results recorded before paths were tracked.
+    #[serde(default)]
+    pub path: String,
     pub message: String,
 }
 
@@ -109,6 +159,12 @@ pub struct LevelSummary {
     pub generated_count: usize,
     pub failed_count: usize,
     pub total_count: usize,
+    /// Wall-clock time from the level's first submission to its last result.
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Most of the level's nodes seen processing at once; `None` when the queue cannot tell.
+    #[serde(default)]
+    pub peak_concurrency: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,6 +175,12 @@ pub struct GenerationResult {
     pub level_summaries: Vec<LevelSummary>,
     pub total_generated: usize,
     pub total_failed: usize,
+    /// The run hit its `--timeout` before every planned node finished.
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Planned nodes with no result when the run stopped early on its timeout.
+    #[serde(default)]
+    pub remaining_nodes: usize,
 }
 
 impl GenerationResult {
@@ -130,8 +192,21 @@ impl GenerationResult {
             level_summaries: Vec::new(),
             total_generated: 0,
             total_failed: 0,
+            timed_out: false,
+            remaining_nodes: 0,
         }
     }
+
+    /// Per-node failures as `(node_path, error_message)`, sorted by path for stable output.
+    pub fn failure_report(&self) -> Vec<(String, String)> {
+        let mut report: Vec<(String, String)> = self
+            .failures
+            .values()
+            .map(|detail| (detail.path.clone(), detail.message.clone()))
+            .collect();
+        report.sort();
+        report
+    }
 }
 
 #[cfg(test)]
@@ -153,6 +228,13 @@ mod tests {
             frame_type: "context-writer".to_string(),
             force: false,
             program: TargetExecutionProgram::single_shot(),
+            user_prompt_override: None,
+            stream_output: false,
+            preserve_mtime: false,
+            record_provenance: false,
+            prompt_vars: Default::default(),
+            on_conflict: Default::default(),
+            expected_head: None,
         }
     }
 
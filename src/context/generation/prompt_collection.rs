@@ -15,7 +15,9 @@ pub fn build_prompt_messages(
     prompt_contract: &PromptContract,
 ) -> Result<PromptAssemblyOutput, ApiError> {
     let user_prompt_template = match node_record.node_type {
-        NodeType::File { .. } => prompt_contract.user_prompt_file.clone(),
+        NodeType::File { .. } | NodeType::Symlink { .. } => {
+            prompt_contract.user_prompt_file.clone()
+        }
         NodeType::Directory => prompt_contract.user_prompt_directory.clone(),
     };
 
@@ -24,12 +26,14 @@ pub fn build_prompt_messages(
         &node_record.path.display().to_string(),
         match node_record.node_type {
             NodeType::File { size, .. } => Some(size),
-            NodeType::Directory => None,
+            NodeType::Directory | NodeType::Symlink { .. } => None,
         },
+        &request.prompt_vars,
     );
 
     let prompt_context = match node_record.node_type {
         NodeType::File { .. } => Some(collect_file_source_context(node_record)?),
+        NodeType::Symlink { .. } => None,
         NodeType::Directory => {
             let child_context_text =
                 collect_directory_child_context_text(api, node_record, request)?;
@@ -88,18 +92,29 @@ fn collect_directory_child_context_text(
         return Ok(String::new());
     }
 
-    let child_view = crate::context::query::view::ContextView {
+    let child_view = |frame_type: String| crate::context::query::view::ContextView {
         max_frames: 1,
         ordering: OrderingPolicy::Recency,
         filters: vec![
-            FrameFilter::ByType(request.frame_type.clone()),
+            FrameFilter::ByType(frame_type),
             FrameFilter::ByAgent(request.agent_id.clone()),
         ],
     };
+    // A directory generated under the agent's default frame type reads each child under that
+    // child's own default, which differs by node type when frame_type_per_node_type is set.
+    let agent = api.get_agent(&request.agent_id)?;
+    let uses_default_frame_type =
+        request.frame_type == agent.default_frame_type(&node_record.node_type);
 
     let mut child_sections = Vec::new();
     for child_id in &node_record.children {
-        let child_context = api.get_node(*child_id, child_view.clone())?;
+        let mut child_context = api.get_node(*child_id, child_view(request.frame_type.clone()))?;
+        if uses_default_frame_type {
+            let child_frame_type = agent.default_frame_type(&child_context.node_record.node_type);
+            if child_frame_type != request.frame_type {
+                child_context = api.get_node(*child_id, child_view(child_frame_type))?;
+            }
+        }
         if child_context.frames.is_empty() {
             continue;
         }
@@ -107,6 +122,7 @@ fn collect_directory_child_context_text(
         let child_kind = match child_context.node_record.node_type {
             NodeType::File { .. } => "File",
             NodeType::Directory => "Directory",
+            NodeType::Symlink { .. } => "Symlink",
         };
         let child_text = child_context
             .frames
@@ -2,7 +2,11 @@
 //! CLI and other callers use this only; no plan/queue/executor orchestration in adapters.
 
 use crate::agent::profile::prompt_contract::PromptContract;
+use crate::agent::{AgentIdentity, Capability};
 use crate::api::ContextApi;
+use crate::context::generation::contracts::HeadConflictPolicy;
+use crate::context::generation::exclude::PathExclusions;
+use crate::context::generation::ordering::apply_order_hints;
 use crate::context::generation::plan::{
     FailurePolicy, GenerationItem, GenerationNodeType, GenerationPlan, PlanPriority,
 };
@@ -15,51 +19,21 @@ use crate::merkle_traversal::{traverse, TraversalStrategy};
 use crate::provider::ProviderExecutionBinding;
 use crate::store::NodeType;
 use crate::telemetry::{now_millis, ProgressRuntime};
-use crate::types::NodeID;
+use crate::types::{parse_node_id, NodeID};
 use crate::workspace;
 use serde_json::json;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
-fn format_failure_samples(
-    result: &crate::context::generation::plan::GenerationResult,
-    max_samples: usize,
-) -> String {
-    let mut messages: Vec<&str> = result
-        .failures
-        .values()
-        .map(|detail| detail.message.as_str())
-        .collect();
-    messages.sort_unstable();
-    messages.dedup();
-
-    let samples: Vec<&str> = messages.into_iter().take(max_samples).collect();
-    if samples.is_empty() {
-        return String::new();
-    }
-
-    let mut out = format!(" Sample errors: {}", samples.join(" | "));
-    let remaining = result.failures.len().saturating_sub(samples.len());
-    if remaining > 0 {
-        out.push_str(&format!(" | ... and {} more", remaining));
-    }
-    out
-}
-
-fn parse_node_id(s: &str) -> Result<NodeID, ApiError> {
-    let s = s.strip_prefix("0x").unwrap_or(s);
-    let bytes =
-        hex::decode(s).map_err(|e| ApiError::InvalidFrame(format!("Invalid hex string: {}", e)))?;
-    if bytes.len() != 32 {
-        return Err(ApiError::InvalidFrame(format!(
-            "NodeID must be 32 bytes, got {} bytes",
-            bytes.len()
-        )));
-    }
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&bytes);
-    Ok(crate::types::Hash::from(hash))
+/// One line per failed node so users can see exactly which paths failed and why.
+fn format_failure_report(result: &crate::context::generation::plan::GenerationResult) -> String {
+    result
+        .failure_report()
+        .into_iter()
+        .map(|(path, message)| format!("\n  {}: {}", path, message))
+        .collect()
 }
 
 fn resolve_agent_id(api: &ContextApi, agent_id: Option<&str>) -> Result<String, ApiError> {
@@ -85,10 +59,44 @@ fn resolve_agent_id(api: &ContextApi, agent_id: Option<&str>) -> Result<String,
     }
 }
 
+/// Provider for a generate run: explicit `--provider` wins, then the agent's `default_provider`.
+pub fn resolve_provider_name(
+    api: &ContextApi,
+    agent_id: Option<&str>,
+    provider_name: Option<&str>,
+) -> Result<String, ApiError> {
+    if let Some(provider_name) = provider_name {
+        return Ok(provider_name.to_string());
+    }
+    let default_provider = resolve_agent_id(api, agent_id)
+        .ok()
+        .and_then(|agent_id| api.get_agent(&agent_id).ok())
+        .and_then(|agent| agent.default_provider().map(str::to_string));
+    default_provider.ok_or_else(|| {
+        ApiError::ProviderNotConfigured(
+            "Provider is required. Use `--provider <provider_name>` to specify a provider, or set `default_provider` on the agent. Use `meld provider list` to see available providers.".to_string(),
+        )
+    })
+}
+
+/// Frame type for a planned node: the requested type, or the agent's default for its node type.
+fn resolve_frame_type(
+    requested_frame_type: Option<&str>,
+    agent: &AgentIdentity,
+    node_type: &NodeType,
+) -> String {
+    requested_frame_type
+        .map(str::to_string)
+        .unwrap_or_else(|| agent.default_frame_type(node_type))
+}
+
 fn find_missing_descendant_heads(
     api: &ContextApi,
     target_node_id: NodeID,
-    frame_type: &str,
+    requested_frame_type: Option<&str>,
+    agent: &AgentIdentity,
+    include_binary: bool,
+    max_depth: Option<usize>,
 ) -> Result<Vec<String>, ApiError> {
     let mut missing = Vec::new();
     let mut visited: HashSet<NodeID> = HashSet::new();
@@ -99,9 +107,12 @@ fn find_missing_descendant_heads(
         .map_err(ApiError::from)?
         .ok_or(ApiError::NodeNotFound(target_node_id))?;
     for child in &target_record.children {
-        queue.push_back(*child);
+        queue.push_back((*child, 1usize));
     }
-    while let Some(node_id) = queue.pop_front() {
+    while let Some((node_id, depth)) = queue.pop_front() {
+        if max_depth.is_some_and(|max| depth > max) {
+            continue;
+        }
         if !visited.insert(node_id) {
             continue;
         }
@@ -110,16 +121,103 @@ fn find_missing_descendant_heads(
             .get(&node_id)
             .map_err(ApiError::from)?
             .ok_or(ApiError::NodeNotFound(node_id))?;
-        if api.get_head(&node_id, frame_type)?.is_none() {
+        let frame_type = resolve_frame_type(requested_frame_type, agent, &record.node_type);
+        let skipped = record.is_symlink() || (!include_binary && record.is_binary());
+        if !skipped && api.get_head(&node_id, &frame_type)?.is_none() {
             missing.push(record.path.to_string_lossy().to_string());
         }
         for child in &record.children {
-            queue.push_back(*child);
+            queue.push_back((*child, depth + 1));
         }
     }
     Ok(missing)
 }
 
+/// Active head frame types indexed by node path, for `--only-stale` basis change detection.
+struct PathHeads(HashMap<PathBuf, HashSet<String>>);
+
+impl PathHeads {
+    fn load(api: &ContextApi) -> Result<Self, ApiError> {
+        let entries = api.head_index().read().active_entries();
+        let mut by_path: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+        for entry in entries {
+            if let Some(record) = api
+                .node_store()
+                .get(&entry.node_id)
+                .map_err(ApiError::from)?
+            {
+                by_path
+                    .entry(record.path)
+                    .or_default()
+                    .insert(entry.frame_type);
+            }
+        }
+        Ok(Self(by_path))
+    }
+
+    /// Whether `path` has a `frame_type` head on some node. Called for nodes without a head of
+    /// their own, so a match is a head whose basis is an earlier NodeID for the same path.
+    fn basis_changed(&self, path: &Path, frame_type: &str) -> bool {
+        self.0
+            .get(path)
+            .is_some_and(|frame_types| frame_types.contains(frame_type))
+    }
+}
+
+/// Why a node is left out of the plan, or `None` when it should be generated.
+///
+/// A head marked stale by `context touch` is regenerated like a forced one.
+fn plan_skip_reason(
+    api: &ContextApi,
+    node_id: &NodeID,
+    path: &Path,
+    frame_type: &str,
+    force: bool,
+    stale: bool,
+    path_heads: Option<&PathHeads>,
+) -> Result<Option<&'static str>, ApiError> {
+    if force || stale {
+        return Ok(None);
+    }
+    if api.get_head(node_id, frame_type)?.is_some() {
+        return Ok(Some("head_reuse"));
+    }
+    if path_heads.is_some_and(|heads| !heads.basis_changed(path, frame_type)) {
+        return Ok(Some("not_stale"));
+    }
+    Ok(None)
+}
+
+/// Drop nodes whose path matches `--exclude` from bottom-up `batches`, along with levels left
+/// empty. Returns the kept batches and the number of nodes dropped.
+fn exclude_paths(
+    api: &ContextApi,
+    batches: Vec<Vec<NodeID>>,
+    exclusions: &PathExclusions,
+) -> Result<(Vec<Vec<NodeID>>, usize), ApiError> {
+    let mut excluded = 0;
+    let mut kept = Vec::with_capacity(batches.len());
+    for level in batches {
+        let mut level_kept = Vec::with_capacity(level.len());
+        for node_id in level {
+            let record = api
+                .node_store()
+                .get(&node_id)
+                .map_err(ApiError::from)?
+                .ok_or(ApiError::NodeNotFound(node_id))?;
+            if exclusions.excludes(&record.path) {
+                excluded += 1;
+            } else {
+                level_kept.push(node_id);
+            }
+        }
+        if !level_kept.is_empty() {
+            kept.push(level_kept);
+        }
+    }
+    Ok((kept, excluded))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_plan(
     api: &ContextApi,
@@ -130,11 +228,32 @@ fn build_plan(
     is_directory_target: bool,
     recursive: bool,
     force: bool,
-    agent_id: &str,
+    only_stale: bool,
+    include_binary: bool,
+    max_depth: Option<usize>,
+    order_hints: &[(NodeID, NodeID)],
+    exclusions: &PathExclusions,
+    agent: &AgentIdentity,
     provider: &ProviderExecutionBinding,
-    frame_type: &str,
+    requested_frame_type: Option<&str>,
     program: &TargetExecutionProgram,
+    failure_policy: FailurePolicy,
+    priority: PlanPriority,
 ) -> Result<GenerationPlan, ApiError> {
+    let agent_id = agent.agent_id.as_str();
+    let path_heads = if only_stale && !force {
+        Some(PathHeads::load(api)?)
+    } else {
+        None
+    };
+    let target_record = api
+        .node_store()
+        .get(&target_node_id)
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NodeNotFound(target_node_id))?;
+    let target_frame_type =
+        resolve_frame_type(requested_frame_type, agent, &target_record.node_type);
+
     if !recursive && is_directory_target && !force {
         if let (Some(prog), Some(sid)) = (progress, session_id) {
             prog.emit_event_best_effort(
@@ -143,11 +262,18 @@ fn build_plan(
                 json!({
                     "node_id": hex::encode(target_node_id),
                     "path": target_path.to_string_lossy(),
-                    "frame_type": frame_type,
+                    "frame_type": target_frame_type,
                 }),
             );
         }
-        let missing = find_missing_descendant_heads(api, target_node_id, frame_type)?;
+        let missing = find_missing_descendant_heads(
+            api,
+            target_node_id,
+            requested_frame_type,
+            agent,
+            include_binary,
+            max_depth,
+        )?;
         if !missing.is_empty() {
             if let (Some(prog), Some(sid)) = (progress, session_id) {
                 prog.emit_event_best_effort(
@@ -171,7 +297,7 @@ fn build_plan(
                 json!({
                     "node_id": hex::encode(target_node_id),
                     "path": target_path.to_string_lossy(),
-                    "frame_type": frame_type,
+                    "frame_type": target_frame_type,
                 }),
             );
         }
@@ -179,8 +305,37 @@ fn build_plan(
 
     let mut levels: Vec<Vec<GenerationItem>> = Vec::new();
     if recursive {
-        let traversal = traverse(api, target_node_id, TraversalStrategy::BottomUp)?;
-        for level in traversal.into_batches() {
+        let batches = traverse(api, target_node_id, TraversalStrategy::BottomUp)?.into_batches();
+        // Bottom-up batches run deepest first, one per depth; drop those past the bound.
+        let excluded_levels =
+            max_depth.map_or(0, |max| batches.len().saturating_sub(max.saturating_add(1)));
+        let mut batches: Vec<Vec<NodeID>> = batches.into_iter().skip(excluded_levels).collect();
+        if !exclusions.is_empty() {
+            let (kept, excluded) = exclude_paths(api, batches, exclusions)?;
+            batches = kept;
+            if let (Some(prog), Some(sid)) = (progress, session_id) {
+                prog.emit_event_best_effort(
+                    sid,
+                    "nodes_excluded",
+                    json!({
+                        "node_id": hex::encode(target_node_id),
+                        "path": target_path.to_string_lossy(),
+                        "excluded_count": excluded,
+                    }),
+                );
+            }
+        }
+        if !order_hints.is_empty() {
+            let mut tree_edges = Vec::new();
+            for node_id in batches.iter().flatten() {
+                let record = api.node_store().get(node_id).map_err(ApiError::from)?;
+                if let Some(parent) = record.and_then(|record| record.parent) {
+                    tree_edges.push((*node_id, parent));
+                }
+            }
+            batches = apply_order_hints(batches, &tree_edges, order_hints)?;
+        }
+        for level in batches {
             let mut items = Vec::new();
             for node_id in level {
                 let record = api
@@ -188,7 +343,8 @@ fn build_plan(
                     .get(&node_id)
                     .map_err(ApiError::from)?
                     .ok_or(ApiError::NodeNotFound(node_id))?;
-                if !force && api.get_head(&node_id, frame_type)?.is_some() {
+                let frame_type = resolve_frame_type(requested_frame_type, agent, &record.node_type);
+                if record.is_symlink() {
                     if let (Some(prog), Some(sid)) = (progress, session_id) {
                         prog.emit_event_best_effort(
                             sid,
@@ -199,7 +355,50 @@ fn build_plan(
                                 "agent_id": agent_id,
                                 "provider_name": provider.provider_name,
                                 "frame_type": frame_type,
-                                "reason": "head_reuse",
+                                "reason": "symlink",
+                            }),
+                        );
+                    }
+                    continue;
+                }
+                if !include_binary && record.is_binary() {
+                    if let (Some(prog), Some(sid)) = (progress, session_id) {
+                        prog.emit_event_best_effort(
+                            sid,
+                            "node_skipped",
+                            json!({
+                                "node_id": hex::encode(node_id),
+                                "path": record.path.to_string_lossy(),
+                                "agent_id": agent_id,
+                                "provider_name": provider.provider_name,
+                                "frame_type": frame_type,
+                                "reason": "binary",
+                            }),
+                        );
+                    }
+                    continue;
+                }
+                let stale = record.is_head_stale(&frame_type);
+                if let Some(reason) = plan_skip_reason(
+                    api,
+                    &node_id,
+                    &record.path,
+                    &frame_type,
+                    force,
+                    stale,
+                    path_heads.as_ref(),
+                )? {
+                    if let (Some(prog), Some(sid)) = (progress, session_id) {
+                        prog.emit_event_best_effort(
+                            sid,
+                            "node_skipped",
+                            json!({
+                                "node_id": hex::encode(node_id),
+                                "path": record.path.to_string_lossy(),
+                                "agent_id": agent_id,
+                                "provider_name": provider.provider_name,
+                                "frame_type": frame_type,
+                                "reason": reason,
                             }),
                         );
                     }
@@ -209,14 +408,23 @@ fn build_plan(
                     node_id,
                     path: record.path.to_string_lossy().to_string(),
                     node_type: match record.node_type {
-                        NodeType::File { .. } => GenerationNodeType::File,
+                        NodeType::File { .. } | NodeType::Symlink { .. } => {
+                            GenerationNodeType::File
+                        }
                         NodeType::Directory => GenerationNodeType::Directory,
                     },
                     agent_id: agent_id.to_string(),
                     provider: provider.clone(),
-                    frame_type: frame_type.to_string(),
-                    force,
+                    frame_type,
+                    force: force || stale,
                     program: program.clone(),
+                    user_prompt_override: None,
+                    stream_output: false,
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    prompt_vars: Default::default(),
+                    on_conflict: Default::default(),
+                    expected_head: None,
                 });
             }
             if !items.is_empty() {
@@ -224,7 +432,16 @@ fn build_plan(
             }
         }
     } else {
-        if !force && api.get_head(&target_node_id, frame_type)?.is_some() {
+        let stale = target_record.is_head_stale(&target_frame_type);
+        if let Some(reason) = plan_skip_reason(
+            api,
+            &target_node_id,
+            &target_record.path,
+            &target_frame_type,
+            force,
+            stale,
+            path_heads.as_ref(),
+        )? {
             if let (Some(prog), Some(sid)) = (progress, session_id) {
                 prog.emit_event_best_effort(
                     sid,
@@ -234,8 +451,8 @@ fn build_plan(
                         "path": target_path.to_string_lossy(),
                         "agent_id": agent_id,
                         "provider_name": provider.provider_name,
-                        "frame_type": frame_type,
-                        "reason": "head_reuse",
+                        "frame_type": target_frame_type,
+                        "reason": reason,
                     }),
                 );
             }
@@ -248,30 +465,32 @@ fn build_plan(
                 source: format!("context generate {}", target_path.to_string_lossy()),
                 session_id: session_id.map(String::from),
                 levels: Vec::new(),
-                priority: PlanPriority::Urgent,
-                failure_policy: FailurePolicy::StopOnLevelFailure,
+                priority,
+                failure_policy,
                 target_path: target_path.to_string_lossy().to_string(),
                 total_nodes: 0,
                 total_levels: 0,
             });
         }
-        let target_record = api
-            .node_store()
-            .get(&target_node_id)
-            .map_err(ApiError::from)?
-            .ok_or(ApiError::NodeNotFound(target_node_id))?;
         levels.push(vec![GenerationItem {
             node_id: target_node_id,
             path: target_record.path.to_string_lossy().to_string(),
             node_type: match target_record.node_type {
-                NodeType::File { .. } => GenerationNodeType::File,
+                NodeType::File { .. } | NodeType::Symlink { .. } => GenerationNodeType::File,
                 NodeType::Directory => GenerationNodeType::Directory,
             },
             agent_id: agent_id.to_string(),
             provider: provider.clone(),
-            frame_type: frame_type.to_string(),
-            force,
+            frame_type: target_frame_type,
+            force: force || stale,
             program: program.clone(),
+            user_prompt_override: None,
+            stream_output: false,
+            preserve_mtime: false,
+            record_provenance: false,
+            prompt_vars: Default::default(),
+            on_conflict: Default::default(),
+            expected_head: None,
         }]);
     }
 
@@ -286,8 +505,8 @@ fn build_plan(
         session_id: session_id.map(String::from),
         total_levels: levels.len(),
         levels,
-        priority: PlanPriority::Urgent,
-        failure_policy: FailurePolicy::StopOnLevelFailure,
+        priority,
+        failure_policy,
         target_path: target_path.to_string_lossy().to_string(),
         total_nodes,
     })
@@ -303,7 +522,40 @@ pub struct GenerateRequest {
     pub workflow_id: Option<String>,
     pub frame_type: Option<String>,
     pub force: bool,
+    /// Generate only nodes whose path has a head for an earlier NodeID; skip new and current nodes.
+    pub only_stale: bool,
     pub no_recursive: bool,
+    /// Keep generating remaining levels after node failures and report every failure at the end.
+    pub continue_on_error: bool,
+    /// Verbatim user prompt replacing the agent template; single-node, single-shot targets only.
+    pub user_prompt_override: Option<String>,
+    /// Stream the provider response to stderr; single-node, single-shot targets only.
+    pub stream_output: bool,
+    /// Cap on provider retries across the whole plan; `None` keeps only the per-request limit.
+    pub max_total_retries: Option<usize>,
+    /// Plan files flagged `is_binary` by the tree builder instead of skipping them.
+    pub include_binary: bool,
+    /// Custom prompt placeholders from `--prompt-var`, applied after the built-in ones.
+    pub prompt_vars: BTreeMap<String, String>,
+    /// Deepest level below the target to plan (0 = target only); `None` plans the whole subtree.
+    /// Also bounds the descendant head check for non-recursive directory targets.
+    pub max_depth: Option<usize>,
+    /// `--after` (before, after) pairs: the first node is planned in an earlier level than the
+    /// second. Recursive plans only.
+    pub order_hints: Vec<(NodeID, NodeID)>,
+    /// Queue priority for every item in the plan; interactive runs use `Urgent`.
+    pub priority: PlanPriority,
+    /// Record each file's modification time in its frame's `source_mtime` metadata.
+    pub preserve_mtime: bool,
+    /// Record generation duration, retry count, and provider name in each frame's metadata.
+    pub record_provenance: bool,
+    /// Wall-clock limit for the whole run; `None` runs until every planned node finishes.
+    pub timeout: Option<Duration>,
+    /// How each frame is written when another writer moved its head after the plan was built.
+    pub on_conflict: HeadConflictPolicy,
+    /// `--exclude` globs over workspace-relative paths; matching nodes and their subtrees are
+    /// left out of recursive plans.
+    pub exclude: Vec<String>,
 }
 
 /// Single generate entry point: resolve node/agent/provider, build plan, create queue, execute.
@@ -315,14 +567,16 @@ pub fn run_generate(
     session_id: Option<&str>,
     request: &GenerateRequest,
 ) -> Result<String, ApiError> {
+    workspace::ensure_workspace_populated(api.as_ref(), workspace_root)?;
     let node_id = match (request.node.as_deref(), request.path.as_deref()) {
         (Some(node_str), None) => parse_node_id(node_str)?,
-        (None, Some(p)) => workspace::resolve_workspace_node_id(
+        (None, Some(p)) => workspace::resolve_workspace_node_id_with_policy(
             api.as_ref(),
             workspace_root,
             Some(p),
             None,
             false,
+            api.strict_paths(),
         )?,
         (Some(_), Some(_)) => {
             return Err(ApiError::ConfigError(
@@ -341,11 +595,6 @@ pub fn run_generate(
         let registry = api.provider_registry().read();
         registry.get_or_error(&request.provider.provider_name)?;
     }
-    let frame_type = request
-        .frame_type
-        .clone()
-        .unwrap_or_else(|| format!("context-{}", agent_id));
-
     let agent = api.get_agent(&agent_id)?;
     let execution_program = if let Some(workflow_id) = request.workflow_id.as_deref() {
         TargetExecutionProgram::workflow(workflow_id)
@@ -372,9 +621,119 @@ pub fn run_generate(
     }
 
     let is_directory_target = matches!(node_record.node_type, NodeType::Directory);
+    let frame_type = resolve_frame_type(
+        request.frame_type.as_deref(),
+        &agent,
+        &node_record.node_type,
+    );
     let recursive = is_directory_target && !request.no_recursive;
 
-    let plan = build_plan(
+    if node_record.is_symlink() {
+        return Ok(format!(
+            "Skipped symlink {}.\nSymlinks are indexed but never generated.",
+            node_path
+        ));
+    }
+
+    if !request.include_binary && node_record.is_binary() {
+        return Ok(format!(
+            "Skipped binary file {}.\nUse --include-binary to generate a frame for it.",
+            node_path
+        ));
+    }
+
+    let exclusions = PathExclusions::parse(workspace_root, &request.exclude)?;
+    if !exclusions.is_empty() && !recursive {
+        return Err(ApiError::ConfigError(
+            "--exclude filters a recursive plan; it needs a directory target without --no-recursive."
+                .to_string(),
+        ));
+    }
+
+    if !request.order_hints.is_empty() && !recursive {
+        return Err(ApiError::ConfigError(
+            "--after orders nodes within a recursive plan; it needs a directory target without --no-recursive."
+                .to_string(),
+        ));
+    }
+
+    if request.stream_output {
+        if recursive {
+            return Err(ApiError::ConfigError(
+                "--stream requires a single-node target. Use --no-recursive for directory targets."
+                    .to_string(),
+            ));
+        }
+        if execution_program.kind
+            != crate::context::generation::TargetExecutionProgramKind::SingleShot
+        {
+            return Err(ApiError::ConfigError(format!(
+                "--stream is not supported for workflow '{}'.",
+                execution_program.workflow_id().unwrap_or_default()
+            )));
+        }
+    }
+
+    if request.preserve_mtime
+        && execution_program.kind
+            != crate::context::generation::TargetExecutionProgramKind::SingleShot
+    {
+        return Err(ApiError::ConfigError(format!(
+            "--preserve-mtime is not supported for workflow '{}'.",
+            execution_program.workflow_id().unwrap_or_default()
+        )));
+    }
+
+    if request.record_provenance
+        && execution_program.kind
+            != crate::context::generation::TargetExecutionProgramKind::SingleShot
+    {
+        return Err(ApiError::ConfigError(format!(
+            "--record-provenance is not supported for workflow '{}'.",
+            execution_program.workflow_id().unwrap_or_default()
+        )));
+    }
+
+    if request.user_prompt_override.is_some() {
+        if recursive {
+            return Err(ApiError::ConfigError(
+                "Prompt override requires a single-node target. Use --no-recursive for directory targets.".to_string(),
+            ));
+        }
+        if execution_program.kind
+            != crate::context::generation::TargetExecutionProgramKind::SingleShot
+        {
+            return Err(ApiError::ConfigError(format!(
+                "Prompt override is not supported for workflow '{}'.",
+                execution_program.workflow_id().unwrap_or_default()
+            )));
+        }
+    }
+
+    if request.on_conflict != HeadConflictPolicy::LastWins
+        && execution_program.kind
+            != crate::context::generation::TargetExecutionProgramKind::SingleShot
+    {
+        return Err(ApiError::ConfigError(format!(
+            "--on-conflict {} is not supported for workflow '{}'.",
+            request.on_conflict.as_str(),
+            execution_program.workflow_id().unwrap_or_default()
+        )));
+    }
+
+    if !request.prompt_vars.is_empty()
+        && execution_program.kind
+            != crate::context::generation::TargetExecutionProgramKind::SingleShot
+    {
+        return Err(ApiError::ConfigError(format!(
+            "--prompt-var is not supported for workflow '{}'.",
+            execution_program.workflow_id().unwrap_or_default()
+        )));
+    }
+
+    // An override prompt asks for a new frame, so it regenerates even when a head exists.
+    let force = request.force || request.user_prompt_override.is_some();
+    let mut plan = build_plan(
         api.as_ref(),
         progress.as_ref(),
         session_id,
@@ -382,13 +741,65 @@ pub fn run_generate(
         &node_record.path,
         is_directory_target,
         recursive,
-        request.force,
-        &agent_id,
+        force,
+        request.only_stale,
+        request.include_binary,
+        request.max_depth,
+        &request.order_hints,
+        &exclusions,
+        &agent,
         &request.provider,
-        &frame_type,
+        request.frame_type.as_deref(),
         &execution_program,
+        if request.continue_on_error {
+            FailurePolicy::Continue
+        } else {
+            FailurePolicy::StopOnLevelFailure
+        },
+        request.priority,
     )?;
 
+    if api.require_synthesize() && !agent.can_synthesize() {
+        if let Some(item) = plan
+            .levels
+            .iter()
+            .flatten()
+            .find(|item| item.node_type == GenerationNodeType::Directory)
+        {
+            let synthesizers: Vec<String> = api
+                .agent_registry()
+                .read()
+                .list_by_capability(Capability::Synthesize)
+                .iter()
+                .map(|agent| agent.agent_id.clone())
+                .collect();
+            return Err(ApiError::Unauthorized(format!(
+                "Agent '{}' lacks the Synthesize capability required for directory frames ({}). Agents with Synthesize: {}",
+                agent_id,
+                item.path,
+                if synthesizers.is_empty() {
+                    "none".to_string()
+                } else {
+                    synthesizers.join(", ")
+                }
+            )));
+        }
+    }
+
+    for item in plan.levels.iter_mut().flatten() {
+        item.user_prompt_override = request.user_prompt_override.clone();
+        item.stream_output = request.stream_output;
+        item.preserve_mtime = request.preserve_mtime;
+        item.record_provenance = request.record_provenance;
+        item.prompt_vars = request.prompt_vars.clone();
+        item.on_conflict = request.on_conflict;
+        item.expected_head = api
+            .head_index()
+            .read()
+            .get_head(&item.node_id, &item.frame_type)
+            .map_err(ApiError::from)?;
+    }
+
     if let (Some(prog), Some(sid)) = (progress.as_deref(), session_id) {
         prog.emit_event_best_effort(
             sid,
@@ -402,8 +813,14 @@ pub fn run_generate(
                 "frame_type": frame_type,
                 "program_kind": execution_program.kind_str(),
                 "workflow_id": execution_program.workflow_id(),
-                "force": request.force,
+                "force": force,
+                "only_stale": request.only_stale,
                 "recursive": recursive,
+                "max_depth": request.max_depth,
+                "order_hints": request.order_hints.len(),
+                "exclude": request.exclude,
+                "priority": plan.priority.as_str(),
+                "on_conflict": request.on_conflict.as_str(),
                 "total_nodes": plan.total_nodes,
                 "total_levels": plan.total_levels
             }),
@@ -411,6 +828,9 @@ pub fn run_generate(
     }
 
     if plan.total_nodes == 0 {
+        if request.only_stale {
+            return Ok("No stale frames for requested target.".to_string());
+        }
         return Ok(
             "Frame already exists for requested target.\nUse --force to generate a new frame."
                 .to_string(),
@@ -426,7 +846,10 @@ pub fn run_generate(
             .map_err(|e| ApiError::ProviderError(format!("Failed to create runtime: {}", e)))?
     };
 
-    let gen_config = GenerationConfig::default();
+    let gen_config = GenerationConfig {
+        max_total_retries: request.max_total_retries,
+        ..GenerationConfig::default()
+    };
     let event_context = match (session_id, &progress) {
         (Some(sid), Some(prog)) => Some(QueueEventContext {
             session_id: sid.to_string(),
@@ -442,15 +865,27 @@ pub fn run_generate(
 
     let _guard = rt.enter();
     queue.start()?;
-    let executor = GenerationExecutor::new(progress);
+    let executor = GenerationExecutor::new(progress).with_time_limit(request.timeout);
     drop(_guard);
     let result = rt.block_on(async { executor.execute(queue.as_ref(), plan).await })?;
 
+    if result.timed_out {
+        return Err(ApiError::GenerationFailed(format!(
+            "Generation timed out after {}s: generated={}, failed={}, remaining={}. Completed frames are kept; rerun to generate the remaining nodes.{}",
+            request.timeout.unwrap_or_default().as_secs(),
+            result.total_generated,
+            result.total_failed,
+            result.remaining_nodes,
+            format_failure_report(&result)
+        )));
+    }
+
     if result.total_failed > 0 {
-        let failure_samples = format_failure_samples(&result, 3);
         return Err(ApiError::GenerationFailed(format!(
-            "Generation completed with failures. generated={}, failed={}.{}",
-            result.total_generated, result.total_failed, failure_samples
+            "Generation completed with failures. generated={}, failed={}. Failed nodes:{}",
+            result.total_generated,
+            result.total_failed,
+            format_failure_report(&result)
         )));
     }
     Ok(format!(
@@ -0,0 +1,193 @@
+//! Portable single-node frame export and import.
+//!
+//! `context get --export <file>` writes the frames a `context get` selects for one node, plus
+//! that node's head pointers, to a JSON file. `context import <file>` writes them onto a node in
+//! another store. Frame IDs are recomputed on import, so altered content is rejected.
+
+use crate::api::ContextApi;
+use crate::context::frame::id::compute_frame_id;
+use crate::context::frame::{Basis, Frame};
+use crate::error::ApiError;
+use crate::types::{FrameID, NodeID};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Identifies a node export file.
+pub const NODE_EXPORT_FORMAT: &str = "meld-node-frames";
+/// Current node export layout version.
+pub const NODE_EXPORT_VERSION: u32 = 1;
+
+/// One node's frames and head pointers, as written by `context get --export`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeFrameExport {
+    pub format: String,
+    pub version: u32,
+    /// Hex NodeID in the exporting store.
+    pub node_id: String,
+    /// Node path relative to the exporting workspace root (absolute if outside it).
+    pub path: PathBuf,
+    pub frames: Vec<Frame>,
+    /// Frame type to hex FrameID of the node's head, for each exported frame type.
+    pub heads: BTreeMap<String, String>,
+}
+
+/// Result of importing a node export.
+#[derive(Debug, Clone)]
+pub struct NodeImportResult {
+    pub node_id: NodeID,
+    /// Frame IDs written, in write order; they differ from the export when the node differs.
+    pub frame_ids: Vec<FrameID>,
+    /// Frame types whose head was set to an imported head frame.
+    pub heads: Vec<String>,
+}
+
+/// Build an export of `frames` (already selected for `node_id`) and the node's current heads.
+pub fn export_node_frames(
+    api: &ContextApi,
+    workspace_root: &Path,
+    node_id: NodeID,
+    node_path: &Path,
+    frames: Vec<Frame>,
+) -> Result<NodeFrameExport, ApiError> {
+    let mut heads = BTreeMap::new();
+    for frame in &frames {
+        if heads.contains_key(&frame.frame_type) {
+            continue;
+        }
+        if let Some(head) = api.get_head(&node_id, &frame.frame_type)? {
+            heads.insert(frame.frame_type.clone(), hex::encode(head));
+        }
+    }
+    let root = crate::tree::path::canonicalize_path(workspace_root)
+        .unwrap_or_else(|_| workspace_root.to_path_buf());
+    let path = node_path
+        .strip_prefix(&root)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| node_path.to_path_buf());
+    Ok(NodeFrameExport {
+        format: NODE_EXPORT_FORMAT.to_string(),
+        version: NODE_EXPORT_VERSION,
+        node_id: hex::encode(node_id),
+        path,
+        frames,
+        heads,
+    })
+}
+
+/// Write `export` to `file` as pretty JSON.
+pub fn write_node_export(file: &Path, export: &NodeFrameExport) -> Result<(), ApiError> {
+    let json = serde_json::to_string_pretty(export)
+        .map_err(|e| ApiError::ConfigError(format!("Failed to serialize node export: {}", e)))?;
+    std::fs::write(file, json).map_err(|e| {
+        ApiError::StorageError(crate::error::StorageError::io_context(
+            "write node export",
+            file,
+            e,
+        ))
+    })
+}
+
+/// Read a node export written by [`write_node_export`].
+pub fn read_node_export(file: &Path) -> Result<NodeFrameExport, ApiError> {
+    let content = std::fs::read_to_string(file).map_err(|e| {
+        ApiError::StorageError(crate::error::StorageError::io_context(
+            "read node export",
+            file,
+            e,
+        ))
+    })?;
+    let export: NodeFrameExport = serde_json::from_str(&content).map_err(|e| {
+        ApiError::ConfigError(format!("Invalid node export {}: {}", file.display(), e))
+    })?;
+    if export.format != NODE_EXPORT_FORMAT || export.version != NODE_EXPORT_VERSION {
+        return Err(ApiError::ConfigError(format!(
+            "Unsupported node export {}: format '{}' version {} (expected '{}' version {})",
+            file.display(),
+            export.format,
+            export.version,
+            NODE_EXPORT_FORMAT,
+            NODE_EXPORT_VERSION
+        )));
+    }
+    Ok(export)
+}
+
+/// Write the exported frames onto `node_id`.
+///
+/// Every frame is integrity-checked against its exported FrameID before anything is written.
+/// Frames are rebased onto `node_id` when it differs from their original node. Non-head frames
+/// are written oldest first and the exported head frames last, so they become the heads here.
+/// Each write goes through [`ContextApi::put_frame`], so the frame's agent must be a registered
+/// writer in this workspace.
+pub fn import_node_frames(
+    api: &ContextApi,
+    node_id: NodeID,
+    export: NodeFrameExport,
+) -> Result<NodeImportResult, ApiError> {
+    for frame in &export.frames {
+        let computed = compute_frame_id(
+            &frame.basis,
+            &frame.content,
+            &frame.frame_type,
+            &frame.agent_id,
+        )
+        .map_err(ApiError::from)?;
+        if computed != frame.frame_id {
+            return Err(ApiError::InvalidFrame(format!(
+                "Frame {} failed integrity check: content does not match its FrameID",
+                hex::encode(frame.frame_id)
+            )));
+        }
+    }
+
+    let NodeFrameExport {
+        mut frames, heads, ..
+    } = export;
+    let is_head =
+        |frame: &Frame| heads.get(&frame.frame_type) == Some(&hex::encode(frame.frame_id));
+    frames.sort_by_key(|frame| (is_head(frame), frame.timestamp));
+
+    let mut result = NodeImportResult {
+        node_id,
+        frame_ids: Vec::new(),
+        heads: Vec::new(),
+    };
+    for frame in frames {
+        let head = is_head(&frame);
+        let frame_type = frame.frame_type.clone();
+        let frame = rebase_frame(frame, node_id)?;
+        let agent_id = frame.agent_id.clone();
+        result
+            .frame_ids
+            .push(api.put_frame(node_id, frame, agent_id)?);
+        if head {
+            result.heads.push(frame_type);
+        }
+    }
+    Ok(result)
+}
+
+fn rebase_frame(frame: Frame, node_id: NodeID) -> Result<Frame, ApiError> {
+    let basis = match frame.basis {
+        Basis::Node(node) if node == node_id => return Ok(frame),
+        Basis::Both { node, .. } if node == node_id => return Ok(frame),
+        Basis::Node(_) => Basis::Node(node_id),
+        Basis::Both { frame: parent, .. } => Basis::Both {
+            node: node_id,
+            frame: parent,
+        },
+        Basis::Frame(_) => return Ok(frame),
+    };
+    let timestamp = frame.timestamp;
+    let mut rebased = Frame::new(
+        basis,
+        frame.content,
+        frame.frame_type,
+        frame.agent_id,
+        frame.metadata,
+    )
+    .map_err(ApiError::from)?;
+    rebased.timestamp = timestamp;
+    Ok(rebased)
+}
@@ -1,14 +1,22 @@
 //! Context query: view policy, composition, and query service.
 //! Single owner of context read behavior; api delegates to this module.
 
+pub mod budget;
 pub mod composition;
 pub mod get;
+pub mod search;
 pub mod service;
 pub mod view;
 pub mod view_policy;
 
+pub use budget::{apply_token_budget, estimate_tokens, TokenBudgetOutcome};
 pub use composition::{compose_frames, CompositionPolicy, CompositionSource};
-pub use get::get_node_for_cli;
+pub use get::{
+    count_frames_for_cli, for_each_node_context_for_cli, get_node_for_cli,
+    keep_newest_frame_per_agent, prepend_ancestor_frames,
+};
+pub use search::{search_frames_for_cli, SearchHit};
+pub use service::count_frames as count_frames_query;
 pub use service::get_node as get_node_query;
 pub use view::{ContextView, ContextViewBuilder, NodeContext};
 pub use view_policy::{get_context_view, FrameFilter, OrderingPolicy, ViewPolicy};
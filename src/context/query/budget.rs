@@ -0,0 +1,98 @@
+//! Token budget for combined `context get` output.
+//!
+//! Frames arrive in `--ordering` order, so the highest-priority frames come first. The budget
+//! keeps the longest prefix whose estimated size, separators included, fits.
+
+use crate::context::frame::Frame;
+
+/// Characters per estimated token; a rough average for English text and source code.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Estimate the tokens in `text`: one per [`CHARS_PER_TOKEN`] characters, rounded up.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// What [`apply_token_budget`] kept and dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenBudgetOutcome {
+    pub budget: usize,
+    pub kept: usize,
+    pub dropped: usize,
+    /// Estimated tokens of the kept frames joined by the separator.
+    pub estimated_tokens: usize,
+}
+
+/// Keep frames, in order, until the next one would push the combined output past `budget`.
+///
+/// Each frame after the first also pays for one `separator`. Frames are never skipped to fit a
+/// smaller one later: everything after the first frame that does not fit is dropped.
+pub fn apply_token_budget(
+    frames: &mut Vec<Frame>,
+    budget: usize,
+    separator: &str,
+) -> TokenBudgetOutcome {
+    let separator_tokens = estimate_tokens(separator);
+    let mut used = 0;
+    let mut kept = 0;
+    for frame in frames.iter() {
+        let text = String::from_utf8_lossy(&frame.content);
+        let cost = estimate_tokens(&text) + if kept > 0 { separator_tokens } else { 0 };
+        if used + cost > budget {
+            break;
+        }
+        used += cost;
+        kept += 1;
+    }
+    let dropped = frames.len() - kept;
+    frames.truncate(kept);
+    TokenBudgetOutcome {
+        budget,
+        kept,
+        dropped,
+        estimated_tokens: used,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::frame::Basis;
+    use crate::metadata::FrameMetadata;
+
+    fn frame(content: &str) -> Frame {
+        Frame::new(
+            Basis::Node([7u8; 32]),
+            content.as_bytes().to_vec(),
+            "context-test".to_string(),
+            "writer".to_string(),
+            FrameMetadata::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("abcdefghi"), 3);
+    }
+
+    #[test]
+    fn budget_keeps_prefix_and_stops_at_first_frame_that_does_not_fit() {
+        // 8 + (1 + 8) = 17 tokens for the first two; the third would need 26.
+        let mut frames = vec![
+            frame(&"a".repeat(32)),
+            frame(&"b".repeat(32)),
+            frame(&"c".repeat(32)),
+            frame("d"),
+        ];
+        let outcome = apply_token_budget(&mut frames, 20, "----");
+        assert_eq!(outcome.kept, 2);
+        assert_eq!(outcome.dropped, 2);
+        assert_eq!(outcome.estimated_tokens, 17);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].content, "b".repeat(32).into_bytes());
+    }
+}
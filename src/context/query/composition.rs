@@ -258,79 +258,18 @@ pub fn compose_frames(
     // Step 2: Apply filters
     let filtered_frames: Vec<(NodeID, Frame)> = candidate_frames
         .into_iter()
-        .filter(|(_, frame)| {
-            policy.filters.iter().all(|filter| match filter {
-                FrameFilter::ByType(filter_type) => frame.frame_type == *filter_type,
-                FrameFilter::ByAgent(filter_agent) => {
-                    frame.agent_id() == Some(filter_agent.as_str())
-                }
-            })
-        })
-        .collect();
-
-    // Step 3: Score and order frames (policy-driven)
-    let mut scored_frames: Vec<(i64, NodeID, Frame)> = filtered_frames
-        .into_iter()
-        .map(|(node_id, frame)| {
-            let score = match policy.ordering {
-                OrderingPolicy::Recency => {
-                    // Use timestamp as score (newer = higher)
-                    frame
-                        .timestamp
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|d| d.as_secs() as i64)
-                        .unwrap_or(0)
-                }
-                OrderingPolicy::Type => {
-                    // Use frame type hash as score (deterministic)
-                    use std::collections::hash_map::DefaultHasher;
-                    use std::hash::{Hash, Hasher};
-                    let mut hasher = DefaultHasher::new();
-                    frame.frame_type.hash(&mut hasher);
-                    hasher.finish() as i64
-                }
-                OrderingPolicy::Agent => {
-                    // Use agent ID hash as score (deterministic)
-                    use std::collections::hash_map::DefaultHasher;
-                    use std::hash::{Hash, Hasher};
-                    let mut hasher = DefaultHasher::new();
-                    frame.agent_id().unwrap_or("").hash(&mut hasher);
-                    hasher.finish() as i64
-                }
-            };
-            (score, node_id, frame)
-        })
+        .filter(|(_, frame)| policy.filters.iter().all(|filter| filter.matches(frame)))
         .collect();
 
-    // Sort by score (descending for Recency, ascending for Type/Agent to maintain lexicographic order)
-    match policy.ordering {
-        OrderingPolicy::Recency => {
-            scored_frames.sort_by(|(score_a, _, _), (score_b, _, _)| score_b.cmp(score_a));
-        }
-        OrderingPolicy::Type | OrderingPolicy::Agent => {
-            // For Type and Agent, we want lexicographic order, so we sort by the actual values
-            // after sorting by score. But since we're using hash, we'll just sort by score.
-            // Actually, let's sort by the actual values for Type and Agent
-            scored_frames.sort_by(|(_, _, frame_a), (_, _, frame_b)| match policy.ordering {
-                OrderingPolicy::Type => frame_a.frame_type.cmp(&frame_b.frame_type),
-                OrderingPolicy::Agent => {
-                    let agent_a = frame_a.agent_id().unwrap_or("");
-                    let agent_b = frame_b.agent_id().unwrap_or("");
-                    agent_a.cmp(agent_b)
-                }
-                _ => unreachable!(),
-            });
-        }
-    }
+    // Step 3: Order frames (policy-driven, ties broken by frame_id)
+    let mut ordered_frames = filtered_frames;
+    ordered_frames.sort_by(|(_, a), (_, b)| policy.ordering.compare_frames(a, b));
 
     // Step 4: Select top N frames (bounded by max_frames)
-    scored_frames.truncate(policy.max_frames);
+    ordered_frames.truncate(policy.max_frames);
 
     // Step 5: Extract frames
-    Ok(scored_frames
-        .into_iter()
-        .map(|(_, _, frame)| frame)
-        .collect())
+    Ok(ordered_frames.into_iter().map(|(_, frame)| frame).collect())
 }
 
 #[cfg(test)]
@@ -2,27 +2,15 @@
 
 use crate::api::{ContextApi, ContextView, NodeContext};
 use crate::error::ApiError;
-use crate::types::NodeID;
+use crate::merkle_traversal::{traverse, TraversalStrategy};
+use crate::store::NodeType;
+use crate::types::{parse_node_id, NodeID};
 use crate::views::OrderingPolicy;
 use crate::workspace;
 use crate::workspace::WorkspaceScanState;
+use std::collections::HashMap;
 use std::path::Path;
 
-fn parse_node_id(s: &str) -> Result<NodeID, ApiError> {
-    let s = s.strip_prefix("0x").unwrap_or(s);
-    let bytes =
-        hex::decode(s).map_err(|e| ApiError::InvalidFrame(format!("Invalid hex string: {}", e)))?;
-    if bytes.len() != 32 {
-        return Err(ApiError::InvalidFrame(format!(
-            "NodeID must be 32 bytes, got {} bytes",
-            bytes.len()
-        )));
-    }
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&bytes);
-    Ok(crate::types::Hash::from(hash))
-}
-
 #[derive(Debug, Clone)]
 pub struct CliNodeContext {
     pub context: NodeContext,
@@ -30,6 +18,9 @@ pub struct CliNodeContext {
 }
 
 /// Single get entry point: resolve node_id, build ContextView, call api.get_node.
+///
+/// With `flatten_directory`, a directory target returns the head frames of its descendant
+/// files (in path order) instead of its own frames.
 #[allow(clippy::too_many_arguments)]
 pub fn get_node_for_cli(
     api: &ContextApi,
@@ -40,25 +31,257 @@ pub fn get_node_for_cli(
     frame_type: Option<&str>,
     max_frames: usize,
     ordering: &str,
+    meta: &[String],
     _include_deleted: bool,
+    flatten_directory: bool,
 ) -> Result<CliNodeContext, ApiError> {
-    let node_id = match (node, path) {
-        (Some(node_str), None) => parse_node_id(node_str)?,
-        (None, Some(p)) => {
-            workspace::resolve_workspace_node_id(api, workspace_root, Some(p), None, false)?
+    let node_id = resolve_target(api, workspace_root, node, path)?;
+    let view = build_view(agent, frame_type, max_frames, ordering, meta)?;
+    let context = if flatten_directory {
+        flatten_directory_context(api, node_id, view)?
+    } else {
+        api.get_node(node_id, view)?
+    };
+    let mut warnings = scan_warnings(api, workspace_root);
+    if !context.node_record.path.exists() {
+        warnings.push("Stored node path no longer exists on disk.".to_string());
+    }
+
+    Ok(CliNodeContext { context, warnings })
+}
+
+/// Put the head frame of each ancestor directory before the target's frames, root first.
+///
+/// Walks `NodeRecord.parent` up to the root. Each ancestor contributes its head of
+/// `frame_type`, or with no type given, its head of every frame type the target's frames carry
+/// (in their order). Ancestors without such a head are skipped. Returns the frames added.
+pub fn prepend_ancestor_frames(
+    api: &ContextApi,
+    context: &mut NodeContext,
+    frame_type: Option<&str>,
+) -> Result<usize, ApiError> {
+    let frame_types: Vec<String> = match frame_type {
+        Some(frame_type) => vec![frame_type.to_string()],
+        None => {
+            let mut types: Vec<String> = Vec::new();
+            for frame in &context.frames {
+                if !types.contains(&frame.frame_type) {
+                    types.push(frame.frame_type.clone());
+                }
+            }
+            types
+        }
+    };
+
+    let mut levels = Vec::new();
+    let mut parent = context.node_record.parent;
+    while let Some(ancestor_id) = parent {
+        let Some(record) = api.node_store().get(&ancestor_id).map_err(ApiError::from)? else {
+            break;
+        };
+        let mut frames = Vec::new();
+        for frame_type in &frame_types {
+            let Some(frame_id) = api.get_head(&ancestor_id, frame_type)? else {
+                continue;
+            };
+            if let Some(frame) = api.frame_storage().get(&frame_id).map_err(ApiError::from)? {
+                frames.push(frame);
+            }
+        }
+        levels.push(frames);
+        parent = record.parent;
+    }
+
+    let ancestor_frames: Vec<_> = levels.into_iter().rev().flatten().collect();
+    let added = ancestor_frames.len();
+    context.frames.splice(0..0, ancestor_frames);
+    context.frame_count += added;
+    Ok(added)
+}
+
+/// Collapse the selected frames to the newest one per agent, then cap at `max_frames`.
+///
+/// Frames are grouped by `agent_id()` (frames without one form their own group); the newest
+/// by timestamp wins, ties broken by `frame_id`. Survivors keep the view's ordering. Deleted
+/// frames are passed over unless `include_deleted`, so an agent's newest live frame is kept.
+/// Callers select with an unbounded `max_frames` so no agent is cut before grouping.
+pub fn keep_newest_frame_per_agent(
+    context: &mut NodeContext,
+    include_deleted: bool,
+    max_frames: usize,
+) {
+    let mut newest: HashMap<Option<String>, usize> = HashMap::new();
+    for (index, frame) in context.frames.iter().enumerate() {
+        if !include_deleted && frame.is_deleted() {
+            continue;
         }
-        (Some(_), Some(_)) => {
-            return Err(ApiError::ConfigError(
-                "Cannot specify both --node and --path. Use one or the other.".to_string(),
-            ));
+        let key = frame.agent_id().map(str::to_string);
+        match newest.get(&key) {
+            Some(&current) => {
+                let current_frame = &context.frames[current];
+                if (frame.timestamp, frame.frame_id)
+                    > (current_frame.timestamp, current_frame.frame_id)
+                {
+                    newest.insert(key, index);
+                }
+            }
+            None => {
+                newest.insert(key, index);
+            }
         }
-        (None, None) => {
-            return Err(ApiError::ConfigError(
-                "Must specify either --node <node_id> or --path <path>.".to_string(),
-            ));
+    }
+    let keep: std::collections::HashSet<usize> = newest.into_values().collect();
+    let mut index = 0;
+    context.frames.retain(|_| {
+        let kept = keep.contains(&index);
+        index += 1;
+        kept
+    });
+    context.frames.truncate(max_frames);
+}
+
+/// Count the frames `context get` would select for the target, ignoring `max_frames`.
+///
+/// Applies the same agent, frame type, metadata, and deleted filters as [`get_node_for_cli`]
+/// but never builds a `NodeContext`, so no frame text is decoded or formatted. With
+/// `flatten_directory`, a directory target counts the frames of its active descendant files.
+#[allow(clippy::too_many_arguments)]
+pub fn count_frames_for_cli(
+    api: &ContextApi,
+    workspace_root: &Path,
+    node: Option<&str>,
+    path: Option<&Path>,
+    agent: Option<&str>,
+    frame_type: Option<&str>,
+    meta: &[String],
+    include_deleted: bool,
+    flatten_directory: bool,
+) -> Result<(NodeID, usize), ApiError> {
+    let node_id = resolve_target(api, workspace_root, node, path)?;
+    let view = build_view(agent, frame_type, usize::MAX, "recency", meta)?;
+    let node_record = api
+        .node_store()
+        .get(&node_id)
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NodeNotFound(node_id))?;
+    if !flatten_directory || !matches!(node_record.node_type, NodeType::Directory) {
+        let count = api.count_node_frames(node_id, view, include_deleted)?;
+        return Ok((node_id, count));
+    }
+
+    let mut count = 0;
+    for level in traverse(api, node_id, TraversalStrategy::TopDown)?.into_batches() {
+        for descendant in level {
+            let Some(record) = api.node_store().get(&descendant).map_err(ApiError::from)? else {
+                continue;
+            };
+            if matches!(record.node_type, NodeType::File { .. }) && record.tombstoned_at.is_none() {
+                count += api.count_node_frames(record.node_id, view.clone(), include_deleted)?;
+            }
         }
-    };
+    }
+    Ok((node_id, count))
+}
+
+/// Hand the context of the target and each active descendant (top-down) to `emit`, one node
+/// at a time, so callers can write each result before the next is loaded.
+///
+/// With `files_only`, directory nodes are skipped. Returns the target node and the number of
+/// contexts emitted.
+#[allow(clippy::too_many_arguments)]
+pub fn for_each_node_context_for_cli<F>(
+    api: &ContextApi,
+    workspace_root: &Path,
+    node: Option<&str>,
+    path: Option<&Path>,
+    agent: Option<&str>,
+    frame_type: Option<&str>,
+    max_frames: usize,
+    ordering: &str,
+    meta: &[String],
+    files_only: bool,
+    mut emit: F,
+) -> Result<(NodeID, usize), ApiError>
+where
+    F: FnMut(CliNodeContext) -> Result<(), ApiError>,
+{
+    let node_id = resolve_target(api, workspace_root, node, path)?;
+    let view = build_view(agent, frame_type, max_frames, ordering, meta)?;
+    let scan_warnings = scan_warnings(api, workspace_root);
+
+    let mut emitted = 0;
+    for level in traverse(api, node_id, TraversalStrategy::TopDown)?.into_batches() {
+        let mut records = Vec::new();
+        for descendant in level {
+            let Some(record) = api.node_store().get(&descendant).map_err(ApiError::from)? else {
+                continue;
+            };
+            let is_directory = matches!(record.node_type, NodeType::Directory);
+            if record.tombstoned_at.is_none() && !(files_only && is_directory) {
+                records.push(record);
+            }
+        }
+        records.sort_by(|a, b| a.path.cmp(&b.path));
+        for record in records {
+            let context = api.get_node(record.node_id, view.clone())?;
+            let mut warnings = scan_warnings.clone();
+            if !context.node_record.path.exists() {
+                warnings.push("Stored node path no longer exists on disk.".to_string());
+            }
+            emit(CliNodeContext { context, warnings })?;
+            emitted += 1;
+        }
+    }
+    Ok((node_id, emitted))
+}
+
+pub(crate) fn resolve_target(
+    api: &ContextApi,
+    workspace_root: &Path,
+    node: Option<&str>,
+    path: Option<&Path>,
+) -> Result<NodeID, ApiError> {
+    match (node, path) {
+        (Some(node_str), None) => parse_node_id(node_str),
+        (None, Some(p)) => workspace::resolve_workspace_node_id_with_policy(
+            api,
+            workspace_root,
+            Some(p),
+            None,
+            false,
+            api.strict_paths(),
+        ),
+        (Some(_), Some(_)) => Err(ApiError::ConfigError(
+            "Cannot specify both --node and --path. Use one or the other.".to_string(),
+        )),
+        (None, None) => Err(ApiError::ConfigError(
+            "Must specify either --node <node_id> or --path <path>.".to_string(),
+        )),
+    }
+}
+
+/// Parse repeated `--meta key=value` arguments into the metadata a frame must carry.
+fn parse_meta_filters(meta: &[String]) -> Result<HashMap<String, String>, ApiError> {
+    let mut filters = HashMap::new();
+    for entry in meta {
+        let Some((key, value)) = entry.split_once('=').filter(|(key, _)| !key.is_empty()) else {
+            return Err(ApiError::ConfigError(format!(
+                "Invalid --meta filter: '{}'. Expected key=value.",
+                entry
+            )));
+        };
+        filters.insert(key.to_string(), value.to_string());
+    }
+    Ok(filters)
+}
 
+fn build_view(
+    agent: Option<&str>,
+    frame_type: Option<&str>,
+    max_frames: usize,
+    ordering: &str,
+    meta: &[String],
+) -> Result<ContextView, ApiError> {
     let ordering_policy = match ordering {
         "recency" => OrderingPolicy::Recency,
         "deterministic" => OrderingPolicy::Type,
@@ -82,8 +305,11 @@ pub fn get_node_for_cli(
     if let Some(ft) = frame_type {
         builder = builder.by_type(ft);
     }
-    let view = builder.build();
-    let context = api.get_node(node_id, view)?;
+    builder = builder.by_metadata(parse_meta_filters(meta)?);
+    Ok(builder.build())
+}
+
+fn scan_warnings(api: &ContextApi, workspace_root: &Path) -> Vec<String> {
     let mut warnings = Vec::new();
     if let Ok(scan_info) = workspace::read_workspace_scan_state(api, workspace_root) {
         if matches!(scan_info.scan_state, WorkspaceScanState::Stale) {
@@ -92,9 +318,49 @@ pub fn get_node_for_cli(
             );
         }
     }
-    if !context.node_record.path.exists() {
-        warnings.push("Stored node path no longer exists on disk.".to_string());
+    warnings
+}
+
+/// Collect the frames selected by `view` for every active descendant file of `node_id`.
+/// File targets are returned unchanged.
+fn flatten_directory_context(
+    api: &ContextApi,
+    node_id: NodeID,
+    view: ContextView,
+) -> Result<NodeContext, ApiError> {
+    let node_record = api
+        .node_store()
+        .get(&node_id)
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NodeNotFound(node_id))?;
+    if !matches!(node_record.node_type, NodeType::Directory) {
+        return api.get_node(node_id, view);
     }
 
-    Ok(CliNodeContext { context, warnings })
+    let mut files = Vec::new();
+    for level in traverse(api, node_id, TraversalStrategy::TopDown)?.into_batches() {
+        for descendant in level {
+            let Some(record) = api.node_store().get(&descendant).map_err(ApiError::from)? else {
+                continue;
+            };
+            if matches!(record.node_type, NodeType::File { .. }) && record.tombstoned_at.is_none() {
+                files.push(record);
+            }
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut frames = Vec::new();
+    let mut frame_count = 0;
+    for file in files {
+        let file_context = api.get_node(file.node_id, view.clone())?;
+        frame_count += file_context.frames.len();
+        frames.extend(file_context.frames);
+    }
+    Ok(NodeContext {
+        node_id,
+        node_record,
+        frames,
+        frame_count,
+    })
 }
@@ -0,0 +1,192 @@
+//! Context search: full-text match over stored frame contents.
+//!
+//! Walks every frame blob in frame storage, keeps frames whose basis node lies under the
+//! search scope, and hands each match to the caller as soon as it is found.
+
+use crate::api::ContextApi;
+use crate::context::frame::Basis;
+use crate::context::query::get::resolve_target;
+use crate::error::ApiError;
+use crate::types::{FrameID, NodeID};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Longest snippet returned per match, in characters, not counting the `...` markers.
+pub const SEARCH_SNIPPET_MAX_CHARS: usize = 160;
+
+/// One frame whose content matched the query.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub node_id: NodeID,
+    pub path: PathBuf,
+    pub frame_id: FrameID,
+    pub frame_type: String,
+    /// Text around the first match, whitespace collapsed, capped at [`SEARCH_SNIPPET_MAX_CHARS`].
+    pub snippet: String,
+}
+
+/// Build the matcher: a case-insensitive literal by default, or `query` as a regex.
+pub fn build_search_pattern(query: &str, regex: bool) -> Result<Regex, ApiError> {
+    if query.is_empty() {
+        return Err(ApiError::ConfigError(
+            "Search query must not be empty.".to_string(),
+        ));
+    }
+    if regex {
+        Regex::new(query).map_err(|e| {
+            ApiError::ConfigError(format!("Invalid --regex pattern '{}': {}", query, e))
+        })
+    } else {
+        RegexBuilder::new(&regex::escape(query))
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| ApiError::ConfigError(format!("Invalid search query '{}': {}", query, e)))
+    }
+}
+
+/// Search the content of every non-deleted frame attached to an active node under `path`
+/// (the whole workspace when `None`), optionally limited to one frame type.
+///
+/// Each match is passed to `emit` before the next frame is loaded. Returns the number of hits.
+pub fn search_frames_for_cli<F>(
+    api: &ContextApi,
+    workspace_root: &Path,
+    path: Option<&Path>,
+    frame_type: Option<&str>,
+    query: &str,
+    regex: bool,
+    mut emit: F,
+) -> Result<usize, ApiError>
+where
+    F: FnMut(SearchHit) -> Result<(), ApiError>,
+{
+    let pattern = build_search_pattern(query, regex)?;
+    let scope = match path {
+        Some(path) => {
+            let scope_id = resolve_target(api, workspace_root, None, Some(path))?;
+            let record = api
+                .node_store()
+                .get(&scope_id)
+                .map_err(ApiError::from)?
+                .ok_or(ApiError::NodeNotFound(scope_id))?;
+            Some(record.path)
+        }
+        None => None,
+    };
+
+    // Node path per basis node, or None when the node is missing, tombstoned, or out of scope.
+    let mut node_paths: HashMap<NodeID, Option<PathBuf>> = HashMap::new();
+    let mut hits = 0;
+    for frame_id in api.frame_storage().iter_ids() {
+        let frame_id = frame_id.map_err(ApiError::from)?;
+        let Some(frame) = api.frame_storage().get(&frame_id).map_err(ApiError::from)? else {
+            continue;
+        };
+        if frame.is_deleted() || frame_type.is_some_and(|t| !frame.is_type(t)) {
+            continue;
+        }
+        let node_id = match &frame.basis {
+            Basis::Node(node) | Basis::Both { node, .. } => *node,
+            Basis::Frame(_) => continue,
+        };
+        let node_path = match node_paths.get(&node_id) {
+            Some(cached) => cached.clone(),
+            None => {
+                let in_scope = api
+                    .node_store()
+                    .get(&node_id)
+                    .map_err(ApiError::from)?
+                    .filter(|record| record.tombstoned_at.is_none())
+                    .map(|record| record.path)
+                    .filter(|node_path| scope.as_ref().is_none_or(|s| node_path.starts_with(s)));
+                node_paths.insert(node_id, in_scope.clone());
+                in_scope
+            }
+        };
+        let Some(node_path) = node_path else {
+            continue;
+        };
+
+        let text = String::from_utf8_lossy(&frame.content);
+        let Some(found) = pattern.find(&text) else {
+            continue;
+        };
+        emit(SearchHit {
+            node_id,
+            path: node_path,
+            frame_id,
+            frame_type: frame.frame_type,
+            snippet: build_snippet(&text, found.start(), found.end(), SEARCH_SNIPPET_MAX_CHARS),
+        })?;
+        hits += 1;
+    }
+    Ok(hits)
+}
+
+/// Text around `text[start..end]`, centered on the match and at most `max_chars` characters
+/// after whitespace is collapsed. `...` marks text cut from either end.
+fn build_snippet(text: &str, start: usize, end: usize, max_chars: usize) -> String {
+    let match_chars = text[start..end].chars().count();
+    let context = max_chars.saturating_sub(match_chars) / 2;
+    let window_start = text[..start]
+        .char_indices()
+        .rev()
+        .take(context)
+        .last()
+        .map_or(start, |(i, _)| i);
+    let window_end = text[end..]
+        .char_indices()
+        .nth(context)
+        .map_or(text.len(), |(i, _)| end + i);
+
+    let collapsed = text[window_start..window_end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut snippet: String = collapsed.chars().take(max_chars).collect();
+    let cut_end = window_end < text.len() || snippet.len() < collapsed.len();
+    if window_start > 0 {
+        snippet.insert_str(0, "...");
+    }
+    if cut_end {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_queries_ignore_case_and_regex_metacharacters() {
+        let pattern = build_search_pattern("a.b", false).unwrap();
+        assert!(pattern.is_match("see A.B here"));
+        assert!(!pattern.is_match("aXb"));
+
+        let pattern = build_search_pattern("a.b", true).unwrap();
+        assert!(pattern.is_match("aXb"));
+        assert!(!pattern.is_match("AXB"));
+
+        assert!(build_search_pattern("(", true).is_err());
+        assert!(build_search_pattern("", false).is_err());
+    }
+
+    #[test]
+    fn snippet_is_centered_and_capped() {
+        let text = format!("{}needle\n\n{}", "x ".repeat(100), "y ".repeat(100));
+        let start = text.find("needle").unwrap();
+        let snippet = build_snippet(&text, start, start + "needle".len(), 20);
+        assert!(
+            snippet.starts_with("...") && snippet.ends_with("..."),
+            "{}",
+            snippet
+        );
+        let body = snippet.trim_matches('.');
+        assert!(body.chars().count() <= 20, "{}", snippet);
+        assert!(body.contains("needle y"), "{}", snippet);
+
+        assert_eq!(build_snippet("short needle", 6, 12, 20), "short needle");
+    }
+}
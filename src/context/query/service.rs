@@ -17,16 +17,7 @@ pub fn get_node(
     node_id: NodeID,
     view_policy: &ViewPolicy,
 ) -> Result<(NodeRecord, Vec<Frame>, usize), ApiError> {
-    let node_record = node_store
-        .get(&node_id)
-        .map_err(ApiError::from)?
-        .ok_or_else(|| {
-            warn!("Node not found");
-            ApiError::NodeNotFound(node_id)
-        })?;
-    if node_record.tombstoned_at.is_some() {
-        return Err(ApiError::NodeNotFound(node_id));
-    }
+    let node_record = readable_node_record(node_store, node_id)?;
 
     if frame_ids.is_empty() {
         return Ok((node_record, vec![], 0));
@@ -48,3 +39,148 @@ pub fn get_node(
     let total_frame_count = frame_set.len();
     Ok((node_record, frames, total_frame_count))
 }
+
+/// Count the frames `view_policy` filters keep, without ordering, truncation, or a second
+/// content load. `load` fetches one frame; each id is loaded at most once.
+///
+/// Deleted frames are counted only with `include_deleted`.
+pub fn count_frames<L>(
+    node_store: &dyn NodeRecordStore,
+    frame_ids: &[FrameID],
+    node_id: NodeID,
+    view_policy: &ViewPolicy,
+    include_deleted: bool,
+    mut load: L,
+) -> Result<usize, ApiError>
+where
+    L: FnMut(&FrameID) -> Result<Option<Frame>, ApiError>,
+{
+    readable_node_record(node_store, node_id)?;
+
+    let mut count = 0;
+    for frame_id in frame_ids {
+        let Some(frame) = load(frame_id)? else {
+            continue;
+        };
+        if !include_deleted && frame.is_deleted() {
+            continue;
+        }
+        if view_policy
+            .filters
+            .iter()
+            .all(|filter| filter.matches(&frame))
+        {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn readable_node_record(
+    node_store: &dyn NodeRecordStore,
+    node_id: NodeID,
+) -> Result<NodeRecord, ApiError> {
+    let node_record = node_store
+        .get(&node_id)
+        .map_err(ApiError::from)?
+        .ok_or_else(|| {
+            warn!("Node not found");
+            ApiError::NodeNotFound(node_id)
+        })?;
+    // Nodes deleted with --keep-frames stay readable by NodeID.
+    if node_record.tombstoned_at.is_some() && !node_record.keeps_frames() {
+        return Err(ApiError::NodeNotFound(node_id));
+    }
+    Ok(node_record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::frame::Basis;
+    use crate::context::query::view_policy::{FrameFilter, OrderingPolicy};
+    use crate::store::{NodeType, SledNodeRecordStore};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn frame(node_id: NodeID, frame_type: &str, deleted: bool) -> Frame {
+        let mut metadata = HashMap::new();
+        if deleted {
+            metadata.insert("deleted".to_string(), "true".to_string());
+        }
+        Frame::new(
+            Basis::Node(node_id),
+            format!("{} body", frame_type).into_bytes(),
+            frame_type.to_string(),
+            "writer".to_string(),
+            metadata,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn count_frames_loads_each_head_once_and_applies_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let node_store = SledNodeRecordStore::new(temp_dir.path().join("store")).unwrap();
+        let node_id = [7u8; 32];
+        node_store
+            .put(&NodeRecord {
+                node_id,
+                path: PathBuf::from("a.rs"),
+                node_type: NodeType::Directory,
+                children: vec![],
+                parent: None,
+                frame_set_root: None,
+                metadata: Default::default(),
+                tombstoned_at: None,
+            })
+            .unwrap();
+
+        let frames: HashMap<FrameID, Frame> = [
+            frame(node_id, "summary", false),
+            frame(node_id, "review", false),
+            frame(node_id, "outline", true),
+        ]
+        .into_iter()
+        .map(|frame| (frame.frame_id, frame))
+        .collect();
+        let frame_ids: Vec<FrameID> = frames.keys().copied().collect();
+        let loads = RefCell::new(Vec::new());
+        let count = |filters: Vec<FrameFilter>, include_deleted: bool| {
+            loads.borrow_mut().clear();
+            let policy = ViewPolicy {
+                max_frames: 1,
+                ordering: OrderingPolicy::Recency,
+                filters,
+            };
+            count_frames(
+                &node_store,
+                &frame_ids,
+                node_id,
+                &policy,
+                include_deleted,
+                |frame_id| {
+                    loads.borrow_mut().push(*frame_id);
+                    Ok(frames.get(frame_id).cloned())
+                },
+            )
+            .unwrap()
+        };
+
+        // max_frames does not cap the count; the deleted frame is excluded by default.
+        assert_eq!(count(vec![], false), 2);
+        assert_eq!(loads.borrow().len(), frame_ids.len());
+        assert_eq!(count(vec![], true), 3);
+        assert_eq!(
+            count(vec![FrameFilter::ByType("outline".to_string())], false),
+            0
+        );
+        assert_eq!(
+            count(vec![FrameFilter::ByType("outline".to_string())], true),
+            1
+        );
+        assert_eq!(loads.borrow().len(), frame_ids.len());
+    }
+}
@@ -4,8 +4,9 @@
 use super::view_policy::{FrameFilter, OrderingPolicy, ViewPolicy};
 use crate::context::frame::Frame;
 use crate::store::NodeRecord;
-use crate::types::NodeID;
+use crate::types::{Hash, NodeID};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Context view policy for frame selection
 ///
@@ -86,6 +87,14 @@ impl ContextViewBuilder {
         self
     }
 
+    /// Fully deterministic ordering: most recent first, ties broken by `frame_id` bytes
+    ///
+    /// Identical stored frames yield byte-for-byte identical `context get` output.
+    pub fn stable(mut self) -> Self {
+        self.ordering = Some(OrderingPolicy::Recency);
+        self
+    }
+
     /// Order by frame type (lexicographic)
     pub fn by_type_ordering(mut self) -> Self {
         self.ordering = Some(OrderingPolicy::Type);
@@ -110,6 +119,15 @@ impl ContextViewBuilder {
         self
     }
 
+    /// Filter by metadata: keep frames whose metadata has every given key with exactly
+    /// the given value
+    pub fn by_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        if !metadata.is_empty() {
+            self.filters.push(FrameFilter::Metadata(metadata));
+        }
+        self
+    }
+
     /// Build the ContextView
     ///
     /// Uses default values for any fields not explicitly set:
@@ -140,6 +158,21 @@ pub struct NodeContext {
 }
 
 impl NodeContext {
+    /// Stable hash of the context a consumer saw: the node ID and the ordered frame IDs.
+    ///
+    /// Metadata, timestamps, and `frame_count` do not participate, so identical frame
+    /// selections hash identically across processes. Frame order is significant.
+    pub fn content_hash(&self) -> Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"node_context");
+        hasher.update(&self.node_id);
+        hasher.update(&(self.frames.len() as u64).to_be_bytes());
+        for frame in &self.frames {
+            hasher.update(&frame.frame_id);
+        }
+        *hasher.finalize().as_bytes()
+    }
+
     /// Get all frame contents as UTF-8 strings
     ///
     /// Filters out frames with invalid UTF-8 content.
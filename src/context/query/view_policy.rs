@@ -5,8 +5,13 @@ use crate::context::frame::{Frame, FrameMerkleSet, FrameStorage};
 use crate::error::StorageError;
 use crate::types::FrameID;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// Ordering policy for frame selection
+///
+/// Every policy is a total order: frames that tie on the policy key are ordered by
+/// `frame_id` bytes, so the same stored frames always produce the same sequence.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum OrderingPolicy {
     /// Order by recency (most recent first, by timestamp)
@@ -17,6 +22,18 @@ pub enum OrderingPolicy {
     Agent,
 }
 
+impl OrderingPolicy {
+    /// Compare two frames under this policy, breaking ties by `frame_id` byte order.
+    pub fn compare_frames(&self, a: &Frame, b: &Frame) -> Ordering {
+        let primary = match self {
+            OrderingPolicy::Recency => b.timestamp.cmp(&a.timestamp),
+            OrderingPolicy::Type => a.frame_type.cmp(&b.frame_type),
+            OrderingPolicy::Agent => a.agent_id().unwrap_or("").cmp(b.agent_id().unwrap_or("")),
+        };
+        primary.then_with(|| a.frame_id.cmp(&b.frame_id))
+    }
+}
+
 /// Frame filter
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum FrameFilter {
@@ -24,6 +41,21 @@ pub enum FrameFilter {
     ByType(String),
     /// Filter frames by agent ID
     ByAgent(String),
+    /// Keep frames whose metadata has every given key with exactly the given value
+    Metadata(HashMap<String, String>),
+}
+
+impl FrameFilter {
+    /// Whether `frame` passes this filter.
+    pub fn matches(&self, frame: &Frame) -> bool {
+        match self {
+            FrameFilter::ByType(filter_type) => frame.frame_type == *filter_type,
+            FrameFilter::ByAgent(filter_agent) => frame.agent_id() == Some(filter_agent.as_str()),
+            FrameFilter::Metadata(expected) => expected
+                .iter()
+                .all(|(key, value)| frame.metadata_value(key) == Some(value.as_str())),
+        }
+    }
 }
 
 /// Context view policy
@@ -54,32 +86,11 @@ pub fn get_context_view(
 
     let filtered_frames: Vec<(FrameID, Frame)> = frames_with_metadata
         .into_iter()
-        .filter(|(_, frame)| {
-            policy.filters.iter().all(|filter| match filter {
-                FrameFilter::ByType(filter_type) => frame.frame_type == *filter_type,
-                FrameFilter::ByAgent(filter_agent) => {
-                    frame.agent_id() == Some(filter_agent.as_str())
-                }
-            })
-        })
+        .filter(|(_, frame)| policy.filters.iter().all(|filter| filter.matches(frame)))
         .collect();
 
     let mut sorted_frames = filtered_frames;
-    match policy.ordering {
-        OrderingPolicy::Recency => {
-            sorted_frames.sort_by(|(_, a), (_, b)| b.timestamp.cmp(&a.timestamp));
-        }
-        OrderingPolicy::Type => {
-            sorted_frames.sort_by(|(_, a), (_, b)| a.frame_type.cmp(&b.frame_type));
-        }
-        OrderingPolicy::Agent => {
-            sorted_frames.sort_by(|(_, a), (_, b)| {
-                let agent_a = a.agent_id().unwrap_or("");
-                let agent_b = b.agent_id().unwrap_or("");
-                agent_a.cmp(agent_b)
-            });
-        }
-    }
+    sorted_frames.sort_by(|(_, a), (_, b)| policy.ordering.compare_frames(a, b));
 
     sorted_frames.truncate(policy.max_frames);
 
@@ -94,7 +105,6 @@ mod tests {
     use super::*;
     use crate::context::frame::{Basis, Frame};
     use crate::types::NodeID;
-    use std::collections::HashMap;
     use tempfile::TempDir;
 
     fn create_test_frame(frame_id_base: u8, frame_type: &str, agent_id: Option<&str>) -> Frame {
@@ -207,6 +217,53 @@ mod tests {
         assert_eq!(view1, view2);
     }
 
+    #[test]
+    fn test_colliding_timestamps_order_by_frame_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FrameStorage::new(temp_dir.path()).unwrap();
+        let shared_timestamp =
+            std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let mut frames = Vec::new();
+        for i in 0..6 {
+            let mut frame = create_test_frame(i, "test", Some("agent1"));
+            frame.timestamp = shared_timestamp;
+            storage.store(&frame).unwrap();
+            frames.push(frame);
+        }
+        let mut expected: Vec<FrameID> = frames.iter().map(|frame| frame.frame_id).collect();
+        expected.sort();
+
+        for ordering in [
+            OrderingPolicy::Recency,
+            OrderingPolicy::Type,
+            OrderingPolicy::Agent,
+        ] {
+            let mut forward = FrameMerkleSet::new();
+            for frame in &frames {
+                forward.add_frame(frame.frame_id).unwrap();
+            }
+            let mut reverse = FrameMerkleSet::new();
+            for frame in frames.iter().rev() {
+                reverse.add_frame(frame.frame_id).unwrap();
+            }
+            let policy = ViewPolicy {
+                max_frames: 100,
+                ordering,
+                filters: vec![],
+            };
+            for _ in 0..3 {
+                assert_eq!(
+                    get_context_view(&forward, &storage, &policy).unwrap(),
+                    expected
+                );
+                assert_eq!(
+                    get_context_view(&reverse, &storage, &policy).unwrap(),
+                    expected
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_empty_view() {
         let temp_dir = TempDir::new().unwrap();
@@ -246,4 +303,60 @@ mod tests {
         assert!(view.contains(&frame3.frame_id));
         assert!(!view.contains(&frame2.frame_id));
     }
+
+    #[test]
+    fn test_filter_by_metadata_requires_every_pair() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FrameStorage::new(temp_dir.path()).unwrap();
+        let with_metadata = |frame_id_base: u8, pairs: &[(&str, &str)]| {
+            let metadata = pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>();
+            Frame::new(
+                Basis::Node([1u8; 32]),
+                format!("content_{}", frame_id_base).into_bytes(),
+                "test".to_string(),
+                "agent1".to_string(),
+                metadata,
+            )
+            .unwrap()
+        };
+        let frame1 = with_metadata(1, &[("file_class", "rust"), ("model", "m1")]);
+        let frame2 = with_metadata(2, &[("file_class", "markdown"), ("model", "m1")]);
+        let frame3 = with_metadata(3, &[("file_class", "rust"), ("model", "m2")]);
+        let mut frame_set = FrameMerkleSet::new();
+        for frame in [&frame1, &frame2, &frame3] {
+            storage.store(frame).unwrap();
+            frame_set.add_frame(frame.frame_id).unwrap();
+        }
+        let policy_for = |pairs: &[(&str, &str)]| ViewPolicy {
+            max_frames: 100,
+            ordering: OrderingPolicy::Recency,
+            filters: vec![FrameFilter::Metadata(
+                pairs
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            )],
+        };
+
+        let view =
+            get_context_view(&frame_set, &storage, &policy_for(&[("file_class", "rust")])).unwrap();
+        assert_eq!(view.len(), 2);
+        assert!(view.contains(&frame1.frame_id));
+        assert!(view.contains(&frame3.frame_id));
+
+        let view = get_context_view(
+            &frame_set,
+            &storage,
+            &policy_for(&[("file_class", "rust"), ("model", "m1")]),
+        )
+        .unwrap();
+        assert_eq!(view, vec![frame1.frame_id]);
+
+        let view =
+            get_context_view(&frame_set, &storage, &policy_for(&[("file_class", "Rust")])).unwrap();
+        assert!(view.is_empty());
+    }
 }
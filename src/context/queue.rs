@@ -4,7 +4,7 @@
 //! Handles large-scale operations efficiently through batching, rate limiting, and concurrent processing.
 
 use crate::api::ContextApi;
-use crate::context::generation::contracts::GeneratedMetadataBuilder;
+use crate::context::generation::contracts::{GeneratedMetadataBuilder, HeadConflictPolicy};
 use crate::context::generation::{TargetExecutionProgram, TargetExecutionProgramKind};
 use crate::control::compatibility::execute_target_request;
 use crate::error::ApiError;
@@ -20,13 +20,21 @@ use crate::types::{FrameID, NodeID};
 use hex;
 use parking_lot::RwLock;
 use serde_json::json;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{oneshot, Mutex, Notify, Semaphore};
+use tokio::sync::{oneshot, Mutex, Notify, OwnedSemaphorePermit, Semaphore};
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+mod adaptive;
+mod breaker;
+mod stats;
+
+use adaptive::AdaptiveConcurrency;
+use breaker::CircuitBreaker;
+use stats::QueueCounters;
+
 /// Priority level for generation requests
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Priority {
@@ -108,6 +116,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn circuit_open_errors_are_not_retryable() {
+        let open = ApiError::CircuitOpen {
+            provider: "local".to_string(),
+            failures: 5,
+        };
+        let failed = ApiError::ProviderError("connection reset".to_string());
+        let program = TargetExecutionProgram::single_shot();
+
+        assert!(!FrameGenerationQueue::is_retryable_error(&program, &open));
+        assert!(FrameGenerationQueue::is_retryable_error(&program, &failed));
+    }
+
     #[test]
     fn request_identity_includes_provider_name_and_runtime_overrides() {
         let default_provider = crate::provider::ProviderExecutionBinding::new(
@@ -184,6 +205,20 @@ pub struct QueueEventContext {
 pub struct GenerationRequestOptions {
     pub force: bool,
     pub plan_id: Option<String>,
+    /// Verbatim user prompt replacing the agent template for this request.
+    pub user_prompt_override: Option<String>,
+    /// Stream the provider response to stderr while it is generated.
+    pub stream_output: bool,
+    /// Record the source file's modification time in the frame's `source_mtime` metadata.
+    pub preserve_mtime: bool,
+    /// Record generation duration, retry count, and provider name in frame metadata.
+    pub record_provenance: bool,
+    /// Custom prompt placeholders from `--prompt-var`.
+    pub prompt_vars: BTreeMap<String, String>,
+    /// How to write the frame when the head moved away from `expected_head`.
+    pub on_conflict: HeadConflictPolicy,
+    /// Head the plan saw for this node and frame type.
+    pub expected_head: Option<FrameID>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -375,10 +410,21 @@ impl PartialOrd for GenerationRequest {
 pub struct GenerationConfig {
     /// Maximum concurrent generations per agent
     pub max_concurrent_per_agent: usize,
+    /// Consecutive successes needed to raise a provider's reduced concurrency limit by one.
+    /// Rate-limit errors halve the limit for every agent using that provider.
+    pub rate_limit_recovery_successes: usize,
+    /// Consecutive provider failures that open the provider's circuit; while open, its
+    /// requests fail fast without a provider call
+    pub provider_failure_threshold: usize,
+    /// How long an open circuit fails fast before letting one probe request through
+    pub provider_circuit_cooldown_ms: u64,
     /// Batch size for processing requests
     pub batch_size: usize,
     /// Maximum retry attempts per request
     pub max_retry_attempts: usize,
+    /// Maximum retries across every request handled by this queue; `None` leaves only the
+    /// per-request limit. Once spent, failing requests fail without further retry.
+    pub max_total_retries: Option<usize>,
     /// Delay between retries (milliseconds)
     pub retry_delay_ms: u64,
     /// Rate limit: minimum delay between requests per agent (milliseconds)
@@ -393,8 +439,12 @@ impl Default for GenerationConfig {
     fn default() -> Self {
         Self {
             max_concurrent_per_agent: 3,
+            rate_limit_recovery_successes: 5,
+            provider_failure_threshold: 5,
+            provider_circuit_cooldown_ms: 30_000,
             batch_size: 50,
             max_retry_attempts: 3,
+            max_total_retries: None,
             retry_delay_ms: 1000,
             rate_limit_ms: Some(100), // 100ms between requests per agent
             max_queue_size: 10000,
@@ -403,7 +453,7 @@ impl Default for GenerationConfig {
     }
 }
 
-/// Queue statistics
+/// Queue statistics snapshot, read from the queue's atomic counters
 #[derive(Debug, Clone, Default)]
 pub struct QueueStats {
     /// Number of pending requests
@@ -414,6 +464,27 @@ pub struct QueueStats {
     pub completed: usize,
     /// Number of failed requests
     pub failed: usize,
+    /// Number of retries consumed across all requests
+    pub retries: usize,
+}
+
+/// One batch request: node, agent, provider, optional frame type, and priority.
+pub type BatchRequest = (NodeID, String, String, Option<String>, Priority);
+
+/// Outcome of [`FrameGenerationQueue::enqueue_batch_partial`].
+#[derive(Debug)]
+pub struct PartialBatchEnqueue {
+    /// Request IDs of the accepted requests, deduplicated ones included, in batch order
+    pub request_ids: Vec<RequestId>,
+    /// Requests that did not fit in the queue, in batch order, ready to resubmit
+    pub rejected: Vec<BatchRequest>,
+}
+
+impl PartialBatchEnqueue {
+    /// Number of batch requests accepted, new or deduplicated.
+    pub fn accepted(&self) -> usize {
+        self.request_ids.len()
+    }
 }
 
 /// Per-agent rate limiter
@@ -432,11 +503,10 @@ impl AgentRateLimiter {
         }
     }
 
-    async fn acquire(&self, agent_id: &str) -> Result<tokio::sync::SemaphorePermit<'_>, ApiError> {
+    async fn acquire(&self, agent_id: &str) -> Result<OwnedSemaphorePermit, ApiError> {
         // Acquire semaphore (concurrency limit)
-        let permit = self
-            .semaphore
-            .acquire()
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
             .await
             .map_err(|_| ApiError::ProviderRateLimit("Semaphore closed".to_string()))?;
 
@@ -486,10 +556,14 @@ pub struct FrameGenerationQueue {
     api: Arc<ContextApi>,
     /// Rate limiters per agent
     rate_limiters: Arc<RwLock<HashMap<String, AgentRateLimiter>>>,
+    /// Adaptive concurrency controllers per provider, shared by every agent using it
+    provider_limiters: Arc<RwLock<HashMap<String, Arc<AdaptiveConcurrency>>>>,
+    /// Circuit breakers per provider
+    provider_breakers: Arc<RwLock<HashMap<String, Arc<CircuitBreaker>>>>,
     /// Running state
     running: Arc<RwLock<bool>>,
     /// Statistics
-    stats: Arc<RwLock<QueueStats>>,
+    stats: Arc<QueueCounters>,
     /// Optional observability context for queue and provider lifecycle events
     event_context: Option<QueueEventContext>,
     /// Index of active requests (queued or in-flight) by dedupe identity
@@ -529,14 +603,34 @@ impl FrameGenerationQueue {
             config,
             api,
             rate_limiters: Arc::new(RwLock::new(HashMap::new())),
+            provider_limiters: Arc::new(RwLock::new(HashMap::new())),
+            provider_breakers: Arc::new(RwLock::new(HashMap::new())),
             running: Arc::new(RwLock::new(false)),
-            stats: Arc::new(RwLock::new(QueueStats::default())),
+            stats: Arc::new(QueueCounters::default()),
             event_context,
             dedupe_index: Arc::new(Mutex::new(HashMap::new())),
             metadata_builder: Arc::new(metadata_builder),
         }
     }
 
+    /// Frame type for a request that did not name one: the agent's default for the node type.
+    ///
+    /// Unknown agents and nodes fall back to the flat `context-<agent_id>` type; they are
+    /// rejected when the request is processed.
+    fn default_frame_type(&self, node_id: &NodeID, agent_id: &str) -> String {
+        let flat = || format!("context-{}", agent_id);
+        let Ok(agent) = self.api.get_agent(agent_id) else {
+            return flat();
+        };
+        if !agent.frame_type_per_node_type {
+            return flat();
+        }
+        match self.api.node_store().get(node_id) {
+            Ok(Some(record)) => agent.default_frame_type(&record.node_type),
+            _ => flat(),
+        }
+    }
+
     /// Enqueue a generation request (async - returns immediately)
     pub async fn enqueue(
         &self,
@@ -555,7 +649,7 @@ impl FrameGenerationQueue {
         )?;
         let resolved_frame_type = frame_type
             .clone()
-            .unwrap_or_else(|| format!("context-{}", agent_id));
+            .unwrap_or_else(|| self.default_frame_type(&node_id, &agent_id));
         let identity = RequestIdentity::new(
             node_id,
             &agent_id,
@@ -617,10 +711,7 @@ impl FrameGenerationQueue {
         dedupe.insert(identity, DedupeEntry::new(request_id));
 
         // Update stats
-        {
-            let mut stats = self.stats.write();
-            stats.pending += 1;
-        }
+        self.stats.enqueued(1);
         self.emit_queue_stats_event();
 
         // Notify workers that a new item is available
@@ -693,7 +784,8 @@ impl FrameGenerationQueue {
         let mut queue = self.queue.lock().await;
         let mut dedupe = self.dedupe_index.lock().await;
 
-        let resolved_frame_type = frame_type.unwrap_or_else(|| format!("context-{}", agent_id));
+        let resolved_frame_type =
+            frame_type.unwrap_or_else(|| self.default_frame_type(&node_id, &agent_id));
         let identity = RequestIdentity::new(
             node_id,
             &agent_id,
@@ -777,10 +869,7 @@ impl FrameGenerationQueue {
         entry.push_waiter(QueueWaiter::new(started_tx, tx));
         dedupe.insert(identity, entry);
 
-        {
-            let mut stats = self.stats.write();
-            stats.pending += 1;
-        }
+        self.stats.enqueued(1);
         self.emit_queue_stats_event();
 
         self.notify.notify_one();
@@ -837,20 +926,47 @@ impl FrameGenerationQueue {
     }
 
     /// Enqueue multiple requests (batch enqueue)
+    ///
+    /// All or nothing: if the new requests would push the queue past `max_queue_size`, none are
+    /// enqueued and an error is returned. See [`Self::enqueue_batch_partial`] to accept what fits.
     pub async fn enqueue_batch(
         &self,
-        requests: Vec<(NodeID, String, String, Option<String>, Priority)>,
+        requests: Vec<BatchRequest>,
     ) -> Result<Vec<RequestId>, ApiError> {
+        let outcome = self.enqueue_batch_inner(requests, false).await?;
+        Ok(outcome.request_ids)
+    }
+
+    /// Enqueue as many requests as fit under `max_queue_size` and return the rest.
+    ///
+    /// Requests are taken in batch order; once the queue is full every later new request is
+    /// rejected, so callers can resubmit `rejected` as is. Requests that deduplicate onto one
+    /// already queued take no capacity and are always accepted.
+    pub async fn enqueue_batch_partial(
+        &self,
+        requests: Vec<BatchRequest>,
+    ) -> Result<PartialBatchEnqueue, ApiError> {
+        self.enqueue_batch_inner(requests, true).await
+    }
+
+    async fn enqueue_batch_inner(
+        &self,
+        requests: Vec<BatchRequest>,
+        partial: bool,
+    ) -> Result<PartialBatchEnqueue, ApiError> {
         let mut queue = self.queue.lock().await;
         let mut dedupe = self.dedupe_index.lock().await;
         let mut request_ids: Vec<RequestId> = Vec::new();
         let mut new_requests = Vec::new();
         let mut staged = HashMap::new();
         let mut enqueue_events = Vec::new();
+        let mut rejected = Vec::new();
         let program = TargetExecutionProgram::single_shot();
 
-        for (node_id, agent_id, provider_name, frame_type, priority) in requests {
-            let frame_type = frame_type.unwrap_or_else(|| format!("context-{}", agent_id));
+        for (node_id, agent_id, provider_name, requested_frame_type, priority) in requests {
+            let frame_type = requested_frame_type
+                .clone()
+                .unwrap_or_else(|| self.default_frame_type(&node_id, &agent_id));
             let provider = ProviderExecutionBinding::new(
                 provider_name.clone(),
                 ProviderRuntimeOverrides::default(),
@@ -892,6 +1008,17 @@ impl FrameGenerationQueue {
                 continue;
             }
 
+            if partial && queue.len() + new_requests.len() >= self.config.max_queue_size {
+                rejected.push((
+                    node_id,
+                    agent_id,
+                    provider_name,
+                    requested_frame_type,
+                    priority,
+                ));
+                continue;
+            }
+
             let request_id = RequestId::next();
             let request = GenerationRequest {
                 request_id,
@@ -943,10 +1070,7 @@ impl FrameGenerationQueue {
         let batch_size = new_count;
 
         // Update stats
-        {
-            let mut stats = self.stats.write();
-            stats.pending += batch_size;
-        }
+        self.stats.enqueued(batch_size);
         self.emit_queue_stats_event();
 
         // Notify workers (multiple times for multiple items)
@@ -960,14 +1084,26 @@ impl FrameGenerationQueue {
 
         debug!(
             batch_size = batch_size,
+            rejected = rejected.len(),
             "Enqueued batch of generation requests"
         );
+        if !rejected.is_empty() {
+            warn!(
+                accepted = request_ids.len(),
+                rejected = rejected.len(),
+                max_size = self.config.max_queue_size,
+                "Queue full; returned the rest of the batch to the caller"
+            );
+        }
 
         for payload in enqueue_events {
             self.emit_queue_event("request_enqueued", payload);
         }
 
-        Ok(request_ids)
+        Ok(PartialBatchEnqueue {
+            request_ids,
+            rejected,
+        })
     }
 
     /// Start background workers
@@ -990,6 +1126,8 @@ impl FrameGenerationQueue {
             let api = Arc::clone(&self.api);
             let config = self.config.clone();
             let rate_limiters = Arc::clone(&self.rate_limiters);
+            let provider_limiters = Arc::clone(&self.provider_limiters);
+            let provider_breakers = Arc::clone(&self.provider_breakers);
             let running = Arc::clone(&self.running);
             let stats = Arc::clone(&self.stats);
             let event_context = self.event_context.clone();
@@ -1004,6 +1142,8 @@ impl FrameGenerationQueue {
                     api,
                     config,
                     rate_limiters,
+                    provider_limiters,
+                    provider_breakers,
                     running,
                     stats,
                     event_context,
@@ -1046,9 +1186,30 @@ impl FrameGenerationQueue {
         Ok(())
     }
 
+    /// Whether workers are running (between [`Self::start`] and [`Self::stop`])
+    pub fn is_running(&self) -> bool {
+        *self.running.read()
+    }
+
     /// Get queue statistics
     pub fn stats(&self) -> QueueStats {
-        self.stats.read().clone()
+        self.stats.snapshot()
+    }
+
+    /// Effective concurrency limit for a provider, once it has handled a request.
+    pub fn provider_concurrency(&self, provider_name: &str) -> Option<usize> {
+        self.provider_limiters
+            .read()
+            .get(provider_name)
+            .map(|limiter| limiter.limit())
+    }
+
+    /// Whether a provider's circuit is open, once it has handled a request.
+    pub fn provider_circuit_open(&self, provider_name: &str) -> Option<bool> {
+        self.provider_breakers
+            .read()
+            .get(provider_name)
+            .map(|breaker| breaker.is_open())
     }
 
     /// Wait for queue to drain (all requests processed)
@@ -1057,8 +1218,7 @@ impl FrameGenerationQueue {
         loop {
             let is_complete = {
                 let queue = self.queue.lock().await;
-                let stats = self.stats.read();
-                queue.is_empty() && stats.processing == 0
+                queue.is_empty() && self.stats.processing() == 0
             };
 
             if is_complete {
@@ -1115,8 +1275,10 @@ impl FrameGenerationQueue {
         api: Arc<ContextApi>,
         config: GenerationConfig,
         rate_limiters: Arc<RwLock<HashMap<String, AgentRateLimiter>>>,
+        provider_limiters: Arc<RwLock<HashMap<String, Arc<AdaptiveConcurrency>>>>,
+        provider_breakers: Arc<RwLock<HashMap<String, Arc<CircuitBreaker>>>>,
         running: Arc<RwLock<bool>>,
-        stats: Arc<RwLock<QueueStats>>,
+        stats: Arc<QueueCounters>,
         event_context: Option<QueueEventContext>,
         dedupe_index: Arc<Mutex<HashMap<RequestIdentity, DedupeEntry>>>,
         metadata_builder: Arc<GeneratedMetadataBuilder>,
@@ -1148,11 +1310,7 @@ impl FrameGenerationQueue {
             };
 
             // Update stats
-            {
-                let mut stats = stats.write();
-                stats.pending = stats.pending.saturating_sub(1);
-                stats.processing += 1;
-            }
+            stats.started();
             Self::emit_queue_stats_event_static(stats.clone(), event_context.clone());
             Self::emit_queue_event_static(
                 event_context.clone(),
@@ -1188,11 +1346,39 @@ impl FrameGenerationQueue {
                 last_request,
                 min_delay,
             };
+            // Rate limits are per provider, so every agent using one backs off together. The
+            // ceiling is the worker count, which no provider can exceed anyway.
+            let provider_limiter = {
+                let mut limiters = provider_limiters.write();
+                Arc::clone(
+                    limiters
+                        .entry(request.provider.provider_name.clone())
+                        .or_insert_with(|| {
+                            Arc::new(AdaptiveConcurrency::new(
+                                config.workers_per_agent,
+                                config.rate_limit_recovery_successes,
+                            ))
+                        }),
+                )
+            };
             let request_identity = RequestIdentity::from_request(&request);
 
-            // Acquire rate limiter permit
-            let _permit = match rate_limiter.acquire(&request.agent_id).await {
-                Ok(permit) => permit,
+            // Acquire rate limiter permit, then the provider's adaptive permit
+            let permit = match rate_limiter.acquire(&request.agent_id).await {
+                Ok(permit) => match provider_limiter.acquire().await {
+                    Some(provider_permit) => (permit, provider_permit),
+                    None => {
+                        error!(
+                            worker_id,
+                            provider_name = %request.provider.provider_name,
+                            "Provider concurrency limiter closed"
+                        );
+                        let mut queue_guard = queue.lock().await;
+                        queue_guard.push(request.clone());
+                        stats.returned();
+                        continue;
+                    }
+                },
                 Err(e) => {
                     error!(
                         worker_id,
@@ -1203,49 +1389,109 @@ impl FrameGenerationQueue {
                     // Re-queue request (maintains priority order automatically)
                     let mut queue_guard = queue.lock().await;
                     queue_guard.push(request.clone());
-                    {
-                        let mut stats = stats.write();
-                        stats.processing = stats.processing.saturating_sub(1);
-                        stats.pending += 1;
-                    }
+                    stats.returned();
                     continue;
                 }
             };
 
-            {
-                let mut dedupe = dedupe_index.lock().await;
-                if let Some(entry) = dedupe.get_mut(&request_identity) {
-                    entry.mark_started();
-                }
-            }
+            // Fail fast while the provider's circuit is open
+            let provider_breaker = {
+                let mut breakers = provider_breakers.write();
+                Arc::clone(
+                    breakers
+                        .entry(request.provider.provider_name.clone())
+                        .or_insert_with(|| {
+                            Arc::new(CircuitBreaker::new(
+                                config.provider_failure_threshold,
+                                Duration::from_millis(config.provider_circuit_cooldown_ms),
+                            ))
+                        }),
+                )
+            };
+            let result = match provider_breaker.check(&request.provider.provider_name) {
+                Err(err) => Err(err),
+                Ok(()) => {
+                    {
+                        let mut dedupe = dedupe_index.lock().await;
+                        if let Some(entry) = dedupe.get_mut(&request_identity) {
+                            entry.mark_started();
+                        }
+                    }
 
-            // Process request
-            let result = Self::process_request(
-                &request,
-                &api,
-                &config,
-                event_context.clone(),
-                metadata_builder.as_ref(),
-            )
-            .await;
+                    // Process request
+                    let result = Self::process_request(
+                        &request,
+                        &api,
+                        &config,
+                        event_context.clone(),
+                        metadata_builder.as_ref(),
+                    )
+                    .await;
+
+                    match &result {
+                        Ok(_) => {
+                            if let Some(limit) = provider_limiter.on_success() {
+                                debug!(
+                                    provider_name = %request.provider.provider_name,
+                                    limit,
+                                    "Provider concurrency raised"
+                                );
+                            }
+                        }
+                        Err(ApiError::ProviderRateLimit(_)) => {
+                            let limit = provider_limiter.on_rate_limited();
+                            warn!(
+                                agent_id = %request.agent_id,
+                                provider_name = %request.provider.provider_name,
+                                limit,
+                                "Provider rate limited; provider concurrency reduced"
+                            );
+                        }
+                        Err(_) => {}
+                    }
+                    drop(permit);
+                    match &result {
+                        Ok(_) => provider_breaker.on_success(),
+                        Err(err) if breaker::is_provider_failure(err) => {
+                            if provider_breaker.on_failure() {
+                                warn!(
+                                    provider_name = %request.provider.provider_name,
+                                    cooldown_ms = config.provider_circuit_cooldown_ms,
+                                    "Provider circuit opened; failing its requests fast"
+                                );
+                            }
+                        }
+                        Err(_) => provider_breaker.on_inconclusive(),
+                    }
+                    result
+                }
+            };
 
             // Determine if we should retry (before sending result to completion channel)
             let should_retry = {
-                let mut stats_guard = stats.write();
-                stats_guard.processing = stats_guard.processing.saturating_sub(1);
+                stats.finished();
                 match &result {
                     Ok(_) => {
-                        stats_guard.completed += 1;
+                        stats.completed();
                         false
                     }
                     Err(err) => {
                         // Check if we should retry
-                        let retry = request.retry_count < config.max_retry_attempts
+                        let retryable = request.retry_count < config.max_retry_attempts
                             && Self::is_retryable_error(&request.program, err);
-                        if retry {
-                            // Will update stats after re-queuing
-                        } else {
-                            stats_guard.failed += 1;
+                        // Reserving counts the retry now so concurrent workers see the spent budget
+                        let retry = retryable && stats.try_reserve_retry(config.max_total_retries);
+                        if retryable && !retry {
+                            warn!(
+                                worker_id,
+                                node_id = %hex::encode(request.node_id),
+                                agent_id = %request.agent_id,
+                                retries = stats.retries(),
+                                "Retry budget exhausted; not retrying request"
+                            );
+                        }
+                        if !retry {
+                            stats.failed();
                             error!(
                                 worker_id,
                                 node_id = %hex::encode(request.node_id),
@@ -1275,7 +1521,7 @@ impl FrameGenerationQueue {
                 }
             }
 
-            // Re-queue if needed (after dropping stats guard)
+            // Re-queue if needed
             if should_retry {
                 Self::emit_provider_event_static(
                     event_context.clone(),
@@ -1302,9 +1548,7 @@ impl FrameGenerationQueue {
                 notify.notify_one();
 
                 // Update stats after re-queuing
-                let mut stats_guard = stats.write();
-                stats_guard.pending += 1;
-                drop(stats_guard);
+                stats.enqueued(1);
                 Self::emit_queue_stats_event_static(stats.clone(), event_context.clone());
             }
         }
@@ -1362,10 +1606,11 @@ impl FrameGenerationQueue {
             ApiError::PromptContextArtifactDigestMismatch { .. } => false,
             ApiError::PromptContextArtifactSizeMismatch { .. } => false,
             ApiError::PromptLinkContractInvalid { .. } => false,
+            ApiError::HeadConflict { .. } => false,
             ApiError::ProviderNotConfigured(_) => false,
             ApiError::ProviderRateLimit(_) => true,
             ApiError::ProviderRequestFailed(_) => true,
-            ApiError::ProviderError(_) => true,
+            ApiError::CircuitOpen { .. } => false,
             _ => true, // Retry other errors by default
         }
     }
@@ -1401,11 +1646,11 @@ impl FrameGenerationQueue {
     }
 
     fn emit_queue_stats_event_static(
-        stats: Arc<RwLock<QueueStats>>,
+        stats: Arc<QueueCounters>,
         event_context: Option<QueueEventContext>,
     ) {
         if let Some(ctx) = event_context {
-            let snapshot = stats.read().clone();
+            let snapshot = stats.snapshot();
             ctx.progress.emit_event_best_effort(
                 &ctx.session_id,
                 "queue_stats",
@@ -1414,6 +1659,7 @@ impl FrameGenerationQueue {
                     processing: snapshot.processing,
                     completed: snapshot.completed,
                     failed: snapshot.failed,
+                    retries: snapshot.retries,
                 }),
             );
         }
@@ -0,0 +1,173 @@
+//! Per-provider adaptive concurrency: additive increase, multiplicative decrease.
+//!
+//! Every agent using a provider shares its controller, since rate limits are the provider's.
+//! A rate-limit error halves the effective limit by withdrawing permits; every
+//! `recovery_successes` consecutive successes give one permit back, until the maximum is
+//! reached again.
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// AIMD concurrency controller for one provider.
+pub(crate) struct AdaptiveConcurrency {
+    semaphore: Arc<Semaphore>,
+    max: usize,
+    recovery_successes: usize,
+    state: Mutex<AimdState>,
+}
+
+struct AimdState {
+    /// Effective concurrency limit.
+    limit: usize,
+    /// Successes since the last limit change.
+    successes: usize,
+    /// Permits still to withdraw; they were in use when the limit was cut.
+    pending_forget: usize,
+}
+
+/// Concurrency permit; returned to the semaphore on drop unless the limit was cut meanwhile.
+pub(crate) struct AdaptivePermit {
+    permit: Option<OwnedSemaphorePermit>,
+    controller: Arc<AdaptiveConcurrency>,
+}
+
+impl Drop for AdaptivePermit {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            if self.controller.take_pending_forget() {
+                permit.forget();
+            }
+        }
+    }
+}
+
+impl AdaptiveConcurrency {
+    pub(crate) fn new(max: usize, recovery_successes: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+            max,
+            recovery_successes: recovery_successes.max(1),
+            state: Mutex::new(AimdState {
+                limit: max,
+                successes: 0,
+                pending_forget: 0,
+            }),
+        }
+    }
+
+    /// Wait for a permit under the current effective limit.
+    pub(crate) async fn acquire(self: &Arc<Self>) -> Option<AdaptivePermit> {
+        let permit = Arc::clone(&self.semaphore).acquire_owned().await.ok()?;
+        Some(AdaptivePermit {
+            permit: Some(permit),
+            controller: Arc::clone(self),
+        })
+    }
+
+    /// Current effective concurrency limit.
+    pub(crate) fn limit(&self) -> usize {
+        self.state.lock().limit
+    }
+
+    /// Halve the limit (never below one). Returns the new limit.
+    pub(crate) fn on_rate_limited(&self) -> usize {
+        let mut state = self.state.lock();
+        state.successes = 0;
+        let new_limit = (state.limit / 2).max(1);
+        let reduce = state.limit - new_limit;
+        state.limit = new_limit;
+        if reduce > 0 {
+            let forgotten = self.semaphore.forget_permits(reduce);
+            state.pending_forget += reduce - forgotten;
+        }
+        new_limit
+    }
+
+    /// Count a success; after enough in a row, raise the limit by one toward the maximum.
+    /// Returns the new limit when it changed.
+    pub(crate) fn on_success(&self) -> Option<usize> {
+        let mut state = self.state.lock();
+        if state.limit >= self.max {
+            return None;
+        }
+        state.successes += 1;
+        if state.successes < self.recovery_successes {
+            return None;
+        }
+        state.successes = 0;
+        state.limit += 1;
+        if state.pending_forget > 0 {
+            state.pending_forget -= 1;
+        } else {
+            self.semaphore.add_permits(1);
+        }
+        Some(state.limit)
+    }
+
+    fn take_pending_forget(&self) -> bool {
+        let mut state = self.state.lock();
+        if state.pending_forget == 0 {
+            return false;
+        }
+        state.pending_forget -= 1;
+        true
+    }
+
+    #[cfg(test)]
+    fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdaptiveConcurrency;
+    use std::sync::Arc;
+
+    #[test]
+    fn rate_limits_halve_and_successes_restore_limit() {
+        let controller = AdaptiveConcurrency::new(8, 2);
+
+        // Alternate: a rate limit resets the success streak, so no recovery in between.
+        assert_eq!(controller.on_rate_limited(), 4);
+        assert_eq!(controller.on_success(), None);
+        assert_eq!(controller.on_rate_limited(), 2);
+        assert_eq!(controller.on_success(), None);
+        assert_eq!(controller.on_rate_limited(), 1);
+        assert_eq!(controller.on_rate_limited(), 1);
+        assert_eq!(controller.limit(), 1);
+        assert_eq!(controller.available_permits(), 1);
+
+        // A stretch of successes restores one permit per two successes.
+        let mut limits = Vec::new();
+        for _ in 0..20 {
+            if let Some(limit) = controller.on_success() {
+                limits.push(limit);
+            }
+        }
+        assert_eq!(limits, vec![2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(controller.limit(), 8);
+        assert_eq!(controller.available_permits(), 8);
+    }
+
+    #[tokio::test]
+    async fn cut_while_permits_are_held_withdraws_them_on_release() {
+        let controller = Arc::new(AdaptiveConcurrency::new(4, 1));
+        let mut held = Vec::new();
+        for _ in 0..3 {
+            held.push(controller.acquire().await.unwrap());
+        }
+        assert_eq!(controller.available_permits(), 1);
+
+        // Limit 4 -> 2: one idle permit is withdrawn now, one more on release.
+        assert_eq!(controller.on_rate_limited(), 2);
+        assert_eq!(controller.available_permits(), 0);
+        held.clear();
+        assert_eq!(controller.available_permits(), 2);
+
+        assert_eq!(controller.on_success(), Some(3));
+        assert_eq!(controller.available_permits(), 3);
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-provider circuit breaker.
+//!
+//! After `failure_threshold` consecutive provider failures the circuit opens: requests for that
+//! provider fail at once with [`ApiError::CircuitOpen`] instead of waiting on a doomed call.
+//! Once `cooldown` has passed the circuit is half-open and lets a single probe request through;
+//! a successful probe closes it, a failed one opens it for another cooldown. State lives in
+//! memory for the life of the queue.
+
+use crate::error::ApiError;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Circuit breaker for one provider.
+pub(crate) struct CircuitBreaker {
+    failure_threshold: usize,
+    cooldown: Duration,
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    /// Requests flow; counts provider failures since the last success.
+    Closed { failures: usize },
+    /// Requests fail fast until the cooldown ends.
+    Open { until: Instant },
+    /// Cooldown over; `probing` while the one probe request is in flight.
+    HalfOpen { probing: bool },
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: usize, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Mutex::new(CircuitState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Admit a request for `provider_name`, or fail fast while the circuit is open.
+    pub(crate) fn check(&self, provider_name: &str) -> Result<(), ApiError> {
+        if self.allow_at(Instant::now()) {
+            Ok(())
+        } else {
+            Err(ApiError::CircuitOpen {
+                provider: provider_name.to_string(),
+                failures: self.failure_threshold,
+            })
+        }
+    }
+
+    fn allow_at(&self, now: Instant) -> bool {
+        let mut state = self.state.lock();
+        match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { until } if now < until => false,
+            CircuitState::Open { .. } | CircuitState::HalfOpen { probing: false } => {
+                *state = CircuitState::HalfOpen { probing: true };
+                true
+            }
+            CircuitState::HalfOpen { probing: true } => false,
+        }
+    }
+
+    /// The provider answered; close the circuit.
+    pub(crate) fn on_success(&self) {
+        *self.state.lock() = CircuitState::Closed { failures: 0 };
+    }
+
+    /// The provider failed. Returns true when this failure opened the circuit.
+    pub(crate) fn on_failure(&self) -> bool {
+        self.on_failure_at(Instant::now())
+    }
+
+    fn on_failure_at(&self, now: Instant) -> bool {
+        let mut state = self.state.lock();
+        let open = match *state {
+            CircuitState::Closed { failures } => {
+                let failures = failures + 1;
+                *state = CircuitState::Closed { failures };
+                failures >= self.failure_threshold
+            }
+            CircuitState::HalfOpen { .. } => true,
+            CircuitState::Open { .. } => false,
+        };
+        if open {
+            *state = CircuitState::Open {
+                until: now + self.cooldown,
+            };
+        }
+        open
+    }
+
+    /// The admitted request ended without reaching a verdict on the provider; let another
+    /// probe through if this was one.
+    pub(crate) fn on_inconclusive(&self) {
+        let mut state = self.state.lock();
+        if *state == (CircuitState::HalfOpen { probing: true }) {
+            *state = CircuitState::HalfOpen { probing: false };
+        }
+    }
+
+    /// Whether requests are currently failing fast.
+    pub(crate) fn is_open(&self) -> bool {
+        matches!(*self.state.lock(), CircuitState::Open { until } if Instant::now() < until)
+    }
+}
+
+/// Whether `error` counts against the provider's circuit: the provider failed or was
+/// unreachable, as opposed to rate limiting (handled by adaptive concurrency) or a local error.
+pub(crate) fn is_provider_failure(error: &ApiError) -> bool {
+    matches!(
+        error,
+        ApiError::ProviderRequestFailed(_) | ApiError::ProviderError(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_provider_failure, CircuitBreaker};
+    use crate::error::ApiError;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn consecutive_failures_open_the_circuit_and_fail_fast() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+        let now = Instant::now();
+
+        assert!(!breaker.on_failure_at(now));
+        breaker.on_success();
+        assert!(!breaker.on_failure_at(now));
+        assert!(!breaker.on_failure_at(now));
+        assert!(breaker.allow_at(now));
+        assert!(breaker.on_failure_at(now));
+
+        assert!(!breaker.allow_at(now + Duration::from_secs(29)));
+        let err = breaker.check("local").unwrap_err();
+        assert!(
+            matches!(err, ApiError::CircuitOpen { ref provider, failures: 3 } if provider == "local")
+        );
+        assert!(!is_provider_failure(&err));
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn probe_after_cooldown_closes_or_reopens_the_circuit() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(30));
+        let opened = Instant::now();
+        assert!(breaker.on_failure_at(opened));
+
+        // One probe after the cooldown; a second caller still fails fast meanwhile.
+        let after_cooldown = opened + Duration::from_secs(31);
+        assert!(breaker.allow_at(after_cooldown));
+        assert!(!breaker.allow_at(after_cooldown));
+
+        // A failed probe opens the circuit for another cooldown.
+        assert!(breaker.on_failure_at(after_cooldown));
+        assert!(!breaker.allow_at(after_cooldown + Duration::from_secs(29)));
+
+        let retry = after_cooldown + Duration::from_secs(31);
+        assert!(breaker.allow_at(retry));
+        breaker.on_success();
+        assert!(breaker.allow_at(retry));
+        assert!(breaker.allow_at(retry));
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn inconclusive_probe_lets_the_next_one_through() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        let now = Instant::now();
+        assert!(breaker.on_failure_at(now));
+        assert!(breaker.allow_at(now));
+        assert!(!breaker.allow_at(now));
+        breaker.on_inconclusive();
+        assert!(breaker.allow_at(now));
+    }
+
+    #[test]
+    fn only_provider_failures_count() {
+        assert!(is_provider_failure(&ApiError::ProviderRequestFailed(
+            "connection refused".to_string()
+        )));
+        assert!(!is_provider_failure(&ApiError::ProviderRateLimit(
+            "429".to_string()
+        )));
+        assert!(!is_provider_failure(&ApiError::ConfigError(
+            "bad".to_string()
+        )));
+    }
+}
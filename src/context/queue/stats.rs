@@ -0,0 +1,155 @@
+//! Lock-free queue counters.
+//!
+//! Workers update these on every request transition, so each counter is its own atomic rather
+//! than a field behind a shared lock. [`QueueCounters::snapshot`] reads them one by one; a
+//! snapshot taken mid-transition may be off by one between `pending` and `processing`, but no
+//! counter ever underflows.
+
+use super::QueueStats;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Default)]
+pub(crate) struct QueueCounters {
+    pending: AtomicUsize,
+    processing: AtomicUsize,
+    completed: AtomicUsize,
+    failed: AtomicUsize,
+    retries: AtomicUsize,
+}
+
+impl QueueCounters {
+    /// `count` requests entered the queue.
+    pub(crate) fn enqueued(&self, count: usize) {
+        self.pending.fetch_add(count, Ordering::AcqRel);
+    }
+
+    /// A worker took a request off the queue.
+    pub(crate) fn started(&self) {
+        saturating_decrement(&self.pending);
+        self.processing.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// A worker put a request back on the queue without processing it.
+    pub(crate) fn returned(&self) {
+        saturating_decrement(&self.processing);
+        self.pending.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// A worker finished processing a request, whatever the outcome.
+    pub(crate) fn finished(&self) {
+        saturating_decrement(&self.processing);
+    }
+
+    pub(crate) fn completed(&self) {
+        self.completed.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(crate) fn failed(&self) {
+        self.failed.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Spend one retry unless `max_total` retries are already spent.
+    ///
+    /// The check and the increment are one atomic step, so concurrent workers never overspend.
+    pub(crate) fn try_reserve_retry(&self, max_total: Option<usize>) -> bool {
+        self.retries
+            .fetch_update(
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                |spent| match max_total {
+                    Some(max) if spent >= max => None,
+                    _ => Some(spent + 1),
+                },
+            )
+            .is_ok()
+    }
+
+    pub(crate) fn retries(&self) -> usize {
+        self.retries.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn processing(&self) -> usize {
+        self.processing.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn snapshot(&self) -> QueueStats {
+        QueueStats {
+            pending: self.pending.load(Ordering::Acquire),
+            processing: self.processing.load(Ordering::Acquire),
+            completed: self.completed.load(Ordering::Acquire),
+            failed: self.failed.load(Ordering::Acquire),
+            retries: self.retries.load(Ordering::Acquire),
+        }
+    }
+}
+
+fn saturating_decrement(counter: &AtomicUsize) {
+    let _ = counter.fetch_update(Ordering::AcqRel, Ordering::Acquire, |value| {
+        Some(value.saturating_sub(1))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueueCounters;
+    use std::sync::Arc;
+
+    #[test]
+    fn concurrent_updates_produce_exact_totals() {
+        const THREADS: usize = 16;
+        const REQUESTS: usize = 2_000;
+        const RETRY_BUDGET: usize = 5_000;
+
+        let counters = Arc::new(QueueCounters::default());
+        let handles: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                let counters = Arc::clone(&counters);
+                std::thread::spawn(move || {
+                    let mut reserved = 0;
+                    for request in 0..REQUESTS {
+                        counters.enqueued(1);
+                        counters.started();
+                        if request % 4 == 0 {
+                            counters.returned();
+                            counters.started();
+                        }
+                        counters.finished();
+                        if (thread + request) % 3 == 0 {
+                            counters.failed();
+                        } else {
+                            counters.completed();
+                        }
+                        if counters.try_reserve_retry(Some(RETRY_BUDGET)) {
+                            reserved += 1;
+                        }
+                    }
+                    reserved
+                })
+            })
+            .collect();
+        let reserved: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+        let stats = counters.snapshot();
+        let failed = (0..THREADS)
+            .flat_map(|thread| (0..REQUESTS).map(move |request| (thread + request) % 3 == 0))
+            .filter(|failed| *failed)
+            .count();
+        assert_eq!(stats.pending, 0);
+        assert_eq!(stats.processing, 0);
+        assert_eq!(stats.failed, failed);
+        assert_eq!(stats.completed, THREADS * REQUESTS - failed);
+        assert_eq!(stats.retries, RETRY_BUDGET);
+        assert_eq!(reserved, RETRY_BUDGET);
+    }
+
+    #[test]
+    fn decrements_saturate_at_zero() {
+        let counters = QueueCounters::default();
+        counters.started();
+        counters.finished();
+        counters.finished();
+        let stats = counters.snapshot();
+        assert_eq!(stats.pending, 0);
+        assert_eq!(stats.processing, 0);
+    }
+}
@@ -1,17 +1,33 @@
+use crate::agent::profile::prompt_contract::parse_prompt_vars;
 use crate::api::ContextApi;
 use crate::cli::{
-    format_context_json_output, format_context_text_output, parse_provider_additional_json_file,
-    ContextCommands,
+    format_context_count_output, format_context_json_output, format_context_ndjson_line,
+    format_context_search_hit, format_context_size_output, format_context_text_output,
+    parse_provider_additional_json_file, ContextCommands, ContextViewOptions, GenerateArgs,
+};
+use crate::context::frame::inspect::resolve_frame_bases;
+use crate::context::generation::contracts::HeadConflictPolicy;
+use crate::context::generation::ordering::parse_order_hints;
+use crate::context::generation::plan::PlanPriority;
+use crate::context::generation::run::{resolve_provider_name, run_generate, GenerateRequest};
+use crate::context::portable::{
+    export_node_frames, import_node_frames, read_node_export, write_node_export,
+};
+use crate::context::query::get::resolve_target;
+use crate::context::query::{
+    apply_token_budget, count_frames_for_cli, for_each_node_context_for_cli, get_node_for_cli,
+    keep_newest_frame_per_agent, prepend_ancestor_frames, search_frames_for_cli,
 };
-use crate::context::generation::run::{run_generate, GenerateRequest};
-use crate::context::query::get_node_for_cli;
 use crate::error::ApiError;
 use crate::provider::{ProviderExecutionBinding, ProviderRuntimeOverrides};
 use crate::telemetry::ProgressRuntime;
+use crate::types::NodeID;
 use crate::workflow::WorkflowRegistry;
 use serde_json::json;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub fn handle_cli_command(
     api: Arc<ContextApi>,
@@ -22,25 +38,55 @@ pub fn handle_cli_command(
     session_id: &str,
 ) -> Result<String, ApiError> {
     match command {
-        ContextCommands::Generate {
-            node,
-            path,
-            path_positional,
-            agent,
-            provider,
-            workflow_id,
-            provider_model,
-            provider_additional_json_file,
-            frame_type,
-            force,
-            no_recursive,
-        } => {
+        ContextCommands::Generate(args) => {
+            let GenerateArgs {
+                node,
+                path,
+                path_positional,
+                agent,
+                provider,
+                workflow_id,
+                provider_model,
+                provider_additional_json_file,
+                frame_type,
+                force,
+                only_missing: _,
+                only_stale,
+                no_recursive,
+                continue_on_error,
+                stdin_prompt,
+                prompt_file,
+                stream,
+                max_total_retries,
+                include_binary,
+                prompt_var,
+                max_depth,
+                after,
+                exclude,
+                priority,
+                preserve_mtime,
+                record_provenance,
+                timeout,
+                on_conflict,
+            } = args.as_ref();
             let path_merged = path.as_ref().or(path_positional.as_ref());
+            let provider_name = resolve_provider_name(&api, agent.as_deref(), provider.as_deref())?;
             let provider_binding = build_generate_provider_binding(
-                provider.as_deref(),
+                &provider_name,
                 provider_model.as_deref(),
                 provider_additional_json_file.as_ref(),
             )?;
+            let user_prompt_override =
+                read_user_prompt_override(*stdin_prompt, prompt_file.as_ref())?;
+            let prompt_vars = parse_prompt_vars(prompt_var)?;
+            let order_hints = parse_order_hints(after)?;
+            let priority = PlanPriority::parse(priority)?;
+            let on_conflict = HeadConflictPolicy::parse(on_conflict).ok_or_else(|| {
+                ApiError::ConfigError(format!(
+                    "Invalid --on-conflict '{}'. Expected last-wins, fail, or keep-both.",
+                    on_conflict
+                ))
+            })?;
             let request = GenerateRequest {
                 node: node.clone(),
                 path: path_merged.cloned(),
@@ -49,7 +95,22 @@ pub fn handle_cli_command(
                 workflow_id: workflow_id.clone(),
                 frame_type: frame_type.clone(),
                 force: *force,
+                only_stale: *only_stale,
                 no_recursive: *no_recursive,
+                continue_on_error: *continue_on_error,
+                user_prompt_override,
+                stream_output: *stream,
+                max_total_retries: *max_total_retries,
+                include_binary: *include_binary,
+                prompt_vars,
+                max_depth: *max_depth,
+                order_hints,
+                priority,
+                preserve_mtime: *preserve_mtime,
+                record_provenance: *record_provenance,
+                timeout: timeout.map(Duration::from_secs),
+                on_conflict,
+                exclude: exclude.clone(),
             };
             run_generate(
                 api,
@@ -72,8 +133,9 @@ pub fn handle_cli_command(
             recursive,
         } => {
             let path_merged = path.as_ref().or(path_positional.as_ref());
+            let provider_name = resolve_provider_name(&api, agent.as_deref(), provider.as_deref())?;
             let provider_binding = build_generate_provider_binding(
-                provider.as_deref(),
+                &provider_name,
                 provider_model.as_deref(),
                 provider_additional_json_file.as_ref(),
             )?;
@@ -85,7 +147,22 @@ pub fn handle_cli_command(
                 workflow_id: workflow_id.clone(),
                 frame_type: frame_type.clone(),
                 force: true,
+                only_stale: false,
                 no_recursive: !*recursive,
+                continue_on_error: false,
+                user_prompt_override: None,
+                stream_output: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_vars: Default::default(),
+                max_depth: None,
+                order_hints: Vec::new(),
+                priority: PlanPriority::Urgent,
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: HeadConflictPolicy::LastWins,
+                exclude: Vec::new(),
             };
             run_generate(
                 api,
@@ -107,41 +184,227 @@ pub fn handle_cli_command(
             format,
             include_metadata,
             include_deleted,
+            flatten_directory,
+            meta,
+            count_only,
+            export,
+            budget_tokens,
+            with_ancestors,
+            size_only,
+            newest_per_agent,
+            resolve_basis,
+            include_provenance,
         } => {
+            if budget_tokens.is_some() && !*combine && !*flatten_directory {
+                return Err(ApiError::ConfigError(
+                    "--budget-tokens applies to combined output; add --combine.".to_string(),
+                ));
+            }
+            if *size_only && !*combine && !*flatten_directory {
+                return Err(ApiError::ConfigError(
+                    "--size-only measures combined output; add --combine.".to_string(),
+                ));
+            }
             let effective_frame_type = resolve_context_get_frame_type(
                 &api,
                 workflow_registry,
                 agent.as_deref(),
                 frame_type.as_deref(),
             )?;
-            let context = get_node_for_cli(
+            if *count_only {
+                let (node_id, count) = count_frames_for_cli(
+                    &api,
+                    workspace_root,
+                    node.as_deref(),
+                    path.as_deref(),
+                    agent.as_deref(),
+                    effective_frame_type.as_deref(),
+                    meta,
+                    *include_deleted,
+                    *flatten_directory,
+                )?;
+                let output = format_context_count_output(count, format)?;
+                progress.emit_event_best_effort(
+                    session_id,
+                    "context_read_summary",
+                    json!({
+                        "node_id": hex::encode(node_id),
+                        "frame_count": count,
+                        "count_only": true,
+                        "flatten_directory": flatten_directory,
+                        "format": format
+                    }),
+                );
+                return Ok(output);
+            }
+            if *size_only && format == "ndjson" {
+                return Err(ApiError::ConfigError(
+                    "--size-only is not supported with --format ndjson.".to_string(),
+                ));
+            }
+            if *newest_per_agent && format == "ndjson" {
+                return Err(ApiError::ConfigError(
+                    "--newest-per-agent is not supported with --format ndjson.".to_string(),
+                ));
+            }
+            if *with_ancestors && format == "ndjson" {
+                return Err(ApiError::ConfigError(
+                    "--with-ancestors is not supported with --format ndjson.".to_string(),
+                ));
+            }
+            if format == "ndjson" {
+                // Each node's line is written as soon as it is read, so large trees are never
+                // held in memory whole.
+                let stdout = std::io::stdout();
+                let mut stdout = stdout.lock();
+                let (node_id, node_count) = for_each_node_context_for_cli(
+                    &api,
+                    workspace_root,
+                    node.as_deref(),
+                    path.as_deref(),
+                    agent.as_deref(),
+                    effective_frame_type.as_deref(),
+                    *max_frames,
+                    ordering,
+                    meta,
+                    *flatten_directory,
+                    |node_context| {
+                        let bases = if *resolve_basis {
+                            Some(resolve_frame_bases(&api, &node_context.context.frames)?)
+                        } else {
+                            None
+                        };
+                        let view = ContextViewOptions {
+                            include_metadata: *include_metadata,
+                            include_deleted: *include_deleted,
+                            bases: bases.as_ref(),
+                            include_provenance: *include_provenance,
+                        };
+                        let line = format_context_ndjson_line(
+                            &node_context.context,
+                            &node_context.warnings,
+                            &view,
+                        )?;
+                        writeln!(stdout, "{}", line).map_err(|e| ApiError::StorageError(e.into()))
+                    },
+                )?;
+                progress.emit_event_best_effort(
+                    session_id,
+                    "context_read_summary",
+                    json!({
+                        "node_id": hex::encode(node_id),
+                        "node_count": node_count,
+                        "max_frames": max_frames,
+                        "ordering": ordering,
+                        "flatten_directory": flatten_directory,
+                        "format": format
+                    }),
+                );
+                return Ok(String::new());
+            }
+            let mut context = get_node_for_cli(
                 &api,
                 workspace_root,
                 node.as_deref(),
                 path.as_deref(),
                 agent.as_deref(),
                 effective_frame_type.as_deref(),
-                *max_frames,
+                if *newest_per_agent {
+                    usize::MAX
+                } else {
+                    *max_frames
+                },
                 ordering,
+                meta,
                 *include_deleted,
+                *flatten_directory,
             )?;
+            if *newest_per_agent {
+                keep_newest_frame_per_agent(&mut context.context, *include_deleted, *max_frames);
+            }
+            let ancestor_frames = if *with_ancestors {
+                Some(prepend_ancestor_frames(
+                    &api,
+                    &mut context.context,
+                    effective_frame_type.as_deref(),
+                )?)
+            } else {
+                None
+            };
+            let budget = budget_tokens.map(|budget| {
+                if !*include_deleted {
+                    context.context.frames.retain(|frame| !frame.is_deleted());
+                }
+                let outcome = apply_token_budget(&mut context.context.frames, budget, separator);
+                if outcome.dropped > 0 {
+                    context.warnings.push(format!(
+                        "Token budget {}: kept {} frame(s) (~{} tokens), dropped {}.",
+                        outcome.budget, outcome.kept, outcome.estimated_tokens, outcome.dropped
+                    ));
+                }
+                outcome
+            });
+            if let Some(file) = export {
+                let context = context.context;
+                let frames: Vec<_> = context
+                    .frames
+                    .into_iter()
+                    .filter(|frame| *include_deleted || !frame.is_deleted())
+                    .collect();
+                let frame_count = frames.len();
+                let node_export = export_node_frames(
+                    &api,
+                    workspace_root,
+                    context.node_id,
+                    &context.node_record.path,
+                    frames,
+                )?;
+                write_node_export(file, &node_export)?;
+                progress.emit_event_best_effort(
+                    session_id,
+                    "context_export_summary",
+                    json!({
+                        "node_id": hex::encode(context.node_id),
+                        "frame_count": frame_count,
+                        "head_count": node_export.heads.len(),
+                        "file": file.to_string_lossy()
+                    }),
+                );
+                return Ok(format!(
+                    "Exported {} frame(s) of node {} to {}\n",
+                    frame_count,
+                    hex::encode(context.node_id),
+                    file.display()
+                ));
+            }
+            let bases = if *resolve_basis {
+                Some(resolve_frame_bases(&api, &context.context.frames)?)
+            } else {
+                None
+            };
+            let view = ContextViewOptions {
+                include_metadata: *include_metadata,
+                include_deleted: *include_deleted,
+                bases: bases.as_ref(),
+                include_provenance: *include_provenance,
+            };
             let formatted = match format.as_str() {
-                "text" => format_context_text_output(
+                _ if *size_only => format_context_size_output(
                     &context.context,
-                    &context.warnings,
-                    *include_metadata,
-                    *combine,
                     separator,
                     *include_deleted,
+                    format,
                 ),
-                "json" => format_context_json_output(
+                "text" => format_context_text_output(
                     &context.context,
                     &context.warnings,
-                    *include_metadata,
-                    *include_deleted,
+                    &view,
+                    *combine || *flatten_directory,
+                    separator,
                 ),
+                "json" => format_context_json_output(&context.context, &context.warnings, &view),
                 _ => Err(ApiError::ConfigError(format!(
-                    "Invalid format: '{}'. Must be 'text' or 'json'.",
+                    "Invalid format: '{}'. Must be 'text', 'json', or 'ndjson'.",
                     format
                 ))),
             }?;
@@ -154,11 +417,167 @@ pub fn handle_cli_command(
                     "max_frames": max_frames,
                     "ordering": ordering,
                     "combine": combine,
-                    "format": format
+                    "flatten_directory": flatten_directory,
+                    "format": format,
+                    "budget_tokens": budget_tokens,
+                    "dropped_frames": budget.map(|outcome| outcome.dropped),
+                    "ancestor_frames": ancestor_frames,
+                    "size_only": size_only,
+                    "newest_per_agent": newest_per_agent,
+                    "resolve_basis": resolve_basis,
+                    "include_provenance": include_provenance
                 }),
             );
             Ok(formatted)
         }
+        ContextCommands::Import { file, node, path } => {
+            let node_export = read_node_export(file)?;
+            let target_path = match (node, path) {
+                (None, None) => Some(node_export.path.clone()),
+                _ => path.clone(),
+            };
+            let node_id = resolve_target(
+                &api,
+                workspace_root,
+                node.as_deref(),
+                target_path.as_deref(),
+            )?;
+            let result = import_node_frames(&api, node_id, node_export)?;
+            progress.emit_event_best_effort(
+                session_id,
+                "context_import_summary",
+                json!({
+                    "node_id": hex::encode(node_id),
+                    "frame_count": result.frame_ids.len(),
+                    "heads": result.heads,
+                    "file": file.to_string_lossy()
+                }),
+            );
+            let heads = if result.heads.is_empty() {
+                "none".to_string()
+            } else {
+                result.heads.join(", ")
+            };
+            Ok(format!(
+                "Imported {} frame(s) into node {} (heads: {})\n",
+                result.frame_ids.len(),
+                hex::encode(node_id),
+                heads
+            ))
+        }
+        ContextCommands::Copy {
+            node,
+            path,
+            from_type,
+            to_type,
+        } => {
+            let node_id = resolve_target(&api, workspace_root, node.as_deref(), path.as_deref())?;
+            let frame_id = api.copy_frame(node_id, from_type, to_type)?;
+            progress.emit_event_best_effort(
+                session_id,
+                "context_copy_summary",
+                json!({
+                    "node_id": hex::encode(node_id),
+                    "from_type": from_type,
+                    "to_type": to_type,
+                    "frame_id": hex::encode(frame_id)
+                }),
+            );
+            Ok(format!(
+                "Copied head frame of type '{}' to '{}' on node {}: {}\n",
+                from_type,
+                to_type,
+                hex::encode(node_id),
+                hex::encode(frame_id)
+            ))
+        }
+        ContextCommands::Touch {
+            node,
+            path,
+            recursive,
+            frame_type,
+        } => {
+            let node_id = resolve_target(&api, workspace_root, node.as_deref(), path.as_deref())?;
+            let node_ids: Vec<NodeID> = if *recursive {
+                api.collect_subtree_node_ids(node_id)?.into_iter().collect()
+            } else {
+                vec![node_id]
+            };
+            let mut nodes_touched = 0usize;
+            let mut heads_marked = 0usize;
+            for node_id in node_ids {
+                let live = api
+                    .node_store()
+                    .get(&node_id)
+                    .map_err(ApiError::from)?
+                    .is_some_and(|record| record.tombstoned_at.is_none());
+                if !live {
+                    continue;
+                }
+                let marked = api.mark_heads_stale(node_id, frame_type.as_deref())?;
+                if !marked.is_empty() {
+                    nodes_touched += 1;
+                    heads_marked += marked.len();
+                }
+            }
+            progress.emit_event_best_effort(
+                session_id,
+                "context_touch_summary",
+                json!({
+                    "node_id": hex::encode(node_id),
+                    "recursive": recursive,
+                    "frame_type": frame_type,
+                    "nodes_touched": nodes_touched,
+                    "heads_marked": heads_marked
+                }),
+            );
+            Ok(format!(
+                "Marked {} head(s) stale on {} node(s)\n",
+                heads_marked, nodes_touched
+            ))
+        }
+        ContextCommands::Search {
+            query,
+            path,
+            frame_type,
+            regex,
+            format,
+        } => {
+            if format != "text" && format != "ndjson" {
+                return Err(ApiError::ConfigError(format!(
+                    "Invalid format: '{}'. Must be 'text' or 'ndjson'.",
+                    format
+                )));
+            }
+            let mut output = String::new();
+            let hits = search_frames_for_cli(
+                &api,
+                workspace_root,
+                path.as_deref(),
+                frame_type.as_deref(),
+                query,
+                *regex,
+                |hit| {
+                    output.push_str(&format_context_search_hit(&hit, format)?);
+                    Ok(())
+                },
+            )?;
+            progress.emit_event_best_effort(
+                session_id,
+                "context_search_summary",
+                json!({
+                    "hit_count": hits,
+                    "scoped": path.is_some(),
+                    "frame_type": frame_type,
+                    "regex": regex,
+                    "format": format
+                }),
+            );
+            if hits == 0 && format == "text" {
+                output.push_str("No matching frames found.\n");
+            }
+            Ok(output)
+        }
     }
 }
 
@@ -198,15 +617,10 @@ fn resolve_context_get_frame_type(
 }
 
 fn build_generate_provider_binding(
-    provider_name: Option<&str>,
+    provider_name: &str,
     provider_model: Option<&str>,
     provider_additional_json_file: Option<&PathBuf>,
 ) -> Result<ProviderExecutionBinding, ApiError> {
-    let provider_name = provider_name.ok_or_else(|| {
-        ApiError::ProviderNotConfigured(
-            "Provider is required. Use `--provider <provider_name>` to specify a provider. Use `meld provider list` to see available providers.".to_string(),
-        )
-    })?;
     let provider_additional_json =
         parse_provider_additional_json_file(provider_additional_json_file)
             .map_err(ApiError::ConfigError)?;
@@ -219,3 +633,31 @@ fn build_generate_provider_binding(
         provider_runtime_overrides,
     )?)
 }
+
+/// Read a user prompt override from stdin or a file; `None` keeps the agent template.
+fn read_user_prompt_override(
+    stdin_prompt: bool,
+    prompt_file: Option<&PathBuf>,
+) -> Result<Option<String>, ApiError> {
+    let prompt = if stdin_prompt {
+        std::io::read_to_string(std::io::stdin()).map_err(|e| {
+            ApiError::ConfigError(format!("Failed to read prompt from stdin: {}", e))
+        })?
+    } else if let Some(prompt_file) = prompt_file {
+        std::fs::read_to_string(prompt_file).map_err(|e| {
+            ApiError::ConfigError(format!(
+                "Failed to read prompt file {}: {}",
+                prompt_file.display(),
+                e
+            ))
+        })?
+    } else {
+        return Ok(None);
+    };
+    if prompt.trim().is_empty() {
+        return Err(ApiError::ConfigError(
+            "Prompt override is empty".to_string(),
+        ));
+    }
+    Ok(Some(prompt))
+}
@@ -17,6 +17,26 @@ pub struct RestoreResult {
     pub head_entries_restored: u64,
 }
 
+/// Result of reconciling the head index against frame storage.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeadRecoveryResult {
+    pub heads_checked: u64,
+    pub dangling_heads_dropped: u64,
+}
+
+/// Result of replacing the head index with a named snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeadRestoreResult {
+    pub snapshot: String,
+    /// Heads moved to the frame recorded in the snapshot.
+    pub heads_selected: u64,
+    /// Active heads the snapshot does not have, now cleared.
+    pub heads_removed: u64,
+    pub heads_unchanged: u64,
+    /// Snapshot heads dropped because their frame blob no longer exists.
+    pub dangling_heads_skipped: u64,
+}
+
 /// Result of a compact operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompactResult {
@@ -36,6 +36,13 @@ pub async fn execute_target_request(
         frame_type: request.frame_type.clone(),
         retry_count: request.retry_count,
         force: request.options.force,
+        user_prompt_override: request.options.user_prompt_override.clone(),
+        stream_output: request.options.stream_output,
+        preserve_mtime: request.options.preserve_mtime,
+        record_provenance: request.options.record_provenance,
+        prompt_vars: request.options.prompt_vars.clone(),
+        on_conflict: request.options.on_conflict,
+        expected_head: request.options.expected_head,
     };
     execute_generation_request(&orchestration_request, api, metadata_builder, event_context).await
 }
@@ -27,6 +27,10 @@ pub struct LevelCompletedEventData {
     pub generated_count: usize,
     pub failed_count: usize,
     pub total_count: usize,
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub peak_concurrency: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -71,6 +75,8 @@ pub struct GenerationFailedEventData {
     pub failed_level_index: Option<usize>,
     pub total_generated: usize,
     pub total_failed: usize,
+    #[serde(default)]
+    pub failures: Vec<GenerationFailureEntry>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -78,6 +84,24 @@ pub struct GenerationCompletedEventData {
     pub plan_id: String,
     pub total_generated: usize,
     pub total_failed: usize,
+    #[serde(default)]
+    pub failures: Vec<GenerationFailureEntry>,
+}
+
+/// One failed node reported at the end of a generation run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationFailureEntry {
+    pub path: String,
+    pub error: String,
+}
+
+impl GenerationFailureEntry {
+    pub fn from_report(report: Vec<(String, String)>) -> Vec<Self> {
+        report
+            .into_iter()
+            .map(|(path, error)| Self { path, error })
+            .collect()
+    }
 }
 
 fn control_envelope(
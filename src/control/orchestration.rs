@@ -9,8 +9,9 @@ use crate::control::events::{
     generation_completed_envelope, generation_failed_envelope, generation_started_envelope,
     level_completed_envelope, level_started_envelope, node_completed_envelope,
     node_failed_envelope, node_started_envelope, GenerationCompletedEventData,
-    GenerationFailedEventData, GenerationStartedEventData, LevelCompletedEventData,
-    LevelStartedEventData, NodeCompletedEventData, NodeFailedEventData, NodeStartedEventData,
+    GenerationFailedEventData, GenerationFailureEntry, GenerationStartedEventData,
+    LevelCompletedEventData, LevelStartedEventData, NodeCompletedEventData, NodeFailedEventData,
+    NodeStartedEventData,
 };
 use crate::error::ApiError;
 use crate::events::EventEnvelope;
@@ -18,7 +19,11 @@ use crate::telemetry::ProgressRuntime;
 use crate::types::FrameID;
 use futures::stream::{FuturesUnordered, StreamExt};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::time::MissedTickBehavior;
+
+/// How often a running level samples the queue's in-flight count.
+const CONCURRENCY_SAMPLE_INTERVAL: Duration = Duration::from_millis(25);
 
 #[allow(async_fn_in_trait)]
 pub trait QueueSubmitter: Send + Sync {
@@ -29,6 +34,12 @@ pub trait QueueSubmitter: Send + Sync {
         plan_id: &str,
         wait_timeout: Option<Duration>,
     ) -> Result<FrameID, ApiError>;
+
+    /// Requests being processed right now, if the submitter can tell. Sampled while a level
+    /// runs to report how many of its nodes actually ran at once.
+    fn in_flight(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl QueueSubmitter for FrameGenerationQueue {
@@ -50,16 +61,28 @@ impl QueueSubmitter for FrameGenerationQueue {
             crate::context::queue::GenerationRequestOptions {
                 force: item.force,
                 plan_id: Some(plan_id.to_string()),
+                user_prompt_override: item.user_prompt_override.clone(),
+                stream_output: item.stream_output,
+                preserve_mtime: item.preserve_mtime,
+                record_provenance: item.record_provenance,
+                prompt_vars: item.prompt_vars.clone(),
+                on_conflict: item.on_conflict,
+                expected_head: item.expected_head,
             },
         )
         .await
     }
+
+    fn in_flight(&self) -> Option<usize> {
+        Some(self.stats().processing)
+    }
 }
 
 /// Executes a generation plan by submitting items to a queue and collecting results.
 pub struct GenerationExecutor {
     progress: Option<Arc<ProgressRuntime>>,
     wait_timeout: Option<Duration>,
+    time_limit: Option<Duration>,
 }
 
 impl GenerationExecutor {
@@ -70,6 +93,7 @@ impl GenerationExecutor {
         Self {
             progress,
             wait_timeout: Some(Self::DEFAULT_WAIT_TIMEOUT),
+            time_limit: None,
         }
     }
 
@@ -80,9 +104,18 @@ impl GenerationExecutor {
         Self {
             progress,
             wait_timeout,
+            time_limit: None,
         }
     }
 
+    /// Bound the wall-clock time of the whole plan. When the limit passes, nodes still
+    /// waiting on the queue are abandoned and `execute` returns the partial result with
+    /// `timed_out` set; frames already written stay in storage.
+    pub fn with_time_limit(mut self, time_limit: Option<Duration>) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
     pub async fn execute<Q: QueueSubmitter>(
         &self,
         queue: &Q,
@@ -91,6 +124,9 @@ impl GenerationExecutor {
         plan.validate()?;
         let mut result = GenerationResult::new(plan.plan_id.clone());
         let session_id = plan.session_id.clone();
+        let deadline = self
+            .time_limit
+            .map(|limit| tokio::time::Instant::now() + limit);
 
         self.emit_envelope(
             session_id.as_deref(),
@@ -117,6 +153,10 @@ impl GenerationExecutor {
         );
 
         for (level_index, level_items) in plan.levels.iter().enumerate() {
+            if deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline) {
+                result.remaining_nodes = plan.levels[level_index..].iter().map(Vec::len).sum();
+                return Ok(self.finish_timed_out(&plan, result));
+            }
             let plan_id = plan.plan_id.clone();
             let queue_priority: Priority = plan.priority.into();
             self.emit_envelope(
@@ -131,6 +171,7 @@ impl GenerationExecutor {
                 ),
             );
 
+            let level_started = Instant::now();
             let mut generated_count = 0usize;
             let mut failed_count = 0usize;
             let mut futures = FuturesUnordered::new();
@@ -165,7 +206,28 @@ impl GenerationExecutor {
             }
 
             let mut fail_immediately_hit = false;
-            while let Some((item, outcome)) = futures.next().await {
+            let mut timed_out = false;
+            let mut peak_in_flight: Option<usize> = None;
+            let mut sample = tokio::time::interval(CONCURRENCY_SAMPLE_INTERVAL);
+            sample.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                let next = tokio::select! {
+                    biased;
+                    next = futures.next() => next,
+                    _ = sample.tick() => {
+                        if let Some(in_flight) = queue.in_flight() {
+                            peak_in_flight = Some(peak_in_flight.unwrap_or(0).max(in_flight));
+                        }
+                        continue;
+                    }
+                    _ = sleep_until_deadline(deadline) => {
+                        timed_out = true;
+                        break;
+                    }
+                };
+                let Some((item, outcome)) = next else {
+                    break;
+                };
                 match outcome {
                     Ok(frame_id) => {
                         generated_count += 1;
@@ -194,6 +256,7 @@ impl GenerationExecutor {
                         result.failures.insert(
                             item.node_id,
                             GenerationErrorDetail {
+                                path: item.path.clone(),
                                 message: err.to_string(),
                             },
                         );
@@ -223,6 +286,11 @@ impl GenerationExecutor {
                 }
             }
 
+            // Dropping the unfinished futures abandons their queue waits.
+            drop(futures);
+            let duration_ms = level_started.elapsed().as_millis() as u64;
+            // The queue counts every request it is working on, so cap at this level's size.
+            let peak_concurrency = peak_in_flight.map(|peak| peak.min(level_items.len()));
             result.total_generated += generated_count;
             result.total_failed += failed_count;
             result.level_summaries.push(LevelSummary {
@@ -230,6 +298,8 @@ impl GenerationExecutor {
                 generated_count,
                 failed_count,
                 total_count: level_items.len(),
+                duration_ms,
+                peak_concurrency,
             });
 
             self.emit_envelope(
@@ -242,10 +312,21 @@ impl GenerationExecutor {
                         generated_count,
                         failed_count,
                         total_count: level_items.len(),
+                        duration_ms,
+                        peak_concurrency,
                     },
                 ),
             );
 
+            if timed_out {
+                result.remaining_nodes = level_items.len() - generated_count - failed_count
+                    + plan.levels[level_index + 1..]
+                        .iter()
+                        .map(Vec::len)
+                        .sum::<usize>();
+                return Ok(self.finish_timed_out(&plan, result));
+            }
+
             if fail_immediately_hit {
                 self.emit_envelope(
                     session_id.as_deref(),
@@ -257,6 +338,7 @@ impl GenerationExecutor {
                             failed_level_index: None,
                             total_generated: result.total_generated,
                             total_failed: result.total_failed,
+                            failures: GenerationFailureEntry::from_report(result.failure_report()),
                         },
                     ),
                 );
@@ -278,6 +360,7 @@ impl GenerationExecutor {
                             failed_level_index: Some(level_index),
                             total_generated: result.total_generated,
                             total_failed: result.total_failed,
+                            failures: GenerationFailureEntry::from_report(result.failure_report()),
                         },
                     ),
                 );
@@ -293,6 +376,7 @@ impl GenerationExecutor {
                     plan_id: result.plan_id.clone(),
                     total_generated: result.total_generated,
                     total_failed: result.total_failed,
+                    failures: GenerationFailureEntry::from_report(result.failure_report()),
                 },
             ),
         );
@@ -300,6 +384,29 @@ impl GenerationExecutor {
         Ok(result)
     }
 
+    fn finish_timed_out(
+        &self,
+        plan: &GenerationPlan,
+        mut result: GenerationResult,
+    ) -> GenerationResult {
+        result.timed_out = true;
+        self.emit_envelope(
+            plan.session_id.as_deref(),
+            generation_failed_envelope(
+                plan.session_id.as_deref().unwrap_or(&plan.plan_id),
+                GenerationFailedEventData {
+                    plan_id: plan.plan_id.clone(),
+                    reason: "timeout".to_string(),
+                    failed_level_index: None,
+                    total_generated: result.total_generated,
+                    total_failed: result.total_failed,
+                    failures: GenerationFailureEntry::from_report(result.failure_report()),
+                },
+            ),
+        );
+        result
+    }
+
     fn wait_timeout_for_item(&self, item: &GenerationItem) -> Option<Duration> {
         if self.wait_timeout.is_some() && item.program.workflow_id().is_some() {
             return Some(Self::DEFAULT_WORKFLOW_WAIT_TIMEOUT);
@@ -316,6 +423,14 @@ impl GenerationExecutor {
     }
 }
 
+/// Resolves at `deadline`, or never when there is none.
+async fn sleep_until_deadline(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,10 +438,13 @@ mod tests {
     use crate::types::Hash;
     use parking_lot::Mutex;
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     struct MockQueue {
         outcomes: Mutex<HashMap<String, Result<FrameID, ApiError>>>,
         received_timeouts: Mutex<Vec<Option<Duration>>>,
+        delay: Option<Duration>,
+        in_flight: AtomicUsize,
     }
 
     impl MockQueue {
@@ -334,6 +452,15 @@ mod tests {
             Self {
                 outcomes: Mutex::new(outcomes),
                 received_timeouts: Mutex::new(Vec::new()),
+                delay: None,
+                in_flight: AtomicUsize::new(0),
+            }
+        }
+
+        fn with_delay(delay: Duration) -> Self {
+            Self {
+                delay: Some(delay),
+                ..Self::new(HashMap::new())
             }
         }
     }
@@ -347,11 +474,20 @@ mod tests {
             wait_timeout: Option<Duration>,
         ) -> Result<FrameID, ApiError> {
             self.received_timeouts.lock().push(wait_timeout);
+            self.in_flight.fetch_add(1, Ordering::SeqCst);
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
             self.outcomes
                 .lock()
                 .remove(&hex::encode(item.node_id))
                 .unwrap_or_else(|| Ok(Hash::from([9u8; 32])))
         }
+
+        fn in_flight(&self) -> Option<usize> {
+            Some(self.in_flight.load(Ordering::SeqCst))
+        }
     }
 
     fn item(id: u8) -> GenerationItem {
@@ -368,6 +504,13 @@ mod tests {
             frame_type: "context-writer".to_string(),
             force: false,
             program: crate::context::generation::TargetExecutionProgram::single_shot(),
+            user_prompt_override: None,
+            stream_output: false,
+            preserve_mtime: false,
+            record_provenance: false,
+            prompt_vars: Default::default(),
+            on_conflict: Default::default(),
+            expected_head: None,
         }
     }
 
@@ -387,6 +530,13 @@ mod tests {
             program: crate::context::generation::TargetExecutionProgram::workflow(
                 "docs_writer_thread_v1",
             ),
+            user_prompt_override: None,
+            stream_output: false,
+            preserve_mtime: false,
+            record_provenance: false,
+            prompt_vars: Default::default(),
+            on_conflict: Default::default(),
+            expected_head: None,
         }
     }
 
@@ -438,6 +588,51 @@ mod tests {
         assert_eq!(result.total_failed, 1);
     }
 
+    fn plan_with_one_failing_node(policy: FailurePolicy) -> (MockQueue, GenerationPlan) {
+        let mut outcomes = HashMap::new();
+        outcomes.insert(
+            hex::encode(Hash::from([2u8; 32])),
+            Err(ApiError::GenerationFailed("provider rejected".to_string())),
+        );
+        let plan = GenerationPlan {
+            levels: vec![vec![item(1), item(2), item(4)], vec![item(3)]],
+            total_nodes: 4,
+            ..plan(policy)
+        };
+        (MockQueue::new(outcomes), plan)
+    }
+
+    #[tokio::test]
+    async fn stop_on_level_failure_reports_failed_node_path_and_error() {
+        let (queue, plan) = plan_with_one_failing_node(FailurePolicy::StopOnLevelFailure);
+        let result = GenerationExecutor::new(None)
+            .execute(&queue, plan)
+            .await
+            .unwrap();
+        assert_eq!(result.total_generated, 2);
+        assert_eq!(result.level_summaries.len(), 1);
+        let report = result.failure_report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].0, "/tmp/2.txt");
+        assert!(report[0].1.contains("provider rejected"));
+    }
+
+    #[tokio::test]
+    async fn continue_policy_completes_remaining_levels_and_reports_failures() {
+        let (queue, plan) = plan_with_one_failing_node(FailurePolicy::Continue);
+        let result = GenerationExecutor::new(None)
+            .execute(&queue, plan)
+            .await
+            .unwrap();
+        assert_eq!(result.total_generated, 3);
+        assert_eq!(result.level_summaries.len(), 2);
+        assert!(result.successes.contains_key(&Hash::from([3u8; 32])));
+        let report = result.failure_report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].0, "/tmp/2.txt");
+        assert!(report[0].1.contains("provider rejected"));
+    }
+
     #[tokio::test]
     async fn executor_uses_default_wait_timeout() {
         let queue = MockQueue::new(HashMap::new());
@@ -491,4 +686,61 @@ mod tests {
         let timeouts = queue.received_timeouts.lock();
         assert_eq!(*timeouts, vec![Some(Duration::from_secs(900))]);
     }
+
+    #[tokio::test]
+    async fn level_summaries_report_duration_and_peak_concurrency() {
+        let queue = MockQueue::with_delay(Duration::from_millis(100));
+        let result = GenerationExecutor::new(None)
+            .execute(&queue, plan(FailurePolicy::Continue))
+            .await
+            .unwrap();
+
+        assert_eq!(result.level_summaries.len(), 2);
+        let first = &result.level_summaries[0];
+        assert_eq!(first.total_count, 2);
+        assert_eq!(first.generated_count, 2);
+        assert_eq!(first.peak_concurrency, Some(2));
+        assert!(first.duration_ms >= 100, "{}", first.duration_ms);
+        // Both nodes ran side by side, so the level took about one delay, not two.
+        assert!(first.duration_ms < 200, "{}", first.duration_ms);
+
+        let second = &result.level_summaries[1];
+        assert_eq!(second.total_count, 1);
+        assert_eq!(second.peak_concurrency, Some(1));
+        assert!(second.duration_ms >= 100, "{}", second.duration_ms);
+    }
+
+    #[tokio::test]
+    async fn time_limit_returns_partial_result_near_deadline() {
+        let queue = MockQueue::with_delay(Duration::from_millis(500));
+        let started = Instant::now();
+        let result = GenerationExecutor::new(None)
+            .with_time_limit(Some(Duration::from_millis(100)))
+            .execute(&queue, plan(FailurePolicy::Continue))
+            .await
+            .unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(result.timed_out);
+        assert!(elapsed >= Duration::from_millis(100), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(400), "{:?}", elapsed);
+        assert_eq!(result.total_generated, 0);
+        assert_eq!(result.remaining_nodes, 3);
+        assert_eq!(result.level_summaries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn time_limit_keeps_completed_levels_and_counts_remaining_nodes() {
+        let queue = MockQueue::with_delay(Duration::from_millis(100));
+        let result = GenerationExecutor::new(None)
+            .with_time_limit(Some(Duration::from_millis(150)))
+            .execute(&queue, plan(FailurePolicy::Continue))
+            .await
+            .unwrap();
+
+        assert!(result.timed_out);
+        assert_eq!(result.total_generated, 2);
+        assert_eq!(result.remaining_nodes, 1);
+        assert!(!result.successes.contains_key(&Hash::from([3u8; 32])));
+    }
 }
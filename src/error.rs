@@ -2,6 +2,7 @@
 
 use crate::metadata::frame_key_descriptor::FrameMetadataMutabilityClass;
 use crate::types::{FrameID, Hash, NodeID};
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Storage-related errors
@@ -24,6 +25,25 @@ pub enum StorageError {
 
     #[error("Storage I/O error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("Storage I/O error: failed to {op} {}: {source}", .path.display())]
+    IoContext {
+        path: PathBuf,
+        op: &'static str,
+        source: std::io::Error,
+    },
+}
+
+impl StorageError {
+    /// IO failure with the file and operation that failed, e.g. `("open frame store", path, e)`.
+    /// Prefer this over `From<io::Error>` wherever the path is known.
+    pub fn io_context(op: &'static str, path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        StorageError::IoContext {
+            path: path.into(),
+            op,
+            source,
+        }
+    }
 }
 
 impl Clone for StorageError {
@@ -40,6 +60,11 @@ impl Clone for StorageError {
             StorageError::IoError(err) => {
                 StorageError::IoError(std::io::Error::new(err.kind(), err.to_string()))
             }
+            StorageError::IoContext { path, op, source } => StorageError::IoContext {
+                path: path.clone(),
+                op,
+                source: std::io::Error::new(source.kind(), source.to_string()),
+            },
         }
     }
 }
@@ -153,6 +178,9 @@ pub enum ApiError {
     #[error("Provider model not found: {0}")]
     ProviderModelNotFound(String),
 
+    #[error("Provider error: circuit open for provider '{provider}' after {failures} consecutive failures")]
+    CircuitOpen { provider: String, failures: usize },
+
     #[error("Storage error: {0}")]
     StorageError(#[from] StorageError),
 
@@ -162,8 +190,25 @@ pub enum ApiError {
     #[error("Generation failed: {0}")]
     GenerationFailed(String),
 
+    #[error(
+        "Head for node {node_id} frame type '{frame_type}' changed since the plan was built (expected {expected}, found {current}); frame {frame_id} was stored but not made head"
+    )]
+    HeadConflict {
+        node_id: String,
+        frame_type: String,
+        expected: String,
+        current: String,
+        frame_id: String,
+    },
+
     #[error("Path not found in tree: {0}. Run `meld scan` to update tree or start `meld watch`.")]
     PathNotInTree(std::path::PathBuf),
+
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+
+    #[error("Workspace has no indexable files: {0}. Review ignore patterns (`meld ignore`, .gitignore), then run `meld scan`.")]
+    EmptyWorkspace(std::path::PathBuf),
 }
 
 impl Clone for ApiError {
@@ -274,10 +319,29 @@ impl Clone for ApiError {
             ApiError::ProviderModelNotFound(message) => {
                 ApiError::ProviderModelNotFound(message.clone())
             }
+            ApiError::CircuitOpen { provider, failures } => ApiError::CircuitOpen {
+                provider: provider.clone(),
+                failures: *failures,
+            },
             ApiError::StorageError(err) => ApiError::StorageError(err.clone()),
             ApiError::ConfigError(message) => ApiError::ConfigError(message.clone()),
             ApiError::GenerationFailed(message) => ApiError::GenerationFailed(message.clone()),
+            ApiError::HeadConflict {
+                node_id,
+                frame_type,
+                expected,
+                current,
+                frame_id,
+            } => ApiError::HeadConflict {
+                node_id: node_id.clone(),
+                frame_type: frame_type.clone(),
+                expected: expected.clone(),
+                current: current.clone(),
+                frame_id: frame_id.clone(),
+            },
             ApiError::PathNotInTree(path) => ApiError::PathNotInTree(path.clone()),
+            ApiError::InvalidPath(message) => ApiError::InvalidPath(message.clone()),
+            ApiError::EmptyWorkspace(path) => ApiError::EmptyWorkspace(path.clone()),
         }
     }
 }
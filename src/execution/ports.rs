@@ -456,12 +456,13 @@ impl meld_execution::NodeResolutionPort for ContextApi {
         node: Option<&str>,
         include_tombstoned: bool,
     ) -> Result<NodeID, ApiError> {
-        crate::workspace::resolve_workspace_node_id(
+        crate::workspace::resolve_workspace_node_id_with_policy(
             self,
             workspace_root,
             path,
             node,
             include_tombstoned,
+            self.strict_paths(),
         )
     }
 }
@@ -692,7 +693,7 @@ fn execution_node_record(record: NodeRecord) -> ExecutionNodeRecord<NodeID> {
         node_id: record.node_id,
         path: record.path.to_string_lossy().to_string(),
         node_kind: match record.node_type {
-            NodeType::File { .. } => ExecutionNodeKind::File,
+            NodeType::File { .. } | NodeType::Symlink { .. } => ExecutionNodeKind::File,
             NodeType::Directory => ExecutionNodeKind::Directory,
         },
         children: record.children,
@@ -4,12 +4,16 @@
 //! when a graph runtime is configured. This index remains for legacy workspace
 //! migration, graphless tests, restore compatibility, and tombstone compacting.
 
+pub mod snapshot;
+pub mod tooling;
+
 use crate::error::StorageError;
 use crate::types::{FrameID, NodeID};
 use bincode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 const HEAD_INDEX_VERSION_V1: u32 = 1;
@@ -86,6 +90,32 @@ impl HeadIndex {
         Ok(())
     }
 
+    /// Compare-and-swap form of [`HeadIndex::update_head`]: move the head to `frame_id` only
+    /// when the active head is still `expected_prior` (`None`: no active head).
+    ///
+    /// Returns `false` and leaves the index unchanged when another writer moved the head.
+    /// Callers hold the node write lock so the check and the swap are not interleaved.
+    pub fn update_head_if(
+        &mut self,
+        node_id: &NodeID,
+        frame_type: &str,
+        frame_id: &FrameID,
+        expected_prior: Option<&FrameID>,
+    ) -> Result<bool, StorageError> {
+        if self.get_head(node_id, frame_type)?.as_ref() != expected_prior {
+            return Ok(false);
+        }
+        self.update_head(node_id, frame_type, frame_id)?;
+        Ok(true)
+    }
+
+    /// Remove a head entry outright, tombstoned or not. Returns the frame it pointed at.
+    pub fn remove_head(&mut self, node_id: &NodeID, frame_type: &str) -> Option<FrameID> {
+        self.heads
+            .remove(&(*node_id, frame_type.to_string()))
+            .map(|entry| entry.frame_id)
+    }
+
     /// Tombstone all head entries for a node (all frame types).
     pub fn tombstone_heads_for_node(&mut self, node_id: &NodeID) {
         let now = std::time::SystemTime::now()
@@ -163,6 +193,19 @@ impl HeadIndex {
         node_ids.len()
     }
 
+    /// All entries, including tombstoned heads that have not been purged.
+    pub fn entries(&self) -> Vec<LegacyHeadEntry> {
+        self.heads
+            .iter()
+            .map(|((node_id, frame_type), entry)| LegacyHeadEntry {
+                node_id: *node_id,
+                frame_type: frame_type.clone(),
+                frame_id: entry.frame_id,
+                tombstoned_at: entry.tombstoned_at,
+            })
+            .collect()
+    }
+
     pub fn active_entries(&self) -> Vec<LegacyHeadEntry> {
         self.heads
             .iter()
@@ -221,12 +264,8 @@ impl HeadIndex {
         }
 
         // Read file
-        let bytes = fs::read(path).map_err(|e| {
-            StorageError::IoError(std::io::Error::other(format!(
-                "Failed to read head index from {:?}: {}",
-                path, e
-            )))
-        })?;
+        let bytes =
+            fs::read(path).map_err(|e| StorageError::io_context("read head index", path, e))?;
 
         // Try legacy V1 format (single bincode blob) first.
         if let Ok(persistence) = bincode::deserialize::<HeadIndexPersistenceV1>(&bytes) {
@@ -337,14 +376,19 @@ impl HeadIndex {
         serialized.extend_from_slice(&HEAD_INDEX_VERSION_V2.to_le_bytes());
         serialized.extend_from_slice(&payload);
 
-        // Write to temporary file (atomic write)
+        // Write and sync a temporary file (atomic, durable write)
         let temp_path = path.with_extension("bin.tmp");
-        fs::write(&temp_path, &serialized).map_err(|e| {
-            StorageError::IoError(std::io::Error::other(format!(
-                "Failed to write head index to {:?}: {}",
-                temp_path, e
-            )))
-        })?;
+        fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(&serialized)?;
+                file.sync_all()
+            })
+            .map_err(|e| {
+                StorageError::IoError(std::io::Error::other(format!(
+                    "Failed to write head index to {:?}: {}",
+                    temp_path, e
+                )))
+            })?;
 
         // Atomically rename temp file to final location
         fs::rename(&temp_path, path).map_err(|e| {
@@ -485,6 +529,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_head_if_swaps_only_from_expected_prior() {
+        let mut index = HeadIndex::new();
+        let node_id: NodeID = [1u8; 32];
+        let first: FrameID = [2u8; 32];
+        let second: FrameID = [3u8; 32];
+        let racing: FrameID = [4u8; 32];
+
+        assert!(index
+            .update_head_if(&node_id, "test", &first, None)
+            .unwrap());
+        assert!(!index
+            .update_head_if(&node_id, "test", &second, None)
+            .unwrap());
+        assert_eq!(index.get_head(&node_id, "test").unwrap(), Some(first));
+
+        index.update_head(&node_id, "test", &racing).unwrap();
+        assert!(!index
+            .update_head_if(&node_id, "test", &second, Some(&first))
+            .unwrap());
+        assert_eq!(index.get_head(&node_id, "test").unwrap(), Some(racing));
+        assert!(index
+            .update_head_if(&node_id, "test", &second, Some(&racing))
+            .unwrap());
+        assert_eq!(index.get_head(&node_id, "test").unwrap(), Some(second));
+
+        // A tombstoned head counts as no active head.
+        index.tombstone_head(&node_id, "test");
+        assert!(index
+            .update_head_if(&node_id, "test", &first, None)
+            .unwrap());
+        assert_eq!(index.get_head(&node_id, "test").unwrap(), Some(first));
+    }
+
     #[test]
     fn test_tombstone_and_restore_heads() {
         let mut index = HeadIndex::new();
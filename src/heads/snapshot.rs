@@ -0,0 +1,163 @@
+//! Named head index snapshots for `heads snapshot` and `heads restore`.
+//!
+//! A snapshot is a copy of the head index in its on-disk format, kept next to
+//! `head_index.bin` under `head_snapshots/<name>.bin`. Frames are append-only, so a snapshot
+//! only records head pointers and restoring one never rewrites frame blobs.
+
+use crate::error::{ApiError, StorageError};
+use crate::heads::HeadIndex;
+use crate::types::FrameID;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Directory under the workspace data directory that holds snapshots.
+pub const SNAPSHOT_DIR_NAME: &str = "head_snapshots";
+
+const SNAPSHOT_EXTENSION: &str = "bin";
+
+/// One saved snapshot as listed by `heads list-snapshots`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HeadSnapshotInfo {
+    pub name: String,
+    pub path: String,
+    /// Head entries recorded in the snapshot, tombstoned ones included.
+    pub head_count: usize,
+    /// Last write time of the snapshot file, in milliseconds since the Unix epoch.
+    pub created_at_ms: u64,
+}
+
+/// Directory holding the snapshots of the workspace at `workspace_root`.
+///
+/// Fails when the head index path has no parent directory, rather than falling back to a
+/// relative path that would land in the current directory.
+pub fn snapshot_dir(workspace_root: &Path) -> Result<PathBuf, ApiError> {
+    let index_path = HeadIndex::persistence_path(workspace_root);
+    index_path
+        .parent()
+        .map(|parent| parent.join(SNAPSHOT_DIR_NAME))
+        .ok_or_else(|| {
+            ApiError::ConfigError(format!(
+                "Cannot place head snapshots: head index path '{}' has no parent directory.",
+                index_path.display()
+            ))
+        })
+}
+
+/// Snapshot file for `name`. Names are limited to letters, digits, `-`, `_`, and `.` and may
+/// not start with `.`, so they always map to a file inside the snapshot directory.
+pub fn snapshot_path(workspace_root: &Path, name: &str) -> Result<PathBuf, ApiError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(ApiError::ConfigError(format!(
+            "Invalid head snapshot name '{}': use letters, digits, '-', '_', or '.', not starting with '.'.",
+            name
+        )));
+    }
+    Ok(snapshot_dir(workspace_root)?.join(format!("{}.{}", name, SNAPSHOT_EXTENSION)))
+}
+
+/// Load snapshot `name`; a missing snapshot is an error rather than an empty index.
+pub fn load_snapshot(workspace_root: &Path, name: &str) -> Result<HeadIndex, ApiError> {
+    let path = snapshot_path(workspace_root, name)?;
+    if !path.exists() {
+        return Err(ApiError::ConfigError(format!(
+            "Head snapshot '{}' not found. Run `meld heads list-snapshots` to see saved snapshots.",
+            name
+        )));
+    }
+    HeadIndex::load_from_disk(&path).map_err(ApiError::from)
+}
+
+/// Describe the snapshot file at `path`.
+pub fn snapshot_info(name: &str, path: &Path, head_count: usize) -> HeadSnapshotInfo {
+    let created_at_ms = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0);
+    HeadSnapshotInfo {
+        name: name.to_string(),
+        path: path.display().to_string(),
+        head_count,
+        created_at_ms,
+    }
+}
+
+/// Every saved snapshot, ordered by name. Files that fail to load are skipped with a warning.
+pub fn list_snapshots(workspace_root: &Path) -> Result<Vec<HeadSnapshotInfo>, ApiError> {
+    let dir = snapshot_dir(workspace_root)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| StorageError::io_context("read head snapshot directory", &dir, e))?;
+    let mut snapshots = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_EXTENSION) {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        match HeadIndex::load_from_disk(&path) {
+            Ok(index) => snapshots.push(snapshot_info(name, &path, index.heads.len())),
+            Err(err) => tracing::warn!(
+                path = %path.display(),
+                error = %err,
+                "Skipping unreadable head snapshot"
+            ),
+        }
+    }
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(snapshots)
+}
+
+/// Frames recorded by any saved snapshot, tombstoned heads included. Unlike
+/// [`list_snapshots`], an unreadable snapshot is an error, since callers use this to decide
+/// which frames are still needed.
+pub fn snapshot_frame_ids(workspace_root: &Path) -> Result<HashSet<FrameID>, ApiError> {
+    let dir = snapshot_dir(workspace_root)?;
+    let mut frame_ids = HashSet::new();
+    if !dir.exists() {
+        return Ok(frame_ids);
+    }
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| StorageError::io_context("read head snapshot directory", &dir, e))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| StorageError::io_context("read head snapshot directory", &dir, e))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_EXTENSION) {
+            continue;
+        }
+        let index = HeadIndex::load_from_disk(&path).map_err(ApiError::from)?;
+        frame_ids.extend(index.heads.values().map(|entry| entry.frame_id));
+    }
+    Ok(frame_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_names_stay_inside_the_snapshot_dir() {
+        let root = Path::new("/tmp/meld-snapshot-names");
+        let path = snapshot_path(root, "before-regen_1.2").unwrap();
+        assert_eq!(path.parent().unwrap(), snapshot_dir(root).unwrap());
+        assert_eq!(path.file_name().unwrap(), "before-regen_1.2.bin");
+
+        for name in ["", ".hidden", "../escape", "a/b", "with space"] {
+            assert!(snapshot_path(root, name).is_err(), "{:?}", name);
+        }
+    }
+}
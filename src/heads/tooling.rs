@@ -0,0 +1,29 @@
+use crate::api::ContextApi;
+use crate::cli::{
+    format_head_restore_json, format_head_restore_result, format_head_snapshot_result,
+    format_head_snapshots_json, format_head_snapshots_text, HeadsCommands,
+};
+use crate::error::ApiError;
+
+pub fn handle_cli_command(api: &ContextApi, command: &HeadsCommands) -> Result<String, ApiError> {
+    match command {
+        HeadsCommands::Snapshot { name, force } => {
+            let info = api.snapshot_heads(name, *force)?;
+            Ok(format_head_snapshot_result(&info))
+        }
+        HeadsCommands::Restore { name, format } => {
+            let result = api.restore_head_snapshot(name)?;
+            match format.as_str() {
+                "json" => format_head_restore_json(&result),
+                _ => Ok(format_head_restore_result(&result)),
+            }
+        }
+        HeadsCommands::ListSnapshots { format } => {
+            let snapshots = api.head_snapshots()?;
+            match format.as_str() {
+                "json" => format_head_snapshots_json(&snapshots),
+                _ => Ok(format_head_snapshots_text(&snapshots)),
+            }
+        }
+    }
+}
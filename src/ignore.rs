@@ -12,6 +12,12 @@
 //! ignore_list: we read .gitignore line by line and write/update a marked block
 //! in ignore_list so the list stays in sync without reading .gitignore again
 //! until it changes.
+//!
+//! Wherever .gitignore is honored, git's other ignore sources are read too:
+//! `.git/info/exclude` and the user's global excludes file (see [`git_excludes`]). An
+//! ignore_list without the .gitignore entry or block opts out of all three.
+
+pub mod git_excludes;
 
 use crate::config::xdg;
 use crate::error::ApiError;
@@ -44,11 +50,15 @@ fn gitignore_hash_path(workspace_root: &Path) -> Result<PathBuf, ApiError> {
 
 /// Read workspace .gitignore into a list of pattern strings (minimal parse: trim, skip empty and #).
 pub fn read_gitignore_patterns(workspace_root: &Path) -> Vec<String> {
-    let gitignore_path = workspace_root.join(".gitignore");
-    if !gitignore_path.exists() || !gitignore_path.is_file() {
+    read_pattern_file(&workspace_root.join(".gitignore"))
+}
+
+/// Read one gitignore-format file; a missing or unreadable file has no patterns.
+fn read_pattern_file(path: &Path) -> Vec<String> {
+    if !path.exists() || !path.is_file() {
         return Vec::new();
     }
-    let Ok(contents) = fs::read_to_string(&gitignore_path) else {
+    let Ok(contents) = fs::read_to_string(path) else {
         return Vec::new();
     };
     let mut out = Vec::new();
@@ -206,17 +216,46 @@ pub fn maybe_sync_gitignore_after_tree(
 /// to patterns from workspace .gitignore. When it exists, we use the "# .gitignore" block
 /// if present (synced from .gitignore when that file changes); otherwise a line ".gitignore"
 /// expands to the workspace .gitignore patterns. Built-in defaults are always prepended.
+/// When the list honors .gitignore (by default, the block, or the entry), `.git/info/exclude`
+/// and the global excludes file are read too, ahead of the .gitignore patterns.
 pub fn load_ignore_patterns(workspace_root: &Path) -> Result<Vec<String>, ApiError> {
-    let mut patterns: Vec<String> = BUILTIN_DEFAULTS.iter().map(|s| (*s).to_string()).collect();
+    collect_ignore_patterns(workspace_root, true)
+}
+
+/// Ignore patterns as loaded before git's exclude files were read. The absolute-path NodeID
+/// scheme (see [`crate::tree::legacy`]) was computed with these.
+pub fn load_ignore_patterns_without_git_excludes(
+    workspace_root: &Path,
+) -> Result<Vec<String>, ApiError> {
+    collect_ignore_patterns(workspace_root, false)
+}
 
+fn collect_ignore_patterns(
+    workspace_root: &Path,
+    git_excludes: bool,
+) -> Result<Vec<String>, ApiError> {
+    let mut patterns: Vec<String> = BUILTIN_DEFAULTS.iter().map(|s| (*s).to_string()).collect();
     let list_path = ignore_list_path(workspace_root)?;
 
-    if !list_path.exists() || !list_path.is_file() {
-        patterns.extend(read_gitignore_patterns(workspace_root));
-        return Ok(patterns);
+    let (list_patterns, honors_gitignore) = if !list_path.exists() || !list_path.is_file() {
+        (read_gitignore_patterns(workspace_root), true)
+    } else {
+        let contents = fs::read_to_string(&list_path).unwrap_or_default();
+        parse_ignore_list(workspace_root, &contents)
+    };
+
+    if git_excludes && honors_gitignore {
+        patterns.extend(git_excludes::read_git_exclude_patterns(workspace_root));
     }
+    patterns.extend(list_patterns);
+    Ok(patterns)
+}
 
-    let contents = fs::read_to_string(&list_path).unwrap_or_default();
+/// Patterns from ignore_list contents, and whether the list honors .gitignore through its
+/// synced block or a ".gitignore" line.
+fn parse_ignore_list(workspace_root: &Path, contents: &str) -> (Vec<String>, bool) {
+    let mut patterns = Vec::new();
+    let mut honors_gitignore = false;
     let mut in_block = false;
 
     for line in contents.lines() {
@@ -226,6 +265,7 @@ pub fn load_ignore_patterns(workspace_root: &Path) -> Result<Vec<String>, ApiErr
         }
         if line == GITIGNORE_BLOCK_START {
             in_block = true;
+            honors_gitignore = true;
             continue;
         }
         if line == GITIGNORE_BLOCK_END {
@@ -240,13 +280,14 @@ pub fn load_ignore_patterns(workspace_root: &Path) -> Result<Vec<String>, ApiErr
             continue;
         }
         if line == GITIGNORE_ENTRY {
+            honors_gitignore = true;
             patterns.extend(read_gitignore_patterns(workspace_root));
         } else {
             patterns.push(line.to_string());
         }
     }
 
-    Ok(patterns)
+    (patterns, honors_gitignore)
 }
 
 /// Read and parse the ignore list file (for list mode).
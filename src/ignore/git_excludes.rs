@@ -0,0 +1,133 @@
+//! Git's other ignore sources: `.git/info/exclude` and the user's global excludes file.
+//!
+//! Both are read whenever the ignore list honors .gitignore, even if the workspace has no
+//! .gitignore file, so scans agree with git about which files are ignored. The global file is
+//! `core.excludesFile` from the global or repository git config, or
+//! `$XDG_CONFIG_HOME/git/ignore` when that key is not set.
+
+use crate::config::xdg;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Patterns from the global excludes file, then from `.git/info/exclude`. This is git's
+/// precedence order: later sources take priority over earlier ones, and the workspace
+/// .gitignore (read after these) over both.
+pub fn read_git_exclude_patterns(workspace_root: &Path) -> Vec<String> {
+    let mut patterns = Vec::new();
+    if let Some(global) = global_excludes_file(workspace_root) {
+        patterns.extend(super::read_pattern_file(&global));
+    }
+    patterns.extend(super::read_pattern_file(
+        &workspace_root.join(".git").join("info").join("exclude"),
+    ));
+    patterns
+}
+
+/// The user's global excludes file. Config files are read in git's order, which is
+/// `$XDG_CONFIG_HOME/git/config`, then `~/.gitconfig` (or `$GIT_CONFIG_GLOBAL` instead of
+/// both), then the repository's `.git/config`. The last `core.excludesFile` wins.
+pub fn global_excludes_file(workspace_root: &Path) -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut config_files = Vec::new();
+    if let Some(global) = std::env::var_os("GIT_CONFIG_GLOBAL") {
+        config_files.push(PathBuf::from(global));
+    } else {
+        if let Ok(config_home) = xdg::config_home() {
+            config_files.push(config_home.join("git").join("config"));
+        }
+        if let Some(home) = &home {
+            config_files.push(home.join(".gitconfig"));
+        }
+    }
+    config_files.push(workspace_root.join(".git").join("config"));
+
+    let configured = config_files
+        .iter()
+        .rev()
+        .find_map(|path| read_core_excludes_file(path));
+    match configured {
+        Some(value) => Some(expand_home(&value, home.as_deref())),
+        None => xdg::config_home()
+            .ok()
+            .map(|config_home| config_home.join("git").join("ignore")),
+    }
+}
+
+/// Last `excludesFile` value in the `[core]` section of one git config file.
+fn read_core_excludes_file(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut in_core = false;
+    let mut value = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[') {
+            let name = section.split(']').next().unwrap_or_default().trim();
+            in_core = name.eq_ignore_ascii_case("core");
+            continue;
+        }
+        if !in_core {
+            continue;
+        }
+        let Some((key, raw)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim().eq_ignore_ascii_case("excludesfile") {
+            let raw = raw.trim();
+            let unquoted = raw
+                .strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix('"'))
+                .unwrap_or(raw);
+            value = Some(unquoted.to_string());
+        }
+    }
+    value.filter(|value| !value.is_empty())
+}
+
+fn expand_home(value: &str, home: Option<&Path>) -> PathBuf {
+    match (value.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reads_last_core_excludes_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = temp_dir.path().join("config");
+        fs::write(
+            &config,
+            "[user]\n\texcludesFile = not-core\n[core]\n\texcludesfile = first\n\
+             [core \"sub\"]\n\texcludesFile = sub\n[Core]\n\texcludesFile = \"~/.gitignore_global\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_core_excludes_file(&config).as_deref(),
+            Some("~/.gitignore_global")
+        );
+        assert_eq!(
+            read_core_excludes_file(&temp_dir.path().join("missing")),
+            None
+        );
+    }
+
+    #[test]
+    fn expands_home_prefix() {
+        let home = Path::new("/home/dev");
+        assert_eq!(
+            expand_home("~/.gitignore_global", Some(home)),
+            home.join(".gitignore_global")
+        );
+        assert_eq!(
+            expand_home("/etc/gitignore", Some(home)),
+            PathBuf::from("/etc/gitignore")
+        );
+    }
+}
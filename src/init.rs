@@ -5,12 +5,16 @@
 //! binary at build time and copied to XDG config directories at runtime.
 
 use crate::agent::{AgentRegistry, AgentRole, AgentStorage, XdgAgentStorage};
-use crate::config::{xdg, AgentConfig};
+use crate::config::{xdg, AgentConfig, ProviderConfig};
 use crate::error::ApiError;
+use crate::provider::CompletionOptions;
 
 pub mod summary;
+pub mod templates;
 pub mod tooling;
 
+pub use templates::{find_template, InitTemplate, DEFAULT_TEMPLATE_NAME, INIT_TEMPLATES};
+
 /// Default prompts embedded in binary at compile time
 pub const DEFAULT_PROMPTS: &[(&str, &str)] = &[
     (
@@ -52,21 +56,23 @@ pub const DEFAULT_WORKFLOW_FILES: &[(&str, &str)] = &[
 ];
 
 /// Default agent configuration data
-struct DefaultAgent {
-    id: &'static str,
-    role: AgentRole,
-    prompt_file: Option<&'static str>,
-    user_prompt_file: Option<&'static str>,
-    user_prompt_directory: Option<&'static str>,
+pub(crate) struct DefaultAgent {
+    pub(crate) id: &'static str,
+    pub(crate) role: AgentRole,
+    pub(crate) prompt_file: Option<&'static str>,
+    pub(crate) user_prompt_file: Option<&'static str>,
+    pub(crate) user_prompt_directory: Option<&'static str>,
+    pub(crate) default_provider: Option<&'static str>,
 }
 
-const DEFAULT_AGENTS: &[DefaultAgent] = &[
+pub(crate) const DEFAULT_AGENTS: &[DefaultAgent] = &[
     DefaultAgent {
         id: "reader",
         role: AgentRole::Reader,
         prompt_file: None,
         user_prompt_file: None,
         user_prompt_directory: None,
+        default_provider: None,
     },
     DefaultAgent {
         id: "code-analyzer",
@@ -74,6 +80,7 @@ const DEFAULT_AGENTS: &[DefaultAgent] = &[
         prompt_file: Some("prompts/code-analyzer.md"),
         user_prompt_file: Some("Analyze the code file at {path}. Provide a comprehensive analysis including:\n- Code structure and organization\n- Key functions and their purposes\n- Dependencies and relationships\n- Notable patterns or conventions\n- Potential issues or improvements"),
         user_prompt_directory: Some("Analyze the directory structure at {path}. Provide an overview including:\n- Directory purpose and organization\n- Key files and their roles\n- Module relationships\n- Overall architecture patterns"),
+        default_provider: None,
     },
     DefaultAgent {
         id: "docs-writer",
@@ -81,6 +88,7 @@ const DEFAULT_AGENTS: &[DefaultAgent] = &[
         prompt_file: Some("prompts/docs-writer.md"),
         user_prompt_file: Some("Generate comprehensive documentation for the code file at {path}. Include:\n- Purpose and overview\n- API documentation\n- Usage examples\n- Important notes and warnings\n- Related components"),
         user_prompt_directory: Some("Generate documentation for the directory at {path}. Include:\n- Directory purpose and structure\n- Module overview\n- Key components and their roles\n- Usage guidelines"),
+        default_provider: None,
     },
 ];
 
@@ -105,18 +113,41 @@ impl InitResult {
 /// Summary of initialization operations
 #[derive(Debug, Clone)]
 pub struct InitSummary {
+    pub template: String,
     pub prompts: InitResult,
     pub workflows: InitResult,
     pub agents: InitResult,
+    pub providers: InitResult,
     pub validation: ValidationSummary,
 }
 
 /// Preview of what would be initialized
 #[derive(Debug, Clone)]
 pub struct InitPreview {
+    pub template: String,
     pub prompts: Vec<String>,
     pub workflows: Vec<String>,
     pub agents: Vec<String>,
+    pub providers: Vec<String>,
+}
+
+/// Health of an existing installation, as reported by `meld init --check`
+#[derive(Debug, Clone)]
+pub struct InitCheck {
+    pub template: String,
+    /// Missing directories, prompts, workflows, agent configs, and provider stub
+    pub missing: Vec<String>,
+    /// Agent configs present but not loadable as a set
+    pub broken: Vec<String>,
+    pub validation: ValidationSummary,
+}
+
+impl InitCheck {
+    pub fn is_healthy(&self) -> bool {
+        self.missing.is_empty()
+            && self.broken.is_empty()
+            && self.validation.results.iter().all(|(_, valid, _)| *valid)
+    }
 }
 
 /// Validation summary for initialized agents
@@ -127,10 +158,18 @@ pub struct ValidationSummary {
 
 /// Initialize all default prompts
 pub fn initialize_prompts(force: bool) -> Result<InitResult, ApiError> {
+    initialize_template_prompts(find_template(DEFAULT_TEMPLATE_NAME)?, force)
+}
+
+/// Initialize the prompts of one template
+pub fn initialize_template_prompts(
+    template: &InitTemplate,
+    force: bool,
+) -> Result<InitResult, ApiError> {
     let prompts_dir = xdg::prompts_dir()?;
     let mut result = InitResult::new();
 
-    for (filename, content) in DEFAULT_PROMPTS {
+    for (filename, content) in template.prompts {
         let prompt_path = prompts_dir.join(filename);
 
         if prompt_path.exists() && !force {
@@ -157,10 +196,18 @@ pub fn initialize_prompts(force: bool) -> Result<InitResult, ApiError> {
 
 /// Initialize all default agents
 pub fn initialize_agents(force: bool) -> Result<InitResult, ApiError> {
+    initialize_template_agents(find_template(DEFAULT_TEMPLATE_NAME)?, force)
+}
+
+/// Initialize the agents of one template
+pub fn initialize_template_agents(
+    template: &InitTemplate,
+    force: bool,
+) -> Result<InitResult, ApiError> {
     let agents_dir = XdgAgentStorage::new().agents_dir()?;
     let mut result = InitResult::new();
 
-    for agent in DEFAULT_AGENTS {
+    for agent in template.agents {
         let config_path = agents_dir.join(format!("{}.toml", agent.id));
 
         if config_path.exists() && !force {
@@ -175,6 +222,9 @@ pub fn initialize_agents(force: bool) -> Result<InitResult, ApiError> {
             system_prompt: None,
             system_prompt_path: agent.prompt_file.map(|s| s.to_string()),
             workflow_id: None,
+            default_provider: agent.default_provider.map(|s| s.to_string()),
+            frame_type_per_node_type: false,
+            capabilities: Vec::new(),
             metadata: Default::default(),
         };
 
@@ -221,6 +271,49 @@ pub fn initialize_agents(force: bool) -> Result<InitResult, ApiError> {
     Ok(result)
 }
 
+/// Write the provider stub of one template, if it has one. An existing provider config with
+/// the same name is kept unless `force` is set.
+pub fn initialize_template_provider(
+    template: &InitTemplate,
+    force: bool,
+) -> Result<InitResult, ApiError> {
+    let mut result = InitResult::new();
+    let Some(provider) = &template.provider else {
+        return Ok(result);
+    };
+
+    let config_path = xdg::providers_dir()?.join(format!("{}.toml", provider.name));
+    if config_path.exists() && !force {
+        result.skipped.push(provider.name.to_string());
+        return Ok(result);
+    }
+
+    let provider_config = ProviderConfig {
+        provider_name: Some(provider.name.to_string()),
+        provider_type: provider.provider_type,
+        model: provider.model.to_string(),
+        api_key: None,
+        endpoint: None,
+        default_options: CompletionOptions::default(),
+    };
+    match toml::to_string_pretty(&provider_config) {
+        Ok(toml_content) => match std::fs::write(&config_path, toml_content) {
+            Ok(_) => result.created.push(provider.name.to_string()),
+            Err(e) => result.errors.push(format!(
+                "Failed to write provider config {}: {}",
+                config_path.display(),
+                e
+            )),
+        },
+        Err(e) => result.errors.push(format!(
+            "Failed to serialize provider config for {}: {}",
+            provider.name, e
+        )),
+    }
+
+    Ok(result)
+}
+
 /// Initialize default workflow profiles and prompt assets.
 pub fn initialize_workflows(force: bool) -> Result<InitResult, ApiError> {
     let workflow_dir = crate::config::WorkflowConfig::default().resolve_user_profile_dir()?;
@@ -264,44 +357,62 @@ pub fn initialize_workflows(force: bool) -> Result<InitResult, ApiError> {
     Ok(result)
 }
 
-/// Initialize all default agents and prompts
+/// Initialize the default template: prompts, workflows, and agents
 pub fn initialize_all(force: bool) -> Result<InitSummary, ApiError> {
+    initialize_template(DEFAULT_TEMPLATE_NAME, force)
+}
+
+/// Initialize a named template: its prompts, agents, and provider stub, plus default workflows
+pub fn initialize_template(name: &str, force: bool) -> Result<InitSummary, ApiError> {
+    let template = find_template(name)?;
+
     // Ensure all XDG directories exist
     XdgAgentStorage::new().agents_dir()?;
     xdg::providers_dir()?;
     xdg::prompts_dir()?;
     crate::config::WorkflowConfig::default().resolve_user_profile_dir()?;
 
-    // Initialize prompts first
-    let prompts_result = initialize_prompts(force)?;
+    // Initialize prompts and providers first so agents can reference them
+    let prompts_result = initialize_template_prompts(template, force)?;
     let workflows_result = initialize_workflows(force)?;
+    let providers_result = initialize_template_provider(template, force)?;
 
     // Initialize agents
-    let agents_result = initialize_agents(force)?;
+    let agents_result = initialize_template_agents(template, force)?;
 
     // Validate initialization
-    let validation = validate_initialization()?;
+    let validation = validate_agents(&template.agent_ids())?;
 
     Ok(InitSummary {
+        template: template.name.to_string(),
         prompts: prompts_result,
         workflows: workflows_result,
         agents: agents_result,
+        providers: providers_result,
         validation,
     })
 }
 
 /// List what would be initialized without actually creating files
 pub fn list_initialization() -> Result<InitPreview, ApiError> {
+    list_template_initialization(DEFAULT_TEMPLATE_NAME)
+}
+
+/// List what a named template would initialize without actually creating files
+pub fn list_template_initialization(name: &str) -> Result<InitPreview, ApiError> {
+    let template = find_template(name)?;
     let prompts_dir = xdg::prompts_dir()?;
     let workflows_dir = crate::config::WorkflowConfig::default().resolve_user_profile_dir()?;
     let agents_dir = XdgAgentStorage::new().agents_dir()?;
+    let providers_dir = xdg::providers_dir()?;
 
     let mut prompts = Vec::new();
     let mut workflows = Vec::new();
     let mut agents = Vec::new();
+    let mut providers = Vec::new();
 
     // Check prompts
-    for (filename, _) in DEFAULT_PROMPTS {
+    for (filename, _) in template.prompts {
         let prompt_path = prompts_dir.join(filename);
         if !prompt_path.exists() {
             prompts.push(filename.to_string());
@@ -316,29 +427,128 @@ pub fn list_initialization() -> Result<InitPreview, ApiError> {
     }
 
     // Check agents
-    for agent in DEFAULT_AGENTS {
+    for agent in template.agents {
         let config_path = agents_dir.join(format!("{}.toml", agent.id));
         if !config_path.exists() {
             agents.push(agent.id.to_string());
         }
     }
 
+    // Check provider stub
+    if let Some(provider) = &template.provider {
+        let config_path = providers_dir.join(format!("{}.toml", provider.name));
+        if !config_path.exists() {
+            providers.push(provider.name.to_string());
+        }
+    }
+
     Ok(InitPreview {
+        template: template.name.to_string(),
         prompts,
         workflows,
         agents,
+        providers,
     })
 }
 
-/// Validate all initialized agents
+/// Check the default template's installation without creating anything
+pub fn check_initialization() -> Result<InitCheck, ApiError> {
+    check_template_initialization(DEFAULT_TEMPLATE_NAME)
+}
+
+/// Check that everything a named template initializes is present and valid.
+///
+/// Unlike [`list_template_initialization`], this neither creates the XDG directories nor
+/// assumes a fresh setup: each existing agent config is parsed and validated, prompt files
+/// included, and every missing piece is reported by path.
+pub fn check_template_initialization(name: &str) -> Result<InitCheck, ApiError> {
+    let template = find_template(name)?;
+    let meld_dir = xdg::config_home()?.join("meld");
+    let prompts_dir = meld_dir.join("prompts");
+    let agents_dir = meld_dir.join("agents");
+    let providers_dir = meld_dir.join("providers");
+    let workflows_dir = crate::config::WorkflowConfig::default().resolve_user_profile_dir()?;
+
+    let mut missing = Vec::new();
+    for dir in [&agents_dir, &prompts_dir, &providers_dir, &workflows_dir] {
+        if !dir.is_dir() {
+            missing.push(format!("directory {}", dir.display()));
+        }
+    }
+    for (filename, _) in template.prompts {
+        let prompt_path = prompts_dir.join(filename);
+        if !prompt_path.is_file() {
+            missing.push(format!("prompt {} ({})", filename, prompt_path.display()));
+        }
+    }
+    for (relative_path, _) in DEFAULT_WORKFLOW_FILES {
+        let workflow_path = workflows_dir.join(relative_path);
+        if !workflow_path.is_file() {
+            missing.push(format!(
+                "workflow {} ({})",
+                relative_path,
+                workflow_path.display()
+            ));
+        }
+    }
+    if let Some(provider) = &template.provider {
+        let config_path = providers_dir.join(format!("{}.toml", provider.name));
+        if !config_path.is_file() {
+            missing.push(format!(
+                "provider {} ({})",
+                provider.name,
+                config_path.display()
+            ));
+        }
+    }
+
+    let mut present = Vec::new();
+    for agent in template.agents {
+        let config_path = agents_dir.join(format!("{}.toml", agent.id));
+        if config_path.is_file() {
+            present.push(agent.id);
+        } else {
+            missing.push(format!("agent {} ({})", agent.id, config_path.display()));
+        }
+    }
+
+    let mut broken = Vec::new();
+    let validation = if present.is_empty() {
+        ValidationSummary {
+            results: Vec::new(),
+        }
+    } else {
+        match validate_agents(&present) {
+            Ok(validation) => validation,
+            Err(e) => {
+                broken.push(e.to_string());
+                ValidationSummary {
+                    results: Vec::new(),
+                }
+            }
+        }
+    };
+
+    Ok(InitCheck {
+        template: template.name.to_string(),
+        missing,
+        broken,
+        validation,
+    })
+}
+
+/// Validate all initialized default agents
 pub fn validate_initialization() -> Result<ValidationSummary, ApiError> {
+    validate_agents(&find_template(DEFAULT_TEMPLATE_NAME)?.agent_ids())
+}
+
+fn validate_agents(agent_ids: &[&str]) -> Result<ValidationSummary, ApiError> {
     let mut registry = AgentRegistry::new();
     registry.load_from_xdg()?;
 
-    let agent_ids = ["reader", "code-analyzer", "docs-writer"];
     let mut results = Vec::new();
 
-    for agent_id in &agent_ids {
+    for agent_id in agent_ids {
         match registry.validate_agent(agent_id) {
             Ok(validation_result) => {
                 let is_valid = validation_result.is_valid();
@@ -397,6 +607,9 @@ mod tests {
                 system_prompt: None,
                 system_prompt_path: agent.prompt_file.map(|s| s.to_string()),
                 workflow_id: None,
+                default_provider: None,
+                frame_type_per_node_type: false,
+                capabilities: Vec::new(),
                 metadata: Default::default(),
             };
 
@@ -421,4 +634,27 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_template_agents_reference_embedded_prompts() {
+        for template in INIT_TEMPLATES {
+            assert!(
+                !template.agents.is_empty(),
+                "{} has no agents",
+                template.name
+            );
+            for agent in template.agents {
+                if let Some(prompt_file) = agent.prompt_file {
+                    let filename = prompt_file.trim_start_matches("prompts/");
+                    assert!(
+                        template.prompt_files().contains(&filename),
+                        "{} agent {} references missing prompt {}",
+                        template.name,
+                        agent.id,
+                        prompt_file
+                    );
+                }
+            }
+        }
+    }
 }
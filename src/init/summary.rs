@@ -5,6 +5,7 @@ use crate::telemetry::summary::TypedSummaryEvent;
 pub fn command(
     force: bool,
     list_only: bool,
+    check_only: bool,
     ok: bool,
     duration_ms: u128,
     error: Option<&str>,
@@ -14,6 +15,7 @@ pub fn command(
         json!({
             "force": force,
             "list_only": list_only,
+            "check_only": check_only,
             "ok": ok,
             "duration_ms": duration_ms,
             "error": error,
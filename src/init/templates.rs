@@ -0,0 +1,148 @@
+//! Named init templates: preset agent, prompt, and provider bundles for `meld init --template`.
+//!
+//! Template prompts are embedded in the binary at build time, like the default prompts.
+
+use super::{DefaultAgent, DEFAULT_AGENTS, DEFAULT_PROMPTS};
+use crate::agent::AgentRole;
+use crate::config::ProviderType;
+use crate::error::ApiError;
+
+/// Template used when `meld init` runs without `--template`.
+pub const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+/// Provider config written by a template, unless one with the same name exists.
+pub struct TemplateProvider {
+    pub name: &'static str,
+    pub provider_type: ProviderType,
+    pub model: &'static str,
+}
+
+/// A named bundle of prompts, agents, and an optional provider stub.
+pub struct InitTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub(crate) prompts: &'static [(&'static str, &'static str)],
+    pub(crate) agents: &'static [DefaultAgent],
+    pub provider: Option<TemplateProvider>,
+}
+
+impl InitTemplate {
+    /// Agent IDs this template creates, in creation order.
+    pub fn agent_ids(&self) -> Vec<&'static str> {
+        self.agents.iter().map(|agent| agent.id).collect()
+    }
+
+    /// Prompt file names this template writes to the prompts directory.
+    pub fn prompt_files(&self) -> Vec<&'static str> {
+        self.prompts.iter().map(|(filename, _)| *filename).collect()
+    }
+}
+
+const RUST_PROJECT_PROMPTS: &[(&str, &str)] = &[
+    (
+        "rust-code-analyzer.md",
+        include_str!("../../prompts/templates/rust-project/rust-code-analyzer.md"),
+    ),
+    (
+        "rust-docs-writer.md",
+        include_str!("../../prompts/templates/rust-project/rust-docs-writer.md"),
+    ),
+];
+
+const RUST_PROJECT_AGENTS: &[DefaultAgent] = &[
+    DefaultAgent {
+        id: "reader",
+        role: AgentRole::Reader,
+        prompt_file: None,
+        user_prompt_file: None,
+        user_prompt_directory: None,
+        default_provider: None,
+    },
+    DefaultAgent {
+        id: "code-analyzer",
+        role: AgentRole::Writer,
+        prompt_file: Some("prompts/rust-code-analyzer.md"),
+        user_prompt_file: Some("Analyze the Rust source file at {path}. Cover:\n- Module purpose and public items\n- Key types, traits, and impls\n- Error handling and panic paths\n- Unsafe code and concurrency concerns\n- Potential issues or improvements"),
+        user_prompt_directory: Some("Analyze the Rust module directory at {path}. Cover:\n- Crate or module hierarchy\n- Entry points and re-exports\n- Dependencies between child modules\n- Shared types and error handling"),
+        default_provider: None,
+    },
+    DefaultAgent {
+        id: "docs-writer",
+        role: AgentRole::Writer,
+        prompt_file: Some("prompts/rust-docs-writer.md"),
+        user_prompt_file: Some("Write rustdoc-style documentation for the Rust source file at {path}. Include:\n- Summary of the module\n- Public API with errors and panics\n- Important invariants"),
+        user_prompt_directory: Some("Write documentation for the Rust module directory at {path}. Include:\n- Module inventory\n- How the modules fit together\n- Key public types and entry points"),
+        default_provider: None,
+    },
+];
+
+const DOCS_ONLY_PROMPTS: &[(&str, &str)] = &[(
+    "docs-writer.md",
+    include_str!("../../prompts/docs-writer.md"),
+)];
+
+const DOCS_ONLY_AGENTS: &[DefaultAgent] = &[
+    DefaultAgent {
+        id: "reader",
+        role: AgentRole::Reader,
+        prompt_file: None,
+        user_prompt_file: None,
+        user_prompt_directory: None,
+        default_provider: None,
+    },
+    DefaultAgent {
+        id: "docs-writer",
+        role: AgentRole::Writer,
+        prompt_file: Some("prompts/docs-writer.md"),
+        user_prompt_file: Some("Generate comprehensive documentation for the code file at {path}. Include:\n- Purpose and overview\n- API documentation\n- Usage examples\n- Important notes and warnings\n- Related components"),
+        user_prompt_directory: Some("Generate documentation for the directory at {path}. Include:\n- Directory purpose and structure\n- Module overview\n- Key components and their roles\n- Usage guidelines"),
+        default_provider: Some("local-ollama"),
+    },
+];
+
+/// Built-in templates, listed by `meld init --list-templates`.
+pub const INIT_TEMPLATES: &[InitTemplate] = &[
+    InitTemplate {
+        name: DEFAULT_TEMPLATE_NAME,
+        description: "Reader, code-analyzer, and docs-writer agents with general prompts",
+        prompts: DEFAULT_PROMPTS,
+        agents: DEFAULT_AGENTS,
+        provider: None,
+    },
+    InitTemplate {
+        name: "rust-project",
+        description: "Reader, code-analyzer, and docs-writer agents tuned for Rust crates",
+        prompts: RUST_PROJECT_PROMPTS,
+        agents: RUST_PROJECT_AGENTS,
+        provider: None,
+    },
+    InitTemplate {
+        name: "docs-only",
+        description: "Reader and docs-writer agents with a local Ollama provider stub",
+        prompts: DOCS_ONLY_PROMPTS,
+        agents: DOCS_ONLY_AGENTS,
+        provider: Some(TemplateProvider {
+            name: "local-ollama",
+            provider_type: ProviderType::Ollama,
+            model: "llama3.1",
+        }),
+    },
+];
+
+/// Look up a built-in template by name.
+pub fn find_template(name: &str) -> Result<&'static InitTemplate, ApiError> {
+    INIT_TEMPLATES
+        .iter()
+        .find(|template| template.name == name)
+        .ok_or_else(|| {
+            ApiError::ConfigError(format!(
+                "Unknown init template '{}'. Available templates: {}",
+                name,
+                INIT_TEMPLATES
+                    .iter()
+                    .map(|template| template.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+}
@@ -1,12 +1,34 @@
-use crate::cli::{format_init_preview, format_init_summary};
+use crate::cli::{
+    format_init_check, format_init_preview, format_init_summary, format_init_templates,
+};
 use crate::error::ApiError;
+use crate::init::DEFAULT_TEMPLATE_NAME;
 
-pub fn handle_cli_command(force: bool, list: bool) -> Result<String, ApiError> {
+pub fn handle_cli_command(
+    force: bool,
+    list: bool,
+    check: bool,
+    template: Option<&str>,
+    list_templates: bool,
+) -> Result<String, ApiError> {
+    if list_templates {
+        return Ok(format_init_templates(crate::init::INIT_TEMPLATES));
+    }
+    let template = template.unwrap_or(DEFAULT_TEMPLATE_NAME);
+    if check {
+        let report = crate::init::check_template_initialization(template)?;
+        let output = format_init_check(&report);
+        return if report.is_healthy() {
+            Ok(output)
+        } else {
+            Err(ApiError::ConfigError(output))
+        };
+    }
     if list {
-        let preview = crate::init::list_initialization()?;
+        let preview = crate::init::list_template_initialization(template)?;
         Ok(format_init_preview(&preview))
     } else {
-        let summary = crate::init::initialize_all(force)?;
+        let summary = crate::init::initialize_template(template, force)?;
         Ok(format_init_summary(&summary, force))
     }
 }
@@ -18,6 +18,7 @@ use crate::task::{
     TASK_EXPANSION_REQUEST_ARTIFACT_TYPE_ID, TASK_EXPANSION_SCHEMA_VERSION,
     TASK_EXPANSION_TEMPLATE_ARTIFACT_TYPE_ID, TRAVERSAL_PREREQUISITE_EXPANSION_KIND,
 };
+use crate::types::{HexHash, NodeID};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::collections::{BTreeMap, HashSet};
@@ -35,7 +36,7 @@ impl MerkleTraversalCapability {
         format!("{invocation_id}::{output_slot_id}")
     }
 
-    fn parse_node_id(payload: &CapabilityInvocationPayload) -> Result<[u8; 32], ApiError> {
+    fn parse_node_id(payload: &CapabilityInvocationPayload) -> Result<NodeID, ApiError> {
         let input = payload
             .supplied_inputs
             .iter()
@@ -59,19 +60,12 @@ impl MerkleTraversalCapability {
                     payload.invocation_id
                 ))
             })?;
-        let bytes = hex::decode(node_hex).map_err(|err| {
-            ApiError::ConfigError(format!("Invalid node hex '{}': {}", node_hex, err))
-        })?;
-        if bytes.len() != 32 {
-            return Err(ApiError::ConfigError(format!(
-                "Invalid node hex '{}' length '{}'",
-                node_hex,
-                bytes.len()
-            )));
-        }
-        let mut node_id = [0u8; 32];
-        node_id.copy_from_slice(&bytes);
-        Ok(node_id)
+        node_hex
+            .parse::<HexHash>()
+            .map(NodeID::from)
+            .map_err(|err| {
+                ApiError::ConfigError(format!("Invalid node hex '{}': {}", node_hex, err))
+            })
     }
 
     fn parse_strategy(
@@ -12,7 +12,7 @@ use crate::task::contracts::{
     TaskInitSlotSpec,
 };
 use crate::task::expansion::{CompiledTaskDelta, TaskExpansionRequest};
-use crate::types::NodeID;
+use crate::types::{HexHash, NodeID};
 use crate::workspace::publish::{publish_filter_dependency, publish_filter_instance_id};
 use serde_json::{json, Value};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -518,19 +518,10 @@ fn node_id_prefix(node_id_hex: &str) -> Result<String, ApiError> {
 }
 
 fn decode_node_id(value: &str) -> Result<NodeID, ApiError> {
-    let bytes = hex::decode(value).map_err(|err| {
-        ApiError::ConfigError(format!("Invalid node id hex '{}': {}", value, err))
-    })?;
-    if bytes.len() != 32 {
-        return Err(ApiError::ConfigError(format!(
-            "Invalid node id hex '{}' length '{}'",
-            value,
-            bytes.len()
-        )));
-    }
-    let mut node_id = [0u8; 32];
-    node_id.copy_from_slice(&bytes);
-    Ok(node_id)
+    value
+        .parse::<HexHash>()
+        .map(NodeID::from)
+        .map_err(|err| ApiError::ConfigError(format!("Invalid node id hex '{}': {}", value, err)))
 }
 
 fn binding(binding_id: &str, value: Value) -> BoundBindingValue {
@@ -10,3 +10,15 @@ pub(crate) mod owned_frame_metadata_keys;
 pub mod prompt_link_contract;
 
 pub use frame_types::FrameMetadata;
+
+use serde::Serializer;
+use std::collections::{BTreeMap, HashMap};
+
+/// Serialize a `HashMap`-backed metadata map with its keys in sorted order, so JSON output and
+/// exports of the same metadata are byte-identical across runs.
+pub(crate) fn serialize_sorted_map<S: Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
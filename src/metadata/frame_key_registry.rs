@@ -12,18 +12,24 @@ pub use crate::metadata::frame_key_descriptor::{
 
 pub use context_keys::{
     FORBIDDEN_KEY_CONTEXT, FORBIDDEN_KEY_RAW_CONTEXT, FORBIDDEN_KEY_RAW_PROMPT, KEY_AGENT_ID,
-    KEY_DELETED, KEY_PROMPT,
+    KEY_DELETED, KEY_GENERATION_DURATION_MS, KEY_PROMPT, KEY_PROVIDER_NAME, KEY_RETRY_COUNT,
+    KEY_SOURCE_MTIME, PROVENANCE_KEYS,
 };
 pub use owned_keys::{KEY_CONTEXT_DIGEST, KEY_PROMPT_DIGEST, KEY_PROMPT_LINK_ID};
-pub use provider_keys::{KEY_MODEL, KEY_PROVIDER, KEY_PROVIDER_TYPE};
+pub use provider_keys::{KEY_MODEL, KEY_PROVIDER, KEY_PROVIDER_TYPE, KEY_TRUNCATED};
 
 const FRAME_METADATA_KEY_REGISTRY: &[FrameMetadataKeyDescriptor] = &[
     context_keys::DESCRIPTOR_AGENT_ID,
     provider_keys::DESCRIPTOR_PROVIDER,
     provider_keys::DESCRIPTOR_MODEL,
     provider_keys::DESCRIPTOR_PROVIDER_TYPE,
+    provider_keys::DESCRIPTOR_TRUNCATED,
     context_keys::DESCRIPTOR_PROMPT,
     context_keys::DESCRIPTOR_DELETED,
+    context_keys::DESCRIPTOR_SOURCE_MTIME,
+    context_keys::DESCRIPTOR_GENERATION_DURATION_MS,
+    context_keys::DESCRIPTOR_RETRY_COUNT,
+    context_keys::DESCRIPTOR_PROVIDER_NAME,
     owned_keys::DESCRIPTOR_PROMPT_DIGEST,
     owned_keys::DESCRIPTOR_CONTEXT_DIGEST,
     owned_keys::DESCRIPTOR_PROMPT_LINK_ID,
@@ -60,8 +66,13 @@ mod tests {
             KEY_PROVIDER,
             KEY_MODEL,
             KEY_PROVIDER_TYPE,
+            KEY_TRUNCATED,
             KEY_PROMPT,
             KEY_DELETED,
+            KEY_SOURCE_MTIME,
+            KEY_GENERATION_DURATION_MS,
+            KEY_RETRY_COUNT,
+            KEY_PROVIDER_NAME,
             KEY_PROMPT_DIGEST,
             KEY_CONTEXT_DIGEST,
             KEY_PROMPT_LINK_ID,
@@ -157,6 +168,8 @@ mod tests {
             KEY_PROVIDER,
             KEY_MODEL,
             KEY_PROVIDER_TYPE,
+            KEY_TRUNCATED,
+            KEY_SOURCE_MTIME,
             KEY_PROMPT_DIGEST,
             KEY_CONTEXT_DIGEST,
             KEY_PROMPT_LINK_ID,
@@ -1,14 +1,16 @@
 //! Frame metadata domain types.
 
-use crate::metadata::frame_key_registry::is_key_visible_by_default;
+use crate::metadata::frame_key_registry::{is_key_visible_by_default, PROVENANCE_KEYS};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::ops::{Deref, DerefMut};
 
-/// Frame metadata contract type.
+/// Frame metadata contract type. Keys serialize in sorted order.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(transparent)]
-pub struct FrameMetadata(HashMap<String, String>);
+pub struct FrameMetadata(
+    #[serde(serialize_with = "crate::metadata::serialize_sorted_map")] HashMap<String, String>,
+);
 
 impl FrameMetadata {
     pub fn new() -> Self {
@@ -86,12 +88,24 @@ pub fn project_visible_metadata(metadata: &FrameMetadata) -> VisibleFrameMetadat
         .collect()
 }
 
+/// [`project_visible_metadata`] plus the generation provenance keys, for `--include-provenance`.
+pub fn project_metadata_with_provenance(metadata: &FrameMetadata) -> VisibleFrameMetadata {
+    let mut projected = project_visible_metadata(metadata);
+    for key in PROVENANCE_KEYS {
+        if let Some(value) = metadata.get(key) {
+            projected.insert(key.to_string(), value.clone());
+        }
+    }
+    projected
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::agent::profile::metadata_types::AgentMetadata;
     use crate::metadata::frame_key_registry::{
         FORBIDDEN_KEY_RAW_PROMPT, KEY_AGENT_ID, KEY_DELETED, KEY_PROMPT, KEY_PROMPT_DIGEST,
+        KEY_PROVIDER_NAME, KEY_RETRY_COUNT,
     };
     use crate::store::node_metadata::NodeMetadata;
     use std::any::TypeId;
@@ -125,4 +139,64 @@ mod tests {
         assert!(!projected.contains_key(KEY_PROMPT));
         assert!(!projected.contains_key(FORBIDDEN_KEY_RAW_PROMPT));
     }
+
+    #[test]
+    fn frame_json_is_stable_with_sorted_metadata_keys() {
+        let keys = [
+            "provider",
+            "agent_id",
+            "model",
+            "deleted",
+            "prompt_digest",
+            "zeta",
+        ];
+        let frame_with = |ordered_keys: Vec<&str>| {
+            let mut metadata = FrameMetadata::new();
+            for key in ordered_keys {
+                metadata.insert(key.to_string(), format!("{}-value", key));
+            }
+            let mut frame = crate::context::frame::Frame::new(
+                crate::context::frame::Basis::Node([3u8; 32]),
+                b"content".to_vec(),
+                "summary".to_string(),
+                "agent".to_string(),
+                metadata,
+            )
+            .unwrap();
+            frame.timestamp = std::time::UNIX_EPOCH;
+            frame
+        };
+
+        let first = serde_json::to_string(&frame_with(keys.to_vec())).unwrap();
+        let second =
+            serde_json::to_string(&frame_with(keys.iter().rev().copied().collect())).unwrap();
+        assert_eq!(first, second);
+
+        let metadata_json = &first[first.find("\"metadata\":").unwrap()..];
+        let mut sorted = keys.to_vec();
+        sorted.sort();
+        let positions: Vec<usize> = sorted
+            .iter()
+            .map(|key| metadata_json.find(&format!("\"{}\":", key)).unwrap())
+            .collect();
+        assert!(
+            positions.windows(2).all(|pair| pair[0] < pair[1]),
+            "metadata keys are not sorted: {}",
+            metadata_json
+        );
+    }
+
+    #[test]
+    fn provenance_projection_adds_only_provenance_keys() {
+        let mut metadata = FrameMetadata::new();
+        metadata.insert(KEY_AGENT_ID.to_string(), "writer".to_string());
+        metadata.insert(KEY_PROMPT_DIGEST.to_string(), "digest".to_string());
+        metadata.insert(KEY_RETRY_COUNT.to_string(), "1".to_string());
+        metadata.insert(KEY_PROVIDER_NAME.to_string(), "local".to_string());
+
+        assert!(!project_visible_metadata(&metadata).contains_key(KEY_RETRY_COUNT));
+        let projected = project_metadata_with_provenance(&metadata);
+        let keys: Vec<&str> = projected.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["prompt_digest", "provider_name", "retry_count"]);
+    }
 }
@@ -22,15 +22,18 @@ pub mod diagnostics;
 pub mod executor;
 pub(crate) mod frame_metadata_keys;
 pub mod generation;
+pub mod latency;
 pub mod profile;
 pub mod storage;
+pub mod streaming;
 pub mod summary;
 pub mod tooling;
 
 pub use crate::execution::{ProviderExecutionBinding, ProviderRuntimeOverrides};
+pub use clients::ProviderHttpClientPool;
 pub use meld_execution::generation::{
     ChatMessage, CompletionOptions, CompletionResponse, GeneratedFrameMetadataInput, MessageRole,
-    PromptAssemblyOutput, TokenUsage,
+    PromptAssemblyOutput, TokenUsage, FINISH_REASON_LENGTH,
 };
 pub use profile::{ProviderConfig, ProviderType, ValidationResult};
 
@@ -45,6 +48,7 @@ pub enum ModelProvider {
     Anthropic {
         model: String,
         api_key: String,
+        base_url: Option<String>, // Default: https://api.anthropic.com/v1
     },
     Ollama {
         model: String,
@@ -55,10 +59,24 @@ pub enum ModelProvider {
         endpoint: String, // Full endpoint URL (e.g., http://localhost:8080/v1)
         api_key: Option<String>,
     },
+    Mock {
+        model: String,
+    },
 }
 
 /// Streaming completion type
-pub type CompletionStream = Pin<Box<dyn Stream<Item = Result<String, ApiError>> + Send>>;
+pub type CompletionStream =
+    Pin<Box<dyn Stream<Item = Result<streaming::StreamChunk, ApiError>> + Send>>;
+
+/// Models a provider reports through its model listing endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelListing {
+    /// Model identifiers as the provider names them.
+    Listed(Vec<String>),
+    /// The provider answered but cannot enumerate models, so availability is not checked.
+    /// Carries the reason shown to the user.
+    Unlisted(String),
+}
 
 /// Model provider client trait
 #[async_trait]
@@ -77,6 +95,12 @@ pub trait ModelProviderClient: Send + Sync {
         options: CompletionOptions,
     ) -> Result<CompletionStream, ApiError>;
 
+    /// Whether [`ModelProviderClient::stream`] is implemented. Callers that prefer streaming
+    /// fall back to [`ModelProviderClient::complete`] when this is false.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
     /// Get the provider name
     fn provider_name(&self) -> &str;
 
@@ -84,7 +108,14 @@ pub trait ModelProviderClient: Send + Sync {
     fn model_name(&self) -> &str;
 
     /// List available models from the provider
-    async fn list_models(&self) -> Result<Vec<String>, ApiError>;
+    async fn list_models(&self) -> Result<ModelListing, ApiError>;
+
+    /// Whether `listed`, a name from [`ModelProviderClient::list_models`], serves requests for
+    /// `model` as configured. Providers whose listings use other spellings of the same model
+    /// (tags, aliases) override this.
+    fn lists_model(&self, listed: &str, model: &str) -> bool {
+        listed == model
+    }
 }
 
 // OpenAI-compatible API request/response structures
@@ -159,7 +190,7 @@ fn merge_additional_json(request_body: &mut Value, additional_json: &BTreeMap<St
 }
 
 // Helper function to map HTTP errors to ApiError
-fn map_http_error(error: reqwest::Error) -> ApiError {
+pub(crate) fn map_http_error(error: reqwest::Error) -> ApiError {
     if error.is_status() {
         let status = error.status().unwrap();
         match status.as_u16() {
@@ -202,15 +233,29 @@ pub struct OpenAIClient {
 
 impl OpenAIClient {
     pub fn new(model: String, api_key: String, base_url: Option<String>) -> Result<Self, ApiError> {
-        let client = build_provider_http_client()?;
+        Ok(Self::with_http_client(
+            build_provider_http_client()?,
+            model,
+            api_key,
+            base_url,
+        ))
+    }
+
+    /// Client that sends requests through `client`, sharing its connections.
+    pub fn with_http_client(
+        client: Client,
+        model: String,
+        api_key: String,
+        base_url: Option<String>,
+    ) -> Self {
         let base_url = base_url.unwrap_or_else(|| "https://api.openai.com/v1".to_string());
 
-        Ok(Self {
+        Self {
             client,
             model,
             api_key,
             base_url,
-        })
+        }
     }
 }
 
@@ -299,16 +344,73 @@ impl ModelProviderClient for OpenAIClient {
 
     async fn stream(
         &self,
-        _messages: Vec<ChatMessage>,
-        _options: CompletionOptions,
+        messages: Vec<ChatMessage>,
+        options: CompletionOptions,
     ) -> Result<CompletionStream, ApiError> {
-        // Streaming implementation would go here
-        // For now, return an error indicating it's not implemented
-        Err(ApiError::ProviderError(
-            "Streaming not yet implemented for OpenAI".to_string(),
+        let openai_messages: Vec<OpenAIMessage> = messages
+            .into_iter()
+            .map(|msg| OpenAIMessage {
+                role: role_to_string(msg.role).to_string(),
+                content: msg.content,
+            })
+            .collect();
+
+        // Usage arrives in a final chunk only when asked for; a configured value wins.
+        let mut additional_json = options.additional_json;
+        additional_json
+            .entry("stream_options".to_string())
+            .or_insert_with(|| json!({ "include_usage": true }));
+
+        let request = ChatCompletionRequest {
+            model: self.model.clone(),
+            messages: openai_messages,
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+            top_p: options.top_p,
+            frequency_penalty: options.frequency_penalty,
+            presence_penalty: options.presence_penalty,
+            stop: options.stop,
+            stream: true,
+            additional_json,
+        };
+
+        let url = format!("{}/chat/completions", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(map_http_error)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(match status.as_u16() {
+                401 => {
+                    ApiError::ProviderAuthFailed(format!("Authentication failed: {}", error_text))
+                }
+                429 => ApiError::ProviderRateLimit(format!("Rate limit exceeded: {}", error_text)),
+                404 => ApiError::ProviderModelNotFound(format!("Model not found: {}", error_text)),
+                _ => ApiError::ProviderRequestFailed(format!("Request failed: {}", error_text)),
+            });
+        }
+
+        Ok(streaming::response_stream(
+            response,
+            streaming::StreamFormat::OpenAiSse,
         ))
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &str {
         "openai"
     }
@@ -317,7 +419,7 @@ impl ModelProviderClient for OpenAIClient {
         &self.model
     }
 
-    async fn list_models(&self) -> Result<Vec<String>, ApiError> {
+    async fn list_models(&self) -> Result<ModelListing, ApiError> {
         let url = format!("{}/models", self.base_url);
         let response = self
             .client
@@ -352,7 +454,17 @@ impl ModelProviderClient for OpenAIClient {
             ApiError::ProviderError(format!("Failed to parse models response: {}", e))
         })?;
 
-        Ok(models.data.into_iter().map(|m| m.id).collect())
+        Ok(ModelListing::Listed(
+            models.data.into_iter().map(|m| m.id).collect(),
+        ))
+    }
+}
+
+/// Map an Anthropic `stop_reason` onto the OpenAI style finish reasons used elsewhere.
+fn anthropic_finish_reason(stop_reason: Option<&str>) -> String {
+    match stop_reason {
+        Some("max_tokens") => FINISH_REASON_LENGTH.to_string(),
+        _ => "stop".to_string(),
     }
 }
 
@@ -361,34 +473,40 @@ pub struct AnthropicClient {
     client: Client,
     model: String,
     api_key: String,
+    base_url: String,
 }
 
+/// Anthropic API version sent with every request.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
 impl AnthropicClient {
-    pub fn new(model: String, api_key: String) -> Result<Self, ApiError> {
-        let client = build_provider_http_client()?;
-        Ok(Self {
-            client,
+    pub fn new(model: String, api_key: String, base_url: Option<String>) -> Result<Self, ApiError> {
+        Ok(Self::with_http_client(
+            build_provider_http_client()?,
             model,
             api_key,
-        })
+            base_url,
+        ))
     }
-}
 
-#[async_trait]
-impl ModelProviderClient for AnthropicClient {
-    async fn complete(
-        &self,
-        messages: Vec<ChatMessage>,
-        options: CompletionOptions,
-    ) -> Result<CompletionResponse, ApiError> {
-        // Anthropic API uses a different format, but we'll map it to OpenAI-compatible
-        // For now, we'll use a simplified approach that works with OpenAI-compatible endpoints
-        // In a real implementation, we'd use the Anthropic SDK or map their API format
-
-        // Anthropic API endpoint
-        let url = "https://api.anthropic.com/v1/messages";
+    /// Client that sends requests through `client`, sharing its connections.
+    pub fn with_http_client(
+        client: Client,
+        model: String,
+        api_key: String,
+        base_url: Option<String>,
+    ) -> Self {
+        let base_url = base_url.unwrap_or_else(|| "https://api.anthropic.com/v1".to_string());
+        Self {
+            client,
+            model,
+            api_key,
+            base_url,
+        }
+    }
 
-        // Convert messages to Anthropic format
+    /// Convert chat messages and options to an Anthropic messages request body.
+    fn request_body(&self, messages: &[ChatMessage], options: &CompletionOptions) -> Value {
         let system_message = messages
             .iter()
             .find(|m| m.role == MessageRole::System)
@@ -420,14 +538,18 @@ impl ModelProviderClient for AnthropicClient {
             request_body["temperature"] = json!(temp);
         }
         merge_additional_json(&mut request_body, &options.additional_json);
+        request_body
+    }
 
+    async fn send(&self, request_body: &Value) -> Result<reqwest::Response, ApiError> {
+        let url = format!("{}/messages", self.base_url);
         let response = self
             .client
-            .post(url)
+            .post(&url)
             .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
+            .header("anthropic-version", ANTHROPIC_VERSION)
             .header("Content-Type", "application/json")
-            .json(&request_body)
+            .json(request_body)
             .send()
             .await
             .map_err(map_http_error)?;
@@ -447,12 +569,31 @@ impl ModelProviderClient for AnthropicClient {
                 _ => ApiError::ProviderRequestFailed(format!("Request failed: {}", error_text)),
             });
         }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl ModelProviderClient for AnthropicClient {
+    async fn complete(
+        &self,
+        messages: Vec<ChatMessage>,
+        options: CompletionOptions,
+    ) -> Result<CompletionResponse, ApiError> {
+        // Anthropic API uses a different format, but we'll map it to OpenAI-compatible
+        // For now, we'll use a simplified approach that works with OpenAI-compatible endpoints
+        // In a real implementation, we'd use the Anthropic SDK or map their API format
+
+        let request_body = self.request_body(&messages, &options);
+        let response = self.send(&request_body).await?;
 
         #[derive(Deserialize)]
         struct AnthropicResponse {
             content: Vec<AnthropicContent>,
             model: String,
             usage: Option<AnthropicUsage>,
+            #[serde(default)]
+            stop_reason: Option<String>,
         }
 
         #[derive(Deserialize)]
@@ -490,20 +631,28 @@ impl ModelProviderClient for AnthropicClient {
                 completion_tokens: usage.output_tokens,
                 total_tokens: usage.input_tokens + usage.output_tokens,
             },
-            finish_reason: Some("stop".to_string()),
+            finish_reason: Some(anthropic_finish_reason(completion.stop_reason.as_deref())),
         })
     }
 
     async fn stream(
         &self,
-        _messages: Vec<ChatMessage>,
-        _options: CompletionOptions,
+        messages: Vec<ChatMessage>,
+        options: CompletionOptions,
     ) -> Result<CompletionStream, ApiError> {
-        Err(ApiError::ProviderError(
-            "Streaming not yet implemented for Anthropic".to_string(),
+        let mut request_body = self.request_body(&messages, &options);
+        request_body["stream"] = json!(true);
+        let response = self.send(&request_body).await?;
+        Ok(streaming::response_stream(
+            response,
+            streaming::StreamFormat::AnthropicSse,
         ))
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &str {
         "anthropic"
     }
@@ -512,12 +661,69 @@ impl ModelProviderClient for AnthropicClient {
         &self.model
     }
 
-    async fn list_models(&self) -> Result<Vec<String>, ApiError> {
-        // Anthropic doesn't have a public models list endpoint
-        // Return an error indicating this isn't supported
-        Err(ApiError::ProviderError(
-            "Anthropic API does not provide a models list endpoint".to_string(),
-        ))
+    async fn list_models(&self) -> Result<ModelListing, ApiError> {
+        #[derive(Deserialize)]
+        struct ModelsPage {
+            data: Vec<ModelInfo>,
+            #[serde(default)]
+            has_more: bool,
+            last_id: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct ModelInfo {
+            id: String,
+        }
+
+        // `/models` is paginated; follow `last_id` until the listing is exhausted.
+        let mut models = Vec::new();
+        let mut after_id: Option<String> = None;
+        loop {
+            let mut request = self
+                .client
+                .get(format!("{}/models", self.base_url))
+                .query(&[("limit", "1000")])
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION);
+            if let Some(after_id) = &after_id {
+                request = request.query(&[("after_id", after_id)]);
+            }
+            let response = request.send().await.map_err(map_http_error)?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(ApiError::ProviderError(format!(
+                    "Failed to list models: status {} - {}",
+                    status, error_text
+                )));
+            }
+
+            let page: ModelsPage = response.json().await.map_err(|e| {
+                ApiError::ProviderError(format!("Failed to parse models response: {}", e))
+            })?;
+            models.extend(page.data.into_iter().map(|m| m.id));
+            match page.last_id {
+                Some(last_id) if page.has_more => after_id = Some(last_id),
+                _ => break,
+            }
+        }
+        Ok(ModelListing::Listed(models))
+    }
+
+    /// `<family>-latest` aliases are not listed; they match any dated `<family>-YYYYMMDD`.
+    fn lists_model(&self, listed: &str, model: &str) -> bool {
+        listed == model
+            || model.strip_suffix("-latest").is_some_and(|family| {
+                listed
+                    .strip_prefix(family)
+                    .and_then(|rest| rest.strip_prefix('-'))
+                    .is_some_and(|date| {
+                        !date.is_empty() && date.bytes().all(|b| b.is_ascii_digit())
+                    })
+            })
     }
 }
 
@@ -530,14 +736,22 @@ pub struct OllamaClient {
 
 impl OllamaClient {
     pub fn new(model: String, base_url: Option<String>) -> Result<Self, ApiError> {
+        Ok(Self::with_http_client(
+            build_provider_http_client()?,
+            model,
+            base_url,
+        ))
+    }
+
+    /// Client that sends requests through `client`, sharing its connections.
+    pub fn with_http_client(client: Client, model: String, base_url: Option<String>) -> Self {
         let base_url = base_url.unwrap_or_else(|| "http://localhost:11434".to_string());
-        let client = build_provider_http_client()?;
 
-        Ok(Self {
+        Self {
             client,
             model,
             base_url,
-        })
+        }
     }
 }
 
@@ -622,14 +836,74 @@ impl ModelProviderClient for OllamaClient {
 
     async fn stream(
         &self,
-        _messages: Vec<ChatMessage>,
-        _options: CompletionOptions,
+        messages: Vec<ChatMessage>,
+        options: CompletionOptions,
     ) -> Result<CompletionStream, ApiError> {
-        Err(ApiError::ProviderError(
-            "Streaming not yet implemented for Ollama".to_string(),
+        // The OpenAI-compatible endpoint buffers; the native chat endpoint streams NDJSON chunks.
+        let ollama_messages: Vec<OpenAIMessage> = messages
+            .into_iter()
+            .map(|msg| OpenAIMessage {
+                role: role_to_string(msg.role).to_string(),
+                content: msg.content,
+            })
+            .collect();
+
+        let mut model_options = serde_json::Map::new();
+        if let Some(temperature) = options.temperature {
+            model_options.insert("temperature".to_string(), json!(temperature));
+        }
+        if let Some(top_p) = options.top_p {
+            model_options.insert("top_p".to_string(), json!(top_p));
+        }
+        if let Some(max_tokens) = options.max_tokens {
+            model_options.insert("num_predict".to_string(), json!(max_tokens));
+        }
+        if let Some(stop) = options.stop {
+            model_options.insert("stop".to_string(), json!(stop));
+        }
+
+        let mut request_body = json!({
+            "model": self.model,
+            "messages": ollama_messages,
+            "stream": true,
+        });
+        if !model_options.is_empty() {
+            request_body["options"] = Value::Object(model_options);
+        }
+        merge_additional_json(&mut request_body, &options.additional_json);
+
+        let url = format!("{}/api/chat", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(map_http_error)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ApiError::ProviderRequestFailed(format!(
+                "Request failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        Ok(streaming::response_stream(
+            response,
+            streaming::StreamFormat::NdJson,
         ))
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &str {
         "ollama"
     }
@@ -638,7 +912,7 @@ impl ModelProviderClient for OllamaClient {
         &self.model
     }
 
-    async fn list_models(&self) -> Result<Vec<String>, ApiError> {
+    async fn list_models(&self) -> Result<ModelListing, ApiError> {
         let url = format!("{}/api/tags", self.base_url);
         let response = self.client.get(&url).send().await.map_err(map_http_error)?;
 
@@ -667,7 +941,14 @@ impl ModelProviderClient for OllamaClient {
             ApiError::ProviderError(format!("Failed to parse models response: {}", e))
         })?;
 
-        Ok(tags.models.into_iter().map(|m| m.name).collect())
+        Ok(ModelListing::Listed(
+            tags.models.into_iter().map(|m| m.name).collect(),
+        ))
+    }
+
+    /// `/api/tags` names carry a tag (`llama2:latest`); an untagged model means `:latest`.
+    fn lists_model(&self, listed: &str, model: &str) -> bool {
+        listed == model || (!model.contains(':') && listed.strip_suffix(":latest") == Some(model))
     }
 }
 
@@ -681,13 +962,27 @@ pub struct CustomLocalClient {
 
 impl CustomLocalClient {
     pub fn new(model: String, endpoint: String, api_key: Option<String>) -> Result<Self, ApiError> {
-        let client = build_provider_http_client()?;
-        Ok(Self {
+        Ok(Self::with_http_client(
+            build_provider_http_client()?,
+            model,
+            endpoint,
+            api_key,
+        ))
+    }
+
+    /// Client that sends requests through `client`, sharing its connections.
+    pub fn with_http_client(
+        client: Client,
+        model: String,
+        endpoint: String,
+        api_key: Option<String>,
+    ) -> Self {
+        Self {
             client,
             model,
             endpoint,
             api_key,
-        })
+        }
     }
 }
 
@@ -777,14 +1072,68 @@ impl ModelProviderClient for CustomLocalClient {
 
     async fn stream(
         &self,
-        _messages: Vec<ChatMessage>,
-        _options: CompletionOptions,
+        messages: Vec<ChatMessage>,
+        options: CompletionOptions,
     ) -> Result<CompletionStream, ApiError> {
-        Err(ApiError::ProviderError(
-            "Streaming not yet implemented for CustomLocal".to_string(),
+        let openai_messages: Vec<OpenAIMessage> = messages
+            .into_iter()
+            .map(|msg| OpenAIMessage {
+                role: role_to_string(msg.role).to_string(),
+                content: msg.content,
+            })
+            .collect();
+
+        let request = ChatCompletionRequest {
+            model: self.model.clone(),
+            messages: openai_messages,
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+            top_p: options.top_p,
+            frequency_penalty: options.frequency_penalty,
+            presence_penalty: options.presence_penalty,
+            stop: options.stop,
+            stream: true,
+            additional_json: options.additional_json,
+        };
+        let url = format!("{}/chat/completions", self.endpoint);
+        let mut request_builder = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json");
+
+        if let Some(api_key) = &self.api_key {
+            request_builder =
+                request_builder.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let response = request_builder
+            .json(&request)
+            .send()
+            .await
+            .map_err(map_http_error)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ApiError::ProviderRequestFailed(format!(
+                "Request failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        Ok(streaming::response_stream(
+            response,
+            streaming::StreamFormat::OpenAiSse,
         ))
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &str {
         "local"
     }
@@ -793,7 +1142,7 @@ impl ModelProviderClient for CustomLocalClient {
         &self.model
     }
 
-    async fn list_models(&self) -> Result<Vec<String>, ApiError> {
+    async fn list_models(&self) -> Result<ModelListing, ApiError> {
         // Try OpenAI-compatible /v1/models endpoint
         let url = format!("{}/models", self.endpoint);
         let mut request_builder = self
@@ -808,6 +1157,14 @@ impl ModelProviderClient for CustomLocalClient {
 
         let response = request_builder.send().await.map_err(map_http_error)?;
 
+        // Many OpenAI-compatible servers answer completions but not `/models`.
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(ModelListing::Unlisted(format!(
+                "{} does not serve a model listing",
+                self.endpoint
+            )));
+        }
+
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response
@@ -833,7 +1190,94 @@ impl ModelProviderClient for CustomLocalClient {
             ApiError::ProviderError(format!("Failed to parse models response: {}", e))
         })?;
 
-        Ok(models.data.into_iter().map(|m| m.id).collect())
+        Ok(ModelListing::Listed(
+            models.data.into_iter().map(|m| m.id).collect(),
+        ))
+    }
+}
+
+/// Offline provider client for tests and CI
+///
+/// Completions are a deterministic function of the model name and the messages, so the same
+/// prompt always yields the same frame content without any network access. Each whitespace
+/// separated word counts as one token; a `max_tokens` below the word count cuts the content
+/// and reports a `length` finish reason.
+pub struct MockClient {
+    model: String,
+}
+
+impl MockClient {
+    pub fn new(model: String) -> Self {
+        Self { model }
+    }
+
+    /// Completion text returned for `messages`: the model name and a BLAKE3 digest of the
+    /// role and content of every message, in order.
+    pub fn completion_content(model: &str, messages: &[ChatMessage]) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for message in messages {
+            hasher.update(role_to_string(message.role).as_bytes());
+            hasher.update(b"\n");
+            hasher.update(message.content.as_bytes());
+            hasher.update(b"\n");
+        }
+        format!("mock({}) {}", model, hasher.finalize().to_hex())
+    }
+}
+
+#[async_trait]
+impl ModelProviderClient for MockClient {
+    async fn complete(
+        &self,
+        messages: Vec<ChatMessage>,
+        options: CompletionOptions,
+    ) -> Result<CompletionResponse, ApiError> {
+        let mut content = Self::completion_content(&self.model, &messages);
+        let prompt_tokens = messages
+            .iter()
+            .map(|message| message.content.split_whitespace().count() as u32)
+            .sum();
+        let mut finish_reason = "stop";
+        if let Some(max_tokens) = options.max_tokens {
+            let words = content.split_whitespace().collect::<Vec<_>>();
+            if words.len() > max_tokens as usize {
+                content = words[..max_tokens as usize].join(" ");
+                finish_reason = FINISH_REASON_LENGTH;
+            }
+        }
+        let completion_tokens = content.split_whitespace().count() as u32;
+        Ok(CompletionResponse {
+            content,
+            model: self.model.clone(),
+            usage: TokenUsage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+            },
+            finish_reason: Some(finish_reason.to_string()),
+        })
+    }
+
+    async fn stream(
+        &self,
+        _messages: Vec<ChatMessage>,
+        _options: CompletionOptions,
+    ) -> Result<CompletionStream, ApiError> {
+        Err(ApiError::ProviderError(
+            "Streaming not supported by the mock provider".to_string(),
+        ))
+    }
+
+    fn provider_name(&self) -> &str {
+        "mock"
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    async fn list_models(&self) -> Result<ModelListing, ApiError> {
+        Ok(ModelListing::Listed(vec![self.model.clone()]))
     }
 }
 
@@ -841,36 +1285,60 @@ impl ModelProviderClient for CustomLocalClient {
 pub struct ProviderFactory;
 
 impl ProviderFactory {
+    /// Create a client with its own HTTP connection pool.
     pub fn create_client(
         provider: &ModelProvider,
+    ) -> Result<Box<dyn ModelProviderClient>, ApiError> {
+        Self::create_client_with_http(provider, build_provider_http_client)
+    }
+
+    /// Create a client that reuses `pool`'s HTTP client for the provider's endpoint.
+    pub fn create_pooled_client(
+        provider: &ModelProvider,
+        pool: &ProviderHttpClientPool,
+    ) -> Result<Box<dyn ModelProviderClient>, ApiError> {
+        Self::create_client_with_http(provider, || pool.client_for(provider))
+    }
+
+    fn create_client_with_http(
+        provider: &ModelProvider,
+        http_client: impl FnOnce() -> Result<Client, ApiError>,
     ) -> Result<Box<dyn ModelProviderClient>, ApiError> {
         match provider {
             ModelProvider::OpenAI {
                 model,
                 api_key,
                 base_url,
-            } => Ok(Box::new(OpenAIClient::new(
+            } => Ok(Box::new(OpenAIClient::with_http_client(
+                http_client()?,
                 model.clone(),
                 api_key.clone(),
                 base_url.clone(),
-            )?)),
-            ModelProvider::Anthropic { model, api_key } => Ok(Box::new(AnthropicClient::new(
+            ))),
+            ModelProvider::Anthropic {
+                model,
+                api_key,
+                base_url,
+            } => Ok(Box::new(AnthropicClient::with_http_client(
+                http_client()?,
                 model.clone(),
                 api_key.clone(),
-            )?)),
-            ModelProvider::Ollama { model, base_url } => Ok(Box::new(OllamaClient::new(
-                model.clone(),
                 base_url.clone(),
-            )?)),
+            ))),
+            ModelProvider::Ollama { model, base_url } => Ok(Box::new(
+                OllamaClient::with_http_client(http_client()?, model.clone(), base_url.clone()),
+            )),
             ModelProvider::LocalCustom {
                 model,
                 endpoint,
                 api_key,
-            } => Ok(Box::new(CustomLocalClient::new(
+            } => Ok(Box::new(CustomLocalClient::with_http_client(
+                http_client()?,
                 model.clone(),
                 endpoint.clone(),
                 api_key.clone(),
-            )?)),
+            ))),
+            ModelProvider::Mock { model } => Ok(Box::new(MockClient::new(model.clone()))),
         }
     }
 }
@@ -1077,9 +1545,9 @@ impl ModelProviderClient for MockProvider {
         &self.model_name
     }
 
-    async fn list_models(&self) -> Result<Vec<String>, ApiError> {
+    async fn list_models(&self) -> Result<ModelListing, ApiError> {
         // Mock provider returns empty list for testing
-        Ok(vec![])
+        Ok(ModelListing::Listed(vec![]))
     }
 }
 
@@ -1438,6 +1906,7 @@ mod tests {
         let provider = ModelProvider::Anthropic {
             model: "claude-3-opus".to_string(),
             api_key: "test-key".to_string(),
+            base_url: None,
         };
 
         let client = ProviderFactory::create_client(&provider).unwrap();
@@ -1470,6 +1939,41 @@ mod tests {
         assert_eq!(client.model_name(), "custom-model");
     }
 
+    #[tokio::test]
+    async fn test_provider_factory_mock_is_deterministic() {
+        let provider = ModelProvider::Mock {
+            model: "echo".to_string(),
+        };
+        let client = ProviderFactory::create_client(&provider).unwrap();
+        assert_eq!(client.provider_name(), "mock");
+        assert_eq!(client.model_name(), "echo");
+
+        let messages = |content: &str| {
+            vec![ChatMessage {
+                role: MessageRole::User,
+                content: content.to_string(),
+            }]
+        };
+        let first = client
+            .complete(messages("summarize a.rs"), CompletionOptions::default())
+            .await
+            .unwrap();
+        let second = client
+            .complete(messages("summarize a.rs"), CompletionOptions::default())
+            .await
+            .unwrap();
+        let other = client
+            .complete(messages("summarize b.rs"), CompletionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(first.content, second.content);
+        assert_ne!(first.content, other.content);
+        assert_eq!(
+            first.content,
+            MockClient::completion_content("echo", &messages("summarize a.rs"))
+        );
+    }
+
     #[test]
     fn test_message_role_serialization() {
         let role = MessageRole::System;
@@ -1,3 +1,5 @@
+pub mod pool;
 pub mod resolver;
 
+pub use pool::ProviderHttpClientPool;
 pub use resolver::ProviderClientResolver;
@@ -0,0 +1,88 @@
+//! Shared HTTP clients for provider calls.
+//!
+//! A `reqwest::Client` owns a connection pool, so building one per request pays connection
+//! setup and the TLS handshake on every provider call. The pool builds one client per provider
+//! endpoint on first use and hands out clones, which share that client's connections. Queue
+//! workers on different threads share the same pool.
+
+use crate::error::ApiError;
+use crate::provider::{build_provider_http_client, ModelProvider};
+use parking_lot::Mutex;
+use reqwest::Client;
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct ProviderHttpClientPool {
+    clients: Mutex<HashMap<String, Client>>,
+}
+
+impl ProviderHttpClientPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// HTTP client for the endpoint `provider` talks to, built on first use.
+    pub fn client_for(&self, provider: &ModelProvider) -> Result<Client, ApiError> {
+        let key = endpoint_key(provider);
+        let mut clients = self.clients.lock();
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+        let client = build_provider_http_client()?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Number of endpoints with a cached client.
+    pub fn len(&self) -> usize {
+        self.clients.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Provider kind and endpoint; unset base URLs share the kind's default endpoint.
+fn endpoint_key(provider: &ModelProvider) -> String {
+    let (kind, endpoint) = match provider {
+        ModelProvider::OpenAI { base_url, .. } => ("openai", base_url.as_deref()),
+        ModelProvider::Anthropic { base_url, .. } => ("anthropic", base_url.as_deref()),
+        ModelProvider::Ollama { base_url, .. } => ("ollama", base_url.as_deref()),
+        ModelProvider::LocalCustom { endpoint, .. } => ("local", Some(endpoint.as_str())),
+        ModelProvider::Mock { .. } => ("mock", None),
+    };
+    format!("{}|{}", kind, endpoint.unwrap_or("default"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(endpoint: &str, model: &str) -> ModelProvider {
+        ModelProvider::LocalCustom {
+            model: model.to_string(),
+            endpoint: endpoint.to_string(),
+            api_key: None,
+        }
+    }
+
+    #[test]
+    fn reuses_one_client_per_endpoint() {
+        let pool = ProviderHttpClientPool::new();
+        pool.client_for(&local("http://127.0.0.1:1/v1", "a"))
+            .unwrap();
+        pool.client_for(&local("http://127.0.0.1:1/v1", "b"))
+            .unwrap();
+        assert_eq!(pool.len(), 1);
+
+        pool.client_for(&local("http://127.0.0.1:2/v1", "a"))
+            .unwrap();
+        pool.client_for(&ModelProvider::Ollama {
+            model: "a".to_string(),
+            base_url: None,
+        })
+        .unwrap();
+        assert_eq!(pool.len(), 3);
+    }
+}
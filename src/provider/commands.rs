@@ -1,8 +1,10 @@
 use crate::error::ApiError;
-use crate::provider::diagnostics::ProviderDiagnosticsService;
+use crate::provider::diagnostics::{ModelAvailability, ProviderDiagnosticsService};
+use crate::provider::latency::{load_latency_stats, ProviderLatencyStats};
 use crate::provider::profile::{ProviderConfig, ProviderType, ValidationResult};
-use crate::provider::ProviderRegistry;
+use crate::provider::{CompletionResponse, ProviderRegistry};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 pub struct ProviderCommandService;
@@ -11,6 +13,8 @@ pub struct ProviderCommandService;
 #[derive(Debug, Clone)]
 pub struct ProviderListResult {
     pub providers: Vec<ProviderConfig>,
+    /// Recorded latency per provider name; present only when latency was requested.
+    pub latencies: Option<BTreeMap<String, ProviderLatencyStats>>,
 }
 
 /// Result of provider show (config plus optional API key status).
@@ -52,6 +56,32 @@ pub struct ProviderTestResult {
     pub connectivity_ok: bool,
     pub model_available: bool,
     pub available_models: Vec<String>,
+    /// Set when the provider cannot enumerate models and availability was not checked.
+    pub model_check_skipped: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// Result of `provider validate --all`.
+#[derive(Debug, Clone)]
+pub struct ProviderValidateAllResult {
+    pub results: Vec<ValidationResult>,
+    /// Providers left unvalidated because `fail_fast` stopped at an invalid provider.
+    pub skipped: usize,
+}
+
+impl ProviderValidateAllResult {
+    pub fn invalid_count(&self) -> usize {
+        self.results.iter().filter(|r| !r.is_valid()).count()
+    }
+}
+
+/// Outcome of `provider test --prompt`: the raw completion or the error that prevented it.
+#[derive(Debug, Clone)]
+pub struct ProviderPromptTestResult {
+    pub provider_name: String,
+    pub model: String,
+    pub prompt: String,
+    pub response: Option<CompletionResponse>,
     pub error_message: Option<String>,
 }
 
@@ -62,8 +92,9 @@ impl ProviderCommandService {
             "anthropic" => Ok(ProviderType::Anthropic),
             "ollama" => Ok(ProviderType::Ollama),
             "local" => Ok(ProviderType::LocalCustom),
+            "mock" => Ok(ProviderType::Mock),
             _ => Err(ApiError::ConfigError(format!(
-                "Invalid type filter: {}. Must be openai, anthropic, ollama, or local",
+                "Invalid type filter: {}. Must be openai, anthropic, ollama, local, or mock",
                 type_str
             ))),
         }
@@ -73,7 +104,7 @@ impl ProviderCommandService {
         match provider_type {
             ProviderType::OpenAI => Some("https://api.openai.com/v1".to_string()),
             ProviderType::Ollama => Some("http://localhost:11434".to_string()),
-            ProviderType::LocalCustom | ProviderType::Anthropic => None,
+            ProviderType::LocalCustom | ProviderType::Anthropic | ProviderType::Mock => None,
         }
     }
 
@@ -81,7 +112,7 @@ impl ProviderCommandService {
         match provider_type {
             ProviderType::OpenAI => Some("OPENAI_API_KEY"),
             ProviderType::Anthropic => Some("ANTHROPIC_API_KEY"),
-            ProviderType::Ollama | ProviderType::LocalCustom => None,
+            ProviderType::Ollama | ProviderType::LocalCustom | ProviderType::Mock => None,
         }
     }
 
@@ -139,14 +170,42 @@ impl ProviderCommandService {
     }
 
     /// List providers, optionally filtered by type.
+    ///
+    /// With `show_latency`, providers are sorted fastest-first by recorded average latency;
+    /// providers without samples sort last.
     pub fn run_list(
         registry: &ProviderRegistry,
         type_filter: Option<&str>,
+        show_latency: bool,
     ) -> Result<ProviderListResult, ApiError> {
         let provider_type = type_filter.map(Self::parse_provider_type).transpose()?;
-        let providers = registry.list_by_type(provider_type);
+        let mut providers: Vec<ProviderConfig> = registry
+            .list_by_type(provider_type)
+            .into_iter()
+            .cloned()
+            .collect();
+        let latencies = if show_latency {
+            let latencies = load_latency_stats()?;
+            let average_of = |provider: &ProviderConfig| {
+                provider
+                    .provider_name
+                    .as_deref()
+                    .and_then(|name| latencies.get(name))
+                    .map(|stats| stats.average_ms)
+            };
+            providers.sort_by(|a, b| match (average_of(a), average_of(b)) {
+                (Some(a_ms), Some(b_ms)) => a_ms.total_cmp(&b_ms),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.provider_name.cmp(&b.provider_name),
+            });
+            Some(latencies)
+        } else {
+            None
+        };
         Ok(ProviderListResult {
-            providers: providers.into_iter().cloned().collect(),
+            providers,
+            latencies,
         })
     }
 
@@ -210,22 +269,35 @@ impl ProviderCommandService {
         let mut result = ProviderDiagnosticsService::validate_provider(registry, provider_name)?;
         if test_connectivity || check_model {
             result.add_check("Provider client created", true);
-            match ProviderDiagnosticsService::list_available_models(registry, provider_name) {
-                Ok(available_models) => {
+            let model = registry.get_or_error(provider_name)?.model.clone();
+            match ProviderDiagnosticsService::check_model_availability(
+                registry,
+                provider_name,
+                &model,
+                None,
+            ) {
+                Ok(availability) => {
                     result.add_check("API connectivity: OK", true);
                     if check_model {
-                        let provider = registry.get_or_error(provider_name)?;
-                        if available_models.iter().any(|m| m == &provider.model) {
-                            result.add_check(
-                                &format!("Model '{}' is available", provider.model),
-                                true,
-                            );
-                        } else {
-                            result.add_error(format!(
-                                "Model '{}' not found. Available models: {}",
-                                provider.model,
-                                available_models.join(", ")
-                            ));
+                        match availability {
+                            ModelAvailability::Listed {
+                                available: true, ..
+                            } => {
+                                result.add_check(&format!("Model '{}' is available", model), true);
+                            }
+                            ModelAvailability::Listed { models, .. } => {
+                                result.add_error(format!(
+                                    "Model '{}' not found. Available models: {}",
+                                    model,
+                                    models.join(", ")
+                                ));
+                            }
+                            ModelAvailability::Unlisted(reason) => {
+                                result.add_warning(format!(
+                                    "Model availability not checked: {}",
+                                    reason
+                                ));
+                            }
                         }
                     }
                 }
@@ -237,6 +309,61 @@ impl ProviderCommandService {
         Ok(result)
     }
 
+    /// Validate every provider in name order. With `fail_fast`, stop after the first invalid one.
+    pub fn run_validate_all(
+        registry: &ProviderRegistry,
+        test_connectivity: bool,
+        check_model: bool,
+        fail_fast: bool,
+    ) -> Result<ProviderValidateAllResult, ApiError> {
+        let mut names: Vec<String> = registry
+            .list_all()
+            .into_iter()
+            .map(|config| {
+                config
+                    .provider_name
+                    .as_deref()
+                    .unwrap_or("unknown")
+                    .to_string()
+            })
+            .collect();
+        names.sort();
+        let total = names.len();
+        let mut results = Vec::new();
+        for name in names {
+            let result = Self::run_validate(registry, &name, test_connectivity, check_model)
+                .unwrap_or_else(|e| {
+                    let mut r = ValidationResult::new(name.clone());
+                    r.add_error(format!("Failed to validate: {}", e));
+                    r
+                });
+            let valid = result.is_valid();
+            results.push(result);
+            if fail_fast && !valid {
+                break;
+            }
+        }
+        let skipped = total - results.len();
+        Ok(ProviderValidateAllResult { results, skipped })
+    }
+
+    /// Offline validation only: config shape, endpoint, and API key resolution from config or
+    /// environment. Never contacts the provider; any error fails the command.
+    pub fn run_check_credentials(
+        registry: &ProviderRegistry,
+        provider_name: &str,
+    ) -> Result<ValidationResult, ApiError> {
+        let result = ProviderDiagnosticsService::validate_provider(registry, provider_name)?;
+        if !result.is_valid() {
+            return Err(ApiError::ConfigError(format!(
+                "Provider '{}' failed offline validation: {}",
+                provider_name,
+                result.errors.join("; ")
+            )));
+        }
+        Ok(result)
+    }
+
     /// Create provider and persist; reloads registry.
     pub fn run_create(
         registry: &mut ProviderRegistry,
@@ -262,6 +389,59 @@ impl ProviderCommandService {
         })
     }
 
+    /// Create a provider from an existing one's settings; reloads registry.
+    ///
+    /// Type, model, endpoint, and default options are copied and then overridden by any given
+    /// flag. The API key is only carried over with `copy_key`. An existing provider named
+    /// `provider_name` is replaced only with `force`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_clone(
+        registry: &mut ProviderRegistry,
+        provider_name: &str,
+        source_name: &str,
+        model: Option<&str>,
+        endpoint: Option<&str>,
+        api_key: Option<&str>,
+        copy_key: bool,
+        force: bool,
+    ) -> Result<ProviderCreateResult, ApiError> {
+        if provider_name == source_name {
+            return Err(ApiError::ConfigError(format!(
+                "Cannot clone provider '{}' onto itself",
+                source_name
+            )));
+        }
+        let source = registry.get_or_error(source_name)?.clone();
+        if !force
+            && (registry.get(provider_name).is_some()
+                || Self::provider_config_path(registry, provider_name)?.exists())
+        {
+            return Err(ApiError::ConfigError(format!(
+                "Provider already exists: {}. Use --force to overwrite it.",
+                provider_name
+            )));
+        }
+
+        let api_key = match api_key {
+            Some(key) => Some(key.to_string()),
+            None if copy_key => source.api_key.clone(),
+            None => None,
+        };
+        let config = Self::build_provider_config(
+            provider_name,
+            source.provider_type,
+            model.map_or(source.model, str::to_string),
+            endpoint.map(str::to_string).or(source.endpoint),
+            api_key,
+            source.default_options,
+        );
+        let path = Self::persist_provider_config(registry, provider_name, &config)?;
+        Ok(ProviderCreateResult {
+            provider_name: provider_name.to_string(),
+            config_path: path,
+        })
+    }
+
     /// Remove provider and reload registry.
     pub fn run_remove(
         registry: &mut ProviderRegistry,
@@ -285,44 +465,128 @@ impl ProviderCommandService {
         let model_checked = model_override
             .map(String::from)
             .unwrap_or_else(|| provider.model.clone());
-        match ProviderDiagnosticsService::list_available_models_with_timeout(
+        match ProviderDiagnosticsService::check_model_availability(
             registry,
             provider_name,
-            timeout_secs,
+            &model_checked,
+            Some(timeout_secs),
         ) {
-            Ok(available_models) => {
-                let model_available = available_models.iter().any(|m| m == &model_checked);
-                Ok(ProviderTestResult {
-                    provider_name: provider_name.to_string(),
-                    model_checked: model_checked.clone(),
-                    connectivity_ok: true,
-                    model_available,
-                    available_models,
-                    error_message: None,
-                })
-            }
+            Ok(ModelAvailability::Listed { available, models }) => Ok(ProviderTestResult {
+                provider_name: provider_name.to_string(),
+                model_checked,
+                connectivity_ok: true,
+                model_available: available,
+                available_models: models,
+                model_check_skipped: None,
+                error_message: None,
+            }),
+            Ok(ModelAvailability::Unlisted(reason)) => Ok(ProviderTestResult {
+                provider_name: provider_name.to_string(),
+                model_checked,
+                connectivity_ok: true,
+                model_available: false,
+                available_models: Vec::new(),
+                model_check_skipped: Some(reason),
+                error_message: None,
+            }),
             Err(e) => Ok(ProviderTestResult {
                 provider_name: provider_name.to_string(),
                 model_checked,
                 connectivity_ok: false,
                 model_available: false,
                 available_models: Vec::new(),
+                model_check_skipped: None,
                 error_message: Some(e.to_string()),
             }),
         }
     }
 
-    /// Update provider by flags (model, endpoint, api_key); does not open editor.
+    /// Send one prompt through the full completion path and capture the raw response.
+    pub fn run_test_prompt(
+        registry: &ProviderRegistry,
+        provider_name: &str,
+        model_override: Option<&str>,
+        prompt: &str,
+        timeout_secs: u64,
+    ) -> Result<ProviderPromptTestResult, ApiError> {
+        let provider = registry.get_or_error(provider_name)?;
+        let model = model_override
+            .map(String::from)
+            .unwrap_or_else(|| provider.model.clone());
+        let (response, error_message) =
+            match ProviderDiagnosticsService::complete_prompt_with_timeout(
+                registry,
+                provider_name,
+                model_override,
+                prompt,
+                timeout_secs,
+            ) {
+                Ok(response) => (Some(response), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+        Ok(ProviderPromptTestResult {
+            provider_name: provider_name.to_string(),
+            model,
+            prompt: prompt.to_string(),
+            response,
+            error_message,
+        })
+    }
+
+    /// Clear one optional field on a provider config so the runtime default applies.
+    /// Identity fields (`provider_name`, `type`, `model`) are required and cannot be unset.
+    pub fn unset_provider_field(config: &mut ProviderConfig, field: &str) -> Result<(), ApiError> {
+        if let Some((_, clear)) = UNSETTABLE_PROVIDER_FIELDS
+            .iter()
+            .find(|(name, _)| *name == field)
+        {
+            clear(config);
+            return Ok(());
+        }
+        if matches!(field, "model" | "type" | "provider_type" | "provider_name") {
+            return Err(ApiError::ConfigError(format!(
+                "Cannot unset required provider field: {}",
+                field
+            )));
+        }
+        let fields: Vec<&str> = UNSETTABLE_PROVIDER_FIELDS
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        Err(ApiError::ConfigError(format!(
+            "Unknown provider field: {}. Unsettable fields: {}",
+            field,
+            fields.join(", ")
+        )))
+    }
+
+    /// Update provider by flags (model, endpoint, api_key, unset); does not open editor.
+    /// Unsets are validated before anything is written so a bad field leaves the config untouched.
     pub fn run_update_flags(
         registry: &mut ProviderRegistry,
         provider_name: &str,
         model: Option<&str>,
         endpoint: Option<&str>,
         api_key: Option<&str>,
+        unset: &[String],
     ) -> Result<(), ApiError> {
         registry.get_or_error(provider_name)?;
+        for (field, is_set) in [
+            ("endpoint", endpoint.is_some()),
+            ("api_key", api_key.is_some()),
+        ] {
+            if is_set && unset.iter().any(|u| u == field) {
+                return Err(ApiError::ConfigError(format!(
+                    "Cannot both set and unset provider field: {}",
+                    field
+                )));
+            }
+        }
         let config_path = Self::provider_config_path(registry, provider_name)?;
         let mut provider_config = Self::load_provider_config_from_path(&config_path)?;
+        for field in unset {
+            Self::unset_provider_field(&mut provider_config, field)?;
+        }
         if let Some(m) = model {
             provider_config.model = m.to_string();
         }
@@ -336,3 +600,32 @@ impl ProviderCommandService {
         Ok(())
     }
 }
+
+/// Resets one optional provider field to its runtime default.
+type ClearProviderField = fn(&mut ProviderConfig);
+
+/// Optional provider fields accepted by `provider edit --unset`, each with how it is cleared.
+const UNSETTABLE_PROVIDER_FIELDS: &[(&str, ClearProviderField)] = &[
+    ("endpoint", |config| config.endpoint = None),
+    ("api_key", |config| config.api_key = None),
+    ("temperature", |config| {
+        config.default_options.temperature = None
+    }),
+    ("max_tokens", |config| {
+        config.default_options.max_tokens = None
+    }),
+    ("top_p", |config| config.default_options.top_p = None),
+    ("frequency_penalty", |config| {
+        config.default_options.frequency_penalty = None
+    }),
+    ("presence_penalty", |config| {
+        config.default_options.presence_penalty = None
+    }),
+    ("stop", |config| config.default_options.stop = None),
+    ("additional_json", |config| {
+        config.default_options.additional_json.clear()
+    }),
+    ("truncation_retry_max_tokens", |config| {
+        config.default_options.truncation_retry_max_tokens = None
+    }),
+];
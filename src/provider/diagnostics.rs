@@ -1,9 +1,23 @@
 use crate::error::ApiError;
 use crate::provider::profile::{ProviderConfig, ProviderType, ValidationResult};
-use crate::provider::ProviderRegistry;
+use crate::provider::{
+    ChatMessage, CompletionResponse, MessageRole, ModelListing, ProviderFactory, ProviderRegistry,
+};
 
 pub struct ProviderDiagnosticsService;
 
+/// Whether a model is among the models a provider lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelAvailability {
+    /// The provider listed its models; `available` says whether the checked model is one.
+    Listed {
+        available: bool,
+        models: Vec<String>,
+    },
+    /// The provider cannot enumerate models, so the check was skipped for this reason.
+    Unlisted(String),
+}
+
 impl ProviderDiagnosticsService {
     fn load_provider_for_validation(
         registry: &ProviderRegistry,
@@ -71,7 +85,9 @@ impl ProviderDiagnosticsService {
                     "Not set".to_string()
                 }
             }
-            ProviderType::Ollama | ProviderType::LocalCustom => "Not required".to_string(),
+            ProviderType::Ollama | ProviderType::LocalCustom | ProviderType::Mock => {
+                "Not required".to_string()
+            }
         }
     }
 
@@ -155,6 +171,9 @@ impl ProviderDiagnosticsService {
             ProviderType::Ollama => {
                 result.add_check("API key not required for local provider", true);
             }
+            ProviderType::Mock => {
+                result.add_check("API key not required for mock provider", true);
+            }
             ProviderType::LocalCustom => {
                 if provider.api_key.is_some() {
                     result.add_check("API key configured for local custom provider", true);
@@ -211,29 +230,75 @@ impl ProviderDiagnosticsService {
     pub fn list_available_models(
         registry: &ProviderRegistry,
         provider_name: &str,
-    ) -> Result<Vec<String>, ApiError> {
+    ) -> Result<ModelListing, ApiError> {
         let client = registry.create_client(provider_name)?;
         let rt = tokio::runtime::Runtime::new()
             .map_err(|e| ApiError::ProviderError(format!("Failed to create runtime: {}", e)))?;
         rt.block_on(client.list_models())
     }
 
-    pub fn list_available_models_with_timeout(
+    /// List the provider's models and look for `model`, matching names the way the provider
+    /// spells them (e.g. Ollama tags). An error means the provider could not be reached.
+    pub fn check_model_availability(
         registry: &ProviderRegistry,
         provider_name: &str,
-        timeout_secs: u64,
-    ) -> Result<Vec<String>, ApiError> {
+        model: &str,
+        timeout_secs: Option<u64>,
+    ) -> Result<ModelAvailability, ApiError> {
         let client = registry.create_client(provider_name)?;
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| ApiError::ProviderError(format!("Failed to create runtime: {}", e)))?;
+        let listing = rt.block_on(async {
+            match timeout_secs {
+                Some(timeout_secs) => tokio::time::timeout(
+                    std::time::Duration::from_secs(timeout_secs),
+                    client.list_models(),
+                )
+                .await
+                .map_err(|_| {
+                    ApiError::ProviderError(format!("API connectivity timeout ({}s)", timeout_secs))
+                })?,
+                None => client.list_models().await,
+            }
+        })?;
+        Ok(match listing {
+            ModelListing::Listed(models) => ModelAvailability::Listed {
+                available: models
+                    .iter()
+                    .any(|listed| client.lists_model(listed, model)),
+                models,
+            },
+            ModelListing::Unlisted(reason) => ModelAvailability::Unlisted(reason),
+        })
+    }
+
+    /// Send `prompt` as a single user message with the provider's default options.
+    pub fn complete_prompt_with_timeout(
+        registry: &ProviderRegistry,
+        provider_name: &str,
+        model_override: Option<&str>,
+        prompt: &str,
+        timeout_secs: u64,
+    ) -> Result<CompletionResponse, ApiError> {
+        let mut provider = registry.get_or_error(provider_name)?.clone();
+        if let Some(model) = model_override {
+            provider.model = model.to_string();
+        }
+        let client = ProviderFactory::create_client(&provider.to_model_provider()?)?;
+        let messages = vec![ChatMessage {
+            role: MessageRole::User,
+            content: prompt.to_string(),
+        }];
         let rt = tokio::runtime::Runtime::new()
             .map_err(|e| ApiError::ProviderError(format!("Failed to create runtime: {}", e)))?;
         rt.block_on(async {
             tokio::time::timeout(
                 std::time::Duration::from_secs(timeout_secs),
-                client.list_models(),
+                client.complete(messages, provider.default_options.clone()),
             )
             .await
             .map_err(|_| {
-                ApiError::ProviderError(format!("API connectivity timeout ({}s)", timeout_secs))
+                ApiError::ProviderError(format!("Completion timeout ({}s)", timeout_secs))
             })?
         })
     }
@@ -1,13 +1,18 @@
 use crate::context::generation::contracts::GenerationOrchestrationRequest;
-use crate::error::ApiError;
+use crate::error::{ApiError, StorageError};
 use crate::execution::{ExecutionEventContext, ProviderExecutionPort, ProviderValidationPort};
+use crate::provider::streaming;
 use crate::provider::{
-    ChatMessage, CompletionResponse, ModelProviderClient, ProviderConfig, ProviderFactory,
+    ChatMessage, CompletionOptions, CompletionResponse, ModelListing, ModelProviderClient,
+    ProviderConfig, ProviderFactory, TokenUsage,
 };
 use crate::telemetry::ProviderLifecycleEventData;
 use serde_json::json;
-use std::time::Instant;
-use tracing::info;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Streamed text is batched into one progress event per interval rather than one per delta.
+const STREAM_EVENT_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct ProviderPreparation {
     pub provider_config: ProviderConfig,
@@ -51,7 +56,7 @@ pub fn prepare_provider_for_request_from_api(
     let provider_type =
         crate::provider::profile::provider_type_slug(provider_config.provider_type).to_string();
     let model_provider = provider_config.to_model_provider()?;
-    let client = ProviderFactory::create_client(&model_provider)?;
+    let client = ProviderFactory::create_pooled_client(&model_provider, api.provider_clients())?;
     drop(provider_registry);
 
     Ok(ProviderPreparation {
@@ -110,11 +115,23 @@ pub async fn execute_completion_from_api(
         },
     );
 
-    let response = match preparation
-        .client
-        .complete(messages, completion_options)
+    let outcome = if request.stream_output && preparation.client.supports_streaming() {
+        stream_completion(
+            api,
+            request,
+            event_context,
+            preparation,
+            messages,
+            completion_options,
+        )
         .await
-    {
+    } else {
+        preparation
+            .client
+            .complete(messages, completion_options)
+            .await
+    };
+    let response = match outcome {
         Ok(r) => Ok(r),
         Err(e) => {
             emit_provider_event(
@@ -134,20 +151,17 @@ pub async fn execute_completion_from_api(
 
             if let ApiError::ProviderModelNotFound(_) = e {
                 match preparation.client.list_models().await {
-                    Ok(available_models) => {
-                        if available_models.is_empty() {
-                            Err(ApiError::ProviderModelNotFound(format!(
-                                "Model '{}' not found. Unable to retrieve available models list.",
-                                preparation.client.model_name()
-                            )))
-                        } else {
-                            Err(ApiError::ProviderModelNotFound(format!(
-                                "Model '{}' not found. Available models: {}",
-                                preparation.client.model_name(),
-                                available_models.join(", ")
-                            )))
-                        }
+                    Ok(ModelListing::Listed(available_models)) if !available_models.is_empty() => {
+                        Err(ApiError::ProviderModelNotFound(format!(
+                            "Model '{}' not found. Available models: {}",
+                            preparation.client.model_name(),
+                            available_models.join(", ")
+                        )))
                     }
+                    Ok(_) => Err(ApiError::ProviderModelNotFound(format!(
+                        "Model '{}' not found. Unable to retrieve available models list.",
+                        preparation.client.model_name()
+                    ))),
                     Err(_) => Err(e),
                 }
             } else {
@@ -157,6 +171,24 @@ pub async fn execute_completion_from_api(
     }?;
 
     let duration = start.elapsed();
+    let provider_name = request.provider.provider_name.clone();
+    let duration_ms = duration.as_secs_f64() * 1000.0;
+    let recorded = tokio::task::spawn_blocking(move || {
+        crate::provider::latency::record_latency_sample(&provider_name, duration_ms)
+    })
+    .await
+    .unwrap_or_else(|e| {
+        Err(ApiError::StorageError(StorageError::IoError(
+            std::io::Error::other(e),
+        )))
+    });
+    if let Err(err) = recorded {
+        warn!(
+            provider_name = %request.provider.provider_name,
+            error = %err,
+            "Failed to record provider latency"
+        );
+    }
     info!(
         request_id = request.request_id,
         node_id = %hex::encode(request.node_id),
@@ -186,6 +218,68 @@ pub async fn execute_completion_from_api(
     Ok(response)
 }
 
+/// Stream a completion and return the assembled response. Text is published as
+/// `provider_stream_delta` progress events for the command to echo as it arrives.
+///
+/// Finish reason and usage come from the stream's closing events; token counts the provider
+/// does not report are recorded as zero.
+async fn stream_completion(
+    api: &crate::api::ContextApi,
+    request: &GenerationOrchestrationRequest,
+    event_context: Option<&ExecutionEventContext>,
+    preparation: &ProviderPreparation,
+    messages: Vec<ChatMessage>,
+    options: CompletionOptions,
+) -> Result<CompletionResponse, ApiError> {
+    let stream = preparation.client.stream(messages, options).await?;
+    let mut pending = String::new();
+    let mut last_emitted = Instant::now();
+    let collected = streaming::collect_stream(stream, |text| {
+        pending.push_str(text);
+        if last_emitted.elapsed() >= STREAM_EVENT_INTERVAL {
+            emit_stream_delta(api, event_context, request, &std::mem::take(&mut pending));
+            last_emitted = Instant::now();
+        }
+    })
+    .await?;
+    if !pending.is_empty() {
+        emit_stream_delta(api, event_context, request, &pending);
+    }
+
+    let prompt_tokens = collected.prompt_tokens.unwrap_or(0);
+    let completion_tokens = collected.completion_tokens.unwrap_or(0);
+    Ok(CompletionResponse {
+        content: collected.text,
+        model: preparation.client.model_name().to_string(),
+        usage: TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens.saturating_add(completion_tokens),
+        },
+        finish_reason: collected.finish_reason,
+    })
+}
+
+fn emit_stream_delta(
+    api: &crate::api::ContextApi,
+    event_context: Option<&ExecutionEventContext>,
+    request: &GenerationOrchestrationRequest,
+    text: &str,
+) {
+    if let Some(ctx) = event_context {
+        let _ =
+            <crate::api::ContextApi as meld_execution::ExecutionProgressPort>::emit_progress_event(
+                api,
+                ctx,
+                "provider_stream_delta",
+                json!({
+                    "node_id": hex::encode(request.node_id),
+                    "text": text,
+                }),
+            );
+    }
+}
+
 fn emit_provider_event(
     api: &crate::api::ContextApi,
     event_context: Option<&ExecutionEventContext>,
@@ -9,6 +9,7 @@ use crate::metadata::frame_key_descriptor::{
 pub const KEY_PROVIDER: &str = "provider";
 pub const KEY_MODEL: &str = "model";
 pub const KEY_PROVIDER_TYPE: &str = "provider_type";
+pub const KEY_TRUNCATED: &str = "truncated";
 
 pub const DESCRIPTOR_PROVIDER: FrameMetadataKeyDescriptor = FrameMetadataKeyDescriptor {
     key: KEY_PROVIDER,
@@ -48,3 +49,17 @@ pub const DESCRIPTOR_PROVIDER_TYPE: FrameMetadataKeyDescriptor = FrameMetadataKe
     write_policy: FrameMetadataWritePolicy::Allowed,
     visibility_policy: FrameMetadataVisibilityPolicy::VisibleByDefault,
 };
+
+/// Set to `true` when the provider stopped at its token limit and the content is incomplete.
+pub const DESCRIPTOR_TRUNCATED: FrameMetadataKeyDescriptor = FrameMetadataKeyDescriptor {
+    key: KEY_TRUNCATED,
+    owner_domain: "provider",
+    schema_type: FrameMetadataSchemaType::Utf8String,
+    mutability_class: FrameMetadataMutabilityClass::Annotation,
+    hash_impact: FrameMetadataHashImpact::NonHashCritical,
+    max_bytes: DESCRIPTOR_DEFAULT_MAX_BYTES,
+    retention_policy: FrameMetadataRetentionPolicy::Persistent,
+    redaction_policy: FrameMetadataRedactionPolicy::VisibleByDefault,
+    write_policy: FrameMetadataWritePolicy::Allowed,
+    visibility_policy: FrameMetadataVisibilityPolicy::VisibleByDefault,
+};
@@ -0,0 +1,125 @@
+//! Per-provider latency tracking.
+//!
+//! Successful completions fold their duration into an exponential moving average that is
+//! persisted under `$XDG_DATA_HOME/meld/provider_latency.json`, so `provider list
+//! --show-latency` can rank providers across runs.
+
+use crate::config::xdg;
+use crate::error::{ApiError, StorageError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::warn;
+
+/// Weight given to the newest sample when updating the moving average.
+const LATENCY_EMA_ALPHA: f64 = 0.2;
+
+/// Serializes read-modify-write cycles on the latency file within one process.
+static LATENCY_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Recorded latency for one provider.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProviderLatencyStats {
+    /// Exponential moving average of completion latency in milliseconds.
+    pub average_ms: f64,
+    /// Number of samples folded into the average.
+    pub samples: u64,
+}
+
+impl ProviderLatencyStats {
+    fn record(&mut self, duration_ms: f64) {
+        self.average_ms = if self.samples == 0 {
+            duration_ms
+        } else {
+            LATENCY_EMA_ALPHA * duration_ms + (1.0 - LATENCY_EMA_ALPHA) * self.average_ms
+        };
+        self.samples += 1;
+    }
+}
+
+/// Path of the persisted latency file.
+pub fn latency_store_path() -> Result<PathBuf, ApiError> {
+    let data_home = xdg::data_home().ok_or_else(|| {
+        ApiError::ConfigError(
+            "Could not determine XDG data home directory (HOME not set)".to_string(),
+        )
+    })?;
+    Ok(data_home.join("meld").join("provider_latency.json"))
+}
+
+/// Load recorded latency keyed by provider name.
+///
+/// A missing file yields an empty map. So does a file that no longer parses (for example one
+/// truncated by a crash); latency is advisory, so it is logged and rebuilt from new samples.
+pub fn load_latency_stats() -> Result<BTreeMap<String, ProviderLatencyStats>, ApiError> {
+    let path = latency_store_path()?;
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(ApiError::StorageError(StorageError::IoError(e))),
+    };
+    match serde_json::from_str(&content) {
+        Ok(stats) => Ok(stats),
+        Err(e) => {
+            warn!(
+                path = %path.display(),
+                error = %e,
+                "Ignoring unreadable provider latency file"
+            );
+            Ok(BTreeMap::new())
+        }
+    }
+}
+
+/// Fold one completion duration into the provider's moving average and persist it.
+///
+/// Blocking file I/O; async callers should run it on a blocking thread. The file is replaced
+/// through a temporary file and rename, so readers never see a partial write.
+pub fn record_latency_sample(provider_name: &str, duration_ms: f64) -> Result<(), ApiError> {
+    let _guard = LATENCY_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut stats = load_latency_stats()?;
+    stats
+        .entry(provider_name.to_string())
+        .or_insert(ProviderLatencyStats {
+            average_ms: 0.0,
+            samples: 0,
+        })
+        .record(duration_ms);
+
+    let path = latency_store_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| ApiError::StorageError(StorageError::IoError(e)))?;
+    }
+    let content = serde_json::to_string_pretty(&stats)
+        .map_err(|e| ApiError::StorageError(StorageError::InvalidPath(e.to_string())))?;
+    // Unique per process, since the lock above does not cover other meld processes.
+    let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    std::fs::write(&temp_path, content)
+        .and_then(|()| std::fs::rename(&temp_path, &path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            ApiError::StorageError(StorageError::IoError(e))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sample_sets_average_and_later_samples_smooth() {
+        let mut stats = ProviderLatencyStats {
+            average_ms: 0.0,
+            samples: 0,
+        };
+        stats.record(100.0);
+        assert_eq!(stats.average_ms, 100.0);
+        stats.record(200.0);
+        assert!((stats.average_ms - 120.0).abs() < f64::EPSILON);
+        assert_eq!(stats.samples, 2);
+    }
+}
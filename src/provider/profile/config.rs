@@ -40,6 +40,9 @@ pub enum ProviderType {
     Ollama,
     #[serde(rename = "local")]
     LocalCustom,
+    /// Offline provider whose completions are a deterministic function of the prompt.
+    #[serde(rename = "mock")]
+    Mock,
 }
 
 impl ProviderConfig {
@@ -162,6 +165,7 @@ impl ProviderConfig {
                 Ok(ModelProvider::Anthropic {
                     model: self.model.clone(),
                     api_key,
+                    base_url: self.endpoint.clone(),
                 })
             }
             ProviderType::Ollama => Ok(ModelProvider::Ollama {
@@ -180,6 +184,9 @@ impl ProviderConfig {
                     api_key,
                 })
             }
+            ProviderType::Mock => Ok(ModelProvider::Mock {
+                model: self.model.clone(),
+            }),
         }
     }
 }
@@ -50,5 +50,6 @@ pub fn provider_type_slug(provider_type: ProviderType) -> &'static str {
         ProviderType::Anthropic => "anthropic",
         ProviderType::Ollama => "ollama",
         ProviderType::LocalCustom => "local",
+        ProviderType::Mock => "mock",
     }
 }
@@ -0,0 +1,374 @@
+//! Incremental decoding of streamed provider responses.
+//!
+//! OpenAI-compatible and Anthropic endpoints stream server-sent events; Ollama's native chat
+//! endpoint streams newline-delimited JSON. Bodies arrive in arbitrary byte chunks, so bytes are
+//! buffered until a full line is available before it is decoded into a text delta. Closing
+//! events also carry the finish reason and token counts, which are decoded alongside the text.
+
+use crate::error::ApiError;
+use crate::provider::{anthropic_finish_reason, map_http_error, CompletionStream};
+use futures::StreamExt;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// Wire format of a streamed completion body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// `data: {...}` events carrying `choices[0].delta.content`, ended by `data: [DONE]`.
+    OpenAiSse,
+    /// `data: {...}` events with text in `content_block_delta`, ended by `message_stop`.
+    AnthropicSse,
+    /// One JSON object per line carrying `message.content`, ended by `"done": true`.
+    NdJson,
+}
+
+/// One decoded event of a streamed completion.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamChunk {
+    /// Text to append to the completion; empty for events that only report metadata.
+    pub text: String,
+    /// Why generation stopped, as an OpenAI style finish reason.
+    pub finish_reason: Option<String>,
+    /// Prompt token count, when the event reports it.
+    pub prompt_tokens: Option<u32>,
+    /// Completion token count, when the event reports it.
+    pub completion_tokens: Option<u32>,
+}
+
+/// Meaning of one complete line of a streamed body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamLine {
+    /// Text or completion metadata.
+    Chunk(StreamChunk),
+    /// The provider signalled the end of the completion. NDJSON bodies end on an object that
+    /// still carries metadata; other formats end on an empty chunk.
+    Done(StreamChunk),
+    /// Blank lines, event names, keep-alives, and events without text or metadata.
+    Skip,
+}
+
+/// Decode one line (without its trailing newline) of a streamed body.
+pub fn parse_stream_line(format: StreamFormat, line: &str) -> Result<StreamLine, ApiError> {
+    let line = line.trim_end_matches('\r');
+    if line.trim().is_empty() {
+        return Ok(StreamLine::Skip);
+    }
+    match format {
+        StreamFormat::OpenAiSse => {
+            let Some(data) = sse_data(line) else {
+                return Ok(StreamLine::Skip);
+            };
+            if data == "[DONE]" {
+                return Ok(StreamLine::Done(StreamChunk::default()));
+            }
+            let event = parse_event(data)?;
+            Ok(chunk_or_skip(StreamChunk {
+                text: string_at(&event, "/choices/0/delta/content").unwrap_or_default(),
+                finish_reason: string_at(&event, "/choices/0/finish_reason"),
+                prompt_tokens: count_at(&event, "/usage/prompt_tokens"),
+                completion_tokens: count_at(&event, "/usage/completion_tokens"),
+            }))
+        }
+        StreamFormat::AnthropicSse => {
+            let Some(data) = sse_data(line) else {
+                return Ok(StreamLine::Skip);
+            };
+            let event = parse_event(data)?;
+            match event.get("type").and_then(Value::as_str) {
+                Some("content_block_delta") => Ok(chunk_or_skip(StreamChunk {
+                    text: string_at(&event, "/delta/text").unwrap_or_default(),
+                    ..StreamChunk::default()
+                })),
+                Some("message_start") => Ok(chunk_or_skip(StreamChunk {
+                    prompt_tokens: count_at(&event, "/message/usage/input_tokens"),
+                    ..StreamChunk::default()
+                })),
+                Some("message_delta") => Ok(chunk_or_skip(StreamChunk {
+                    finish_reason: event
+                        .pointer("/delta/stop_reason")
+                        .and_then(Value::as_str)
+                        .map(|reason| anthropic_finish_reason(Some(reason))),
+                    completion_tokens: count_at(&event, "/usage/output_tokens"),
+                    ..StreamChunk::default()
+                })),
+                Some("message_stop") => Ok(StreamLine::Done(StreamChunk::default())),
+                Some("error") => Err(ApiError::ProviderError(format!(
+                    "Provider stream error: {}",
+                    event["error"]
+                ))),
+                _ => Ok(StreamLine::Skip),
+            }
+        }
+        StreamFormat::NdJson => {
+            let event = parse_event(line)?;
+            if let Some(error) = event.get("error") {
+                return Err(ApiError::ProviderError(format!(
+                    "Provider stream error: {}",
+                    error
+                )));
+            }
+            let text = string_at(&event, "/message/content").unwrap_or_default();
+            if event.get("done").and_then(Value::as_bool) == Some(true) {
+                return Ok(StreamLine::Done(StreamChunk {
+                    text,
+                    finish_reason: string_at(&event, "/done_reason"),
+                    prompt_tokens: count_at(&event, "/prompt_eval_count"),
+                    completion_tokens: count_at(&event, "/eval_count"),
+                }));
+            }
+            Ok(chunk_or_skip(StreamChunk {
+                text,
+                ..StreamChunk::default()
+            }))
+        }
+    }
+}
+
+/// Turn a successful streaming HTTP response into a stream of decoded chunks.
+pub fn response_stream(response: reqwest::Response, format: StreamFormat) -> CompletionStream {
+    let state = StreamState {
+        response,
+        format,
+        buffer: Vec::new(),
+        pending: VecDeque::new(),
+        finished: false,
+    };
+    Box::pin(futures::stream::try_unfold(state, next_delta))
+}
+
+struct StreamState {
+    response: reqwest::Response,
+    format: StreamFormat,
+    buffer: Vec<u8>,
+    pending: VecDeque<StreamChunk>,
+    finished: bool,
+}
+
+async fn next_delta(
+    mut state: StreamState,
+) -> Result<Option<(StreamChunk, StreamState)>, ApiError> {
+    loop {
+        if let Some(chunk) = state.pending.pop_front() {
+            return Ok(Some((chunk, state)));
+        }
+        if state.finished {
+            return Ok(None);
+        }
+        match state.response.chunk().await.map_err(map_http_error)? {
+            Some(bytes) => state.buffer.extend_from_slice(&bytes),
+            // A final line without a trailing newline is still a line.
+            None => {
+                state.finished = true;
+                state.buffer.push(b'\n');
+            }
+        }
+        while let Some(newline) = state.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = state.buffer.drain(..=newline).collect();
+            let line = std::str::from_utf8(&line[..newline]).map_err(|e| {
+                ApiError::ProviderError(format!("Invalid UTF-8 in provider stream: {}", e))
+            })?;
+            match parse_stream_line(state.format, line)? {
+                StreamLine::Chunk(chunk) => state.pending.push_back(chunk),
+                StreamLine::Done(chunk) => {
+                    if chunk != StreamChunk::default() {
+                        state.pending.push_back(chunk);
+                    }
+                    state.finished = true;
+                    state.buffer.clear();
+                }
+                StreamLine::Skip => {}
+            }
+        }
+    }
+}
+
+/// Drain `stream`, handing each text delta to `on_text`. Returns the assembled content with the
+/// last finish reason and token counts the provider reported.
+pub async fn collect_stream<F>(
+    mut stream: CompletionStream,
+    mut on_text: F,
+) -> Result<StreamChunk, ApiError>
+where
+    F: FnMut(&str),
+{
+    let mut collected = StreamChunk::default();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if !chunk.text.is_empty() {
+            on_text(&chunk.text);
+            collected.text.push_str(&chunk.text);
+        }
+        collected.finish_reason = chunk.finish_reason.or(collected.finish_reason);
+        collected.prompt_tokens = chunk.prompt_tokens.or(collected.prompt_tokens);
+        collected.completion_tokens = chunk.completion_tokens.or(collected.completion_tokens);
+    }
+    Ok(collected)
+}
+
+fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim)
+}
+
+fn parse_event(data: &str) -> Result<Value, ApiError> {
+    serde_json::from_str(data)
+        .map_err(|e| ApiError::ProviderError(format!("Failed to parse stream event: {}", e)))
+}
+
+fn string_at(event: &Value, pointer: &str) -> Option<String> {
+    event
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn count_at(event: &Value, pointer: &str) -> Option<u32> {
+    event
+        .pointer(pointer)
+        .and_then(Value::as_u64)
+        .map(|count| u32::try_from(count).unwrap_or(u32::MAX))
+}
+
+fn chunk_or_skip(chunk: StreamChunk) -> StreamLine {
+    if chunk == StreamChunk::default() {
+        StreamLine::Skip
+    } else {
+        StreamLine::Chunk(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> StreamLine {
+        StreamLine::Chunk(StreamChunk {
+            text: text.to_string(),
+            ..StreamChunk::default()
+        })
+    }
+
+    #[test]
+    fn openai_sse_lines_yield_deltas_until_done() {
+        let format = StreamFormat::OpenAiSse;
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"data: {"choices":[{"index":0,"delta":{"content":"Hel"}}]}"#
+            )
+            .unwrap(),
+            text("Hel")
+        );
+        assert_eq!(
+            parse_stream_line(format, r#"data: {"choices":[{"index":0,"delta":{}}]}"#).unwrap(),
+            StreamLine::Skip
+        );
+        assert_eq!(parse_stream_line(format, "").unwrap(), StreamLine::Skip);
+        assert_eq!(
+            parse_stream_line(format, ": keep-alive").unwrap(),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"data: {"choices":[{"index":0,"delta":{},"finish_reason":"length"}]}"#
+            )
+            .unwrap(),
+            StreamLine::Chunk(StreamChunk {
+                finish_reason: Some("length".to_string()),
+                ..StreamChunk::default()
+            })
+        );
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"data: {"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3}}"#
+            )
+            .unwrap(),
+            StreamLine::Chunk(StreamChunk {
+                prompt_tokens: Some(12),
+                completion_tokens: Some(3),
+                ..StreamChunk::default()
+            })
+        );
+        assert_eq!(
+            parse_stream_line(format, "data: [DONE]\r").unwrap(),
+            StreamLine::Done(StreamChunk::default())
+        );
+    }
+
+    #[test]
+    fn anthropic_sse_reads_text_deltas_and_stop() {
+        let format = StreamFormat::AnthropicSse;
+        assert_eq!(
+            parse_stream_line(format, "event: content_block_delta").unwrap(),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"hi"}}"#
+            )
+            .unwrap(),
+            text("hi")
+        );
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"data: {"type":"message_start","message":{"usage":{"input_tokens":9,"output_tokens":1}}}"#
+            )
+            .unwrap(),
+            StreamLine::Chunk(StreamChunk {
+                prompt_tokens: Some(9),
+                ..StreamChunk::default()
+            })
+        );
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"data: {"type":"message_delta","delta":{"stop_reason":"max_tokens"},"usage":{"output_tokens":40}}"#
+            )
+            .unwrap(),
+            StreamLine::Chunk(StreamChunk {
+                finish_reason: Some("length".to_string()),
+                completion_tokens: Some(40),
+                ..StreamChunk::default()
+            })
+        );
+        assert_eq!(
+            parse_stream_line(format, r#"data: {"type":"message_stop"}"#).unwrap(),
+            StreamLine::Done(StreamChunk::default())
+        );
+        assert!(parse_stream_line(
+            format,
+            r#"data: {"type":"error","error":{"type":"overloaded_error"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn ndjson_reads_message_content_until_done() {
+        let format = StreamFormat::NdJson;
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"{"model":"llama2","message":{"role":"assistant","content":"ok"},"done":false}"#
+            )
+            .unwrap(),
+            text("ok")
+        );
+        assert_eq!(
+            parse_stream_line(
+                format,
+                r#"{"model":"llama2","message":{"role":"assistant","content":""},"done":true,"done_reason":"length","prompt_eval_count":20,"eval_count":64}"#
+            )
+            .unwrap(),
+            StreamLine::Done(StreamChunk {
+                text: String::new(),
+                finish_reason: Some("length".to_string()),
+                prompt_tokens: Some(20),
+                completion_tokens: Some(64),
+            })
+        );
+        assert!(parse_stream_line(format, "not json").is_err());
+    }
+}
@@ -1,8 +1,10 @@
 use crate::api::ContextApi;
 use crate::cli::{
     format_provider_list_result_json, format_provider_list_result_text,
-    format_provider_show_result_json, format_provider_show_result_text,
-    format_provider_test_result, format_provider_validation_result, ProviderCommands,
+    format_provider_prompt_test_result, format_provider_show_result_json,
+    format_provider_show_result_text, format_provider_test_result,
+    format_provider_validation_result, format_provider_validation_results_all, to_json_output,
+    ProviderCommands,
 };
 use crate::error::ApiError;
 use crate::provider::commands::ProviderCommandService;
@@ -34,7 +36,8 @@ pub fn handle_cli_command(
         ProviderCommands::List {
             format,
             type_filter,
-        } => handle_list(api, format, type_filter.as_deref()),
+            show_latency,
+        } => handle_list(api, format, type_filter.as_deref(), *show_latency),
         ProviderCommands::Show {
             provider_name,
             format,
@@ -42,26 +45,51 @@ pub fn handle_cli_command(
         } => handle_show(api, provider_name, format, *include_credentials),
         ProviderCommands::Validate {
             provider_name,
+            all,
+            fail_fast,
             test_connectivity,
             check_model,
+            check_credentials_only,
             verbose,
-        } => handle_validate(
-            api,
-            provider_name,
-            *test_connectivity,
-            *check_model,
-            *verbose,
-        ),
+        } => {
+            if *all {
+                return handle_validate_all(
+                    api,
+                    *test_connectivity,
+                    *check_model,
+                    *fail_fast,
+                    *verbose,
+                );
+            }
+            let provider_name = provider_name.as_deref().ok_or_else(|| {
+                ApiError::ConfigError(
+                    "Provider name required unless --all is specified".to_string(),
+                )
+            })?;
+            if *check_credentials_only {
+                handle_check_credentials(api, provider_name, *verbose)
+            } else {
+                handle_validate(
+                    api,
+                    provider_name,
+                    *test_connectivity,
+                    *check_model,
+                    *verbose,
+                )
+            }
+        }
         ProviderCommands::Test {
             provider_name,
             model,
             timeout,
+            prompt,
         } => handle_test(
             api,
             progress,
             provider_name,
             model.as_deref(),
             *timeout,
+            prompt.as_deref(),
             session_id,
         ),
         ProviderCommands::Create {
@@ -72,21 +100,37 @@ pub fn handle_cli_command(
             api_key,
             interactive,
             non_interactive,
-        } => handle_create(
-            api,
-            provider_name,
-            type_.as_deref(),
-            model.as_deref(),
-            endpoint.as_deref(),
-            api_key.as_deref(),
-            *interactive,
-            *non_interactive,
-        ),
+            clone,
+            copy_key,
+            force,
+        } => match clone {
+            Some(source_name) => handle_clone(
+                api,
+                provider_name,
+                source_name,
+                model.as_deref(),
+                endpoint.as_deref(),
+                api_key.as_deref(),
+                *copy_key,
+                *force,
+            ),
+            None => handle_create(
+                api,
+                provider_name,
+                type_.as_deref(),
+                model.as_deref(),
+                endpoint.as_deref(),
+                api_key.as_deref(),
+                *interactive,
+                *non_interactive,
+            ),
+        },
         ProviderCommands::Edit {
             provider_name,
             model,
             endpoint,
             api_key,
+            unset,
             editor,
         } => handle_edit(
             api,
@@ -94,6 +138,7 @@ pub fn handle_cli_command(
             model.as_deref(),
             endpoint.as_deref(),
             api_key.as_deref(),
+            unset,
             editor.as_deref(),
         ),
         ProviderCommands::Remove {
@@ -107,9 +152,10 @@ fn handle_list(
     api: &ContextApi,
     format: &str,
     type_filter: Option<&str>,
+    show_latency: bool,
 ) -> Result<String, ApiError> {
     let registry = api.provider_registry().read();
-    let result = ProviderCommandService::run_list(&registry, type_filter)?;
+    let result = ProviderCommandService::run_list(&registry, type_filter, show_latency)?;
     match format {
         "json" => Ok(format_provider_list_result_json(&result)),
         _ => Ok(format_provider_list_result_text(&result)),
@@ -147,6 +193,44 @@ fn handle_validate(
     Ok(format_provider_validation_result(&result, verbose))
 }
 
+fn handle_validate_all(
+    api: &ContextApi,
+    test_connectivity: bool,
+    check_model: bool,
+    fail_fast: bool,
+    verbose: bool,
+) -> Result<String, ApiError> {
+    let registry = api.provider_registry().read();
+    let result = ProviderCommandService::run_validate_all(
+        &registry,
+        test_connectivity,
+        check_model,
+        fail_fast,
+    )?;
+    if result.results.is_empty() {
+        return Ok("No providers found to validate.".to_string());
+    }
+    let report = format_provider_validation_results_all(&result, verbose);
+    if result.invalid_count() > 0 {
+        return Err(ApiError::ConfigError(format!(
+            "{} provider(s) failed validation\n\n{}",
+            result.invalid_count(),
+            report
+        )));
+    }
+    Ok(report)
+}
+
+fn handle_check_credentials(
+    api: &ContextApi,
+    provider_name: &str,
+    verbose: bool,
+) -> Result<String, ApiError> {
+    let registry = api.provider_registry().read();
+    let result = ProviderCommandService::run_check_credentials(&registry, provider_name)?;
+    Ok(format_provider_validation_result(&result, verbose))
+}
+
 fn handle_status(
     api: &ContextApi,
     format: &str,
@@ -165,7 +249,7 @@ fn handle_status(
         .collect();
 
     if format == "json" {
-        serde_json::to_string_pretty(&ProviderStatusOutput {
+        to_json_output(&ProviderStatusOutput {
             providers: entries.clone(),
             total: entries.len(),
         })
@@ -181,6 +265,7 @@ fn handle_test(
     provider_name: &str,
     model_override: Option<&str>,
     timeout: u64,
+    prompt: Option<&str>,
     session_id: &str,
 ) -> Result<String, ApiError> {
     let registry = api.provider_registry().read();
@@ -206,11 +291,53 @@ fn handle_test(
     );
 
     let start = std::time::Instant::now();
+    if let Some(prompt) = prompt {
+        let result = ProviderCommandService::run_test_prompt(
+            &registry,
+            provider_name,
+            model_override,
+            prompt,
+            timeout,
+        )?;
+        let elapsed_ms = start.elapsed().as_millis();
+        emit_test_outcome(
+            progress,
+            session_id,
+            &result.provider_name,
+            &result.model,
+            elapsed_ms,
+            result.error_message.clone(),
+        );
+        return Ok(format_provider_prompt_test_result(
+            &result,
+            Some(elapsed_ms),
+        ));
+    }
     let result =
         ProviderCommandService::run_test(&registry, provider_name, model_override, timeout)?;
     let elapsed_ms = start.elapsed().as_millis();
 
-    let event_name = if result.connectivity_ok {
+    emit_test_outcome(
+        progress,
+        session_id,
+        &result.provider_name,
+        &result.model_checked,
+        elapsed_ms,
+        result.error_message.clone(),
+    );
+
+    Ok(format_provider_test_result(&result, Some(elapsed_ms)))
+}
+
+fn emit_test_outcome(
+    progress: &Arc<ProgressRuntime>,
+    session_id: &str,
+    provider_name: &str,
+    model: &str,
+    elapsed_ms: u128,
+    error: Option<String>,
+) {
+    let event_name = if error.is_none() {
         "provider_response_received"
     } else {
         "provider_request_failed"
@@ -221,15 +348,13 @@ fn handle_test(
         json!(ProviderLifecycleEventData {
             node_id: "provider_test".to_string(),
             agent_id: "provider_test".to_string(),
-            provider_name: result.provider_name.clone(),
-            frame_type: result.model_checked.clone(),
+            provider_name: provider_name.to_string(),
+            frame_type: model.to_string(),
             duration_ms: Some(elapsed_ms),
-            error: result.error_message.clone(),
+            error,
             retry_count: Some(0),
         }),
     );
-
-    Ok(format_provider_test_result(&result, Some(elapsed_ms)))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -290,12 +415,52 @@ fn handle_create(
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
+fn handle_clone(
+    api: &ContextApi,
+    provider_name: &str,
+    source_name: &str,
+    model: Option<&str>,
+    endpoint: Option<&str>,
+    api_key: Option<&str>,
+    copy_key: bool,
+    force: bool,
+) -> Result<String, ApiError> {
+    let mut registry = api.provider_registry().write();
+    let source_has_key = registry
+        .get_or_error(source_name)?
+        .api_key
+        .as_ref()
+        .is_some_and(|key| !key.is_empty());
+    let result = ProviderCommandService::run_clone(
+        &mut registry,
+        provider_name,
+        source_name,
+        model,
+        endpoint,
+        api_key,
+        copy_key,
+        force,
+    )?;
+
+    let mut output = format!(
+        "Provider created: {} (cloned from {})\nConfiguration file: {}",
+        result.provider_name,
+        source_name,
+        result.config_path.display()
+    );
+    if source_has_key && api_key.is_none() && !copy_key {
+        output.push_str("\nAPI key not copied. Use --copy-key or --api-key to set one.");
+    }
+    Ok(output)
+}
+
 fn create_interactive() -> Result<ProviderCreationDialogResult, ApiError> {
     use dialoguer::{Input, Select};
 
     let type_selection = Select::new()
         .with_prompt("Provider type")
-        .items(&["openai", "anthropic", "ollama", "local"])
+        .items(&["openai", "anthropic", "ollama", "local", "mock"])
         .default(0)
         .interact()
         .map_err(|e| ApiError::ConfigError(format!("Failed to get user input: {}", e)))?;
@@ -305,6 +470,7 @@ fn create_interactive() -> Result<ProviderCreationDialogResult, ApiError> {
         1 => crate::provider::ProviderType::Anthropic,
         2 => crate::provider::ProviderType::Ollama,
         3 => crate::provider::ProviderType::LocalCustom,
+        4 => crate::provider::ProviderType::Mock,
         _ => unreachable!(),
     };
 
@@ -333,7 +499,10 @@ fn create_interactive() -> Result<ProviderCreationDialogResult, ApiError> {
     };
 
     let env_var = ProviderCommandService::required_api_key_env_var(provider_type).unwrap_or("");
-    let api_key = if provider_type == crate::provider::ProviderType::Ollama {
+    let api_key = if matches!(
+        provider_type,
+        crate::provider::ProviderType::Ollama | crate::provider::ProviderType::Mock
+    ) {
         None
     } else {
         let prompt = if env_var.is_empty() {
@@ -390,9 +559,10 @@ fn handle_edit(
     model: Option<&str>,
     endpoint: Option<&str>,
     api_key: Option<&str>,
+    unset: &[String],
     editor: Option<&str>,
 ) -> Result<String, ApiError> {
-    if model.is_some() || endpoint.is_some() || api_key.is_some() {
+    if model.is_some() || endpoint.is_some() || api_key.is_some() || !unset.is_empty() {
         let mut registry = api.provider_registry().write();
         ProviderCommandService::run_update_flags(
             &mut registry,
@@ -400,6 +570,7 @@ fn handle_edit(
             model,
             endpoint,
             api_key,
+            unset,
         )?;
     } else {
         edit_with_editor(api, provider_name, editor)?;
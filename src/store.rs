@@ -9,20 +9,36 @@ pub mod persistence;
 pub use persistence::SledNodeRecordStore;
 
 use crate::error::StorageError;
-use crate::store::node_metadata::NodeMetadata;
+use crate::store::node_metadata::{NodeMetadata, BINARY_METADATA_KEY};
 use crate::tree::node::MerkleNode;
 use crate::tree::Tree;
 use crate::types::{Hash, NodeID};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Node type enumeration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NodeType {
-    File { size: u64, content_hash: [u8; 32] },
+    File {
+        size: u64,
+        content_hash: [u8; 32],
+    },
     Directory,
+    /// Unfollowed symbolic link; the target is recorded but never read
+    Symlink {
+        target: PathBuf,
+    },
 }
 
+/// Node metadata marker set by `workspace delete --keep-frames`: the node is tombstoned but its
+/// head frames stay retrievable, and `compact` skips it unless told to purge kept frames.
+pub const KEEP_FRAMES_METADATA_KEY: &str = "keep_frames";
+
+/// Node metadata marker set by `context touch`: comma-separated frame types whose heads are stale.
+/// Generation regenerates those heads as if forced; a new head of the type clears it.
+pub const STALE_FRAME_TYPES_METADATA_KEY: &str = "stale_frame_types";
+
 /// NodeRecord: Metadata and relationships for a filesystem node
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeRecord {
@@ -37,11 +53,51 @@ pub struct NodeRecord {
     pub tombstoned_at: Option<u64>,
 }
 
+impl NodeRecord {
+    /// Whether this record was tombstoned with its frames kept retrievable.
+    pub fn keeps_frames(&self) -> bool {
+        self.tombstoned_at.is_some() && self.metadata.contains_key(KEEP_FRAMES_METADATA_KEY)
+    }
+
+    /// Frame types whose heads `context touch` marked stale.
+    pub fn stale_frame_types(&self) -> Vec<&str> {
+        self.metadata
+            .get(STALE_FRAME_TYPES_METADATA_KEY)
+            .map(|types| types.split(',').filter(|t| !t.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether the head of `frame_type` is marked stale.
+    pub fn is_head_stale(&self, frame_type: &str) -> bool {
+        self.stale_frame_types().contains(&frame_type)
+    }
+}
+
 /// NodeRecord Store interface
 pub trait NodeRecordStore {
     fn get(&self, node_id: &NodeID) -> Result<Option<NodeRecord>, StorageError>;
+    /// Write the full record, replacing any stored metadata.
+    ///
+    /// Writers that only own some metadata keys should use [`NodeRecordStore::merge_metadata`];
+    /// scan writes through [`NodeRecordStore::put_preserving_metadata`] so unchanged nodes keep
+    /// their metadata.
     fn put(&self, record: &NodeRecord) -> Result<(), StorageError>;
 
+    /// Write a freshly built record, keeping metadata already stored under the same node id.
+    ///
+    /// Unchanged nodes keep their id across scans, so metadata written by other owners (via
+    /// [`NodeRecordStore::merge_metadata`]) survives a rescan. Keys on `record` win on conflict.
+    /// The read and the write happen atomically, so a concurrent merge is never lost.
+    fn put_preserving_metadata(&self, record: NodeRecord) -> Result<(), StorageError>;
+
+    /// Merge `updates` into a node's metadata atomically, leaving structural fields and
+    /// other metadata keys untouched. Returns the updated record.
+    fn merge_metadata(
+        &self,
+        node_id: &NodeID,
+        updates: HashMap<String, String>,
+    ) -> Result<NodeRecord, StorageError>;
+
     /// Find a node record by its canonicalized path
     ///
     /// Returns the NodeRecord if found, None if the path is not in the tree.
@@ -60,6 +116,12 @@ pub trait NodeRecordStore {
     /// Used for restore path resolution. Path key is only removed on purge.
     fn get_by_path(&self, path: &Path) -> Result<Option<NodeRecord>, StorageError>;
 
+    /// List node records at `prefix` or below it, including tombstoned nodes.
+    ///
+    /// Matches whole path components: `/a/b` covers `/a/b` and `/a/b/c` but not `/a/bc`.
+    /// Served from the path index, so the cost follows the subtree size, not the store size.
+    fn list_under_path(&self, prefix: &Path) -> Result<Vec<NodeRecord>, StorageError>;
+
     /// Mark a node as tombstoned. Sets tombstoned_at to current timestamp.
     /// Does not tombstone descendants; caller is responsible for cascade.
     fn tombstone(&self, node_id: &NodeID) -> Result<NodeRecord, StorageError>;
@@ -74,6 +136,12 @@ pub trait NodeRecordStore {
     /// List all tombstoned node IDs, optionally filtered by age (older_than timestamp).
     fn list_tombstoned(&self, older_than: Option<u64>) -> Result<Vec<NodeID>, StorageError>;
 
+    /// Read a store-level setting written by [`NodeRecordStore::put_setting`].
+    fn get_setting(&self, key: &str) -> Result<Option<String>, StorageError>;
+
+    /// Record a store-level setting, such as the walker options of the last scan.
+    fn put_setting(&self, key: &str, value: &str) -> Result<(), StorageError>;
+
     /// Flush any buffered writes to disk. Default implementation is a no-op.
     fn flush(&self) -> Result<(), StorageError> {
         Ok(())
@@ -81,6 +149,18 @@ pub trait NodeRecordStore {
 }
 
 impl NodeRecord {
+    /// True when the tree builder flagged this file as binary.
+    pub fn is_binary(&self) -> bool {
+        self.metadata
+            .get(BINARY_METADATA_KEY)
+            .is_some_and(|value| value == "true")
+    }
+
+    /// True for symlink nodes, which are indexed but never generated.
+    pub fn is_symlink(&self) -> bool {
+        matches!(self.node_type, NodeType::Symlink { .. })
+    }
+
     /// Convert a MerkleNode to a NodeRecord
     ///
     /// Requires the tree to look up parent relationships.
@@ -126,6 +206,22 @@ impl NodeRecord {
                     tombstoned_at: None,
                 })
             }
+            MerkleNode::Symlink(link) => Ok(NodeRecord {
+                node_id,
+                path: link.path.clone(),
+                node_type: NodeType::Symlink {
+                    target: link.target.clone(),
+                },
+                children: vec![],
+                parent: tree.find_parent(&node_id),
+                frame_set_root: None,
+                metadata: link
+                    .metadata
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+                tombstoned_at: None,
+            }),
         }
     }
 
@@ -138,7 +234,7 @@ impl NodeRecord {
     ) -> Result<(), StorageError> {
         for (node_id, node) in &tree.nodes {
             let record = Self::from_merkle_node(*node_id, node, tree)?;
-            store.put(&record)?;
+            store.put_preserving_metadata(record)?;
         }
         Ok(())
     }
@@ -4,10 +4,15 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 
-/// Node metadata contract type.
+/// File metadata key set to `"true"` when the content looks binary.
+pub const BINARY_METADATA_KEY: &str = "is_binary";
+
+/// Node metadata contract type. Keys serialize in sorted order.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(transparent)]
-pub struct NodeMetadata(HashMap<String, String>);
+pub struct NodeMetadata(
+    #[serde(serialize_with = "crate::metadata::serialize_sorted_map")] HashMap<String, String>,
+);
 
 impl NodeMetadata {
     pub fn new() -> Self {
@@ -5,7 +5,9 @@ use crate::store::{NodeRecord, NodeRecordStore};
 use crate::types::NodeID;
 use bincode;
 use sled;
-use std::path::Path;
+use sled::transaction::{ConflictableTransactionError, TransactionError};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tracing::warn;
 
 fn deserialize_node_record(bytes: &[u8]) -> Result<NodeRecord, StorageError> {
@@ -26,14 +28,61 @@ fn serialize_node_record(record: &NodeRecord) -> Result<Vec<u8>, StorageError> {
     })
 }
 
+/// Error for a failed `sled::open`, naming the database path.
+pub(crate) fn sled_open_error(path: &Path, err: sled::Error) -> StorageError {
+    let source = match err {
+        sled::Error::Io(io_err) => io_err,
+        other => std::io::Error::other(other.to_string()),
+    };
+    StorageError::io_context("open sled database", path, source)
+}
+
 fn is_corrupt_node_record_error(err: &StorageError) -> bool {
     matches!(err, StorageError::IoError(io_err) if io_err.kind() == std::io::ErrorKind::InvalidData)
 }
 
 fn is_node_record_key(key: &[u8]) -> bool {
-    // Path index keys are namespaced as "path:<canonical-path>" and can
-    // coincidentally be 32 bytes long, so length alone is not sufficient.
-    !key.starts_with(b"path:") && key.len() == 32
+    // Path index and setting keys are namespaced ("path:<canonical-path>", "meta:<name>") and
+    // can coincidentally be 32 bytes long, so length alone is not sufficient.
+    !key.starts_with(PATH_KEY_PREFIX.as_bytes())
+        && !key.starts_with(META_KEY_PREFIX.as_bytes())
+        && key.len() == 32
+}
+
+/// Namespace for the path → NodeID secondary index, kept in the same tree as the records.
+const PATH_KEY_PREFIX: &str = "path:";
+
+/// Namespace for store-level settings and markers, kept in the same tree as the records.
+const META_KEY_PREFIX: &str = "meta:";
+
+/// Written once every node record has a path index entry; stores opened without it are
+/// reindexed on open.
+const PATH_INDEX_MARKER_KEY: &[u8] = b"meta:path_index";
+
+/// Suffixes of the fresh copy and the moved-aside original during a vacuum.
+const VACUUM_FRESH_SUFFIX: &str = ".vacuum";
+const VACUUM_OLD_SUFFIX: &str = ".vacuum-old";
+
+fn path_key(path: &Path) -> String {
+    format!("{}{}", PATH_KEY_PREFIX, path.to_string_lossy())
+}
+
+fn serialize_path_value(node_id: &NodeID) -> Result<Vec<u8>, StorageError> {
+    bincode::serialize(node_id).map_err(|e| {
+        StorageError::IoError(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Failed to serialize node ID for path mapping: {}", e),
+        ))
+    })
+}
+
+fn deserialize_path_value(bytes: &[u8]) -> Result<NodeID, StorageError> {
+    bincode::deserialize(bytes).map_err(|e| {
+        StorageError::IoError(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Failed to deserialize node ID from path mapping: {}", e),
+        ))
+    })
 }
 
 /// Sled-based implementation of NodeRecordStore
@@ -47,18 +96,18 @@ impl SledNodeRecordStore {
     /// The path can be a directory (sled will create a database there) or
     /// a file path (sled will use it as the database file).
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, StorageError> {
-        let db = sled::open(path).map_err(|e| {
-            StorageError::IoError(std::io::Error::other(format!(
-                "Failed to open sled database: {}",
-                e
-            )))
-        })?;
-        Ok(Self { db })
+        let path = path.as_ref();
+        let db = sled::open(path).map_err(|e| sled_open_error(path, e))?;
+        Self::from_db(db)
     }
 
     /// Create a new SledNodeRecordStore from an existing sled database handle.
-    pub fn from_db(db: sled::Db) -> Self {
-        Self { db }
+    ///
+    /// Rebuilds the path index first if the database predates it.
+    pub fn from_db(db: sled::Db) -> Result<Self, StorageError> {
+        let store = Self { db };
+        store.ensure_path_index()?;
+        Ok(store)
     }
 
     /// Get the underlying sled database (for advanced operations)
@@ -97,13 +146,8 @@ impl NodeRecordStore for SledNodeRecordStore {
 
         // Store path-to-NodeID mapping for efficient path lookups
         // Use a prefix to separate path mappings from node records
-        let path_key = format!("path:{}", record.path.to_string_lossy());
-        let path_value = bincode::serialize(&record.node_id).map_err(|e| {
-            StorageError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to serialize node ID for path mapping: {}", e),
-            ))
-        })?;
+        let path_key = path_key(&record.path);
+        let path_value = serialize_path_value(&record.node_id)?;
 
         self.db
             .insert(path_key.as_bytes(), path_value)
@@ -117,6 +161,66 @@ impl NodeRecordStore for SledNodeRecordStore {
         Ok(())
     }
 
+    fn put_preserving_metadata(&self, record: NodeRecord) -> Result<(), StorageError> {
+        let key = record.node_id.as_slice().to_vec();
+        let path_key = path_key(&record.path);
+        let path_value = serialize_path_value(&record.node_id)?;
+        self.db
+            .transaction(|tx| {
+                // Transactions may retry, so merge into a fresh copy on each attempt.
+                let mut merged = record.clone();
+                if let Some(bytes) = tx.get(&key)? {
+                    let existing = deserialize_node_record(&bytes)
+                        .map_err(ConflictableTransactionError::Abort)?;
+                    for (meta_key, value) in existing.metadata {
+                        merged.metadata.entry(meta_key).or_insert(value);
+                    }
+                }
+                let value =
+                    serialize_node_record(&merged).map_err(ConflictableTransactionError::Abort)?;
+                tx.insert(key.as_slice(), value)?;
+                tx.insert(path_key.as_bytes(), path_value.as_slice())?;
+                Ok(())
+            })
+            .map_err(|e| match e {
+                TransactionError::Abort(err) => err,
+                TransactionError::Storage(err) => StorageError::IoError(std::io::Error::other(
+                    format!("Failed to put node record: {}", err),
+                )),
+            })
+    }
+
+    fn merge_metadata(
+        &self,
+        node_id: &NodeID,
+        updates: HashMap<String, String>,
+    ) -> Result<NodeRecord, StorageError> {
+        let key = node_id.as_slice();
+        self.db
+            .transaction(|tx| {
+                let Some(bytes) = tx.get(key)? else {
+                    return Err(ConflictableTransactionError::Abort(
+                        StorageError::InvalidPath("Node not found".to_string()),
+                    ));
+                };
+                let mut record =
+                    deserialize_node_record(&bytes).map_err(ConflictableTransactionError::Abort)?;
+                for (meta_key, value) in &updates {
+                    record.metadata.insert(meta_key.clone(), value.clone());
+                }
+                let value =
+                    serialize_node_record(&record).map_err(ConflictableTransactionError::Abort)?;
+                tx.insert(key, value)?;
+                Ok(record)
+            })
+            .map_err(|e| match e {
+                TransactionError::Abort(err) => err,
+                TransactionError::Storage(err) => StorageError::IoError(std::io::Error::other(
+                    format!("Failed to merge node metadata: {}", err),
+                )),
+            })
+    }
+
     fn find_by_path(&self, path: &Path) -> Result<Option<NodeRecord>, StorageError> {
         let record = self.get_by_path(path)?;
         // Active-only: skip tombstoned nodes
@@ -124,8 +228,7 @@ impl NodeRecordStore for SledNodeRecordStore {
     }
 
     fn get_by_path(&self, path: &Path) -> Result<Option<NodeRecord>, StorageError> {
-        let path_str = path.to_string_lossy();
-        let path_key = format!("path:{}", path_str);
+        let path_key = path_key(path);
 
         match self.db.get(path_key.as_bytes()).map_err(|e| {
             StorageError::IoError(std::io::Error::other(format!(
@@ -134,18 +237,42 @@ impl NodeRecordStore for SledNodeRecordStore {
             )))
         })? {
             Some(node_id_bytes) => {
-                let node_id: NodeID = bincode::deserialize(&node_id_bytes).map_err(|e| {
-                    StorageError::IoError(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        format!("Failed to deserialize node ID from path mapping: {}", e),
-                    ))
-                })?;
+                let node_id = deserialize_path_value(&node_id_bytes)?;
                 self.get(&node_id)
             }
             None => Ok(None),
         }
     }
 
+    fn list_under_path(&self, prefix: &Path) -> Result<Vec<NodeRecord>, StorageError> {
+        // The key scan is by string prefix, so `/a/b` also yields `/a/bc`; keep only mappings
+        // whose path has `prefix` as a leading run of components. Rebuilding the prefix from
+        // its components drops a trailing separator, so the prefix's own mapping is scanned too.
+        let prefix: PathBuf = prefix.components().collect();
+        let mut records = Vec::new();
+        for item in self.db.scan_prefix(path_key(&prefix).as_bytes()) {
+            let (key, node_id_bytes) = item.map_err(|e| {
+                StorageError::IoError(std::io::Error::other(format!(
+                    "Failed to scan path mappings: {}",
+                    e
+                )))
+            })?;
+            let key = String::from_utf8_lossy(&key);
+            let mapped_path = Path::new(&key[PATH_KEY_PREFIX.len()..]);
+            if !mapped_path.starts_with(&prefix) {
+                continue;
+            }
+            let node_id = deserialize_path_value(&node_id_bytes)?;
+            // Skip mappings whose record was removed or has since moved.
+            if let Some(record) = self.get(&node_id)? {
+                if record.path.starts_with(&prefix) {
+                    records.push(record);
+                }
+            }
+        }
+        Ok(records)
+    }
+
     fn list_all(&self) -> Result<Vec<NodeRecord>, StorageError> {
         let mut records = Vec::new();
         for item in self.db.iter() {
@@ -223,13 +350,18 @@ impl NodeRecordStore for SledNodeRecordStore {
                 e
             )))
         })?;
-        let path_key = format!("path:{}", record.path.to_string_lossy());
-        self.db.remove(path_key.as_bytes()).map_err(|e| {
-            StorageError::IoError(std::io::Error::other(format!(
-                "Failed to remove path mapping: {}",
-                e
-            )))
-        })?;
+        // A newer node may have taken over the path since; only drop the mapping if it is ours.
+        let path_key = path_key(&record.path);
+        let path_value = serialize_path_value(node_id)?;
+        let _ = self
+            .db
+            .compare_and_swap(path_key.as_bytes(), Some(path_value), None::<&[u8]>)
+            .map_err(|e| {
+                StorageError::IoError(std::io::Error::other(format!(
+                    "Failed to remove path mapping: {}",
+                    e
+                )))
+            })?;
         Ok(())
     }
 
@@ -266,6 +398,34 @@ impl NodeRecordStore for SledNodeRecordStore {
         Ok(out)
     }
 
+    fn get_setting(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let value = self
+            .db
+            .get(format!("{}{}", META_KEY_PREFIX, key).as_bytes())
+            .map_err(|e| {
+                StorageError::IoError(std::io::Error::other(format!(
+                    "Failed to read store setting {}: {}",
+                    key, e
+                )))
+            })?;
+        Ok(value.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    fn put_setting(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.db
+            .insert(
+                format!("{}{}", META_KEY_PREFIX, key).as_bytes(),
+                value.as_bytes(),
+            )
+            .map_err(|e| {
+                StorageError::IoError(std::io::Error::other(format!(
+                    "Failed to write store setting {}: {}",
+                    key, e
+                )))
+            })?;
+        Ok(())
+    }
+
     fn flush(&self) -> Result<(), StorageError> {
         self.db.flush().map_err(|e| {
             StorageError::IoError(std::io::Error::other(format!(
@@ -302,14 +462,8 @@ impl SledNodeRecordStore {
             batch.insert(key, value);
 
             // Maintain the same path secondary index written by put.
-            let path_key = format!("path:{}", record.path.to_string_lossy());
-            let path_value = bincode::serialize(&record.node_id).map_err(|e| {
-                StorageError::IoError(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Failed to serialize node ID for path mapping: {}", e),
-                ))
-            })?;
-            batch.insert(path_key.as_bytes(), path_value);
+            let path_key = path_key(&record.path);
+            batch.insert(path_key.as_bytes(), serialize_path_value(&record.node_id)?);
         }
 
         self.db.apply_batch(batch).map_err(|e| {
@@ -322,6 +476,55 @@ impl SledNodeRecordStore {
         Ok(())
     }
 
+    /// Rebuild the path index unless it is already marked complete.
+    ///
+    /// Adds a mapping for every record whose path has none, preferring an active record when
+    /// several share a path, and leaves existing mappings alone. Returns the number of mappings
+    /// written.
+    pub fn ensure_path_index(&self) -> Result<usize, StorageError> {
+        let sled_error = |action: &str, e: sled::Error| {
+            StorageError::IoError(std::io::Error::other(format!(
+                "Failed to {} during path index rebuild: {}",
+                action, e
+            )))
+        };
+        if self
+            .db
+            .contains_key(PATH_INDEX_MARKER_KEY)
+            .map_err(|e| sled_error("read marker", e))?
+        {
+            return Ok(0);
+        }
+        let mut by_path: HashMap<String, (NodeID, bool)> = HashMap::new();
+        for record in self.list_all()? {
+            let active = record.tombstoned_at.is_none();
+            let entry = by_path
+                .entry(path_key(&record.path))
+                .or_insert((record.node_id, active));
+            if active && !entry.1 {
+                *entry = (record.node_id, active);
+            }
+        }
+        let mut batch = sled::Batch::default();
+        let mut written = 0;
+        for (key, (node_id, _)) in by_path {
+            if self
+                .db
+                .contains_key(key.as_bytes())
+                .map_err(|e| sled_error("read mapping", e))?
+            {
+                continue;
+            }
+            batch.insert(key.as_bytes(), serialize_path_value(&node_id)?);
+            written += 1;
+        }
+        batch.insert(PATH_INDEX_MARKER_KEY, b"1".as_slice());
+        self.db
+            .apply_batch(batch)
+            .map_err(|e| sled_error("apply batch", e))?;
+        Ok(written)
+    }
+
     /// Flush all pending writes to disk
     pub fn flush(&self) -> Result<(), StorageError> {
         self.db.flush().map_err(|e| {
@@ -334,12 +537,213 @@ impl SledNodeRecordStore {
     }
 }
 
+/// Sibling path of `path` with `suffix` appended to its file name.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Reclaim the space held by purged data in the sled database at `path`.
+///
+/// Sled never shrinks its log while entries are rewritten in place, so every tree is copied
+/// into a fresh database next to `path` (`<name>.vacuum`), which then replaces the original.
+/// The database must not be open anywhere, including in this process; opening it fails
+/// otherwise. A swap interrupted by a crash is finished by [`recover_interrupted_vacuum`].
+pub fn vacuum_sled_store(path: &Path) -> Result<(), StorageError> {
+    let sled_error = |action: &str, e: sled::Error| {
+        StorageError::IoError(std::io::Error::other(format!(
+            "Failed to {} during vacuum: {}",
+            action, e
+        )))
+    };
+    recover_interrupted_vacuum(path)?;
+    let fresh_path = sibling_path(path, VACUUM_FRESH_SUFFIX);
+    let old_path = sibling_path(path, VACUUM_OLD_SUFFIX);
+    {
+        let db = sled::open(path).map_err(|e| sled_open_error(path, e))?;
+        let fresh = sled::open(&fresh_path).map_err(|e| sled_open_error(&fresh_path, e))?;
+        for name in db.tree_names() {
+            let tree = db
+                .open_tree(&name)
+                .map_err(|e| sled_error("open tree", e))?;
+            let fresh_tree = fresh
+                .open_tree(&name)
+                .map_err(|e| sled_error("open tree", e))?;
+            for item in tree.iter() {
+                let (key, value) = item.map_err(|e| sled_error("read entry", e))?;
+                fresh_tree
+                    .insert(key, value)
+                    .map_err(|e| sled_error("copy entry", e))?;
+            }
+        }
+        fresh.flush().map_err(|e| sled_error("flush copy", e))?;
+    }
+    std::fs::rename(path, &old_path)
+        .map_err(|e| StorageError::io_context("move node store aside", path, e))?;
+    std::fs::rename(&fresh_path, path)
+        .map_err(|e| StorageError::io_context("replace node store", path, e))?;
+    std::fs::remove_dir_all(&old_path)
+        .map_err(|e| StorageError::io_context("remove vacuumed node store", &old_path, e))?;
+    Ok(())
+}
+
+/// Finish or roll back a [`vacuum_sled_store`] interrupted by a crash. Call before opening
+/// the database at `path`.
+///
+/// The fresh copy is flushed before the original is moved aside, so a missing `path` with
+/// a fresh copy present means the copy is complete and only the final rename was lost.
+/// A fresh copy next to an existing `path` may be partial and is discarded.
+pub fn recover_interrupted_vacuum(path: &Path) -> Result<(), StorageError> {
+    let fresh_path = sibling_path(path, VACUUM_FRESH_SUFFIX);
+    let old_path = sibling_path(path, VACUUM_OLD_SUFFIX);
+    if !path.exists() && fresh_path.exists() {
+        warn!(path = %path.display(), "Finishing interrupted node store vacuum");
+        std::fs::rename(&fresh_path, path)
+            .map_err(|e| StorageError::io_context("replace node store", path, e))?;
+    }
+    for leftover in [&fresh_path, &old_path] {
+        if leftover.exists() {
+            std::fs::remove_dir_all(leftover).map_err(|e| {
+                StorageError::io_context("remove interrupted vacuum copy", leftover, e)
+            })?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::store::NodeType;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_merge_metadata_concurrent_disjoint_keys_survive() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = std::sync::Arc::new(SledNodeRecordStore::new(temp_dir.path()).unwrap());
+        let node_id = [7u8; 32];
+        let mut metadata = crate::store::node_metadata::NodeMetadata::new();
+        metadata.insert("existing".to_string(), "kept".to_string());
+        store
+            .put(&NodeRecord {
+                node_id,
+                path: std::path::PathBuf::from("/test/merge.txt"),
+                node_type: NodeType::File {
+                    size: 1,
+                    content_hash: [3u8; 32],
+                },
+                children: vec![],
+                parent: None,
+                frame_set_root: None,
+                metadata,
+                tombstoned_at: None,
+            })
+            .unwrap();
+
+        let writers: Vec<_> = ["alpha", "beta"]
+            .into_iter()
+            .map(|prefix| {
+                let store = std::sync::Arc::clone(&store);
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        let updates = HashMap::from([(format!("{}_{}", prefix, i), i.to_string())]);
+                        store.merge_metadata(&node_id, updates).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let record = store.get(&node_id).unwrap().unwrap();
+        assert_eq!(record.metadata.len(), 101);
+        assert_eq!(
+            record.metadata.get("existing").map(String::as_str),
+            Some("kept")
+        );
+        for i in 0..50 {
+            assert_eq!(
+                record.metadata.get(&format!("alpha_{}", i)),
+                Some(&i.to_string())
+            );
+            assert_eq!(
+                record.metadata.get(&format!("beta_{}", i)),
+                Some(&i.to_string())
+            );
+        }
+        assert_eq!(record.path, std::path::PathBuf::from("/test/merge.txt"));
+    }
+
+    #[test]
+    fn test_put_preserving_metadata_keeps_concurrent_merges() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = std::sync::Arc::new(SledNodeRecordStore::new(temp_dir.path()).unwrap());
+        let node_id = [8u8; 32];
+        let fresh = |value: &str| {
+            let mut metadata = crate::store::node_metadata::NodeMetadata::new();
+            metadata.insert("scan".to_string(), value.to_string());
+            NodeRecord {
+                node_id,
+                path: std::path::PathBuf::from("/test/rescan.txt"),
+                node_type: NodeType::File {
+                    size: 1,
+                    content_hash: [4u8; 32],
+                },
+                children: vec![],
+                parent: None,
+                frame_set_root: None,
+                metadata,
+                tombstoned_at: None,
+            }
+        };
+        store.put(&fresh("initial")).unwrap();
+
+        let merger = {
+            let store = std::sync::Arc::clone(&store);
+            std::thread::spawn(move || {
+                for i in 0..50 {
+                    let updates = HashMap::from([(format!("owner_{}", i), i.to_string())]);
+                    store.merge_metadata(&node_id, updates).unwrap();
+                }
+            })
+        };
+        for i in 0..50 {
+            store
+                .put_preserving_metadata(fresh(&format!("rescan_{}", i)))
+                .unwrap();
+        }
+        merger.join().unwrap();
+
+        let record = store.get(&node_id).unwrap().unwrap();
+        for i in 0..50 {
+            assert_eq!(
+                record.metadata.get(&format!("owner_{}", i)),
+                Some(&i.to_string())
+            );
+        }
+        assert_eq!(
+            record.metadata.get("scan").map(String::as_str),
+            Some("rescan_49")
+        );
+        assert_eq!(
+            store
+                .get_by_path(std::path::Path::new("/test/rescan.txt"))
+                .unwrap()
+                .map(|record| record.node_id),
+            Some(node_id)
+        );
+    }
+
+    #[test]
+    fn test_merge_metadata_missing_node_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SledNodeRecordStore::new(temp_dir.path()).unwrap();
+        let result = store.merge_metadata(&[9u8; 32], HashMap::new());
+        assert!(matches!(result, Err(StorageError::InvalidPath(_))));
+    }
+
     #[test]
     fn test_store_and_retrieve() {
         let temp_dir = TempDir::new().unwrap();
@@ -728,4 +1132,143 @@ mod tests {
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].node_id, [1u8; 32]);
     }
+
+    fn file_record(node_id: NodeID, path: &str) -> NodeRecord {
+        NodeRecord {
+            node_id,
+            path: std::path::PathBuf::from(path),
+            node_type: NodeType::File {
+                size: 0,
+                content_hash: [0u8; 32],
+            },
+            children: vec![],
+            parent: None,
+            frame_set_root: None,
+            metadata: Default::default(),
+            tombstoned_at: None,
+        }
+    }
+
+    #[test]
+    fn test_path_lookup_reads_index_entry_directly() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SledNodeRecordStore::new(temp_dir.path()).unwrap();
+        for i in 0..50u8 {
+            store
+                .put(&file_record([i; 32], &format!("/ws/file_{}.txt", i)))
+                .unwrap();
+        }
+
+        let found = store
+            .get_by_path(Path::new("/ws/file_42.txt"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.node_id, [42u8; 32]);
+        // The lookup is a single keyed read of the index entry.
+        let mapped = store.db.get(b"path:/ws/file_42.txt").unwrap().unwrap();
+        assert_eq!(deserialize_path_value(&mapped).unwrap(), [42u8; 32]);
+    }
+
+    #[test]
+    fn test_list_under_path_returns_exactly_the_subtree() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SledNodeRecordStore::new(temp_dir.path()).unwrap();
+        store.put(&file_record([1u8; 32], "/ws/src")).unwrap();
+        store.put(&file_record([2u8; 32], "/ws/src/a.rs")).unwrap();
+        store
+            .put(&file_record([3u8; 32], "/ws/src/nested/b.rs"))
+            .unwrap();
+        store
+            .put(&file_record([4u8; 32], "/ws/srcgen/c.rs"))
+            .unwrap();
+        store.put(&file_record([5u8; 32], "/ws/README.md")).unwrap();
+
+        let mut ids: Vec<NodeID> = store
+            .list_under_path(Path::new("/ws/src"))
+            .unwrap()
+            .into_iter()
+            .map(|r| r.node_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![[1u8; 32], [2u8; 32], [3u8; 32]]);
+        assert_eq!(
+            store.list_under_path(Path::new("/ws/src/")).unwrap().len(),
+            3
+        );
+
+        assert_eq!(store.list_under_path(Path::new("/")).unwrap().len(), 5);
+        assert!(store
+            .list_under_path(Path::new("/ws/missing"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_missing_path_index_is_rebuilt_on_open() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let store = SledNodeRecordStore::new(temp_dir.path()).unwrap();
+            store.put(&file_record([1u8; 32], "/ws/a.txt")).unwrap();
+            let mut stale = file_record([2u8; 32], "/ws/b.txt");
+            stale.tombstoned_at = Some(1);
+            store.put(&stale).unwrap();
+            store.put(&file_record([3u8; 32], "/ws/b.txt")).unwrap();
+            // Simulate a store written before the index existed.
+            for key in [
+                b"path:/ws/a.txt".as_slice(),
+                b"path:/ws/b.txt",
+                PATH_INDEX_MARKER_KEY,
+            ] {
+                store.db.remove(key).unwrap();
+            }
+            store.flush().unwrap();
+        }
+
+        let store = SledNodeRecordStore::new(temp_dir.path()).unwrap();
+        let a = store.get_by_path(Path::new("/ws/a.txt")).unwrap().unwrap();
+        assert_eq!(a.node_id, [1u8; 32]);
+        let b = store.get_by_path(Path::new("/ws/b.txt")).unwrap().unwrap();
+        assert_eq!(
+            b.node_id, [3u8; 32],
+            "active record wins the rebuilt mapping"
+        );
+        assert_eq!(store.ensure_path_index().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_purge_keeps_mapping_taken_over_by_newer_node() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SledNodeRecordStore::new(temp_dir.path()).unwrap();
+        store.put(&file_record([1u8; 32], "/ws/a.txt")).unwrap();
+        store.tombstone(&[1u8; 32]).unwrap();
+        store.put(&file_record([2u8; 32], "/ws/a.txt")).unwrap();
+
+        store.purge(&[1u8; 32], u64::MAX).unwrap();
+        let record = store.get_by_path(Path::new("/ws/a.txt")).unwrap().unwrap();
+        assert_eq!(record.node_id, [2u8; 32]);
+    }
+
+    #[test]
+    fn test_interrupted_vacuum_finishes_with_complete_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("store");
+        {
+            let store = SledNodeRecordStore::new(&store_path).unwrap();
+            store.put(&file_record([1u8; 32], "/ws/a.txt")).unwrap();
+            store.flush().unwrap();
+        }
+        // Crash after the original was moved aside, before the copy took its place.
+        let fresh_path = sibling_path(&store_path, VACUUM_FRESH_SUFFIX);
+        let old_path = sibling_path(&store_path, VACUUM_OLD_SUFFIX);
+        vacuum_sled_store(&store_path).unwrap();
+        std::fs::rename(&store_path, &fresh_path).unwrap();
+        std::fs::create_dir_all(&old_path).unwrap();
+
+        recover_interrupted_vacuum(&store_path).unwrap();
+        assert!(!fresh_path.exists());
+        assert!(!old_path.exists());
+        let store = SledNodeRecordStore::new(&store_path).unwrap();
+        let record = store.get_by_path(Path::new("/ws/a.txt")).unwrap().unwrap();
+        assert_eq!(record.node_id, [1u8; 32]);
+    }
 }
@@ -5,11 +5,13 @@ mod types;
 pub mod contracts;
 pub mod emission;
 pub mod events;
+pub mod export;
 pub mod facade;
 pub mod routing;
 pub mod sessions;
 pub mod sinks;
 pub mod summary;
+pub mod tooling;
 
 pub use crate::session::{PrunePolicy, SessionStatus};
 pub use contracts::{DomainObjectRef, EventRelation};
@@ -36,6 +36,8 @@ pub struct QueueStatsEventData {
     pub processing: usize,
     pub completed: usize,
     pub failed: usize,
+    #[serde(default)]
+    pub retries: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
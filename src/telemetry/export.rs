@@ -0,0 +1,119 @@
+//! Session listing and event stream export for offline analysis.
+//! Reads the Sled-backed session and event stores; never mutates them.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::ApiError;
+use crate::session::{SessionRecord, SessionStatus};
+use crate::telemetry::ProgressRuntime;
+
+/// One row of `telemetry list-sessions` output.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionListEntry {
+    pub session_id: String,
+    pub command: String,
+    pub started_at_ms: u64,
+    pub ended_at_ms: Option<u64>,
+    pub duration_ms: Option<u64>,
+    pub status: String,
+    /// `None` while the session is active or was interrupted.
+    pub success: Option<bool>,
+}
+
+impl From<&SessionRecord> for SessionListEntry {
+    fn from(record: &SessionRecord) -> Self {
+        let success = match record.status {
+            SessionStatus::Completed => Some(true),
+            SessionStatus::Failed => Some(false),
+            SessionStatus::Active | SessionStatus::Interrupted => None,
+        };
+        Self {
+            session_id: record.session_id.clone(),
+            command: record.command.clone(),
+            started_at_ms: record.started_at_ms,
+            ended_at_ms: record.ended_at_ms,
+            duration_ms: record
+                .ended_at_ms
+                .map(|ended| ended.saturating_sub(record.started_at_ms)),
+            status: record.status.as_str().to_string(),
+            success,
+        }
+    }
+}
+
+/// Result of a session export.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionExportResult {
+    pub session_id: String,
+    pub event_count: usize,
+}
+
+/// Most recent sessions first, capped at `limit`.
+pub fn list_recent_sessions(
+    progress: &ProgressRuntime,
+    limit: usize,
+) -> Result<Vec<SessionListEntry>, ApiError> {
+    Ok(progress
+        .list_sessions()?
+        .iter()
+        .take(limit)
+        .map(SessionListEntry::from)
+        .collect())
+}
+
+/// Most recent session id, skipping `exclude` so the exporting command does not export itself.
+pub fn latest_session_id(
+    progress: &ProgressRuntime,
+    exclude: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    Ok(progress
+        .list_sessions()?
+        .into_iter()
+        .find(|record| Some(record.session_id.as_str()) != exclude)
+        .map(|record| record.session_id))
+}
+
+/// Write every event of one session to `file` as JSON Lines, ordered by sequence.
+pub fn export_session_jsonl(
+    progress: &ProgressRuntime,
+    session_id: &str,
+    file: &Path,
+) -> Result<SessionExportResult, ApiError> {
+    if progress.get_session(session_id)?.is_none() {
+        return Err(ApiError::ConfigError(format!(
+            "Telemetry session not found: {}",
+            session_id
+        )));
+    }
+    let events = progress
+        .store()
+        .read_events(session_id)
+        .map_err(ApiError::from)?;
+
+    let handle = File::create(file).map_err(|e| {
+        ApiError::ConfigError(format!(
+            "Failed to create export file {}: {}",
+            file.display(),
+            e
+        ))
+    })?;
+    let mut writer = BufWriter::new(handle);
+    for event in &events {
+        let line = serde_json::to_string(event)
+            .map_err(|e| ApiError::ConfigError(format!("Failed to serialize event: {}", e)))?;
+        writeln!(writer, "{}", line)
+            .map_err(|e| ApiError::StorageError(crate::error::StorageError::IoError(e)))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| ApiError::StorageError(crate::error::StorageError::IoError(e)))?;
+
+    Ok(SessionExportResult {
+        session_id: session_id.to_string(),
+        event_count: events.len(),
+    })
+}
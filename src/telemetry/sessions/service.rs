@@ -1,6 +1,8 @@
 //! Compatibility facade for session lifecycle and canonical event emission.
 
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use serde_json::Value;
 use tracing::warn;
@@ -10,11 +12,16 @@ use crate::events::store::EventStore;
 use crate::events::EventRuntime;
 use crate::session as lifecycle;
 use crate::session::events::{session_ended_envelope, session_started_envelope};
+use crate::telemetry::sinks::file::ProgressFileSink;
+
+/// How long a command waits at exit for the progress file to catch up.
+const PROGRESS_FILE_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
 
 #[derive(Clone)]
 pub struct ProgressRuntime {
     events: Arc<EventRuntime>,
     sessions: Arc<lifecycle::SessionRuntime>,
+    progress_file: Arc<OnceLock<Arc<ProgressFileSink>>>,
 }
 
 impl ProgressRuntime {
@@ -22,7 +29,43 @@ impl ProgressRuntime {
         let events = Arc::new(EventRuntime::new(db.clone())?);
         let session_store = Arc::new(lifecycle::SessionStore::new(db)?);
         let sessions = Arc::new(lifecycle::SessionRuntime::new(session_store));
-        Ok(Self { events, sessions })
+        Ok(Self {
+            events,
+            sessions,
+            progress_file: Arc::new(OnceLock::new()),
+        })
+    }
+
+    /// Mirror every stored event to `path` as JSON Lines (`--progress-file`).
+    /// Only the first call takes effect.
+    pub fn mirror_to_file(&self, path: &Path) {
+        if self.progress_file.get().is_some() {
+            return;
+        }
+        let sink = Arc::new(ProgressFileSink::spawn(path));
+        if self.progress_file.set(Arc::clone(&sink)).is_ok() {
+            self.events.store().add_observer(sink);
+        }
+    }
+
+    /// Give the progress file mirror a bounded chance to write queued events.
+    pub fn flush_progress_file(&self) {
+        let Some(sink) = self.progress_file.get() else {
+            return;
+        };
+        if !sink.flush(PROGRESS_FILE_FLUSH_TIMEOUT) {
+            warn!(
+                path = %sink.path().display(),
+                "progress file did not catch up before timeout"
+            );
+        }
+        if sink.dropped() > 0 {
+            warn!(
+                path = %sink.path().display(),
+                dropped = sink.dropped(),
+                "progress file dropped events"
+            );
+        }
     }
 
     pub fn start_command_session(&self, command_name: String) -> Result<String, ApiError> {
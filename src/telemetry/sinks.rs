@@ -1,5 +1,6 @@
-//! Telemetry sinks: store, TUI, OpenTelemetry.
+//! Telemetry sinks: store, progress file, TUI, OpenTelemetry.
 
+pub mod file;
 pub mod otel;
 pub mod store;
 pub mod tui;
@@ -0,0 +1,122 @@
+//! Progress file sink: mirrors stored events as JSON Lines to a file or named pipe.
+//!
+//! Writes happen on a background thread fed by a bounded channel. Emitters never wait: when
+//! the channel is full (e.g. a pipe nobody is reading), events are dropped from the mirror.
+//! The Sled event store remains the source of truth.
+
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::events::store::EventObserver;
+use crate::events::EventRecord;
+
+/// Events buffered for the writer thread before new ones are dropped.
+const PROGRESS_FILE_CAPACITY: usize = 4096;
+
+enum MirrorMessage {
+    Line(String),
+    Flush(SyncSender<()>),
+}
+
+/// Best-effort JSON Lines mirror of the event spine.
+pub struct ProgressFileSink {
+    path: PathBuf,
+    sender: SyncSender<MirrorMessage>,
+    dropped: AtomicU64,
+}
+
+impl ProgressFileSink {
+    /// Start the writer thread. The file is opened on that thread, so opening a named pipe
+    /// without a reader does not block the command.
+    pub fn spawn(path: &Path) -> Self {
+        let (sender, receiver) = sync_channel(PROGRESS_FILE_CAPACITY);
+        let thread_path = path.to_path_buf();
+        let spawned = thread::Builder::new()
+            .name("meld-progress-file".to_string())
+            .spawn(move || write_loop(&thread_path, receiver));
+        if let Err(err) = spawned {
+            warn!(path = %path.display(), error = %err, "failed to start progress file writer");
+        }
+        Self {
+            path: path.to_path_buf(),
+            sender,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Events dropped because the writer fell behind or stopped.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Wait up to `timeout` for queued events to be written. Returns false on timeout.
+    pub fn flush(&self, timeout: Duration) -> bool {
+        let (ack_tx, ack_rx) = sync_channel(1);
+        if self.sender.try_send(MirrorMessage::Flush(ack_tx)).is_err() {
+            return false;
+        }
+        ack_rx.recv_timeout(timeout).is_ok()
+    }
+}
+
+impl EventObserver for ProgressFileSink {
+    fn on_event(&self, event: &EventRecord) {
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!(error = %err, "failed to serialize event for progress file");
+                return;
+            }
+        };
+        match self.sender.try_send(MirrorMessage::Line(line)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+fn write_loop(path: &Path, receiver: Receiver<MirrorMessage>) {
+    let file = match OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => file,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "failed to open progress file");
+            return;
+        }
+    };
+    let mut writer = BufWriter::new(file);
+    for message in receiver {
+        match message {
+            MirrorMessage::Line(line) => {
+                let written = writer
+                    .write_all(line.as_bytes())
+                    .and_then(|_| writer.write_all(b"\n"))
+                    .and_then(|_| writer.flush());
+                if let Err(err) = written {
+                    warn!(path = %path.display(), error = %err, "progress file write failed");
+                    return;
+                }
+            }
+            MirrorMessage::Flush(ack) => {
+                if let Err(err) = writer.flush() {
+                    warn!(path = %path.display(), error = %err, "progress file flush failed");
+                    return;
+                }
+                let _ = ack.send(());
+            }
+        }
+    }
+    let _ = writer.flush();
+}
@@ -0,0 +1,44 @@
+use crate::cli::{
+    format_telemetry_export_result, format_telemetry_sessions_json, format_telemetry_sessions_text,
+    TelemetryCommands,
+};
+use crate::error::ApiError;
+use crate::telemetry::export::{export_session_jsonl, latest_session_id, list_recent_sessions};
+use crate::telemetry::ProgressRuntime;
+
+pub fn handle_cli_command(
+    progress: &ProgressRuntime,
+    command: &TelemetryCommands,
+    session_id: &str,
+) -> Result<String, ApiError> {
+    match command {
+        TelemetryCommands::ListSessions { limit, format } => {
+            let sessions = list_recent_sessions(progress, *limit)?;
+            match format.as_str() {
+                "json" => format_telemetry_sessions_json(&sessions),
+                _ => Ok(format_telemetry_sessions_text(&sessions)),
+            }
+        }
+        TelemetryCommands::Export {
+            session,
+            last,
+            file,
+        } => {
+            let target = match (session, *last) {
+                (Some(id), _) => id.clone(),
+                (None, true) => {
+                    latest_session_id(progress, Some(session_id))?.ok_or_else(|| {
+                        ApiError::ConfigError("No telemetry sessions recorded".to_string())
+                    })?
+                }
+                (None, false) => {
+                    return Err(ApiError::ConfigError(
+                        "Either --session or --last must be provided".to_string(),
+                    ))
+                }
+            };
+            let result = export_session_jsonl(progress, &target, file)?;
+            Ok(format_telemetry_export_result(&result, file))
+        }
+    }
+}
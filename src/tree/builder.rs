@@ -1,17 +1,37 @@
 //! Tree builder for constructing filesystem Merkle trees
 
 use crate::error::StorageError;
-use crate::tree::hasher;
-use crate::tree::node::{DirectoryNode, FileNode, MerkleNode};
+use crate::store::node_metadata::BINARY_METADATA_KEY;
+use crate::tree::hasher::{self, ContentHasher, ContentNormalization, ContentReadOptions};
+use crate::tree::node::{DirectoryNode, FileNode, MerkleNode, SymlinkNode};
 use crate::tree::path;
 use crate::tree::walker::{Entry, Walker, WalkerConfig};
-use crate::types::NodeID;
+use crate::types::{Hash, NodeID};
 use hex;
 use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{ErrorKind, Read};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tracing::{debug, error, info, instrument, trace, warn};
 
+/// Leading bytes inspected for NUL bytes when sniffing binary content.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// Null-byte heuristic over the first few KB of a file's content.
+pub fn looks_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
+/// Path hashed into a NodeID: relative to the workspace root, so NodeIDs do not change when
+/// the same workspace is scanned from another absolute location. Paths that resolve outside
+/// the root (e.g. through symlinks) keep their canonical form.
+fn workspace_relative<'a>(canonical_root: &Path, canonical_path: &'a Path) -> &'a Path {
+    canonical_path
+        .strip_prefix(canonical_root)
+        .unwrap_or(canonical_path)
+}
+
 /// Complete Merkle tree structure
 #[derive(Debug, Clone)]
 pub struct Tree {
@@ -43,6 +63,17 @@ impl Tree {
         }
     }
 
+    /// Directory NodeIDs keyed by directory path, in path order.
+    pub fn directory_node_ids(&self) -> BTreeMap<PathBuf, NodeID> {
+        self.nodes
+            .iter()
+            .filter_map(|(node_id, node)| match node {
+                MerkleNode::Directory(dir) => Some((dir.path.clone(), *node_id)),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Return the NodeID of the .gitignore file node if present in the tree.
     pub fn find_gitignore_node_id(&self) -> Option<NodeID> {
         use std::ffi::OsStr;
@@ -50,6 +81,7 @@ impl Tree {
             let path = match node {
                 MerkleNode::File(f) => &f.path,
                 MerkleNode::Directory(d) => &d.path,
+                MerkleNode::Symlink(l) => &l.path,
             };
             if path.file_name() == Some(OsStr::new(".gitignore")) {
                 return Some(*node_id);
@@ -63,6 +95,8 @@ impl Tree {
 pub struct TreeBuilder {
     root: PathBuf,
     walker_config: Option<WalkerConfig>,
+    normalization: ContentNormalization,
+    read_options: ContentReadOptions,
 }
 
 impl TreeBuilder {
@@ -71,6 +105,8 @@ impl TreeBuilder {
         Self {
             root,
             walker_config: None,
+            normalization: ContentNormalization::Exact,
+            read_options: ContentReadOptions::default(),
         }
     }
 
@@ -81,6 +117,20 @@ impl TreeBuilder {
         self
     }
 
+    /// Set how text file content is normalized before hashing. Binary files always hash
+    /// their exact bytes.
+    pub fn with_content_normalization(mut self, normalization: ContentNormalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Set the read buffer size and memory-mapping threshold used when hashing file content.
+    /// Content hashes do not depend on these settings.
+    pub fn with_read_options(mut self, read_options: ContentReadOptions) -> Self {
+        self.read_options = read_options;
+        self
+    }
+
     /// Build the complete Merkle tree from the filesystem
     ///
     /// This processes files and directories bottom-up to compute NodeIDs,
@@ -109,26 +159,48 @@ impl TreeBuilder {
         // Step 2: Separate files and directories
         let mut files = Vec::new();
         let mut directories = Vec::new();
+        let mut symlinks = Vec::new();
 
         for entry in entries {
             match entry {
                 Entry::File { path, size } => files.push((path, size)),
                 Entry::Directory { path } => directories.push(path),
+                Entry::Symlink { path, target } => symlinks.push((path, target)),
             }
         }
 
         // Step 3: Process files first (they have no dependencies)
         let mut node_map: HashMap<PathBuf, NodeID> = HashMap::new();
         let mut nodes: HashMap<NodeID, MerkleNode> = HashMap::new();
+        let canonical_root = path::canonicalize_path(&self.root)?;
 
         for (file_path, size) in files {
-            let (node_id, file_node) = self.hash_file(&file_path, size)?;
             // Canonicalize path for consistent lookups
             let canonical_path = path::canonicalize_path(&file_path)?;
+            let relative_path = workspace_relative(&canonical_root, &canonical_path);
+            let (node_id, file_node) = self.hash_file(&file_path, relative_path, size)?;
             node_map.insert(canonical_path, node_id);
             nodes.insert(node_id, MerkleNode::File(file_node));
         }
 
+        // Symlinks are leaves too; they are keyed by their own path, not their target's
+        for (link_path, target) in symlinks {
+            let canonical_path = path::canonicalize_link_path(&link_path)?;
+            let relative_path = workspace_relative(&canonical_root, &canonical_path);
+            let metadata = BTreeMap::new();
+            let node_id = hasher::compute_symlink_node_id(relative_path, &target, &metadata)?;
+            trace!(path = %link_path.display(), target = %target.display(), "Recorded symlink");
+            node_map.insert(canonical_path, node_id);
+            nodes.insert(
+                node_id,
+                MerkleNode::Symlink(SymlinkNode {
+                    path: link_path,
+                    target,
+                    metadata,
+                }),
+            );
+        }
+
         // Step 4: Process directories bottom-up (deepest first)
         // Add root directory to the list if it's not already there
         if !directories.contains(&self.root) {
@@ -143,9 +215,10 @@ impl TreeBuilder {
         });
 
         for dir_path in directories {
-            let (node_id, dir_node) = self.hash_directory(&dir_path, &node_map)?;
             // Canonicalize path for consistent lookups
             let canonical_path = path::canonicalize_path(&dir_path)?;
+            let relative_path = workspace_relative(&canonical_root, &canonical_path);
+            let (node_id, dir_node) = self.hash_directory(&dir_path, relative_path, &node_map)?;
             node_map.insert(canonical_path, node_id);
             nodes.insert(node_id, MerkleNode::Directory(dir_node));
         }
@@ -164,7 +237,6 @@ impl TreeBuilder {
         }
 
         // Step 6: Get root directory NodeID
-        let canonical_root = path::canonicalize_path(&self.root)?;
         let root_id = node_map.get(&canonical_root).copied().ok_or_else(|| {
             error!("Root directory not found in node map: {:?}", canonical_root);
             StorageError::InvalidPath(format!(
@@ -196,28 +268,31 @@ impl TreeBuilder {
         Ok(tree.root_id)
     }
 
-    /// Hash a file and compute its NodeID
-    #[instrument(skip(self), fields(path = %file_path.display()))]
-    fn hash_file(&self, file_path: &Path, size: u64) -> Result<(NodeID, FileNode), StorageError> {
+    /// Hash a file and compute its NodeID from its workspace-relative path
+    #[instrument(skip(self, relative_path), fields(path = %file_path.display()))]
+    fn hash_file(
+        &self,
+        file_path: &Path,
+        relative_path: &Path,
+        size: u64,
+    ) -> Result<(NodeID, FileNode), StorageError> {
         trace!("Hashing file");
-        // Read file content
-        let content = std::fs::read(file_path).map_err(|e| {
+        let (content_hash, is_binary) = self.hash_content(file_path, size).map_err(|e| {
             error!("Failed to read file: {}", e);
             StorageError::IoError(std::io::Error::other(format!(
                 "Failed to read file {:?}: {}",
                 file_path, e
             )))
         })?;
-
-        // Compute content hash
-        let content_hash = hasher::compute_content_hash(&content);
         trace!(content_hash = %hex::encode(content_hash), "Computed content hash");
 
-        // Extract metadata (currently empty, can be extended)
-        let metadata = BTreeMap::new();
+        let mut metadata = BTreeMap::new();
+        if is_binary {
+            metadata.insert(BINARY_METADATA_KEY.to_string(), "true".to_string());
+        }
 
         // Compute NodeID
-        let node_id = hasher::compute_file_node_id(file_path, &content_hash, &metadata)?;
+        let node_id = hasher::compute_file_node_id(relative_path, &content_hash, &metadata)?;
 
         // Create FileNode
         let file_node = FileNode {
@@ -230,12 +305,62 @@ impl TreeBuilder {
         Ok((node_id, file_node))
     }
 
+    /// Stream file content through the content hasher without holding the whole file in
+    /// memory. Returns the content hash and whether the content looks binary; binary files are
+    /// never normalized.
+    fn hash_content(&self, file_path: &Path, size: u64) -> std::io::Result<(Hash, bool)> {
+        let mut file = File::open(file_path)?;
+        let chunk_size = self.read_options.chunk_size.max(1);
+
+        if size > 0 && size >= self.read_options.mmap_threshold {
+            // SAFETY: not guaranteed. Reading the mapping is only sound while no other process
+            // truncates or rewrites the file; if one does, the read raises SIGBUS and meld
+            // crashes. Nothing here can prevent that, so mapping is opt-in
+            // (`DEFAULT_HASH_MMAP_THRESHOLD`) and `workspace watch`, which hashes files as
+            // they are being written, always streams (`ContentReadOptions::streaming`).
+            let mapped = unsafe { memmap2::Mmap::map(&file)? };
+            let is_binary = looks_binary(&mapped);
+            let mut hasher = ContentHasher::new(self.content_normalization_for(is_binary));
+            for chunk in mapped.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+            return Ok((hasher.finalize(), is_binary));
+        }
+
+        let mut prefix = Vec::with_capacity(BINARY_SNIFF_LEN);
+        (&mut file)
+            .take(BINARY_SNIFF_LEN as u64)
+            .read_to_end(&mut prefix)?;
+        let is_binary = looks_binary(&prefix);
+        let mut hasher = ContentHasher::new(self.content_normalization_for(is_binary));
+        hasher.update(&prefix);
+        let mut buffer = vec![0u8; chunk_size];
+        loop {
+            match file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => hasher.update(&buffer[..read]),
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok((hasher.finalize(), is_binary))
+    }
+
+    fn content_normalization_for(&self, is_binary: bool) -> ContentNormalization {
+        if is_binary {
+            ContentNormalization::Exact
+        } else {
+            self.normalization
+        }
+    }
+
     /// Hash a directory and compute its NodeID
     ///
     /// Requires that all children have already been processed and are in node_map.
     fn hash_directory(
         &self,
         dir_path: &Path,
+        relative_path: &Path,
         node_map: &HashMap<PathBuf, NodeID>,
     ) -> Result<(NodeID, DirectoryNode), StorageError> {
         // Read directory contents
@@ -259,9 +384,19 @@ impl TreeBuilder {
 
             let child_path = entry.path();
             let child_name = entry.file_name().to_string_lossy().to_string();
-
-            // Canonicalize child path for consistent lookup
-            let canonical_child_path = match path::canonicalize_path(&child_path) {
+            let is_symlink = entry.file_type().is_ok_and(|t| t.is_symlink());
+
+            // Canonicalize child path for consistent lookup; links are looked up by their own
+            // path when recorded as symlink nodes, and by their target when followed
+            let canonical_child = if is_symlink {
+                path::canonicalize_link_path(&child_path)
+                    .ok()
+                    .filter(|p| node_map.contains_key(p))
+                    .map_or_else(|| path::canonicalize_path(&child_path), Ok)
+            } else {
+                path::canonicalize_path(&child_path)
+            };
+            let canonical_child_path = match canonical_child {
                 Ok(p) => p,
                 Err(_) => {
                     // Skip if canonicalization fails (might be symlink or special file)
@@ -288,7 +423,7 @@ impl TreeBuilder {
         let metadata = BTreeMap::new();
 
         // Compute NodeID
-        let node_id = hasher::compute_directory_node_id(dir_path, &children, &metadata)?;
+        let node_id = hasher::compute_directory_node_id(relative_path, &children, &metadata)?;
 
         // Create DirectoryNode
         let dir_node = DirectoryNode {
@@ -355,6 +490,154 @@ mod tests {
         assert!(tree.nodes.len() >= 4);
     }
 
+    #[test]
+    fn test_build_tree_flags_binary_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+
+        fs::write(root.join("notes.txt"), "plain text").unwrap();
+        fs::write(root.join("logo.png"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+
+        let tree = TreeBuilder::new(root.clone()).build().unwrap();
+        let is_binary = |name: &str| {
+            tree.nodes.values().find_map(|node| match node {
+                MerkleNode::File(file) if file.path.ends_with(name) => {
+                    Some(file.metadata.get(BINARY_METADATA_KEY).cloned())
+                }
+                _ => None,
+            })
+        };
+        assert_eq!(is_binary("notes.txt"), Some(None));
+        assert_eq!(is_binary("logo.png"), Some(Some("true".to_string())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_build_tree_records_symlinks_without_following() {
+        use crate::store::{NodeRecord, NodeType};
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::write(root.join("real.txt"), "real content").unwrap();
+        symlink("real.txt", root.join("alias.txt")).unwrap();
+        symlink("missing/elsewhere", root.join("dangling")).unwrap();
+
+        let tree = TreeBuilder::new(root.clone()).build().unwrap();
+        let link_record = |name: &str| {
+            let (node_id, node) = tree
+                .nodes
+                .iter()
+                .find(|(_, node)| matches!(node, MerkleNode::Symlink(l) if l.path.ends_with(name)))
+                .unwrap_or_else(|| panic!("{name} should be a symlink node"));
+            NodeRecord::from_merkle_node(*node_id, node, &tree).unwrap()
+        };
+
+        let alias = link_record("alias.txt");
+        assert!(matches!(
+            alias.node_type,
+            NodeType::Symlink { ref target } if target == Path::new("real.txt")
+        ));
+        assert_eq!(alias.parent, Some(tree.root_id));
+        let dangling = link_record("dangling");
+        assert!(matches!(
+            dangling.node_type,
+            NodeType::Symlink { ref target } if target == Path::new("missing/elsewhere")
+        ));
+        // 1 file + 2 symlinks + 1 root directory; the link target is not indexed twice
+        assert_eq!(tree.nodes.len(), 4);
+
+        // Retargeting a link changes its NodeID and the root
+        let root_before = tree.root_id;
+        fs::remove_file(root.join("dangling")).unwrap();
+        symlink("real.txt", root.join("dangling")).unwrap();
+        assert_ne!(TreeBuilder::new(root).compute_root().unwrap(), root_before);
+    }
+
+    #[test]
+    fn test_line_ending_normalization_matches_crlf_and_lf_checkouts() {
+        let lf_dir = TempDir::new().unwrap();
+        let crlf_dir = TempDir::new().unwrap();
+        fs::write(lf_dir.path().join("main.rs"), "fn main() {}\n// end\n").unwrap();
+        fs::write(
+            crlf_dir.path().join("main.rs"),
+            "fn main() {}\r\n// end\r\n",
+        )
+        .unwrap();
+        let root_for = |dir: &TempDir, normalization| {
+            TreeBuilder::new(dir.path().to_path_buf())
+                .with_content_normalization(normalization)
+                .compute_root()
+                .unwrap()
+        };
+
+        assert_ne!(
+            root_for(&lf_dir, ContentNormalization::Exact),
+            root_for(&crlf_dir, ContentNormalization::Exact)
+        );
+        assert_eq!(
+            root_for(&lf_dir, ContentNormalization::LineEndings),
+            root_for(&crlf_dir, ContentNormalization::LineEndings)
+        );
+    }
+
+    #[test]
+    fn test_line_ending_normalization_skips_binary_files() {
+        let lf_dir = TempDir::new().unwrap();
+        let crlf_dir = TempDir::new().unwrap();
+        fs::write(lf_dir.path().join("blob.bin"), b"\0data\n").unwrap();
+        fs::write(crlf_dir.path().join("blob.bin"), b"\0data\r\n").unwrap();
+        let root_for = |dir: &TempDir| {
+            TreeBuilder::new(dir.path().to_path_buf())
+                .with_content_normalization(ContentNormalization::LineEndings)
+                .compute_root()
+                .unwrap()
+        };
+
+        assert_ne!(root_for(&lf_dir), root_for(&crlf_dir));
+    }
+
+    #[test]
+    fn test_streamed_and_mapped_hashes_match_whole_buffer_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("large.txt");
+        let content: Vec<u8> = (0..200_000)
+            .flat_map(|i| format!("line {i}\r\n").into_bytes())
+            .collect();
+        let lf_content: Vec<u8> = (0..200_000)
+            .flat_map(|i| format!("line {i}\n").into_bytes())
+            .collect();
+        assert!(content.len() > 2 * 1024 * 1024);
+        fs::write(&path, &content).unwrap();
+        let size = content.len() as u64;
+
+        for (normalization, hashed) in [
+            (ContentNormalization::Exact, &content),
+            (ContentNormalization::LineEndings, &lf_content),
+        ] {
+            let expected = hasher::compute_content_hash(hashed);
+            // An odd chunk size splits CRLF pairs across reads; threshold 1 forces mapping.
+            for read_options in [
+                ContentReadOptions::default(),
+                ContentReadOptions {
+                    chunk_size: 4097,
+                    mmap_threshold: u64::MAX,
+                },
+                ContentReadOptions {
+                    chunk_size: 4097,
+                    mmap_threshold: 1,
+                },
+            ] {
+                let builder = TreeBuilder::new(temp_dir.path().to_path_buf())
+                    .with_content_normalization(normalization)
+                    .with_read_options(read_options);
+                let (content_hash, is_binary) = builder.hash_content(&path, size).unwrap();
+                assert!(!is_binary);
+                assert_eq!(content_hash, expected, "{normalization:?} {read_options:?}");
+            }
+        }
+    }
+
     #[test]
     fn test_compute_root_deterministic() {
         let temp_dir = TempDir::new().unwrap();
@@ -370,6 +653,27 @@ mod tests {
         assert_eq!(root1, root2);
     }
 
+    #[test]
+    fn test_compute_root_independent_of_workspace_location() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        for root in [first.path(), second.path()] {
+            fs::create_dir_all(root.join("src/nested")).unwrap();
+            fs::write(root.join("README.md"), "readme").unwrap();
+            fs::write(root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+            fs::write(root.join("src/nested/mod.txt"), "nested").unwrap();
+        }
+
+        let first_root = TreeBuilder::new(first.path().to_path_buf())
+            .compute_root()
+            .unwrap();
+        let second_root = TreeBuilder::new(second.path().to_path_buf())
+            .compute_root()
+            .unwrap();
+
+        assert_eq!(first_root, second_root);
+    }
+
     #[test]
     fn test_compute_root_changes_with_content() {
         let temp_dir = TempDir::new().unwrap();
@@ -5,21 +5,20 @@ use crate::tree::path;
 use crate::types::{Hash, NodeID};
 use blake3::Hasher;
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Component, Path};
 
 /// Compute NodeID for a file node
 ///
 /// NodeID = hash("file" || path_len || path || content_hash || metadata)
 ///
-/// This ensures deterministic NodeID generation: same file content and path
-/// always produces the same NodeID.
+/// `file_path` is the path relative to the workspace root, so the same file content and
+/// relative path produce the same NodeID wherever the workspace is mounted.
 pub fn compute_file_node_id(
     file_path: &Path,
     content_hash: &Hash,
     metadata: &BTreeMap<String, String>,
 ) -> Result<NodeID, StorageError> {
-    let canonical_path = path::canonicalize_path(file_path)?;
-    let path_string = canonical_path.to_string_lossy();
+    let path_string = hash_path_string(file_path);
     let path_bytes = path_string.as_bytes();
 
     let mut hasher = Hasher::new();
@@ -51,14 +50,20 @@ pub fn compute_file_node_id(
 ///
 /// NodeID = hash("directory" || path_len || path || children_count || children || metadata)
 ///
-/// Children must be sorted by name for determinism.
+/// `dir_path` is relative to the workspace root (empty for the root itself). Children must be
+/// sorted by name with no duplicates; any other order is rejected rather than hashed.
 pub fn compute_directory_node_id(
     dir_path: &Path,
     children: &[(String, NodeID)], // Must be sorted by name
     metadata: &BTreeMap<String, String>,
 ) -> Result<NodeID, StorageError> {
-    let canonical_path = path::canonicalize_path(dir_path)?;
-    let path_string = canonical_path.to_string_lossy();
+    if let Some(pair) = children.windows(2).find(|pair| pair[0].0 >= pair[1].0) {
+        return Err(StorageError::InvalidPath(format!(
+            "Directory {:?} children are not sorted by name ('{}' before '{}')",
+            dir_path, pair[0].0, pair[1].0
+        )));
+    }
+    let path_string = hash_path_string(dir_path);
     let path_bytes = path_string.as_bytes();
 
     let mut hasher = Hasher::new();
@@ -93,6 +98,59 @@ pub fn compute_directory_node_id(
     Ok(*hasher.finalize().as_bytes())
 }
 
+/// Compute NodeID for a symlink node
+///
+/// NodeID = hash("symlink" || path_len || path || target_len || target || metadata)
+///
+/// The link target stands in for content: the link is never followed, so the NodeID changes
+/// only when the link is retargeted or moved.
+pub fn compute_symlink_node_id(
+    link_path: &Path,
+    target: &Path,
+    metadata: &BTreeMap<String, String>,
+) -> Result<NodeID, StorageError> {
+    let path_string = hash_path_string(link_path);
+    let path_bytes = path_string.as_bytes();
+    let target_string = hash_path_string(target);
+    let target_bytes = target_string.as_bytes();
+
+    let mut hasher = Hasher::new();
+
+    // Hash type discriminator
+    hasher.update(b"symlink");
+
+    // Hash path length and path
+    hasher.update(&(path_bytes.len() as u64).to_be_bytes());
+    hasher.update(path_bytes);
+
+    // Hash target length and target
+    hasher.update(&(target_bytes.len() as u64).to_be_bytes());
+    hasher.update(target_bytes);
+
+    // Hash metadata (sorted for determinism)
+    for (key, value) in metadata.iter() {
+        hasher.update(key.as_bytes());
+        hasher.update(b":");
+        hasher.update(value.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    Ok(*hasher.finalize().as_bytes())
+}
+
+/// Path as hashed: NFC-normalized components joined by `/`, independent of platform separator.
+fn hash_path_string(path: &Path) -> String {
+    let joined = path
+        .components()
+        .map(|component| match component {
+            Component::RootDir => "".into(),
+            other => other.as_os_str().to_string_lossy(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    path::normalize_path_string(&joined)
+}
+
 /// Compute content hash for file bytes
 ///
 /// Uses BLAKE3 to hash file content deterministically.
@@ -102,6 +160,122 @@ pub fn compute_content_hash(content: &[u8]) -> Hash {
     *hasher.finalize().as_bytes()
 }
 
+/// How file bytes are normalized before content hashing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContentNormalization {
+    /// Hash the exact bytes on disk.
+    #[default]
+    Exact,
+    /// Hash text with CRLF line endings rewritten to LF, so checkouts with different
+    /// line-ending conventions produce the same content hash.
+    LineEndings,
+}
+
+impl ContentNormalization {
+    /// Normalization selected by `system.normalize_line_endings`.
+    pub fn from_line_endings_flag(normalize_line_endings: bool) -> Self {
+        if normalize_line_endings {
+            Self::LineEndings
+        } else {
+            Self::Exact
+        }
+    }
+}
+
+/// Default read buffer for streamed content hashing.
+pub const DEFAULT_HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Default file size at and above which content is memory-mapped instead of read: never.
+///
+/// Mapping is opt-in because a file truncated by another process while it is mapped raises
+/// SIGBUS and kills the process, which is a realistic race for a file being rewritten during
+/// `workspace watch`.
+pub const DEFAULT_HASH_MMAP_THRESHOLD: u64 = u64::MAX;
+
+/// How file content is read for hashing. Every setting yields the same content hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentReadOptions {
+    /// Bytes read (or fed from a mapping) per hasher update.
+    pub chunk_size: usize,
+    /// Files at least this large are memory-mapped instead of read into buffers; `u64::MAX`
+    /// disables mapping.
+    pub mmap_threshold: u64,
+}
+
+impl ContentReadOptions {
+    /// These options with memory mapping turned off, for readers racing writers to the same
+    /// files.
+    pub fn streaming(self) -> Self {
+        Self {
+            mmap_threshold: u64::MAX,
+            ..self
+        }
+    }
+}
+
+impl Default for ContentReadOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_HASH_CHUNK_SIZE,
+            mmap_threshold: DEFAULT_HASH_MMAP_THRESHOLD,
+        }
+    }
+}
+
+/// Incremental content hasher. Feeding content in any split produces the same hash as
+/// `compute_content_hash` over the whole (normalized) content.
+pub struct ContentHasher {
+    hasher: Hasher,
+    normalization: ContentNormalization,
+    /// A trailing `\r` from the previous chunk, held until the next byte shows whether it
+    /// starts a CRLF pair.
+    pending_cr: bool,
+    scratch: Vec<u8>,
+}
+
+impl ContentHasher {
+    pub fn new(normalization: ContentNormalization) -> Self {
+        Self {
+            hasher: Hasher::new(),
+            normalization,
+            pending_cr: false,
+            scratch: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        match self.normalization {
+            ContentNormalization::Exact => {
+                self.hasher.update(chunk);
+            }
+            ContentNormalization::LineEndings => {
+                self.scratch.clear();
+                for &byte in chunk {
+                    if self.pending_cr {
+                        self.pending_cr = false;
+                        if byte != b'\n' {
+                            self.scratch.push(b'\r');
+                        }
+                    }
+                    if byte == b'\r' {
+                        self.pending_cr = true;
+                    } else {
+                        self.scratch.push(byte);
+                    }
+                }
+                self.hasher.update(&self.scratch);
+            }
+        }
+    }
+
+    pub fn finalize(mut self) -> Hash {
+        if self.pending_cr {
+            self.hasher.update(b"\r");
+        }
+        *self.hasher.finalize().as_bytes()
+    }
+}
+
 /// Compute a generic hash of arbitrary data
 pub fn compute_hash(data: &[u8]) -> Hash {
     let mut hasher = Hasher::new();
@@ -124,6 +298,53 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    fn hash_with(normalization: ContentNormalization, content: &[u8]) -> Hash {
+        let mut hasher = ContentHasher::new(normalization);
+        hasher.update(content);
+        hasher.finalize()
+    }
+
+    #[test]
+    fn test_line_ending_normalization_equates_crlf_and_lf() {
+        let lf = b"fn main() {\n    run();\n}\n";
+        let crlf = b"fn main() {\r\n    run();\r\n}\r\n";
+
+        let exact = ContentNormalization::Exact;
+        assert_eq!(hash_with(exact, lf), compute_content_hash(lf));
+        assert_ne!(hash_with(exact, lf), hash_with(exact, crlf));
+
+        let normalized = ContentNormalization::LineEndings;
+        assert_eq!(hash_with(normalized, crlf), compute_content_hash(lf));
+        // Lone carriage returns are content, not line endings.
+        assert_eq!(
+            hash_with(normalized, b"a\rb"),
+            compute_content_hash(b"a\rb")
+        );
+    }
+
+    #[test]
+    fn test_content_hasher_matches_whole_buffer_hash_for_any_split() {
+        let content = b"line one\r\nline two\r\r\nlone\rcr\r\n\r";
+        let normalized = b"line one\nline two\r\nlone\rcr\n\r";
+        for (normalization, expected_content) in [
+            (ContentNormalization::Exact, &content[..]),
+            (ContentNormalization::LineEndings, &normalized[..]),
+        ] {
+            let expected = compute_content_hash(expected_content);
+            for chunk_size in 1..=content.len() {
+                let mut hasher = ContentHasher::new(normalization);
+                for chunk in content.chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(
+                    hasher.finalize(),
+                    expected,
+                    "{normalization:?} with {chunk_size}-byte chunks"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_file_node_id_deterministic() {
         let temp_dir = TempDir::new().unwrap();
@@ -191,4 +412,15 @@ mod tests {
 
         assert_ne!(node_id1, node_id2);
     }
+
+    #[test]
+    fn test_directory_node_id_rejects_unsorted_children() {
+        let children = vec![
+            ("file2.txt".to_string(), [2u8; 32]),
+            ("file1.txt".to_string(), [1u8; 32]),
+        ];
+        let err =
+            compute_directory_node_id(Path::new("dir"), &children, &BTreeMap::new()).unwrap_err();
+        assert!(err.to_string().contains("not sorted"), "{}", err);
+    }
 }
@@ -0,0 +1,216 @@
+//! NodeIDs under the original hashing scheme, which hashed each node's canonical absolute path.
+//!
+//! Stores scanned before paths were hashed relative to the workspace root keep their heads
+//! under these IDs. The first scan after the upgrade maps them onto the current IDs so existing
+//! frames stay attached (see `ContextApi::carry_heads_forward`).
+//!
+//! Legacy IDs are recomputed from the filesystem with that scheme's own rules rather than from
+//! the current tree, which has since changed what it indexes: hidden entries and empty
+//! directories were indexed, bare ignore patterns matched as substrings, symlinks were skipped,
+//! and nodes carried no metadata.
+
+use crate::error::StorageError;
+use crate::tree::builder::Tree;
+use crate::tree::node::MerkleNode;
+use crate::tree::path;
+use crate::types::{Hash, NodeID};
+use blake3::Hasher;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Map each file and directory in `tree` to the NodeID the absolute-path scheme gave it.
+///
+/// `ignore_patterns` must be the patterns that scheme walked with
+/// (`ignore::load_ignore_patterns_without_git_excludes`). Nodes the old walker did not index
+/// have no legacy ID and are left out.
+pub fn legacy_node_ids(
+    tree: &Tree,
+    workspace_root: &Path,
+    ignore_patterns: &[String],
+) -> Result<HashMap<NodeID, NodeID>, StorageError> {
+    let by_path = legacy_ids_by_path(workspace_root, ignore_patterns)?;
+    let mut legacy_ids = HashMap::new();
+    for (node_id, node) in &tree.nodes {
+        let node_path = match node {
+            MerkleNode::File(file) => &file.path,
+            MerkleNode::Directory(dir) => &dir.path,
+            MerkleNode::Symlink(_) => continue,
+        };
+        if let Some(legacy_id) = by_path.get(&path::canonicalize_path(node_path)?) {
+            legacy_ids.insert(*node_id, *legacy_id);
+        }
+    }
+    Ok(legacy_ids)
+}
+
+/// Legacy NodeID of every file and directory the old walker indexed, keyed by canonical path.
+fn legacy_ids_by_path(
+    root: &Path,
+    ignore_patterns: &[String],
+) -> Result<HashMap<PathBuf, NodeID>, StorageError> {
+    let mut files = Vec::new();
+    let mut directories = Vec::new();
+    for entry in WalkDir::new(root).follow_links(false) {
+        let entry = entry.map_err(|e| {
+            StorageError::IoError(std::io::Error::other(format!(
+                "Failed to walk directory: {}",
+                e
+            )))
+        })?;
+        let entry_path = entry.path();
+        if is_ignored(entry_path, ignore_patterns) || entry_path == root {
+            continue;
+        }
+        let metadata = entry
+            .metadata()
+            .map_err(|e| StorageError::io_context("read metadata for", entry_path, e.into()))?;
+        if metadata.is_file() {
+            files.push(entry_path.to_path_buf());
+        } else if metadata.is_dir() {
+            directories.push(entry_path.to_path_buf());
+        }
+    }
+
+    let mut ids = HashMap::new();
+    for file_path in files {
+        let canonical = path::canonicalize_path(&file_path)?;
+        let content_hash = content_hash(&file_path)?;
+        ids.insert(canonical.clone(), file_node_id(&canonical, &content_hash));
+    }
+
+    directories.push(root.to_path_buf());
+    directories.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    for dir_path in directories {
+        // Children were read back from the directory and matched by canonical path, so a
+        // symlink to an indexed node counted as a child under the link's name.
+        let mut children = Vec::new();
+        let entries = fs::read_dir(&dir_path)
+            .map_err(|e| StorageError::io_context("read directory", &dir_path, e))?;
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| StorageError::io_context("read entry in", &dir_path, e))?;
+            let Ok(canonical_child) = path::canonicalize_path(&entry.path()) else {
+                continue;
+            };
+            if let Some(child_id) = ids.get(&canonical_child) {
+                children.push((entry.file_name().to_string_lossy().to_string(), *child_id));
+            }
+        }
+        children.sort_by(|a, b| a.0.cmp(&b.0));
+        let canonical = path::canonicalize_path(&dir_path)?;
+        ids.insert(canonical.clone(), directory_node_id(&canonical, &children));
+    }
+    Ok(ids)
+}
+
+/// The old walker's rule: a pattern matched anywhere in the path string or as a whole
+/// component, and `.gitignore` itself was never ignored.
+fn is_ignored(entry_path: &Path, ignore_patterns: &[String]) -> bool {
+    if entry_path.file_name() == Some(std::ffi::OsStr::new(".gitignore")) {
+        return false;
+    }
+    let path_str = entry_path.to_string_lossy();
+    ignore_patterns.iter().any(|pattern| {
+        path_str.contains(pattern.as_str())
+            || entry_path.components().any(|component| {
+                matches!(component, std::path::Component::Normal(name)
+                    if name.to_string_lossy() == pattern.as_str())
+            })
+    })
+}
+
+fn content_hash(file_path: &Path) -> Result<Hash, StorageError> {
+    let mut file =
+        fs::File::open(file_path).map_err(|e| StorageError::io_context("open", file_path, e))?;
+    let mut hasher = Hasher::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| StorageError::io_context("read", file_path, e))?;
+    Ok(*hasher.finalize().as_bytes())
+}
+
+/// hash("file" || path_len || absolute path || content_hash)
+fn file_node_id(canonical_path: &Path, content_hash: &Hash) -> NodeID {
+    let path_string = canonical_path.to_string_lossy();
+    let path_bytes = path_string.as_bytes();
+    let mut hasher = Hasher::new();
+    hasher.update(b"file");
+    hasher.update(&(path_bytes.len() as u64).to_be_bytes());
+    hasher.update(path_bytes);
+    hasher.update(content_hash);
+    *hasher.finalize().as_bytes()
+}
+
+/// hash("directory" || path_len || absolute path || children_count || name:child_id ...)
+fn directory_node_id(canonical_path: &Path, children: &[(String, NodeID)]) -> NodeID {
+    let path_string = canonical_path.to_string_lossy();
+    let path_bytes = path_string.as_bytes();
+    let mut hasher = Hasher::new();
+    hasher.update(b"directory");
+    hasher.update(&(path_bytes.len() as u64).to_be_bytes());
+    hasher.update(path_bytes);
+    hasher.update(&(children.len() as u64).to_be_bytes());
+    for (name, node_id) in children {
+        hasher.update(name.as_bytes());
+        hasher.update(b":");
+        hasher.update(node_id);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::builder::TreeBuilder;
+    use tempfile::TempDir;
+
+    #[test]
+    fn legacy_ids_use_the_old_walker_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("empty")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+        fs::write(root.join(".env"), "KEY=value").unwrap();
+        let tree = TreeBuilder::new(root.to_path_buf()).build().unwrap();
+
+        let legacy_ids = legacy_node_ids(&tree, root, &[]).unwrap();
+        // The current tree skips `.env` and `empty`; every node it keeps still maps.
+        assert_eq!(legacy_ids.len(), tree.nodes.len());
+
+        let canonical_root = path::canonicalize_path(root).unwrap();
+        let lib_id = file_node_id(
+            &canonical_root.join("src/lib.rs"),
+            &blake3::hash(b"pub fn f() {}").into(),
+        );
+        let env_id = file_node_id(
+            &canonical_root.join(".env"),
+            &blake3::hash(b"KEY=value").into(),
+        );
+        let src_id = directory_node_id(
+            &canonical_root.join("src"),
+            &[("lib.rs".to_string(), lib_id)],
+        );
+        let empty_id = directory_node_id(&canonical_root.join("empty"), &[]);
+        let root_id = directory_node_id(
+            &canonical_root,
+            &[
+                (".env".to_string(), env_id),
+                ("empty".to_string(), empty_id),
+                ("src".to_string(), src_id),
+            ],
+        );
+        assert_eq!(legacy_ids[&tree.root_id], root_id);
+        assert!(legacy_ids.values().any(|legacy_id| *legacy_id == lib_id));
+        assert!(!tree.nodes.contains_key(&root_id));
+    }
+
+    #[test]
+    fn bare_patterns_matched_as_substrings() {
+        let patterns = vec!["build".to_string()];
+        assert!(is_ignored(Path::new("/w/rebuild.rs"), &patterns));
+        assert!(!is_ignored(Path::new("/w/build/.gitignore"), &patterns));
+        assert!(!is_ignored(Path::new("/w/src/lib.rs"), &patterns));
+    }
+}
@@ -5,8 +5,10 @@
 
 pub mod builder;
 pub mod hasher;
+pub mod legacy;
 pub mod node;
 pub mod path;
+pub mod stats;
 pub mod walker;
 
 pub use builder::Tree;
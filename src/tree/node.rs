@@ -21,9 +21,19 @@ pub struct DirectoryNode {
     pub metadata: BTreeMap<String, String>,
 }
 
+/// Symlink node representation; the link itself, never its target's content
+#[derive(Debug, Clone)]
+pub struct SymlinkNode {
+    pub path: PathBuf,
+    /// Link target exactly as stored in the link (may be relative or dangling)
+    pub target: PathBuf,
+    pub metadata: BTreeMap<String, String>,
+}
+
 /// Merkle node type
 #[derive(Debug, Clone)]
 pub enum MerkleNode {
     File(FileNode),
     Directory(DirectoryNode),
+    Symlink(SymlinkNode),
 }
@@ -37,6 +37,25 @@ pub fn canonicalize_path(path: &Path) -> Result<PathBuf, crate::error::StorageEr
     Ok(normalized_path)
 }
 
+/// Canonicalize the path of a symlink without resolving the link itself
+///
+/// The parent directory is canonicalized and the link's own file name appended, so two links
+/// to the same target keep distinct paths.
+pub fn canonicalize_link_path(path: &Path) -> Result<PathBuf, crate::error::StorageError> {
+    let file_name = path.file_name().ok_or_else(|| {
+        crate::error::StorageError::InvalidPath(format!(
+            "Symlink path has no file name: {}",
+            path.display()
+        ))
+    })?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name: String = file_name.to_string_lossy().nfc().collect();
+    Ok(canonicalize_path(parent)?.join(name))
+}
+
 /// Normalize a path string for hashing (without filesystem access)
 ///
 /// This is used when we already have a canonical path and just need
@@ -0,0 +1,119 @@
+//! Tree composition metrics for `scan --stats`
+
+use crate::store::node_metadata::BINARY_METADATA_KEY;
+use crate::tree::builder::Tree;
+use crate::tree::node::MerkleNode;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Number of largest files reported.
+pub const LARGEST_FILES_LIMIT: usize = 10;
+
+/// File class for files whose content sniffed as binary.
+pub const FILE_CLASS_BINARY: &str = "binary";
+
+/// File class for everything else.
+pub const FILE_CLASS_TEXT: &str = "text";
+
+/// Composition of a built tree. Paths are relative to the workspace root.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TreeStats {
+    pub file_count: usize,
+    /// Directories below the workspace root; the root itself is not counted.
+    pub directory_count: usize,
+    pub symlink_count: usize,
+    /// Sum of file sizes in bytes.
+    pub total_bytes: u64,
+    /// Largest files first; ties ordered by path.
+    pub largest_files: Vec<FileSizeEntry>,
+    /// Most deeply nested path, if the tree has anything below the root.
+    pub deepest_path: Option<String>,
+    /// Path components in `deepest_path`.
+    pub max_depth: usize,
+    /// File count per class (`text` or `binary`).
+    pub file_classes: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSizeEntry {
+    pub path: String,
+    pub bytes: u64,
+}
+
+impl TreeStats {
+    /// Walk `tree.nodes` once and keep the `largest` biggest files.
+    pub fn from_tree(tree: &Tree, largest: usize) -> Self {
+        let root_path = tree.nodes.get(&tree.root_id).map(|root| match root {
+            MerkleNode::File(node) => node.path.as_path(),
+            MerkleNode::Directory(node) => node.path.as_path(),
+            MerkleNode::Symlink(node) => node.path.as_path(),
+        });
+        let relative = |path: &Path| {
+            root_path
+                .and_then(|root| path.strip_prefix(root).ok())
+                .unwrap_or(path)
+                .to_path_buf()
+        };
+
+        let mut stats = TreeStats::default();
+        let mut files = Vec::new();
+        let mut deepest: Option<(usize, String)> = None;
+        for (node_id, node) in &tree.nodes {
+            if *node_id == tree.root_id {
+                continue;
+            }
+            let path = match node {
+                MerkleNode::File(file) => {
+                    stats.file_count += 1;
+                    stats.total_bytes += file.size;
+                    let class = if file.metadata.contains_key(BINARY_METADATA_KEY) {
+                        FILE_CLASS_BINARY
+                    } else {
+                        FILE_CLASS_TEXT
+                    };
+                    *stats.file_classes.entry(class.to_string()).or_default() += 1;
+                    let path = relative(&file.path);
+                    files.push(FileSizeEntry {
+                        path: path.display().to_string(),
+                        bytes: file.size,
+                    });
+                    path
+                }
+                MerkleNode::Directory(dir) => {
+                    stats.directory_count += 1;
+                    relative(&dir.path)
+                }
+                MerkleNode::Symlink(link) => {
+                    stats.symlink_count += 1;
+                    relative(&link.path)
+                }
+            };
+            let depth = path.components().count();
+            let display = path.display().to_string();
+            let deeper = match &deepest {
+                None => true,
+                Some((best_depth, best_path)) => {
+                    depth > *best_depth || (depth == *best_depth && display < *best_path)
+                }
+            };
+            if deeper {
+                deepest = Some((depth, display));
+            }
+        }
+
+        files.sort_by(|a, b| {
+            Reverse(a.bytes)
+                .cmp(&Reverse(b.bytes))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        files.truncate(largest);
+        stats.largest_files = files;
+        if let Some((depth, path)) = deepest {
+            stats.max_depth = depth;
+            stats.deepest_path = Some(path);
+        }
+        stats
+    }
+}
@@ -1,6 +1,7 @@
 //! Filesystem walker for traversing directory structures
 
 use crate::error::StorageError;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use walkdir::{DirEntry, WalkDir};
 
@@ -11,6 +12,8 @@ pub enum Entry {
     File { path: PathBuf, size: u64 },
     /// A directory entry with its path
     Directory { path: PathBuf },
+    /// A symbolic link that is not followed, with its target as stored in the link
+    Symlink { path: PathBuf, target: PathBuf },
 }
 
 /// Filesystem walker configuration
@@ -22,6 +25,14 @@ pub struct WalkerConfig {
     pub ignore_patterns: Vec<String>,
     /// Maximum depth to traverse (None = unlimited)
     pub max_depth: Option<usize>,
+    /// Index dot-prefixed entries below the root (default: false).
+    /// `.git` is excluded and `.gitignore` is included regardless of this flag.
+    pub include_hidden: bool,
+    /// Record directories with no indexable descendants (default: false).
+    /// A directory counts as empty when nothing below it survives the ignore and hidden rules,
+    /// within `max_depth`. Empty directories change their parent's NodeID, so scans with and
+    /// without this flag produce different root hashes.
+    pub include_empty_dirs: bool,
 }
 
 impl Default for WalkerConfig {
@@ -35,6 +46,8 @@ impl Default for WalkerConfig {
                 ".cargo".to_string(),
             ],
             max_depth: None,
+            include_hidden: false,
+            include_empty_dirs: false,
         }
     }
 }
@@ -89,6 +102,18 @@ impl Walker {
                 continue;
             }
 
+            // Record unfollowed links as their own entries instead of reading through them
+            if !self.config.follow_symlinks && entry.path_is_symlink() {
+                let target = std::fs::read_link(&path).map_err(|e| {
+                    StorageError::IoError(std::io::Error::other(format!(
+                        "Failed to read symlink {:?}: {}",
+                        path, e
+                    )))
+                })?;
+                entries.push(Entry::Symlink { path, target });
+                continue;
+            }
+
             let metadata = entry.metadata().map_err(|e| {
                 StorageError::IoError(std::io::Error::other(format!(
                     "Failed to read metadata for {:?}: {}",
@@ -104,16 +129,24 @@ impl Walker {
             } else if metadata.is_dir() {
                 entries.push(Entry::Directory { path });
             }
-            // Skip symlinks if not following them
+            // Skip other special files (sockets, FIFOs, devices)
+        }
+
+        if !self.config.include_empty_dirs {
+            self.retain_populated_directories(&mut entries);
         }
 
         // Sort entries by path for determinism
         entries.sort_by(|a, b| {
             let path_a = match a {
-                Entry::File { path, .. } | Entry::Directory { path } => path,
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path,
             };
             let path_b = match b {
-                Entry::File { path, .. } | Entry::Directory { path } => path,
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path,
             };
             path_a.cmp(path_b)
         });
@@ -121,18 +154,43 @@ impl Walker {
         Ok(entries)
     }
 
-    /// Check if an entry should be ignored based on ignore patterns.
+    /// Drop directory entries with no file or symlink anywhere below them.
+    fn retain_populated_directories(&self, entries: &mut Vec<Entry>) {
+        let mut populated: HashSet<PathBuf> = HashSet::new();
+        for entry in entries.iter() {
+            let path = match entry {
+                Entry::File { path, .. } | Entry::Symlink { path, .. } => path,
+                Entry::Directory { .. } => continue,
+            };
+            for ancestor in path.ancestors().skip(1) {
+                if ancestor == self.root || !populated.insert(ancestor.to_path_buf()) {
+                    break;
+                }
+            }
+        }
+        entries.retain(|entry| match entry {
+            Entry::Directory { path } => populated.contains(path),
+            Entry::File { .. } | Entry::Symlink { .. } => true,
+        });
+    }
+
+    /// Check if an entry should be ignored based on hidden-file rules and ignore patterns.
     /// We never ignore a file named exactly ".gitignore" so it stays in the tree and can be synced to ignore_list.
     fn should_ignore(&self, entry: &DirEntry) -> bool {
         let path = entry.path();
+        if self.is_hidden_excluded(path) {
+            return true;
+        }
         if path.file_name() == Some(std::ffi::OsStr::new(".gitignore")) {
             return false;
         }
         let path_str = path.to_string_lossy();
 
         for pattern in &self.config.ignore_patterns {
-            // Check if path contains the ignore pattern
-            if path_str.contains(pattern) {
+            // Path-like patterns match as substrings. Bare names only match whole components so
+            // that `.git` does not swallow `.github` once hidden entries are indexed; before
+            // hidden entries could be indexed, bare names matched as substrings too.
+            if pattern.contains('/') && path_str.contains(pattern) {
                 return true;
             }
 
@@ -148,6 +206,23 @@ impl Walker {
 
         false
     }
+
+    /// Hidden-file rules apply only to components below the root, so a workspace that itself
+    /// lives under a dot directory is still walked. `.git` is a hardcoded exclusion that
+    /// `include_hidden` cannot lift.
+    fn is_hidden_excluded(&self, path: &std::path::Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        relative.components().any(|component| match component {
+            std::path::Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                name == ".git"
+                    || (!self.config.include_hidden
+                        && name.starts_with('.')
+                        && name != ".gitignore")
+            }
+            _ => false,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -194,8 +269,7 @@ mod tests {
         let walker = Walker::new(root);
         let entries = walker.walk().unwrap();
 
-        // Should have 1 directory (dir1) and 1 file
-        // Note: dir2 is empty, so it should still be collected
+        // Should have 1 directory (dir1) and 1 file; dir2 is empty, so it is omitted by default
         let dirs: Vec<_> = entries
             .iter()
             .filter_map(|e| match e {
@@ -234,7 +308,9 @@ mod tests {
         let paths: Vec<_> = entries
             .iter()
             .map(|e| match e {
-                Entry::File { path, .. } | Entry::Directory { path } => path.clone(),
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path.clone(),
             })
             .collect();
 
@@ -245,6 +321,85 @@ mod tests {
         assert!(paths.iter().any(|p| p.ends_with("file.txt")));
     }
 
+    fn walked_paths(walker: &Walker) -> Vec<PathBuf> {
+        walker
+            .walk()
+            .unwrap()
+            .into_iter()
+            .map(|e| match e {
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_walker_excludes_hidden_entries_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::write(root.join("visible.txt"), "content").unwrap();
+        fs::write(root.join(".env.example"), "KEY=value").unwrap();
+        fs::create_dir(root.join(".github")).unwrap();
+        fs::write(root.join(".github").join("ci.yml"), "on: push").unwrap();
+        fs::write(root.join(".gitignore"), "target").unwrap();
+
+        let paths = walked_paths(&Walker::new(root));
+
+        assert!(paths.iter().any(|p| p.ends_with("visible.txt")));
+        assert!(paths.iter().any(|p| p.ends_with(".gitignore")));
+        assert!(!paths.iter().any(|p| p.ends_with(".env.example")));
+        assert!(!paths
+            .iter()
+            .any(|p| p.to_string_lossy().contains(".github")));
+    }
+
+    #[test]
+    fn test_walker_include_hidden_indexes_dotfiles_but_not_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::write(root.join(".env.example"), "KEY=value").unwrap();
+        fs::create_dir(root.join(".github")).unwrap();
+        fs::write(root.join(".github").join("ci.yml"), "on: push").unwrap();
+        let git_dir = root.join(".git");
+        fs::create_dir(&git_dir).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main").unwrap();
+
+        // Empty ignore patterns prove `.git` exclusion does not depend on the ignore list.
+        let config = WalkerConfig {
+            ignore_patterns: Vec::new(),
+            include_hidden: true,
+            ..WalkerConfig::default()
+        };
+        let paths = walked_paths(&Walker::with_config(root, config));
+
+        assert!(paths.iter().any(|p| p.ends_with(".env.example")));
+        assert!(paths.iter().any(|p| p.ends_with(".github/ci.yml")));
+        assert!(!paths.iter().any(|p| p.to_string_lossy().contains(".git/")));
+        assert!(!paths.iter().any(|p| p.ends_with(".git")));
+    }
+
+    #[test]
+    fn test_walker_bare_patterns_match_whole_components() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::create_dir(root.join("build")).unwrap();
+        fs::write(root.join("build").join("out.o"), "obj").unwrap();
+        fs::write(root.join("rebuild.rs"), "fn main() {}").unwrap();
+        fs::create_dir_all(root.join("docs").join("gen")).unwrap();
+        fs::write(root.join("docs").join("gen").join("api.md"), "api").unwrap();
+
+        let config = WalkerConfig {
+            ignore_patterns: vec!["build".to_string(), "docs/gen".to_string()],
+            ..WalkerConfig::default()
+        };
+        let paths = walked_paths(&Walker::with_config(root, config));
+
+        assert!(paths.iter().any(|p| p.ends_with("rebuild.rs")));
+        assert!(!paths.iter().any(|p| p.ends_with("build/out.o")));
+        assert!(!paths.iter().any(|p| p.ends_with("docs/gen/api.md")));
+    }
+
     #[test]
     fn test_walker_deterministic_ordering() {
         let temp_dir = TempDir::new().unwrap();
@@ -263,10 +418,14 @@ mod tests {
         assert_eq!(entries1.len(), entries2.len());
         for (e1, e2) in entries1.iter().zip(entries2.iter()) {
             let path1 = match e1 {
-                Entry::File { path, .. } | Entry::Directory { path } => path,
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path,
             };
             let path2 = match e2 {
-                Entry::File { path, .. } | Entry::Directory { path } => path,
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path,
             };
             assert_eq!(path1, path2);
         }
@@ -275,11 +434,39 @@ mod tests {
         let paths: Vec<_> = entries1
             .iter()
             .map(|e| match e {
-                Entry::File { path, .. } | Entry::Directory { path } => path.clone(),
+                Entry::File { path, .. }
+                | Entry::Directory { path }
+                | Entry::Symlink { path, .. } => path.clone(),
             })
             .collect();
         let mut sorted_paths = paths.clone();
         sorted_paths.sort();
         assert_eq!(paths, sorted_paths);
     }
+
+    #[test]
+    fn test_walker_omits_empty_directories_unless_included() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(root.join("empty").join("nested")).unwrap();
+        fs::create_dir_all(root.join("ignored_only")).unwrap();
+        fs::write(root.join("ignored_only").join("x.log"), "x").unwrap();
+        fs::create_dir_all(root.join("full").join("deep")).unwrap();
+        fs::write(root.join("full").join("deep").join("a.txt"), "a").unwrap();
+
+        let config = |include_empty_dirs| WalkerConfig {
+            ignore_patterns: vec!["x.log".to_string()],
+            include_empty_dirs,
+            ..WalkerConfig::default()
+        };
+        let omitted = walked_paths(&Walker::with_config(root.clone(), config(false)));
+        assert!(omitted.iter().any(|p| p.ends_with("full/deep")));
+        assert!(!omitted.iter().any(|p| p.ends_with("empty")));
+        assert!(!omitted.iter().any(|p| p.ends_with("empty/nested")));
+        assert!(!omitted.iter().any(|p| p.ends_with("ignored_only")));
+
+        let included = walked_paths(&Walker::with_config(root, config(true)));
+        assert!(included.iter().any(|p| p.ends_with("empty/nested")));
+        assert!(included.iter().any(|p| p.ends_with("ignored_only")));
+    }
 }
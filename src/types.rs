@@ -1,5 +1,10 @@
 //! Core types for the Merkle filesystem state management system.
 
+use crate::error::ApiError;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
 /// NodeID: Deterministic hash of a filesystem node (file or directory)
 pub type NodeID = [u8; 32];
 
@@ -8,3 +13,160 @@ pub type FrameID = [u8; 32];
 
 /// Hash: Generic 256-bit hash value
 pub type Hash = [u8; 32];
+
+/// Hex form of a [`Hash`], [`NodeID`], or [`FrameID`].
+///
+/// The IDs stay plain byte arrays everywhere they are stored; wrap one in `HexHash` to print
+/// it as lowercase hex or to parse user input. Parsing accepts an optional `0x` prefix and
+/// requires exactly 32 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub struct HexHash(pub Hash);
+
+/// Why a string or byte slice is not a 32-byte hash.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum HashParseError {
+    #[error("Invalid hex string: {0}")]
+    InvalidHex(String),
+
+    #[error("hash must be 32 bytes, got {0} bytes")]
+    WrongLength(usize),
+}
+
+impl HashParseError {
+    /// Message naming the kind of ID that failed to parse, e.g. `NodeID must be 32 bytes`.
+    pub fn describe(&self, id_kind: &str) -> String {
+        match self {
+            HashParseError::InvalidHex(_) => self.to_string(),
+            HashParseError::WrongLength(len) => {
+                format!("{} must be 32 bytes, got {} bytes", id_kind, len)
+            }
+        }
+    }
+}
+
+impl fmt::Display for HexHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for HexHash {
+    type Err = HashParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.strip_prefix("0x").unwrap_or(s);
+        let bytes = hex::decode(trimmed).map_err(|e| HashParseError::InvalidHex(e.to_string()))?;
+        HexHash::try_from(bytes.as_slice())
+    }
+}
+
+impl TryFrom<&[u8]> for HexHash {
+    type Error = HashParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let hash: Hash = bytes
+            .try_into()
+            .map_err(|_| HashParseError::WrongLength(bytes.len()))?;
+        Ok(HexHash(hash))
+    }
+}
+
+impl From<Hash> for HexHash {
+    fn from(hash: Hash) -> Self {
+        HexHash(hash)
+    }
+}
+
+impl From<HexHash> for Hash {
+    fn from(hash: HexHash) -> Self {
+        hash.0
+    }
+}
+
+/// Parse a `--node` style NodeID: 64 hex chars with an optional `0x` prefix.
+pub fn parse_node_id(s: &str) -> Result<NodeID, ApiError> {
+    s.parse::<HexHash>()
+        .map(Hash::from)
+        .map_err(|e| ApiError::InvalidFrame(e.describe("NodeID")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+
+    fn expected() -> Hash {
+        std::array::from_fn(|i| i as u8 + 1)
+    }
+
+    #[test]
+    fn parses_plain_and_prefixed_hex() {
+        assert_eq!(HEX.parse::<HexHash>().unwrap().0, expected());
+        assert_eq!(
+            format!("0x{}", HEX).parse::<HexHash>().unwrap().0,
+            expected()
+        );
+        assert_eq!(HEX.to_uppercase().parse::<HexHash>().unwrap().0, expected());
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert_eq!(
+            "abcd".parse::<HexHash>(),
+            Err(HashParseError::WrongLength(2))
+        );
+        assert_eq!(
+            format!("{}00", HEX).parse::<HexHash>(),
+            Err(HashParseError::WrongLength(33))
+        );
+        assert_eq!("".parse::<HexHash>(), Err(HashParseError::WrongLength(0)));
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        let bad = format!("{}zz", &HEX[..62]);
+        assert!(matches!(
+            bad.parse::<HexHash>(),
+            Err(HashParseError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            "0x0".parse::<HexHash>(),
+            Err(HashParseError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        let hash = HexHash(expected());
+        assert_eq!(hash.to_string(), HEX);
+        assert_eq!(hash.to_string().parse::<HexHash>().unwrap(), hash);
+    }
+
+    #[test]
+    fn try_from_slice_checks_length() {
+        assert_eq!(
+            HexHash::try_from(expected().as_slice()).unwrap().0,
+            expected()
+        );
+        assert_eq!(
+            HexHash::try_from(&expected()[..31]),
+            Err(HashParseError::WrongLength(31))
+        );
+    }
+
+    #[test]
+    fn parse_node_id_reports_node_id_errors() {
+        assert_eq!(parse_node_id(&format!("0x{}", HEX)).unwrap(), expected());
+        let err = parse_node_id("abcd").unwrap_err();
+        assert!(matches!(err, ApiError::InvalidFrame(_)));
+        assert!(
+            err.to_string()
+                .contains("NodeID must be 32 bytes, got 2 bytes"),
+            "{}",
+            err
+        );
+        let err = parse_node_id("not hex").unwrap_err();
+        assert!(err.to_string().contains("Invalid hex string"), "{}", err);
+    }
+}
@@ -7,7 +7,7 @@ use crate::context::queue::QueueEventContext;
 use crate::error::ApiError;
 use crate::execution::ExecutionEventContext;
 use crate::provider::{ProviderExecutionBinding, ProviderRuntimeOverrides};
-use crate::types::NodeID;
+use crate::types::{parse_node_id, NodeID};
 use crate::workflow::facade::{build_target_execution_request, execute_registered_workflow_target};
 use crate::workflow::profile::WorkflowProfile;
 use crate::workflow::registry::WorkflowRegistry;
@@ -191,21 +191,6 @@ impl WorkflowCommandService {
     }
 }
 
-fn parse_node_id(s: &str) -> Result<NodeID, ApiError> {
-    let trimmed = s.strip_prefix("0x").unwrap_or(s);
-    let bytes = hex::decode(trimmed)
-        .map_err(|err| ApiError::InvalidFrame(format!("Invalid hex: {}", err)))?;
-    if bytes.len() != 32 {
-        return Err(ApiError::InvalidFrame(format!(
-            "NodeID must be 32 bytes, got {}",
-            bytes.len()
-        )));
-    }
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&bytes);
-    Ok(crate::types::Hash::from(hash))
-}
-
 fn resolve_node_id(
     api: &ContextApi,
     workspace_root: &Path,
@@ -214,9 +199,14 @@ fn resolve_node_id(
 ) -> Result<NodeID, ApiError> {
     match (node, path) {
         (Some(node_id), None) => parse_node_id(node_id),
-        (None, Some(path)) => {
-            workspace::resolve_workspace_node_id(api, workspace_root, Some(path), None, false)
-        }
+        (None, Some(path)) => workspace::resolve_workspace_node_id_with_policy(
+            api,
+            workspace_root,
+            Some(path),
+            None,
+            false,
+            api.strict_paths(),
+        ),
         (Some(_), Some(_)) => Err(ApiError::ConfigError(
             "Cannot specify both --node and --path".to_string(),
         )),
@@ -211,7 +211,9 @@ pub fn build_target_execution_request(
         node_id,
         path: record.path.to_string_lossy().to_string(),
         node_type: match record.node_type {
-            NodeType::File { .. } => crate::context::generation::GenerationNodeType::File,
+            NodeType::File { .. } | NodeType::Symlink { .. } => {
+                crate::context::generation::GenerationNodeType::File
+            }
             NodeType::Directory => crate::context::generation::GenerationNodeType::Directory,
         },
         agent_id,
@@ -1,6 +1,6 @@
 use crate::api::ContextApi;
-use crate::cli::WorkflowCommands;
-use crate::config::ConfigLoader;
+use crate::cli::{to_json_output, WorkflowCommands};
+use crate::config::MerkleConfig;
 use crate::context::queue::QueueEventContext;
 use crate::error::ApiError;
 use crate::telemetry::ProgressRuntime;
@@ -11,7 +11,7 @@ use std::sync::Arc;
 pub fn handle_cli_command(
     api: &ContextApi,
     workspace_root: &Path,
-    config_path: Option<&Path>,
+    load_config: &dyn Fn() -> Result<MerkleConfig, ApiError>,
     workflow_registry: &parking_lot::RwLock<WorkflowRegistry>,
     progress: &Arc<ProgressRuntime>,
     command: &WorkflowCommands,
@@ -22,7 +22,7 @@ pub fn handle_cli_command(
             let registry = workflow_registry.read();
             let result = WorkflowCommandService::run_list(&registry);
             if format == "json" {
-                serde_json::to_string_pretty(&result).map_err(|err| {
+                to_json_output(&result).map_err(|err| {
                     ApiError::ConfigError(format!(
                         "Failed to serialize workflow list result: {}",
                         err
@@ -44,10 +44,10 @@ pub fn handle_cli_command(
             }
         }
         WorkflowCommands::Validate { format } => {
-            let config = load_runtime_config(workspace_root, config_path)?;
+            let config = load_config()?;
             let result = WorkflowCommandService::run_validate(&config.workflows)?;
             if format == "json" {
-                serde_json::to_string_pretty(&result).map_err(|err| {
+                to_json_output(&result).map_err(|err| {
                     ApiError::ConfigError(format!(
                         "Failed to serialize workflow validate result: {}",
                         err
@@ -67,7 +67,7 @@ pub fn handle_cli_command(
             let registry = workflow_registry.read();
             let result = WorkflowCommandService::run_inspect(&registry, workflow_id)?;
             if format == "json" {
-                serde_json::to_string_pretty(&result).map_err(|err| {
+                to_json_output(&result).map_err(|err| {
                     ApiError::ConfigError(format!(
                         "Failed to serialize workflow inspect result: {}",
                         err
@@ -136,21 +136,3 @@ pub fn handle_cli_command(
         }
     }
 }
-
-fn load_runtime_config(
-    workspace_root: &Path,
-    config_path: Option<&Path>,
-) -> Result<crate::config::MerkleConfig, ApiError> {
-    if let Some(config_path) = config_path {
-        ConfigLoader::load_from_file(config_path).map_err(|err| {
-            ApiError::ConfigError(format!(
-                "Failed to load config from {}: {}",
-                config_path.display(),
-                err
-            ))
-        })
-    } else {
-        ConfigLoader::load(workspace_root)
-            .map_err(|err| ApiError::ConfigError(format!("Failed to load config: {}", err)))
-    }
-}
@@ -7,9 +7,13 @@ mod danger;
 pub mod events;
 mod facade;
 mod format;
+mod hierarchy;
 pub mod publish;
 pub(crate) mod reducer;
+mod repair;
 mod section;
+mod size;
+pub mod status_watch;
 pub mod summary;
 pub mod tooling;
 mod types;
@@ -4,44 +4,141 @@
 
 use crate::agent::AgentRegistry;
 use crate::api::ContextApi;
+use crate::context::frame::Basis;
 use crate::context::head::CurrentFrameHeadRead;
 use crate::error::ApiError;
 use crate::execution::ContextReadPort;
 use crate::ignore;
-use crate::store::{NodeRecord, NodeRecordStore};
+use crate::store::{NodeRecord, NodeRecordStore, NodeType};
 use crate::telemetry::ProgressRuntime;
 use crate::tree::builder::TreeBuilder;
+use crate::tree::hasher::ContentNormalization;
+use crate::tree::stats::{TreeStats, LARGEST_FILES_LIMIT};
 use crate::tree::walker::WalkerConfig;
-use crate::types::NodeID;
+use crate::types::{HashParseError, HexHash, NodeID};
 use crate::workspace::events::{
     node_observed_envelope, scan_completed_envelope, snapshot_materialized_envelope,
     snapshot_selected_envelope, source_attached_envelope,
 };
+use crate::workspace::hierarchy;
+use crate::workspace::repair;
 use crate::workspace::section;
+use crate::workspace::size;
 use crate::workspace::types::{
     AgentStatusEntry, AgentStatusOutput, IgnoreResult, ListDeletedResult, ListDeletedRow,
-    ProviderStatusEntry, ProviderStatusOutput, UnifiedStatusOutput, ValidateResult,
-    WorkspaceScanInfo, WorkspaceScanState, WorkspaceStatusRequest, WorkspaceStatusResult,
+    NodeTreeEntry, ProviderStatusEntry, ProviderStatusOutput, RepairReport, ScanOutcome,
+    UnifiedStatusOutput, ValidateResult, WorkspaceScanInfo, WorkspaceScanState,
+    WorkspaceSizeResult, WorkspaceStatusRequest, WorkspaceStatusResult, WorkspaceStatusSummary,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 
-pub(crate) fn workspace_walker_config(workspace_root: &Path) -> WalkerConfig {
+/// Store setting holding the walker options of the last scan or watch start.
+const WALKER_OPTIONS_SETTING: &str = "walker_options";
+
+/// Walker options chosen when the tree was scanned. They change which nodes exist and so the
+/// root hash; they are recorded in the node store so status, freshness and validate rebuild the
+/// tree the same way the scan did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ScanWalkerOptions {
+    pub include_hidden: bool,
+    pub include_empty_dirs: bool,
+}
+
+impl ScanWalkerOptions {
+    /// Options recorded by the last scan; defaults for stores scanned before they were recorded.
+    pub(crate) fn recorded(node_store: &dyn NodeRecordStore) -> Result<Self, ApiError> {
+        match node_store
+            .get_setting(WALKER_OPTIONS_SETTING)
+            .map_err(ApiError::from)?
+        {
+            Some(value) => serde_json::from_str(&value).map_err(|e| {
+                ApiError::ConfigError(format!("Invalid recorded walker options: {}", e))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub(crate) fn record(&self, node_store: &dyn NodeRecordStore) -> Result<(), ApiError> {
+        let value = serde_json::to_string(self)
+            .map_err(|e| ApiError::ConfigError(format!("Invalid walker options: {}", e)))?;
+        node_store
+            .put_setting(WALKER_OPTIONS_SETTING, &value)
+            .map_err(ApiError::from)
+    }
+}
+
+/// Walker config for workspace scans: workspace ignore patterns, no symlink following.
+pub(crate) fn workspace_walker_config(
+    workspace_root: &Path,
+    options: ScanWalkerOptions,
+) -> WalkerConfig {
     let ignore_patterns = ignore::load_ignore_patterns(workspace_root)
         .unwrap_or_else(|_| WalkerConfig::default().ignore_patterns);
     WalkerConfig {
         follow_symlinks: false,
         ignore_patterns,
         max_depth: None,
+        include_hidden: options.include_hidden,
+        include_empty_dirs: options.include_empty_dirs,
+    }
+}
+
+/// Store setting recording which NodeID scheme the stored heads use.
+const NODE_ID_SCHEME_SETTING: &str = "node_id_scheme";
+
+/// Paths hashed relative to the workspace root; stores without the setting predate it.
+const RELATIVE_PATH_NODE_ID_SCHEME: &str = "relative-path";
+
+/// Rebase heads recorded under absolute-path NodeIDs onto `tree`'s NodeIDs, once per store.
+///
+/// Runs before the first scan or watch start writes the new records, then marks the store so
+/// later scans skip the legacy walk. Returns the number of heads rebased.
+pub(crate) fn migrate_legacy_node_ids(
+    api: &ContextApi,
+    workspace_root: &Path,
+    tree: &crate::tree::builder::Tree,
+) -> Result<u64, ApiError> {
+    let node_store = api.node_store().as_ref();
+    if node_store
+        .get_setting(NODE_ID_SCHEME_SETTING)
+        .map_err(ApiError::from)?
+        .as_deref()
+        == Some(RELATIVE_PATH_NODE_ID_SCHEME)
+    {
+        return Ok(0);
     }
+    let has_heads = !api.head_index().read().active_entries().is_empty();
+    let rebased = if has_heads {
+        let ignore_patterns = ignore::load_ignore_patterns_without_git_excludes(workspace_root)
+            .unwrap_or_else(|_| WalkerConfig::default().ignore_patterns);
+        let legacy_ids =
+            crate::tree::legacy::legacy_node_ids(tree, workspace_root, &ignore_patterns)
+                .map_err(ApiError::StorageError)?;
+        api.carry_heads_forward(&legacy_ids)?
+    } else {
+        0
+    };
+    node_store
+        .put_setting(NODE_ID_SCHEME_SETTING, RELATIVE_PATH_NODE_ID_SCHEME)
+        .map_err(ApiError::from)?;
+    Ok(rebased)
 }
 
-pub(crate) fn current_workspace_root_hash(workspace_root: &Path) -> Result<NodeID, ApiError> {
+/// Root hash of the workspace as it is now, walked with the options of the last scan.
+pub(crate) fn current_workspace_root_hash(
+    node_store: &dyn NodeRecordStore,
+    workspace_root: &Path,
+    normalization: ContentNormalization,
+) -> Result<NodeID, ApiError> {
+    let options = ScanWalkerOptions::recorded(node_store)?;
     TreeBuilder::new(workspace_root.to_path_buf())
-        .with_walker_config(workspace_walker_config(workspace_root))
+        .with_walker_config(workspace_walker_config(workspace_root, options))
+        .with_content_normalization(normalization)
         .compute_root()
         .map_err(ApiError::from)
 }
@@ -102,8 +199,10 @@ pub(crate) fn stored_workspace_root_hash(
 pub(crate) fn assess_workspace_scan_state(
     node_store: &dyn NodeRecordStore,
     workspace_root: &Path,
+    normalization: ContentNormalization,
 ) -> Result<WorkspaceScanInfo, ApiError> {
-    let current_root_hash_id = current_workspace_root_hash(workspace_root)?;
+    let current_root_hash_id =
+        current_workspace_root_hash(node_store, workspace_root, normalization)?;
     let current_root_hash = hex::encode(current_root_hash_id);
     let active_node_count = node_store.list_active().map_err(ApiError::from)?.len();
     let stored_root_hash =
@@ -124,24 +223,74 @@ pub(crate) fn assess_workspace_scan_state(
     })
 }
 
+/// Whether the scanned tree holds any file nodes; `None` when nothing has been scanned.
+///
+/// A tree whose files were all ignored still stores the root directory, so it counts as scanned
+/// but empty.
+pub fn workspace_has_indexable_files(api: &ContextApi) -> Result<Option<bool>, ApiError> {
+    let active = api.node_store().list_active().map_err(ApiError::from)?;
+    if active.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(active.iter().any(is_indexable_file)))
+}
+
+/// A file node other than `.gitignore`, which the walker always indexes so it can be synced
+/// to the ignore list.
+pub(crate) fn is_indexable_file(record: &NodeRecord) -> bool {
+    matches!(record.node_type, NodeType::File { .. })
+        && record.path.file_name() != Some(std::ffi::OsStr::new(".gitignore"))
+}
+
+/// Fail with [`ApiError::EmptyWorkspace`] when the scanned tree holds no files.
+///
+/// Unscanned workspaces pass; path resolution reports those with its own guidance.
+pub fn ensure_workspace_populated(api: &ContextApi, workspace_root: &Path) -> Result<(), ApiError> {
+    match workspace_has_indexable_files(api)? {
+        Some(false) => Err(ApiError::EmptyWorkspace(workspace_root.to_path_buf())),
+        Some(true) | None => Ok(()),
+    }
+}
+
 pub fn read_workspace_scan_state(
     api: &ContextApi,
     workspace_root: &Path,
 ) -> Result<WorkspaceScanInfo, ApiError> {
-    assess_workspace_scan_state(api.node_store().as_ref(), workspace_root)
+    assess_workspace_scan_state(
+        api.node_store().as_ref(),
+        workspace_root,
+        api.content_normalization(),
+    )
 }
 
 /// Resolve path or --node to NodeID. If include_tombstoned is true, use get_by_path (for restore).
+///
+/// Paths are held to the workspace root; use [`resolve_workspace_node_id_with_policy`] to relax that.
 pub fn resolve_workspace_node_id(
     api: &(impl ContextReadPort + ?Sized),
     workspace_root: &Path,
     path: Option<&Path>,
     node: Option<&str>,
     include_tombstoned: bool,
+) -> Result<NodeID, ApiError> {
+    resolve_workspace_node_id_with_policy(api, workspace_root, path, node, include_tombstoned, true)
+}
+
+/// Like [`resolve_workspace_node_id`], with `strict_paths` controlling workspace containment.
+pub fn resolve_workspace_node_id_with_policy(
+    api: &(impl ContextReadPort + ?Sized),
+    workspace_root: &Path,
+    path: Option<&Path>,
+    node: Option<&str>,
+    include_tombstoned: bool,
+    strict_paths: bool,
 ) -> Result<NodeID, ApiError> {
     match (path, node) {
         (Some(p), None) => {
             let resolved_path = workspace_lookup_path(workspace_root, p);
+            if strict_paths {
+                ensure_path_within_workspace(workspace_root, &resolved_path)?;
+            }
             let record = if include_tombstoned {
                 api.read_node_record_by_path(&resolved_path, true)?
             } else {
@@ -171,15 +320,20 @@ pub fn resolve_workspace_node_id(
             Err(ApiError::PathNotInTree(resolved_path))
         }
         (None, Some(hex_str)) => {
-            let bytes = hex::decode(hex_str.trim_start_matches("0x"))
-                .map_err(|_| ApiError::ConfigError(format!("Invalid node ID hex: {}", hex_str)))?;
-            if bytes.len() != 32 {
-                return Err(ApiError::ConfigError(
-                    "Node ID must be 32 bytes (64 hex chars).".to_string(),
-                ));
-            }
-            let mut node_id = [0u8; 32];
-            node_id.copy_from_slice(&bytes);
+            let node_id: NodeID = match hex_str.parse::<HexHash>() {
+                Ok(hash) => hash.into(),
+                Err(HashParseError::InvalidHex(_)) => {
+                    return Err(ApiError::ConfigError(format!(
+                        "Invalid node ID hex: {}",
+                        hex_str
+                    )))
+                }
+                Err(HashParseError::WrongLength(_)) => {
+                    return Err(ApiError::ConfigError(
+                        "Node ID must be 32 bytes (64 hex chars).".to_string(),
+                    ))
+                }
+            };
             if api.read_node_record(&node_id)?.is_none() {
                 return Err(ApiError::NodeNotFound(node_id));
             }
@@ -194,30 +348,74 @@ pub fn resolve_workspace_node_id(
     }
 }
 
-/// Fallback: match by canonical path when direct path lookup misses.
+/// Reject `path` when, after canonicalization, it is not under the canonical workspace root.
+///
+/// Paths that no longer exist (e.g. tombstoned nodes) are checked through their deepest existing
+/// ancestor so `..` segments and symlinks cannot escape the root.
+pub fn ensure_path_within_workspace(workspace_root: &Path, path: &Path) -> Result<(), ApiError> {
+    let canonical_root = canonicalize_existing_prefix(workspace_root);
+    let canonical_target = canonicalize_existing_prefix(path);
+    if canonical_target.starts_with(&canonical_root) {
+        return Ok(());
+    }
+    Err(ApiError::InvalidPath(format!(
+        "{} is outside the workspace root {}; set system.strict_paths = false to allow it",
+        path.display(),
+        canonical_root.display()
+    )))
+}
+
+fn canonicalize_existing_prefix(path: &Path) -> PathBuf {
+    let mut existing = path.to_path_buf();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = crate::tree::path::canonicalize_path(&existing) {
+            let mut resolved = canonical;
+            for component in missing.iter().rev() {
+                resolved.push(component);
+            }
+            return lexically_normalize(&resolved);
+        }
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                missing.push(name.to_os_string());
+                existing = parent.to_path_buf();
+            }
+            _ => return lexically_normalize(path),
+        }
+    }
+}
+
+fn lexically_normalize(path: &Path) -> PathBuf {
+    use std::path::Component;
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// Fallback: look up legacy records stored under a workspace-relative path (`src/a.rs` or
+/// `./src/a.rs`) when direct path lookup misses. Both probes go through the path index.
 pub fn resolve_node_id_by_canonical_fallback(
     store: &(impl ContextReadPort + ?Sized),
     workspace_root: &Path,
     canonical_target: &Path,
     include_tombstoned: bool,
 ) -> Result<Option<NodeID>, ApiError> {
-    let records = if include_tombstoned {
-        store.list_node_records(true)?
-    } else {
-        store.list_node_records(false)?
+    let canonical_root = crate::tree::path::canonicalize_path(workspace_root)
+        .unwrap_or_else(|_| workspace_root.to_path_buf());
+    let Ok(relative) = canonical_target.strip_prefix(&canonical_root) else {
+        return Ok(None);
     };
-
-    for record in records {
-        let candidate = if record.path.is_absolute() {
-            record.path.clone()
-        } else {
-            workspace_root.join(&record.path)
-        };
-        let canonical_candidate = match crate::tree::path::canonicalize_path(&candidate) {
-            Ok(path) => path,
-            Err(_) => continue,
-        };
-        if canonical_candidate == canonical_target {
+    for candidate in [relative.to_path_buf(), Path::new(".").join(relative)] {
+        if let Some(record) = store.read_node_record_by_path(&candidate, include_tombstoned)? {
             return Ok(Some(record.node_id));
         }
     }
@@ -243,6 +441,46 @@ fn count_frame_files(path: &PathBuf) -> Result<usize, ApiError> {
     Ok(count)
 }
 
+/// Row order for `workspace list-deleted --sort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeletedSort {
+    Path,
+    /// Youngest tombstones first.
+    Age,
+    /// Oldest tombstones first.
+    TombstonedAt,
+}
+
+impl DeletedSort {
+    fn parse(field: &str) -> Result<Self, ApiError> {
+        match field {
+            "path" => Ok(Self::Path),
+            "age" => Ok(Self::Age),
+            "tombstoned_at" => Ok(Self::TombstonedAt),
+            other => Err(ApiError::ConfigError(format!(
+                "Unsupported sort field '{}'; use path, age, or tombstoned_at",
+                other
+            ))),
+        }
+    }
+
+    fn apply(self, rows: &mut [ListDeletedRow]) {
+        match self {
+            Self::Path => rows.sort_by(|a, b| a.path.cmp(&b.path)),
+            Self::Age => rows.sort_by(|a, b| {
+                b.tombstoned_at
+                    .cmp(&a.tombstoned_at)
+                    .then_with(|| a.path.cmp(&b.path))
+            }),
+            Self::TombstonedAt => rows.sort_by(|a, b| {
+                a.tombstoned_at
+                    .cmp(&b.tombstoned_at)
+                    .then_with(|| a.path.cmp(&b.path))
+            }),
+        }
+    }
+}
+
 /// Stateless workspace command service.
 pub struct WorkspaceCommandService;
 
@@ -255,17 +493,99 @@ impl WorkspaceCommandService {
         agent_registry: &AgentRegistry,
     ) -> Result<WorkspaceStatusResult, ApiError> {
         let node_store = api.node_store().as_ref() as &dyn NodeRecordStore;
-        section::build_workspace_status(
+        let mut status = section::build_workspace_status(
             node_store,
             api,
             agent_registry,
             &request.workspace_root,
             &request.store_path,
             request.include_breakdown,
+            request.include_content_hash,
+            api.content_normalization(),
+        )?;
+        if request.check_heads {
+            status.head_index_health = Some(section::build_head_index_health(api)?);
+        }
+        if request.by_agent {
+            let generatable_nodes = status
+                .tree
+                .as_ref()
+                .map_or(0, |tree| tree.total_nodes - tree.symlink_nodes);
+            status.agent_coverage = Some(section::build_agent_coverage(api, generatable_nodes)?);
+        }
+        if request.check_staleness {
+            status.mtime_staleness = Some(section::build_mtime_staleness(api)?);
+        }
+        Ok(status)
+    }
+
+    /// Node, head frame, and coverage counts for one workspace, for `status --workspaces`.
+    pub fn status_summary(
+        api: &ContextApi,
+        workspace_root: &Path,
+        store_path: &Path,
+        agent_registry: &AgentRegistry,
+    ) -> Result<WorkspaceStatusSummary, ApiError> {
+        let request = WorkspaceStatusRequest {
+            workspace_root: workspace_root.to_path_buf(),
+            store_path: store_path.to_path_buf(),
+            include_breakdown: false,
+            include_content_hash: false,
+            check_heads: false,
+            by_agent: false,
+            check_staleness: false,
+        };
+        let status = Self::status(api, &request, agent_registry)?;
+        Ok(WorkspaceStatusSummary {
+            workspace: workspace_root.display().to_string(),
+            scan_state: Some(status.scan_state),
+            total_nodes: status.tree.as_ref().map_or(0, |tree| tree.total_nodes),
+            head_frames: api.head_index().read().active_entries().len() as u64,
+            context_coverage: status.context_coverage.unwrap_or_default(),
+            error: None,
+        })
+    }
+
+    /// On-disk bytes per storage component, optionally with frame bytes grouped by frame type.
+    pub fn size(
+        api: &ContextApi,
+        workspace_root: &Path,
+        store_path: &Path,
+        frame_storage_path: &Path,
+        by_frame_type: bool,
+    ) -> Result<WorkspaceSizeResult, ApiError> {
+        size::measure_workspace_size(
+            api,
+            workspace_root,
+            store_path,
+            frame_storage_path,
+            by_frame_type,
         )
     }
 
+    /// Node hierarchy from the workspace root, or from `path` when given.
+    pub fn tree(
+        api: &ContextApi,
+        workspace_root: &Path,
+        path: Option<&Path>,
+        max_depth: Option<usize>,
+        include_frames: bool,
+    ) -> Result<NodeTreeEntry, ApiError> {
+        let root_id = resolve_workspace_node_id_with_policy(
+            api,
+            workspace_root,
+            Some(path.unwrap_or(workspace_root)),
+            None,
+            false,
+            api.strict_paths(),
+        )?;
+        hierarchy::build_node_tree(api, workspace_root, root_id, max_depth, include_frames)
+    }
+
     /// Validate store, head index, and root consistency.
+    ///
+    /// Head frames whose basis names a different node than the one they are indexed under are
+    /// reported as errors (index corruption or a bad migration).
     pub fn validate(
         api: &ContextApi,
         workspace_root: &Path,
@@ -274,7 +594,11 @@ impl WorkspaceCommandService {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
 
-        let root_hash = match current_workspace_root_hash(workspace_root) {
+        let root_hash = match current_workspace_root_hash(
+            api.node_store().as_ref(),
+            workspace_root,
+            api.content_normalization(),
+        ) {
             Ok(hash) => hash,
             Err(e) => {
                 errors.push(format!("Failed to compute workspace root: {}", e));
@@ -312,17 +636,28 @@ impl WorkspaceCommandService {
             let node_id = record.node_id;
             let frame_ids = api.current_frame_heads_for_node(&node_id)?;
             for frame_id in frame_ids {
-                if api
-                    .frame_storage()
-                    .get(&frame_id)
-                    .map_err(ApiError::from)?
-                    .is_none()
-                {
+                let Some(frame) = api.frame_storage().get(&frame_id).map_err(ApiError::from)?
+                else {
                     warnings.push(format!(
                         "Head frame {} for node {} not found in storage",
                         hex::encode(frame_id),
                         hex::encode(node_id)
                     ));
+                    continue;
+                };
+                // Frame-only bases name no node, so only Node and Both can disagree with the index.
+                let basis_node = match &frame.basis {
+                    Basis::Node(node) | Basis::Both { node, .. } => Some(*node),
+                    Basis::Frame(_) => None,
+                };
+                if let Some(basis_node) = basis_node.filter(|basis_node| *basis_node != node_id) {
+                    errors.push(format!(
+                        "Head frame {} ({}) has basis node {} but is indexed as a head of node {}",
+                        hex::encode(frame_id),
+                        frame.frame_type,
+                        hex::encode(basis_node),
+                        hex::encode(node_id)
+                    ));
                 }
             }
         }
@@ -346,6 +681,26 @@ impl WorkspaceCommandService {
         })
     }
 
+    /// Fix what validation can detect in the head index and frame storage, then validate.
+    ///
+    /// With `dry_run` nothing changes and the report lists what would be repaired. Orphan
+    /// frame blobs are only deleted with `allow_data_loss`.
+    pub fn repair(
+        api: &ContextApi,
+        workspace_root: &Path,
+        frame_storage_path: &PathBuf,
+        dry_run: bool,
+        allow_data_loss: bool,
+    ) -> Result<RepairReport, ApiError> {
+        let actions = repair::repair_workspace(api, dry_run, allow_data_loss)?;
+        let validation = Self::validate(api, workspace_root, frame_storage_path)?;
+        Ok(RepairReport {
+            dry_run,
+            actions,
+            validation,
+        })
+    }
+
     /// List ignore list or add a path.
     pub fn ignore(
         workspace_root: &Path,
@@ -380,8 +735,16 @@ impl WorkspaceCommandService {
         node: Option<&str>,
         dry_run: bool,
         no_ignore: bool,
+        keep_frames: bool,
     ) -> Result<String, ApiError> {
-        let node_id = resolve_workspace_node_id(api, workspace_root, path, node, false)?;
+        let node_id = resolve_workspace_node_id_with_policy(
+            api,
+            workspace_root,
+            path,
+            node,
+            false,
+            api.strict_paths(),
+        )?;
         let store = api.node_store();
         let record = store
             .get(&node_id)
@@ -397,12 +760,22 @@ impl WorkspaceCommandService {
             for nid in &set {
                 total_heads += api.current_frame_heads_for_node(nid)?.len() as u64;
             }
+            if keep_frames {
+                return Ok(format!(
+                    "Would delete {} nodes, keeping {} head entries.",
+                    n, total_heads
+                ));
+            }
             return Ok(format!(
                 "Would delete {} nodes, {} head entries.",
                 n, total_heads
             ));
         }
-        let result = api.tombstone_node(node_id)?;
+        let result = if keep_frames {
+            api.tombstone_node_keep_frames(node_id)?
+        } else {
+            api.tombstone_node(node_id)?
+        };
         let path_for_ignore = if !no_ignore {
             let norm = ignore::normalize_workspace_relative(workspace_root, &record.path)?;
             ignore::append_to_ignore_list(workspace_root, &norm)?;
@@ -410,10 +783,17 @@ impl WorkspaceCommandService {
         } else {
             None
         };
-        let mut msg = format!(
-            "Deleted {} nodes, {} head entries.",
-            result.nodes_tombstoned, result.head_entries_tombstoned
-        );
+        let mut msg = if keep_frames {
+            format!(
+                "Deleted {} nodes; head frames kept retrievable by node ID.",
+                result.nodes_tombstoned
+            )
+        } else {
+            format!(
+                "Deleted {} nodes, {} head entries.",
+                result.nodes_tombstoned, result.head_entries_tombstoned
+            )
+        };
         if let Some(p) = path_for_ignore {
             msg.push_str(&format!(" Added {} to ignore list.", p));
         }
@@ -428,7 +808,14 @@ impl WorkspaceCommandService {
         node: Option<&str>,
         dry_run: bool,
     ) -> Result<String, ApiError> {
-        let node_id = resolve_workspace_node_id(api, workspace_root, path, node, true)?;
+        let node_id = resolve_workspace_node_id_with_policy(
+            api,
+            workspace_root,
+            path,
+            node,
+            true,
+            api.strict_paths(),
+        )?;
         let store = api.node_store();
         let record = store
             .get(&node_id)
@@ -458,12 +845,121 @@ impl WorkspaceCommandService {
         ))
     }
 
+    /// Restore every tombstoned node, optionally limited to an age window in days.
+    ///
+    /// Matching nodes whose parent also matches are restored with that parent's subtree.
+    /// The age window selects subtree roots only: a restored directory brings back all of
+    /// its tombstoned descendants, including ones deleted outside the window. Nodes whose
+    /// parent stays tombstoned are skipped with a warning, so a restore never leaves an
+    /// active child under a deleted directory.
+    pub fn restore_all(
+        api: &ContextApi,
+        workspace_root: &Path,
+        older_than: Option<u64>,
+        newer_than: Option<u64>,
+        dry_run: bool,
+    ) -> Result<String, ApiError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let days_to_cutoff = |days: u64| now.saturating_sub(days.saturating_mul(24 * 60 * 60));
+        let older_cutoff = older_than.map(days_to_cutoff);
+        let newer_cutoff = newer_than.map(days_to_cutoff);
+        let store = api.node_store();
+        let mut matching = Vec::new();
+        for nid in store.list_tombstoned(None).map_err(ApiError::from)? {
+            let Some(record) = store.get(&nid).map_err(ApiError::from)? else {
+                continue;
+            };
+            let ts = record.tombstoned_at.unwrap_or(0);
+            if older_cutoff.is_some_and(|cutoff| ts > cutoff)
+                || newer_cutoff.is_some_and(|cutoff| ts < cutoff)
+            {
+                continue;
+            }
+            matching.push(record);
+        }
+        let matching_ids: std::collections::HashSet<NodeID> =
+            matching.iter().map(|record| record.node_id).collect();
+
+        let mut roots = Vec::new();
+        let mut skipped = Vec::new();
+        for record in matching {
+            let Some(parent_id) = record.parent else {
+                roots.push(record);
+                continue;
+            };
+            if matching_ids.contains(&parent_id) {
+                continue;
+            }
+            let parent_deleted = store
+                .get(&parent_id)
+                .map_err(ApiError::from)?
+                .is_some_and(|parent| parent.tombstoned_at.is_some());
+            if parent_deleted {
+                tracing::warn!(
+                    path = %record.path.display(),
+                    "Skipping restore of node whose parent stays deleted"
+                );
+                skipped.push(record);
+            } else {
+                roots.push(record);
+            }
+        }
+        roots.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut nodes = 0u64;
+        let mut heads = 0u64;
+        if dry_run {
+            for root in &roots {
+                let set = api.collect_subtree_node_ids(root.node_id)?;
+                nodes += set.len() as u64;
+                for nid in &set {
+                    heads += api.current_frame_heads_for_node(nid)?.len() as u64;
+                }
+            }
+        } else {
+            for root in &roots {
+                let result = api.restore_node(root.node_id)?;
+                nodes += result.nodes_restored;
+                heads += result.head_entries_restored;
+                let _ = ignore::remove_from_ignore_list(workspace_root, &root.path);
+            }
+        }
+
+        let mut msg = format!(
+            "{} {} nodes, {} head entries from {} deleted roots.",
+            if dry_run { "Would restore" } else { "Restored" },
+            nodes,
+            heads,
+            roots.len()
+        );
+        if !skipped.is_empty() {
+            let mut paths: Vec<String> = skipped
+                .iter()
+                .map(|record| {
+                    ignore::normalize_workspace_relative(workspace_root, &record.path)
+                        .unwrap_or_else(|_| record.path.to_string_lossy().to_string())
+                })
+                .collect();
+            paths.sort();
+            msg.push_str(&format!(
+                " Skipped {} nodes whose parent stays deleted: {}.",
+                paths.len(),
+                paths.join(", ")
+            ));
+        }
+        Ok(msg)
+    }
+
     /// Purge old tombstones; optionally purge frame blobs.
     pub fn compact(
         api: &ContextApi,
         ttl: Option<u64>,
         all: bool,
         keep_frames: bool,
+        purge_kept: bool,
         dry_run: bool,
     ) -> Result<String, ApiError> {
         let now = std::time::SystemTime::now()
@@ -477,10 +973,7 @@ impl WorkspaceCommandService {
             ttl_days * 24 * 60 * 60
         };
         let cutoff = now.saturating_sub(ttl_seconds);
-        let node_ids = api
-            .node_store()
-            .list_tombstoned(Some(cutoff))
-            .map_err(ApiError::from)?;
+        let node_ids = api.compactable_tombstoned(cutoff, purge_kept)?;
         if dry_run {
             let mut frames = 0u64;
             let mut artifacts = 0u64;
@@ -497,6 +990,19 @@ impl WorkspaceCommandService {
                 .iter()
                 .filter(|(_, e)| e.tombstoned_at.is_some_and(|ts| ts <= cutoff))
                 .count();
+            let mut kept_heads = 0usize;
+            if purge_kept {
+                for nid in &node_ids {
+                    kept_heads += api
+                        .head_index()
+                        .read()
+                        .entries_for_node(nid)
+                        .iter()
+                        .filter(|entry| entry.tombstoned_at.is_none())
+                        .count();
+                }
+            }
+            let head_count = head_count + kept_heads;
             return Ok(format!(
                 "Would compact {} nodes, {} head entries, {} frames, {} artifacts.",
                 node_ids.len(),
@@ -505,7 +1011,7 @@ impl WorkspaceCommandService {
                 artifacts
             ));
         }
-        let result = api.compact(ttl_seconds, !keep_frames)?;
+        let result = api.compact(ttl_seconds, !keep_frames, purge_kept)?;
         Ok(format!(
             "Compacted {} nodes, {} head entries, {} frames, {} artifacts.",
             result.nodes_purged,
@@ -515,11 +1021,32 @@ impl WorkspaceCommandService {
         ))
     }
 
-    /// List tombstoned nodes with optional age filter.
+    /// Reclaim the space freed by purges in the node store at `store_path` and report its
+    /// on-disk size before and after (`workspace compact --vacuum`).
+    ///
+    /// The store is rewritten into a fresh copy, so it must not be open: the CLI runs this
+    /// after the command's run context has been dropped.
+    pub fn vacuum_node_store(store_path: &Path) -> Result<String, ApiError> {
+        let before = size::disk_bytes(store_path)?;
+        crate::store::persistence::vacuum_sled_store(store_path).map_err(ApiError::from)?;
+        let after = size::disk_bytes(store_path)?;
+        Ok(format!(
+            "Vacuumed node store: {} -> {} bytes ({} reclaimed).",
+            before,
+            after,
+            before.saturating_sub(after)
+        ))
+    }
+
+    /// List tombstoned nodes with an optional age filter, optionally sorted by `path`, `age`, or
+    /// `tombstoned_at`.
     pub fn list_deleted(
         api: &ContextApi,
         older_than: Option<u64>,
+        sort: Option<&str>,
+        reverse: bool,
     ) -> Result<ListDeletedResult, ApiError> {
+        let sort = sort.map(DeletedSort::parse).transpose()?;
         let cutoff = older_than.map(|days| {
             let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -564,32 +1091,70 @@ impl WorkspaceCommandService {
                 });
             }
         }
+        if let Some(sort) = sort {
+            sort.apply(&mut rows);
+            if reverse {
+                rows.reverse();
+            }
+        }
         Ok(ListDeletedResult { rows })
     }
 
     /// Scan filesystem and rebuild tree: ignore load, TreeBuilder, store population, flush, ignore sync.
     /// Returns a summary string. Progress/session_id optional for telemetry events.
+    /// `include_hidden` lifts the walker's dotfile exclusion; ignore rules still apply.
+    /// `include_empty_dirs` records directories with nothing indexable below them.
+    #[allow(clippy::too_many_arguments)]
     pub fn scan(
         api: &ContextApi,
         workspace_root: &Path,
         force: bool,
+        include_hidden: bool,
+        include_empty_dirs: bool,
         progress: Option<&Arc<ProgressRuntime>>,
         session_id: Option<&str>,
     ) -> Result<String, ApiError> {
+        Self::scan_with_stats(
+            api,
+            workspace_root,
+            force,
+            include_hidden,
+            include_empty_dirs,
+            false,
+            progress,
+            session_id,
+        )
+        .map(|outcome| outcome.message)
+    }
+
+    /// [`Self::scan`], optionally computing [`TreeStats`] from the built tree before it is
+    /// stored. Stats are reported even when the tree already exists and nothing is written.
+    #[allow(clippy::too_many_arguments)]
+    pub fn scan_with_stats(
+        api: &ContextApi,
+        workspace_root: &Path,
+        force: bool,
+        include_hidden: bool,
+        include_empty_dirs: bool,
+        stats: bool,
+        progress: Option<&Arc<ProgressRuntime>>,
+        session_id: Option<&str>,
+    ) -> Result<ScanOutcome, ApiError> {
         let scan_started = Instant::now();
-        let ignore_patterns = ignore::load_ignore_patterns(workspace_root)
-            .unwrap_or_else(|_| WalkerConfig::default().ignore_patterns);
-        let walker_config = WalkerConfig {
-            follow_symlinks: false,
-            ignore_patterns,
-            max_depth: None,
+        let options = ScanWalkerOptions {
+            include_hidden,
+            include_empty_dirs,
         };
-        let builder =
-            TreeBuilder::new(workspace_root.to_path_buf()).with_walker_config(walker_config);
+        let builder = TreeBuilder::new(workspace_root.to_path_buf())
+            .with_walker_config(workspace_walker_config(workspace_root, options))
+            .with_content_normalization(api.content_normalization())
+            .with_read_options(api.content_read_options());
         let tree = builder.build().map_err(ApiError::StorageError)?;
         let total_nodes = tree.nodes.len();
+        let stats = stats.then(|| TreeStats::from_tree(&tree, LARGEST_FILES_LIMIT));
         let previous_root_hash =
             stored_workspace_root_hash(api.node_store().as_ref(), workspace_root, &tree.root_id)?;
+        options.record(api.node_store().as_ref())?;
 
         if !force
             && api
@@ -609,19 +1174,28 @@ impl WorkspaceCommandService {
                 );
             }
             let root_hex = hex::encode(tree.root_id);
-            return Ok(format!(
-                "Tree already exists (root: {}). Use --force to rebuild.",
-                root_hex
-            ));
+            return Ok(ScanOutcome {
+                message: format!(
+                    "Tree already exists (root: {}). Use --force to rebuild.",
+                    root_hex
+                ),
+                stats,
+            });
         }
 
+        // Stores scanned under the absolute-path NodeID scheme keep their heads under the old
+        // IDs; carry them over before the new records are written.
+        migrate_legacy_node_ids(api, workspace_root, &tree)?;
+
         let store = api.node_store().as_ref() as &dyn NodeRecordStore;
         const SCAN_PROGRESS_BATCH_NODES: usize = 128;
         let mut processed_nodes = 0usize;
         for (node_id, node) in &tree.nodes {
             let record = NodeRecord::from_merkle_node(*node_id, node, &tree)
                 .map_err(ApiError::StorageError)?;
-            store.put(&record).map_err(ApiError::from)?;
+            store
+                .put_preserving_metadata(record)
+                .map_err(ApiError::from)?;
             processed_nodes += 1;
             if let (Some(prog), Some(sid)) = (progress, session_id) {
                 if processed_nodes.is_multiple_of(SCAN_PROGRESS_BATCH_NODES)
@@ -676,9 +1250,100 @@ impl WorkspaceCommandService {
                 }),
             );
         }
+        Ok(ScanOutcome {
+            message: format!("Scanned {} nodes (root: {})", total_nodes, root_hex),
+            stats,
+        })
+    }
+
+    /// Rebuild the tree and check that its root matches the root recorded by the last scan.
+    ///
+    /// Fails when the rebuilt root differs, which means NodeIDs are not stable across scans of
+    /// an unchanged workspace (or the workspace changed between the two passes).
+    pub fn verify_stable_scan(
+        api: &ContextApi,
+        workspace_root: &Path,
+        include_hidden: bool,
+        include_empty_dirs: bool,
+    ) -> Result<String, ApiError> {
+        let canonical_root =
+            crate::tree::path::canonicalize_path(workspace_root).map_err(ApiError::StorageError)?;
+        let recorded = api
+            .node_store()
+            .find_by_path(&canonical_root)
+            .map_err(ApiError::from)?
+            .map(|record| record.node_id)
+            .ok_or_else(|| {
+                ApiError::ConfigError(
+                    "No scanned root recorded for this workspace; run `meld scan` first"
+                        .to_string(),
+                )
+            })?;
+        let rebuilt = TreeBuilder::new(workspace_root.to_path_buf())
+            .with_walker_config(workspace_walker_config(
+                workspace_root,
+                ScanWalkerOptions {
+                    include_hidden,
+                    include_empty_dirs,
+                },
+            ))
+            .with_content_normalization(api.content_normalization())
+            .with_read_options(api.content_read_options())
+            .compute_root()
+            .map_err(ApiError::StorageError)?;
+        if rebuilt != recorded {
+            return Err(ApiError::StorageError(crate::error::StorageError::IoError(
+                std::io::Error::other(format!(
+                    "Root hash changed between scans (scanned: {}, rescanned: {}); NodeIDs are not stable for this workspace",
+                    hex::encode(recorded),
+                    hex::encode(rebuilt)
+                )),
+            )));
+        }
+        Ok(format!(
+            "Root hash stable across rescan (root: {})",
+            hex::encode(rebuilt)
+        ))
+    }
+
+    /// Build the tree twice and check that every directory gets the same NodeID both times.
+    ///
+    /// Directory NodeIDs hash their children in name order, so any difference means child order
+    /// leaked into the hash (or the workspace changed between the two builds).
+    pub fn verify_tree_determinism(
+        api: &ContextApi,
+        workspace_root: &Path,
+    ) -> Result<String, ApiError> {
+        let options = ScanWalkerOptions::recorded(api.node_store().as_ref())?;
+        let build = || {
+            TreeBuilder::new(workspace_root.to_path_buf())
+                .with_walker_config(workspace_walker_config(workspace_root, options))
+                .with_content_normalization(api.content_normalization())
+                .with_read_options(api.content_read_options())
+                .build()
+                .map_err(ApiError::StorageError)
+        };
+        let first = build()?.directory_node_ids();
+        let second = build()?.directory_node_ids();
+        let mismatched: Vec<String> = first
+            .keys()
+            .chain(second.keys().filter(|path| !first.contains_key(*path)))
+            .filter(|path| first.get(*path) != second.get(*path))
+            .map(|path| path.display().to_string())
+            .collect();
+        if !mismatched.is_empty() {
+            return Err(ApiError::StorageError(crate::error::StorageError::IoError(
+                std::io::Error::other(format!(
+                    "Directory NodeIDs differ between two builds for {} director{}: {}",
+                    mismatched.len(),
+                    if mismatched.len() == 1 { "y" } else { "ies" },
+                    mismatched.join(", ")
+                )),
+            )));
+        }
         Ok(format!(
-            "Scanned {} nodes (root: {})",
-            total_nodes, root_hex
+            "Directory hashing is deterministic: {} directories matched across two builds",
+            first.len()
         ))
     }
 
@@ -694,6 +1359,7 @@ impl WorkspaceCommandService {
         include_agents: bool,
         include_providers: bool,
         include_breakdown: bool,
+        include_content_hash: bool,
         test_connectivity: bool,
     ) -> Result<UnifiedStatusOutput, ApiError> {
         let workspace = if include_workspace {
@@ -701,6 +1367,10 @@ impl WorkspaceCommandService {
                 workspace_root: workspace_root.to_path_buf(),
                 store_path: store_path.to_path_buf(),
                 include_breakdown,
+                include_content_hash,
+                check_heads: false,
+                by_agent: false,
+                check_staleness: false,
             };
             Some(Self::status(api, &request, agent_registry)?)
         } else {
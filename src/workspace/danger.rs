@@ -16,10 +16,12 @@ struct FlushTarget {
 pub struct WorkspaceDangerService;
 
 impl WorkspaceDangerService {
-    /// Remove all workspace runtime state except logs.
+    /// Remove all workspace runtime state except logs. Storage paths come from the config
+    /// with `profile` applied.
     pub fn flush(
         workspace_root: &Path,
         config_path: Option<&Path>,
+        profile: Option<&str>,
         dry_run: bool,
         yes: bool,
     ) -> Result<String, ApiError> {
@@ -45,7 +47,7 @@ impl WorkspaceDangerService {
             ));
         }
 
-        let targets = Self::resolve_flush_targets(&workspace_root, config_path)?;
+        let targets = Self::resolve_flush_targets(&workspace_root, config_path, profile)?;
         let existing_targets: Vec<FlushTarget> = targets
             .into_iter()
             .filter(|target| target.path.exists())
@@ -88,12 +90,9 @@ impl WorkspaceDangerService {
     fn resolve_flush_targets(
         workspace_root: &Path,
         config_path: Option<&Path>,
+        profile: Option<&str>,
     ) -> Result<Vec<FlushTarget>, ApiError> {
-        let config = if let Some(config_path) = config_path {
-            ConfigLoader::load_from_file(config_path)?
-        } else {
-            ConfigLoader::load(workspace_root)?
-        };
+        let config = ConfigLoader::load_with_profile(workspace_root, config_path, &[], profile)?;
 
         let (store_path, frames_path, artifacts_path) =
             config.system.storage.resolve_paths(workspace_root)?;
@@ -236,7 +235,8 @@ mod tests {
         let workspace = temp.path().join("workspace");
         fs::create_dir_all(&workspace).unwrap();
 
-        let error = WorkspaceDangerService::flush(&workspace, None, false, false).unwrap_err();
+        let error =
+            WorkspaceDangerService::flush(&workspace, None, None, false, false).unwrap_err();
         assert!(error.to_string().contains("--yes"));
     }
 }
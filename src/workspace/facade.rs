@@ -4,19 +4,25 @@ pub use super::ci::{
     BatchOperation, BatchReport, CiIntegration, DiffReport, ValidationReport, WorkspaceReport,
 };
 pub use super::commands::{
-    read_workspace_scan_state, resolve_node_id_by_canonical_fallback, resolve_workspace_node_id,
-    WorkspaceCommandService,
+    ensure_path_within_workspace, ensure_workspace_populated, read_workspace_scan_state,
+    resolve_node_id_by_canonical_fallback, resolve_workspace_node_id,
+    resolve_workspace_node_id_with_policy, workspace_has_indexable_files, WorkspaceCommandService,
 };
 pub use super::danger::WorkspaceDangerService;
 pub use super::format::{
-    format_agent_status_text, format_provider_status_text, format_section_heading,
-    format_unified_status_text, format_workspace_status_text,
+    format_agent_status_text, format_multi_workspace_status_text, format_provider_status_text,
+    format_section_heading, format_unified_status_text, format_workspace_status_text,
+};
+pub use super::section::{
+    build_agent_coverage, build_head_index_health, build_mtime_staleness, build_workspace_status,
 };
-pub use super::section::build_workspace_status;
 pub use super::types::{
-    AgentStatusEntry, AgentStatusOutput, ContextCoverageEntry, IgnoreResult, ListDeletedResult,
-    ListDeletedRow, PathCount, ProviderStatusEntry, ProviderStatusOutput, TreeStatus,
-    UnifiedStatusOutput, ValidateResult, WorkspaceScanInfo, WorkspaceScanState, WorkspaceStatus,
-    WorkspaceStatusRequest, WorkspaceStatusResult,
+    AgentCoverageEntry, AgentStatusEntry, AgentStatusOutput, ContextCoverageEntry, HeadIndexHealth,
+    IgnoreResult, ListDeletedResult, ListDeletedRow, MtimeStaleFrame, MtimeStaleness,
+    MultiWorkspaceStatusOutput, NodeTreeEntry, PathCount, ProviderStatusEntry,
+    ProviderStatusOutput, RepairAction, RepairKind, RepairReport, ScanOutcome, TreeStatus,
+    UnifiedStatusOutput, ValidateResult, WorkspaceScanInfo, WorkspaceScanState,
+    WorkspaceSizeResult, WorkspaceStatus, WorkspaceStatusRequest, WorkspaceStatusResult,
+    WorkspaceStatusSummary,
 };
 pub use super::watch::{ChangeEvent, EditorHooks, WatchConfig, WatchDaemon};
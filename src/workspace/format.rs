@@ -1,22 +1,36 @@
 //! Format workspace, agent, provider, and unified status as text.
 
+use crate::cli::{color_enabled, styled_table};
 use crate::workspace::types::{
-    AgentStatusEntry, ProviderStatusEntry, UnifiedStatusOutput, WorkspaceScanState, WorkspaceStatus,
+    AgentStatusEntry, HeadIndexHealth, MtimeStaleness, MultiWorkspaceStatusOutput,
+    ProviderStatusEntry, UnifiedStatusOutput, WorkspaceScanState, WorkspaceStatus,
 };
 use comfy_table::presets::UTF8_BORDERS_ONLY;
-use comfy_table::Table;
 use owo_colors::OwoColorize;
 
-/// Format a section heading with bold/underline. Respects NO_COLOR and TTY.
+/// Format a section heading, bold and underlined when `--color` allows it.
 pub fn format_section_heading(title: &str) -> String {
-    format!("{}", title.bold().underline())
+    if color_enabled() {
+        format!("{}", title.bold().underline())
+    } else {
+        title.to_string()
+    }
 }
 
 /// Format workspace status as human-readable text.
 pub fn format_workspace_status_text(data: &WorkspaceStatus, include_breakdown: bool) -> String {
-    fn short_hash(value: &str) -> String {
-        format!("{}...", &value[..value.len().min(7)])
-    }
+    // Full hashes are shown when content hashes were requested, so auditors can copy them.
+    let full_hashes = data
+        .tree
+        .as_ref()
+        .is_some_and(|tree| tree.file_hashes.is_some());
+    let display_hash = |value: &str| -> String {
+        if full_hashes {
+            value.to_string()
+        } else {
+            format!("{}...", &value[..value.len().min(7)])
+        }
+    };
 
     let mut out = String::new();
     out.push_str(&format!(
@@ -31,29 +45,39 @@ pub fn format_workspace_status_text(data: &WorkspaceStatus, include_breakdown: b
             out.push_str(msg);
             out.push('\n');
         }
+        if let Some(ref health) = data.head_index_health {
+            out.push('\n');
+            push_head_index_health(&mut out, health);
+        }
         return out;
     }
     let tree = data.tree.as_ref().unwrap();
     out.push_str(&format!("  Store path: {}\n", data.store_path));
     match data.scan_state {
         WorkspaceScanState::Current => {
-            out.push_str(&format!("  Root hash: {}\n", short_hash(&tree.root_hash)));
+            out.push_str(&format!("  Root hash: {}\n", display_hash(&tree.root_hash)));
         }
         WorkspaceScanState::Stale => {
             out.push_str(&format!(
                 "  Current root hash: {}\n",
-                short_hash(&tree.root_hash)
+                display_hash(&tree.root_hash)
             ));
             let stored_root = data
                 .stored_root_hash
                 .as_deref()
-                .map(short_hash)
+                .map(display_hash)
                 .unwrap_or_else(|| "missing".to_string());
             out.push_str(&format!("  Stored root hash: {}\n", stored_root));
         }
         WorkspaceScanState::Missing => {}
     }
     out.push_str(&format!("  Total nodes: {}\n", tree.total_nodes));
+    if tree.symlink_nodes > 0 {
+        out.push_str(&format!(
+            "  Symlinks: {} (not generated)\n",
+            tree.symlink_nodes
+        ));
+    }
     match data.scan_state {
         WorkspaceScanState::Current => out.push_str("  Scanned: yes\n\n"),
         WorkspaceScanState::Stale => out.push_str("  Scanned: yes, stale\n\n"),
@@ -66,23 +90,37 @@ pub fn format_workspace_status_text(data: &WorkspaceStatus, include_breakdown: b
     if include_breakdown {
         if let Some(ref breakdown) = tree.breakdown {
             out.push_str("  Top-level breakdown\n\n");
-            let mut table = Table::new();
-            table.load_preset(UTF8_BORDERS_ONLY);
-            table.set_header(vec!["Path", "Nodes"]);
+            let mut table = styled_table(UTF8_BORDERS_ONLY, &["Path", "Nodes"]);
             for row in breakdown {
                 table.add_row(vec![row.path.clone(), row.nodes.to_string()]);
             }
             out.push_str(&format!("{}\n\n", table));
         }
     }
+    if let Some(ref file_hashes) = tree.file_hashes {
+        out.push_str(&format!(
+            "{}\n\n",
+            format_section_heading("File content hashes")
+        ));
+        let mut table = styled_table(UTF8_BORDERS_ONLY, &["Path", "Size", "Content hash"]);
+        for row in file_hashes {
+            table.add_row(vec![
+                row.path.clone(),
+                row.size.to_string(),
+                row.content_hash.clone(),
+            ]);
+        }
+        out.push_str(&format!("{}\n\n", table));
+    }
     if let Some(ref coverage) = data.context_coverage {
         out.push_str(&format!(
             "{}\n\n",
             format_section_heading("Context coverage")
         ));
-        let mut table = Table::new();
-        table.load_preset(UTF8_BORDERS_ONLY);
-        table.set_header(vec!["Agent", "With frame", "Without", "Coverage"]);
+        let mut table = styled_table(
+            UTF8_BORDERS_ONLY,
+            &["Agent", "With frame", "Without", "Coverage"],
+        );
         for row in coverage {
             let pct = row
                 .coverage_pct
@@ -97,22 +135,94 @@ pub fn format_workspace_status_text(data: &WorkspaceStatus, include_breakdown: b
         }
         out.push_str(&format!("{}\n\n", table));
     }
+    if let Some(ref agent_coverage) = data.agent_coverage {
+        out.push_str(&format!(
+            "{}\n\n",
+            format_section_heading("Coverage by agent")
+        ));
+        if agent_coverage.is_empty() {
+            out.push_str("  No head frames.\n\n");
+        } else {
+            let mut table =
+                styled_table(UTF8_BORDERS_ONLY, &["Agent", "Nodes covered", "Coverage"]);
+            for row in agent_coverage {
+                let pct = row
+                    .coverage_pct
+                    .map(|p| format!("{}%", p))
+                    .unwrap_or_else(|| "-".to_string());
+                table.add_row(vec![
+                    row.agent_id.clone(),
+                    row.nodes_covered.to_string(),
+                    pct,
+                ]);
+            }
+            out.push_str(&format!("{}\n\n", table));
+        }
+    }
     if let Some(ref top_paths) = data.top_paths_by_node_count {
         out.push_str(&format!(
             "{}\n\n",
             format_section_heading("Top paths by node count")
         ));
-        let mut table = Table::new();
-        table.load_preset(UTF8_BORDERS_ONLY);
-        table.set_header(vec!["Path", "Nodes"]);
+        let mut table = styled_table(UTF8_BORDERS_ONLY, &["Path", "Nodes"]);
         for row in top_paths {
             table.add_row(vec![row.path.clone(), row.nodes.to_string()]);
         }
         out.push_str(&format!("{}\n", table));
     }
+    if let Some(ref health) = data.head_index_health {
+        out.push('\n');
+        push_head_index_health(&mut out, health);
+    }
+    if let Some(ref staleness) = data.mtime_staleness {
+        out.push('\n');
+        push_mtime_staleness(&mut out, staleness);
+    }
     out
 }
 
+fn push_head_index_health(out: &mut String, health: &HeadIndexHealth) {
+    out.push_str(&format!(
+        "{}\n",
+        format_section_heading("Head index health")
+    ));
+    out.push_str(&format!("  Total entries: {}\n", health.total_entries));
+    out.push_str(&format!(
+        "  Dangling (frame missing): {}\n",
+        health.dangling_entries
+    ));
+    out.push_str(&format!(
+        "  Stale (node tombstoned or missing): {}\n",
+        health.stale_entries
+    ));
+}
+
+fn push_mtime_staleness(out: &mut String, staleness: &MtimeStaleness) {
+    out.push_str(&format!(
+        "{}\n",
+        format_section_heading("Source mtime staleness")
+    ));
+    out.push_str(&format!(
+        "  Frames with source_mtime: {}\n",
+        staleness.checked_frames
+    ));
+    out.push_str(&format!(
+        "  Modified since generation: {}\n",
+        staleness.stale_frames.len()
+    ));
+    if !staleness.stale_frames.is_empty() {
+        let mut table = styled_table(UTF8_BORDERS_ONLY, &["Path", "Frame type", "Frame"]);
+        for row in &staleness.stale_frames {
+            table.add_row(vec![
+                row.path.clone(),
+                row.frame_type.clone(),
+                row.frame_id.clone(),
+            ]);
+        }
+        out.push_str(&format!("{}\n", table));
+    }
+}
+
 /// Format agent status as human-readable text.
 pub fn format_agent_status_text(entries: &[AgentStatusEntry]) -> String {
     let mut out = String::new();
@@ -121,9 +231,7 @@ pub fn format_agent_status_text(entries: &[AgentStatusEntry]) -> String {
         out.push_str("No agents configured.\n");
         return out;
     }
-    let mut table = Table::new();
-    table.load_preset(UTF8_BORDERS_ONLY);
-    table.set_header(vec!["Agent", "Role", "Valid", "Prompt"]);
+    let mut table = styled_table(UTF8_BORDERS_ONLY, &["Agent", "Role", "Valid", "Prompt"]);
     for row in entries {
         let valid_str = if row.valid { "yes" } else { "no" };
         let prompt_str = if row.role == "Reader" {
@@ -161,10 +269,13 @@ pub fn format_provider_status_text(
         out.push_str("No providers configured.\n");
         return out;
     }
-    let mut table = Table::new();
-    table.load_preset(UTF8_BORDERS_ONLY);
+    let header: &[&str] = if include_connectivity {
+        &["Provider", "Type", "Model", "Connectivity"]
+    } else {
+        &["Provider", "Type", "Model"]
+    };
+    let mut table = styled_table(UTF8_BORDERS_ONLY, header);
     if include_connectivity {
-        table.set_header(vec!["Provider", "Type", "Model", "Connectivity"]);
         for row in entries {
             let conn = row
                 .connectivity
@@ -184,7 +295,6 @@ pub fn format_provider_status_text(
             ]);
         }
     } else {
-        table.set_header(vec!["Provider", "Type", "Model"]);
         for row in entries {
             table.add_row(vec![
                 row.provider_name.clone(),
@@ -225,3 +335,54 @@ pub fn format_unified_status_text(
 
     out
 }
+
+/// Format `status --workspaces` as one table row per workspace, then any open errors.
+pub fn format_multi_workspace_status_text(data: &MultiWorkspaceStatusOutput) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{}\n\n", format_section_heading("Workspaces")));
+    let mut table = styled_table(
+        UTF8_BORDERS_ONLY,
+        &["Workspace", "Scan", "Nodes", "Head frames", "Coverage"],
+    );
+    for row in &data.workspaces {
+        let scan = match (row.scan_state, &row.error) {
+            (_, Some(_)) => "error",
+            (Some(WorkspaceScanState::Current), None) => "current",
+            (Some(WorkspaceScanState::Stale), None) => "stale",
+            (Some(WorkspaceScanState::Missing), None) | (None, None) => "missing",
+        };
+        let coverage = if row.context_coverage.is_empty() {
+            "-".to_string()
+        } else {
+            row.context_coverage
+                .iter()
+                .map(|entry| match entry.coverage_pct {
+                    Some(pct) => format!("{} {}%", entry.agent_id, pct),
+                    None => format!("{} -", entry.agent_id),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        table.add_row(vec![
+            row.workspace.clone(),
+            scan.to_string(),
+            row.total_nodes.to_string(),
+            row.head_frames.to_string(),
+            coverage,
+        ]);
+    }
+    out.push_str(&format!("{}\n", table));
+
+    let failed: Vec<_> = data
+        .workspaces
+        .iter()
+        .filter_map(|row| row.error.as_ref().map(|error| (&row.workspace, error)))
+        .collect();
+    if !failed.is_empty() {
+        out.push_str("\nErrors:\n");
+        for (workspace, error) in failed {
+            out.push_str(&format!("  {}: {}\n", workspace, error));
+        }
+    }
+    out
+}
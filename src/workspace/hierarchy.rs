@@ -0,0 +1,119 @@
+//! Node hierarchy rendering for the `tree` command.
+//! Walks `NodeRecord.children` from a root node; reads only.
+
+use crate::api::ContextApi;
+use crate::error::ApiError;
+use crate::store::{NodeRecord, NodeType};
+use crate::types::NodeID;
+use crate::workspace::types::NodeTreeEntry;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Build the node tree rooted at `root_id`.
+///
+/// `max_depth` limits how many levels below the root are expanded. Tombstoned, missing, and
+/// already-visited children are skipped so malformed or cyclic stores still render.
+pub fn build_node_tree(
+    api: &ContextApi,
+    workspace_root: &Path,
+    root_id: NodeID,
+    max_depth: Option<usize>,
+    include_frames: bool,
+) -> Result<NodeTreeEntry, ApiError> {
+    let record = api
+        .node_store()
+        .get(&root_id)
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NodeNotFound(root_id))?;
+    let mut visited = HashSet::from([root_id]);
+    build_entry(
+        api,
+        workspace_root,
+        &record,
+        0,
+        max_depth,
+        include_frames,
+        &mut visited,
+    )
+}
+
+fn build_entry(
+    api: &ContextApi,
+    workspace_root: &Path,
+    record: &NodeRecord,
+    depth: usize,
+    max_depth: Option<usize>,
+    include_frames: bool,
+    visited: &mut HashSet<NodeID>,
+) -> Result<NodeTreeEntry, ApiError> {
+    let mut entry = NodeTreeEntry {
+        name: display_name(workspace_root, &record.path),
+        path: relative_path(workspace_root, &record.path),
+        node_type: match record.node_type {
+            NodeType::File { .. } => "file".to_string(),
+            NodeType::Directory => "directory".to_string(),
+            NodeType::Symlink { .. } => "symlink".to_string(),
+        },
+        head_frames: include_frames.then(|| api.get_all_heads(&record.node_id).len()),
+        children: Vec::new(),
+        truncated: false,
+    };
+
+    if max_depth.is_some_and(|max| depth >= max) {
+        entry.truncated = !record.children.is_empty();
+        return Ok(entry);
+    }
+
+    let mut children = Vec::new();
+    for child_id in &record.children {
+        if !visited.insert(*child_id) {
+            tracing::warn!(
+                node_id = %hex::encode(child_id),
+                "Skipping already visited child while rendering tree"
+            );
+            continue;
+        }
+        let Some(child) = api.node_store().get(child_id).map_err(ApiError::from)? else {
+            tracing::warn!(
+                node_id = %hex::encode(child_id),
+                "Skipping child with no node record while rendering tree"
+            );
+            continue;
+        };
+        if child.tombstoned_at.is_some() {
+            continue;
+        }
+        children.push(child);
+    }
+    children.sort_by(|a, b| a.path.cmp(&b.path));
+
+    for child in &children {
+        entry.children.push(build_entry(
+            api,
+            workspace_root,
+            child,
+            depth + 1,
+            max_depth,
+            include_frames,
+            visited,
+        )?);
+    }
+    Ok(entry)
+}
+
+fn relative_path(workspace_root: &Path, path: &Path) -> String {
+    match path.strip_prefix(workspace_root) {
+        Ok(rel) if rel.as_os_str().is_empty() => ".".to_string(),
+        Ok(rel) => rel.to_string_lossy().to_string(),
+        Err(_) => path.to_string_lossy().to_string(),
+    }
+}
+
+fn display_name(workspace_root: &Path, path: &Path) -> String {
+    if path == workspace_root {
+        return ".".to_string();
+    }
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
@@ -14,7 +14,7 @@ use crate::task::contracts::{
     TaskDependencyKind, TaskInitSlotSpec,
 };
 use crate::task::expansion::{CompiledTaskDelta, TaskExpansionRequest};
-use crate::types::{FrameID, NodeID};
+use crate::types::{FrameID, HexHash, NodeID};
 pub use meld_execution::publish::{FrameHeadPublishTemplate, FrameHeadWriteExpansionContent};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -319,35 +319,17 @@ pub fn record_published_head(
 }
 
 pub fn decode_node_id(value: &str) -> Result<NodeID, ApiError> {
-    let bytes = hex::decode(value).map_err(|err| {
-        ApiError::ConfigError(format!("Invalid node id hex '{}': {}", value, err))
-    })?;
-    if bytes.len() != 32 {
-        return Err(ApiError::ConfigError(format!(
-            "Invalid node id hex '{}' length '{}'",
-            value,
-            bytes.len()
-        )));
-    }
-    let mut node_id = [0u8; 32];
-    node_id.copy_from_slice(&bytes);
-    Ok(node_id)
+    value
+        .parse::<HexHash>()
+        .map(NodeID::from)
+        .map_err(|err| ApiError::ConfigError(format!("Invalid node id hex '{}': {}", value, err)))
 }
 
 pub fn decode_frame_id(value: &str) -> Result<FrameID, ApiError> {
-    let bytes = hex::decode(value).map_err(|err| {
-        ApiError::ConfigError(format!("Invalid frame id hex '{}': {}", value, err))
-    })?;
-    if bytes.len() != 32 {
-        return Err(ApiError::ConfigError(format!(
-            "Invalid frame id hex '{}' length '{}'",
-            value,
-            bytes.len()
-        )));
-    }
-    let mut frame_id = [0u8; 32];
-    frame_id.copy_from_slice(&bytes);
-    Ok(frame_id)
+    value
+        .parse::<HexHash>()
+        .map(FrameID::from)
+        .map_err(|err| ApiError::ConfigError(format!("Invalid frame id hex '{}': {}", value, err)))
 }
 
 pub fn publish_node_ref_slot_id(node_id_hex: &str) -> Result<String, ApiError> {
@@ -0,0 +1,214 @@
+//! Inconsistency repair for `workspace validate --repair`.
+//! Detects head entries and frame blobs that disagree with each other or with the node store,
+//! and applies the safe remedy for each; frame deletion is gated on `allow_data_loss`.
+//! Only frames outside all history are orphans: superseded frames of live nodes, frames a
+//! head snapshot points at, and whole basis chains stay.
+
+use crate::api::ContextApi;
+use crate::context::frame::Basis;
+use crate::error::ApiError;
+use crate::heads::LegacyHeadEntry;
+use crate::types::{FrameID, NodeID};
+use crate::workspace::types::{RepairAction, RepairKind};
+use std::collections::{HashMap, HashSet};
+
+const DATA_LOSS_REASON: &str = "requires --allow-data-loss";
+
+/// One detected issue and the head entry it concerns, if any.
+struct Finding {
+    kind: RepairKind,
+    entry: Option<LegacyHeadEntry>,
+    frame_id: FrameID,
+}
+
+/// Detect every fixable inconsistency and, unless `dry_run`, repair it.
+///
+/// Head repairs always apply. Orphan frames are deleted only with `allow_data_loss`;
+/// otherwise they are reported as skipped. Actions are ordered by kind, then node and frame.
+pub fn repair_workspace(
+    api: &ContextApi,
+    dry_run: bool,
+    allow_data_loss: bool,
+) -> Result<Vec<RepairAction>, ApiError> {
+    let findings = find_inconsistencies(api)?;
+    let mut actions = Vec::with_capacity(findings.len());
+
+    let removable: Vec<LegacyHeadEntry> = findings
+        .iter()
+        .filter(|finding| {
+            matches!(
+                finding.kind,
+                RepairKind::DanglingHead | RepairKind::OrphanHeadEntry
+            )
+        })
+        .filter_map(|finding| finding.entry.clone())
+        .collect();
+    let removed = if dry_run || removable.is_empty() {
+        Vec::new()
+    } else {
+        api.remove_head_entries(&removable)?
+    };
+
+    for finding in findings {
+        let skipped_reason =
+            (finding.kind.loses_data() && !allow_data_loss).then(|| DATA_LOSS_REASON.to_string());
+        let applied = if dry_run || skipped_reason.is_some() {
+            false
+        } else {
+            match (finding.kind, &finding.entry) {
+                (RepairKind::DanglingHead | RepairKind::OrphanHeadEntry, Some(entry)) => {
+                    removed.contains(entry)
+                }
+                (RepairKind::UntombstonedHead, Some(entry)) => api
+                    .tombstone_head(entry.node_id, &entry.frame_type)?
+                    .is_some(),
+                (RepairKind::OrphanFrame, _) => {
+                    api.frame_storage()
+                        .purge(&finding.frame_id)
+                        .map_err(ApiError::from)?;
+                    true
+                }
+                _ => false,
+            }
+        };
+        actions.push(RepairAction {
+            kind: finding.kind,
+            node_id: finding
+                .entry
+                .as_ref()
+                .map(|entry| hex::encode(entry.node_id)),
+            frame_type: finding.entry.map(|entry| entry.frame_type),
+            frame_id: hex::encode(finding.frame_id),
+            applied,
+            skipped_reason,
+        });
+    }
+    Ok(actions)
+}
+
+/// Frames that are still history: the frames of every node the store still has a record
+/// for (superseded heads included), the `head_frames` of sound head entries and saved head
+/// snapshots, and every frame reachable from those through basis frame links.
+fn live_frames(api: &ContextApi, head_frames: Vec<FrameID>) -> Result<HashSet<FrameID>, ApiError> {
+    let mut bases: HashMap<FrameID, Option<FrameID>> = HashMap::new();
+    let mut known_nodes: HashMap<NodeID, bool> = HashMap::new();
+    let mut pending = head_frames;
+    pending.extend(api.head_snapshot_frame_ids()?);
+    for frame_id in api.frame_storage().iter_ids() {
+        let frame_id = frame_id.map_err(ApiError::from)?;
+        let Some(frame) = api
+            .frame_storage()
+            .read_unverified(&frame_id)
+            .map_err(ApiError::from)?
+        else {
+            continue;
+        };
+        let (node, basis_frame) = match frame.basis {
+            Basis::Node(node) => (Some(node), None),
+            Basis::Frame(basis_frame) => (None, Some(basis_frame)),
+            Basis::Both { node, frame } => (Some(node), Some(frame)),
+        };
+        bases.insert(frame_id, basis_frame);
+        if let Some(node) = node {
+            let node_known = match known_nodes.get(&node) {
+                Some(known) => *known,
+                None => {
+                    let known = api
+                        .node_store()
+                        .get(&node)
+                        .map_err(ApiError::from)?
+                        .is_some();
+                    known_nodes.insert(node, known);
+                    known
+                }
+            };
+            if node_known {
+                pending.push(frame_id);
+            }
+        }
+    }
+
+    let mut live = HashSet::new();
+    while let Some(frame_id) = pending.pop() {
+        if !live.insert(frame_id) {
+            continue;
+        }
+        if let Some(Some(basis_frame)) = bases.get(&frame_id) {
+            pending.push(*basis_frame);
+        }
+    }
+    Ok(live)
+}
+
+/// Classify head entries against frame storage and the node store, then find frame blobs
+/// nothing refers to once the broken head entries are gone.
+fn find_inconsistencies(api: &ContextApi) -> Result<Vec<Finding>, ApiError> {
+    let dangling: HashSet<(NodeID, String)> = api
+        .dangling_heads()?
+        .into_iter()
+        .map(|entry| (entry.node_id, entry.frame_type))
+        .collect();
+
+    let mut findings = Vec::new();
+    let mut live_roots: Vec<FrameID> = Vec::new();
+    let entries = api.head_index().read().entries();
+    for entry in entries {
+        if dangling.contains(&(entry.node_id, entry.frame_type.clone())) {
+            findings.push(Finding {
+                kind: RepairKind::DanglingHead,
+                frame_id: entry.frame_id,
+                entry: Some(entry),
+            });
+            continue;
+        }
+        let record = api
+            .node_store()
+            .get(&entry.node_id)
+            .map_err(ApiError::from)?;
+        let Some(record) = record else {
+            findings.push(Finding {
+                kind: RepairKind::OrphanHeadEntry,
+                frame_id: entry.frame_id,
+                entry: Some(entry),
+            });
+            continue;
+        };
+
+        live_roots.push(entry.frame_id);
+        if record.tombstoned_at.is_some() && !record.keeps_frames() && entry.tombstoned_at.is_none()
+        {
+            findings.push(Finding {
+                kind: RepairKind::UntombstonedHead,
+                frame_id: entry.frame_id,
+                entry: Some(entry),
+            });
+        }
+    }
+
+    let live = live_frames(api, live_roots)?;
+    for frame_id in api.frame_storage().iter_ids() {
+        let frame_id = frame_id.map_err(ApiError::from)?;
+        if !live.contains(&frame_id) {
+            findings.push(Finding {
+                kind: RepairKind::OrphanFrame,
+                entry: None,
+                frame_id,
+            });
+        }
+    }
+
+    findings.sort_by(|a, b| {
+        let key = |finding: &Finding| {
+            (
+                finding.kind,
+                finding
+                    .entry
+                    .as_ref()
+                    .map(|entry| (entry.node_id, entry.frame_type.clone())),
+                finding.frame_id,
+            )
+        };
+        key(a).cmp(&key(b))
+    });
+    Ok(findings)
+}
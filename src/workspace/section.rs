@@ -1,14 +1,22 @@
 //! Internal workspace-section build used by status and unified_status.
 
 use crate::agent::{AgentRegistry, AgentRole};
+use crate::api::ContextApi;
+use crate::context::generation::orchestration::source_mtime_nanos;
 use crate::context::head::CurrentFrameHeadRead;
 use crate::error::ApiError;
+use crate::metadata::frame_key_registry::KEY_SOURCE_MTIME;
 use crate::store::NodeRecord;
 use crate::store::NodeRecordStore;
+use crate::store::NodeType;
+use crate::tree::hasher::ContentNormalization;
 use crate::types::NodeID;
-use crate::workspace::commands::{assess_workspace_scan_state, current_workspace_root_hash};
+use crate::workspace::commands::{
+    assess_workspace_scan_state, current_workspace_root_hash, is_indexable_file,
+};
 use crate::workspace::types::{
-    ContextCoverageEntry, PathCount, TreeStatus, WorkspaceScanState, WorkspaceStatus,
+    AgentCoverageEntry, ContextCoverageEntry, FileContentHash, HeadIndexHealth, MtimeStaleFrame,
+    MtimeStaleness, PathCount, TreeStatus, WorkspaceScanState, WorkspaceStatus,
 };
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
@@ -16,6 +24,9 @@ use std::path::{Path, PathBuf};
 /// Build workspace status from store, current frame heads, agent registry, and workspace root.
 ///
 /// When `include_breakdown` is true, the tree section includes top-level path breakdown.
+/// When `include_content_hash` is true, the tree section lists every file's recorded content hash.
+/// `normalization` must match the one used by scan so the current root hash is comparable.
+#[allow(clippy::too_many_arguments)]
 pub fn build_workspace_status(
     node_store: &dyn NodeRecordStore,
     head_reader: &dyn CurrentFrameHeadRead,
@@ -23,6 +34,8 @@ pub fn build_workspace_status(
     workspace_root: &Path,
     store_path: &Path,
     include_breakdown: bool,
+    include_content_hash: bool,
+    normalization: ContentNormalization,
 ) -> Result<WorkspaceStatus, ApiError> {
     fn collect_reachable_records(
         node_store: &dyn NodeRecordStore,
@@ -51,7 +64,7 @@ pub fn build_workspace_status(
         Ok(records)
     }
 
-    let scan_info = assess_workspace_scan_state(node_store, workspace_root)?;
+    let scan_info = assess_workspace_scan_state(node_store, workspace_root, normalization)?;
     if matches!(scan_info.scan_state, WorkspaceScanState::Missing) {
         return Ok(WorkspaceStatus {
             scanned: false,
@@ -63,16 +76,22 @@ pub fn build_workspace_status(
             tree: None,
             context_coverage: None,
             top_paths_by_node_count: None,
+            head_index_health: None,
+            agent_coverage: None,
+            mtime_staleness: None,
         });
     }
 
     let records = if matches!(scan_info.scan_state, WorkspaceScanState::Current) {
-        let root_id = current_workspace_root_hash(workspace_root)?;
+        let root_id = current_workspace_root_hash(node_store, workspace_root, normalization)?;
         collect_reachable_records(node_store, root_id)?
     } else {
         node_store.list_active().map_err(ApiError::from)?
     };
     let total_nodes = records.len() as u64;
+    let symlink_nodes = records.iter().filter(|record| record.is_symlink()).count() as u64;
+    let generatable_nodes = total_nodes - symlink_nodes;
+    let has_files = records.iter().any(is_indexable_file);
 
     let workspace_root_buf = workspace_root.to_path_buf();
     let mut prefix_counts: HashMap<String, u64> = HashMap::new();
@@ -134,6 +153,29 @@ pub fn build_workspace_status(
         None
     };
 
+    let file_hashes = if include_content_hash {
+        let mut hashes: Vec<FileContentHash> = records
+            .iter()
+            .filter_map(|record| match record.node_type {
+                NodeType::File { size, content_hash } => Some(FileContentHash {
+                    path: record
+                        .path
+                        .strip_prefix(&workspace_root_buf)
+                        .unwrap_or(record.path.as_path())
+                        .to_string_lossy()
+                        .to_string(),
+                    size,
+                    content_hash: hex::encode(content_hash),
+                }),
+                NodeType::Directory | NodeType::Symlink { .. } => None,
+            })
+            .collect();
+        hashes.sort_by(|a, b| a.path.cmp(&b.path));
+        Some(hashes)
+    } else {
+        None
+    };
+
     let writers = agent_registry.list_by_role(Some(AgentRole::Writer));
     let mut agent_ids: std::collections::HashSet<String> =
         writers.iter().map(|a| a.agent_id.clone()).collect();
@@ -141,9 +183,9 @@ pub fn build_workspace_status(
     for agent_id in agent_ids.drain() {
         let frame_type = format!("context-{}", agent_id);
         let nodes_with_frame = head_reader.count_nodes_for_frame_type(&frame_type)? as u64;
-        let nodes_without_frame = total_nodes.saturating_sub(nodes_with_frame);
-        let coverage_pct = if total_nodes > 0 {
-            Some((nodes_with_frame * 100) / total_nodes)
+        let nodes_without_frame = generatable_nodes.saturating_sub(nodes_with_frame);
+        let coverage_pct = if generatable_nodes > 0 {
+            Some((nodes_with_frame * 100) / generatable_nodes)
         } else {
             Some(0)
         };
@@ -162,6 +204,11 @@ pub fn build_workspace_status(
         store_path: normalize_display_path(store_path),
         message: if matches!(scan_info.scan_state, WorkspaceScanState::Stale) {
             Some("Run meld scan to refresh tree to current workspace state.".to_string())
+        } else if !has_files {
+            Some(
+                "Workspace has no indexable files. Review ignore patterns (`meld ignore`, .gitignore), then run meld scan."
+                    .to_string(),
+            )
         } else {
             None
         },
@@ -170,13 +217,148 @@ pub fn build_workspace_status(
         tree: Some(TreeStatus {
             root_hash: scan_info.current_root_hash,
             total_nodes,
+            symlink_nodes,
             breakdown,
+            file_hashes,
         }),
         context_coverage: Some(context_coverage),
         top_paths_by_node_count: Some(top_paths),
+        head_index_health: None,
+        agent_coverage: None,
+        mtime_staleness: None,
     })
 }
 
+/// Count head index entries, and those pointing at missing frame blobs or dead nodes.
+///
+/// Heads of nodes deleted with `--keep-frames` are meant to stay and are not counted stale.
+///
+/// Checks every entry against frame storage and the node store, so it is opt-in for status.
+pub fn build_head_index_health(api: &ContextApi) -> Result<HeadIndexHealth, ApiError> {
+    let entries = api.head_index().read().entries();
+    let mut health = HeadIndexHealth::default();
+    for entry in entries {
+        health.total_entries += 1;
+        if !api
+            .frame_storage()
+            .exists(&entry.frame_id)
+            .map_err(ApiError::from)?
+        {
+            health.dangling_entries += 1;
+        }
+        let node_live = api
+            .node_store()
+            .get(&entry.node_id)
+            .map_err(ApiError::from)?
+            .is_some_and(|record| record.tombstoned_at.is_none() || record.keeps_frames());
+        if !node_live {
+            health.stale_entries += 1;
+        }
+    }
+    Ok(health)
+}
+
+/// Compare each live file's head frames that recorded `source_mtime` with the file's mtime now.
+///
+/// A frame is stale when the file was modified after generation. This is a cheap heuristic next
+/// to basis-hash checks: it stats files instead of hashing them, and a touch without a content
+/// change still counts. Frames generated without `--preserve-mtime` are not checked.
+pub fn build_mtime_staleness(api: &ContextApi) -> Result<MtimeStaleness, ApiError> {
+    let entries = api.head_index().read().active_entries();
+    let mut staleness = MtimeStaleness::default();
+    for entry in entries {
+        let Some(record) = api
+            .node_store()
+            .get(&entry.node_id)
+            .map_err(ApiError::from)?
+            .filter(|record| record.tombstoned_at.is_none())
+        else {
+            continue;
+        };
+        let Some(frame) = api
+            .frame_storage()
+            .get(&entry.frame_id)
+            .map_err(ApiError::from)?
+        else {
+            continue;
+        };
+        let Some(recorded) = frame
+            .metadata_value(KEY_SOURCE_MTIME)
+            .and_then(|value| value.parse::<u128>().ok())
+        else {
+            continue;
+        };
+        staleness.checked_frames += 1;
+        let modified_since = source_mtime_nanos(&record).is_none_or(|current| current > recorded);
+        if modified_since {
+            staleness.stale_frames.push(MtimeStaleFrame {
+                path: normalize_display_path(&record.path),
+                frame_type: frame.frame_type.clone(),
+                frame_id: hex::encode(entry.frame_id),
+            });
+        }
+    }
+    staleness.stale_frames.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then_with(|| a.frame_type.cmp(&b.frame_type))
+    });
+    Ok(staleness)
+}
+
+/// Group live nodes by the agents that produced their head frames.
+///
+/// A node counts once per agent however many of its frame types that agent heads. Reads every
+/// head frame from frame storage, so it is opt-in for status. Percentages are of
+/// `generatable_nodes`.
+pub fn build_agent_coverage(
+    api: &ContextApi,
+    generatable_nodes: u64,
+) -> Result<Vec<AgentCoverageEntry>, ApiError> {
+    let entries = api.head_index().read().active_entries();
+    let mut nodes_by_agent: HashMap<String, HashSet<NodeID>> = HashMap::new();
+    for entry in entries {
+        let node_live = api
+            .node_store()
+            .get(&entry.node_id)
+            .map_err(ApiError::from)?
+            .is_some_and(|record| record.tombstoned_at.is_none());
+        if !node_live {
+            continue;
+        }
+        let Some(frame) = api
+            .frame_storage()
+            .get(&entry.frame_id)
+            .map_err(ApiError::from)?
+        else {
+            continue;
+        };
+        if let Some(agent_id) = frame.agent_id() {
+            nodes_by_agent
+                .entry(agent_id.to_string())
+                .or_default()
+                .insert(entry.node_id);
+        }
+    }
+    let mut coverage: Vec<AgentCoverageEntry> = nodes_by_agent
+        .into_iter()
+        .map(|(agent_id, nodes)| {
+            let nodes_covered = nodes.len() as u64;
+            AgentCoverageEntry {
+                agent_id,
+                nodes_covered,
+                coverage_pct: Some(
+                    (nodes_covered * 100)
+                        .checked_div(generatable_nodes)
+                        .unwrap_or(0),
+                ),
+            }
+        })
+        .collect();
+    coverage.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+    Ok(coverage)
+}
+
 fn normalize_display_path(path: &Path) -> String {
     let buf: PathBuf = path.to_path_buf();
     buf.display().to_string()
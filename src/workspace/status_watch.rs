@@ -0,0 +1,141 @@
+//! `status --watch`: re-render a status view on a timer.
+//!
+//! On a terminal each refresh redraws the screen in place; otherwise each refresh is printed
+//! as a separate snapshot so logs and pipes stay readable. Ctrl-C ends the loop; the command
+//! session is then marked interrupted on the next start, as with `meld watch`.
+
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{SecondsFormat, Utc};
+
+use crate::error::ApiError;
+use crate::workspace::types::{WorkspaceScanState, WorkspaceStatus};
+
+/// Clear the screen and move the cursor home.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Workspace figures compared between refreshes to show trends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusSample {
+    pub scan_state: WorkspaceScanState,
+    pub total_nodes: u64,
+    /// Nodes with a head frame, summed over agents.
+    pub nodes_with_frame: u64,
+}
+
+impl StatusSample {
+    pub fn from_workspace(status: &WorkspaceStatus) -> Self {
+        Self {
+            scan_state: status.scan_state,
+            total_nodes: status.tree.as_ref().map_or(0, |tree| tree.total_nodes),
+            nodes_with_frame: status
+                .context_coverage
+                .iter()
+                .flatten()
+                .map(|entry| entry.nodes_with_frame)
+                .sum(),
+        }
+    }
+}
+
+/// One rendered refresh: the status text plus the figures used for trends.
+pub struct StatusSnapshot {
+    pub body: String,
+    pub sample: Option<StatusSample>,
+}
+
+/// Refresh loop state for `status --watch`.
+pub struct StatusWatch {
+    in_place: bool,
+    cycles: u64,
+    previous: Option<StatusSample>,
+}
+
+impl StatusWatch {
+    /// `in_place` redraws the screen on each refresh; use it only when stdout is a terminal.
+    pub fn new(in_place: bool) -> Self {
+        Self {
+            in_place,
+            cycles: 0,
+            previous: None,
+        }
+    }
+
+    /// Take one snapshot and write it to `out`.
+    pub fn cycle<F>(&mut self, out: &mut dyn Write, refresh: F) -> Result<(), ApiError>
+    where
+        F: FnOnce() -> Result<StatusSnapshot, ApiError>,
+    {
+        let snapshot = refresh()?;
+        self.cycles += 1;
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let mut frame = String::new();
+        if self.in_place {
+            frame.push_str(CLEAR_SCREEN);
+        } else if self.cycles > 1 {
+            frame.push('\n');
+        }
+        frame.push_str(&format!(
+            "[refresh {} at {}]\n{}\n",
+            self.cycles, timestamp, snapshot.body
+        ));
+        if let Some(sample) = snapshot.sample {
+            frame.push_str(&format_trend(self.previous.as_ref(), &sample));
+            self.previous = Some(sample);
+        }
+        out.write_all(frame.as_bytes())
+            .and_then(|_| out.flush())
+            .map_err(|e| ApiError::StorageError(crate::error::StorageError::IoError(e)))
+    }
+
+    /// Refresh every `interval` until interrupted, or until `max_cycles` refreshes ran.
+    pub fn run<F>(
+        &mut self,
+        out: &mut dyn Write,
+        interval: Duration,
+        max_cycles: Option<u64>,
+        mut refresh: F,
+    ) -> Result<(), ApiError>
+    where
+        F: FnMut() -> Result<StatusSnapshot, ApiError>,
+    {
+        loop {
+            self.cycle(out, &mut refresh)?;
+            if max_cycles.is_some_and(|max| self.cycles >= max) {
+                return Ok(());
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn format_trend(previous: Option<&StatusSample>, current: &StatusSample) -> String {
+    let delta = |now: u64, before: Option<u64>| match before {
+        Some(before) => format!("{} ({:+})", now, now as i64 - before as i64),
+        None => now.to_string(),
+    };
+    let scan_state = match current.scan_state {
+        WorkspaceScanState::Missing => "missing",
+        WorkspaceScanState::Current => "current",
+        WorkspaceScanState::Stale => "stale",
+    };
+    let mut line = format!(
+        "Trend: nodes {}, nodes with frames {}, scan {}",
+        delta(current.total_nodes, previous.map(|p| p.total_nodes)),
+        delta(
+            current.nodes_with_frame,
+            previous.map(|p| p.nodes_with_frame)
+        ),
+        scan_state
+    );
+    if let Some(previous) = previous {
+        if previous.scan_state != current.scan_state {
+            line.push_str(" (changed)");
+        }
+    }
+    line.push('\n');
+    line
+}
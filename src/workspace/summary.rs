@@ -40,11 +40,13 @@ pub fn validate(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn delete(
     target_path: bool,
     target_node: bool,
     dry_run: bool,
     no_ignore: bool,
+    keep_frames: bool,
     ok: bool,
     duration_ms: u128,
     error: Option<&str>,
@@ -56,6 +58,7 @@ pub fn delete(
             "target": summary_target(target_path, target_node),
             "dry_run": dry_run,
             "no_ignore": no_ignore,
+            "keep_frames": keep_frames,
             "ok": ok,
             "duration_ms": duration_ms,
             "error": error,
@@ -63,9 +66,13 @@ pub fn delete(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn restore(
     target_path: bool,
     target_node: bool,
+    all: bool,
+    older_than_days: Option<u64>,
+    newer_than_days: Option<u64>,
     dry_run: bool,
     ok: bool,
     duration_ms: u128,
@@ -75,7 +82,9 @@ pub fn restore(
         "workspace_mutation_summary",
         json!({
             "operation": "restore",
-            "target": summary_target(target_path, target_node),
+            "target": if all { "all" } else { summary_target(target_path, target_node) },
+            "older_than_days": older_than_days,
+            "newer_than_days": newer_than_days,
             "dry_run": dry_run,
             "ok": ok,
             "duration_ms": duration_ms,
@@ -84,11 +93,14 @@ pub fn restore(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn compact(
     ttl_days: Option<u64>,
     all: bool,
     keep_frames: bool,
+    purge_kept: bool,
     dry_run: bool,
+    vacuum: bool,
     ok: bool,
     duration_ms: u128,
     error: Option<&str>,
@@ -100,7 +112,9 @@ pub fn compact(
             "ttl_days": ttl_days,
             "all": all,
             "keep_frames": keep_frames,
+            "purge_kept": purge_kept,
             "dry_run": dry_run,
+            "vacuum": vacuum,
             "ok": ok,
             "duration_ms": duration_ms,
             "error": error,
@@ -108,8 +122,11 @@ pub fn compact(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn list_deleted(
     older_than_days: Option<u64>,
+    sort: Option<&str>,
+    reverse: bool,
     format: &str,
     ok: bool,
     duration_ms: u128,
@@ -120,6 +137,8 @@ pub fn list_deleted(
         json!({
             "scope": "workspace_deleted",
             "older_than_days": older_than_days,
+            "sort": sort,
+            "reverse": reverse,
             "format": format,
             "ok": ok,
             "duration_ms": duration_ms,
@@ -150,6 +169,26 @@ pub fn ignore(
     )
 }
 
+pub fn size(
+    by_frame_type: bool,
+    format: &str,
+    ok: bool,
+    duration_ms: u128,
+    error: Option<&str>,
+) -> TypedSummaryEvent {
+    TypedSummaryEvent::new(
+        "status_summary",
+        json!({
+            "scope": "workspace_size",
+            "by_frame_type": by_frame_type,
+            "format": format,
+            "ok": ok,
+            "duration_ms": duration_ms,
+            "error": error,
+        }),
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn unified_status(
     format: &str,
@@ -179,6 +218,42 @@ pub fn unified_status(
     )
 }
 
+pub fn tree(
+    scoped: bool,
+    depth: Option<usize>,
+    frames: bool,
+    format: &str,
+    ok: bool,
+    duration_ms: u128,
+    error: Option<&str>,
+) -> TypedSummaryEvent {
+    TypedSummaryEvent::new(
+        "status_summary",
+        json!({
+            "scope": "tree",
+            "scoped": scoped,
+            "depth": depth,
+            "frames": frames,
+            "format": format,
+            "ok": ok,
+            "duration_ms": duration_ms,
+            "error": error,
+        }),
+    )
+}
+
+pub fn tree_determinism(ok: bool, duration_ms: u128, error: Option<&str>) -> TypedSummaryEvent {
+    TypedSummaryEvent::new(
+        "status_summary",
+        json!({
+            "scope": "tree_determinism",
+            "ok": ok,
+            "duration_ms": duration_ms,
+            "error": error,
+        }),
+    )
+}
+
 pub fn validate_workspace(ok: bool, duration_ms: u128, error: Option<&str>) -> TypedSummaryEvent {
     validate("text", ok, duration_ms, error)
 }
@@ -1,56 +1,112 @@
 use crate::agent::registry::AgentRegistry;
 use crate::api::ContextApi;
 use crate::cli::{
-    format_ignore_result, format_list_deleted_result, format_validate_result_text,
+    format_ignore_result, format_list_deleted_result, format_node_tree, format_repair_report_text,
+    format_scan_stats, format_validate_result_text, format_workspace_size_result, to_json_output,
     WorkspaceCommands,
 };
-use crate::config::ConfigLoader;
+use crate::config::MerkleConfig;
 use crate::error::ApiError;
 use crate::ignore;
 use crate::telemetry::ProgressRuntime;
 use crate::workflow::binding::validate_agent_binding;
 use crate::workflow::WorkflowRegistry;
 use crate::workspace::events::scan_started_envelope;
+use crate::workspace::status_watch::{StatusSample, StatusSnapshot, StatusWatch};
 use crate::workspace::{
-    format_unified_status_text, format_workspace_status_text, WatchConfig, WatchDaemon,
-    WorkspaceCommandService, WorkspaceStatusRequest,
+    format_multi_workspace_status_text, format_unified_status_text, format_workspace_status_text,
+    MultiWorkspaceStatusOutput, WatchConfig, WatchDaemon, WorkspaceCommandService,
+    WorkspaceStatusRequest, WorkspaceStatusSummary,
 };
-use std::path::Path;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
+/// Unless `allow_empty_workspace` is set, a scan that indexes no files appends a warning.
+/// With `stats`, tree composition follows the summary in `format` (text or json).
+#[allow(clippy::too_many_arguments)]
 pub fn handle_scan_command(
     api: &ContextApi,
     workspace_root: &Path,
     progress: &Arc<ProgressRuntime>,
     force: bool,
+    include_hidden: bool,
+    include_empty_dirs: bool,
+    verify_stable: bool,
+    allow_empty_workspace: bool,
+    stats: bool,
+    format: &str,
     session_id: &str,
 ) -> Result<String, ApiError> {
     progress.emit_event_best_effort(
         session_id,
         "scan_started",
-        serde_json::json!({ "force": force }),
+        serde_json::json!({
+            "force": force,
+            "include_hidden": include_hidden,
+            "include_empty_dirs": include_empty_dirs,
+        }),
     );
-    let node_count = crate::workspace::commands::current_workspace_root_hash(workspace_root)
-        .ok()
-        .and_then(|_| {
-            Some(
-                crate::tree::builder::TreeBuilder::new(workspace_root.to_path_buf())
-                    .with_walker_config(crate::workspace::commands::workspace_walker_config(
-                        workspace_root,
-                    ))
-                    .build()
-                    .ok()?
-                    .nodes
-                    .len(),
-            )
-        })
-        .unwrap_or_default();
+    let node_count = crate::workspace::commands::current_workspace_root_hash(
+        api.node_store().as_ref(),
+        workspace_root,
+        api.content_normalization(),
+    )
+    .ok()
+    .and_then(|_| {
+        Some(
+            crate::tree::builder::TreeBuilder::new(workspace_root.to_path_buf())
+                .with_walker_config(crate::workspace::commands::workspace_walker_config(
+                    workspace_root,
+                    crate::workspace::commands::ScanWalkerOptions {
+                        include_hidden,
+                        include_empty_dirs,
+                    },
+                ))
+                .build()
+                .ok()?
+                .nodes
+                .len(),
+        )
+    })
+    .unwrap_or_default();
     progress.emit_envelope_best_effort(scan_started_envelope(
         session_id,
         workspace_root,
         node_count,
     ));
-    WorkspaceCommandService::scan(api, workspace_root, force, Some(progress), Some(session_id))
+    let outcome = WorkspaceCommandService::scan_with_stats(
+        api,
+        workspace_root,
+        force,
+        include_hidden,
+        include_empty_dirs,
+        stats,
+        Some(progress),
+        Some(session_id),
+    )?;
+    let mut output = outcome.message;
+    if !allow_empty_workspace
+        && crate::workspace::commands::workspace_has_indexable_files(api)? == Some(false)
+    {
+        output.push_str(
+            "\nWarning: no indexable files found; every file may be ignored. Review ignore patterns (`meld ignore`, .gitignore) or pass --allow-empty-workspace if this is intended.",
+        );
+    }
+    if verify_stable {
+        let verified = WorkspaceCommandService::verify_stable_scan(
+            api,
+            workspace_root,
+            include_hidden,
+            include_empty_dirs,
+        )?;
+        output = format!("{}\n{}", output, verified);
+    }
+    match outcome.stats {
+        Some(stats) => format_scan_stats(&output, &stats, format),
+        None => Ok(output),
+    }
 }
 
 pub fn handle_cli_command(
@@ -61,33 +117,67 @@ pub fn handle_cli_command(
     command: &WorkspaceCommands,
 ) -> Result<String, ApiError> {
     match command {
-        WorkspaceCommands::Status { format, breakdown } => {
-            let registry = api.agent_registry().read();
+        WorkspaceCommands::Status {
+            format,
+            breakdown,
+            include_content_hash,
+            check_heads,
+            by_agent,
+            check_staleness,
+            watch,
+            interval,
+        } => {
             let request = WorkspaceStatusRequest {
                 workspace_root: workspace_root.to_path_buf(),
                 store_path: store_path.to_path_buf(),
                 include_breakdown: *breakdown,
+                include_content_hash: *include_content_hash,
+                check_heads: *check_heads,
+                by_agent: *by_agent,
+                check_staleness: *check_staleness,
             };
-            let status = WorkspaceCommandService::status(api, &request, &registry)?;
+            if *watch {
+                let stdout = std::io::stdout();
+                let mut status_watch = StatusWatch::new(stdout.is_terminal());
+                status_watch.run(
+                    &mut stdout.lock(),
+                    Duration::from_secs(*interval),
+                    None,
+                    || workspace_status_snapshot(api, &request, format),
+                )?;
+                return Ok(String::new());
+            }
+            Ok(workspace_status_snapshot(api, &request, format)?.body)
+        }
+        WorkspaceCommands::Validate {
+            format,
+            repair: true,
+            dry_run,
+            allow_data_loss,
+        } => {
+            let report = WorkspaceCommandService::repair(
+                api,
+                workspace_root,
+                &frame_storage_path.to_path_buf(),
+                *dry_run,
+                *allow_data_loss,
+            )?;
             if format == "json" {
-                serde_json::to_string_pretty(&status).map_err(|e| {
+                to_json_output(&report).map_err(|e| {
                     ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
                 })
             } else {
-                Ok(format_workspace_status_text(
-                    &status,
-                    request.include_breakdown,
-                ))
+                Ok(format_repair_report_text(&report))
             }
         }
-        WorkspaceCommands::Validate { format } => {
+        WorkspaceCommands::Validate { format, .. } => {
             let result = WorkspaceCommandService::validate(
                 api,
                 workspace_root,
                 &frame_storage_path.to_path_buf(),
             )?;
             if format == "json" {
-                serde_json::to_string_pretty(&result).map_err(|e| {
+                to_json_output(&result).map_err(|e| {
                     ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
                 })
             } else {
@@ -108,6 +198,7 @@ pub fn handle_cli_command(
             node,
             dry_run,
             no_ignore,
+            keep_frames,
         } => WorkspaceCommandService::delete(
             api,
             workspace_root,
@@ -115,31 +206,101 @@ pub fn handle_cli_command(
             node.as_deref(),
             *dry_run,
             *no_ignore,
+            *keep_frames,
         ),
         WorkspaceCommands::Restore {
             path,
             node,
+            all,
+            older_than,
+            newer_than,
             dry_run,
-        } => WorkspaceCommandService::restore(
-            api,
-            workspace_root,
-            path.as_deref(),
-            node.as_deref(),
-            *dry_run,
-        ),
+        } => {
+            if *all {
+                WorkspaceCommandService::restore_all(
+                    api,
+                    workspace_root,
+                    *older_than,
+                    *newer_than,
+                    *dry_run,
+                )
+            } else {
+                WorkspaceCommandService::restore(
+                    api,
+                    workspace_root,
+                    path.as_deref(),
+                    node.as_deref(),
+                    *dry_run,
+                )
+            }
+        }
         WorkspaceCommands::Compact {
             ttl,
             all,
             keep_frames,
+            purge_kept,
             dry_run,
-        } => WorkspaceCommandService::compact(api, *ttl, *all, *keep_frames, *dry_run),
-        WorkspaceCommands::ListDeleted { older_than, format } => {
-            let result = WorkspaceCommandService::list_deleted(api, *older_than)?;
+            vacuum,
+        } => {
+            // The vacuum replaces the node store on disk, which the open run context holds;
+            // only the meld binary can run it, after the context is dropped.
+            if *vacuum {
+                return Err(ApiError::ConfigError(
+                    "--vacuum needs the node store closed; run `meld workspace compact --vacuum` \
+                     from the command line"
+                        .to_string(),
+                ));
+            }
+            WorkspaceCommandService::compact(api, *ttl, *all, *keep_frames, *purge_kept, *dry_run)
+        }
+        WorkspaceCommands::ListDeleted {
+            older_than,
+            sort,
+            reverse,
+            format,
+        } => {
+            let result =
+                WorkspaceCommandService::list_deleted(api, *older_than, sort.as_deref(), *reverse)?;
             format_list_deleted_result(&result, format.as_str())
         }
+        WorkspaceCommands::Size {
+            by_frame_type,
+            format,
+        } => {
+            let result = WorkspaceCommandService::size(
+                api,
+                workspace_root,
+                store_path,
+                frame_storage_path,
+                *by_frame_type,
+            )?;
+            format_workspace_size_result(&result, format.as_str())
+        }
     }
 }
 
+/// Render `workspace status` once; `status --watch` calls this on every refresh.
+pub fn workspace_status_snapshot(
+    api: &ContextApi,
+    request: &WorkspaceStatusRequest,
+    format: &str,
+) -> Result<StatusSnapshot, ApiError> {
+    let registry = api.agent_registry().read();
+    let status = WorkspaceCommandService::status(api, request, &registry)?;
+    let body = if format == "json" {
+        to_json_output(&status).map_err(|e| {
+            ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
+        })?
+    } else {
+        format_workspace_status_text(&status, request.include_breakdown)
+    };
+    Ok(StatusSnapshot {
+        body,
+        sample: Some(StatusSample::from_workspace(&status)),
+    })
+}
+
+/// `watch` is the refresh interval for `status --watch`; `None` renders once.
 #[allow(clippy::too_many_arguments)]
 pub fn handle_status_command(
     api: &ContextApi,
@@ -150,8 +311,113 @@ pub fn handle_status_command(
     agents_only: bool,
     providers_only: bool,
     breakdown: bool,
+    include_content_hash: bool,
     test_connectivity: bool,
+    watch: Option<Duration>,
 ) -> Result<String, ApiError> {
+    let render = || {
+        unified_status_snapshot(
+            api,
+            workspace_root,
+            store_path,
+            format,
+            workspace_only,
+            agents_only,
+            providers_only,
+            breakdown,
+            include_content_hash,
+            test_connectivity,
+        )
+    };
+    match watch {
+        Some(interval) => {
+            let stdout = std::io::stdout();
+            let mut status_watch = StatusWatch::new(stdout.is_terminal());
+            status_watch.run(&mut stdout.lock(), interval, None, render)?;
+            Ok(String::new())
+        }
+        None => Ok(render()?.body),
+    }
+}
+
+/// `status --workspaces`: summarize each listed workspace, opening every one other than the
+/// current workspace through `open_workspace` (its own config and store).
+///
+/// A workspace that fails to open or read is reported in its row; the others still run.
+pub fn handle_multi_workspace_status_command<F>(
+    api: &ContextApi,
+    workspace_root: &Path,
+    store_path: &Path,
+    workspaces: &[PathBuf],
+    format: &str,
+    open_workspace: F,
+) -> Result<String, ApiError>
+where
+    F: Fn(&Path) -> Result<(Arc<ContextApi>, PathBuf), ApiError>,
+{
+    let current_root = workspace_root.canonicalize().ok();
+    let summaries = workspaces
+        .iter()
+        .map(|path| {
+            let summary = path
+                .canonicalize()
+                .map_err(|e| {
+                    ApiError::ConfigError(format!(
+                        "Cannot open workspace {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })
+                .and_then(|root| {
+                    if current_root.as_ref() == Some(&root) {
+                        let registry = api.agent_registry().read();
+                        return WorkspaceCommandService::status_summary(
+                            api,
+                            workspace_root,
+                            store_path,
+                            &registry,
+                        );
+                    }
+                    let (workspace_api, workspace_store_path) = open_workspace(&root)?;
+                    let registry = workspace_api.agent_registry().read();
+                    WorkspaceCommandService::status_summary(
+                        &workspace_api,
+                        &root,
+                        &workspace_store_path,
+                        &registry,
+                    )
+                });
+            summary.unwrap_or_else(|err| {
+                WorkspaceStatusSummary::failed(path.display().to_string(), err.to_string())
+            })
+        })
+        .collect();
+    let output = MultiWorkspaceStatusOutput {
+        workspaces: summaries,
+    };
+
+    if format == "json" {
+        to_json_output(&output).map_err(|e| {
+            ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
+        })
+    } else {
+        Ok(format_multi_workspace_status_text(&output))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn unified_status_snapshot(
+    api: &ContextApi,
+    workspace_root: &Path,
+    store_path: &Path,
+    format: &str,
+    workspace_only: bool,
+    agents_only: bool,
+    providers_only: bool,
+    breakdown: bool,
+    include_content_hash: bool,
+    test_connectivity: bool,
+) -> Result<StatusSnapshot, ApiError> {
     let include_all = !workspace_only && !agents_only && !providers_only;
     let include_workspace = include_all || workspace_only;
     let include_agents = include_all || agents_only;
@@ -168,20 +434,40 @@ pub fn handle_status_command(
         include_agents,
         include_providers,
         breakdown,
+        include_content_hash,
         test_connectivity,
     )?;
 
-    if format == "json" {
-        serde_json::to_string_pretty(&unified).map_err(|e| {
+    let body = if format == "json" {
+        to_json_output(&unified).map_err(|e| {
             ApiError::StorageError(crate::error::StorageError::InvalidPath(e.to_string()))
-        })
+        })?
     } else {
-        Ok(format_unified_status_text(
-            &unified,
-            breakdown,
-            test_connectivity,
-        ))
-    }
+        format_unified_status_text(&unified, breakdown, test_connectivity)
+    };
+    Ok(StatusSnapshot {
+        body,
+        sample: unified.workspace.as_ref().map(StatusSample::from_workspace),
+    })
+}
+
+pub fn handle_tree_command(
+    api: &ContextApi,
+    workspace_root: &Path,
+    path: Option<&Path>,
+    depth: Option<usize>,
+    frames: bool,
+    format: &str,
+) -> Result<String, ApiError> {
+    let tree = WorkspaceCommandService::tree(api, workspace_root, path, depth, frames)?;
+    format_node_tree(&tree, format)
+}
+
+pub fn handle_tree_verify_determinism_command(
+    api: &ContextApi,
+    workspace_root: &Path,
+) -> Result<String, ApiError> {
+    WorkspaceCommandService::verify_tree_determinism(api, workspace_root)
 }
 
 pub fn handle_validate_command(
@@ -198,19 +484,20 @@ pub fn handle_validate_command(
 pub fn handle_watch_command(
     api: Arc<ContextApi>,
     workspace_root: &Path,
-    config_path: Option<&Path>,
+    config: &MerkleConfig,
     workflow_registry: &Arc<parking_lot::RwLock<WorkflowRegistry>>,
     progress: &Arc<ProgressRuntime>,
     debounce_ms: u64,
     batch_window_ms: u64,
+    include_hidden: bool,
+    once: bool,
     session_id: &str,
 ) -> Result<String, ApiError> {
-    let config = load_runtime_config(workspace_root, config_path)?;
     let loaded_workflow_registry = WorkflowRegistry::load(&config.workflows)?;
 
     {
         let mut registry = api.agent_registry().write();
-        registry.load_from_config(&config).map_err(|e| {
+        registry.load_from_config(config).map_err(|e| {
             ApiError::ConfigError(format!("Failed to load agents from config: {}", e))
         })?;
         validate_bindings(&registry, &loaded_workflow_registry)?;
@@ -229,6 +516,8 @@ pub fn handle_watch_command(
         debounce_ms,
         batch_window_ms,
         ignore_patterns,
+        include_hidden,
+        once,
         session_id: Some(session_id.to_string()),
         progress: Some(Arc::clone(progress)),
         workflow_registry: Some(Arc::clone(workflow_registry)),
@@ -236,8 +525,16 @@ pub fn handle_watch_command(
     };
 
     let daemon = WatchDaemon::new(api, watch_config)?;
+    daemon.install_signal_handlers()?;
     tracing::info!("Starting watch mode daemon");
-    daemon.start()?;
+    let watched = daemon.start();
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| ApiError::ConfigError(format!("Failed to create runtime: {}", e)))?;
+    rt.block_on(daemon.stop())?;
+    watched?;
+    if once {
+        return Ok("Watch pass completed".to_string());
+    }
     Ok("Watch daemon stopped".to_string())
 }
 
@@ -250,21 +547,3 @@ fn validate_bindings(
     }
     Ok(())
 }
-
-fn load_runtime_config(
-    workspace_root: &Path,
-    config_path: Option<&Path>,
-) -> Result<crate::config::MerkleConfig, ApiError> {
-    if let Some(config_path) = config_path {
-        ConfigLoader::load_from_file(config_path).map_err(|err| {
-            ApiError::ConfigError(format!(
-                "Failed to load config from {}: {}",
-                config_path.display(),
-                err
-            ))
-        })
-    } else {
-        ConfigLoader::load(workspace_root)
-            .map_err(|err| ApiError::ConfigError(format!("Failed to load config: {}", err)))
-    }
-}
@@ -1,5 +1,6 @@
 //! Shared types for workspace commands and status.
 
+use crate::tree::stats::TreeStats;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -9,6 +10,10 @@ pub struct WorkspaceStatusRequest {
     pub workspace_root: PathBuf,
     pub store_path: PathBuf,
     pub include_breakdown: bool,
+    pub include_content_hash: bool,
+    pub check_heads: bool,
+    pub by_agent: bool,
+    pub check_staleness: bool,
 }
 
 /// Workspace status: not-scanned or scanned with tree, coverage, top paths.
@@ -29,6 +34,16 @@ pub struct WorkspaceStatus {
     pub context_coverage: Option<Vec<ContextCoverageEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_paths_by_node_count: Option<Vec<PathCount>>,
+    /// Head index garbage counts, present only when requested with `--check-heads`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_index_health: Option<HeadIndexHealth>,
+    /// Nodes whose head frames were produced by each agent, present only with `--by-agent`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_coverage: Option<Vec<AgentCoverageEntry>>,
+    /// Head frames whose source file changed after generation, present only with
+    /// `--check-staleness`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtime_staleness: Option<MtimeStaleness>,
 }
 
 /// Result type for workspace status command; aligns with AgentStatusEntryResult / ProviderStatusEntryResult naming.
@@ -58,8 +73,49 @@ pub struct WorkspaceScanInfo {
 pub struct TreeStatus {
     pub root_hash: String,
     pub total_nodes: u64,
+    /// Symlink nodes among `total_nodes`; indexed but never generated, so excluded from coverage.
+    #[serde(default)]
+    pub symlink_nodes: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub breakdown: Option<Vec<PathCount>>,
+    /// Per-file content hashes, present only when requested for external verification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_hashes: Option<Vec<FileContentHash>>,
+}
+
+/// Recorded Blake3 content hash for one file node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileContentHash {
+    pub path: String,
+    pub size: u64,
+    pub content_hash: String,
+}
+
+/// Head index entries that no longer match stored frames or live nodes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeadIndexHealth {
+    /// All head entries, including tombstoned heads not yet purged.
+    pub total_entries: u64,
+    /// Entries whose frame blob is missing from frame storage.
+    pub dangling_entries: u64,
+    /// Entries whose node is tombstoned (without `--keep-frames`) or no longer in the node store.
+    pub stale_entries: u64,
+}
+
+/// Head frames whose recorded `source_mtime` is older than their file's current mtime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MtimeStaleness {
+    /// Head frames of live files that recorded `source_mtime`.
+    pub checked_frames: u64,
+    pub stale_frames: Vec<MtimeStaleFrame>,
+}
+
+/// One head frame whose file was modified, or removed, after the frame was generated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtimeStaleFrame {
+    pub path: String,
+    pub frame_type: String,
+    pub frame_id: String,
 }
 
 /// Path prefix and node count.
@@ -79,6 +135,15 @@ pub struct ContextCoverageEntry {
     pub coverage_pct: Option<u64>,
 }
 
+/// Nodes with at least one head frame produced by an agent, whatever the frame type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCoverageEntry {
+    pub agent_id: String,
+    pub nodes_covered: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage_pct: Option<u64>,
+}
+
 // --- Agent status (for unified status) ---
 
 /// One row for agent status table / JSON.
@@ -130,8 +195,91 @@ pub struct UnifiedStatusOutput {
     pub providers: Option<ProviderStatusOutput>,
 }
 
+/// One workspace in `status --workspaces` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceStatusSummary {
+    pub workspace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_state: Option<WorkspaceScanState>,
+    pub total_nodes: u64,
+    /// Active head frames across all nodes and frame types.
+    pub head_frames: u64,
+    pub context_coverage: Vec<ContextCoverageEntry>,
+    /// Why the workspace could not be opened or read; counts are zero when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl WorkspaceStatusSummary {
+    /// Row for a workspace that could not be opened or read.
+    pub fn failed(workspace: String, error: String) -> Self {
+        Self {
+            workspace,
+            scan_state: None,
+            total_nodes: 0,
+            head_frames: 0,
+            context_coverage: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+/// `status --workspaces` output: one summary per requested workspace, in argument order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiWorkspaceStatusOutput {
+    pub workspaces: Vec<WorkspaceStatusSummary>,
+}
+
 // --- Command result DTOs (for CLI formatting) ---
 
+/// Result of a scan: the summary line and, with `--stats`, the built tree's composition.
+#[derive(Debug, Clone)]
+pub struct ScanOutcome {
+    pub message: String,
+    pub stats: Option<TreeStats>,
+}
+
+/// Result of workspace size command.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceSizeResult {
+    pub components: Vec<StorageComponentSize>,
+    pub total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames_by_type: Option<Vec<FrameTypeSize>>,
+}
+
+/// On-disk bytes for one storage component (node store, frame storage, head index).
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageComponentSize {
+    pub component: String,
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// Frame blob count and bytes for one frame type.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameTypeSize {
+    pub frame_type: String,
+    pub frame_count: u64,
+    pub bytes: u64,
+}
+
+/// One node in the `tree` command output; children are sorted by path.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeTreeEntry {
+    pub name: String,
+    pub path: String,
+    pub node_type: String,
+    /// Active head frame count; present only when frame counts were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_frames: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<NodeTreeEntry>,
+    /// True when the depth limit hid this node's children.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
 /// Result of workspace validate command.
 #[derive(Debug, Clone, Serialize)]
 pub struct ValidateResult {
@@ -143,6 +291,69 @@ pub struct ValidateResult {
     pub warnings: Vec<String>,
 }
 
+/// Class of inconsistency `workspace validate --repair` knows how to fix.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairKind {
+    /// Head entry pointing at a missing frame blob; the entry is removed.
+    DanglingHead,
+    /// Head entry for a node that is no longer in the node store; the entry is removed.
+    OrphanHeadEntry,
+    /// Live head entry on a tombstoned node (not deleted with `--keep-frames`); the head is
+    /// tombstoned to match its node.
+    UntombstonedHead,
+    /// Frame blob of a node no longer in the node store that no head, head snapshot, or live
+    /// frame's basis chain refers to; the blob is deleted.
+    OrphanFrame,
+}
+
+impl RepairKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RepairKind::DanglingHead => "dangling_head",
+            RepairKind::OrphanHeadEntry => "orphan_head_entry",
+            RepairKind::UntombstonedHead => "untombstoned_head",
+            RepairKind::OrphanFrame => "orphan_frame",
+        }
+    }
+
+    /// Repairs that delete stored frame content.
+    pub fn loses_data(self) -> bool {
+        matches!(self, RepairKind::OrphanFrame)
+    }
+}
+
+/// One detected inconsistency and what repair did about it.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairAction {
+    pub kind: RepairKind,
+    /// Hex NodeID; absent for orphan frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_type: Option<String>,
+    pub frame_id: String,
+    /// False for dry runs and for data-loss repairs skipped without `--allow-data-loss`.
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_reason: Option<String>,
+}
+
+/// Result of `workspace validate --repair`: repair actions, then validation of the result.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairReport {
+    pub dry_run: bool,
+    pub actions: Vec<RepairAction>,
+    /// Validation after repairs were applied (before, for dry runs).
+    pub validation: ValidateResult,
+}
+
+impl RepairReport {
+    pub fn applied_count(&self) -> usize {
+        self.actions.iter().filter(|action| action.applied).count()
+    }
+}
+
 /// Result of workspace ignore command: list entries or single added path.
 #[derive(Debug, Clone, Serialize)]
 pub enum IgnoreResult {
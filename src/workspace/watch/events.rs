@@ -20,10 +20,17 @@ pub struct WatchConfig {
     pub batch_window_ms: u64,
     /// Maximum events per batch
     pub max_batch_size: usize,
+    /// Process pending events as soon as this many distinct paths are waiting, without
+    /// waiting for the batch window, so bursts cannot grow the pending set without bound
+    pub max_events_per_batch: usize,
     /// Ignore patterns (glob patterns)
     pub ignore_patterns: Vec<String>,
+    /// Index dot-prefixed entries when rebuilding the tree
+    pub include_hidden: bool,
     /// Maximum event queue size
     pub max_queue_size: usize,
+    /// Process a single batch window after the initial tree pass, then return from `start`
+    pub once: bool,
     /// Enable automatic contextframe creation for agents
     pub auto_create_frames: bool,
     /// Batch size for contextframe creation
@@ -47,6 +54,7 @@ impl Default for WatchConfig {
             debounce_ms: 100,
             batch_window_ms: 50,
             max_batch_size: 100,
+            max_events_per_batch: 1000,
             ignore_patterns: vec![
                 "**/.git/**".to_string(),
                 "**/.meld/**".to_string(),
@@ -56,7 +64,9 @@ impl Default for WatchConfig {
                 "**/*.swp".to_string(),
                 "**/*.tmp".to_string(),
             ],
+            include_hidden: false,
             max_queue_size: 10000,
+            once: false,
             auto_create_frames: true,
             frame_batch_size: 50,
             auto_generate_frames: false,
@@ -77,6 +87,64 @@ pub enum ChangeEvent {
     Renamed { from: PathBuf, to: PathBuf },
 }
 
+fn event_path(event: &ChangeEvent) -> &PathBuf {
+    match event {
+        ChangeEvent::Created(p) | ChangeEvent::Modified(p) | ChangeEvent::Removed(p) => p,
+        ChangeEvent::Renamed { to, .. } => to,
+    }
+}
+
+/// Events waiting to be processed, one per affected path in first-seen order.
+///
+/// A later event for a path replaces the earlier one, so a file touched many times in a
+/// burst counts once toward `max_events_per_batch`.
+pub(crate) struct PendingEvents {
+    events: Vec<ChangeEvent>,
+    positions: HashMap<PathBuf, usize>,
+    max_events: usize,
+}
+
+impl PendingEvents {
+    pub(crate) fn new(max_events: usize) -> Self {
+        Self {
+            events: Vec::new(),
+            positions: HashMap::new(),
+            max_events: max_events.max(1),
+        }
+    }
+
+    /// Add events; returns the whole batch once the cap is reached.
+    pub(crate) fn push_all(
+        &mut self,
+        events: impl IntoIterator<Item = ChangeEvent>,
+    ) -> Option<Vec<ChangeEvent>> {
+        for event in events {
+            let path = event_path(&event).clone();
+            match self.positions.get(&path) {
+                Some(&position) => self.events[position] = event,
+                None => {
+                    self.positions.insert(path, self.events.len());
+                    self.events.push(event);
+                }
+            }
+        }
+        if self.events.len() >= self.max_events {
+            Some(self.take())
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn take(&mut self) -> Vec<ChangeEvent> {
+        self.positions.clear();
+        std::mem::take(&mut self.events)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
 /// Event batcher for grouping and debouncing events
 pub(crate) struct EventBatcher {
     config: WatchConfig,
@@ -94,12 +162,7 @@ impl EventBatcher {
     }
 
     pub(crate) fn add_event(&mut self, event: ChangeEvent) -> bool {
-        let path = match &event {
-            ChangeEvent::Created(p) | ChangeEvent::Modified(p) | ChangeEvent::Removed(p) => {
-                p.clone()
-            }
-            ChangeEvent::Renamed { to, .. } => to.clone(),
-        };
+        let path = event_path(&event).clone();
 
         if self.should_ignore(&path) {
             return false;
@@ -167,3 +230,54 @@ impl EventBatcher {
         path_normalized == pattern_normalized || path_normalized.contains(&pattern_normalized)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_events_flush_at_cap_and_count_repeated_paths_once() {
+        let mut pending = PendingEvents::new(10);
+        let mut batches = Vec::new();
+
+        // 25 distinct files, each touched three times within one window.
+        for i in 0..25 {
+            let path = PathBuf::from(format!("/ws/file{}.rs", i));
+            let burst = [
+                ChangeEvent::Created(path.clone()),
+                ChangeEvent::Modified(path.clone()),
+                ChangeEvent::Modified(path),
+            ];
+            for event in burst {
+                if let Some(batch) = pending.push_all([event]) {
+                    batches.push(batch);
+                }
+            }
+        }
+
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![10, 10],
+            "batches flush as soon as 10 distinct paths are pending"
+        );
+        assert_eq!(
+            batches[0][0],
+            ChangeEvent::Modified(PathBuf::from("/ws/file0.rs")),
+            "the latest event for a path replaces earlier ones"
+        );
+        // file9's burst was split by the first flush, so its later edits start the second
+        // batch rather than being dropped; the same happens to file18 at the second flush.
+        assert_eq!(
+            batches[1][0],
+            ChangeEvent::Modified(PathBuf::from("/ws/file9.rs"))
+        );
+
+        let rest = pending.take();
+        assert_eq!(rest.len(), 7);
+        assert_eq!(
+            rest[0],
+            ChangeEvent::Modified(PathBuf::from("/ws/file18.rs"))
+        );
+        assert!(pending.is_empty());
+    }
+}
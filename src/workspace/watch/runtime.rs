@@ -1,6 +1,6 @@
 //! Watch daemon and runtime logic.
 
-use super::events::{ChangeEvent, EventBatcher, WatchConfig};
+use super::events::{ChangeEvent, EventBatcher, PendingEvents, WatchConfig};
 use crate::agent::AgentIdentity;
 use crate::api::ContextApi;
 use crate::context::head::backfill_legacy_heads_into_spine;
@@ -12,19 +12,24 @@ use crate::ignore;
 use crate::provider::{ProviderExecutionBinding, ProviderRuntimeOverrides};
 use crate::store::{NodeRecord, NodeRecordStore};
 use crate::tree::builder::TreeBuilder;
+use crate::tree::hasher::ContentReadOptions;
 use crate::tree::path::canonicalize_path;
 use crate::tree::walker::WalkerConfig;
 use crate::types::NodeID;
 use crate::workflow::executor::{execute_registered_workflow, WorkflowExecutionRequest};
 use crate::workflow::task_path::build_workflow_task_path_runtime;
-use crate::workspace::commands::{emit_workspace_snapshot_facts, stored_workspace_root_hash};
+use crate::workspace::commands::{
+    emit_workspace_snapshot_facts, migrate_legacy_node_ids, stored_workspace_root_hash,
+    ScanWalkerOptions,
+};
 use notify::{Event, EventKind, RecursiveMode, Watcher};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde_json::json;
 use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
@@ -34,11 +39,39 @@ struct TreeUpdateOutcome {
     observed_nodes: Vec<NodeID>,
 }
 
+/// Shutdown state of the daemon that SIGINT/SIGTERM stops.
+static SIGNAL_TARGET: Mutex<Option<ShutdownSignal>> = Mutex::new(None);
+
+/// The running flag together with whether a shutdown signal already arrived.
+///
+/// Tracked apart from `running`, which is also false before [`WatchDaemon::start`] and after
+/// a `once` pass, so only a repeated signal counts as the second one.
+#[derive(Clone)]
+struct ShutdownSignal {
+    running: Arc<RwLock<bool>>,
+    requested: Arc<AtomicBool>,
+}
+
+impl ShutdownSignal {
+    /// Record a shutdown request and clear `running`. Returns false when one was already made.
+    fn request(&self) -> bool {
+        if self.requested.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        *self.running.write() = false;
+        true
+    }
+}
+
+/// Outcome of the one-time signal handler installation.
+static SIGNAL_HANDLER: OnceLock<Result<(), String>> = OnceLock::new();
+
 /// Watch mode daemon
 pub struct WatchDaemon {
     api: Arc<ContextApi>,
     config: WatchConfig,
     running: Arc<RwLock<bool>>,
+    shutdown_requested: Arc<AtomicBool>,
     generation_queue: Option<Arc<FrameGenerationQueue>>,
 }
 
@@ -58,6 +91,9 @@ impl WatchDaemon {
                 info!("Starting with empty head index");
             }
         }
+        if let Err(err) = api.recover_heads() {
+            warn!(error = %err, "failed to reconcile head index with frame storage");
+        }
         if let Some(progress) = &config.progress {
             let session_id = config
                 .session_id
@@ -98,13 +134,27 @@ impl WatchDaemon {
             api,
             config,
             running: Arc::new(RwLock::new(false)),
+            shutdown_requested: Arc::new(AtomicBool::new(false)),
             generation_queue,
         })
     }
 
     /// Start the watch daemon
+    ///
+    /// Runs until [`WatchDaemon::stop`] is called, or, with [`WatchConfig::once`], until the
+    /// first batch window after the initial tree pass has been processed. Returns at once when
+    /// a shutdown signal arrived before it was called.
     pub fn start(&self) -> Result<(), ApiError> {
-        *self.running.write() = true;
+        {
+            // Checked under the lock so a signal racing this either sees `running` set or is
+            // seen here.
+            let mut running = self.running.write();
+            if self.shutdown_requested.load(Ordering::SeqCst) {
+                info!("Shutdown requested before the watch started");
+                return Ok(());
+            }
+            *running = true;
+        }
 
         info!("Building initial tree");
         self.emit_event_best_effort(
@@ -141,8 +191,9 @@ impl WatchDaemon {
         let mut batcher = EventBatcher::new(self.config.clone());
         let batch_window = Duration::from_millis(self.config.batch_window_ms);
 
-        let mut last_batch_time = Instant::now();
-        let mut pending_events = Vec::new();
+        let watch_started = Instant::now();
+        let mut last_batch_time = watch_started;
+        let mut pending_events = PendingEvents::new(self.config.max_events_per_batch);
 
         loop {
             if !*self.running.read() {
@@ -153,10 +204,18 @@ impl WatchDaemon {
             match rx.recv_timeout(timeout) {
                 Ok(Ok(event)) => {
                     if let Some(change_event) = self.convert_event(event) {
-                        if batcher.add_event(change_event.clone()) {
-                            pending_events.extend(batcher.take_batch());
+                        let full_batch = if batcher.add_event(change_event.clone()) {
+                            pending_events.push_all(batcher.take_batch())
                         } else {
-                            pending_events.push(change_event);
+                            pending_events.push_all([change_event])
+                        };
+                        if let Some(batch) = full_batch {
+                            debug!(
+                                event_count = batch.len(),
+                                "Pending events reached max_events_per_batch; processing early"
+                            );
+                            self.process_events(batch)?;
+                            last_batch_time = Instant::now();
                         }
                     }
                 }
@@ -165,7 +224,7 @@ impl WatchDaemon {
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
                     if !pending_events.is_empty() && last_batch_time.elapsed() >= batch_window {
-                        self.process_events(std::mem::take(&mut pending_events))?;
+                        self.process_events(pending_events.take())?;
                         last_batch_time = Instant::now();
                     }
                 }
@@ -176,15 +235,24 @@ impl WatchDaemon {
             }
 
             if !pending_events.is_empty() && last_batch_time.elapsed() >= batch_window {
-                self.process_events(std::mem::take(&mut pending_events))?;
+                self.process_events(pending_events.take())?;
                 last_batch_time = Instant::now();
             }
+
+            if self.config.once && watch_started.elapsed() >= batch_window {
+                self.process_events(pending_events.take())?;
+                *self.running.write() = false;
+                info!("Single watch pass completed");
+            }
         }
 
         Ok(())
     }
 
     /// Stop the watch daemon
+    ///
+    /// Drains the generation queue, then saves the head index so heads written by the last
+    /// batch survive the restart.
     pub async fn stop(&self) -> Result<(), ApiError> {
         *self.running.write() = false;
 
@@ -192,19 +260,89 @@ impl WatchDaemon {
             queue.stop().await?;
         }
 
+        let head_index_path = HeadIndex::persistence_path(&self.config.workspace_root);
+        self.api
+            .head_index()
+            .read()
+            .save_to_disk(&head_index_path)
+            .map_err(ApiError::from)?;
+        info!(path = ?head_index_path, "Saved head index");
+
         Ok(())
     }
 
-    fn build_initial_tree(&self) -> Result<(), ApiError> {
-        let walker_config = WalkerConfig {
+    /// Route SIGINT and SIGTERM to a graceful stop.
+    ///
+    /// The first signal clears `running`, so [`WatchDaemon::start`] returns after the batch in
+    /// progress (or at once, if it has not started yet) and the caller can run
+    /// [`WatchDaemon::stop`]. A second signal exits at once. The process-wide handler is
+    /// installed on first use and then retargeted to the latest daemon.
+    pub fn install_signal_handlers(&self) -> Result<(), ApiError> {
+        *SIGNAL_TARGET.lock() = Some(self.shutdown_signal());
+        SIGNAL_HANDLER
+            .get_or_init(|| {
+                ctrlc::set_handler(|| {
+                    let Some(signal) = SIGNAL_TARGET.lock().clone() else {
+                        std::process::exit(130);
+                    };
+                    if !signal.request() {
+                        warn!("Second shutdown signal received; exiting without cleanup");
+                        std::process::exit(130);
+                    }
+                    info!("Shutdown signal received; stopping watch daemon");
+                })
+                .map_err(|e| e.to_string())
+            })
+            .clone()
+            .map_err(|e| ApiError::ConfigError(format!("Failed to install signal handlers: {}", e)))
+    }
+
+    fn shutdown_signal(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            running: Arc::clone(&self.running),
+            requested: Arc::clone(&self.shutdown_requested),
+        }
+    }
+
+    /// Walker options for watch rebuilds: the watch's `include_hidden` and the empty-directory
+    /// setting recorded by the last scan.
+    fn walker_options(&self) -> Result<ScanWalkerOptions, ApiError> {
+        Ok(ScanWalkerOptions {
+            include_hidden: self.config.include_hidden,
+            ..ScanWalkerOptions::recorded(self.api.node_store().as_ref())?
+        })
+    }
+
+    /// Files change under the watch, so they are never memory-mapped for hashing.
+    fn read_options(&self) -> ContentReadOptions {
+        self.api.content_read_options().streaming()
+    }
+
+    fn walker_config(&self) -> Result<WalkerConfig, ApiError> {
+        let options = self.walker_options()?;
+        Ok(WalkerConfig {
             follow_symlinks: false,
             ignore_patterns: self.config.ignore_patterns.clone(),
             max_depth: None,
-        };
-        let builder =
-            TreeBuilder::new(self.config.workspace_root.clone()).with_walker_config(walker_config);
+            include_hidden: options.include_hidden,
+            include_empty_dirs: options.include_empty_dirs,
+        })
+    }
+
+    fn build_initial_tree(&self) -> Result<(), ApiError> {
+        // Record the watch's options so status compares against the tree the watch maintains.
+        self.walker_options()?
+            .record(self.api.node_store().as_ref())?;
+        let walker_config = self.walker_config()?;
+        let builder = TreeBuilder::new(self.config.workspace_root.clone())
+            .with_walker_config(walker_config)
+            .with_content_normalization(self.api.content_normalization())
+            .with_read_options(self.read_options());
         let tree = builder.build().map_err(ApiError::from)?;
 
+        // Same legacy NodeID carry-over as `meld scan`.
+        migrate_legacy_node_ids(&self.api, &self.config.workspace_root, &tree)?;
+
         NodeRecord::populate_store_from_tree(
             self.api.node_store().as_ref() as &dyn NodeRecordStore,
             &tree,
@@ -317,13 +455,11 @@ impl WatchDaemon {
         &self,
         paths: &HashSet<PathBuf>,
     ) -> Result<TreeUpdateOutcome, ApiError> {
-        let walker_config = WalkerConfig {
-            follow_symlinks: false,
-            ignore_patterns: self.config.ignore_patterns.clone(),
-            max_depth: None,
-        };
-        let builder =
-            TreeBuilder::new(self.config.workspace_root.clone()).with_walker_config(walker_config);
+        let walker_config = self.walker_config()?;
+        let builder = TreeBuilder::new(self.config.workspace_root.clone())
+            .with_walker_config(walker_config)
+            .with_content_normalization(self.api.content_normalization())
+            .with_read_options(self.read_options());
         let tree = builder.build().map_err(ApiError::from)?;
         let previous_root_hash = stored_workspace_root_hash(
             self.api.node_store().as_ref(),
@@ -665,9 +801,11 @@ mod tests {
     use tempfile::TempDir;
 
     fn create_test_api(workspace_root: &Path) -> ContextApi {
-        let store_path = workspace_root.join("store");
-        let frame_storage_path = workspace_root.join("frames");
-        let artifact_storage_path = workspace_root.join("artifacts");
+        // Keep runtime state beside the workspace so store writes never change its tree.
+        let state_root = workspace_root.parent().unwrap().join("state");
+        let store_path = state_root.join("store");
+        let frame_storage_path = state_root.join("frames");
+        let artifact_storage_path = state_root.join("artifacts");
         std::fs::create_dir_all(&frame_storage_path).unwrap();
         std::fs::create_dir_all(&artifact_storage_path).unwrap();
 
@@ -773,6 +911,97 @@ mod tests {
         assert!(has_frame);
     }
 
+    #[test]
+    fn watch_once_indexes_workspace_and_returns() {
+        let temp = TempDir::new().unwrap();
+        let workspace_root = temp.path().join("workspace");
+        std::fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("new.txt");
+        std::fs::write(&target, "fresh").unwrap();
+        let api = Arc::new(create_test_api(&workspace_root));
+        {
+            let mut agents = api.agent_registry().write();
+            agents.register(AgentIdentity::new(
+                "writer-once".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+
+        let config = WatchConfig {
+            workspace_root,
+            once: true,
+            ..WatchConfig::default()
+        };
+        let daemon = WatchDaemon::new(api.clone(), config).unwrap();
+        daemon.start().unwrap();
+        assert!(!*daemon.running.read());
+
+        let record = api
+            .node_store()
+            .find_by_path(&canonicalize_path(&target).unwrap())
+            .unwrap()
+            .expect("watch pass should index the new file");
+        assert!(api
+            .has_agent_frame(&record.node_id, "writer-once")
+            .expect("frame check should succeed"));
+    }
+
+    #[test]
+    fn signal_before_start_stops_without_counting_as_second() {
+        let temp = TempDir::new().unwrap();
+        let workspace_root = temp.path().join("workspace");
+        std::fs::create_dir_all(&workspace_root).unwrap();
+        let api = Arc::new(create_test_api(&workspace_root));
+        let config = WatchConfig {
+            workspace_root: workspace_root.clone(),
+            ..WatchConfig::default()
+        };
+        let daemon = WatchDaemon::new(Arc::clone(&api), config).unwrap();
+        let signal = daemon.shutdown_signal();
+
+        assert!(signal.request());
+        daemon.start().unwrap();
+        assert!(!*daemon.running.read());
+        assert!(api.node_store().list_all().unwrap().is_empty());
+        assert!(!signal.request());
+    }
+
+    #[tokio::test]
+    async fn stop_drains_generation_queue_and_saves_head_index() {
+        let temp = TempDir::new().unwrap();
+        let workspace_root = temp.path().join("workspace");
+        std::fs::create_dir_all(&workspace_root).unwrap();
+        let api = Arc::new(create_test_api(&workspace_root));
+        let config = WatchConfig {
+            workspace_root: workspace_root.clone(),
+            auto_create_frames: false,
+            auto_generate_frames: true,
+            ..WatchConfig::default()
+        };
+        let daemon = WatchDaemon::new(Arc::clone(&api), config).unwrap();
+        let queue = Arc::clone(daemon.generation_queue.as_ref().unwrap());
+        assert!(queue.is_running());
+
+        let node_id = crate::types::Hash::from([9u8; 32]);
+        let frame_id = [4u8; 32];
+        api.head_index()
+            .write()
+            .update_head(&node_id, "context-watch", &frame_id)
+            .unwrap();
+        *daemon.running.write() = true;
+
+        daemon.stop().await.unwrap();
+
+        assert!(!*daemon.running.read());
+        assert!(!queue.is_running());
+        let saved =
+            HeadIndex::load_from_disk(HeadIndex::persistence_path(&workspace_root)).unwrap();
+        assert_eq!(
+            saved.get_head(&node_id, "context-watch").unwrap(),
+            Some(frame_id)
+        );
+    }
+
     #[test]
     fn ensure_agent_frames_skips_bound_workflow_when_provider_unresolved() {
         let temp = TempDir::new().unwrap();
@@ -25,6 +25,9 @@ fn create_test_agent(
         system_prompt: None,
         system_prompt_path: prompt_path.map(|s| s.to_string()),
         workflow_id: None,
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: Default::default(),
     };
 
@@ -81,6 +84,7 @@ fn test_agent_list_empty() {
             command: AgentCommands::List {
                 format: "text".to_string(),
                 role: None,
+                detailed: false,
             },
         };
 
@@ -227,6 +231,7 @@ fn test_agent_list_text() {
             command: AgentCommands::List {
                 format: "text".to_string(),
                 role: None,
+                detailed: false,
             },
         };
 
@@ -257,6 +262,7 @@ fn test_agent_list_json() {
             command: AgentCommands::List {
                 format: "json".to_string(),
                 role: None,
+                detailed: false,
             },
         };
 
@@ -287,6 +293,7 @@ fn test_agent_list_filtered_by_role() {
             command: AgentCommands::List {
                 format: "text".to_string(),
                 role: Some("Writer".to_string()),
+                detailed: false,
             },
         };
 
@@ -316,6 +323,7 @@ fn test_agent_show_text() {
                 agent_id: "test-agent".to_string(),
                 format: "text".to_string(),
                 include_prompt: false,
+                effective: false,
             },
         };
 
@@ -345,6 +353,7 @@ fn test_agent_show_with_prompt() {
                 agent_id: "test-agent".to_string(),
                 format: "text".to_string(),
                 include_prompt: true,
+                effective: false,
             },
         };
 
@@ -374,6 +383,7 @@ fn test_agent_show_json() {
                 agent_id: "test-agent".to_string(),
                 format: "json".to_string(),
                 include_prompt: false,
+                effective: false,
             },
         };
 
@@ -395,6 +405,7 @@ fn test_agent_show_not_found() {
                 agent_id: "nonexistent".to_string(),
                 format: "text".to_string(),
                 include_prompt: false,
+                effective: false,
             },
         };
 
@@ -403,6 +414,69 @@ fn test_agent_show_not_found() {
     });
 }
 
+#[test]
+fn test_agent_show_effective_reports_xdg_override_of_config_toml() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let workspace = test_dir.path().join("workspace");
+        fs::create_dir_all(workspace.join("config")).unwrap();
+        fs::write(
+            workspace.join("config").join("config.toml"),
+            r#"
+[agents.layered-agent]
+agent_id = "layered-agent"
+role = "Reader"
+
+[agents.layered-agent.metadata]
+tone = "formal"
+"#,
+        )
+        .unwrap();
+
+        let xdg_path = create_test_agent("layered-agent", AgentRole::Reader, None).unwrap();
+        let mut xdg_config: AgentConfig =
+            toml::from_str(&fs::read_to_string(&xdg_path).unwrap()).unwrap();
+        xdg_config
+            .metadata
+            .insert("tone".to_string(), "casual".to_string());
+        fs::write(&xdg_path, toml::to_string_pretty(&xdg_config).unwrap()).unwrap();
+
+        let cli = RunContext::new(workspace, None).unwrap();
+        let command = Commands::Agent {
+            command: AgentCommands::Show {
+                agent_id: "layered-agent".to_string(),
+                format: "json".to_string(),
+                include_prompt: false,
+                effective: true,
+            },
+        };
+
+        let output = cli.execute(&command).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let layers = parsed["layers"].as_array().unwrap();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0]["kind"], "config_toml");
+        assert_eq!(layers[1]["kind"], "xdg");
+
+        let tone = parsed["settings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|setting| setting["key"] == "metadata.tone")
+            .expect("metadata.tone setting");
+        assert_eq!(tone["value"], "casual");
+        assert_eq!(tone["source"]["kind"], "xdg");
+        assert_eq!(
+            tone["source"]["path"].as_str().map(PathBuf::from),
+            Some(xdg_path)
+        );
+        let overrides = tone["overrides"].as_array().unwrap();
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0]["value"], "formal");
+        assert_eq!(overrides[0]["source"]["kind"], "config_toml");
+    });
+}
+
 #[test]
 fn test_agent_validate_valid() {
     let test_dir = TempDir::new().unwrap();
@@ -422,6 +496,7 @@ fn test_agent_validate_valid() {
             command: AgentCommands::Validate {
                 agent_id: Some("test-agent".to_string()),
                 all: false,
+                fail_fast: false,
                 verbose: false,
             },
         };
@@ -452,6 +527,7 @@ fn test_agent_validate_missing_prompt() {
             command: AgentCommands::Validate {
                 agent_id: Some("test-agent".to_string()),
                 all: false,
+                fail_fast: false,
                 verbose: false,
             },
         };
@@ -490,6 +566,7 @@ fn test_agent_validate_all() {
             command: AgentCommands::Validate {
                 agent_id: None,
                 all: true,
+                fail_fast: false,
                 verbose: false,
             },
         };
@@ -522,6 +599,7 @@ fn test_agent_validate_all_verbose() {
             command: AgentCommands::Validate {
                 agent_id: None,
                 all: true,
+                fail_fast: false,
                 verbose: true,
             },
         };
@@ -556,6 +634,7 @@ fn test_agent_validate_all_empty() {
             command: AgentCommands::Validate {
                 agent_id: None,
                 all: true,
+                fail_fast: false,
                 verbose: false,
             },
         };
@@ -571,6 +650,72 @@ fn test_agent_validate_all_empty() {
     });
 }
 
+#[test]
+fn test_agent_validate_all_keep_going_reports_every_failure() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let prompt_path = create_test_prompt_file(&test_dir, "good.md");
+        create_test_agent("agent-a-bad", AgentRole::Writer, Some("/nonexistent/a.md")).unwrap();
+        create_test_agent("agent-b-bad", AgentRole::Writer, Some("/nonexistent/b.md")).unwrap();
+        create_test_agent(
+            "agent-c-good",
+            AgentRole::Writer,
+            Some(prompt_path.to_str().unwrap()),
+        )
+        .unwrap();
+
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+        let command = Commands::Agent {
+            command: AgentCommands::Validate {
+                agent_id: None,
+                all: true,
+                fail_fast: false,
+                verbose: false,
+            },
+        };
+
+        let message = cli.execute(&command).unwrap_err().to_string();
+        assert!(message.contains("2 agent(s) failed validation"));
+        assert!(message.contains("✗ agent-a-bad"));
+        assert!(message.contains("✗ agent-b-bad"));
+        assert!(message.contains("✓ agent-c-good"));
+        assert!(!message.contains("--fail-fast"));
+    });
+}
+
+#[test]
+fn test_agent_validate_all_fail_fast_stops_at_first_failure() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let prompt_path = create_test_prompt_file(&test_dir, "good.md");
+        create_test_agent("agent-a-bad", AgentRole::Writer, Some("/nonexistent/a.md")).unwrap();
+        create_test_agent("agent-b-bad", AgentRole::Writer, Some("/nonexistent/b.md")).unwrap();
+        create_test_agent(
+            "agent-c-good",
+            AgentRole::Writer,
+            Some(prompt_path.to_str().unwrap()),
+        )
+        .unwrap();
+
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+        let command = Commands::Agent {
+            command: AgentCommands::Validate {
+                agent_id: None,
+                all: true,
+                fail_fast: true,
+                verbose: false,
+            },
+        };
+
+        let message = cli.execute(&command).unwrap_err().to_string();
+        assert!(message.contains("1 agent(s) failed validation"));
+        assert!(message.contains("✗ agent-a-bad"));
+        assert!(!message.contains("agent-b-bad"));
+        assert!(!message.contains("agent-c-good"));
+        assert!(message.contains("2 agent(s) not validated"));
+    });
+}
+
 #[test]
 fn test_agent_create_non_interactive() {
     let test_dir = TempDir::new().unwrap();
@@ -753,3 +898,78 @@ fn test_agent_remove_not_found() {
         assert!(result.is_err());
     });
 }
+
+#[test]
+fn test_agent_list_detailed_reports_prompt_existence() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let prompt_path = create_test_prompt_file(&test_dir, "present.md");
+        create_test_agent(
+            "with-prompt",
+            AgentRole::Writer,
+            Some(prompt_path.to_str().unwrap()),
+        )
+        .unwrap();
+        let missing_path = test_dir.path().join("prompts").join("missing.md");
+        create_test_agent(
+            "without-prompt",
+            AgentRole::Writer,
+            Some(missing_path.to_str().unwrap()),
+        )
+        .unwrap();
+
+        let workspace = test_dir.path().to_path_buf();
+        let cli = RunContext::new(workspace, None).unwrap();
+
+        let output = cli
+            .execute(&Commands::Agent {
+                command: AgentCommands::List {
+                    format: "json".to_string(),
+                    role: None,
+                    detailed: true,
+                },
+            })
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let agents = json["agents"].as_array().unwrap();
+        let entry = |id: &str| {
+            agents
+                .iter()
+                .find(|agent| agent["agent_id"] == id)
+                .unwrap_or_else(|| panic!("{} should be listed", id))
+                .clone()
+        };
+        let with_prompt = entry("with-prompt");
+        assert_eq!(with_prompt["prompt_exists"], true);
+        assert!(with_prompt["prompt_path"]
+            .as_str()
+            .unwrap()
+            .ends_with("present.md"));
+        assert!(with_prompt["default_provider"].is_null());
+        let without_prompt = entry("without-prompt");
+        assert_eq!(without_prompt["prompt_exists"], false);
+        assert!(without_prompt["prompt_path"]
+            .as_str()
+            .unwrap()
+            .ends_with("missing.md"));
+
+        let text = cli
+            .execute(&Commands::Agent {
+                command: AgentCommands::List {
+                    format: "text".to_string(),
+                    role: None,
+                    detailed: true,
+                },
+            })
+            .unwrap();
+        let line = |id: &str| {
+            text.lines()
+                .find(|line| line.trim_start().starts_with(id))
+                .unwrap_or_else(|| panic!("{} should have a row", id))
+                .to_string()
+        };
+        assert!(line("with-prompt").contains(" ok "));
+        assert!(line("without-prompt").contains(" missing "));
+        assert!(text.contains("Prompt Path"));
+    });
+}
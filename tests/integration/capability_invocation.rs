@@ -46,6 +46,9 @@ fn create_test_agent(agent_id: &str) {
         system_prompt: Some("You are a careful docs writer.".to_string()),
         system_prompt_path: None,
         workflow_id: None,
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: metadata.into(),
     };
 
@@ -212,7 +215,15 @@ fn workspace_and_traversal_capabilities_follow_scanned_tree() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let mut catalog = CapabilityCatalog::new();
@@ -324,7 +335,15 @@ fn context_provider_finalize_capabilities_materialize_frame() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let node_id = meld::workspace::resolve_workspace_node_id(
@@ -597,7 +616,15 @@ fn context_prepare_dereferences_frame_ref_supporting_input() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let node_id = meld::workspace::resolve_workspace_node_id(
@@ -149,6 +149,9 @@ fn test_config_validation_errors() {
             system_prompt: None,
             system_prompt_path: None,
             workflow_id: None,
+            default_provider: None,
+            frame_type_per_node_type: false,
+            capabilities: Vec::new(),
             metadata: Default::default(),
         },
     );
@@ -6,10 +6,11 @@
 //! - Error handling
 //! - Concurrent request handling
 
-use meld::agent::{AgentIdentity, AgentRegistry, AgentRole};
-use meld::api::{ContextApi, ContextView};
+use meld::agent::{AgentIdentity, AgentRegistry, AgentRole, Capability};
+use meld::api::{ContextApi, ContextView, NodeContext};
 use meld::concurrency::NodeLockManager;
 use meld::context::frame::{Basis, Frame, FrameStorage};
+use meld::context::generation::contracts::HeadConflictPolicy;
 use meld::error::ApiError;
 use meld::heads::HeadIndex;
 use meld::metadata::frame_key_registry::{
@@ -26,6 +27,8 @@ use std::sync::Arc;
 use std::thread;
 use tempfile::TempDir;
 
+use crate::integration::with_xdg_env;
+
 fn create_test_api() -> (ContextApi, TempDir) {
     let temp_dir = TempDir::new().unwrap();
     let store_path = temp_dir.path().join("store");
@@ -145,6 +148,59 @@ fn test_get_node_deterministic() {
     assert_eq!(frame_ids1, frame_ids2);
 }
 
+fn hash_test_context(node_id: NodeID, contents: &[&str]) -> NodeContext {
+    let frames: Vec<Frame> = contents
+        .iter()
+        .map(|content| {
+            Frame::new(
+                Basis::Node(node_id),
+                content.as_bytes().to_vec(),
+                "test".to_string(),
+                "writer-1".to_string(),
+                HashMap::new(),
+            )
+            .unwrap()
+        })
+        .collect();
+    NodeContext {
+        node_id,
+        node_record: create_test_node_record(node_id),
+        frame_count: frames.len(),
+        frames,
+    }
+}
+
+#[test]
+fn test_node_context_hash_is_stable_for_same_frames() {
+    let node_id: NodeID = [1u8; 32];
+    let first = hash_test_context(node_id, &["alpha", "beta"]);
+    let mut second = hash_test_context(node_id, &["alpha", "beta"]);
+
+    // Timestamps, metadata, and total count do not participate in the hash.
+    for frame in &mut second.frames {
+        frame.timestamp = std::time::UNIX_EPOCH;
+        frame
+            .metadata
+            .insert("note".to_string(), "ignored".to_string());
+    }
+    second.frame_count = 42;
+
+    assert_eq!(first.content_hash(), second.content_hash());
+}
+
+#[test]
+fn test_node_context_hash_changes_with_frames_and_order() {
+    let node_id: NodeID = [1u8; 32];
+    let base = hash_test_context(node_id, &["alpha", "beta"]);
+    let extended = hash_test_context(node_id, &["alpha", "beta", "gamma"]);
+    let reordered = hash_test_context(node_id, &["beta", "alpha"]);
+    let other_node = hash_test_context([2u8; 32], &["alpha", "beta"]);
+
+    assert_ne!(base.content_hash(), extended.content_hash());
+    assert_ne!(base.content_hash(), reordered.content_hash());
+    assert_ne!(base.content_hash(), other_node.content_hash());
+}
+
 #[test]
 fn test_put_frame_deterministic() {
     let (api, _temp_dir) = create_test_api();
@@ -315,6 +371,192 @@ fn test_concurrent_put_frame() {
     assert_eq!(context.frames.len(), 5);
 }
 
+fn conflict_frame(node_id: NodeID, frame_type: &str, content: &str) -> Frame {
+    Frame::new(
+        Basis::Node(node_id),
+        content.as_bytes().to_vec(),
+        frame_type.to_string(),
+        "writer-1".to_string(),
+        required_frame_metadata("writer-1"),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_put_frame_checked_applies_head_conflict_policy() {
+    let (api, _temp_dir) = create_test_api();
+    let node_id: NodeID = [1u8; 32];
+    api.node_store()
+        .put(&create_test_node_record(node_id))
+        .unwrap();
+    api.agent_registry().write().register(AgentIdentity::new(
+        "writer-1".to_string(),
+        AgentRole::Writer,
+    ));
+
+    // Plan snapshot sees `planned`; a racing writer then replaces it with `racing`.
+    let race = |frame_type: &str| {
+        let planned = api
+            .put_frame(
+                node_id,
+                conflict_frame(node_id, frame_type, "planned"),
+                "writer-1".to_string(),
+            )
+            .unwrap();
+        let racing = api
+            .put_frame(
+                node_id,
+                conflict_frame(node_id, frame_type, "racing"),
+                "writer-1".to_string(),
+            )
+            .unwrap();
+        (planned, racing)
+    };
+    let head = |frame_type: &str| api.get_head(&node_id, frame_type).unwrap();
+
+    let (planned, _racing) = race("last-wins");
+    let ours = conflict_frame(node_id, "last-wins", "ours");
+    let ours_id = ours.frame_id;
+    let written = api
+        .put_frame_checked(
+            node_id,
+            ours,
+            "writer-1".to_string(),
+            Some(planned),
+            HeadConflictPolicy::LastWins,
+        )
+        .unwrap();
+    assert_eq!(written, ours_id);
+    assert_eq!(head("last-wins"), Some(ours_id));
+
+    let (planned, racing) = race("fail");
+    let ours = conflict_frame(node_id, "fail", "ours");
+    let ours_id = ours.frame_id;
+    let err = api
+        .put_frame_checked(
+            node_id,
+            ours,
+            "writer-1".to_string(),
+            Some(planned),
+            HeadConflictPolicy::Fail,
+        )
+        .unwrap_err();
+    match err {
+        ApiError::HeadConflict {
+            expected,
+            current,
+            frame_id,
+            ..
+        } => {
+            assert_eq!(expected, hex::encode(planned));
+            assert_eq!(current, hex::encode(racing));
+            assert_eq!(frame_id, hex::encode(ours_id));
+        }
+        other => panic!("expected HeadConflict, got {:?}", other),
+    }
+    assert_eq!(head("fail"), Some(racing));
+    assert!(api.frame_storage().exists(&ours_id).unwrap());
+
+    // Without a race the check passes.
+    let next = conflict_frame(node_id, "fail", "next");
+    let next_id = next.frame_id;
+    api.put_frame_checked(
+        node_id,
+        next,
+        "writer-1".to_string(),
+        Some(racing),
+        HeadConflictPolicy::Fail,
+    )
+    .unwrap();
+    assert_eq!(head("fail"), Some(next_id));
+
+    let (planned, racing) = race("keep-both");
+    let written = api
+        .put_frame_checked(
+            node_id,
+            conflict_frame(node_id, "keep-both", "ours"),
+            "writer-1".to_string(),
+            Some(planned),
+            HeadConflictPolicy::KeepBoth,
+        )
+        .unwrap();
+    assert_eq!(head("keep-both"), Some(written));
+    let stored = api.frame_storage().get(&written).unwrap().unwrap();
+    assert_eq!(stored.content, b"ours");
+    match stored.basis {
+        Basis::Both { node, frame } => {
+            assert_eq!(node, node_id);
+            assert_eq!(frame, racing);
+        }
+        other => panic!("expected basis linking the displaced head, got {:?}", other),
+    }
+    let displaced = api.frame_storage().get(&racing).unwrap().unwrap();
+    assert_eq!(displaced.content, b"racing");
+}
+
+#[test]
+fn test_concurrent_put_frame_checked_with_same_expected_head() {
+    let (api, _temp_dir) = create_test_api();
+    let node_id: NodeID = [1u8; 32];
+    api.node_store()
+        .put(&create_test_node_record(node_id))
+        .unwrap();
+    api.agent_registry().write().register(AgentIdentity::new(
+        "writer-1".to_string(),
+        AgentRole::Writer,
+    ));
+    let api = Arc::new(api);
+
+    // Every writer planned against "no head"; they race for the same head.
+    let run = |frame_type: &'static str, policy: HeadConflictPolicy| {
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                let api = api.clone();
+                thread::spawn(move || {
+                    api.put_frame_checked(
+                        node_id,
+                        conflict_frame(node_id, frame_type, &format!("writer {}", i)),
+                        "writer-1".to_string(),
+                        None,
+                        policy,
+                    )
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    let results = run("fail", HeadConflictPolicy::Fail);
+    let written: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+    assert_eq!(written.len(), 1);
+    assert!(results
+        .iter()
+        .filter_map(|r| r.as_ref().err())
+        .all(|err| matches!(err, ApiError::HeadConflict { .. })));
+    assert_eq!(api.get_head(&node_id, "fail").unwrap(), Some(*written[0]));
+
+    let results = run("keep-both", HeadConflictPolicy::KeepBoth);
+    assert!(results.iter().all(|r| r.is_ok()));
+
+    // Every frame is reachable from the head through its basis chain.
+    let mut contents = Vec::new();
+    let mut next = api.get_head(&node_id, "keep-both").unwrap();
+    while let Some(frame_id) = next {
+        let frame = api.frame_storage().get(&frame_id).unwrap().unwrap();
+        contents.push(String::from_utf8(frame.content).unwrap());
+        next = match frame.basis {
+            Basis::Both { frame, .. } => Some(frame),
+            _ => None,
+        };
+    }
+    contents.sort();
+    let expected: Vec<_> = (0..5).map(|i| format!("writer {}", i)).collect();
+    assert_eq!(contents, expected);
+}
+
 #[test]
 fn test_error_handling_node_not_found() {
     let (api, _temp_dir) = create_test_api();
@@ -366,6 +608,58 @@ fn test_error_handling_unauthorized() {
     }
 }
 
+#[test]
+fn test_put_frame_on_directory_requires_synthesize_when_policy_enabled() {
+    let (api, _temp_dir) = create_test_api();
+    let node_id: NodeID = [2u8; 32];
+    let mut node_record = create_test_node_record(node_id);
+    node_record.path = "/test/dir".into();
+    node_record.node_type = NodeType::Directory;
+    api.node_store().put(&node_record).unwrap();
+
+    {
+        let mut registry = api.agent_registry().write();
+        registry.register(AgentIdentity::new(
+            "writer-1".to_string(),
+            AgentRole::Writer,
+        ));
+        let mut synthesizer = AgentIdentity::new("synth-1".to_string(), AgentRole::Writer);
+        synthesizer
+            .grant_capabilities(&[Capability::Synthesize])
+            .unwrap();
+        registry.register(synthesizer);
+    }
+    api.set_require_synthesize(true);
+
+    let frame_for = |agent_id: &str| {
+        Frame::new(
+            Basis::Node(node_id),
+            b"summary".to_vec(),
+            "test".to_string(),
+            agent_id.to_string(),
+            required_frame_metadata(agent_id),
+        )
+        .unwrap()
+    };
+
+    let result = api.put_frame(node_id, frame_for("writer-1"), "writer-1".to_string());
+    assert!(
+        matches!(result, Err(ApiError::Unauthorized(_))),
+        "{:?}",
+        result
+    );
+    assert!(api
+        .put_frame(node_id, frame_for("synth-1"), "synth-1".to_string())
+        .is_ok());
+
+    // A reader cannot be raised to a writer by granting capabilities.
+    let mut reader = AgentIdentity::new("reader-1".to_string(), AgentRole::Reader);
+    assert!(reader
+        .grant_capabilities(&[Capability::Write, Capability::Synthesize])
+        .is_err());
+    assert!(!reader.can_write());
+}
+
 #[test]
 fn test_error_handling_invalid_frame_basis() {
     let (api, _temp_dir) = create_test_api();
@@ -695,6 +989,93 @@ fn test_runtime_write_paths_use_shared_put_frame_boundary() {
     assert!(!adapter_source.contains("frame_storage().store("));
     assert!(!adapter_source.contains("frame_storage.store("));
 
-    assert!(orchestration_source.contains("api.put_frame("));
+    assert!(orchestration_source.contains("api.put_frame_checked("));
     assert!(adapter_source.contains("self.api.put_frame("));
 }
+
+#[test]
+fn test_recover_heads_after_crash_between_frame_and_head_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace_root).unwrap();
+        let frame_storage_path = temp_dir.path().join("frames");
+        let node_store = Arc::new(SledNodeRecordStore::new(temp_dir.path().join("store")).unwrap());
+        let open_api = |head_index: HeadIndex| {
+            let api = ContextApi::with_workspace_root(
+                node_store.clone(),
+                Arc::new(FrameStorage::new(&frame_storage_path).unwrap()),
+                Arc::new(parking_lot::RwLock::new(head_index)),
+                Arc::new(
+                    PromptContextArtifactStorage::new(temp_dir.path().join("artifacts")).unwrap(),
+                ),
+                Arc::new(parking_lot::RwLock::new(AgentRegistry::new())),
+                Arc::new(parking_lot::RwLock::new(
+                    meld::provider::ProviderRegistry::new(),
+                )),
+                Arc::new(NodeLockManager::new()),
+                workspace_root.clone(),
+            );
+            api.agent_registry().write().register(AgentIdentity::new(
+                "writer-1".to_string(),
+                AgentRole::Writer,
+            ));
+            api
+        };
+        let head_index_path = HeadIndex::persistence_path(&workspace_root);
+
+        let node_id: NodeID = [9u8; 32];
+        let agent_id = "writer-1".to_string();
+        let make_frame = |content: &[u8], frame_type: &str| {
+            Frame::new(
+                Basis::Node(node_id),
+                content.to_vec(),
+                frame_type.to_string(),
+                agent_id.clone(),
+                required_frame_metadata(&agent_id),
+            )
+            .unwrap()
+        };
+
+        let api = open_api(HeadIndex::new());
+        api.node_store()
+            .put(&create_test_node_record(node_id))
+            .unwrap();
+        let committed = make_frame(b"committed", "test");
+        let committed_id = api.put_frame(node_id, committed, agent_id.clone()).unwrap();
+
+        // Crash after phase 1: frame stored, head never updated.
+        let orphan = make_frame(b"orphan", "test");
+        api.frame_storage().store(&orphan).unwrap();
+
+        // Head persisted for a frame that never reached storage.
+        let lost = make_frame(b"lost", "summary");
+        {
+            let mut head_index = api.head_index().write();
+            head_index
+                .update_head(&node_id, "summary", &lost.frame_id)
+                .unwrap();
+            head_index.save_to_disk(&head_index_path).unwrap();
+        }
+        drop(api);
+
+        // Restart from what is on disk.
+        let api = open_api(HeadIndex::load_from_disk(&head_index_path).unwrap());
+        let result = api.recover_heads().unwrap();
+        assert_eq!(result.heads_checked, 2);
+        assert_eq!(result.dangling_heads_dropped, 1);
+
+        let head_index = api.head_index().read();
+        assert_eq!(
+            head_index.get_head(&node_id, "test").unwrap(),
+            Some(committed_id)
+        );
+        assert_eq!(head_index.get_head(&node_id, "summary").unwrap(), None);
+        assert!(api.frame_storage().exists(&orphan.frame_id).unwrap());
+        drop(head_index);
+
+        let reloaded = HeadIndex::load_from_disk(&head_index_path).unwrap();
+        assert_eq!(reloaded.entries().len(), 1);
+        assert_eq!(api.recover_heads().unwrap().dangling_heads_dropped, 0);
+    });
+}
@@ -2,7 +2,7 @@
 
 use clap::Parser;
 use meld::agent::{AgentIdentity, AgentRole, AgentStorage, XdgAgentStorage};
-use meld::cli::{Cli, Commands, ContextCommands, RunContext};
+use meld::cli::{Cli, Commands, ContextCommands, GenerateArgs, RunContext};
 use meld::config::{xdg, AgentConfig, MerkleConfig, ProviderConfig, ProviderType};
 use meld::context::frame::{Basis, Frame};
 use meld::error::ApiError;
@@ -12,7 +12,8 @@ use meld::metadata::frame_write_contract::{
 use meld::tree::builder::TreeBuilder;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use tempfile::TempDir;
 
 use crate::integration::with_xdg_env;
@@ -47,6 +48,9 @@ fn create_test_agent_with_workflow(
         system_prompt: None,
         system_prompt_path: prompt_path.map(|s| s.to_string()),
         workflow_id: workflow_id.map(ToString::to_string),
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: Default::default(),
     };
 
@@ -152,7 +156,15 @@ fn test_context_get_with_path() {
 
         // Scan the workspace
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         // Get context for the file
@@ -169,6 +181,16 @@ fn test_context_get_with_path() {
                 format: "text".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -196,7 +218,15 @@ fn test_context_get_with_node_id() {
 
         // Scan the workspace
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         // Get root node ID from status (use JSON format to get full hash)
@@ -207,7 +237,11 @@ fn test_context_get_with_node_id() {
                 agents_only: false,
                 providers_only: false,
                 breakdown: false,
+                include_content_hash: false,
                 test_connectivity: false,
+                watch: false,
+                interval: 2,
+                workspaces: Vec::new(),
             })
             .unwrap();
         let status_json: serde_json::Value = serde_json::from_str(&status_output).unwrap();
@@ -229,6 +263,16 @@ fn test_context_get_with_node_id() {
                 format: "text".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -265,6 +309,16 @@ fn test_context_get_invalid_path() {
                 format: "text".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -276,6 +330,112 @@ fn test_context_get_invalid_path() {
     });
 }
 
+fn context_get_path(run_context: &RunContext, path: PathBuf) -> Result<String, ApiError> {
+    run_context.execute(&Commands::Context {
+        command: ContextCommands::Get {
+            node: None,
+            path: Some(path),
+            agent: None,
+            frame_type: None,
+            max_frames: 10,
+            ordering: "recency".to_string(),
+            combine: false,
+            separator: "\n\n---\n\n".to_string(),
+            format: "text".to_string(),
+            include_metadata: false,
+            include_deleted: false,
+            flatten_directory: false,
+            meta: Vec::new(),
+            count_only: false,
+            export: None,
+            budget_tokens: None,
+            with_ancestors: false,
+            size_only: false,
+            newest_per_agent: false,
+            resolve_basis: false,
+            include_provenance: false,
+        },
+    })
+}
+
+#[test]
+fn test_strict_paths_resolves_in_workspace_path() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src")).unwrap();
+        fs::write(workspace_root.join("src").join("lib.rs"), "fn x() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+
+        let resolved = meld::workspace::resolve_workspace_node_id(
+            run_context.api(),
+            &workspace_root,
+            Some(std::path::Path::new("src/../src/lib.rs")),
+            None,
+            false,
+        );
+        assert!(resolved.is_ok(), "got: {:?}", resolved);
+    });
+}
+
+#[test]
+fn test_strict_paths_rejects_absolute_path_outside_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let outside = temp_dir.path().join("outside.txt");
+        fs::write(&outside, "secret").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        let result = context_get_path(&run_context, outside.clone());
+        assert!(
+            matches!(result, Err(ApiError::InvalidPath(_))),
+            "got: {:?}",
+            result
+        );
+
+        // Disabling strict paths falls back to the plain tree lookup.
+        run_context.api().set_strict_paths(false);
+        let result = context_get_path(&run_context, outside);
+        assert!(
+            matches!(result, Err(ApiError::PathNotInTree(_))),
+            "got: {:?}",
+            result
+        );
+    });
+}
+
+#[test]
+fn test_strict_paths_rejects_parent_traversal_escaping_root() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(temp_dir.path().join("outside.txt"), "secret").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        let result = context_get_path(&run_context, PathBuf::from("../outside.txt"));
+        assert!(
+            matches!(result, Err(ApiError::InvalidPath(_))),
+            "got: {:?}",
+            result
+        );
+    });
+}
+
 #[test]
 fn test_context_get_json_format() {
     let temp_dir = TempDir::new().unwrap();
@@ -288,7 +448,15 @@ fn test_context_get_json_format() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let result = run_context.execute(&Commands::Context {
@@ -304,6 +472,16 @@ fn test_context_get_json_format() {
                 format: "json".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -327,7 +505,15 @@ fn test_context_get_path_uses_stale_scan_data_with_warning() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let root_hash = TreeBuilder::new(workspace_root.clone())
@@ -359,6 +545,16 @@ fn test_context_get_path_uses_stale_scan_data_with_warning() {
                 format: "text".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -386,7 +582,15 @@ fn test_context_generate_path_uses_stale_scan_data() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let root_hash = TreeBuilder::new(workspace_root.clone())
@@ -406,7 +610,7 @@ fn test_context_generate_path_uses_stale_scan_data() {
 
         let stale_context = RunContext::new(workspace_root.clone(), None).unwrap();
         let result = stale_context.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(src_dir),
                 path_positional: None,
@@ -417,8 +621,25 @@ fn test_context_generate_path_uses_stale_scan_data() {
                 provider_additional_json_file: None,
                 frame_type: None,
                 force: false,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
 
         assert!(result.is_err());
@@ -439,10 +660,12 @@ fn test_context_get_with_workflow_agent_prefers_final_result_frame() {
         let test_file = workspace_root.join("test.txt");
         fs::write(&test_file, "test content").unwrap();
 
+        let prompts_dir = xdg::prompts_dir().unwrap();
+        fs::write(prompts_dir.join("docs.md"), "Docs prompt").unwrap();
         create_test_agent_with_workflow(
             "docs-writer",
             AgentRole::Writer,
-            None,
+            Some("prompts/docs.md"),
             Some("docs_writer_thread_v1"),
         )
         .unwrap();
@@ -450,7 +673,15 @@ fn test_context_get_with_workflow_agent_prefers_final_result_frame() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let node_id = meld::workspace::resolve_workspace_node_id(
@@ -501,6 +732,16 @@ fn test_context_get_with_workflow_agent_prefers_final_result_frame() {
                 format: "json".to_string(),
                 include_metadata: true,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -536,7 +777,15 @@ fn test_context_get_json_metadata_projection_filters_internal_keys() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         {
@@ -597,6 +846,16 @@ fn test_context_get_json_metadata_projection_filters_internal_keys() {
                     format: "json".to_string(),
                     include_metadata: true,
                     include_deleted: true,
+                    flatten_directory: false,
+                    meta: Vec::new(),
+                    count_only: false,
+                    export: None,
+                    budget_tokens: None,
+                    with_ancestors: false,
+                    size_only: false,
+                    newest_per_agent: false,
+                    resolve_basis: false,
+                    include_provenance: false,
                 },
             })
             .unwrap();
@@ -605,6 +864,7 @@ fn test_context_get_json_metadata_projection_filters_internal_keys() {
         let frames = parsed["frames"].as_array().unwrap();
         assert_eq!(frames.len(), 1);
         assert_eq!(frames[0]["agent_id"].as_str(), Some("writer-metadata"));
+        assert_eq!(parsed["context_hash"].as_str().map(str::len), Some(64));
 
         let metadata_obj = frames[0]["metadata"].as_object().unwrap();
         assert!(!metadata_obj.contains_key("agent_id"));
@@ -638,148 +898,1581 @@ fn test_context_get_json_metadata_projection_filters_internal_keys() {
 }
 
 #[test]
-fn test_context_get_combine() {
+fn test_context_get_meta_filters_and_together() {
     let temp_dir = TempDir::new().unwrap();
     with_xdg_env(&temp_dir, || {
         let workspace_root = temp_dir.path().join("workspace");
         fs::create_dir_all(&workspace_root).unwrap();
 
-        let test_file = workspace_root.join("test.txt");
-        fs::write(&test_file, "test content").unwrap();
+        let test_file = workspace_root.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
-        let result = run_context.execute(&Commands::Context {
-            command: ContextCommands::Get {
-                node: None,
-                path: Some(test_file),
-                agent: None,
-                frame_type: None,
-                max_frames: 10,
-                ordering: "recency".to_string(),
-                combine: true,
-                separator: " | ".to_string(),
-                format: "text".to_string(),
-                include_metadata: false,
-                include_deleted: false,
-            },
-        });
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-meta".to_string(),
+                AgentRole::Writer,
+            ));
+        }
 
-        assert!(result.is_ok());
-        // With no frames, should still work
+        let node_id = run_context
+            .api()
+            .node_store()
+            .find_by_path(&test_file)
+            .unwrap()
+            .unwrap()
+            .node_id;
+
+        for (frame_type, model, provider) in [
+            ("summary", "model-a", "provider-1"),
+            ("review", "model-b", "provider-1"),
+            ("outline", "model-a", "provider-2"),
+        ] {
+            let mut metadata = generated_metadata("writer-meta", provider);
+            metadata.insert("model".to_string(), model.to_string());
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                format!("{} frame", frame_type).into_bytes(),
+                frame_type.to_string(),
+                "writer-meta".to_string(),
+                metadata,
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-meta".to_string())
+                .unwrap();
+        }
+
+        let get_frame_types = |meta: &[&str]| {
+            let output = run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(test_file.clone()),
+                        agent: None,
+                        frame_type: None,
+                        max_frames: 10,
+                        ordering: "deterministic".to_string(),
+                        combine: false,
+                        separator: "\n\n---\n\n".to_string(),
+                        format: "json".to_string(),
+                        include_metadata: true,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: meta.iter().map(|entry| entry.to_string()).collect(),
+                        count_only: false,
+                        export: None,
+                        budget_tokens: None,
+                        with_ancestors: false,
+                        size_only: false,
+                        newest_per_agent: false,
+                        resolve_basis: false,
+                        include_provenance: false,
+                    },
+                })
+                .unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+            parsed["frames"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|frame| frame["frame_type"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(get_frame_types(&[]), vec!["outline", "review", "summary"]);
+        assert_eq!(
+            get_frame_types(&["model=model-a"]),
+            vec!["outline", "summary"]
+        );
+        assert_eq!(
+            get_frame_types(&["model=model-a", "provider=provider-1"]),
+            vec!["summary"]
+        );
+        assert!(get_frame_types(&["model=model-c"]).is_empty());
+        assert!(get_frame_types(&["model=MODEL-A"]).is_empty());
+
+        let err = run_context
+            .execute(&Commands::Context {
+                command: ContextCommands::Get {
+                    node: None,
+                    path: Some(test_file.clone()),
+                    agent: None,
+                    frame_type: None,
+                    max_frames: 10,
+                    ordering: "recency".to_string(),
+                    combine: false,
+                    separator: "\n\n---\n\n".to_string(),
+                    format: "text".to_string(),
+                    include_metadata: false,
+                    include_deleted: false,
+                    flatten_directory: false,
+                    meta: vec!["model".to_string()],
+                    count_only: false,
+                    export: None,
+                    budget_tokens: None,
+                    with_ancestors: false,
+                    size_only: false,
+                    newest_per_agent: false,
+                    resolve_basis: false,
+                    include_provenance: false,
+                },
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid --meta filter"), "{}", err);
     });
 }
 
 #[test]
-fn test_context_generate_requires_provider() {
+fn test_context_get_count_only_counts_filtered_frames() {
     let temp_dir = TempDir::new().unwrap();
     with_xdg_env(&temp_dir, || {
-        // Create workspace and agent
         let workspace_root = temp_dir.path().join("workspace");
         fs::create_dir_all(&workspace_root).unwrap();
 
-        let prompts_dir = xdg::prompts_dir().unwrap();
-        let prompt_path = prompts_dir.join("test.md");
-        fs::write(&prompt_path, "Test prompt").unwrap();
-
-        create_test_agent("test-agent", AgentRole::Writer, Some("prompts/test.md")).unwrap();
-
-        let test_file = workspace_root.join("test.txt");
-        fs::write(&test_file, "test content").unwrap();
+        let test_file = workspace_root.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
-        // Try to generate without provider
-        let result = run_context.execute(&Commands::Context {
-            command: ContextCommands::Generate {
-                node: None,
-                path: Some(test_file),
-                path_positional: None,
-                agent: Some("test-agent".to_string()),
-                provider: None,
-                workflow_id: None,
-                provider_model: None,
-                provider_additional_json_file: None,
-                frame_type: None,
-                force: false,
-                no_recursive: false,
-            },
-        });
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-count".to_string(),
+                AgentRole::Writer,
+            ));
+        }
 
-        assert!(result.is_err());
-        match result {
-            Err(ApiError::ProviderNotConfigured(_)) => {}
-            _ => panic!("Expected ProviderNotConfigured error"),
+        let node_id = run_context
+            .api()
+            .node_store()
+            .find_by_path(&test_file)
+            .unwrap()
+            .unwrap()
+            .node_id;
+
+        for (frame_type, model, deleted) in [
+            ("summary", "model-a", false),
+            ("review", "model-b", false),
+            ("outline", "model-a", true),
+        ] {
+            let mut metadata = generated_metadata("writer-count", "provider-1");
+            metadata.insert("model".to_string(), model.to_string());
+            if deleted {
+                metadata.insert("deleted".to_string(), "true".to_string());
+            }
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                format!("{} frame", frame_type).into_bytes(),
+                frame_type.to_string(),
+                "writer-count".to_string(),
+                metadata,
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-count".to_string())
+                .unwrap();
         }
+
+        let count =
+            |frame_type: Option<&str>, meta: &[&str], include_deleted: bool, format: &str| {
+                run_context
+                    .execute(&Commands::Context {
+                        command: ContextCommands::Get {
+                            node: None,
+                            path: Some(test_file.clone()),
+                            agent: None,
+                            frame_type: frame_type.map(str::to_string),
+                            max_frames: 1,
+                            ordering: "recency".to_string(),
+                            combine: false,
+                            separator: "\n\n---\n\n".to_string(),
+                            format: format.to_string(),
+                            include_metadata: false,
+                            include_deleted,
+                            flatten_directory: false,
+                            meta: meta.iter().map(|entry| entry.to_string()).collect(),
+                            count_only: true,
+                            export: None,
+                            budget_tokens: None,
+                            with_ancestors: false,
+                            size_only: false,
+                            newest_per_agent: false,
+                            resolve_basis: false,
+                            include_provenance: false,
+                        },
+                    })
+                    .unwrap()
+            };
+
+        // --max-frames does not cap the count; deleted frames need --include-deleted.
+        assert_eq!(count(None, &[], false, "text"), "2\n");
+        assert_eq!(count(None, &[], true, "text"), "3\n");
+        assert_eq!(count(Some("review"), &[], false, "text"), "1\n");
+        assert_eq!(count(Some("outline"), &[], false, "text"), "0\n");
+        assert_eq!(count(None, &["model=model-a"], false, "text"), "1\n");
+        assert_eq!(count(None, &["model=model-a"], true, "text"), "2\n");
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&count(None, &[], true, "json")).unwrap();
+        assert_eq!(parsed, serde_json::json!({ "count": 3 }));
     });
 }
 
 #[test]
-fn test_context_generate_requires_agent_or_default() {
+fn test_context_search_returns_only_matching_nodes() {
     let temp_dir = TempDir::new().unwrap();
     with_xdg_env(&temp_dir, || {
         let workspace_root = temp_dir.path().join("workspace");
-        fs::create_dir_all(&workspace_root).unwrap();
-
-        let prompts_dir = xdg::prompts_dir().unwrap();
-        let prompt_path = prompts_dir.join("test.md");
-        fs::write(&prompt_path, "Test prompt").unwrap();
-
-        // Create a single Writer agent (should be used as default)
-        create_test_agent("test-agent", AgentRole::Writer, Some("prompts/test.md")).unwrap();
-        create_test_provider("test-provider", ProviderType::Ollama).unwrap();
+        fs::create_dir_all(workspace_root.join("src")).unwrap();
+        fs::create_dir_all(workspace_root.join("docs")).unwrap();
 
-        let test_file = workspace_root.join("test.txt");
-        fs::write(&test_file, "test content").unwrap();
+        let parser = workspace_root.join("src/parser.rs");
+        let lexer = workspace_root.join("src/lexer.rs");
+        let guide = workspace_root.join("docs/guide.md");
+        fs::write(&parser, "pub fn parse() {}").unwrap();
+        fs::write(&lexer, "pub fn lex() {}").unwrap();
+        fs::write(&guide, "# Guide").unwrap();
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
-        // Should work without --agent (uses default)
-        // Note: This will fail at generation time if provider is not actually available,
-        // but the agent resolution should work
-        let result = run_context.execute(&Commands::Context {
-            command: ContextCommands::Generate {
-                node: None,
-                path: Some(test_file),
-                path_positional: None,
-                agent: None,
-                provider: Some("test-provider".to_string()),
-                workflow_id: None,
-                provider_model: None,
-                provider_additional_json_file: None,
-                frame_type: None,
-                force: false,
-                no_recursive: false,
-            },
-        });
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-search".to_string(),
+                AgentRole::Writer,
+            ));
+        }
 
-        // May fail at provider connection, but should not fail at agent resolution
-        if let Err(e) = result {
-            // Should not be a "no agent" error
-            assert!(!e.to_string().contains("No Writer agents found"));
+        let filler = "lorem ipsum ".repeat(40);
+        for (file, frame_type, content) in [
+            (
+                &parser,
+                "summary",
+                format!("{}Builds the AST with a Pratt Parser. {}", filler, filler),
+            ),
+            (&lexer, "summary", "Turns bytes into tokens.".to_string()),
+            (
+                &lexer,
+                "review",
+                "Token spans use pratt offsets.".to_string(),
+            ),
+            (
+                &guide,
+                "summary",
+                "Explains the PRATT parser design.".to_string(),
+            ),
+        ] {
+            let node_id = run_context
+                .api()
+                .node_store()
+                .find_by_path(file)
+                .unwrap()
+                .unwrap()
+                .node_id;
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                content.into_bytes(),
+                frame_type.to_string(),
+                "writer-search".to_string(),
+                generated_metadata("writer-search", "provider-1"),
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-search".to_string())
+                .unwrap();
+        }
+
+        let search = |query: &str, path: Option<&str>, frame_type: Option<&str>, regex: bool| {
+            let output = run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Search {
+                        query: query.to_string(),
+                        path: path.map(PathBuf::from),
+                        frame_type: frame_type.map(str::to_string),
+                        regex,
+                        format: "ndjson".to_string(),
+                    },
+                })
+                .unwrap();
+            let mut hits: Vec<(String, String)> = output
+                .lines()
+                .map(|line| {
+                    let hit: serde_json::Value = serde_json::from_str(line).unwrap();
+                    let path = PathBuf::from(hit["path"].as_str().unwrap());
+                    let name = path.file_name().unwrap().to_string_lossy().to_string();
+                    (name, hit["snippet"].as_str().unwrap().to_string())
+                })
+                .collect();
+            hits.sort();
+            hits
+        };
+
+        let names = |hits: Vec<(String, String)>| -> Vec<String> {
+            hits.into_iter().map(|(name, _)| name).collect()
+        };
+
+        // Case-insensitive substring match across the whole workspace.
+        let hits = search("pratt parser", None, None, false);
+        assert_eq!(names(hits.clone()), vec!["guide.md", "parser.rs"]);
+        let (_, parser_snippet) = &hits[1];
+        assert!(
+            parser_snippet.contains("Pratt Parser"),
+            "{}",
+            parser_snippet
+        );
+        assert!(
+            parser_snippet.starts_with("...") && parser_snippet.ends_with("..."),
+            "{}",
+            parser_snippet
+        );
+        assert!(parser_snippet.len() < filler.len(), "{}", parser_snippet);
+        assert_eq!(hits[0].1, "Explains the PRATT parser design.");
+
+        // Scope and frame type narrow the frames searched.
+        assert_eq!(
+            names(search("pratt", Some("src"), None, false)),
+            vec!["lexer.rs", "parser.rs"]
+        );
+        assert_eq!(
+            names(search("pratt", Some("src"), Some("summary"), false)),
+            vec!["parser.rs"]
+        );
+
+        // --regex is case-sensitive and matches patterns.
+        assert_eq!(
+            names(search(r"P\w+ Parser", None, None, true)),
+            vec!["parser.rs"]
+        );
+        assert!(search("no such term", None, None, false).is_empty());
+
+        let text = run_context
+            .execute(&Commands::Context {
+                command: ContextCommands::Search {
+                    query: "tokens".to_string(),
+                    path: None,
+                    frame_type: None,
+                    regex: false,
+                    format: "text".to_string(),
+                },
+            })
+            .unwrap();
+        assert!(text.contains("lexer.rs [summary]"), "{}", text);
+        assert!(text.contains("  Turns bytes into tokens."), "{}", text);
+    });
+}
+
+#[test]
+fn test_context_get_export_import_round_trips_one_node() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let open_workspace = |name: &str| {
+            let root = temp_dir.path().join(name);
+            fs::create_dir_all(&root).unwrap();
+            fs::write(root.join("lib.rs"), "pub fn lib() {}").unwrap();
+            let run_context = RunContext::new(root.clone(), None).unwrap();
+            run_context
+                .execute(&Commands::Scan {
+                    force: true,
+                    include_hidden: false,
+                    include_empty_dirs: false,
+                    verify_stable: false,
+                    allow_empty_workspace: false,
+                    stats: false,
+                    format: "text".to_string(),
+                })
+                .unwrap();
+            run_context
+                .api()
+                .agent_registry()
+                .write()
+                .register(AgentIdentity::new(
+                    "writer-export".to_string(),
+                    AgentRole::Writer,
+                ));
+            (root, run_context)
+        };
+        let get = |run_context: &RunContext, root: &PathBuf, export: Option<PathBuf>| {
+            run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(root.join("lib.rs")),
+                        agent: None,
+                        frame_type: None,
+                        max_frames: 10,
+                        ordering: "recency".to_string(),
+                        combine: false,
+                        separator: "\n\n---\n\n".to_string(),
+                        format: "json".to_string(),
+                        include_metadata: false,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: Vec::new(),
+                        count_only: false,
+                        export,
+                        budget_tokens: None,
+                        with_ancestors: false,
+                        size_only: false,
+                        newest_per_agent: false,
+                        resolve_basis: false,
+                        include_provenance: false,
+                    },
+                })
+                .unwrap()
+        };
+
+        let (root_a, store_a) = open_workspace("workspace-a");
+        let node_id = store_a
+            .api()
+            .node_store()
+            .find_by_path(&root_a.join("lib.rs"))
+            .unwrap()
+            .unwrap()
+            .node_id;
+        for (frame_type, content) in [("analysis", "file analysis"), ("summary", "file summary")] {
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                content.as_bytes().to_vec(),
+                frame_type.to_string(),
+                "writer-export".to_string(),
+                generated_metadata("writer-export", "provider-1"),
+            )
+            .unwrap();
+            store_a
+                .api()
+                .put_frame(node_id, frame, "writer-export".to_string())
+                .unwrap();
         }
+        let head_a = store_a.api().get_head(&node_id, "summary").unwrap();
+
+        let export_file = temp_dir.path().join("lib.node.json");
+        let output = get(&store_a, &root_a, Some(export_file.clone()));
+        assert!(output.contains("Exported 2 frame(s)"), "{}", output);
+        let frames = |output: String| {
+            let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+            parsed["frames"].clone()
+        };
+        let expected = frames(get(&store_a, &root_a, None));
+        assert_eq!(expected.as_array().unwrap().len(), 2);
+
+        let (root_b, store_b) = open_workspace("workspace-b");
+        let output = store_b
+            .execute(&Commands::Context {
+                command: ContextCommands::Import {
+                    file: export_file.clone(),
+                    node: None,
+                    path: None,
+                },
+            })
+            .unwrap();
+        assert!(output.contains("Imported 2 frame(s)"), "{}", output);
+        assert!(output.contains("heads: analysis, summary"), "{}", output);
+
+        // Same content, type, and timestamps, and (same NodeID) the same FrameIDs.
+        assert_eq!(frames(get(&store_b, &root_b, None)), expected);
+        assert_eq!(store_b.api().get_head(&node_id, "summary").unwrap(), head_a);
+
+        // Altered content no longer matches its FrameID and is rejected before any write.
+        let mut export: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&export_file).unwrap()).unwrap();
+        export["frames"][0]["content"] = serde_json::json!(b"forged".to_vec());
+        fs::write(&export_file, serde_json::to_string(&export).unwrap()).unwrap();
+        let err = store_b
+            .execute(&Commands::Context {
+                command: ContextCommands::Import {
+                    file: export_file,
+                    node: None,
+                    path: Some(root_b.join("lib.rs")),
+                },
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("integrity check"), "{}", err);
     });
 }
 
 #[test]
-fn test_context_generate_multiple_agents_requires_flag() {
+fn test_context_get_combine() {
     let temp_dir = TempDir::new().unwrap();
     with_xdg_env(&temp_dir, || {
         let workspace_root = temp_dir.path().join("workspace");
         fs::create_dir_all(&workspace_root).unwrap();
 
-        let prompts_dir = xdg::prompts_dir().unwrap();
-        let prompt_path = prompts_dir.join("test.md");
-        fs::write(&prompt_path, "Test prompt").unwrap();
+        let test_file = workspace_root.join("test.txt");
+        fs::write(&test_file, "test content").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+
+        let result = run_context.execute(&Commands::Context {
+            command: ContextCommands::Get {
+                node: None,
+                path: Some(test_file),
+                agent: None,
+                frame_type: None,
+                max_frames: 10,
+                ordering: "recency".to_string(),
+                combine: true,
+                separator: " | ".to_string(),
+                format: "text".to_string(),
+                include_metadata: false,
+                include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
+            },
+        });
+
+        assert!(result.is_ok());
+        // With no frames, should still work
+    });
+}
+
+#[test]
+fn test_context_get_budget_tokens_keeps_highest_ordered_frames_that_fit() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let test_file = workspace_root.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-budget".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+        let node_id = run_context
+            .api()
+            .node_store()
+            .find_by_path(&test_file)
+            .unwrap()
+            .unwrap()
+            .node_id;
+
+        // Each frame is 40 characters (~10 tokens); recency puts the last one written first.
+        // Distinct frame types keep all four as heads.
+        for (frame_type, label) in [
+            ("summary", "oldest"),
+            ("analysis", "older"),
+            ("review", "newer"),
+            ("notes", "newest"),
+        ] {
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                format!("{:-<40}", label).into_bytes(),
+                frame_type.to_string(),
+                "writer-budget".to_string(),
+                generated_metadata("writer-budget", "provider-1"),
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-budget".to_string())
+                .unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let get = |budget_tokens: Option<usize>| {
+            run_context.execute(&Commands::Context {
+                command: ContextCommands::Get {
+                    node: None,
+                    path: Some(test_file.clone()),
+                    agent: None,
+                    frame_type: None,
+                    max_frames: 10,
+                    ordering: "recency".to_string(),
+                    combine: true,
+                    separator: "\n---\n".to_string(),
+                    format: "text".to_string(),
+                    include_metadata: false,
+                    include_deleted: false,
+                    flatten_directory: false,
+                    meta: Vec::new(),
+                    count_only: false,
+                    export: None,
+                    budget_tokens,
+                    with_ancestors: false,
+                    size_only: false,
+                    newest_per_agent: false,
+                    resolve_basis: false,
+                    include_provenance: false,
+                },
+            })
+        };
+
+        // 10 + (2 separator + 10) = 22 tokens; a third frame would need 34.
+        let output = get(Some(25)).unwrap();
+        assert!(output.contains("newest"), "{}", output);
+        assert!(output.contains("newer"), "{}", output);
+        assert!(!output.contains("older"), "{}", output);
+        assert!(!output.contains("oldest"), "{}", output);
+        assert!(
+            output.contains("Token budget 25: kept 2 frame(s) (~22 tokens), dropped 2."),
+            "{}",
+            output
+        );
+
+        let output = get(Some(5)).unwrap();
+        assert!(output.contains("dropped 4."), "{}", output);
+        assert!(!output.contains("newest"), "{}", output);
+
+        let output = get(None).unwrap();
+        assert!(output.contains("oldest") && !output.contains("Token budget"));
+    });
+}
+
+#[test]
+fn test_context_get_size_only_reports_byte_length_of_combined_output() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let test_file = workspace_root.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-size".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+        let node_id = run_context
+            .api()
+            .node_store()
+            .find_by_path(&test_file)
+            .unwrap()
+            .unwrap()
+            .node_id;
+
+        // Multi-byte characters keep the byte count apart from the character count. Distinct
+        // frame types keep all three as heads.
+        for (frame_type, content) in [
+            ("summary", "first summary"),
+            ("analysis", "zweite Zusammenfassung über"),
+            ("review", "третье резюме"),
+        ] {
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                content.as_bytes().to_vec(),
+                frame_type.to_string(),
+                "writer-size".to_string(),
+                generated_metadata("writer-size", "provider-1"),
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-size".to_string())
+                .unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let get = |size_only: bool, format: &str| {
+            run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(test_file.clone()),
+                        agent: None,
+                        frame_type: None,
+                        max_frames: 10,
+                        ordering: "recency".to_string(),
+                        combine: true,
+                        separator: "\n---\n".to_string(),
+                        format: format.to_string(),
+                        include_metadata: false,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: Vec::new(),
+                        count_only: false,
+                        export: None,
+                        budget_tokens: None,
+                        with_ancestors: false,
+                        size_only,
+                        newest_per_agent: false,
+                        resolve_basis: false,
+                        include_provenance: false,
+                    },
+                })
+                .unwrap()
+        };
+
+        let combined = get(false, "text");
+        let size: serde_json::Value = serde_json::from_str(&get(true, "json")).unwrap();
+        assert_eq!(size["bytes"], combined.len());
+        assert_eq!(size["frame_count"], 3);
+        assert_eq!(
+            size["estimated_tokens"],
+            combined.chars().count().div_ceil(4)
+        );
+
+        let text = get(true, "text");
+        assert!(!text.contains("summary"), "{}", text);
+        assert!(
+            text.contains(&format!("Bytes: {}\n", combined.len())),
+            "{}",
+            text
+        );
+    });
+}
+
+#[test]
+fn test_context_get_with_ancestors_includes_directory_frames_root_first() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let inner_dir = workspace_root.join("outer").join("inner");
+        fs::create_dir_all(&inner_dir).unwrap();
+        let test_file = inner_dir.join("leaf.rs");
+        fs::write(&test_file, "pub fn leaf() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-ancestors".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+        let node_id_for = |path: &Path| {
+            meld::workspace::resolve_workspace_node_id(
+                run_context.api(),
+                &workspace_root,
+                Some(path),
+                None,
+                false,
+            )
+            .unwrap()
+        };
+
+        let nodes = [
+            (workspace_root.clone(), "root summary"),
+            (workspace_root.join("outer"), "outer summary"),
+            (inner_dir.clone(), "inner summary"),
+            (test_file.clone(), "leaf summary"),
+        ];
+        // Write the leaf first so ordering cannot come from frame timestamps.
+        for (path, content) in nodes.iter().rev() {
+            let node_id = node_id_for(path);
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                content.as_bytes().to_vec(),
+                "summary".to_string(),
+                "writer-ancestors".to_string(),
+                generated_metadata("writer-ancestors", "provider-1"),
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-ancestors".to_string())
+                .unwrap();
+        }
+        // A frame of another type on a directory stays out.
+        let outer_id = node_id_for(&workspace_root.join("outer"));
+        let other = Frame::new(
+            Basis::Node(outer_id),
+            b"outer notes".to_vec(),
+            "notes".to_string(),
+            "writer-ancestors".to_string(),
+            generated_metadata("writer-ancestors", "provider-1"),
+        )
+        .unwrap();
+        run_context
+            .api()
+            .put_frame(outer_id, other, "writer-ancestors".to_string())
+            .unwrap();
+
+        let get = |with_ancestors: bool| {
+            run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(test_file.clone()),
+                        agent: None,
+                        frame_type: None,
+                        max_frames: 10,
+                        ordering: "recency".to_string(),
+                        combine: false,
+                        separator: "\n---\n".to_string(),
+                        format: "json".to_string(),
+                        include_metadata: false,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: Vec::new(),
+                        count_only: false,
+                        export: None,
+                        budget_tokens: None,
+                        with_ancestors,
+                        size_only: false,
+                        newest_per_agent: false,
+                        resolve_basis: false,
+                        include_provenance: false,
+                    },
+                })
+                .unwrap()
+        };
+        let contents = |output: &str| -> Vec<String> {
+            let parsed: serde_json::Value = serde_json::from_str(output).unwrap();
+            parsed["frames"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|frame| frame["content"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        assert_eq!(contents(&get(false)), vec!["leaf summary"]);
+        assert_eq!(
+            contents(&get(true)),
+            vec![
+                "root summary",
+                "outer summary",
+                "inner summary",
+                "leaf summary"
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_context_get_newest_per_agent_keeps_one_frame_per_agent() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let test_file = workspace_root.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            for agent in ["writer-alpha", "writer-beta"] {
+                registry.register(AgentIdentity::new(agent.to_string(), AgentRole::Writer));
+            }
+        }
+        let node_id = meld::workspace::resolve_workspace_node_id(
+            run_context.api(),
+            &workspace_root,
+            Some(&test_file),
+            None,
+            false,
+        )
+        .unwrap();
+
+        // Interleave the agents so neither one's frames are contiguous in time.
+        let writes = [
+            ("writer-alpha", "summary", "alpha first"),
+            ("writer-beta", "notes", "beta first"),
+            ("writer-alpha", "review", "alpha second"),
+            ("writer-beta", "outline", "beta second"),
+            ("writer-beta", "digest", "beta third"),
+            ("writer-alpha", "audit", "alpha third"),
+        ];
+        for (agent, frame_type, content) in writes {
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                content.as_bytes().to_vec(),
+                frame_type.to_string(),
+                agent.to_string(),
+                generated_metadata(agent, "provider-1"),
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, agent.to_string())
+                .unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let get = |newest_per_agent: bool, max_frames: usize| {
+            let output = run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(test_file.clone()),
+                        agent: None,
+                        frame_type: None,
+                        max_frames,
+                        ordering: "recency".to_string(),
+                        combine: false,
+                        separator: "\n---\n".to_string(),
+                        format: "json".to_string(),
+                        include_metadata: false,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: Vec::new(),
+                        count_only: false,
+                        export: None,
+                        budget_tokens: None,
+                        with_ancestors: false,
+                        size_only: false,
+                        newest_per_agent,
+                        resolve_basis: false,
+                        include_provenance: false,
+                    },
+                })
+                .unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+            parsed["frames"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|frame| frame["content"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(get(false, 10).len(), 6);
+        assert_eq!(get(true, 10), vec!["alpha third", "beta third"]);
+        // --max-frames applies after grouping, so it cannot hide an agent's newest frame.
+        assert_eq!(get(true, 1), vec!["alpha third"]);
+    });
+}
+
+#[test]
+fn test_context_get_resolve_basis_shows_node_path_and_child_frame() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src")).unwrap();
+        let src_dir = workspace_root.join("src");
+        let test_file = src_dir.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            for agent in ["writer-alpha", "writer-beta"] {
+                registry.register(AgentIdentity::new(agent.to_string(), AgentRole::Writer));
+            }
+        }
+        let resolve = |path: &Path| {
+            meld::workspace::resolve_workspace_node_id(
+                run_context.api(),
+                &workspace_root,
+                Some(path),
+                None,
+                false,
+            )
+            .unwrap()
+        };
+        let file_node = resolve(&test_file);
+        let dir_node = resolve(&src_dir);
+
+        let child = Frame::new(
+            Basis::Node(file_node),
+            b"file summary".to_vec(),
+            "summary".to_string(),
+            "writer-alpha".to_string(),
+            generated_metadata("writer-alpha", "provider-1"),
+        )
+        .unwrap();
+        let child_id = child.frame_id;
+        run_context
+            .api()
+            .put_frame(file_node, child, "writer-alpha".to_string())
+            .unwrap();
+        let synthesized = Frame::new(
+            Basis::Frame(child_id),
+            b"directory rollup".to_vec(),
+            "rollup".to_string(),
+            "writer-beta".to_string(),
+            generated_metadata("writer-beta", "provider-1"),
+        )
+        .unwrap();
+        run_context
+            .api()
+            .put_frame(dir_node, synthesized, "writer-beta".to_string())
+            .unwrap();
+
+        let get = |path: &Path, format: &str| {
+            run_context
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(path.to_path_buf()),
+                        agent: None,
+                        frame_type: None,
+                        max_frames: 10,
+                        ordering: "recency".to_string(),
+                        combine: false,
+                        separator: "\n---\n".to_string(),
+                        format: format.to_string(),
+                        include_metadata: true,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: Vec::new(),
+                        count_only: false,
+                        export: None,
+                        budget_tokens: None,
+                        with_ancestors: false,
+                        size_only: false,
+                        newest_per_agent: false,
+                        resolve_basis: true,
+                        include_provenance: false,
+                    },
+                })
+                .unwrap()
+        };
+
+        let text = get(&test_file, "text");
+        let basis_line = text
+            .lines()
+            .find(|line| line.starts_with("Basis: "))
+            .unwrap_or_else(|| panic!("no basis line in {}", text));
+        assert!(basis_line.starts_with("Basis: node "), "{}", basis_line);
+        assert!(basis_line.ends_with("lib.rs"), "{}", basis_line);
+
+        let json = get(&src_dir, "json");
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let basis = &parsed["frames"][0]["basis"];
+        assert_eq!(basis["kind"], "frame");
+        assert_eq!(basis["frame"]["frame_id"], hex::encode(child_id));
+        assert_eq!(basis["frame"]["frame_type"], "summary");
+        assert_eq!(basis["frame"]["agent_id"], "writer-alpha");
+        assert!(basis.get("node").is_none());
+
+        let text = get(&src_dir, "text");
+        assert!(
+            text.contains(&format!(
+                "Basis: frame {} (summary by writer-alpha)",
+                hex::encode(child_id)
+            )),
+            "{}",
+            text
+        );
+    });
+}
+
+#[test]
+fn test_context_get_flatten_directory_concatenates_file_frames_in_path_order() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let docs = workspace_root.join("docs");
+        fs::create_dir_all(docs.join("nested")).unwrap();
+        fs::write(docs.join("b.md"), "b").unwrap();
+        fs::write(docs.join("a.md"), "a").unwrap();
+        fs::write(docs.join("nested").join("c.md"), "c").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-flat".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+
+        let put_frame = |path: PathBuf, frame_type: &str, content: &str| {
+            let node_id = run_context
+                .api()
+                .node_store()
+                .find_by_path(&path)
+                .unwrap()
+                .unwrap()
+                .node_id;
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                content.as_bytes().to_vec(),
+                frame_type.to_string(),
+                "writer-flat".to_string(),
+                generated_metadata("writer-flat", "test-provider"),
+            )
+            .unwrap();
+            run_context
+                .api()
+                .put_frame(node_id, frame, "writer-flat".to_string())
+                .unwrap();
+        };
+        put_frame(
+            docs.join("nested").join("c.md"),
+            "context-writer-flat",
+            "frame c",
+        );
+        put_frame(docs.join("b.md"), "context-writer-flat", "frame b");
+        put_frame(docs.join("a.md"), "context-writer-flat", "frame a");
+        put_frame(docs.join("a.md"), "notes", "ignored note");
+        put_frame(docs.clone(), "context-writer-flat", "directory summary");
+
+        let output = run_context
+            .execute(&Commands::Context {
+                command: ContextCommands::Get {
+                    node: None,
+                    path: Some(docs),
+                    agent: None,
+                    frame_type: Some("context-writer-flat".to_string()),
+                    max_frames: 10,
+                    ordering: "recency".to_string(),
+                    combine: false,
+                    separator: " | ".to_string(),
+                    format: "text".to_string(),
+                    include_metadata: false,
+                    include_deleted: false,
+                    flatten_directory: true,
+                    meta: Vec::new(),
+                    count_only: false,
+                    export: None,
+                    budget_tokens: None,
+                    with_ancestors: false,
+                    size_only: false,
+                    newest_per_agent: false,
+                    resolve_basis: false,
+                    include_provenance: false,
+                },
+            })
+            .unwrap();
+
+        assert_eq!(output, "frame a | frame b | frame c");
+    });
+}
+
+#[test]
+fn test_context_get_ndjson_emits_one_parseable_line_per_node() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let docs = workspace_root.join("docs");
+        fs::create_dir_all(docs.join("nested")).unwrap();
+        fs::write(docs.join("b.md"), "b").unwrap();
+        fs::write(docs.join("a.md"), "a").unwrap();
+        fs::write(docs.join("nested").join("c.md"), "c").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        let a_id = run_context
+            .api()
+            .node_store()
+            .find_by_path(&docs.join("a.md"))
+            .unwrap()
+            .unwrap()
+            .node_id;
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-nd".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+        let frame = Frame::new(
+            Basis::Node(a_id),
+            b"frame a".to_vec(),
+            "context-writer-nd".to_string(),
+            "writer-nd".to_string(),
+            generated_metadata("writer-nd", "test-provider"),
+        )
+        .unwrap();
+        run_context
+            .api()
+            .put_frame(a_id, frame, "writer-nd".to_string())
+            .unwrap();
+
+        // Lines go straight to stdout as they are produced, so read them from the binary.
+        drop(run_context);
+        let get_ndjson = |flatten_directory: bool| {
+            let mut command = Command::new(env!("CARGO_BIN_EXE_meld"));
+            command
+                .arg("--workspace")
+                .arg(&workspace_root)
+                .args(["context", "get", "--format", "ndjson", "--path"])
+                .arg(&docs);
+            if flatten_directory {
+                command.arg("--flatten-directory");
+            }
+            let output = command.output().unwrap();
+            assert!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            String::from_utf8(output.stdout)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // docs, a.md, b.md, nested, nested/c.md
+        let nodes = get_ndjson(false);
+        assert_eq!(nodes.len(), 5);
+        assert!(nodes[0]["path"].as_str().unwrap().ends_with("docs"));
+        assert_eq!(nodes[0]["node_type"], "directory");
+        let a = nodes
+            .iter()
+            .find(|node| node["path"].as_str().unwrap().ends_with("a.md"))
+            .unwrap();
+        assert_eq!(a["frame_count"], 1);
+        assert_eq!(a["frames"][0]["content"], "frame a");
+
+        let files = get_ndjson(true);
+        assert_eq!(files.len(), 3);
+        assert!(files
+            .iter()
+            .all(|node| node["node_type"].as_str().unwrap().starts_with("file:")));
+    });
+}
+
+#[test]
+fn test_context_generate_on_empty_tree_returns_empty_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join(".gitignore"), "only.log\n").unwrap();
+        fs::write(workspace_root.join("only.log"), "ignored").unwrap();
+
+        let prompts_dir = xdg::prompts_dir().unwrap();
+        fs::write(prompts_dir.join("test.md"), "Test prompt").unwrap();
+        create_test_agent("test-agent", AgentRole::Writer, Some("prompts/test.md")).unwrap();
+        create_test_provider("test-provider", ProviderType::Ollama).unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: true,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+
+        let result = run_context.execute(&Commands::Context {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
+                node: None,
+                path: Some(workspace_root.clone()),
+                path_positional: None,
+                agent: Some("test-agent".to_string()),
+                provider: Some("test-provider".to_string()),
+                workflow_id: None,
+                provider_model: None,
+                provider_additional_json_file: None,
+                frame_type: None,
+                force: false,
+                only_missing: false,
+                only_stale: false,
+                no_recursive: false,
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
+        });
+
+        match result {
+            Err(err @ ApiError::EmptyWorkspace(_)) => {
+                assert!(err.to_string().contains("Review ignore patterns"));
+            }
+            other => panic!("Expected EmptyWorkspace error, got {:?}", other),
+        }
+    });
+}
+
+#[test]
+fn test_context_generate_requires_provider() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        // Create workspace and agent
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+
+        let prompts_dir = xdg::prompts_dir().unwrap();
+        let prompt_path = prompts_dir.join("test.md");
+        fs::write(&prompt_path, "Test prompt").unwrap();
+
+        create_test_agent("test-agent", AgentRole::Writer, Some("prompts/test.md")).unwrap();
+
+        let test_file = workspace_root.join("test.txt");
+        fs::write(&test_file, "test content").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+
+        // Try to generate without provider
+        let result = run_context.execute(&Commands::Context {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
+                node: None,
+                path: Some(test_file),
+                path_positional: None,
+                agent: Some("test-agent".to_string()),
+                provider: None,
+                workflow_id: None,
+                provider_model: None,
+                provider_additional_json_file: None,
+                frame_type: None,
+                force: false,
+                only_missing: false,
+                only_stale: false,
+                no_recursive: false,
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
+        });
+
+        assert!(result.is_err());
+        match result {
+            Err(ApiError::ProviderNotConfigured(_)) => {}
+            _ => panic!("Expected ProviderNotConfigured error"),
+        }
+    });
+}
+
+#[test]
+fn test_context_generate_requires_agent_or_default() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+
+        let prompts_dir = xdg::prompts_dir().unwrap();
+        let prompt_path = prompts_dir.join("test.md");
+        fs::write(&prompt_path, "Test prompt").unwrap();
+
+        // Create a single Writer agent (should be used as default)
+        create_test_agent("test-agent", AgentRole::Writer, Some("prompts/test.md")).unwrap();
+        create_test_provider("test-provider", ProviderType::Ollama).unwrap();
+
+        let test_file = workspace_root.join("test.txt");
+        fs::write(&test_file, "test content").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+
+        // Should work without --agent (uses default)
+        // Note: This will fail at generation time if provider is not actually available,
+        // but the agent resolution should work
+        let result = run_context.execute(&Commands::Context {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
+                node: None,
+                path: Some(test_file),
+                path_positional: None,
+                agent: None,
+                provider: Some("test-provider".to_string()),
+                workflow_id: None,
+                provider_model: None,
+                provider_additional_json_file: None,
+                frame_type: None,
+                force: false,
+                only_missing: false,
+                only_stale: false,
+                no_recursive: false,
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
+        });
+
+        // May fail at provider connection, but should not fail at agent resolution
+        if let Err(e) = result {
+            // Should not be a "no agent" error
+            assert!(!e.to_string().contains("No Writer agents found"));
+        }
+    });
+}
+
+#[test]
+fn test_context_generate_multiple_agents_requires_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+
+        let prompts_dir = xdg::prompts_dir().unwrap();
+        let prompt_path = prompts_dir.join("test.md");
+        fs::write(&prompt_path, "Test prompt").unwrap();
 
         // Create multiple Writer agents
         create_test_agent("agent1", AgentRole::Writer, Some("prompts/test.md")).unwrap();
@@ -791,12 +2484,20 @@ fn test_context_generate_multiple_agents_requires_flag() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         // Should fail without --agent when multiple agents exist
         let result = run_context.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(test_file),
                 path_positional: None,
@@ -807,8 +2508,25 @@ fn test_context_generate_multiple_agents_requires_flag() {
                 provider_additional_json_file: None,
                 frame_type: None,
                 force: false,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
 
         assert!(result.is_err());
@@ -831,7 +2549,15 @@ fn test_context_get_invalid_ordering() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let result = run_context.execute(&Commands::Context {
@@ -847,6 +2573,16 @@ fn test_context_get_invalid_ordering() {
                 format: "text".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -867,7 +2603,15 @@ fn test_context_get_invalid_format() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let result = run_context.execute(&Commands::Context {
@@ -883,6 +2627,16 @@ fn test_context_get_invalid_format() {
                 format: "invalid".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         });
 
@@ -918,3 +2672,93 @@ fn test_context_generate_mutually_exclusive_node_path() {
         // But we handle it in code for safety
     });
 }
+
+#[test]
+fn test_context_copy_retags_head_frame_under_new_type() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+
+        let test_file = workspace_root.join("lib.rs");
+        fs::write(&test_file, "pub fn lib() {}").unwrap();
+
+        let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
+        run_context
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+
+        {
+            let mut registry = run_context.api().agent_registry().write();
+            registry.register(AgentIdentity::new(
+                "writer-copy".to_string(),
+                AgentRole::Writer,
+            ));
+        }
+
+        let api = run_context.api();
+        let node_id = api
+            .node_store()
+            .find_by_path(&test_file)
+            .unwrap()
+            .unwrap()
+            .node_id;
+        let frame = Frame::new(
+            Basis::Node(node_id),
+            b"summary of lib.rs".to_vec(),
+            "context-legacy".to_string(),
+            "writer-copy".to_string(),
+            generated_metadata("writer-copy", "provider-1"),
+        )
+        .unwrap();
+        let source_id = api
+            .put_frame(node_id, frame, "writer-copy".to_string())
+            .unwrap();
+
+        let output = run_context
+            .execute(&Commands::Context {
+                command: ContextCommands::Copy {
+                    node: None,
+                    path: Some(test_file.clone()),
+                    from_type: "context-legacy".to_string(),
+                    to_type: "summary".to_string(),
+                },
+            })
+            .unwrap();
+
+        let copied_id = api.get_head(&node_id, "summary").unwrap().unwrap();
+        assert!(output.contains(&hex::encode(copied_id)));
+        assert_ne!(copied_id, source_id);
+        assert_eq!(
+            api.get_head(&node_id, "context-legacy").unwrap(),
+            Some(source_id)
+        );
+
+        let source = api.frame_storage().get(&source_id).unwrap().unwrap();
+        let copied = api.frame_storage().get(&copied_id).unwrap().unwrap();
+        assert_eq!(copied.frame_type, "summary");
+        assert_eq!(copied.content, source.content);
+        assert_eq!(copied.agent_id, source.agent_id);
+        assert_eq!(source.frame_type, "context-legacy");
+
+        let err = run_context
+            .execute(&Commands::Context {
+                command: ContextCommands::Copy {
+                    node: Some(hex::encode(node_id)),
+                    path: None,
+                    from_type: "missing".to_string(),
+                    to_type: "summary".to_string(),
+                },
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("no head frame of type 'missing'"));
+    });
+}
@@ -20,7 +20,7 @@ fn create_test_api() -> (ContextApi, Arc<ProgressRuntime>, String, tempfile::Tem
     std::fs::create_dir_all(&artifact_storage_path).unwrap();
 
     let db = sled::open(&store_path).unwrap();
-    let node_store = Arc::new(SledNodeRecordStore::from_db(db.clone()));
+    let node_store = Arc::new(SledNodeRecordStore::from_db(db.clone()).unwrap());
     let frame_storage = Arc::new(FrameStorage::new(&frame_storage_path).unwrap());
     let prompt_context_storage =
         Arc::new(PromptContextArtifactStorage::new(&artifact_storage_path).unwrap());
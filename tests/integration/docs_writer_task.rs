@@ -44,6 +44,9 @@ fn create_test_agent(agent_id: &str, workflow_id: Option<&str>) {
         system_prompt: Some("You are a careful docs writer.".to_string()),
         system_prompt_path: None,
         workflow_id: workflow_id.map(ToString::to_string),
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: metadata.into(),
     };
 
@@ -282,7 +285,15 @@ fn docs_writer_task_compiles_bottom_up_dependencies() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let registered_profile = run_context
@@ -366,7 +377,15 @@ fn docs_writer_task_runs_to_completion() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let registered_profile = run_context
@@ -465,7 +484,15 @@ fn docs_writer_task_accepts_wrapped_structured_output() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let registered_profile = run_context
@@ -556,7 +583,15 @@ fn docs_writer_task_expansion_is_idempotent() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let registered_profile = run_context
@@ -697,7 +732,15 @@ fn docs_writer_task_reuses_existing_child_readme_outputs() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let child_node_id = meld::workspace::resolve_workspace_node_id(
@@ -402,6 +402,43 @@ async fn test_batch_enqueue_size_limit() {
     assert!(matches!(result.unwrap_err(), ApiError::ConfigError(_)));
 }
 
+#[tokio::test]
+async fn test_partial_batch_enqueue_returns_requests_that_do_not_fit() {
+    let config = GenerationConfig {
+        max_queue_size: 3,
+        ..GenerationConfig::default()
+    };
+    let (queue, _temp_dir) = create_test_queue_with_config(config);
+    let request = |byte: u8| {
+        (
+            Hash::from([byte; 32]),
+            "agent1".to_string(),
+            "test-provider".to_string(),
+            None,
+            Priority::Normal,
+        )
+    };
+
+    queue.enqueue_batch(vec![request(1)]).await.unwrap();
+
+    // Two slots left: the duplicate of node 1 takes none, nodes 2 and 3 fill the queue.
+    let batch = vec![request(1), request(2), request(3), request(4), request(5)];
+    let batch_size = batch.len();
+    let outcome = queue.enqueue_batch_partial(batch).await.unwrap();
+    assert_eq!(outcome.accepted(), 3);
+    assert_eq!(outcome.accepted() + outcome.rejected.len(), batch_size);
+    let rejected_nodes: Vec<_> = outcome.rejected.iter().map(|r| r.0).collect();
+    assert_eq!(
+        rejected_nodes,
+        vec![Hash::from([4u8; 32]), Hash::from([5u8; 32])]
+    );
+    assert_eq!(queue.stats().pending, 3);
+
+    // The all-or-nothing variant still rejects the whole remainder.
+    assert!(queue.enqueue_batch(outcome.rejected).await.is_err());
+    assert_eq!(queue.stats().pending, 3);
+}
+
 #[tokio::test]
 async fn test_generation_request_ordering() {
     // Test that GenerationRequest implements Ord correctly
@@ -594,6 +631,63 @@ async fn test_priority_enum_ordering() {
     );
 }
 
+#[test]
+fn test_plan_priority_orders_queued_plan_requests() {
+    use meld::context::queue::RequestId;
+    use meld::context::PlanPriority;
+    use std::collections::BinaryHeap;
+
+    let plan_request = |node: u8, priority: PlanPriority, created_at: Instant| GenerationRequest {
+        request_id: RequestId::next(),
+        node_id: Hash::from([node; 32]),
+        agent_id: "agent1".to_string(),
+        provider: meld::provider::ProviderExecutionBinding::new(
+            "test-provider",
+            meld::provider::ProviderRuntimeOverrides::default(),
+        )
+        .unwrap(),
+        frame_type: "test".to_string(),
+        program: meld::context::TargetExecutionProgram::single_shot(),
+        priority: priority.into(),
+        retry_count: 0,
+        created_at,
+        completion_tx: None,
+        options: GenerationRequestOptions {
+            plan_id: Some(format!("plan-{}", node)),
+            ..GenerationRequestOptions::default()
+        },
+    };
+
+    assert_eq!(PlanPriority::parse("low").unwrap(), PlanPriority::Low);
+    assert_eq!(PlanPriority::parse("URGENT").unwrap(), PlanPriority::Urgent);
+    assert!(PlanPriority::parse("later").is_err());
+
+    // A bulk job enqueued at low priority yields to interactive work enqueued after it.
+    let now = Instant::now();
+    let mut heap = BinaryHeap::new();
+    heap.push(plan_request(1, PlanPriority::Low, now));
+    heap.push(plan_request(
+        2,
+        PlanPriority::Low,
+        now + Duration::from_millis(1),
+    ));
+    heap.push(plan_request(
+        3,
+        PlanPriority::Urgent,
+        now + Duration::from_millis(2),
+    ));
+    heap.push(plan_request(
+        4,
+        PlanPriority::Normal,
+        now + Duration::from_millis(3),
+    ));
+
+    let order: Vec<u8> = std::iter::from_fn(|| heap.pop())
+        .map(|request| request.node_id[0])
+        .collect();
+    assert_eq!(order, vec![3, 4, 1, 2]);
+}
+
 #[tokio::test]
 async fn test_concurrent_enqueue() {
     let (queue, _temp_dir) = create_test_queue();
@@ -1198,6 +1292,13 @@ async fn test_queue_rejects_generated_mutability_transition_violation() {
             GenerationRequestOptions {
                 force: true,
                 plan_id: None,
+                user_prompt_override: None,
+                stream_output: false,
+                preserve_mtime: false,
+                record_provenance: false,
+                prompt_vars: Default::default(),
+                on_conflict: Default::default(),
+                expected_head: None,
             },
         )
         .await;
@@ -0,0 +1,168 @@
+//! Integration tests for `frame show` low-level frame inspection
+
+use meld::agent::{AgentIdentity, AgentRole};
+use meld::cli::{Commands, FrameCommands, RunContext};
+use meld::context::frame::{Basis, Frame};
+use meld::error::ApiError;
+use meld::metadata::frame_write_contract::{
+    build_generated_metadata, generated_metadata_input_from_payload,
+};
+use meld::types::FrameID;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::integration::with_xdg_env;
+
+const CONTENT: &str = "frame body under inspection";
+
+/// Scan a one-file workspace and store a single frame on that file.
+fn store_frame(workspace_root: &Path) -> (RunContext, FrameID) {
+    fs::create_dir_all(workspace_root).unwrap();
+    let test_file = workspace_root.join("lib.rs");
+    fs::write(&test_file, "pub fn lib() {}").unwrap();
+
+    let run_context = RunContext::new(workspace_root.to_path_buf(), None).unwrap();
+    run_context
+        .execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+    run_context
+        .api()
+        .agent_registry()
+        .write()
+        .register(AgentIdentity::new(
+            "writer-alpha".to_string(),
+            AgentRole::Writer,
+        ));
+    let node_id = meld::workspace::resolve_workspace_node_id(
+        run_context.api(),
+        workspace_root,
+        Some(&test_file),
+        None,
+        false,
+    )
+    .unwrap();
+
+    let metadata = build_generated_metadata(&generated_metadata_input_from_payload(
+        "writer-alpha",
+        "provider-1",
+        "test-model",
+        "local",
+        "test prompt",
+        "test context",
+    ));
+    let frame = Frame::new(
+        Basis::Node(node_id),
+        CONTENT.as_bytes().to_vec(),
+        "summary".to_string(),
+        "writer-alpha".to_string(),
+        metadata,
+    )
+    .unwrap();
+    let frame_id = frame.frame_id;
+    run_context
+        .api()
+        .put_frame(node_id, frame, "writer-alpha".to_string())
+        .unwrap();
+    (run_context, frame_id)
+}
+
+fn show(run_context: &RunContext, frame_id: &str, format: &str) -> Result<String, ApiError> {
+    run_context.execute(&Commands::Frame {
+        command: FrameCommands::Show {
+            frame_id: frame_id.to_string(),
+            format: format.to_string(),
+            full: false,
+        },
+    })
+}
+
+#[test]
+fn frame_show_reports_basis_metadata_and_verified_hash() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let (run_context, frame_id) = store_frame(&temp_dir.path().join("workspace"));
+        let frame_hex = hex::encode(frame_id);
+
+        let text = show(&run_context, &frame_hex, "text").unwrap();
+        assert!(text.contains("hash verified"), "{}", text);
+        assert!(text.contains("Basis: node"), "{}", text);
+        assert!(text.contains("lib.rs"), "{}", text);
+        assert!(text.contains("Frame Type: summary"), "{}", text);
+        assert!(text.contains("agent_id: writer-alpha"), "{}", text);
+        assert!(text.contains(CONTENT), "{}", text);
+
+        let json = show(&run_context, &frame_hex, "json").unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["frame_id"], frame_hex.as_str());
+        assert_eq!(parsed["integrity"]["verified"], true);
+        assert_eq!(parsed["basis"]["kind"], "node");
+        assert!(parsed["basis"]["node"]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("lib.rs"));
+        assert_eq!(parsed["metadata"]["provider"], "provider-1");
+        assert_eq!(parsed["content_bytes"], CONTENT.len());
+        assert_eq!(parsed["content_truncated"], false);
+    });
+}
+
+#[test]
+fn frame_show_missing_frame_errors_with_id() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let (run_context, _) = store_frame(&temp_dir.path().join("workspace"));
+        let missing = hex::encode([7u8; 32]);
+
+        let err = show(&run_context, &missing, "text").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains(&missing), "{}", message);
+        assert!(message.contains("not found"), "{}", message);
+
+        let err = show(&run_context, "not-hex", "text").unwrap_err();
+        assert!(err.to_string().contains("Invalid frame ID"), "{}", err);
+    });
+}
+
+#[test]
+fn frame_show_reports_hash_mismatch_for_corrupted_blob() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let (run_context, frame_id) = store_frame(&temp_dir.path().join("workspace"));
+        let frame_hex = hex::encode(frame_id);
+
+        // Flip one content byte in place so the blob still deserializes.
+        let blob_path = run_context.api().frame_storage().blob_path(&frame_id);
+        let mut bytes = fs::read(&blob_path).unwrap();
+        let offset = bytes
+            .windows(CONTENT.len())
+            .position(|window| window == CONTENT.as_bytes())
+            .unwrap();
+        bytes[offset] = b'F';
+        fs::write(&blob_path, bytes).unwrap();
+
+        let err = show(&run_context, &frame_hex, "json").unwrap_err();
+        let ApiError::ConfigError(report) = err else {
+            panic!("expected a report error, got {:?}", err);
+        };
+        let parsed: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(parsed["integrity"]["verified"], false);
+        assert_eq!(parsed["integrity"]["stored_id"], frame_hex.as_str());
+        assert_ne!(parsed["integrity"]["computed_id"], frame_hex.as_str());
+        assert!(parsed["content"]
+            .as_str()
+            .unwrap()
+            .starts_with("Frame body"));
+
+        let err = show(&run_context, &frame_hex, "text").unwrap_err();
+        assert!(err.to_string().contains("hash mismatch"), "{}", err);
+    });
+}
@@ -167,10 +167,14 @@ fn terminal_error_class(error: &ApiError) -> String {
         ApiError::ProviderAuthFailed(_) => "ProviderAuthFailed",
         ApiError::ProviderRateLimit(_) => "ProviderRateLimit",
         ApiError::ProviderModelNotFound(_) => "ProviderModelNotFound",
+        ApiError::CircuitOpen { .. } => "CircuitOpen",
         ApiError::StorageError(_) => "StorageError",
         ApiError::ConfigError(_) => "ConfigError",
         ApiError::GenerationFailed(_) => "GenerationFailed",
         ApiError::PathNotInTree(_) => "PathNotInTree",
+        ApiError::InvalidPath(_) => "InvalidPath",
+        ApiError::EmptyWorkspace(_) => "EmptyWorkspace",
+        ApiError::HeadConflict { .. } => "HeadConflict",
     }
     .to_string()
 }
@@ -354,6 +358,13 @@ fn generation_parity_file_success_matches_fixture() {
         frame_type: "context-writer".to_string(),
         retry_count: 0,
         force: true,
+        user_prompt_override: None,
+        stream_output: false,
+        preserve_mtime: false,
+        record_provenance: false,
+        prompt_vars: Default::default(),
+        on_conflict: Default::default(),
+        expected_head: None,
     };
 
     let artifact =
@@ -404,6 +415,13 @@ fn generation_parity_directory_success_matches_fixture() {
         frame_type: "context-writer".to_string(),
         retry_count: 0,
         force: true,
+        user_prompt_override: None,
+        stream_output: false,
+        preserve_mtime: false,
+        record_provenance: false,
+        prompt_vars: Default::default(),
+        on_conflict: Default::default(),
+        expected_head: None,
     };
 
     let artifact = direct_generation_artifact(
@@ -130,9 +130,9 @@ fn test_file_node_id_metadata_sensitivity() {
     assert_ne!(node_id1, node_id2);
 }
 
-/// Test that children order affects directory NodeID
+/// Test that directory children must be sorted by name
 #[test]
-fn test_directory_node_id_children_order_sensitivity() {
+fn test_directory_node_id_rejects_unsorted_children() {
     let temp_dir = TempDir::new().unwrap();
     let test_dir = temp_dir.path().join("test_dir");
     fs::create_dir(&test_dir).unwrap();
@@ -150,13 +150,11 @@ fn test_directory_node_id_children_order_sensitivity() {
 
     let metadata = BTreeMap::new();
 
-    let node_id1 = hasher::compute_directory_node_id(&test_dir, &children1, &metadata).unwrap();
-    let node_id2 = hasher::compute_directory_node_id(&test_dir, &children2, &metadata).unwrap();
+    assert!(hasher::compute_directory_node_id(&test_dir, &children1, &metadata).is_ok());
 
-    // Different order should produce different NodeID
-    // (This tests that we're hashing in the order provided, not sorting)
-    // Note: In practice, children should be sorted before calling this function
-    assert_ne!(node_id1, node_id2);
+    // Unsorted children are rejected rather than hashed in the order provided
+    let err = hasher::compute_directory_node_id(&test_dir, &children2, &metadata).unwrap_err();
+    assert!(err.to_string().contains("not sorted"), "{}", err);
 }
 
 /// Test that empty directory produces consistent NodeID
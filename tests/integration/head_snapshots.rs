@@ -0,0 +1,223 @@
+//! Integration tests for `heads snapshot`, `heads restore`, and `heads list-snapshots`
+
+use meld::agent::{AgentIdentity, AgentRole};
+use meld::cli::{Commands, ContextCommands, HeadsCommands, RunContext};
+use meld::context::frame::{Basis, Frame};
+use meld::error::ApiError;
+use meld::metadata::frame_write_contract::{
+    build_generated_metadata, generated_metadata_input_from_payload,
+};
+use meld::types::NodeID;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::integration::with_xdg_env;
+
+/// Scan a workspace with `lib.rs` and `main.rs` and register the writer agent.
+fn scanned_workspace(workspace_root: &Path) -> RunContext {
+    fs::create_dir_all(workspace_root).unwrap();
+    fs::write(workspace_root.join("lib.rs"), "pub fn lib() {}").unwrap();
+    fs::write(workspace_root.join("main.rs"), "fn main() {}").unwrap();
+
+    let run_context = RunContext::new(workspace_root.to_path_buf(), None).unwrap();
+    run_context
+        .execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+    run_context
+        .api()
+        .agent_registry()
+        .write()
+        .register(AgentIdentity::new(
+            "writer-alpha".to_string(),
+            AgentRole::Writer,
+        ));
+    run_context
+}
+
+fn node_id(run_context: &RunContext, workspace_root: &Path, file: &str) -> NodeID {
+    meld::workspace::resolve_workspace_node_id(
+        run_context.api(),
+        workspace_root,
+        Some(&workspace_root.join(file)),
+        None,
+        false,
+    )
+    .unwrap()
+}
+
+/// Write a summary frame for `node_id`; it becomes the node's head.
+fn write_summary(run_context: &RunContext, node_id: NodeID, content: &str) {
+    let metadata = build_generated_metadata(&generated_metadata_input_from_payload(
+        "writer-alpha",
+        "provider-1",
+        "test-model",
+        "local",
+        "test prompt",
+        "test context",
+    ));
+    let frame = Frame::new(
+        Basis::Node(node_id),
+        content.as_bytes().to_vec(),
+        "summary".to_string(),
+        "writer-alpha".to_string(),
+        metadata,
+    )
+    .unwrap();
+    run_context
+        .api()
+        .put_frame(node_id, frame, "writer-alpha".to_string())
+        .unwrap();
+}
+
+fn context_get(run_context: &RunContext, path: PathBuf) -> Result<String, ApiError> {
+    run_context.execute(&Commands::Context {
+        command: ContextCommands::Get {
+            node: None,
+            path: Some(path),
+            agent: None,
+            frame_type: None,
+            max_frames: 10,
+            ordering: "recency".to_string(),
+            combine: false,
+            separator: "\n\n---\n\n".to_string(),
+            format: "text".to_string(),
+            include_metadata: false,
+            include_deleted: false,
+            flatten_directory: false,
+            meta: Vec::new(),
+            count_only: false,
+            export: None,
+            budget_tokens: None,
+            with_ancestors: false,
+            size_only: false,
+            newest_per_agent: false,
+            resolve_basis: false,
+            include_provenance: false,
+        },
+    })
+}
+
+fn snapshot(run_context: &RunContext, name: &str, force: bool) -> Result<String, ApiError> {
+    run_context.execute(&Commands::Heads {
+        command: HeadsCommands::Snapshot {
+            name: name.to_string(),
+            force,
+        },
+    })
+}
+
+#[test]
+fn heads_restore_rolls_back_regenerated_heads() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let run_context = scanned_workspace(&workspace_root);
+        let lib = node_id(&run_context, &workspace_root, "lib.rs");
+        let main = node_id(&run_context, &workspace_root, "main.rs");
+
+        write_summary(&run_context, lib, "lib summary before regeneration");
+        let saved = snapshot(&run_context, "before-regen", false).unwrap();
+        assert!(saved.contains("'before-regen' (1 head(s))"), "{}", saved);
+
+        // The experimental run moves the lib.rs head and adds one for main.rs.
+        write_summary(&run_context, lib, "lib summary after regeneration");
+        write_summary(&run_context, main, "main summary after regeneration");
+        let regenerated = context_get(&run_context, workspace_root.join("lib.rs")).unwrap();
+        assert!(
+            regenerated.contains("after regeneration"),
+            "{}",
+            regenerated
+        );
+
+        let restored = run_context
+            .execute(&Commands::Heads {
+                command: HeadsCommands::Restore {
+                    name: "before-regen".to_string(),
+                    format: "json".to_string(),
+                },
+            })
+            .unwrap();
+        let restored: serde_json::Value = serde_json::from_str(&restored).unwrap();
+        assert_eq!(restored["heads_selected"], 1);
+        assert_eq!(restored["heads_removed"], 1);
+        assert_eq!(restored["dangling_heads_skipped"], 0);
+
+        let lib_context = context_get(&run_context, workspace_root.join("lib.rs")).unwrap();
+        assert!(
+            lib_context.contains("lib summary before regeneration"),
+            "{}",
+            lib_context
+        );
+        assert!(
+            !lib_context.contains("after regeneration"),
+            "{}",
+            lib_context
+        );
+        let main_context = context_get(&run_context, workspace_root.join("main.rs")).unwrap();
+        assert!(
+            !main_context.contains("main summary after regeneration"),
+            "{}",
+            main_context
+        );
+
+        // The restored index is what a fresh process loads.
+        drop(run_context);
+        let reopened = RunContext::new(workspace_root.clone(), None).unwrap();
+        let lib_context = context_get(&reopened, workspace_root.join("lib.rs")).unwrap();
+        assert!(
+            lib_context.contains("lib summary before regeneration"),
+            "{}",
+            lib_context
+        );
+    });
+}
+
+#[test]
+fn heads_snapshot_lists_and_refuses_to_overwrite_without_force() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let run_context = scanned_workspace(&workspace_root);
+        let lib = node_id(&run_context, &workspace_root, "lib.rs");
+
+        snapshot(&run_context, "empty", false).unwrap();
+        write_summary(&run_context, lib, "lib summary");
+        let err = snapshot(&run_context, "empty", false).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+        snapshot(&run_context, "empty", true).unwrap();
+        snapshot(&run_context, "another", false).unwrap();
+        assert!(snapshot(&run_context, "../escape", false).is_err());
+
+        let listed = run_context
+            .execute(&Commands::Heads {
+                command: HeadsCommands::ListSnapshots {
+                    format: "json".to_string(),
+                },
+            })
+            .unwrap();
+        let listed: serde_json::Value = serde_json::from_str(&listed).unwrap();
+        assert_eq!(listed["total"], 2);
+        assert_eq!(listed["snapshots"][0]["name"], "another");
+        assert_eq!(listed["snapshots"][1]["name"], "empty");
+        assert_eq!(listed["snapshots"][1]["head_count"], 1);
+
+        let err = run_context
+            .execute(&Commands::Heads {
+                command: HeadsCommands::Restore {
+                    name: "missing".to_string(),
+                    format: "text".to_string(),
+                },
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{}", err);
+    });
+}
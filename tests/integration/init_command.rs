@@ -294,3 +294,143 @@ fn test_init_prompts_have_content() {
         }
     });
 }
+
+#[test]
+fn test_init_template_creates_its_agent_set() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let summary = init::initialize_template("rust-project", false).unwrap();
+
+        assert_eq!(summary.template, "rust-project");
+        assert_eq!(summary.agents.created.len(), 3);
+        assert_eq!(summary.prompts.created.len(), 2);
+        assert!(summary.providers.created.is_empty());
+        for (agent_id, is_valid, errors) in &summary.validation.results {
+            assert!(
+                is_valid,
+                "Agent {} failed validation: {:?}",
+                agent_id, errors
+            );
+        }
+
+        let prompts_dir = xdg::prompts_dir().unwrap();
+        assert!(prompts_dir.join("rust-code-analyzer.md").exists());
+        assert!(prompts_dir.join("rust-docs-writer.md").exists());
+        assert!(!prompts_dir.join("code-analyzer.md").exists());
+
+        let agents_dir = XdgAgentStorage::new().agents_dir().unwrap();
+        let content = fs::read_to_string(agents_dir.join("code-analyzer.toml")).unwrap();
+        assert!(content.contains("prompts/rust-code-analyzer.md"));
+    });
+}
+
+#[test]
+fn test_init_template_writes_provider_stub() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let preview = init::list_template_initialization("docs-only").unwrap();
+        assert_eq!(preview.agents, vec!["reader", "docs-writer"]);
+        assert_eq!(preview.providers, vec!["local-ollama"]);
+
+        let summary = init::initialize_template("docs-only", false).unwrap();
+        assert_eq!(summary.agents.created, vec!["reader", "docs-writer"]);
+        assert_eq!(summary.providers.created, vec!["local-ollama"]);
+        assert_eq!(summary.validation.results.len(), 2);
+
+        let providers_dir = xdg::providers_dir().unwrap();
+        let content = fs::read_to_string(providers_dir.join("local-ollama.toml")).unwrap();
+        assert!(content.contains("provider_type = \"ollama\""));
+
+        let agents_dir = XdgAgentStorage::new().agents_dir().unwrap();
+        let content = fs::read_to_string(agents_dir.join("docs-writer.toml")).unwrap();
+        assert!(content.contains("default_provider = \"local-ollama\""));
+
+        // Re-running keeps the existing provider config
+        let summary = init::initialize_template("docs-only", false).unwrap();
+        assert_eq!(summary.providers.skipped, vec!["local-ollama"]);
+    });
+}
+
+#[test]
+fn test_init_unknown_template_errors() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let err = init::initialize_template("no-such-template", false).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Unknown init template 'no-such-template'"));
+        assert!(message.contains("rust-project"));
+
+        assert!(init::list_template_initialization("no-such-template").is_err());
+        let agents_dir = XdgAgentStorage::new().agents_dir().unwrap();
+        assert!(!agents_dir.join("reader.toml").exists());
+    });
+}
+
+#[test]
+fn test_init_check_passes_on_complete_setup() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        init::initialize_all(false).unwrap();
+
+        let check = init::check_initialization().unwrap();
+        assert!(check.is_healthy(), "{:?}", check);
+        assert_eq!(check.validation.results.len(), 3);
+
+        let output = init::tooling::handle_cli_command(false, false, true, None, false).unwrap();
+        assert!(output.contains("Installation is complete and valid."));
+    });
+}
+
+#[test]
+fn test_init_check_reports_deleted_prompt_without_recreating_it() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        init::initialize_all(false).unwrap();
+        let prompt_path = xdg::prompts_dir().unwrap().join("code-analyzer.md");
+        fs::remove_file(&prompt_path).unwrap();
+
+        let check = init::check_initialization().unwrap();
+        assert!(!check.is_healthy());
+        assert!(check
+            .missing
+            .iter()
+            .any(|item| item.starts_with("prompt code-analyzer.md")));
+        let (_, valid, errors) = check
+            .validation
+            .results
+            .iter()
+            .find(|(agent_id, _, _)| agent_id == "code-analyzer")
+            .unwrap();
+        assert!(!valid);
+        assert!(errors
+            .iter()
+            .any(|error| error.contains("Prompt file not found")));
+
+        let err = init::tooling::handle_cli_command(false, false, true, None, false).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("prompt code-analyzer.md"), "{}", message);
+        assert!(
+            message.contains("code-analyzer validation failed"),
+            "{}",
+            message
+        );
+        assert!(!prompt_path.exists());
+    });
+}
+
+#[test]
+fn test_init_check_creates_nothing_on_fresh_setup() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let check = init::check_initialization().unwrap();
+        assert!(!check.is_healthy());
+        assert!(check
+            .missing
+            .iter()
+            .any(|item| item.starts_with("agent reader")));
+
+        let meld_dir = xdg::config_home().unwrap().join("meld");
+        assert!(!meld_dir.join("agents").exists());
+        assert!(!meld_dir.join("prompts").exists());
+    });
+}
@@ -15,19 +15,23 @@ mod docs_writer_task;
 mod event_spine;
 mod execution_projection;
 mod frame_queue;
+mod frame_show;
 mod generation_parity;
 mod hasher_verification;
+mod head_snapshots;
 mod init_command;
 mod logging_default;
 mod model_providers;
 mod node_deletion;
 mod progress_observability;
 mod provider_cli;
+mod repl;
 mod store_integration;
 mod task_artifact_repo;
 mod task_bottom_up_compile_shape;
 mod task_compiler;
 mod task_executor;
+mod telemetry_export;
 mod test_utils;
 mod tooling_integration;
 mod traversal_graph;
@@ -40,6 +44,7 @@ mod workflow_task_compatibility;
 mod workflow_traversal;
 mod workspace_commands;
 mod workspace_isolation;
+mod workspace_repair;
 mod workspace_traversal;
 mod world_state_graph;
 mod xdg_config;
@@ -150,6 +150,7 @@ fn test_completion_options() {
         presence_penalty: None,
         stop: Some(vec!["\n".to_string()]),
         additional_json: std::collections::BTreeMap::new(),
+        truncation_retry_max_tokens: None,
     };
 
     assert_eq!(options.temperature, Some(0.7));
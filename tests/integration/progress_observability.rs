@@ -1,14 +1,16 @@
 use std::fs;
 use std::io::{Read, Write};
 use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
-use meld::agent::{AgentRole, AgentStorage, XdgAgentStorage};
+use meld::agent::{AgentRole, AgentStorage, Capability, XdgAgentStorage};
 use meld::cli::{
-    AgentCommands, Commands, ContextCommands, ProviderCommands, RunContext, WorkflowCommands,
-    WorkspaceCommands,
+    AgentCommands, Commands, ContextCommands, GenerateArgs, ProviderCommands, RunContext,
+    WorkflowCommands, WorkspaceCommands,
 };
 use meld::config::AgentConfig;
 use meld::config::{xdg, ProviderConfig, ProviderType};
@@ -44,6 +46,23 @@ fn create_test_writer_agent(agent_id: &str) {
 }
 
 fn create_test_writer_agent_with_workflow(agent_id: &str, workflow_id: Option<&str>) {
+    write_test_writer_agent(agent_id, workflow_id, None);
+}
+
+fn write_test_writer_agent(
+    agent_id: &str,
+    workflow_id: Option<&str>,
+    default_provider: Option<&str>,
+) {
+    write_test_writer_agent_config(agent_id, workflow_id, default_provider, false);
+}
+
+fn write_test_writer_agent_config(
+    agent_id: &str,
+    workflow_id: Option<&str>,
+    default_provider: Option<&str>,
+    frame_type_per_node_type: bool,
+) {
     if workflow_id.is_some() {
         meld::init::initialize_workflows(false).unwrap();
     }
@@ -67,6 +86,9 @@ fn create_test_writer_agent_with_workflow(agent_id: &str, workflow_id: Option<&s
         system_prompt: Some("You are a test writer.".to_string()),
         system_prompt_path: None,
         workflow_id: workflow_id.map(ToString::to_string),
+        default_provider: default_provider.map(ToString::to_string),
+        frame_type_per_node_type,
+        capabilities: Vec::new(),
         metadata: metadata.into(),
     };
 
@@ -86,57 +108,163 @@ fn spawn_completion_server(
     let handle = thread::spawn(move || {
         for _ in 0..expected_requests {
             let (mut stream, _) = listener.accept().unwrap();
-            let mut buffer = Vec::new();
-            let mut chunk = [0u8; 4096];
-            let mut header_end = None;
-            loop {
-                let read = stream.read(&mut chunk).unwrap();
-                if read == 0 {
-                    break;
-                }
-                buffer.extend_from_slice(&chunk[..read]);
-                if header_end.is_none() {
-                    header_end = find_header_end(&buffer);
-                }
-                if let Some(end) = header_end {
-                    let headers = String::from_utf8_lossy(&buffer[..end]);
-                    let content_length = headers
-                        .lines()
-                        .find_map(|line| {
-                            let lower = line.to_ascii_lowercase();
-                            lower
-                                .strip_prefix("content-length:")
-                                .and_then(|value| value.trim().parse::<usize>().ok())
-                        })
-                        .unwrap_or(0);
-                    let body_start = end + 4;
-                    while buffer.len() < body_start + content_length {
-                        let read = stream.read(&mut chunk).unwrap();
-                        if read == 0 {
-                            break;
-                        }
-                        buffer.extend_from_slice(&chunk[..read]);
+            let Some(body) = read_request_body(&mut stream) else {
+                continue;
+            };
+            tx.send(body).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response_body.len(),
+                response_body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            stream.flush().unwrap();
+        }
+    });
+
+    (endpoint, rx, handle)
+}
+
+/// Answer every request with `response_body` over keep-alive connections until the test
+/// process exits. Returns the endpoint and the number of connections accepted so far.
+fn spawn_keep_alive_completion_server(
+    response_body: &str,
+) -> (String, mpsc::Receiver<String>, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let response_body = response_body.to_string();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::channel();
+
+    let accepted = Arc::clone(&connections);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            accepted.fetch_add(1, Ordering::SeqCst);
+            let tx = tx.clone();
+            let response_body = response_body.clone();
+            thread::spawn(move || {
+                while let Some(body) = read_request_body(&mut stream) {
+                    if tx.send(body).is_err() {
+                        break;
                     }
-                    let body =
-                        String::from_utf8_lossy(&buffer[body_start..body_start + content_length])
-                            .to_string();
-                    tx.send(body).unwrap();
                     let response = format!(
-                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
                         response_body.len(),
                         response_body
                     );
-                    stream.write_all(response.as_bytes()).unwrap();
-                    stream.flush().unwrap();
-                    break;
+                    if stream.write_all(response.as_bytes()).is_err() {
+                        break;
+                    }
+                    let _ = stream.flush();
                 }
-            }
+            });
+        }
+    });
+
+    (endpoint, rx, connections)
+}
+
+/// Serve one request whose response body is written as separate HTTP chunks, so the client
+/// sees the server-sent events arrive incrementally (and split across line boundaries).
+fn spawn_streaming_completion_server(
+    body_chunks: Vec<String>,
+) -> (String, mpsc::Receiver<String>, thread::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let body = read_request_body(&mut stream).unwrap();
+        tx.send(body).unwrap();
+        stream
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+            )
+            .unwrap();
+        stream.flush().unwrap();
+        for chunk in body_chunks {
+            write!(stream, "{:x}\r\n{}\r\n", chunk.len(), chunk).unwrap();
+            stream.flush().unwrap();
+            thread::sleep(Duration::from_millis(10));
         }
+        stream.write_all(b"0\r\n\r\n").unwrap();
+        stream.flush().unwrap();
     });
 
     (endpoint, rx, handle)
 }
 
+/// Answer every request with HTTP 500 until the test process exits.
+fn spawn_failing_completion_server() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let Some(body) = read_request_body(&mut stream) else {
+                continue;
+            };
+            if tx.send(body).is_err() {
+                break;
+            }
+            let body = r#"{"error":{"message":"upstream unavailable"}}"#;
+            let response = format!(
+                "HTTP/1.1 500 Internal Server Error\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+            let _ = stream.flush();
+        }
+    });
+
+    (endpoint, rx)
+}
+
+fn read_request_body(stream: &mut std::net::TcpStream) -> Option<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = stream.read(&mut chunk).unwrap();
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        let Some(end) = find_header_end(&buffer) else {
+            continue;
+        };
+        let headers = String::from_utf8_lossy(&buffer[..end]);
+        let content_length = headers
+            .lines()
+            .find_map(|line| {
+                let lower = line.to_ascii_lowercase();
+                lower
+                    .strip_prefix("content-length:")
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+            })
+            .unwrap_or(0);
+        let body_start = end + 4;
+        while buffer.len() < body_start + content_length {
+            let read = stream.read(&mut chunk).unwrap();
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        return Some(
+            String::from_utf8_lossy(&buffer[body_start..body_start + content_length]).to_string(),
+        );
+    }
+}
+
 fn find_header_end(buffer: &[u8]) -> Option<usize> {
     buffer.windows(4).position(|window| window == b"\r\n\r\n")
 }
@@ -150,7 +278,16 @@ fn scan_emits_session_boundary_events() {
         fs::write(workspace_root.join("a.txt"), "hello").unwrap();
 
         let cli = RunContext::new(workspace_root, None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let runtime = cli.progress_runtime();
         let sessions = runtime.list_sessions().unwrap();
@@ -176,6 +313,45 @@ fn scan_emits_session_boundary_events() {
     });
 }
 
+#[test]
+fn progress_file_mirrors_scan_events_as_json_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join("a.txt"), "hello").unwrap();
+        let progress_path = temp_dir.path().join("progress.jsonl");
+
+        let cli = RunContext::new(workspace_root, None)
+            .unwrap()
+            .progress_file(Some(progress_path.clone()));
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let contents = fs::read_to_string(&progress_path).unwrap();
+        let events: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line should be a JSON event"))
+            .collect();
+        let types: Vec<&str> = events
+            .iter()
+            .map(|event| event["type"].as_str().unwrap())
+            .collect();
+        assert!(types.contains(&"scan_started"));
+        assert!(types.contains(&"scan_completed"));
+        assert_eq!(types.first(), Some(&"session_started"));
+        assert_eq!(types.last(), Some(&"session_ended"));
+    });
+}
+
 #[test]
 fn emitted_event_timestamps_are_iso_8601_with_milliseconds() {
     let temp_dir = TempDir::new().unwrap();
@@ -185,7 +361,16 @@ fn emitted_event_timestamps_are_iso_8601_with_milliseconds() {
         fs::write(workspace_root.join("a.txt"), "hello").unwrap();
 
         let cli = RunContext::new(workspace_root, None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let runtime = cli.progress_runtime();
         let sessions = runtime.list_sessions().unwrap();
@@ -222,7 +407,16 @@ fn scan_emits_batched_progress_events_with_monotonic_counts() {
         }
 
         let cli = RunContext::new(workspace_root, None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let runtime = cli.progress_runtime();
         let sessions = runtime.list_sessions().unwrap();
@@ -280,9 +474,18 @@ fn failed_command_emits_session_end() {
         fs::write(&target, "hello").unwrap();
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(target),
                 path_positional: None,
@@ -293,8 +496,25 @@ fn failed_command_emits_session_end() {
                 provider_additional_json_file: None,
                 frame_type: None,
                 force: false,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
         assert!(result.is_err());
 
@@ -329,10 +549,19 @@ fn context_generate_plan_constructed_includes_path_field() {
         create_test_openai_provider("obs-provider", "gpt-4-test", "http://127.0.0.1:9");
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(target.clone()),
                 path_positional: None,
@@ -343,8 +572,25 @@ fn context_generate_plan_constructed_includes_path_field() {
                 provider_additional_json_file: None,
                 frame_type: Some("context-obs-agent".to_string()),
                 force: true,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
         assert!(result.is_err());
 
@@ -383,6 +629,83 @@ fn context_generate_plan_constructed_includes_path_field() {
     });
 }
 
+#[test]
+fn context_generate_plan_constructed_carries_requested_priority() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("a.txt");
+        fs::write(&target, "hello").unwrap();
+
+        create_test_writer_agent("obs-agent");
+        create_test_openai_provider("obs-provider", "gpt-4-test", "http://127.0.0.1:9");
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let result = cli.execute(&Commands::Context {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
+                node: None,
+                path: Some(target.clone()),
+                path_positional: None,
+                agent: Some("obs-agent".to_string()),
+                provider: Some("obs-provider".to_string()),
+                workflow_id: None,
+                provider_model: None,
+                provider_additional_json_file: None,
+                frame_type: Some("context-obs-agent".to_string()),
+                force: true,
+                only_missing: false,
+                only_stale: false,
+                no_recursive: false,
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "low".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
+        });
+        assert!(result.is_err());
+
+        let runtime = cli.progress_runtime();
+        let sessions = runtime.list_sessions().unwrap();
+        let session = sessions
+            .iter()
+            .find(|s| s.command == "context.generate")
+            .expect("context.generate session should exist");
+        let events = runtime.store().read_events(&session.session_id).unwrap();
+        let plan = events
+            .iter()
+            .find(|e| e.event_type == "plan_constructed")
+            .expect("plan_constructed should be emitted");
+
+        assert_eq!(
+            plan.data.get("priority").and_then(|v| v.as_str()),
+            Some("low")
+        );
+    });
+}
+
 #[test]
 fn context_generate_node_skipped_includes_path_field() {
     let temp_dir = TempDir::new().unwrap();
@@ -396,7 +719,16 @@ fn context_generate_node_skipped_includes_path_field() {
         create_test_openai_provider("skip-provider", "gpt-4-test", "http://127.0.0.1:9");
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let canonical_target = fs::canonicalize(&target).unwrap();
         let record = cli
@@ -428,7 +760,7 @@ fn context_generate_node_skipped_includes_path_field() {
             .unwrap();
 
         let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(target.clone()),
                 path_positional: None,
@@ -439,8 +771,25 @@ fn context_generate_node_skipped_includes_path_field() {
                 provider_additional_json_file: None,
                 frame_type: Some(frame_type),
                 force: false,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
         assert!(result.is_ok());
         assert!(result.unwrap().contains("Frame already exists"));
@@ -477,7 +826,16 @@ fn context_get_emits_summary_event() {
         fs::write(&target, "hello").unwrap();
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         cli.execute(&Commands::Context {
             command: ContextCommands::Get {
                 node: None,
@@ -491,6 +849,16 @@ fn context_get_emits_summary_event() {
                 format: "json".to_string(),
                 include_metadata: false,
                 include_deleted: false,
+                flatten_directory: false,
+                meta: Vec::new(),
+                count_only: false,
+                export: None,
+                budget_tokens: None,
+                with_ancestors: false,
+                size_only: false,
+                newest_per_agent: false,
+                resolve_basis: false,
+                include_provenance: false,
             },
         })
         .unwrap();
@@ -529,6 +897,12 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                     command: WorkspaceCommands::Status {
                         format: "text".to_string(),
                         breakdown: false,
+                        include_content_hash: false,
+                        check_heads: false,
+                        by_agent: false,
+                        check_staleness: false,
+                        watch: false,
+                        interval: 2,
                     },
                 },
                 "workspace.status",
@@ -538,6 +912,9 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                 Commands::Workspace {
                     command: WorkspaceCommands::Validate {
                         format: "text".to_string(),
+                        repair: false,
+                        dry_run: false,
+                        allow_data_loss: false,
                     },
                 },
                 "workspace.validate",
@@ -558,6 +935,8 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                 Commands::Workspace {
                     command: WorkspaceCommands::ListDeleted {
                         older_than: None,
+                        sort: None,
+                        reverse: false,
                         format: "text".to_string(),
                     },
                 },
@@ -571,7 +950,11 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                     agents_only: false,
                     providers_only: false,
                     breakdown: false,
+                    include_content_hash: false,
                     test_connectivity: false,
+                    watch: false,
+                    interval: 2,
+                    workspaces: Vec::new(),
                 },
                 "status",
                 "status_summary",
@@ -582,6 +965,7 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                     command: AgentCommands::List {
                         format: "text".to_string(),
                         role: None,
+                        detailed: false,
                     },
                 },
                 "agent.list",
@@ -592,6 +976,7 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                     command: ProviderCommands::List {
                         format: "text".to_string(),
                         type_filter: None,
+                        show_latency: false,
                     },
                 },
                 "provider.list",
@@ -601,6 +986,9 @@ fn command_families_emit_typed_summaries_with_command_summary() {
                 Commands::Init {
                     force: false,
                     list: true,
+                    check: false,
+                    template: None,
+                    list_templates: false,
                 },
                 "init",
                 "init_summary",
@@ -665,7 +1053,16 @@ fn workflow_execute_emits_lineage_and_provider_events() {
         create_test_openai_provider("workflow-obs-provider", "gpt-4-test", "http://127.0.0.1:9");
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let result = cli.execute(&Commands::Workflow {
             command: WorkflowCommands::Execute {
@@ -727,10 +1124,19 @@ fn context_generate_with_workflow_agent_uses_context_plan_levels() {
         create_test_openai_provider("workflow-plan-provider", "gpt-4-test", "http://127.0.0.1:9");
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(target.clone()),
                 path_positional: None,
@@ -741,8 +1147,25 @@ fn context_generate_with_workflow_agent_uses_context_plan_levels() {
                 provider_additional_json_file: None,
                 frame_type: Some("context-workflow-plan-agent".to_string()),
                 force: true,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
         assert!(result.is_err());
 
@@ -818,11 +1241,20 @@ fn context_generate_recursive_completes_levels_bottom_up() {
         create_test_openai_provider("bottom-up-provider", "gpt-4-test", &endpoint);
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let output = cli
             .execute(&Commands::Context {
-                command: ContextCommands::Generate {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
                     node: None,
                     path: Some(target.clone()),
                     path_positional: None,
@@ -833,8 +1265,25 @@ fn context_generate_recursive_completes_levels_bottom_up() {
                     provider_additional_json_file: None,
                     frame_type: Some("context-bottom-up-agent".to_string()),
                     force: true,
+                    only_missing: false,
+                    only_stale: false,
                     no_recursive: false,
-                },
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
             })
             .unwrap();
 
@@ -925,7 +1374,16 @@ fn workflow_force_generate_tombstones_stale_final_head_and_emits_reset_event() {
         );
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let node_id = resolve_workspace_node_id(
             cli.api(),
@@ -957,7 +1415,7 @@ fn workflow_force_generate_tombstones_stale_final_head_and_emits_reset_event() {
             .unwrap();
 
         let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(target.clone()),
                 path_positional: None,
@@ -968,8 +1426,25 @@ fn workflow_force_generate_tombstones_stale_final_head_and_emits_reset_event() {
                 provider_additional_json_file: None,
                 frame_type: Some(frame_type.clone()),
                 force: true,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
         assert!(result.is_err());
         assert_eq!(cli.api().get_head(&node_id, &frame_type).unwrap(), None);
@@ -1011,7 +1486,16 @@ fn single_shot_force_generate_replaces_stale_attested_head() {
         create_test_openai_provider("force-single-provider", "gpt-4-test", &endpoint);
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let node_id = resolve_workspace_node_id(
             cli.api(),
@@ -1044,7 +1528,7 @@ fn single_shot_force_generate_replaces_stale_attested_head() {
 
         let output = cli
             .execute(&Commands::Context {
-                command: ContextCommands::Generate {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
                     node: None,
                     path: Some(target.clone()),
                     path_positional: None,
@@ -1055,8 +1539,25 @@ fn single_shot_force_generate_replaces_stale_attested_head() {
                     provider_additional_json_file: None,
                     frame_type: Some(frame_type.clone()),
                     force: true,
+                    only_missing: false,
+                    only_stale: false,
                     no_recursive: false,
-                },
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
             })
             .unwrap();
 
@@ -1072,98 +1573,484 @@ fn single_shot_force_generate_replaces_stale_attested_head() {
 }
 
 #[test]
-fn context_regenerate_emits_context_generation_summary() {
+fn context_generate_prompt_file_replaces_agent_user_prompt() {
     let temp_dir = TempDir::new().unwrap();
     with_xdg_env(&temp_dir, || {
         let workspace_root = temp_dir.path().join("workspace");
-        let target = workspace_root.join("workflow");
-        fs::create_dir_all(target.join("record_contracts")).unwrap();
-        fs::write(target.join("record_contracts").join("a.md"), "a").unwrap();
-        fs::write(target.join("commands.md"), "c").unwrap();
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("doc.md");
+        fs::write(&target, "# hello").unwrap();
+        let prompt_path = temp_dir.path().join("prompt.txt");
+        fs::write(&prompt_path, "Custom audit prompt for {path}").unwrap();
 
-        create_test_writer_agent_with_workflow(
-            "workflow-regenerate-agent",
-            Some("docs_writer_thread_v1"),
-        );
-        create_test_openai_provider(
-            "workflow-regenerate-provider",
-            "gpt-4-test",
-            "http://127.0.0.1:9",
-        );
+        create_test_writer_agent("prompt-override-agent");
+        let response_body = r#"{"id":"test","object":"chat.completion","created":0,"model":"gpt-4-test","choices":[{"index":0,"message":{"role":"assistant","content":"audited"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let (endpoint, rx, handle) = spawn_completion_server(response_body, 2);
+        create_test_openai_provider("prompt-override-provider", "gpt-4-test", &endpoint);
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
-        let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Regenerate {
-                node: None,
-                path: Some(target.clone()),
-                path_positional: None,
-                agent: Some("workflow-regenerate-agent".to_string()),
-                provider: Some("workflow-regenerate-provider".to_string()),
-                workflow_id: None,
-                provider_model: None,
-                provider_additional_json_file: None,
-                frame_type: Some("context-workflow-regenerate-agent".to_string()),
-                recursive: true,
-            },
-        });
-        assert!(result.is_err());
+        let generate = || {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("prompt-override-agent".to_string()),
+                    provider: Some("prompt-override-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: Some(prompt_path.clone()),
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap()
+        };
+        let output = generate();
 
-        let runtime = cli.progress_runtime();
-        let sessions = runtime.list_sessions().unwrap();
-        let session = sessions
-            .iter()
-            .find(|s| s.command == "context.regenerate")
-            .expect("context.regenerate session should exist");
-        let events = runtime.store().read_events(&session.session_id).unwrap();
+        assert!(output.contains("Generation completed: generated=1, failed=0"));
+        let request_body = rx.recv_timeout(Duration::from_secs(2)).unwrap();
 
-        let typed_idx = events
-            .iter()
-            .position(|e| e.event_type == "context_generation_summary")
-            .expect("context_generation_summary should be emitted");
-        let generic_idx = events
-            .iter()
-            .position(|e| e.event_type == "command_summary")
-            .expect("command_summary should be emitted");
-        let ended_idx = events
-            .iter()
-            .position(|e| e.event_type == "session_ended")
-            .expect("session_ended should be emitted");
+        assert!(request_body.contains("Custom audit prompt for"));
+        assert!(request_body.contains("doc.md"));
+        assert!(!request_body.contains("Custom audit prompt for {path}"));
+        assert!(!request_body.contains("Summarize"));
+        assert!(request_body.contains("You are a test writer."));
 
-        assert!(typed_idx < ended_idx);
-        assert!(generic_idx < ended_idx);
+        // A head now exists; the override still regenerates without --force.
+        let output = generate();
+        assert!(
+            output.contains("Generation completed: generated=1, failed=0"),
+            "{}",
+            output
+        );
+        let request_body = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert!(request_body.contains("Custom audit prompt for"));
+        handle.join().unwrap();
     });
 }
 
 #[test]
-fn command_summary_success_is_metric_focused_and_bounded() {
+fn context_generate_prompt_file_rejects_recursive_target() {
     let temp_dir = TempDir::new().unwrap();
     with_xdg_env(&temp_dir, || {
         let workspace_root = temp_dir.path().join("workspace");
-        fs::create_dir_all(&workspace_root).unwrap();
-        fs::write(workspace_root.join("a.txt"), "hello").unwrap();
+        let target = workspace_root.join("src");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("a.md"), "a").unwrap();
+        let prompt_path = temp_dir.path().join("prompt.txt");
+        fs::write(&prompt_path, "Custom prompt for {path}").unwrap();
 
-        let cli = RunContext::new(workspace_root, None).unwrap();
-        cli.execute(&Commands::Status {
+        create_test_writer_agent("prompt-recursive-agent");
+        create_test_openai_provider(
+            "prompt-recursive-provider",
+            "gpt-4-test",
+            "http://127.0.0.1:9",
+        );
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
             format: "text".to_string(),
-            workspace_only: false,
-            agents_only: false,
-            providers_only: false,
-            breakdown: false,
-            test_connectivity: false,
         })
         .unwrap();
 
-        let runtime = cli.progress_runtime();
-        let sessions = runtime.list_sessions().unwrap();
-        let session = sessions
-            .iter()
-            .find(|s| s.command == "status")
-            .expect("status session should exist");
-        let events = runtime.store().read_events(&session.session_id).unwrap();
-        let summary = events
-            .iter()
+        let err = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("prompt-recursive-agent".to_string()),
+                    provider: Some("prompt-recursive-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: Some(prompt_path.clone()),
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap_err();
+
+        assert!(err.to_string().contains("single-node target"));
+    });
+}
+
+#[test]
+fn context_generate_stdin_prompt_replaces_agent_user_prompt() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let docs = workspace_root.join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        let target = docs.join("doc.md");
+        fs::write(&target, "# hello").unwrap();
+
+        create_test_writer_agent("stdin-prompt-agent");
+        let response_body = r#"{"id":"test","object":"chat.completion","created":0,"model":"gpt-4-test","choices":[{"index":0,"message":{"role":"assistant","content":"piped"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let (endpoint, rx, handle) = spawn_completion_server(response_body, 1);
+        create_test_openai_provider("stdin-prompt-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        // The prompt is read from the process stdin, so run the binary.
+        drop(cli);
+        let generate = |path: &std::path::Path, prompt: &str| {
+            let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_meld"))
+                .arg("--workspace")
+                .arg(&workspace_root)
+                .args(["context", "generate", "--path"])
+                .arg(path)
+                .args([
+                    "--agent",
+                    "stdin-prompt-agent",
+                    "--provider",
+                    "stdin-prompt-provider",
+                    "--stdin-prompt",
+                ])
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()
+                .unwrap();
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(prompt.as_bytes())
+                .unwrap();
+            child.wait_with_output().unwrap()
+        };
+
+        let output = generate(&docs, "Piped prompt for {path}");
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("single-node target"));
+
+        let output = generate(&target, "  \n");
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Prompt override is empty"));
+
+        let output = generate(&target, "Piped prompt for {path}");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(String::from_utf8_lossy(&output.stdout)
+            .contains("Generation completed: generated=1, failed=0"));
+        let request_body = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert!(request_body.contains("Piped prompt for"));
+        assert!(request_body.contains("doc.md"));
+        assert!(!request_body.contains("Piped prompt for {path}"));
+        assert!(!request_body.contains("Summarize"));
+        assert!(request_body.contains("You are a test writer."));
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn context_generate_uses_agent_default_provider_when_provider_omitted() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("doc.md");
+        fs::write(&target, "# hello").unwrap();
+
+        let response_body = r#"{"id":"test","object":"chat.completion","created":0,"model":"gpt-4-test","choices":[{"index":0,"message":{"role":"assistant","content":"from default provider"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let (endpoint, rx, handle) = spawn_completion_server(response_body, 1);
+        create_test_openai_provider("default-provider", "gpt-4-test", &endpoint);
+        write_test_writer_agent("default-provider-agent", None, Some("default-provider"));
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("default-provider-agent".to_string()),
+                    provider: None,
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+
+        assert!(output.contains("Generation completed: generated=1, failed=0"));
+        let _request_body = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn context_generate_explicit_provider_overrides_agent_default() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("doc.md");
+        fs::write(&target, "# hello").unwrap();
+
+        let response_body = r#"{"id":"test","object":"chat.completion","created":0,"model":"gpt-4-test","choices":[{"index":0,"message":{"role":"assistant","content":"from explicit provider"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let (endpoint, rx, handle) = spawn_completion_server(response_body, 1);
+        create_test_openai_provider("explicit-provider", "gpt-4-test", &endpoint);
+        // The default provider points at a closed port; using it would fail the run.
+        create_test_openai_provider("unreachable-default", "gpt-4-test", "http://127.0.0.1:9");
+        write_test_writer_agent("override-provider-agent", None, Some("unreachable-default"));
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("override-provider-agent".to_string()),
+                    provider: Some("explicit-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+
+        assert!(output.contains("Generation completed: generated=1, failed=0"));
+        let _request_body = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn context_regenerate_emits_context_generation_summary() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("workflow");
+        fs::create_dir_all(target.join("record_contracts")).unwrap();
+        fs::write(target.join("record_contracts").join("a.md"), "a").unwrap();
+        fs::write(target.join("commands.md"), "c").unwrap();
+
+        create_test_writer_agent_with_workflow(
+            "workflow-regenerate-agent",
+            Some("docs_writer_thread_v1"),
+        );
+        create_test_openai_provider(
+            "workflow-regenerate-provider",
+            "gpt-4-test",
+            "http://127.0.0.1:9",
+        );
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let result = cli.execute(&Commands::Context {
+            command: ContextCommands::Regenerate {
+                node: None,
+                path: Some(target.clone()),
+                path_positional: None,
+                agent: Some("workflow-regenerate-agent".to_string()),
+                provider: Some("workflow-regenerate-provider".to_string()),
+                workflow_id: None,
+                provider_model: None,
+                provider_additional_json_file: None,
+                frame_type: Some("context-workflow-regenerate-agent".to_string()),
+                recursive: true,
+            },
+        });
+        assert!(result.is_err());
+
+        let runtime = cli.progress_runtime();
+        let sessions = runtime.list_sessions().unwrap();
+        let session = sessions
+            .iter()
+            .find(|s| s.command == "context.regenerate")
+            .expect("context.regenerate session should exist");
+        let events = runtime.store().read_events(&session.session_id).unwrap();
+
+        let typed_idx = events
+            .iter()
+            .position(|e| e.event_type == "context_generation_summary")
+            .expect("context_generation_summary should be emitted");
+        let generic_idx = events
+            .iter()
+            .position(|e| e.event_type == "command_summary")
+            .expect("command_summary should be emitted");
+        let ended_idx = events
+            .iter()
+            .position(|e| e.event_type == "session_ended")
+            .expect("session_ended should be emitted");
+
+        assert!(typed_idx < ended_idx);
+        assert!(generic_idx < ended_idx);
+    });
+}
+
+#[test]
+fn command_summary_success_is_metric_focused_and_bounded() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join("a.txt"), "hello").unwrap();
+
+        let cli = RunContext::new(workspace_root, None).unwrap();
+        cli.execute(&Commands::Status {
+            format: "text".to_string(),
+            workspace_only: false,
+            agents_only: false,
+            providers_only: false,
+            breakdown: false,
+            include_content_hash: false,
+            test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
+        })
+        .unwrap();
+
+        let runtime = cli.progress_runtime();
+        let sessions = runtime.list_sessions().unwrap();
+        let session = sessions
+            .iter()
+            .find(|s| s.command == "status")
+            .expect("status session should exist");
+        let events = runtime.store().read_events(&session.session_id).unwrap();
+        let summary = events
+            .iter()
             .find(|e| e.event_type == "command_summary")
             .expect("command_summary should be emitted");
 
@@ -1193,11 +2080,20 @@ fn command_summary_failure_message_is_bounded() {
         create_test_writer_agent("summary-agent");
 
         let cli = RunContext::new(workspace_root.clone(), None).unwrap();
-        cli.execute(&Commands::Scan { force: true }).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let provider_name = "p".repeat(700);
         let result = cli.execute(&Commands::Context {
-            command: ContextCommands::Generate {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
                 node: None,
                 path: Some(target),
                 path_positional: None,
@@ -1208,8 +2104,25 @@ fn command_summary_failure_message_is_bounded() {
                 provider_additional_json_file: None,
                 frame_type: None,
                 force: false,
+                only_missing: false,
+                only_stale: false,
                 no_recursive: false,
-            },
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: None,
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
         });
         assert!(result.is_err());
 
@@ -1262,6 +2175,7 @@ fn provider_test_failure_emits_provider_request_failed_event() {
                 provider_name: "provider-test-fail".to_string(),
                 model: Some("gpt-4-test".to_string()),
                 timeout: 1,
+                prompt: None,
             },
         });
         assert!(result.is_ok());
@@ -1355,3 +2269,1636 @@ fn pruning_removes_only_old_completed_sessions() {
         assert!(sessions.iter().any(|s| s.session_id == active));
     });
 }
+
+fn generate_directory_and_read_head_types(
+    agent_id: &str,
+    frame_type_per_node_type: bool,
+) -> (Vec<String>, Vec<String>, String) {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let docs_dir = workspace_root.join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        let file_path = docs_dir.join("a.md");
+        fs::write(&file_path, "# a").unwrap();
+
+        let response_body = r#"{"id":"test","object":"chat.completion","created":0,"model":"gpt-4-test","choices":[{"index":0,"message":{"role":"assistant","content":"summary"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let (endpoint, rx, handle) = spawn_completion_server(response_body, 2);
+        create_test_openai_provider("test-provider", "gpt-4-test", &endpoint);
+        write_test_writer_agent_config(agent_id, None, None, frame_type_per_node_type);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(docs_dir.clone()),
+                    path_positional: None,
+                    agent: Some(agent_id.to_string()),
+                    provider: Some("test-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+        assert!(output.contains("Generation completed: generated=2, failed=0"));
+        handle.join().unwrap();
+
+        let head_types = |path: &std::path::Path| {
+            let node_id =
+                resolve_workspace_node_id(cli.api(), &workspace_root, Some(path), None, false)
+                    .unwrap();
+            let mut types: Vec<String> = [
+                format!("context-{}", agent_id),
+                format!("context-{}-file", agent_id),
+                format!("context-{}-dir", agent_id),
+            ]
+            .into_iter()
+            .filter(|frame_type| cli.api().get_head(&node_id, frame_type).unwrap().is_some())
+            .collect();
+            types.sort();
+            types
+        };
+        let directory_request = rx.try_iter().last().unwrap();
+        (
+            head_types(&file_path),
+            head_types(&docs_dir),
+            directory_request,
+        )
+    })
+}
+
+#[test]
+fn context_generate_suffixes_default_frame_type_by_node_type_when_enabled() {
+    let (file_types, dir_types, directory_request) =
+        generate_directory_and_read_head_types("split-agent", true);
+    assert_eq!(file_types, vec!["context-split-agent-file".to_string()]);
+    assert_eq!(dir_types, vec!["context-split-agent-dir".to_string()]);
+    assert!(directory_request.contains("Type: File"));
+}
+
+#[test]
+fn context_generate_keeps_flat_default_frame_type_when_disabled() {
+    let (file_types, dir_types, directory_request) =
+        generate_directory_and_read_head_types("flat-agent", false);
+    assert_eq!(file_types, vec!["context-flat-agent".to_string()]);
+    assert_eq!(dir_types, vec!["context-flat-agent".to_string()]);
+    assert!(directory_request.contains("Type: File"));
+}
+
+#[test]
+fn context_generate_stream_assembles_frame_from_streamed_chunks() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("doc.md");
+        fs::write(&target, "# hello").unwrap();
+
+        let deltas = ["Stream", "ed sum", "mary ", "of doc.md"];
+        let mut sse_body = String::from(": keep-alive\n\n");
+        for delta in deltas {
+            sse_body.push_str(&format!(
+                "data: {{\"id\":\"test\",\"object\":\"chat.completion.chunk\",\"choices\":[{{\"index\":0,\"delta\":{{\"content\":\"{}\"}}}}]}}\n\n",
+                delta
+            ));
+        }
+        sse_body.push_str(
+            "data: {\"id\":\"test\",\"object\":\"chat.completion.chunk\",\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"length\"}]}\n\n",
+        );
+        sse_body.push_str(
+            "data: {\"id\":\"test\",\"object\":\"chat.completion.chunk\",\"choices\":[],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":4,\"total_tokens\":16}}\n\n",
+        );
+        sse_body.push_str("data: [DONE]\n\n");
+        // Cut the body into pieces that straddle event and line boundaries.
+        let body_chunks: Vec<String> = sse_body
+            .as_bytes()
+            .chunks(37)
+            .map(|piece| String::from_utf8(piece.to_vec()).unwrap())
+            .collect();
+        let (endpoint, rx, handle) = spawn_streaming_completion_server(body_chunks);
+        create_test_openai_provider("stream-provider", "gpt-4-test", &endpoint);
+        create_test_writer_agent("stream-agent");
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("stream-agent".to_string()),
+                    provider: Some("stream-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: true,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+        assert!(output.contains("Generation completed: generated=1, failed=0"));
+
+        let request_body = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        handle.join().unwrap();
+        let request: serde_json::Value = serde_json::from_str(&request_body).unwrap();
+        assert_eq!(request["stream"], true);
+        assert_eq!(request["stream_options"]["include_usage"], true);
+
+        let node_id = resolve_workspace_node_id(
+            cli.api(),
+            &workspace_root,
+            Some(target.as_path()),
+            None,
+            false,
+        )
+        .unwrap();
+        let head = cli
+            .api()
+            .get_head(&node_id, "context-stream-agent")
+            .unwrap()
+            .unwrap();
+        let frame = cli.api().frame_storage().get(&head).unwrap().unwrap();
+        assert_eq!(frame.metadata_value("truncated"), Some("true"));
+        assert_eq!(String::from_utf8(frame.content).unwrap(), deltas.concat());
+
+        // Workers publish the text as progress events; the command echoes it.
+        let runtime = cli.progress_runtime();
+        let session = runtime
+            .list_sessions()
+            .unwrap()
+            .into_iter()
+            .find(|s| s.command == "context.generate")
+            .expect("generate session should exist");
+        let streamed: String = runtime
+            .store()
+            .read_events(&session.session_id)
+            .unwrap()
+            .iter()
+            .filter(|e| e.event_type == "provider_stream_delta")
+            .filter_map(|e| e.data["text"].as_str().map(str::to_string))
+            .collect();
+        assert_eq!(streamed, deltas.concat());
+    });
+}
+
+#[test]
+fn context_generate_stops_retrying_once_total_retry_budget_is_spent() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("flaky.md");
+        fs::write(&target, "# flaky").unwrap();
+
+        let (endpoint, rx) = spawn_failing_completion_server();
+        create_test_openai_provider("flaky-provider", "gpt-4-test", &endpoint);
+        create_test_writer_agent("flaky-agent");
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let result = cli.execute(&Commands::Context {
+            command: ContextCommands::Generate(Box::new(GenerateArgs {
+                node: None,
+                path: Some(target),
+                path_positional: None,
+                agent: Some("flaky-agent".to_string()),
+                provider: Some("flaky-provider".to_string()),
+                workflow_id: None,
+                provider_model: None,
+                provider_additional_json_file: None,
+                frame_type: None,
+                force: false,
+                only_missing: false,
+                only_stale: false,
+                no_recursive: false,
+                continue_on_error: false,
+                stdin_prompt: false,
+                prompt_file: None,
+                stream: false,
+                max_total_retries: Some(1),
+                include_binary: false,
+                prompt_var: Vec::new(),
+                max_depth: None,
+                after: Vec::new(),
+                priority: "urgent".to_string(),
+                preserve_mtime: false,
+                record_provenance: false,
+                timeout: None,
+                on_conflict: "last-wins".to_string(),
+                exclude: vec![],
+            })),
+        });
+        assert!(result.is_err());
+
+        // One initial attempt plus the single budgeted retry, not the per-request limit of 3.
+        assert_eq!(rx.try_iter().count(), 2);
+    });
+}
+
+#[test]
+fn context_generate_skips_binary_files_unless_included() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("notes.md"), "# notes").unwrap();
+        fs::write(target.join("logo.bin"), b"\x7fELF\x02\x01\x01\0\0\0\0").unwrap();
+
+        create_test_writer_agent("binary-agent");
+        let response_body = r##"{"id":"test","object":"chat.completion","created":0,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"generated"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"##;
+        let (endpoint, body_rx, handle) = spawn_completion_server(response_body, 5);
+        create_test_openai_provider("binary-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let generate = |include_binary: bool| {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("binary-agent".to_string()),
+                    provider: Some("binary-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap()
+        };
+
+        // The text file and the directory are planned; the binary file is not.
+        let output = generate(false);
+        assert!(output.contains("generated=2, failed=0"));
+        let prompts: Vec<String> = (0..2)
+            .map(|_| body_rx.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect();
+        assert!(prompts.iter().any(|body| body.contains("notes.md")));
+
+        let runtime = cli.progress_runtime();
+        let sessions = runtime.list_sessions().unwrap();
+        assert_eq!(
+            sessions
+                .iter()
+                .filter(|s| s.command == "context.generate")
+                .count(),
+            1
+        );
+        let session = sessions
+            .iter()
+            .find(|s| s.command == "context.generate")
+            .expect("context.generate session should exist");
+        let events = runtime.store().read_events(&session.session_id).unwrap();
+        let skipped = events
+            .iter()
+            .find(|e| {
+                e.event_type == "node_skipped"
+                    && e.data.get("reason").and_then(|v| v.as_str()) == Some("binary")
+            })
+            .expect("binary node_skipped should be emitted");
+        assert!(skipped
+            .data
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap()
+            .ends_with("logo.bin"));
+
+        let output = generate(true);
+        assert!(output.contains("generated=3, failed=0"));
+        for _ in 0..3 {
+            let _ = body_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn context_generate_summary_only_prints_one_counts_line() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("notes.md"), "# notes").unwrap();
+
+        create_test_writer_agent("summary-agent");
+        let response_body = r##"{"id":"test","object":"chat.completion","created":0,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"generated"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"##;
+        let (endpoint, body_rx, handle) = spawn_completion_server(response_body, 2);
+        create_test_openai_provider("summary-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let cli = cli.summary_only(Some("text".to_string()));
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("summary-agent".to_string()),
+                    provider: Some("summary-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+
+        // docs and docs/notes.md.
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "{}", output);
+        assert!(
+            lines[0].starts_with(
+                "command=context.generate nodes_planned=2 frames_generated=2 failed=0 duration_ms="
+            ),
+            "{}",
+            lines[0]
+        );
+        for _ in 0..2 {
+            let _ = body_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn context_generate_max_depth_plans_target_and_direct_children_only() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(target.join("guides")).unwrap();
+        fs::write(target.join("notes.md"), "# notes").unwrap();
+        fs::write(target.join("guides").join("setup.md"), "# setup").unwrap();
+
+        create_test_writer_agent("depth-agent");
+        let response_body = r##"{"id":"test","object":"chat.completion","created":0,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"generated"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"##;
+        let (endpoint, body_rx, handle) = spawn_completion_server(response_body, 3);
+        create_test_openai_provider("depth-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("depth-agent".to_string()),
+                    provider: Some("depth-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: Some(1),
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+
+        // docs, docs/notes.md and docs/guides are planned; docs/guides/setup.md is not.
+        assert!(output.contains("generated=3, failed=0"));
+        for _ in 0..3 {
+            let _ = body_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        handle.join().unwrap();
+
+        let runtime = cli.progress_runtime();
+        let session = runtime
+            .list_sessions()
+            .unwrap()
+            .into_iter()
+            .find(|s| s.command == "context.generate")
+            .expect("context.generate session should exist");
+        let events = runtime.store().read_events(&session.session_id).unwrap();
+        let plan = events
+            .iter()
+            .find(|e| e.event_type == "plan_constructed")
+            .expect("plan_constructed should be emitted");
+        assert_eq!(plan.data.get("max_depth").and_then(|v| v.as_u64()), Some(1));
+        assert_eq!(
+            plan.data.get("total_nodes").and_then(|v| v.as_u64()),
+            Some(3)
+        );
+        assert_eq!(
+            plan.data.get("total_levels").and_then(|v| v.as_u64()),
+            Some(2)
+        );
+    });
+}
+
+#[test]
+fn context_generate_reuses_provider_connections_across_requests() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        const FILES: usize = 24;
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        for i in 0..FILES {
+            fs::write(
+                target.join(format!("note-{:02}.md", i)),
+                format!("# note {}", i),
+            )
+            .unwrap();
+        }
+
+        create_test_writer_agent("pool-agent");
+        let response_body = r##"{"id":"test","object":"chat.completion","created":0,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"generated"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"##;
+        let (endpoint, body_rx, connections) = spawn_keep_alive_completion_server(response_body);
+        create_test_openai_provider("pool-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("pool-agent".to_string()),
+                    provider: Some("pool-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+        let elapsed = started.elapsed();
+
+        // One frame per file plus the directory.
+        let requests = FILES + 1;
+        assert!(
+            output.contains(&format!("generated={}, failed=0", requests)),
+            "{}",
+            output
+        );
+        assert_eq!(body_rx.try_iter().count(), requests);
+
+        // Workers share pooled connections instead of opening one per request; the queue runs
+        // at most `max_concurrent_per_agent` (3) requests at once.
+        let opened = connections.load(Ordering::SeqCst);
+        assert!(
+            opened <= 3,
+            "{} requests opened {} connections in {:?}",
+            requests,
+            opened,
+            elapsed
+        );
+    });
+}
+
+/// Generate the whole workspace (`src/lib.rs`, `tests/a.rs`, `tests/b.rs`, `README.md`) with
+/// `--exclude` and return the command output and the `plan_constructed` and `nodes_excluded`
+/// event data.
+fn generate_workspace_with_exclude(
+    exclude: &[&str],
+    expected_requests: usize,
+) -> (String, serde_json::Value, serde_json::Value) {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src")).unwrap();
+        fs::create_dir_all(workspace_root.join("tests")).unwrap();
+        fs::write(workspace_root.join("src").join("lib.rs"), "pub fn lib() {}").unwrap();
+        fs::write(
+            workspace_root.join("tests").join("a.rs"),
+            "#[test] fn a() {}",
+        )
+        .unwrap();
+        fs::write(
+            workspace_root.join("tests").join("b.rs"),
+            "#[test] fn b() {}",
+        )
+        .unwrap();
+        fs::write(workspace_root.join("README.md"), "# readme").unwrap();
+
+        create_test_writer_agent("exclude-agent");
+        let response_body = r##"{"id":"test","object":"chat.completion","created":0,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"generated"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"##;
+        let (endpoint, body_rx, handle) = spawn_completion_server(response_body, expected_requests);
+        create_test_openai_provider("exclude-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(workspace_root.clone()),
+                    path_positional: None,
+                    agent: Some("exclude-agent".to_string()),
+                    provider: Some("exclude-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: exclude.iter().map(|p| p.to_string()).collect(),
+                })),
+            })
+            .unwrap();
+        for _ in 0..expected_requests {
+            let _ = body_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        handle.join().unwrap();
+
+        let runtime = cli.progress_runtime();
+        let session = runtime
+            .list_sessions()
+            .unwrap()
+            .into_iter()
+            .find(|s| s.command == "context.generate")
+            .expect("context.generate session should exist");
+        let events = runtime.store().read_events(&session.session_id).unwrap();
+        let event_data = |event_type: &str| {
+            events
+                .iter()
+                .find(|e| e.event_type == event_type)
+                .unwrap_or_else(|| panic!("{} should be emitted", event_type))
+                .data
+                .clone()
+        };
+        (
+            output,
+            event_data("plan_constructed"),
+            event_data("nodes_excluded"),
+        )
+    })
+}
+
+#[test]
+fn context_generate_exclude_glob_omits_matching_files_but_keeps_siblings() {
+    let (output, plan, excluded) = generate_workspace_with_exclude(&["tests/*"], 5);
+
+    // Root, src, src/lib.rs, README.md and the tests directory itself are planned.
+    assert!(output.contains("generated=5, failed=0"), "{}", output);
+    assert_eq!(plan.get("total_nodes").and_then(|v| v.as_u64()), Some(5));
+    assert_eq!(
+        excluded.get("excluded_count").and_then(|v| v.as_u64()),
+        Some(2)
+    );
+}
+
+#[test]
+fn context_generate_exclude_directory_prunes_its_subtree() {
+    let (output, plan, excluded) = generate_workspace_with_exclude(&["tests"], 4);
+
+    assert!(output.contains("generated=4, failed=0"), "{}", output);
+    assert_eq!(plan.get("total_nodes").and_then(|v| v.as_u64()), Some(4));
+    assert_eq!(
+        excluded.get("excluded_count").and_then(|v| v.as_u64()),
+        Some(3)
+    );
+}
+
+fn grant_agent_capabilities(agent_id: &str, capabilities: Vec<Capability>) {
+    let path = XdgAgentStorage::new().path_for(agent_id).unwrap();
+    let mut config: AgentConfig = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    config.capabilities = capabilities;
+    fs::write(path, toml::to_string_pretty(&config).unwrap()).unwrap();
+}
+
+#[test]
+fn context_generate_directory_requires_synthesize_when_policy_enabled() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("notes.md"), "# notes").unwrap();
+
+        create_test_writer_agent("plain-writer");
+        create_test_writer_agent("synth-writer");
+        grant_agent_capabilities("synth-writer", vec![Capability::Synthesize]);
+        let response_body = r##"{"id":"test","object":"chat.completion","created":0,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"generated"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"##;
+        let (endpoint, body_rx, handle) = spawn_completion_server(response_body, 2);
+        create_test_openai_provider("synth-provider", "gpt-4-test", &endpoint);
+
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.api().set_require_synthesize(true);
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let generate = |agent_id: &str| {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some(agent_id.to_string()),
+                    provider: Some("synth-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+        };
+
+        let err = generate("plain-writer").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("lacks the Synthesize capability"));
+        assert!(message.contains("synth-writer"));
+        assert!(body_rx.try_recv().is_err());
+
+        let output = generate("synth-writer").unwrap();
+        assert!(output.contains("generated=2, failed=0"));
+        for _ in 0..2 {
+            let _ = body_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn context_generate_with_mock_provider_stores_deterministic_frames() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("a.md"), "# a").unwrap();
+        fs::write(target.join("b.md"), "# b").unwrap();
+
+        create_test_writer_agent("mock-agent");
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Provider {
+            command: ProviderCommands::Create {
+                provider_name: "mock-provider".to_string(),
+                type_: Some("mock".to_string()),
+                model: Some("echo".to_string()),
+                endpoint: None,
+                api_key: None,
+                interactive: false,
+                non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
+            },
+        })
+        .unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let generate = || {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("mock-agent".to_string()),
+                    provider: Some("mock-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap()
+        };
+        let head_content = |name: &str| {
+            let path = target.join(name);
+            let node_id = resolve_workspace_node_id(
+                cli.api(),
+                &workspace_root,
+                Some(path.as_path()),
+                None,
+                false,
+            )
+            .unwrap();
+            let head = cli
+                .api()
+                .get_head(&node_id, "context-mock-agent")
+                .unwrap()
+                .unwrap();
+            let frame = cli.api().frame_storage().get(&head).unwrap().unwrap();
+            frame.text_content().unwrap()
+        };
+
+        let output = generate();
+        assert!(output.contains("generated=3, failed=0"), "{}", output);
+        let first_a = head_content("a.md");
+        let first_b = head_content("b.md");
+        assert!(first_a.starts_with("mock(echo) "), "{}", first_a);
+        assert_eq!(first_a.len(), "mock(echo) ".len() + 64);
+        assert_ne!(first_a, first_b);
+
+        let output = generate();
+        assert!(output.contains("generated=3, failed=0"), "{}", output);
+        assert_eq!(head_content("a.md"), first_a);
+        assert_eq!(head_content("b.md"), first_b);
+    });
+}
+
+#[test]
+fn context_generate_flags_truncated_mock_response_or_retries_when_configured() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let file_path = workspace_root.join("a.md");
+        fs::write(&file_path, "# a").unwrap();
+
+        create_test_writer_agent("mock-agent");
+        let write_mock_provider = |truncation_retry_max_tokens: Option<u32>| {
+            let providers_dir = xdg::providers_dir().unwrap();
+            fs::create_dir_all(&providers_dir).unwrap();
+            let provider_config = ProviderConfig {
+                provider_name: Some("mock-provider".to_string()),
+                provider_type: ProviderType::Mock,
+                model: "echo".to_string(),
+                api_key: None,
+                endpoint: None,
+                default_options: CompletionOptions {
+                    max_tokens: Some(1),
+                    truncation_retry_max_tokens,
+                    ..CompletionOptions::default()
+                },
+            };
+            fs::write(
+                providers_dir.join("mock-provider.toml"),
+                toml::to_string_pretty(&provider_config).unwrap(),
+            )
+            .unwrap();
+        };
+        let generate = |cli: &RunContext| {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(file_path.clone()),
+                    path_positional: None,
+                    agent: Some("mock-agent".to_string()),
+                    provider: Some("mock-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: true,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+            let node_id = resolve_workspace_node_id(
+                cli.api(),
+                &workspace_root,
+                Some(file_path.as_path()),
+                None,
+                false,
+            )
+            .unwrap();
+            let head = cli
+                .api()
+                .get_head(&node_id, "context-mock-agent")
+                .unwrap()
+                .unwrap();
+            cli.api().frame_storage().get(&head).unwrap().unwrap()
+        };
+
+        write_mock_provider(None);
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let frame = generate(&cli);
+        assert_eq!(frame.text_content().unwrap(), "mock(echo)");
+        assert_eq!(frame.metadata_value("truncated"), Some("true"));
+
+        let shown = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Get {
+                    node: None,
+                    path: Some(file_path.clone()),
+                    agent: None,
+                    frame_type: None,
+                    max_frames: 1,
+                    ordering: "recency".to_string(),
+                    combine: false,
+                    separator: "\n".to_string(),
+                    format: "text".to_string(),
+                    include_metadata: true,
+                    include_deleted: false,
+                    flatten_directory: false,
+                    meta: Vec::new(),
+                    count_only: false,
+                    export: None,
+                    budget_tokens: None,
+                    with_ancestors: false,
+                    size_only: false,
+                    newest_per_agent: false,
+                    resolve_basis: false,
+                    include_provenance: false,
+                },
+            })
+            .unwrap();
+        assert!(shown.contains("truncated: true"), "{}", shown);
+        drop(cli);
+
+        write_mock_provider(Some(2));
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        let frame = generate(&cli);
+        assert!(frame.text_content().unwrap().starts_with("mock(echo) "));
+        assert_eq!(frame.metadata_value("truncated"), None);
+    });
+}
+
+#[test]
+fn context_generate_only_stale_regenerates_changed_nodes_only() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        for name in ["a.md", "b.md", "c.md"] {
+            fs::write(target.join(name), format!("# {}", name)).unwrap();
+        }
+
+        create_test_writer_agent("stale-agent");
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Provider {
+            command: ProviderCommands::Create {
+                provider_name: "stale-provider".to_string(),
+                type_: Some("mock".to_string()),
+                model: Some("echo".to_string()),
+                endpoint: None,
+                api_key: None,
+                interactive: false,
+                non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
+            },
+        })
+        .unwrap();
+        let scan = || {
+            cli.execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        };
+        let generate = |only_missing: bool, only_stale: bool| {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("stale-agent".to_string()),
+                    provider: Some("stale-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing,
+                    only_stale,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap()
+        };
+        let head = |name: &str| {
+            let path = target.join(name);
+            let node_id = resolve_workspace_node_id(
+                cli.api(),
+                &workspace_root,
+                Some(path.as_path()),
+                None,
+                false,
+            )
+            .unwrap();
+            cli.api().get_head(&node_id, "context-stale-agent").unwrap()
+        };
+
+        scan();
+        let output = generate(false, false);
+        assert!(output.contains("generated=4, failed=0"), "{}", output);
+        let before_a = head("a.md").unwrap();
+        let before_c = head("c.md").unwrap();
+
+        // One changed file and one new file: only the changed file and its directory are stale.
+        fs::write(target.join("b.md"), "# b, revised").unwrap();
+        fs::write(target.join("d.md"), "# d").unwrap();
+        scan();
+        assert!(head("b.md").is_none());
+
+        let output = generate(false, true);
+        assert!(output.contains("generated=2, failed=0"), "{}", output);
+        assert!(head("b.md").is_some());
+        assert_eq!(head("a.md"), Some(before_a));
+        assert_eq!(head("c.md"), Some(before_c));
+        assert!(head("d.md").is_none());
+
+        let output = generate(false, true);
+        assert_eq!(output, "No stale frames for requested target.");
+
+        let output = generate(true, false);
+        assert!(output.contains("generated=1, failed=0"), "{}", output);
+        assert!(head("d.md").is_some());
+    });
+}
+
+#[test]
+fn context_touch_marks_heads_stale_for_only_stale_generation() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        let target = workspace_root.join("docs");
+        fs::create_dir_all(&target).unwrap();
+        for name in ["a.md", "b.md", "c.md"] {
+            fs::write(target.join(name), format!("# {}", name)).unwrap();
+        }
+
+        create_test_writer_agent("touch-agent");
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Provider {
+            command: ProviderCommands::Create {
+                provider_name: "touch-provider".to_string(),
+                type_: Some("mock".to_string()),
+                model: Some("echo".to_string()),
+                endpoint: None,
+                api_key: None,
+                interactive: false,
+                non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
+            },
+        })
+        .unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let generate = |only_stale: bool| {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("touch-agent".to_string()),
+                    provider: Some("touch-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap()
+        };
+        let touch = |path: std::path::PathBuf, recursive: bool, frame_type: Option<&str>| {
+            cli.execute(&Commands::Context {
+                command: ContextCommands::Touch {
+                    node: None,
+                    path: Some(path),
+                    recursive,
+                    frame_type: frame_type.map(str::to_string),
+                },
+            })
+            .unwrap()
+        };
+        let is_stale = |name: &str| {
+            cli.api()
+                .node_store()
+                .find_by_path(&target.join(name))
+                .unwrap()
+                .unwrap()
+                .is_head_stale("context-touch-agent")
+        };
+
+        let output = generate(false);
+        assert!(output.contains("generated=4, failed=0"), "{}", output);
+
+        // An unknown frame type has no head to mark.
+        let output = touch(target.join("a.md"), false, Some("context-other"));
+        assert_eq!(output, "Marked 0 head(s) stale on 0 node(s)\n");
+
+        let output = touch(target.join("a.md"), false, None);
+        assert_eq!(output, "Marked 1 head(s) stale on 1 node(s)\n");
+        assert!(is_stale("a.md"));
+        assert!(!is_stale("b.md"));
+
+        let output = generate(true);
+        assert!(output.contains("generated=1, failed=0"), "{}", output);
+        assert!(!is_stale("a.md"));
+        let output = generate(true);
+        assert_eq!(output, "No stale frames for requested target.");
+
+        let output = touch(target.clone(), true, Some("context-touch-agent"));
+        assert_eq!(output, "Marked 4 head(s) stale on 4 node(s)\n");
+        let output = generate(true);
+        assert!(output.contains("generated=4, failed=0"), "{}", output);
+        assert!(!is_stale("c.md"));
+    });
+}
+
+#[test]
+fn context_generate_preserve_mtime_flags_touched_source_in_staleness_check() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("notes.md");
+        fs::write(&target, "# notes").unwrap();
+
+        create_test_writer_agent("mtime-agent");
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Provider {
+            command: ProviderCommands::Create {
+                provider_name: "mtime-provider".to_string(),
+                type_: Some("mock".to_string()),
+                model: Some("echo".to_string()),
+                endpoint: None,
+                api_key: None,
+                interactive: false,
+                non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
+            },
+        })
+        .unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("mtime-agent".to_string()),
+                    provider: Some("mtime-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: true,
+                    record_provenance: false,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+        assert!(output.contains("generated=1, failed=0"), "{}", output);
+
+        let node_id =
+            resolve_workspace_node_id(cli.api(), &workspace_root, Some(&target), None, false)
+                .unwrap();
+        let frame_id = cli
+            .api()
+            .get_head(&node_id, "context-mtime-agent")
+            .unwrap()
+            .unwrap();
+        let frame = cli.api().frame_storage().get(&frame_id).unwrap().unwrap();
+        let modified = fs::metadata(&target).unwrap().modified().unwrap();
+        let expected = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+            .to_string();
+        assert_eq!(
+            frame.metadata_value("source_mtime"),
+            Some(expected.as_str())
+        );
+
+        let staleness = || {
+            let output = cli
+                .execute(&Commands::Workspace {
+                    command: WorkspaceCommands::Status {
+                        format: "json".to_string(),
+                        breakdown: false,
+                        include_content_hash: false,
+                        check_heads: false,
+                        by_agent: false,
+                        check_staleness: true,
+                        watch: false,
+                        interval: 2,
+                    },
+                })
+                .unwrap();
+            let status: serde_json::Value = serde_json::from_str(&output).unwrap();
+            status["mtime_staleness"].clone()
+        };
+
+        let report = staleness();
+        assert_eq!(report["checked_frames"], 1);
+        assert_eq!(report["stale_frames"].as_array().unwrap().len(), 0);
+
+        // Touch the source: same content, later modification time.
+        fs::File::options()
+            .write(true)
+            .open(&target)
+            .unwrap()
+            .set_modified(modified + Duration::from_secs(60))
+            .unwrap();
+
+        let report = staleness();
+        assert_eq!(report["checked_frames"], 1);
+        let stale = report["stale_frames"].as_array().unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0]["frame_type"], "context-mtime-agent");
+        assert_eq!(stale[0]["frame_id"], hex::encode(frame_id));
+        assert!(stale[0]["path"].as_str().unwrap().ends_with("notes.md"));
+    });
+}
+
+#[test]
+fn context_generate_record_provenance_is_shown_by_include_provenance() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let target = workspace_root.join("notes.md");
+        fs::write(&target, "# notes").unwrap();
+
+        create_test_writer_agent("provenance-agent");
+        let cli = RunContext::new(workspace_root.clone(), None).unwrap();
+        cli.execute(&Commands::Provider {
+            command: ProviderCommands::Create {
+                provider_name: "provenance-provider".to_string(),
+                type_: Some("mock".to_string()),
+                model: Some("echo".to_string()),
+                endpoint: None,
+                api_key: None,
+                interactive: false,
+                non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
+            },
+        })
+        .unwrap();
+        cli.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let output = cli
+            .execute(&Commands::Context {
+                command: ContextCommands::Generate(Box::new(GenerateArgs {
+                    node: None,
+                    path: Some(target.clone()),
+                    path_positional: None,
+                    agent: Some("provenance-agent".to_string()),
+                    provider: Some("provenance-provider".to_string()),
+                    workflow_id: None,
+                    provider_model: None,
+                    provider_additional_json_file: None,
+                    frame_type: None,
+                    force: false,
+                    only_missing: false,
+                    only_stale: false,
+                    no_recursive: false,
+                    continue_on_error: false,
+                    stdin_prompt: false,
+                    prompt_file: None,
+                    stream: false,
+                    max_total_retries: None,
+                    include_binary: false,
+                    prompt_var: Vec::new(),
+                    max_depth: None,
+                    after: Vec::new(),
+                    priority: "urgent".to_string(),
+                    preserve_mtime: false,
+                    record_provenance: true,
+                    timeout: None,
+                    on_conflict: "last-wins".to_string(),
+                    exclude: vec![],
+                })),
+            })
+            .unwrap();
+        assert!(output.contains("generated=1, failed=0"), "{}", output);
+
+        let node_id =
+            resolve_workspace_node_id(cli.api(), &workspace_root, Some(&target), None, false)
+                .unwrap();
+        let frame_id = cli
+            .api()
+            .get_head(&node_id, "context-provenance-agent")
+            .unwrap()
+            .unwrap();
+        let frame = cli.api().frame_storage().get(&frame_id).unwrap().unwrap();
+        assert_eq!(frame.metadata_value("retry_count"), Some("0"));
+        assert_eq!(
+            frame.metadata_value("provider_name"),
+            Some("provenance-provider")
+        );
+        assert!(frame
+            .metadata_value("generation_duration_ms")
+            .unwrap()
+            .parse::<u64>()
+            .is_ok());
+
+        let get = |include_provenance: bool| {
+            let output = cli
+                .execute(&Commands::Context {
+                    command: ContextCommands::Get {
+                        node: None,
+                        path: Some(target.clone()),
+                        agent: None,
+                        frame_type: None,
+                        max_frames: 5,
+                        ordering: "recency".to_string(),
+                        combine: false,
+                        separator: "\n".to_string(),
+                        format: "json".to_string(),
+                        include_metadata: true,
+                        include_deleted: false,
+                        flatten_directory: false,
+                        meta: Vec::new(),
+                        count_only: false,
+                        export: None,
+                        budget_tokens: None,
+                        with_ancestors: false,
+                        size_only: false,
+                        newest_per_agent: false,
+                        resolve_basis: false,
+                        include_provenance,
+                    },
+                })
+                .unwrap();
+            let context: serde_json::Value = serde_json::from_str(&output).unwrap();
+            context["frames"][0]["metadata"].clone()
+        };
+
+        let metadata = get(false);
+        assert!(metadata.get("retry_count").is_none(), "{}", metadata);
+        assert!(metadata.get("provider_name").is_none(), "{}", metadata);
+
+        let metadata = get(true);
+        assert_eq!(metadata["retry_count"], "0");
+        assert_eq!(metadata["provider_name"], "provenance-provider");
+        assert!(metadata["generation_duration_ms"].is_string());
+        assert_eq!(metadata["provider"], "provenance-provider");
+    });
+}
@@ -3,8 +3,14 @@
 use meld::cli::{Commands, ProviderCommands, RunContext};
 use meld::config::{xdg, ProviderConfig, ProviderType};
 use meld::error::ApiError;
+use meld::provider::latency::{latency_store_path, load_latency_stats, record_latency_sample};
+use meld::provider::{ChatMessage, MessageRole, MockClient};
 use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
 use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
 use tempfile::TempDir;
 
 use crate::integration::with_xdg_env;
@@ -64,6 +70,7 @@ fn test_provider_list_empty() {
             command: ProviderCommands::List {
                 format: "text".to_string(),
                 type_filter: None,
+                show_latency: false,
             },
         });
 
@@ -99,6 +106,7 @@ fn test_provider_list_with_providers() {
             command: ProviderCommands::List {
                 format: "text".to_string(),
                 type_filter: None,
+                show_latency: false,
             },
         });
 
@@ -131,6 +139,7 @@ fn test_provider_list_filter_by_type() {
             command: ProviderCommands::List {
                 format: "text".to_string(),
                 type_filter: Some("openai".to_string()),
+                show_latency: false,
             },
         });
 
@@ -154,6 +163,7 @@ fn test_provider_list_json_format() {
             command: ProviderCommands::List {
                 format: "json".to_string(),
                 type_filter: None,
+                show_latency: false,
             },
         });
 
@@ -190,6 +200,40 @@ fn test_provider_show() {
     });
 }
 
+#[test]
+fn test_provider_test_prompt_prints_raw_completion() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider("test-mock", ProviderType::Mock, "echo", None).unwrap();
+
+        let workspace = test_dir.path().to_path_buf();
+        let cli = RunContext::new(workspace, None).unwrap();
+
+        let output = cli
+            .execute(&Commands::Provider {
+                command: ProviderCommands::Test {
+                    provider_name: "test-mock".to_string(),
+                    model: None,
+                    timeout: 5,
+                    prompt: Some("Say hello".to_string()),
+                },
+            })
+            .unwrap();
+
+        let expected = MockClient::completion_content(
+            "echo",
+            &[ChatMessage {
+                role: MessageRole::User,
+                content: "Say hello".to_string(),
+            }],
+        );
+        assert!(output.contains("Prompt: Say hello"));
+        assert!(output.contains(&format!("--- response ---\n{}\n", expected)));
+        assert!(output.contains("Finish reason: stop"));
+        assert!(output.contains("Tokens: prompt=2, completion=2, total=4"));
+    });
+}
+
 #[test]
 fn test_provider_show_with_credentials() {
     let test_dir = TempDir::new().unwrap();
@@ -245,9 +289,12 @@ fn test_provider_validate() {
 
         let result = cli.execute(&Commands::Provider {
             command: ProviderCommands::Validate {
-                provider_name: "test-openai".to_string(),
+                provider_name: Some("test-openai".to_string()),
+                all: false,
+                fail_fast: false,
                 test_connectivity: false,
                 check_model: false,
+                check_credentials_only: false,
                 verbose: false,
             },
         });
@@ -258,6 +305,160 @@ fn test_provider_validate() {
     });
 }
 
+fn check_credentials_only(cli: &RunContext, provider_name: &str) -> Result<String, ApiError> {
+    cli.execute(&Commands::Provider {
+        command: ProviderCommands::Validate {
+            provider_name: Some(provider_name.to_string()),
+            all: false,
+            fail_fast: false,
+            test_connectivity: false,
+            check_model: false,
+            check_credentials_only: true,
+            verbose: false,
+        },
+    })
+}
+
+/// Run `f` with `ANTHROPIC_API_KEY` set to `value` (or unset), restoring it afterwards.
+/// Callers hold the env lock via `with_xdg_env`.
+fn with_anthropic_key<R>(value: Option<&str>, f: impl FnOnce() -> R) -> R {
+    let original = std::env::var("ANTHROPIC_API_KEY").ok();
+    match value {
+        Some(value) => std::env::set_var("ANTHROPIC_API_KEY", value),
+        None => std::env::remove_var("ANTHROPIC_API_KEY"),
+    }
+    let result = f();
+    match original {
+        Some(original) => std::env::set_var("ANTHROPIC_API_KEY", original),
+        None => std::env::remove_var("ANTHROPIC_API_KEY"),
+    }
+    result
+}
+
+#[test]
+fn test_provider_validate_check_credentials_only_accepts_env_key() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider(
+            "offline-anthropic",
+            ProviderType::Anthropic,
+            "claude-test",
+            // Unroutable endpoint: the offline check must not try to reach it.
+            Some("http://127.0.0.1:9"),
+        )
+        .unwrap();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        let output = with_anthropic_key(Some("sk-test"), || {
+            check_credentials_only(&cli, "offline-anthropic")
+        })
+        .unwrap();
+        assert!(output.contains("All validation checks passed"));
+    });
+}
+
+#[test]
+fn test_provider_validate_check_credentials_only_fails_without_required_key() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider(
+            "offline-anthropic",
+            ProviderType::Anthropic,
+            "claude-test",
+            None,
+        )
+        .unwrap();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        let err = with_anthropic_key(None, || check_credentials_only(&cli, "offline-anthropic"))
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("failed offline validation"), "{}", message);
+        assert!(message.contains("ANTHROPIC_API_KEY"), "{}", message);
+    });
+}
+
+fn validate_all_providers(cli: &RunContext, fail_fast: bool) -> Result<String, ApiError> {
+    cli.execute(&Commands::Provider {
+        command: ProviderCommands::Validate {
+            provider_name: None,
+            all: true,
+            fail_fast,
+            test_connectivity: false,
+            check_model: false,
+            check_credentials_only: false,
+            verbose: false,
+        },
+    })
+}
+
+/// Two providers missing their required API key, then one that needs none.
+fn create_mixed_validity_providers() {
+    for name in ["provider-a-bad", "provider-b-bad"] {
+        create_test_provider(name, ProviderType::Anthropic, "claude-test", None).unwrap();
+    }
+    create_test_provider("provider-c-good", ProviderType::Mock, "mock-model", None).unwrap();
+}
+
+#[test]
+fn test_provider_validate_all_keep_going_reports_every_failure() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_mixed_validity_providers();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        let message = with_anthropic_key(None, || validate_all_providers(&cli, false))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("2 provider(s) failed validation"),
+            "{}",
+            message
+        );
+        assert!(message.contains("✗ provider-a-bad"), "{}", message);
+        assert!(message.contains("✗ provider-b-bad"), "{}", message);
+        assert!(message.contains("✓ provider-c-good"), "{}", message);
+    });
+}
+
+#[test]
+fn test_provider_validate_all_fail_fast_stops_at_first_failure() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_mixed_validity_providers();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        let message = with_anthropic_key(None, || validate_all_providers(&cli, true))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("1 provider(s) failed validation"),
+            "{}",
+            message
+        );
+        assert!(message.contains("✗ provider-a-bad"), "{}", message);
+        assert!(!message.contains("provider-b-bad"), "{}", message);
+        assert!(!message.contains("provider-c-good"), "{}", message);
+        assert!(
+            message.contains("2 provider(s) not validated"),
+            "{}",
+            message
+        );
+    });
+}
+
+#[test]
+fn test_provider_validate_all_succeeds_when_every_provider_is_valid() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider("provider-c-good", ProviderType::Mock, "mock-model", None).unwrap();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        let output = validate_all_providers(&cli, false).unwrap();
+        assert!(output.contains("Summary: 1 valid, 0 invalid"));
+    });
+}
+
 #[test]
 fn test_provider_validate_not_found() {
     let test_dir = TempDir::new().unwrap();
@@ -267,9 +468,12 @@ fn test_provider_validate_not_found() {
 
         let result = cli.execute(&Commands::Provider {
             command: ProviderCommands::Validate {
-                provider_name: "nonexistent".to_string(),
+                provider_name: Some("nonexistent".to_string()),
+                all: false,
+                fail_fast: false,
                 test_connectivity: false,
                 check_model: false,
+                check_credentials_only: false,
                 verbose: false,
             },
         });
@@ -418,6 +622,9 @@ fn test_provider_create_non_interactive() {
                 api_key: None,
                 interactive: false,
                 non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
             },
         });
 
@@ -448,6 +655,9 @@ fn test_provider_create_missing_required_fields() {
                 api_key: None,
                 interactive: false,
                 non_interactive: true,
+                clone: None,
+                copy_key: false,
+                force: false,
             },
         });
 
@@ -457,6 +667,79 @@ fn test_provider_create_missing_required_fields() {
     });
 }
 
+#[test]
+fn test_provider_create_clone_copies_settings_but_not_key() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let providers_dir = xdg::providers_dir().unwrap();
+        fs::create_dir_all(&providers_dir).unwrap();
+        let source = ProviderConfig {
+            provider_name: Some("shared-endpoint".to_string()),
+            provider_type: ProviderType::LocalCustom,
+            model: "model-a".to_string(),
+            api_key: Some("secret-key".to_string()),
+            endpoint: Some("http://models.internal:8080/v1".to_string()),
+            default_options: meld::provider::CompletionOptions {
+                temperature: Some(0.2),
+                max_tokens: Some(2048),
+                ..Default::default()
+            },
+        };
+        fs::write(
+            providers_dir.join("shared-endpoint.toml"),
+            toml::to_string_pretty(&source).unwrap(),
+        )
+        .unwrap();
+
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+        let clone = |name: &str, model: Option<&str>, copy_key: bool, force: bool| {
+            cli.execute(&Commands::Provider {
+                command: ProviderCommands::Create {
+                    provider_name: name.to_string(),
+                    type_: None,
+                    model: model.map(str::to_string),
+                    endpoint: None,
+                    api_key: None,
+                    interactive: false,
+                    non_interactive: false,
+                    clone: Some("shared-endpoint".to_string()),
+                    copy_key,
+                    force,
+                },
+            })
+        };
+        let load = |name: &str| -> ProviderConfig {
+            let content = fs::read_to_string(providers_dir.join(format!("{}.toml", name))).unwrap();
+            toml::from_str(&content).unwrap()
+        };
+
+        let output = clone("model-b", Some("model-b"), false, false).unwrap();
+        assert!(
+            output.contains("Provider created: model-b (cloned from shared-endpoint)"),
+            "{}",
+            output
+        );
+        assert!(output.contains("API key not copied"), "{}", output);
+        let cloned = load("model-b");
+        assert_eq!(cloned.provider_name.as_deref(), Some("model-b"));
+        assert_eq!(cloned.provider_type, source.provider_type);
+        assert_eq!(cloned.endpoint, source.endpoint);
+        assert_eq!(cloned.model, "model-b");
+        assert_eq!(cloned.default_options.temperature, Some(0.2));
+        assert_eq!(cloned.default_options.max_tokens, Some(2048));
+        assert_eq!(cloned.api_key, None);
+
+        // The target exists now; --force is required to replace it.
+        let err = clone("model-b", None, true, false).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+
+        clone("model-b", None, true, true).unwrap();
+        let cloned = load("model-b");
+        assert_eq!(cloned.api_key.as_deref(), Some("secret-key"));
+        assert_eq!(cloned.model, "model-a");
+    });
+}
+
 #[test]
 fn test_provider_edit_with_flags() {
     let test_dir = TempDir::new().unwrap();
@@ -478,6 +761,7 @@ fn test_provider_edit_with_flags() {
                 model: Some("llama3".to_string()),
                 endpoint: None,
                 api_key: None,
+                unset: vec![],
                 editor: None,
             },
         });
@@ -494,6 +778,97 @@ fn test_provider_edit_with_flags() {
     });
 }
 
+fn edit_provider(
+    cli: &RunContext,
+    provider_name: &str,
+    api_key: Option<&str>,
+    unset: &[&str],
+) -> Result<String, ApiError> {
+    cli.execute(&Commands::Provider {
+        command: ProviderCommands::Edit {
+            provider_name: provider_name.to_string(),
+            model: None,
+            endpoint: None,
+            api_key: api_key.map(ToString::to_string),
+            unset: unset.iter().map(ToString::to_string).collect(),
+            editor: None,
+        },
+    })
+}
+
+fn read_provider_toml(provider_name: &str) -> toml::Value {
+    let config_path = xdg::providers_dir()
+        .unwrap()
+        .join(format!("{}.toml", provider_name));
+    toml::from_str(&fs::read_to_string(config_path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_provider_edit_unset_endpoint_reverts_to_default() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider(
+            "test-provider",
+            ProviderType::Ollama,
+            "llama2",
+            Some("http://custom-host.internal:11434"),
+        )
+        .unwrap();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        edit_provider(&cli, "test-provider", None, &["endpoint"]).unwrap();
+
+        let stored = read_provider_toml("test-provider");
+        assert!(stored.get("endpoint").is_none());
+        assert_eq!(stored.get("model").and_then(|v| v.as_str()), Some("llama2"));
+    });
+}
+
+#[test]
+fn test_provider_edit_unset_api_key() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider("test-provider", ProviderType::OpenAI, "gpt-4", None).unwrap();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        edit_provider(&cli, "test-provider", Some("sk-stored"), &[]).unwrap();
+        assert!(read_provider_toml("test-provider").get("api_key").is_some());
+
+        edit_provider(&cli, "test-provider", None, &["api_key"]).unwrap();
+        assert!(read_provider_toml("test-provider").get("api_key").is_none());
+    });
+}
+
+#[test]
+fn test_provider_edit_unset_required_field_errors() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider(
+            "test-provider",
+            ProviderType::Ollama,
+            "llama2",
+            Some("http://localhost:11434"),
+        )
+        .unwrap();
+        let cli = RunContext::new(test_dir.path().to_path_buf(), None).unwrap();
+
+        let error = edit_provider(&cli, "test-provider", None, &["endpoint", "model"]).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Cannot unset required provider field"));
+
+        // Validation happens before writing, so the valid unset in the same call is not applied.
+        let stored = read_provider_toml("test-provider");
+        assert_eq!(stored.get("model").and_then(|v| v.as_str()), Some("llama2"));
+        assert!(stored.get("endpoint").is_some());
+
+        let error = edit_provider(&cli, "test-provider", None, &["bogus"]).unwrap_err();
+        assert!(error.to_string().contains(
+            "Unknown provider field: bogus. Unsettable fields: endpoint, api_key, temperature"
+        ));
+    });
+}
+
 #[test]
 fn test_provider_edit_not_found() {
     let test_dir = TempDir::new().unwrap();
@@ -507,6 +882,7 @@ fn test_provider_edit_not_found() {
                 model: Some("new-model".to_string()),
                 endpoint: None,
                 api_key: None,
+                unset: vec![],
                 editor: None,
             },
         });
@@ -581,6 +957,7 @@ fn test_provider_list_invalid_type_filter() {
             command: ProviderCommands::List {
                 format: "text".to_string(),
                 type_filter: Some("invalid".to_string()),
+                show_latency: false,
             },
         });
 
@@ -666,3 +1043,295 @@ fn test_provider_registry_list_by_type() {
         assert!(provider_names.contains(&Some("test-anthropic")));
     });
 }
+
+#[test]
+fn test_provider_list_show_latency_sorts_fastest_first() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider("slow-provider", ProviderType::OpenAI, "gpt-4", None).unwrap();
+        create_test_provider("fast-provider", ProviderType::OpenAI, "gpt-4", None).unwrap();
+        create_test_provider("aaa-unsampled", ProviderType::Ollama, "llama2", None).unwrap();
+        record_latency_sample("slow-provider", 900.0).unwrap();
+        record_latency_sample("fast-provider", 120.0).unwrap();
+        record_latency_sample("fast-provider", 80.0).unwrap();
+
+        let workspace = test_dir.path().to_path_buf();
+        let cli = RunContext::new(workspace, None).unwrap();
+
+        let text = cli
+            .execute(&Commands::Provider {
+                command: ProviderCommands::List {
+                    format: "text".to_string(),
+                    type_filter: None,
+                    show_latency: true,
+                },
+            })
+            .unwrap();
+        let fast = text.find("fast-provider").unwrap();
+        let slow = text.find("slow-provider").unwrap();
+        let unsampled = text.find("aaa-unsampled").unwrap();
+        assert!(fast < slow);
+        assert!(slow < unsampled);
+        assert!(text.contains("n/a"));
+
+        let json_output = cli
+            .execute(&Commands::Provider {
+                command: ProviderCommands::List {
+                    format: "json".to_string(),
+                    type_filter: None,
+                    show_latency: true,
+                },
+            })
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json_output).unwrap();
+        let providers = parsed["providers"].as_array().unwrap();
+        let names: Vec<&str> = providers
+            .iter()
+            .map(|p| p["provider_name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["fast-provider", "slow-provider", "aaa-unsampled"]
+        );
+        assert!((providers[0]["avg_latency_ms"].as_f64().unwrap() - 112.0).abs() < 1e-9);
+        assert_eq!(providers[0]["latency_samples"].as_u64(), Some(2));
+        assert!(providers[2]["avg_latency_ms"].is_null());
+    });
+}
+
+#[test]
+fn test_record_latency_sample_writes_outside_workspace_and_replaces_corrupt_file() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let workspace = test_dir.path().join("workspace");
+        fs::create_dir_all(&workspace).unwrap();
+
+        record_latency_sample("provider-a", 100.0).unwrap();
+        let path = latency_store_path().unwrap();
+        assert!(path.exists());
+        assert!(!path.starts_with(&workspace), "{}", path.display());
+
+        // A truncated file is ignored and rewritten from the next sample.
+        fs::write(&path, "{\"provider-a\": {\"average_ms\": 1").unwrap();
+        assert!(load_latency_stats().unwrap().is_empty());
+        record_latency_sample("provider-a", 40.0).unwrap();
+        let stats = load_latency_stats().unwrap();
+        assert_eq!(stats["provider-a"].samples, 1);
+        assert_eq!(stats["provider-a"].average_ms, 40.0);
+
+        let leftovers: Vec<_> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+    });
+}
+
+#[test]
+fn test_json_compact_emits_single_line_equivalent_json() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        create_test_provider("test-openai", ProviderType::OpenAI, "gpt-4", None).unwrap();
+        create_test_provider("test-ollama", ProviderType::Ollama, "llama2", None).unwrap();
+
+        let workspace = test_dir.path().to_path_buf();
+        let list = Commands::Provider {
+            command: ProviderCommands::List {
+                format: "json".to_string(),
+                type_filter: None,
+                show_latency: false,
+            },
+        };
+
+        let cli = RunContext::new(workspace, None).unwrap();
+        let pretty = cli.execute(&list).unwrap();
+        let cli = cli.json_compact(true);
+        let compact = cli.execute(&list).unwrap();
+
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+        let pretty_value: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        let compact_value: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        assert_eq!(pretty_value, compact_value);
+        assert_eq!(compact_value["total"], 2);
+    });
+}
+
+/// Answer one request with `status` and a JSON `body`; sends the request head to the receiver.
+fn spawn_model_listing_server(status: &str, body: &str) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let status = status.to_string();
+    let body = body.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut head = Vec::new();
+        let mut chunk = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let read = stream.read(&mut chunk).unwrap();
+            if read == 0 {
+                break;
+            }
+            head.extend_from_slice(&chunk[..read]);
+        }
+        tx.send(String::from_utf8_lossy(&head).to_string()).unwrap();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+        stream.flush().unwrap();
+    });
+
+    (endpoint, rx)
+}
+
+/// Register a provider pointed at `endpoint` with an inline API key, then run `provider test`.
+fn run_provider_test(
+    workspace: &TempDir,
+    provider_type: ProviderType,
+    model: &str,
+    endpoint: &str,
+) -> String {
+    let providers_dir = xdg::providers_dir().unwrap();
+    fs::create_dir_all(&providers_dir).unwrap();
+    let provider_config = ProviderConfig {
+        provider_name: Some("listing".to_string()),
+        provider_type,
+        model: model.to_string(),
+        api_key: Some("test-key".to_string()),
+        endpoint: Some(endpoint.to_string()),
+        default_options: meld::provider::CompletionOptions::default(),
+    };
+    fs::write(
+        providers_dir.join("listing.toml"),
+        toml::to_string_pretty(&provider_config).unwrap(),
+    )
+    .unwrap();
+
+    let cli = RunContext::new(workspace.path().to_path_buf(), None)
+        .unwrap()
+        .color("never");
+    cli.execute(&Commands::Provider {
+        command: ProviderCommands::Test {
+            provider_name: "listing".to_string(),
+            model: None,
+            timeout: 5,
+            prompt: None,
+        },
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_provider_test_reads_openai_model_listing() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let (endpoint, requests) =
+            spawn_model_listing_server("200 OK", r#"{"data":[{"id":"gpt-4o"},{"id":"gpt-4"}]}"#);
+
+        let output = run_provider_test(&test_dir, ProviderType::OpenAI, "gpt-4o", &endpoint);
+
+        let head = requests.recv().unwrap();
+        assert!(head.starts_with("GET /models "), "{}", head);
+        assert!(
+            output.contains("✓ Model 'gpt-4o' is available"),
+            "{}",
+            output
+        );
+        assert!(output.contains("Provider is working correctly."));
+    });
+}
+
+#[test]
+fn test_provider_test_reads_anthropic_model_listing() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let (endpoint, requests) = spawn_model_listing_server(
+            "200 OK",
+            r#"{"data":[{"id":"claude-3-5-sonnet-20241022","type":"model"}],"has_more":false,"first_id":"claude-3-5-sonnet-20241022","last_id":"claude-3-5-sonnet-20241022"}"#,
+        );
+
+        // `-latest` aliases are not listed; they resolve to a dated model of the same family.
+        let output = run_provider_test(
+            &test_dir,
+            ProviderType::Anthropic,
+            "claude-3-5-sonnet-latest",
+            &endpoint,
+        );
+
+        let head = requests.recv().unwrap();
+        assert!(head.starts_with("GET /models?limit=1000 "), "{}", head);
+        assert!(
+            head.to_ascii_lowercase().contains("x-api-key: test-key"),
+            "{}",
+            head
+        );
+        assert!(head.contains("anthropic-version"), "{}", head);
+        assert!(
+            output.contains("✓ Model 'claude-3-5-sonnet-latest' is available"),
+            "{}",
+            output
+        );
+    });
+}
+
+#[test]
+fn test_provider_test_reads_ollama_tags() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let tags = r#"{"models":[{"name":"llama2:latest"},{"name":"mistral:7b"}]}"#;
+        let (endpoint, requests) = spawn_model_listing_server("200 OK", tags);
+
+        // An untagged model is served by its `:latest` tag.
+        let output = run_provider_test(&test_dir, ProviderType::Ollama, "llama2", &endpoint);
+        let head = requests.recv().unwrap();
+        assert!(head.starts_with("GET /api/tags "), "{}", head);
+        assert!(
+            output.contains("✓ Model 'llama2' is available"),
+            "{}",
+            output
+        );
+
+        let (endpoint, _requests) = spawn_model_listing_server("200 OK", tags);
+        let output = run_provider_test(&test_dir, ProviderType::Ollama, "mistral", &endpoint);
+        assert!(output.contains("✗ Model 'mistral' not found"), "{}", output);
+        assert!(
+            output.contains("Available models: llama2:latest, mistral:7b"),
+            "{}",
+            output
+        );
+    });
+}
+
+#[test]
+fn test_provider_test_skips_model_check_without_listing_endpoint() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let (endpoint, requests) =
+            spawn_model_listing_server("404 Not Found", r#"{"error":"not found"}"#);
+
+        let output = run_provider_test(
+            &test_dir,
+            ProviderType::LocalCustom,
+            "local-model",
+            &endpoint,
+        );
+
+        let head = requests.recv().unwrap();
+        assert!(head.starts_with("GET /models "), "{}", head);
+        assert!(output.contains("✓ API connectivity: OK"), "{}", output);
+        assert!(
+            output.contains("⚠ Model 'local-model' availability not checked"),
+            "{}",
+            output
+        );
+        assert!(!output.contains("not found"), "{}", output);
+        assert!(output.contains("Provider is working correctly."));
+    });
+}
@@ -0,0 +1,160 @@
+//! Integration tests for the repl session dispatcher
+
+use clap::Parser;
+use meld::cli::{map_error, Cli, Commands, ReplOutcome, ReplSession, RunContext};
+use std::fs;
+use tempfile::TempDir;
+
+use crate::integration::with_xdg_env;
+
+fn one_shot(ctx: &RunContext, line: &str) -> Result<String, String> {
+    let args = std::iter::once("meld").chain(line.split_whitespace());
+    let cli = Cli::parse_from(args);
+    ctx.execute(&cli.command).map_err(|e| map_error(&e))
+}
+
+fn workspace_with_files(temp_dir: &TempDir) -> std::path::PathBuf {
+    let workspace_root = temp_dir.path().join("workspace");
+    fs::create_dir_all(workspace_root.join("src")).unwrap();
+    fs::write(workspace_root.join("a.txt"), "alpha").unwrap();
+    fs::write(workspace_root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+    workspace_root
+}
+
+#[test]
+fn test_repl_commands_match_one_shot_output() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = workspace_with_files(&temp_dir);
+        let ctx = RunContext::new(workspace_root, None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let mut session = ReplSession::new(&ctx);
+        for line in [
+            "tree",
+            "tree --depth 1 --format json",
+            "workspace list-deleted",
+        ] {
+            let expected = one_shot(&ctx, line).unwrap();
+            assert_eq!(session.dispatch(line), ReplOutcome::Output(expected));
+        }
+
+        let missing = "context get --path missing.txt";
+        let expected = one_shot(&ctx, missing).unwrap_err();
+        assert_eq!(session.dispatch(missing), ReplOutcome::Error(expected));
+
+        match session.dispatch("tree --no-such-flag") {
+            ReplOutcome::Error(message) => assert!(message.contains("--no-such-flag")),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    });
+}
+
+#[test]
+fn test_repl_reloads_registries_after_init() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = workspace_with_files(&temp_dir);
+        let list = "agent list --format json";
+
+        let repl_output = {
+            let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+            let mut session = ReplSession::new(&ctx);
+            assert!(matches!(session.dispatch("init"), ReplOutcome::Output(_)));
+            match session.dispatch(list) {
+                ReplOutcome::Output(output) => output,
+                other => panic!("expected agent list, got {:?}", other),
+            }
+        };
+        assert!(repl_output.contains("docs-writer"));
+
+        // Agent list order is not stable between registry loads, so compare the entries as sets.
+        let agents = |output: &str| {
+            let parsed: serde_json::Value = serde_json::from_str(output).unwrap();
+            let mut agents = parsed["agents"].as_array().unwrap().clone();
+            agents.sort_by_key(|agent| agent.to_string());
+            agents
+        };
+        let fresh = RunContext::new(workspace_root, None).unwrap();
+        assert_eq!(
+            agents(&repl_output),
+            agents(&one_shot(&fresh, list).unwrap())
+        );
+    });
+}
+
+#[test]
+fn test_repl_builtins() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = workspace_with_files(&temp_dir);
+        let ctx = RunContext::new(workspace_root, None).unwrap();
+        let mut session = ReplSession::new(&ctx);
+
+        assert_eq!(session.dispatch("   "), ReplOutcome::Empty);
+        match session.dispatch("help") {
+            ReplOutcome::Output(help) => {
+                assert!(help.contains("history"));
+                assert!(help.contains("context"));
+            }
+            other => panic!("expected help, got {:?}", other),
+        }
+        match session.dispatch("help tree") {
+            ReplOutcome::Output(help) => assert!(help.contains("--depth")),
+            other => panic!("expected tree help, got {:?}", other),
+        }
+        assert!(matches!(session.dispatch("repl"), ReplOutcome::Error(_)));
+        match session.dispatch("context generate --path a.txt --stdin-prompt") {
+            ReplOutcome::Error(message) => assert!(message.contains("--prompt-file")),
+            other => panic!("expected --stdin-prompt to be rejected, got {:?}", other),
+        }
+        match session.dispatch("workspace compact --all --vacuum") {
+            ReplOutcome::Error(message) => assert!(message.contains("outside the repl")),
+            other => panic!("expected --vacuum to be rejected, got {:?}", other),
+        }
+        assert_eq!(
+            session.dispatch("history"),
+            ReplOutcome::Output(
+                "   1  help\n   2  help tree\n   3  repl\n   4  context generate --path a.txt --stdin-prompt\n   5  workspace compact --all --vacuum\n   6  history".to_string()
+            )
+        );
+        assert_eq!(session.history().len(), 6);
+        assert_eq!(session.dispatch("exit"), ReplOutcome::Exit);
+    });
+}
+
+#[test]
+fn test_run_repl_reads_until_exit() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = workspace_with_files(&temp_dir);
+        let ctx = RunContext::new(workspace_root, None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let expected_tree = one_shot(&ctx, "tree").unwrap();
+
+        let input = "tree\nexit\ntree\n".as_bytes();
+        let mut output = Vec::new();
+        meld::cli::run_repl(&ctx, input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, format!("meld> {}\nmeld> ", expected_tree));
+    });
+}
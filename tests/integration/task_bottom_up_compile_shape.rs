@@ -37,6 +37,9 @@ fn create_test_agent(agent_id: &str, workflow_id: Option<&str>) {
         system_prompt: Some("You are a careful docs writer.".to_string()),
         system_prompt_path: None,
         workflow_id: workflow_id.map(ToString::to_string),
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: metadata.into(),
     };
 
@@ -158,7 +161,15 @@ fn compiled_shape_for_case(case: &BottomUpCompileCase) -> NormalizedCompileShape
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let registered_profile = run_context
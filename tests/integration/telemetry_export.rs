@@ -0,0 +1,133 @@
+use std::fs;
+
+use meld::cli::{Commands, RunContext, TelemetryCommands};
+use tempfile::TempDir;
+
+use crate::integration::with_xdg_env;
+
+fn seed_session(cli: &RunContext, command: &str, event_types: &[&str]) -> String {
+    let runtime = cli.progress_runtime();
+    let session_id = runtime.start_command_session(command.to_string()).unwrap();
+    for (index, event_type) in event_types.iter().enumerate() {
+        runtime
+            .emit_event(
+                &session_id,
+                event_type,
+                serde_json::json!({ "index": index }),
+            )
+            .unwrap();
+    }
+    runtime
+        .finish_command_session(&session_id, true, None)
+        .unwrap();
+    session_id
+}
+
+fn read_event_types(path: &std::path::Path) -> Vec<(String, u64)> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            (
+                value["type"].as_str().unwrap().to_string(),
+                value["seq"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn telemetry_export_writes_session_events_in_order() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let cli = RunContext::new(workspace_root, None).unwrap();
+        let session_id = seed_session(&cli, "manual.seed", &["step_a", "step_b", "step_c"]);
+
+        let export_path = temp_dir.path().join("session.jsonl");
+        let output = cli
+            .execute(&Commands::Telemetry {
+                command: TelemetryCommands::Export {
+                    session: Some(session_id.clone()),
+                    last: false,
+                    file: export_path.clone(),
+                },
+            })
+            .unwrap();
+        assert!(output.contains(&session_id));
+
+        let exported = read_event_types(&export_path);
+        let types: Vec<&str> = exported.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                "session_started",
+                "step_a",
+                "step_b",
+                "step_c",
+                "session_ended"
+            ]
+        );
+        assert!(exported.windows(2).all(|w| w[0].1 < w[1].1));
+    });
+}
+
+#[test]
+fn telemetry_export_last_skips_the_exporting_session() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let cli = RunContext::new(workspace_root, None).unwrap();
+        let session_id = seed_session(&cli, "manual.last", &["only_step"]);
+
+        let export_path = temp_dir.path().join("last.jsonl");
+        let output = cli
+            .execute(&Commands::Telemetry {
+                command: TelemetryCommands::Export {
+                    session: None,
+                    last: true,
+                    file: export_path.clone(),
+                },
+            })
+            .unwrap();
+        assert!(output.contains(&session_id));
+
+        let types: Vec<String> = read_event_types(&export_path)
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect();
+        assert!(types.contains(&"only_step".to_string()));
+    });
+}
+
+#[test]
+fn telemetry_list_sessions_reports_command_and_success() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let cli = RunContext::new(workspace_root, None).unwrap();
+        seed_session(&cli, "manual.listed", &["step"]);
+
+        let output = cli
+            .execute(&Commands::Telemetry {
+                command: TelemetryCommands::ListSessions {
+                    limit: 10,
+                    format: "json".to_string(),
+                },
+            })
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let listed = value["sessions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["command"] == "manual.listed")
+            .expect("seeded session should be listed");
+        assert_eq!(listed["success"], serde_json::json!(true));
+        assert!(listed["duration_ms"].is_u64());
+    });
+}
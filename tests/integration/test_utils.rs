@@ -15,6 +15,8 @@ struct EnvState {
     home: Option<String>,
     xdg_config_home: Option<String>,
     xdg_data_home: Option<String>,
+    git_config_global: Option<String>,
+    meld_profile: Option<String>,
 }
 
 impl EnvState {
@@ -23,6 +25,8 @@ impl EnvState {
             home: std::env::var("HOME").ok(),
             xdg_config_home: std::env::var("XDG_CONFIG_HOME").ok(),
             xdg_data_home: std::env::var("XDG_DATA_HOME").ok(),
+            git_config_global: std::env::var("GIT_CONFIG_GLOBAL").ok(),
+            meld_profile: std::env::var("MELD_PROFILE").ok(),
         }
     }
 
@@ -44,6 +48,18 @@ impl EnvState {
         } else {
             std::env::remove_var("XDG_DATA_HOME");
         }
+
+        if let Some(orig) = self.git_config_global {
+            std::env::set_var("GIT_CONFIG_GLOBAL", orig);
+        } else {
+            std::env::remove_var("GIT_CONFIG_GLOBAL");
+        }
+
+        if let Some(orig) = self.meld_profile {
+            std::env::set_var("MELD_PROFILE", orig);
+        } else {
+            std::env::remove_var("MELD_PROFILE");
+        }
     }
 }
 
@@ -52,6 +68,7 @@ impl EnvState {
 /// This function:
 /// - Creates isolated XDG_CONFIG_HOME and XDG_DATA_HOME directories in the temp dir
 /// - Sets HOME to ensure fallback paths work correctly
+/// - Unsets GIT_CONFIG_GLOBAL so the user's git excludes never reach a scan
 /// - Automatically restores original environment variables after the test
 /// - Uses a global mutex to prevent race conditions in parallel test execution
 ///
@@ -86,6 +103,7 @@ where
         std::env::set_var("HOME", test_home.to_str().unwrap());
         std::env::set_var("XDG_CONFIG_HOME", test_config_home.to_str().unwrap());
         std::env::set_var("XDG_DATA_HOME", test_data_home.to_str().unwrap());
+        std::env::remove_var("GIT_CONFIG_GLOBAL");
 
         // Run test
         let result = f();
@@ -99,8 +117,9 @@ where
 
 /// Set up only XDG_DATA_HOME (for workspace isolation tests)
 ///
-/// This is a lighter-weight version for tests that only need data directory isolation
-/// but don't need config directory isolation.
+/// This is a lighter-weight version for tests that only need data directory isolation.
+/// XDG_CONFIG_HOME points at an empty directory and GIT_CONFIG_GLOBAL is unset, so the
+/// user's git excludes never reach a scan.
 pub fn with_xdg_data_home<F, R>(test_dir: &TempDir, f: F) -> R
 where
     F: FnOnce() -> R,
@@ -111,11 +130,15 @@ where
         let test_data_home = test_dir.path().join("data");
         let test_home = test_dir.path().join("home");
 
+        let test_config_home = test_dir.path().join("config");
+
         std::fs::create_dir_all(&test_data_home).unwrap();
         std::fs::create_dir_all(&test_home).unwrap();
 
         std::env::set_var("HOME", test_home.to_str().unwrap());
+        std::env::set_var("XDG_CONFIG_HOME", test_config_home.to_str().unwrap());
         std::env::set_var("XDG_DATA_HOME", test_data_home.to_str().unwrap());
+        std::env::remove_var("GIT_CONFIG_GLOBAL");
 
         let result = f();
 
@@ -42,7 +42,7 @@ fn create_context_api(
     temp_dir: &tempfile::TempDir,
 ) -> ContextApi {
     let db = progress.store().db().clone();
-    let node_store = Arc::new(SledNodeRecordStore::from_db(db));
+    let node_store = Arc::new(SledNodeRecordStore::from_db(db).unwrap());
     let frame_storage = Arc::new(FrameStorage::new(temp_dir.path().join("frames")).unwrap());
     let prompt_context_storage =
         Arc::new(PromptContextArtifactStorage::new(temp_dir.path().join("artifacts")).unwrap());
@@ -536,6 +536,8 @@ fn current_snapshot_matches_workspace_root_hash() {
         &api,
         &workspace_root,
         true,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
@@ -804,7 +806,15 @@ fn run_context_scan_bootstraps_graph_runtime() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let traversal =
@@ -1,7 +1,9 @@
 //! Integration tests for tree building determinism
 
 use meld::tree::builder::TreeBuilder;
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::PathBuf;
 use tempfile::TempDir;
 
 /// Test that the same filesystem produces the same root hash
@@ -145,3 +147,32 @@ fn test_empty_directory_consistent() {
     // Should be consistent
     assert_eq!(root1, root2);
 }
+
+/// Test that directory NodeIDs do not depend on the order children were created in
+#[test]
+fn test_directory_hashes_independent_of_creation_order() {
+    let names = ["b.txt", "a.txt", "c.txt", "sub/z.txt", "sub/y.txt"];
+    let build = |order: &[&str]| {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::create_dir(root.join("sub")).unwrap();
+        for name in order {
+            fs::write(root.join(name), format!("content of {}", name)).unwrap();
+        }
+        let tree = TreeBuilder::new(root.clone()).build().unwrap();
+        let directories: BTreeMap<PathBuf, _> = tree
+            .directory_node_ids()
+            .into_iter()
+            .map(|(path, node_id)| (path.strip_prefix(&root).unwrap().to_path_buf(), node_id))
+            .collect();
+        (tree.root_id, directories)
+    };
+
+    let reversed: Vec<&str> = names.iter().rev().copied().collect();
+    let (root1, dirs1) = build(&names);
+    let (root2, dirs2) = build(&reversed);
+
+    assert_eq!(dirs1.len(), 2);
+    assert_eq!(dirs1, dirs2);
+    assert_eq!(root1, root2);
+}
@@ -2,6 +2,7 @@
 
 use meld::tree::builder::TreeBuilder;
 use meld::tree::node::MerkleNode;
+use meld::tree::walker::WalkerConfig;
 use std::fs;
 use tempfile::TempDir;
 
@@ -37,7 +38,11 @@ fn test_tree_contains_all_directories() {
     fs::create_dir(root.join("dir2")).unwrap();
     fs::write(root.join("dir1").join("file.txt"), "content").unwrap();
 
-    let builder = TreeBuilder::new(root.clone());
+    // dir2 is empty, so it is only recorded with include_empty_dirs
+    let builder = TreeBuilder::new(root.clone()).with_walker_config(WalkerConfig {
+        include_empty_dirs: true,
+        ..WalkerConfig::default()
+    });
     let tree = builder.build().unwrap();
 
     // Count directory nodes (should include root + dir1 + dir2)
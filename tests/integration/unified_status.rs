@@ -25,6 +25,9 @@ fn create_test_agent(
         system_prompt: None,
         system_prompt_path: prompt_path.map(|s| s.to_string()),
         workflow_id: None,
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: Default::default(),
     };
 
@@ -141,7 +144,11 @@ fn test_unified_status_all_sections_text() {
             agents_only: false,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -155,6 +162,45 @@ fn test_unified_status_all_sections_text() {
     });
 }
 
+#[test]
+fn test_unified_status_color_flag_controls_ansi_escapes() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        clear_configs();
+        create_test_agent("test-reader", AgentRole::Reader, None).unwrap();
+
+        let workspace = test_dir.path().to_path_buf();
+        let status = |color: &str| {
+            RunContext::new(workspace.clone(), None)
+                .unwrap()
+                .color(color)
+                .execute(&Commands::Status {
+                    format: "text".to_string(),
+                    workspace_only: false,
+                    agents_only: false,
+                    providers_only: false,
+                    breakdown: false,
+                    include_content_hash: false,
+                    test_connectivity: false,
+                    watch: false,
+                    interval: 2,
+                    workspaces: Vec::new(),
+                })
+                .unwrap()
+        };
+
+        let plain = status("never");
+        assert!(!plain.contains('\x1b'), "{}", plain);
+        assert!(plain.contains("Workspace Status"));
+        assert!(plain.contains("test-reader"));
+
+        // Test output is never a terminal, so `always` must not depend on TTY detection.
+        let colored = status("always");
+        assert!(colored.contains('\x1b'), "{}", colored);
+        assert!(colored.contains("test-reader"));
+    });
+}
+
 #[test]
 fn test_unified_status_all_sections_json() {
     let test_dir = TempDir::new().unwrap();
@@ -184,7 +230,11 @@ fn test_unified_status_all_sections_json() {
             agents_only: false,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -227,7 +277,11 @@ fn test_unified_status_workspace_only() {
             agents_only: false,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -269,7 +323,11 @@ fn test_unified_status_agents_only() {
             agents_only: true,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -312,7 +370,11 @@ fn test_unified_status_providers_only() {
             agents_only: false,
             providers_only: true,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -341,7 +403,11 @@ fn test_unified_status_with_breakdown() {
             agents_only: false,
             providers_only: false,
             breakdown: true,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -373,7 +439,11 @@ fn test_unified_status_with_test_connectivity() {
             agents_only: false,
             providers_only: true,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: true,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -402,7 +472,11 @@ fn test_unified_status_empty_configs() {
             agents_only: false,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         // Should succeed even with empty configs
@@ -430,7 +504,11 @@ fn test_unified_status_json_workspace_only() {
             agents_only: false,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -467,7 +545,11 @@ fn test_unified_status_json_agents_only() {
             agents_only: true,
             providers_only: false,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -502,7 +584,11 @@ fn test_unified_status_json_providers_only() {
             agents_only: false,
             providers_only: true,
             breakdown: false,
+            include_content_hash: false,
             test_connectivity: false,
+            watch: false,
+            interval: 2,
+            workspaces: Vec::new(),
         });
 
         assert!(result.is_ok());
@@ -514,3 +600,76 @@ fn test_unified_status_json_providers_only() {
         assert!(output.contains("json-only-provider"));
     });
 }
+
+#[test]
+fn test_status_workspaces_aggregates_each_workspace() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        clear_configs();
+
+        let workspace_a = test_dir.path().join("ws-a");
+        let workspace_b = test_dir.path().join("ws-b");
+        fs::create_dir_all(&workspace_a).unwrap();
+        fs::create_dir_all(&workspace_b).unwrap();
+        for name in ["a.txt", "b.txt"] {
+            fs::write(workspace_a.join(name), name).unwrap();
+        }
+        for name in ["c.txt", "d.txt", "e.txt"] {
+            fs::write(workspace_b.join(name), name).unwrap();
+        }
+
+        let scan = Commands::Scan {
+            force: false,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        };
+        // Scan B in its own context and drop it, so its store is free to be opened again.
+        {
+            let cli_b = RunContext::new(workspace_b.clone(), None).unwrap();
+            cli_b.execute(&scan).unwrap();
+        }
+        let cli = RunContext::new(workspace_a.clone(), None).unwrap();
+        cli.execute(&scan).unwrap();
+
+        let missing = test_dir.path().join("does-not-exist");
+        let status = |format: &str| {
+            cli.execute(&Commands::Status {
+                format: format.to_string(),
+                workspace_only: false,
+                agents_only: false,
+                providers_only: false,
+                breakdown: false,
+                include_content_hash: false,
+                test_connectivity: false,
+                watch: false,
+                interval: 2,
+                workspaces: vec![workspace_a.clone(), workspace_b.clone(), missing.clone()],
+            })
+            .unwrap()
+        };
+
+        let parsed: serde_json::Value = serde_json::from_str(&status("json")).unwrap();
+        let rows = parsed["workspaces"].as_array().unwrap();
+        assert_eq!(rows.len(), 3);
+        // Each root plus its files.
+        assert_eq!(rows[0]["total_nodes"], 3);
+        assert_eq!(rows[0]["scan_state"], "current");
+        assert!(rows[0].get("error").is_none());
+        assert_eq!(rows[1]["total_nodes"], 4);
+        assert_eq!(rows[1]["scan_state"], "current");
+        assert!(rows[1].get("error").is_none());
+        assert!(rows[2]["error"]
+            .as_str()
+            .unwrap()
+            .contains("Cannot open workspace"));
+
+        let text = status("text");
+        assert!(text.contains("ws-a"), "{}", text);
+        assert!(text.contains("ws-b"), "{}", text);
+        assert!(text.contains("Errors:"), "{}", text);
+    });
+}
@@ -74,6 +74,9 @@ fn create_workflow_test_agent(
         system_prompt: Some("You are a workflow test writer.".to_string()),
         system_prompt_path: None,
         workflow_id: workflow_id.map(ToString::to_string),
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: metadata.into(),
     };
 
@@ -354,7 +357,15 @@ fn workflow_execute_uses_workflow_runtime_and_can_skip_completed_thread() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let node_id = meld::workspace::resolve_workspace_node_id(
@@ -426,7 +437,15 @@ fn workflow_facade_maps_completed_thread_reuse_to_target_result() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let node_id = meld::workspace::resolve_workspace_node_id(
@@ -510,7 +529,15 @@ fn workflow_execute_uses_runtime_modified_xdg_prompt_in_provider_request() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let output = run_context
@@ -38,6 +38,9 @@ fn create_test_agent(agent_id: &str, workflow_id: Option<&str>) {
         system_prompt: Some("You are a careful docs writer.".to_string()),
         system_prompt_path: None,
         workflow_id: workflow_id.map(ToString::to_string),
+        default_provider: None,
+        frame_type_per_node_type: false,
+        capabilities: Vec::new(),
         metadata: metadata.into(),
     };
 
@@ -213,7 +216,15 @@ fn workflow_execute_routes_docs_writer_through_task_path() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let output = run_context
@@ -283,7 +294,15 @@ fn workflow_task_path_fails_when_required_frame_ref_anchor_is_missing() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
 
         let node_id = meld::workspace::resolve_workspace_node_id(
@@ -346,7 +365,15 @@ fn workflow_task_path_live_and_replay_artifact_resolution_match() {
 
         let run_context = RunContext::new(workspace_root.clone(), None).unwrap();
         run_context
-            .execute(&Commands::Scan { force: true })
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
             .unwrap();
         run_context
             .execute(&Commands::Workflow {
@@ -1,20 +1,27 @@
 //! Integration tests for workspace commands: ignore list, scan, validate.
 //!
 //! Covers meld workspace ignore (list/add), meld scan (idempotency, force,
-//! ignore list and .gitignore sync), and meld workspace validate (passed,
-//! not scanned, JSON format).
+//! ignore list, .gitignore sync, and git's exclude files), meld workspace validate
+//! (passed, not scanned, JSON format, head basis mismatch), and config profiles
+//! selecting the store.
 
 use clap::Parser;
-use meld::cli::{Cli, Commands, DangerCommands, RunContext, WorkspaceCommands};
+use meld::cli::{Cli, Commands, DangerCommands, RunContext, TreeCommands, WorkspaceCommands};
 use meld::config::MerkleConfig;
+use meld::context::frame::{Basis, Frame};
 use meld::ignore;
 use meld::tree::builder::TreeBuilder;
+use meld::tree::node::MerkleNode;
+use meld::workspace::status_watch::StatusWatch;
+use meld::workspace::tooling::workspace_status_snapshot;
+use meld::workspace::{read_workspace_scan_state, WorkspaceScanState, WorkspaceStatusRequest};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::TempDir;
 
-use crate::integration::with_xdg_data_home;
+use crate::integration::{with_xdg_data_home, with_xdg_env};
 
 fn expected_workspace_log_path(state_home: &Path, workspace: &Path) -> PathBuf {
     let canonical = workspace.canonicalize().unwrap();
@@ -239,6 +246,266 @@ fn test_workspace_ignore_dry_run() {
     });
 }
 
+#[test]
+fn test_scan_verify_stable_same_root_at_two_locations() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let scan_root = |name: &str| {
+            let workspace_root = temp_dir.path().join(name);
+            fs::create_dir_all(workspace_root.join("src")).unwrap();
+            fs::write(workspace_root.join("README.md"), "readme").unwrap();
+            fs::write(workspace_root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+            let ctx = RunContext::new(workspace_root, None).unwrap();
+            let out = ctx
+                .execute(&Commands::Scan {
+                    force: true,
+                    include_hidden: false,
+                    include_empty_dirs: false,
+                    verify_stable: true,
+                    allow_empty_workspace: false,
+                    stats: false,
+                    format: "text".to_string(),
+                })
+                .unwrap();
+            assert!(out.contains("Root hash stable across rescan"), "{}", out);
+            out.lines()
+                .next()
+                .and_then(|line| line.split("root: ").nth(1))
+                .map(|root| root.trim_end_matches(')').to_string())
+                .unwrap()
+        };
+
+        assert_eq!(scan_root("first"), scan_root("second"));
+    });
+}
+
+#[test]
+fn test_scan_summary_only_prints_one_summary_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir_all(workspace.join("src")).unwrap();
+    fs::write(workspace.join("a.txt"), "a").unwrap();
+    fs::write(workspace.join("src/lib.rs"), "pub fn lib() {}").unwrap();
+
+    let bin = env!("CARGO_BIN_EXE_meld");
+    let output = Command::new(bin)
+        .env("XDG_STATE_HOME", temp_dir.path().join("state").as_os_str())
+        .env("XDG_DATA_HOME", temp_dir.path().join("data").as_os_str())
+        .env(
+            "XDG_CONFIG_HOME",
+            temp_dir.path().join("config").as_os_str(),
+        )
+        .env("HOME", temp_dir.path().join("home").as_os_str())
+        .arg("--workspace")
+        .arg(&workspace)
+        .arg("scan")
+        .arg("--force")
+        .arg("--stats")
+        .arg("--summary-only")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr={}", stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "stdout={}", stdout);
+    // Root, src, a.txt, src/lib.rs.
+    assert!(
+        lines[0].starts_with("command=scan nodes_scanned=4 duration_ms="),
+        "{}",
+        lines[0]
+    );
+    assert!(stderr.trim().is_empty(), "stderr={}", stderr);
+}
+
+#[test]
+fn test_summary_only_json_and_unsupported_commands() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join("a.txt"), "a").unwrap();
+        let ctx = RunContext::new(workspace_root.clone(), None)
+            .unwrap()
+            .summary_only(Some("json".to_string()));
+        let out = ctx
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: true,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        assert_eq!(out.lines().count(), 1, "{}", out);
+        let summary: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(summary["command"], "scan");
+        assert_eq!(summary["nodes_scanned"], 2);
+        assert!(summary["duration_ms"].is_u64());
+        assert!(summary.get("frames_generated").is_none());
+
+        let err = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Validate {
+                    format: "text".to_string(),
+                    repair: false,
+                    dry_run: false,
+                    allow_data_loss: false,
+                },
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("--summary-only"), "{}", err);
+    });
+}
+
+#[test]
+fn test_profile_store_path_selects_separate_store() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("config")).unwrap();
+        fs::write(workspace_root.join("a.txt"), "a").unwrap();
+        let alt_store = temp_dir.path().join("data").join("meld-alt").join("store");
+        assert!(!alt_store.starts_with(&workspace_root));
+        fs::write(
+            workspace_root.join("config").join("config.toml"),
+            format!(
+                "[profiles.alt.system.storage]\nstore_path = '{}'\n",
+                alt_store.display()
+            ),
+        )
+        .unwrap();
+        let scan = Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        };
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&scan).unwrap();
+        drop(ctx);
+        assert!(!alt_store.exists());
+
+        let ctx = RunContext::new_with_profile(
+            workspace_root.clone(),
+            None,
+            Vec::new(),
+            Some("alt".to_string()),
+        )
+        .unwrap();
+        ctx.execute(&scan).unwrap();
+        drop(ctx);
+        assert!(alt_store.is_dir());
+        assert!(fs::read_dir(&alt_store).unwrap().next().is_some());
+
+        let err = RunContext::new_with_profile(
+            workspace_root.clone(),
+            None,
+            Vec::new(),
+            Some("missing".to_string()),
+        )
+        .err()
+        .expect("unknown profile should fail");
+        let message = err.to_string();
+        assert!(
+            message.contains("Unknown config profile 'missing'"),
+            "{}",
+            message
+        );
+        assert!(message.contains("alt"), "{}", message);
+    });
+}
+
+#[test]
+fn test_profile_applies_to_config_reloads() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let write_workspace = |name: &str| {
+            let root = temp_dir.path().join(name);
+            fs::create_dir_all(root.join("config")).unwrap();
+            fs::write(root.join("a.txt"), "a").unwrap();
+            let alt_store = temp_dir.path().join("data").join(name).join("alt-store");
+            fs::write(
+                root.join("config").join("config.toml"),
+                format!(
+                    "[profiles.alt.system.storage]\nstore_path = '{}'\n",
+                    alt_store.display()
+                ),
+            )
+            .unwrap();
+            (root, alt_store)
+        };
+        let (main_root, main_store) = write_workspace("main");
+        let (other_root, other_store) = write_workspace("other");
+        let other_status = |ctx: &RunContext| {
+            let output = ctx
+                .execute(&Commands::Status {
+                    format: "json".to_string(),
+                    workspace_only: false,
+                    agents_only: false,
+                    providers_only: false,
+                    breakdown: false,
+                    include_content_hash: false,
+                    test_connectivity: false,
+                    watch: false,
+                    interval: 2,
+                    workspaces: vec![other_root.clone()],
+                })
+                .unwrap();
+            let status: serde_json::Value = serde_json::from_str(&output).unwrap();
+            status["workspaces"][0].clone()
+        };
+
+        std::env::set_var("MELD_PROFILE", "alt");
+        let ctx = RunContext::new(other_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        drop(ctx);
+        assert!(other_store.is_dir());
+
+        let ctx = RunContext::new(main_root.clone(), None).unwrap();
+        let reloaded = ctx.load_config().unwrap();
+        let (store_path, _, _) = reloaded.system.storage.resolve_paths(&main_root).unwrap();
+        assert_eq!(store_path, main_store);
+        let other = other_status(&ctx);
+        assert_eq!(other["scan_state"], "current", "{}", other);
+        assert_eq!(other["total_nodes"], 4, "{}", other);
+        drop(ctx);
+        std::env::remove_var("MELD_PROFILE");
+
+        // --profile and --config-override reach every reload, including the other workspaces
+        // opened by status.
+        let ctx = RunContext::new_with_profile(
+            main_root.clone(),
+            None,
+            vec!["system.strict_paths=false".to_string()],
+            Some("alt".to_string()),
+        )
+        .unwrap();
+        let reloaded = ctx.load_config().unwrap();
+        assert!(!reloaded.system.strict_paths);
+        let (store_path, _, _) = reloaded.system.storage.resolve_paths(&main_root).unwrap();
+        assert_eq!(store_path, main_store);
+        let other = other_status(&ctx);
+        assert_eq!(other["total_nodes"], 4, "{}", other);
+    });
+}
+
 #[test]
 fn test_scan_then_validate_passed() {
     let temp_dir = TempDir::new().unwrap();
@@ -247,11 +514,23 @@ fn test_scan_then_validate_passed() {
         fs::create_dir_all(&workspace_root).unwrap();
         fs::write(workspace_root.join("a.txt"), "a").unwrap();
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         let out = ctx
             .execute(&Commands::Workspace {
                 command: WorkspaceCommands::Validate {
                     format: "text".to_string(),
+                    repair: false,
+                    dry_run: false,
+                    allow_data_loss: false,
                 },
             })
             .unwrap();
@@ -272,6 +551,9 @@ fn test_validate_not_scanned_warning() {
             .execute(&Commands::Workspace {
                 command: WorkspaceCommands::Validate {
                     format: "text".to_string(),
+                    repair: false,
+                    dry_run: false,
+                    allow_data_loss: false,
                 },
             })
             .unwrap();
@@ -287,11 +569,23 @@ fn test_validate_format_json() {
         fs::create_dir_all(&workspace_root).unwrap();
         fs::write(workspace_root.join("c.txt"), "c").unwrap();
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         let out = ctx
             .execute(&Commands::Workspace {
                 command: WorkspaceCommands::Validate {
                     format: "json".to_string(),
+                    repair: false,
+                    dry_run: false,
+                    allow_data_loss: false,
                 },
             })
             .unwrap();
@@ -307,6 +601,382 @@ fn test_validate_format_json() {
     });
 }
 
+#[test]
+fn test_validate_flags_head_frame_with_foreign_basis() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let a_path = workspace_root.join("a.txt");
+        let b_path = workspace_root.join("b.txt");
+        fs::write(&a_path, "a").unwrap();
+        fs::write(&b_path, "b").unwrap();
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let resolve = |path: &Path| {
+            meld::workspace::resolve_workspace_node_id(
+                ctx.api(),
+                &workspace_root,
+                Some(path),
+                None,
+                false,
+            )
+            .unwrap()
+        };
+        let a_id = resolve(&a_path);
+        let b_id = resolve(&b_path);
+
+        let frame = Frame::new(
+            Basis::Node(a_id),
+            b"frame for a".to_vec(),
+            "context-test".to_string(),
+            "test-agent".to_string(),
+            HashMap::new(),
+        )
+        .unwrap();
+        ctx.api().frame_storage().store(&frame).unwrap();
+        ctx.api()
+            .head_index()
+            .write()
+            .update_head(&b_id, "context-test", &frame.frame_id)
+            .unwrap();
+
+        let out = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Validate {
+                    format: "json".to_string(),
+                    repair: false,
+                    dry_run: false,
+                    allow_data_loss: false,
+                },
+            })
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["valid"], false);
+        let errors = parsed["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        let error = errors[0].as_str().unwrap();
+        assert!(error.contains(&hex::encode(frame.frame_id)));
+        assert!(error.contains(&format!("basis node {}", hex::encode(a_id))));
+        assert!(error.contains(&format!("head of node {}", hex::encode(b_id))));
+    });
+}
+
+#[test]
+fn test_scan_rebases_heads_recorded_under_legacy_node_ids() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src")).unwrap();
+        fs::create_dir_all(workspace_root.join("empty")).unwrap();
+        fs::write(workspace_root.join("a.txt"), "a").unwrap();
+        fs::write(workspace_root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+        // Indexed under the old scheme only; the legacy root and its IDs must still include it.
+        fs::write(workspace_root.join(".env"), "KEY=value").unwrap();
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+
+        // A store written before NodeIDs hashed workspace-relative paths: heads for the root,
+        // a directory, and a file, all under their absolute-path IDs.
+        let tree = TreeBuilder::new(workspace_root.clone()).build().unwrap();
+        let patterns = ignore::load_ignore_patterns_without_git_excludes(&workspace_root).unwrap();
+        let legacy_ids =
+            meld::tree::legacy::legacy_node_ids(&tree, &workspace_root, &patterns).unwrap();
+        let node_id_at = |suffix: &str| {
+            *tree
+                .nodes
+                .iter()
+                .find(|(_, node)| match node {
+                    MerkleNode::File(file) => file.path.ends_with(suffix),
+                    MerkleNode::Directory(dir) => dir.path.ends_with(suffix),
+                    MerkleNode::Symlink(_) => false,
+                })
+                .unwrap()
+                .0
+        };
+        let current_ids = [tree.root_id, node_id_at("src"), node_id_at("a.txt")];
+        let mut legacy_frames = Vec::new();
+        for node_id in current_ids {
+            let legacy_id = legacy_ids[&node_id];
+            assert_ne!(legacy_id, node_id);
+            let frame = Frame::new(
+                Basis::Node(legacy_id),
+                format!("frame for {}", hex::encode(node_id)).into_bytes(),
+                "context-test".to_string(),
+                "test-agent".to_string(),
+                HashMap::new(),
+            )
+            .unwrap();
+            ctx.api().frame_storage().store(&frame).unwrap();
+            ctx.api()
+                .head_index()
+                .write()
+                .update_head(&legacy_id, "context-test", &frame.frame_id)
+                .unwrap();
+            legacy_frames.push(frame);
+        }
+
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        for (node_id, legacy_frame) in current_ids.iter().zip(&legacy_frames) {
+            let head = ctx
+                .api()
+                .get_head(node_id, "context-test")
+                .unwrap()
+                .unwrap_or_else(|| panic!("no head rebased onto {}", hex::encode(node_id)));
+            let frame = ctx.api().frame_storage().get(&head).unwrap().unwrap();
+            assert!(matches!(frame.basis, Basis::Node(node) if node == *node_id));
+            assert_eq!(frame.content, legacy_frame.content);
+            assert_eq!(frame.timestamp, legacy_frame.timestamp);
+        }
+        // The migration ran once and is recorded; later scans skip the legacy walk.
+        assert_eq!(
+            ctx.api()
+                .node_store()
+                .get_setting("node_id_scheme")
+                .unwrap()
+                .as_deref(),
+            Some("relative-path")
+        );
+
+        let out = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Validate {
+                    format: "json".to_string(),
+                    repair: false,
+                    dry_run: false,
+                    allow_data_loss: false,
+                },
+            })
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["errors"].as_array().unwrap().len(), 0, "{}", out);
+    });
+}
+
+#[test]
+fn test_workspace_status_check_heads_reports_dangling_and_stale() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let a_path = workspace_root.join("a.txt");
+        let b_path = workspace_root.join("b.txt");
+        let c_path = workspace_root.join("c.txt");
+        fs::write(&a_path, "a").unwrap();
+        fs::write(&b_path, "b").unwrap();
+        fs::write(&c_path, "c").unwrap();
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let resolve = |path: &Path| {
+            meld::workspace::resolve_workspace_node_id(
+                ctx.api(),
+                &workspace_root,
+                Some(path),
+                None,
+                false,
+            )
+            .unwrap()
+        };
+        let a_id = resolve(&a_path);
+        let b_id = resolve(&b_path);
+
+        // Stale: a stored frame whose node is tombstoned without touching the head index.
+        let frame = Frame::new(
+            Basis::Node(a_id),
+            b"frame for a".to_vec(),
+            "context-test".to_string(),
+            "test-agent".to_string(),
+            HashMap::new(),
+        )
+        .unwrap();
+        ctx.api().frame_storage().store(&frame).unwrap();
+        ctx.api()
+            .head_index()
+            .write()
+            .update_head(&a_id, "context-test", &frame.frame_id)
+            .unwrap();
+        ctx.api().node_store().tombstone(&a_id).unwrap();
+
+        // Kept: a node deleted with --keep-frames keeps its head on purpose.
+        let c_id = resolve(&c_path);
+        let kept = Frame::new(
+            Basis::Node(c_id),
+            b"frame for c".to_vec(),
+            "context-test".to_string(),
+            "test-agent".to_string(),
+            HashMap::new(),
+        )
+        .unwrap();
+        ctx.api().frame_storage().store(&kept).unwrap();
+        ctx.api()
+            .head_index()
+            .write()
+            .update_head(&c_id, "context-test", &kept.frame_id)
+            .unwrap();
+        ctx.api().tombstone_node_keep_frames(c_id).unwrap();
+
+        // Dangling: a head on a live node whose frame blob was never stored.
+        ctx.api()
+            .head_index()
+            .write()
+            .update_head(&b_id, "context-test", &[7u8; 32])
+            .unwrap();
+
+        let status = |format: &str, check_heads: bool| {
+            ctx.execute(&Commands::Workspace {
+                command: WorkspaceCommands::Status {
+                    format: format.to_string(),
+                    breakdown: false,
+                    include_content_hash: false,
+                    check_heads,
+                    by_agent: false,
+                    check_staleness: false,
+                    watch: false,
+                    interval: 2,
+                },
+            })
+            .unwrap()
+        };
+
+        let parsed: serde_json::Value = serde_json::from_str(&status("json", true)).unwrap();
+        let health = &parsed["head_index_health"];
+        assert_eq!(health["total_entries"], 3);
+        assert_eq!(health["dangling_entries"], 1);
+        assert_eq!(health["stale_entries"], 1);
+
+        let text = status("text", true);
+        assert!(text.contains("Head index health"));
+        assert!(text.contains("Dangling (frame missing): 1"));
+        assert!(text.contains("Stale (node tombstoned or missing): 1"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&status("json", false)).unwrap();
+        assert!(parsed.get("head_index_health").is_none());
+    });
+}
+
+#[test]
+fn test_workspace_status_by_agent_counts_nodes_per_head_frame_agent() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(workspace_root.join(name), name).unwrap();
+        }
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let resolve = |path: &Path| {
+            meld::workspace::resolve_workspace_node_id(
+                ctx.api(),
+                &workspace_root,
+                Some(path),
+                None,
+                false,
+            )
+            .unwrap()
+        };
+        let put_head = |path: &Path, frame_type: &str, agent_id: &str| {
+            let node_id = resolve(path);
+            let frame = Frame::new(
+                Basis::Node(node_id),
+                format!("{} by {}", frame_type, agent_id).into_bytes(),
+                frame_type.to_string(),
+                agent_id.to_string(),
+                HashMap::new(),
+            )
+            .unwrap();
+            ctx.api().frame_storage().store(&frame).unwrap();
+            ctx.api()
+                .head_index()
+                .write()
+                .update_head(&node_id, frame_type, &frame.frame_id)
+                .unwrap();
+        };
+
+        // agent-a heads two frame types on a.txt, which still counts as one node.
+        put_head(&workspace_root.join("a.txt"), "context-a", "agent-a");
+        put_head(&workspace_root.join("a.txt"), "summary", "agent-a");
+        put_head(&workspace_root.join("b.txt"), "context-a", "agent-a");
+        // agent-b overlaps agent-a on b.txt and alone covers c.txt and the root directory.
+        put_head(&workspace_root.join("b.txt"), "context-b", "agent-b");
+        put_head(&workspace_root.join("c.txt"), "context-b", "agent-b");
+        put_head(&workspace_root, "context-b", "agent-b");
+
+        let status = |format: &str, by_agent: bool| {
+            ctx.execute(&Commands::Workspace {
+                command: WorkspaceCommands::Status {
+                    format: format.to_string(),
+                    breakdown: false,
+                    include_content_hash: false,
+                    check_heads: false,
+                    by_agent,
+                    check_staleness: false,
+                    watch: false,
+                    interval: 2,
+                },
+            })
+            .unwrap()
+        };
+
+        let parsed: serde_json::Value = serde_json::from_str(&status("json", true)).unwrap();
+        let total_nodes = parsed["tree"]["total_nodes"].as_u64().unwrap();
+        let coverage = parsed["agent_coverage"].as_array().unwrap();
+        assert_eq!(coverage.len(), 2);
+        assert_eq!(coverage[0]["agent_id"], "agent-a");
+        assert_eq!(coverage[0]["nodes_covered"], 2);
+        assert_eq!(coverage[0]["coverage_pct"], 2 * 100 / total_nodes);
+        assert_eq!(coverage[1]["agent_id"], "agent-b");
+        assert_eq!(coverage[1]["nodes_covered"], 3);
+        assert_eq!(coverage[1]["coverage_pct"], 3 * 100 / total_nodes);
+
+        let text = status("text", true);
+        assert!(text.contains("Coverage by agent"));
+        assert!(text.contains("agent-a"));
+        assert!(text.contains("agent-b"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&status("json", false)).unwrap();
+        assert!(parsed.get("agent_coverage").is_none());
+    });
+}
+
 #[test]
 fn test_scan_without_force_already_exists() {
     let temp_dir = TempDir::new().unwrap();
@@ -315,8 +985,27 @@ fn test_scan_without_force_already_exists() {
         fs::create_dir_all(&workspace_root).unwrap();
         fs::write(workspace_root.join("d.txt"), "d").unwrap();
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
-        let out = ctx.execute(&Commands::Scan { force: false }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let out = ctx
+            .execute(&Commands::Scan {
+                force: false,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
         assert!(out.contains("already exists") && out.contains("--force"));
     });
 }
@@ -329,9 +1018,29 @@ fn test_scan_with_force_repopulates() {
         fs::create_dir_all(&workspace_root).unwrap();
         fs::write(workspace_root.join("e.txt"), "e").unwrap();
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        let out1 = ctx.execute(&Commands::Scan { force: true }).unwrap();
+        let out1 = ctx
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
         fs::write(workspace_root.join("f.txt"), "f").unwrap();
-        let out2 = ctx.execute(&Commands::Scan { force: true }).unwrap();
+        let out2 = ctx
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
         assert!(out1.contains("Scanned"));
         assert!(out2.contains("Scanned"));
         assert!(out1 != out2 || out2.contains("nodes"));
@@ -371,7 +1080,16 @@ fn test_scan_default_uses_gitignore_when_ignore_list_missing() {
         fs::create_dir_all(workspace_root.join("ignore_me")).unwrap();
         fs::write(workspace_root.join("ignore_me").join("x"), "x").unwrap();
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         let records = ctx.api().node_store().list_all().unwrap();
         let paths: Vec<String> = records
             .iter()
@@ -382,6 +1100,306 @@ fn test_scan_default_uses_gitignore_when_ignore_list_missing() {
     });
 }
 
+/// Paths in the tree recorded by a fresh scan. Records from earlier scans stay in the store,
+/// so this walks down from the new root instead of listing every record.
+fn scanned_paths(workspace_root: &Path) -> Vec<String> {
+    let ctx = RunContext::new(workspace_root.to_path_buf(), None).unwrap();
+    let out = ctx
+        .execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+    let root_hex = out
+        .split("root: ")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .unwrap_or_else(|| panic!("missing root in scan output: {}", out));
+    let root: [u8; 32] = hex::decode(root_hex).unwrap().try_into().unwrap();
+
+    let store = ctx.api().node_store();
+    let mut paths = Vec::new();
+    let mut pending = vec![root];
+    while let Some(node_id) = pending.pop() {
+        let record = store.get(&node_id).unwrap().unwrap();
+        paths.push(record.path.to_string_lossy().into_owned());
+        pending.extend(record.children.iter().copied());
+    }
+    paths
+}
+
+#[test]
+fn test_scan_honors_git_info_exclude() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join(".git").join("info")).unwrap();
+        fs::create_dir_all(workspace_root.join("local_only")).unwrap();
+        fs::write(workspace_root.join("keep.txt"), "keep").unwrap();
+        fs::write(workspace_root.join("local_only").join("x"), "x").unwrap();
+        fs::write(
+            workspace_root.join(".git").join("info").join("exclude"),
+            "# git ls-files --others --exclude-from=.git/info/exclude\nlocal_only\n",
+        )
+        .unwrap();
+
+        let paths = scanned_paths(&workspace_root);
+        assert!(paths.iter().any(|p| p.contains("keep")));
+        assert!(
+            !paths.iter().any(|p| p.contains("local_only")),
+            "{:?}",
+            paths
+        );
+    });
+}
+
+#[test]
+fn test_scan_honors_global_git_excludes_file() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join("keep.txt"), "keep").unwrap();
+        fs::write(workspace_root.join("xdg_skip"), "x").unwrap();
+        fs::write(workspace_root.join("custom_skip"), "x").unwrap();
+
+        // Without core.excludesFile, git reads $XDG_CONFIG_HOME/git/ignore.
+        fs::create_dir_all(temp_dir.path().join("git")).unwrap();
+        fs::write(temp_dir.path().join("git").join("ignore"), "xdg_skip\n").unwrap();
+        let paths = scanned_paths(&workspace_root);
+        assert!(paths.iter().any(|p| p.contains("keep")));
+        assert!(paths.iter().any(|p| p.contains("custom_skip")));
+        assert!(!paths.iter().any(|p| p.contains("xdg_skip")), "{:?}", paths);
+
+        // core.excludesFile in ~/.gitconfig replaces the default location.
+        let home = temp_dir.path().join("home");
+        fs::write(
+            home.join(".gitconfig"),
+            "[core]\n\texcludesFile = ~/.gitignore_global\n",
+        )
+        .unwrap();
+        fs::write(home.join(".gitignore_global"), "custom_skip\n").unwrap();
+        let paths = scanned_paths(&workspace_root);
+        assert!(paths.iter().any(|p| p.contains("xdg_skip")));
+        assert!(
+            !paths.iter().any(|p| p.contains("custom_skip")),
+            "{:?}",
+            paths
+        );
+    });
+}
+
+#[test]
+fn test_git_excludes_follow_the_gitignore_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_env(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join(".git").join("info")).unwrap();
+        fs::write(workspace_root.join(".gitignore"), "from_gitignore\n").unwrap();
+        fs::write(
+            workspace_root.join(".git").join("info").join("exclude"),
+            "from_exclude\n",
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.path().join("git")).unwrap();
+        fs::write(temp_dir.path().join("git").join("ignore"), "from_global\n").unwrap();
+        let list_path = ignore::ignore_list_path(&workspace_root).unwrap();
+        fs::create_dir_all(list_path.parent().unwrap()).unwrap();
+        let has = |patterns: &[String], pattern: &str| patterns.iter().any(|p| p == pattern);
+
+        // A list without the .gitignore entry or block opts out of git's excludes as well.
+        fs::write(&list_path, "custom\n").unwrap();
+        let patterns = ignore::load_ignore_patterns(&workspace_root).unwrap();
+        assert!(has(&patterns, "custom"));
+        for pattern in ["from_gitignore", "from_exclude", "from_global"] {
+            assert!(!has(&patterns, pattern), "{:?}", patterns);
+        }
+
+        fs::write(&list_path, "custom\n.gitignore\n").unwrap();
+        let patterns = ignore::load_ignore_patterns(&workspace_root).unwrap();
+        for pattern in ["custom", "from_gitignore", "from_exclude", "from_global"] {
+            assert!(has(&patterns, pattern), "{:?}", patterns);
+        }
+
+        // The synced block honors .gitignore like the entry does.
+        fs::write(
+            &list_path,
+            "# .gitignore\nfrom_gitignore\n# end .gitignore\n",
+        )
+        .unwrap();
+        let patterns = ignore::load_ignore_patterns(&workspace_root).unwrap();
+        assert!(has(&patterns, "from_exclude"), "{:?}", patterns);
+    });
+}
+
+#[test]
+fn test_scan_include_empty_dirs_records_empty_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("empty").join("nested")).unwrap();
+        fs::write(workspace_root.join("a.txt"), "a").unwrap();
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        let scan = |include_empty_dirs: bool| {
+            let out = ctx
+                .execute(&Commands::Scan {
+                    force: true,
+                    include_hidden: false,
+                    include_empty_dirs,
+                    verify_stable: false,
+                    allow_empty_workspace: false,
+                    stats: false,
+                    format: "text".to_string(),
+                })
+                .unwrap();
+            let root = out
+                .split("root: ")
+                .nth(1)
+                .and_then(|rest| rest.split(')').next())
+                .unwrap()
+                .to_string();
+            let empty_recorded = ctx
+                .api()
+                .node_store()
+                .list_active()
+                .unwrap()
+                .iter()
+                .any(|record| record.path.ends_with("empty/nested"));
+            (root, empty_recorded)
+        };
+
+        let (omitted_root, omitted_recorded) = scan(false);
+        assert!(!omitted_recorded);
+
+        // Same content, different mode: the empty directories now hash into the root.
+        let (included_root, included_recorded) = scan(true);
+        assert!(included_recorded);
+        assert_ne!(omitted_root, included_root);
+
+        let scan_info = read_workspace_scan_state(ctx.api(), &workspace_root).unwrap();
+        assert_eq!(scan_info.scan_state, WorkspaceScanState::Current);
+        assert_eq!(scan_info.current_root_hash, included_root);
+    });
+}
+
+#[test]
+fn test_scan_all_ignored_workspace_warns_unless_allowed() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join(".gitignore"), "a.log\nb.log\n").unwrap();
+        fs::write(workspace_root.join("a.log"), "a").unwrap();
+        fs::write(workspace_root.join("b.log"), "b").unwrap();
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        let scan = |allow_empty_workspace: bool| {
+            ctx.execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap()
+        };
+
+        let out = scan(false);
+        assert!(out.contains("Warning: no indexable files found"), "{}", out);
+        assert!(out.contains("--allow-empty-workspace"));
+
+        let out = scan(true);
+        assert!(!out.contains("Warning"), "{}", out);
+
+        let status = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Status {
+                    format: "json".to_string(),
+                    breakdown: false,
+                    include_content_hash: false,
+                    check_heads: false,
+                    by_agent: false,
+                    check_staleness: false,
+                    watch: false,
+                    interval: 2,
+                },
+            })
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&status).unwrap();
+        assert!(parsed["message"]
+            .as_str()
+            .unwrap()
+            .contains("no indexable files"));
+    });
+}
+
+#[test]
+fn test_scan_stats_reports_fixture_composition() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src/util")).unwrap();
+        fs::create_dir_all(workspace_root.join("docs")).unwrap();
+        let files: [(&str, &[u8]); 4] = [
+            ("src/main.rs", b"fn main() {}\n"),
+            ("src/util/helpers.rs", b"pub fn help() -> u8 { 1 }\n"),
+            ("docs/guide.md", b"# Guide\n"),
+            ("data.bin", &[0, 1, 2, 3, 0, 5]),
+        ];
+        for (path, content) in files {
+            fs::write(workspace_root.join(path), content).unwrap();
+        }
+        let total_bytes: usize = files.iter().map(|(_, content)| content.len()).sum();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        let out = ctx
+            .execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: true,
+                format: "json".to_string(),
+            })
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert!(parsed["message"].as_str().unwrap().starts_with("Scanned"));
+        let stats = &parsed["stats"];
+        assert_eq!(stats["file_count"], 4);
+        assert_eq!(stats["directory_count"], 3);
+        assert_eq!(stats["total_bytes"], total_bytes);
+        assert_eq!(stats["file_classes"]["text"], 3);
+        assert_eq!(stats["file_classes"]["binary"], 1);
+        assert_eq!(stats["largest_files"][0]["path"], "src/util/helpers.rs");
+        assert_eq!(stats["deepest_path"], "src/util/helpers.rs");
+        assert_eq!(stats["max_depth"], 3);
+
+        // Stats are still reported when the tree already exists and nothing is rebuilt.
+        let out = ctx
+            .execute(&Commands::Scan {
+                force: false,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: true,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        assert!(out.contains("Tree already exists"), "{}", out);
+        assert!(out.contains("Files: 4 (binary: 1, text: 3)"), "{}", out);
+        assert!(out.contains("Directories: 3"), "{}", out);
+        assert!(out.contains(&format!("({} bytes)", total_bytes)), "{}", out);
+    });
+}
+
 #[test]
 fn test_scan_syncs_gitignore_to_ignore_list() {
     let temp_dir = TempDir::new().unwrap();
@@ -392,7 +1410,16 @@ fn test_scan_syncs_gitignore_to_ignore_list() {
         fs::write(workspace_root.join(".gitignore"), "synced_ignore\n*.log\n").unwrap();
         fs::create_dir_all(workspace_root.join("synced_ignore")).unwrap();
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         let list_path = meld::ignore::ignore_list_path(&workspace_root).unwrap();
         let contents = fs::read_to_string(&list_path).unwrap();
         assert!(contents.contains("# .gitignore"));
@@ -408,6 +1435,65 @@ fn test_scan_syncs_gitignore_to_ignore_list() {
     });
 }
 
+#[test]
+fn test_scan_include_hidden_indexes_dotfiles_but_not_git() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join(".github")).unwrap();
+        fs::write(workspace_root.join("keep.txt"), "keep").unwrap();
+        fs::write(workspace_root.join(".env.example"), "KEY=value").unwrap();
+        fs::write(workspace_root.join(".github").join("ci.yml"), "on: push").unwrap();
+        let git_dir = workspace_root.join(".git");
+        fs::create_dir_all(&git_dir).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main").unwrap();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        let node_paths = |ctx: &RunContext| -> Vec<String> {
+            ctx.api()
+                .node_store()
+                .list_all()
+                .unwrap()
+                .iter()
+                .map(|r| r.path.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let default_paths = node_paths(&ctx);
+        assert!(default_paths.iter().any(|p| p.ends_with("keep.txt")));
+        assert!(!default_paths.iter().any(|p| p.ends_with(".env.example")));
+
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: true,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let hidden_paths = node_paths(&ctx);
+        assert!(hidden_paths.iter().any(|p| p.ends_with(".env.example")));
+        assert!(hidden_paths.iter().any(|p| p.ends_with(".github/ci.yml")));
+        assert!(!hidden_paths.iter().any(|p| p.contains("/.git/")));
+
+        // Status rebuilds the tree with the options the scan recorded, so it stays current.
+        let scan_info = read_workspace_scan_state(ctx.api(), &workspace_root).unwrap();
+        assert_eq!(scan_info.scan_state, WorkspaceScanState::Current);
+    });
+}
+
 #[test]
 fn test_scan_respects_ignore_list() {
     let temp_dir = TempDir::new().unwrap();
@@ -427,7 +1513,16 @@ fn test_scan_respects_ignore_list() {
             },
         })
         .unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
         let records = ctx.api().node_store().list_all().unwrap();
         let paths: Vec<String> = records
             .iter()
@@ -453,7 +1548,16 @@ fn test_scan_then_status_shows_scanned() {
         fs::write(workspace_root.join("ignored").join("x"), "x").unwrap();
 
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let out = ctx
             .execute(&Commands::Status {
@@ -462,7 +1566,11 @@ fn test_scan_then_status_shows_scanned() {
                 agents_only: false,
                 providers_only: false,
                 breakdown: false,
+                include_content_hash: false,
                 test_connectivity: false,
+                watch: false,
+                interval: 2,
+                workspaces: Vec::new(),
             })
             .unwrap();
         assert!(
@@ -474,6 +1582,160 @@ fn test_scan_then_status_shows_scanned() {
     });
 }
 
+#[test]
+fn test_workspace_status_include_content_hash_matches_blake3_of_file_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src")).unwrap();
+        let bytes = b"pub fn audited() {}\n";
+        fs::write(workspace_root.join("src").join("lib.rs"), bytes).unwrap();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let out = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Status {
+                    format: "json".to_string(),
+                    breakdown: false,
+                    include_content_hash: true,
+                    check_heads: false,
+                    by_agent: false,
+                    check_staleness: false,
+                    watch: false,
+                    interval: 2,
+                },
+            })
+            .unwrap();
+        let status: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let tree = &status["tree"];
+        let expected_root = hex::encode(
+            TreeBuilder::new(workspace_root.clone())
+                .compute_root()
+                .unwrap(),
+        );
+        assert_eq!(tree["root_hash"], serde_json::json!(expected_root));
+
+        let entry = tree["file_hashes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["path"] == "src/lib.rs")
+            .expect("file hash entry for src/lib.rs");
+        assert_eq!(
+            entry["content_hash"],
+            serde_json::json!(blake3::hash(bytes).to_hex().to_string())
+        );
+        assert_eq!(entry["size"], serde_json::json!(bytes.len()));
+
+        let without = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Status {
+                    format: "json".to_string(),
+                    breakdown: false,
+                    include_content_hash: false,
+                    check_heads: false,
+                    by_agent: false,
+                    check_staleness: false,
+                    watch: false,
+                    interval: 2,
+                },
+            })
+            .unwrap();
+        let without: serde_json::Value = serde_json::from_str(&without).unwrap();
+        assert!(without["tree"].get("file_hashes").is_none());
+    });
+}
+
+#[test]
+fn test_workspace_size_reports_frame_storage_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join("a.txt"), "a").unwrap();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let content_len = 4096usize;
+        let seeded = [
+            ("context-alpha", 1u8),
+            ("context-alpha", 2),
+            ("context-beta", 3),
+        ];
+        for (frame_type, seed) in seeded {
+            let frame = Frame::new(
+                Basis::Node([seed; 32]),
+                vec![seed; content_len],
+                frame_type.to_string(),
+                "size-agent".to_string(),
+                HashMap::new(),
+            )
+            .unwrap();
+            ctx.api().frame_storage().store(&frame).unwrap();
+        }
+
+        let out = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Size {
+                    by_frame_type: true,
+                    format: "json".to_string(),
+                },
+            })
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let frame_bytes = report["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["component"] == "frame_storage")
+            .unwrap()["bytes"]
+            .as_u64()
+            .unwrap();
+        // Serialized frames carry basis, type, and metadata on top of content bytes.
+        let content_total = (content_len * seeded.len()) as u64;
+        assert!(frame_bytes >= content_total);
+        assert!(frame_bytes <= content_total + 512 * seeded.len() as u64);
+
+        let by_type = report["frames_by_type"].as_array().unwrap();
+        let alpha = by_type
+            .iter()
+            .find(|t| t["frame_type"] == "context-alpha")
+            .unwrap();
+        assert_eq!(alpha["frame_count"], serde_json::json!(2));
+        let typed_total: u64 = by_type.iter().map(|t| t["bytes"].as_u64().unwrap()).sum();
+        assert_eq!(typed_total, frame_bytes);
+
+        let components_total: u64 = report["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["bytes"].as_u64().unwrap())
+            .sum();
+        assert_eq!(report["total_bytes"].as_u64().unwrap(), components_total);
+    });
+}
+
 #[test]
 fn test_status_reports_stale_when_root_record_is_missing() {
     let temp_dir = TempDir::new().unwrap();
@@ -484,7 +1746,16 @@ fn test_status_reports_stale_when_root_record_is_missing() {
         fs::write(src_dir.join("lib.rs"), "pub fn sample() {}\n").unwrap();
 
         let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
-        ctx.execute(&Commands::Scan { force: true }).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
 
         let root_hash = TreeBuilder::new(workspace_root.clone())
             .compute_root()
@@ -510,7 +1781,11 @@ fn test_status_reports_stale_when_root_record_is_missing() {
                 agents_only: false,
                 providers_only: false,
                 breakdown: false,
+                include_content_hash: false,
                 test_connectivity: false,
+                watch: false,
+                interval: 2,
+                workspaces: Vec::new(),
             })
             .unwrap();
 
@@ -523,3 +1798,171 @@ fn test_status_reports_stale_when_root_record_is_missing() {
         assert!(!out.contains("Scanned: no"));
     });
 }
+
+#[test]
+fn test_tree_renders_nested_hierarchy() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src").join("nested")).unwrap();
+        fs::write(workspace_root.join("README.md"), "readme").unwrap();
+        fs::write(workspace_root.join("src").join("lib.rs"), "lib").unwrap();
+        fs::write(
+            workspace_root.join("src").join("nested").join("deep.rs"),
+            "deep",
+        )
+        .unwrap();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: true,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+
+        let text = ctx
+            .execute(&Commands::Tree {
+                command: None,
+                path: None,
+                depth: None,
+                frames: true,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with(". [0 frames]"), "{}", text);
+        assert!(lines.contains(&"  README.md [0 frames]"), "{}", text);
+        assert!(lines.contains(&"  src/ [0 frames]"), "{}", text);
+        assert!(lines.contains(&"    lib.rs [0 frames]"), "{}", text);
+        assert!(lines.contains(&"    nested/ [0 frames]"), "{}", text);
+        assert!(lines.contains(&"      deep.rs [0 frames]"), "{}", text);
+        let src_idx = lines
+            .iter()
+            .position(|l| *l == "  src/ [0 frames]")
+            .unwrap();
+        let deep_idx = lines
+            .iter()
+            .position(|l| *l == "      deep.rs [0 frames]")
+            .unwrap();
+        assert!(src_idx < deep_idx);
+
+        let scoped = ctx
+            .execute(&Commands::Tree {
+                command: None,
+                path: Some(PathBuf::from("src")),
+                depth: Some(1),
+                frames: false,
+                format: "json".to_string(),
+            })
+            .unwrap();
+        let tree: serde_json::Value = serde_json::from_str(&scoped).unwrap();
+        assert_eq!(tree["name"], "src");
+        assert_eq!(tree["node_type"], "directory");
+        let children = tree["children"].as_array().unwrap();
+        let names: Vec<&str> = children
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["lib.rs", "nested"]);
+        assert_eq!(children[1]["truncated"], true);
+        assert!(children[1].get("children").is_none());
+        assert!(tree.get("head_frames").is_none());
+    });
+}
+
+#[test]
+fn test_tree_verify_determinism_reports_matching_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(workspace_root.join("src").join("nested")).unwrap();
+        fs::write(workspace_root.join("src").join("lib.rs"), "lib").unwrap();
+        fs::write(workspace_root.join("src").join("nested").join("a.rs"), "a").unwrap();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        let out = ctx
+            .execute(&Commands::Tree {
+                command: Some(TreeCommands::VerifyDeterminism),
+                path: None,
+                depth: None,
+                frames: false,
+                format: "text".to_string(),
+            })
+            .unwrap();
+        assert!(
+            out.contains("3 directories matched across two builds"),
+            "{}",
+            out
+        );
+
+        let cli = Cli::try_parse_from(["meld", "tree", "verify-determinism"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Tree {
+                command: Some(TreeCommands::VerifyDeterminism),
+                ..
+            }
+        ));
+    });
+}
+
+#[test]
+fn test_workspace_status_watch_refresh_reflects_store_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        fs::write(workspace_root.join("a.txt"), "first").unwrap();
+
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        let scan = || {
+            ctx.execute(&Commands::Scan {
+                force: true,
+                include_hidden: false,
+                include_empty_dirs: false,
+                verify_stable: false,
+                allow_empty_workspace: false,
+                stats: false,
+                format: "text".to_string(),
+            })
+            .unwrap()
+        };
+        scan();
+
+        let request = WorkspaceStatusRequest {
+            workspace_root: workspace_root.clone(),
+            store_path: temp_dir.path().join("store"),
+            include_breakdown: false,
+            include_content_hash: false,
+            check_heads: false,
+            by_agent: false,
+            check_staleness: false,
+        };
+        let refresh = || workspace_status_snapshot(ctx.api(), &request, "text");
+        let mut watch = StatusWatch::new(false);
+        let mut out = Vec::new();
+
+        watch.cycle(&mut out, refresh).unwrap();
+        let first_len = out.len();
+
+        fs::write(workspace_root.join("b.txt"), "second").unwrap();
+        scan();
+        watch.cycle(&mut out, refresh).unwrap();
+
+        let rendered = String::from_utf8(out).unwrap();
+        let (first, second) = rendered.split_at(first_len);
+        assert!(first.contains("[refresh 1 at "));
+        assert!(first.contains("Trend: nodes 2, nodes with frames 0, scan current"));
+        assert!(second.contains("[refresh 2 at "));
+        assert!(second.contains("Trend: nodes 3 (+1), nodes with frames 0 (+0), scan current"));
+        assert!(
+            !second.contains('\x1b'),
+            "snapshots are plain text off a terminal"
+        );
+    });
+}
@@ -0,0 +1,322 @@
+//! Integration tests for `workspace validate --repair`.
+
+use meld::agent::{AgentIdentity, AgentRole};
+use meld::api::ContextApi;
+use meld::cli::{Commands, RunContext, WorkspaceCommands};
+use meld::context::frame::{Basis, Frame};
+use meld::metadata::frame_write_contract::{
+    build_generated_metadata, generated_metadata_input_from_payload,
+};
+use meld::types::{FrameID, NodeID};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::integration::with_xdg_data_home;
+
+const AGENT_ID: &str = "writer-repair";
+const FRAME_TYPE: &str = "summary";
+const MISSING_NODE: NodeID = [7u8; 32];
+const PURGED_NODE: NodeID = [9u8; 32];
+
+struct Inconsistencies {
+    dangling_node: NodeID,
+    tombstoned_node: NodeID,
+    tombstoned_frame: FrameID,
+    orphan_frame: FrameID,
+}
+
+fn node_id(api: &ContextApi, path: &Path) -> NodeID {
+    api.node_store()
+        .find_by_path(path)
+        .unwrap()
+        .unwrap()
+        .node_id
+}
+
+fn frame_for(node_id: NodeID, content: &str) -> Frame {
+    let metadata = build_generated_metadata(&generated_metadata_input_from_payload(
+        AGENT_ID,
+        "provider-1",
+        "test-model",
+        "local",
+        "test prompt",
+        "test context",
+    ));
+    Frame::new(
+        Basis::Node(node_id),
+        content.as_bytes().to_vec(),
+        FRAME_TYPE.to_string(),
+        AGENT_ID.to_string(),
+        metadata,
+    )
+    .unwrap()
+}
+
+/// Scan a small workspace, then break it in one way per repair kind.
+fn break_workspace(ctx: &RunContext, workspace_root: &Path) -> Inconsistencies {
+    ctx.execute(&Commands::Scan {
+        force: false,
+        include_hidden: false,
+        include_empty_dirs: false,
+        verify_stable: false,
+        allow_empty_workspace: false,
+        stats: false,
+        format: "text".to_string(),
+    })
+    .unwrap();
+    let api = ctx.api();
+    api.agent_registry()
+        .write()
+        .register(AgentIdentity::new(AGENT_ID.to_string(), AgentRole::Writer));
+
+    // Dangling head: the head's frame blob is gone.
+    let dangling_node = node_id(api, &workspace_root.join("a.txt"));
+    let dangling_frame = frame_for(dangling_node, "a context");
+    let dangling_frame_id = dangling_frame.frame_id;
+    api.put_frame(dangling_node, dangling_frame, AGENT_ID.to_string())
+        .unwrap();
+    fs::remove_file(api.frame_storage().blob_path(&dangling_frame_id)).unwrap();
+
+    // Untombstoned head: the node record was tombstoned behind the head index's back.
+    let tombstoned_node = node_id(api, &workspace_root.join("b.txt"));
+    let tombstoned_frame = frame_for(tombstoned_node, "b context");
+    let tombstoned_frame_id = tombstoned_frame.frame_id;
+    api.put_frame(tombstoned_node, tombstoned_frame, AGENT_ID.to_string())
+        .unwrap();
+    api.node_store().tombstone(&tombstoned_node).unwrap();
+
+    // Orphan head entry: a head for a node the store has never seen.
+    api.head_index()
+        .write()
+        .update_head(&MISSING_NODE, FRAME_TYPE, &tombstoned_frame_id)
+        .unwrap();
+
+    // Orphan frame: a stored blob for a node the store no longer has, which no head refers to.
+    let orphan = frame_for(PURGED_NODE, "c context");
+    let orphan_frame = orphan.frame_id;
+    api.frame_storage().store(&orphan).unwrap();
+
+    Inconsistencies {
+        dangling_node,
+        tombstoned_node,
+        tombstoned_frame: tombstoned_frame_id,
+        orphan_frame,
+    }
+}
+
+fn setup(temp_dir: &TempDir) -> (RunContext, Inconsistencies) {
+    let workspace_root = temp_dir.path().join("ws");
+    fs::create_dir_all(&workspace_root).unwrap();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(workspace_root.join(name), name).unwrap();
+    }
+    let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+    let broken = break_workspace(&ctx, &workspace_root);
+    (ctx, broken)
+}
+
+fn repair(ctx: &RunContext, dry_run: bool, allow_data_loss: bool) -> serde_json::Value {
+    let out = ctx
+        .execute(&Commands::Workspace {
+            command: WorkspaceCommands::Validate {
+                format: "json".to_string(),
+                repair: true,
+                dry_run,
+                allow_data_loss,
+            },
+        })
+        .unwrap();
+    serde_json::from_str(&out).unwrap()
+}
+
+fn action_kinds(report: &serde_json::Value) -> Vec<String> {
+    report["actions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|action| action["kind"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn head_tombstoned(api: &ContextApi, node_id: &NodeID) -> Option<bool> {
+    api.head_index()
+        .read()
+        .entries_for_node(node_id)
+        .first()
+        .map(|entry| entry.tombstoned_at.is_some())
+}
+
+#[test]
+fn test_validate_repair_dry_run_reports_without_changing_anything() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let (ctx, broken) = setup(&temp_dir);
+
+        let report = repair(&ctx, true, false);
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(
+            action_kinds(&report),
+            vec![
+                "dangling_head",
+                "orphan_head_entry",
+                "untombstoned_head",
+                "orphan_frame"
+            ]
+        );
+        let actions = report["actions"].as_array().unwrap();
+        assert!(actions.iter().all(|action| action["applied"] == false));
+        assert_eq!(actions[3]["frame_id"], hex::encode(broken.orphan_frame));
+        assert_eq!(actions[3]["skipped_reason"], "requires --allow-data-loss");
+
+        let api = ctx.api();
+        assert_eq!(head_tombstoned(api, &broken.dangling_node), Some(false));
+        assert_eq!(head_tombstoned(api, &MISSING_NODE), Some(false));
+        assert_eq!(head_tombstoned(api, &broken.tombstoned_node), Some(false));
+        assert!(api.frame_storage().exists(&broken.orphan_frame).unwrap());
+        assert_eq!(action_kinds(&repair(&ctx, true, false)).len(), 4);
+    });
+}
+
+#[test]
+fn test_validate_repair_fixes_heads_and_keeps_orphan_frames_without_data_loss_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let (ctx, broken) = setup(&temp_dir);
+
+        let report = repair(&ctx, false, false);
+        let actions = report["actions"].as_array().unwrap();
+        assert_eq!(actions.len(), 4);
+        assert!(actions[..3].iter().all(|action| action["applied"] == true));
+        assert_eq!(actions[3]["kind"], "orphan_frame");
+        assert_eq!(actions[3]["applied"], false);
+
+        let api = ctx.api();
+        assert_eq!(head_tombstoned(api, &broken.dangling_node), None);
+        assert_eq!(head_tombstoned(api, &MISSING_NODE), None);
+        assert_eq!(head_tombstoned(api, &broken.tombstoned_node), Some(true));
+        assert!(api
+            .frame_storage()
+            .exists(&broken.tombstoned_frame)
+            .unwrap());
+        assert!(api.frame_storage().exists(&broken.orphan_frame).unwrap());
+
+        assert_eq!(
+            action_kinds(&repair(&ctx, true, false)),
+            vec!["orphan_frame"]
+        );
+    });
+}
+
+#[test]
+fn test_validate_repair_with_allow_data_loss_deletes_orphan_frames() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let (ctx, broken) = setup(&temp_dir);
+
+        let report = repair(&ctx, false, true);
+        let actions = report["actions"].as_array().unwrap();
+        assert_eq!(actions.len(), 4);
+        assert!(actions.iter().all(|action| action["applied"] == true));
+
+        let api = ctx.api();
+        assert!(!api.frame_storage().exists(&broken.orphan_frame).unwrap());
+        assert!(api
+            .frame_storage()
+            .exists(&broken.tombstoned_frame)
+            .unwrap());
+
+        let out = ctx
+            .execute(&Commands::Workspace {
+                command: WorkspaceCommands::Validate {
+                    format: "text".to_string(),
+                    repair: true,
+                    dry_run: true,
+                    allow_data_loss: false,
+                },
+            })
+            .unwrap();
+        assert!(out.contains("Repair: no inconsistencies found"), "{}", out);
+    });
+}
+
+#[test]
+fn test_validate_repair_keeps_history_snapshots_and_basis_chains() {
+    let temp_dir = TempDir::new().unwrap();
+    with_xdg_data_home(&temp_dir, || {
+        let workspace_root = temp_dir.path().join("ws");
+        fs::create_dir_all(&workspace_root).unwrap();
+        for name in ["a.txt", "b.txt"] {
+            fs::write(workspace_root.join(name), name).unwrap();
+        }
+        let ctx = RunContext::new(workspace_root.clone(), None).unwrap();
+        ctx.execute(&Commands::Scan {
+            force: false,
+            include_hidden: false,
+            include_empty_dirs: false,
+            verify_stable: false,
+            allow_empty_workspace: false,
+            stats: false,
+            format: "text".to_string(),
+        })
+        .unwrap();
+        let api = ctx.api();
+        api.agent_registry()
+            .write()
+            .register(AgentIdentity::new(AGENT_ID.to_string(), AgentRole::Writer));
+        let mut kept = Vec::new();
+
+        // Generated, then regenerated twice: the two superseded frames are history.
+        let a = node_id(api, &workspace_root.join("a.txt"));
+        for content in ["a v1", "a v2", "a v3"] {
+            let frame = frame_for(a, content);
+            kept.push(frame.frame_id);
+            api.put_frame(a, frame, AGENT_ID.to_string()).unwrap();
+        }
+
+        // A head that only a snapshot still records, for a node since purged.
+        let snapshotted = frame_for(PURGED_NODE, "purged context");
+        kept.push(snapshotted.frame_id);
+        api.frame_storage().store(&snapshotted).unwrap();
+        api.head_index()
+            .write()
+            .update_head(&PURGED_NODE, FRAME_TYPE, &snapshotted.frame_id)
+            .unwrap();
+        api.snapshot_heads("before-purge", false).unwrap();
+        let purged_entries = api.head_index().read().entries_for_node(&PURGED_NODE);
+        api.remove_head_entries(&purged_entries).unwrap();
+
+        // A two-hop basis chain ending at a frame of a live node.
+        let first = frame_for(MISSING_NODE, "chain start");
+        let second = Frame::new(
+            Basis::Frame(first.frame_id),
+            b"chain middle".to_vec(),
+            FRAME_TYPE.to_string(),
+            AGENT_ID.to_string(),
+            first.metadata.clone(),
+        )
+        .unwrap();
+        let b = node_id(api, &workspace_root.join("b.txt"));
+        let last = Frame::new(
+            Basis::Both {
+                node: b,
+                frame: second.frame_id,
+            },
+            b"chain end".to_vec(),
+            FRAME_TYPE.to_string(),
+            AGENT_ID.to_string(),
+            first.metadata.clone(),
+        )
+        .unwrap();
+        for frame in [&first, &second, &last] {
+            kept.push(frame.frame_id);
+            api.frame_storage().store(frame).unwrap();
+        }
+
+        let report = repair(&ctx, false, true);
+        assert!(action_kinds(&report).is_empty(), "{}", report);
+        for frame_id in kept {
+            assert!(api.frame_storage().exists(&frame_id).unwrap());
+        }
+    });
+}
@@ -20,7 +20,7 @@ fn create_test_api(
     std::fs::create_dir_all(&artifact_storage_path).unwrap();
 
     let db = sled::open(&store_path).unwrap();
-    let node_store = Arc::new(SledNodeRecordStore::from_db(db.clone()));
+    let node_store = Arc::new(SledNodeRecordStore::from_db(db.clone()).unwrap());
     let frame_storage = Arc::new(meld::context::frame::open_storage(&frame_storage_path).unwrap());
     let prompt_context_storage =
         Arc::new(PromptContextArtifactStorage::new(&artifact_storage_path).unwrap());
@@ -59,6 +59,8 @@ fn brand_new_scan_emits_source_and_snapshot() {
         &api,
         workspace_root.path(),
         false,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
@@ -86,6 +88,8 @@ fn repeated_scan_reuses_source_identity() {
         &api,
         workspace_root.path(),
         true,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
@@ -94,6 +98,8 @@ fn repeated_scan_reuses_source_identity() {
         &api,
         workspace_root.path(),
         true,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
@@ -125,6 +131,8 @@ fn snapshot_selected_changes_only_when_root_hash_changes() {
         &api,
         workspace_root.path(),
         true,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
@@ -141,6 +149,8 @@ fn snapshot_selected_changes_only_when_root_hash_changes() {
         &api,
         workspace_root.path(),
         true,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
@@ -159,6 +169,8 @@ fn snapshot_selected_changes_only_when_root_hash_changes() {
         &api,
         workspace_root.path(),
         true,
+        false,
+        false,
         Some(&progress),
         Some(&session_id),
     )
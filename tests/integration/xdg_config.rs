@@ -1,5 +1,6 @@
 //! Integration tests for XDG configuration loading
 
+use meld::agent::commands::AgentCommandService;
 use meld::agent::resolve_prompt_path;
 use meld::agent::{AgentRegistry, AgentRole, AgentStorage, PromptCache, XdgAgentStorage};
 use meld::config::{xdg, MerkleConfig, ProviderConfig, ProviderType};
@@ -452,12 +453,12 @@ role = "Reader"
 }
 
 #[test]
-fn test_agent_registry_load_from_xdg_writer_missing_prompt_retained_for_validation() {
+fn test_agent_registry_load_from_xdg_writer_missing_prompt_skips_agent() {
     let test_dir = TempDir::new().unwrap();
     with_xdg_env(&test_dir, || {
         let agents_dir = XdgAgentStorage::new().agents_dir().unwrap();
 
-        // Create a Writer agent without prompt (should be skipped)
+        // A Writer agent needs system_prompt or system_prompt_path
         let agent_file = agents_dir.join("writer.toml");
         fs::write(
             &agent_file,
@@ -468,18 +469,69 @@ role = "Writer"
         )
         .unwrap();
 
+        fs::write(
+            agents_dir.join("reader.toml"),
+            r#"
+agent_id = "reader"
+role = "Reader"
+"#,
+        )
+        .unwrap();
+
         let mut registry = AgentRegistry::new();
         registry.load_from_xdg().unwrap();
+        assert!(registry.get("writer").is_none());
+        assert!(registry.get("reader").is_some());
 
-        // Writer agent without prompt should remain loaded and fail validation
-        assert!(registry.get("writer").is_some());
+        let err = registry.get_or_error("writer").unwrap_err().to_string();
+        assert!(err.contains("writer.toml"), "{}", err);
+        assert!(err.contains("system_prompt_path:"), "{}", err);
 
         let validation = registry.validate_agent("writer").unwrap();
-        assert!(!validation.is_valid());
         assert!(validation
             .errors
             .iter()
-            .any(|e| e.contains("Missing system_prompt_path")));
+            .any(|e| e.contains("system_prompt_path:")));
+    });
+}
+
+#[test]
+fn test_agent_registry_load_from_xdg_unknown_field_skips_agent() {
+    let test_dir = TempDir::new().unwrap();
+    with_xdg_env(&test_dir, || {
+        let agents_dir = XdgAgentStorage::new().agents_dir().unwrap();
+
+        let agent_file = agents_dir.join("reader.toml");
+        fs::write(
+            &agent_file,
+            r#"
+agent_id = "reader"
+rol = "Reader"
+"#,
+        )
+        .unwrap();
+
+        let mut registry = AgentRegistry::new();
+        registry.load_from_xdg().unwrap();
+        assert!(registry.get("reader").is_none());
+
+        let err = registry.get_or_error("reader").unwrap_err().to_string();
+        assert!(err.contains("unknown field `rol`"), "{}", err);
+        assert!(registry
+            .get_or_error("missing")
+            .unwrap_err()
+            .to_string()
+            .contains("Agent not found"));
+
+        let validation = registry.validate_agent("reader").unwrap();
+        assert!(validation
+            .errors
+            .iter()
+            .any(|e| e.contains("unknown field `rol`")));
+
+        let result = AgentCommandService::remove(&mut registry, "reader").unwrap();
+        assert_eq!(result.agent_id, "reader");
+        assert!(!agent_file.exists());
     });
 }
 